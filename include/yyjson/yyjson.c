@@ -5112,6 +5112,20 @@ read_double:
 
 #endif /* FP_READER */
 
+/** Read a JSON number, as a raw string if `flg` has
+    `YYJSON_READ_NUMBER_AS_RAW`. The raw string is not null-terminated, as
+    the reader continues from the character after it. */
+static_inline bool read_number_flg(u8 **ptr,
+                                   yyjson_read_flag flg,
+                                   yyjson_val *val,
+                                   const char **msg) {
+    if (unlikely(flg & YYJSON_READ_NUMBER_AS_RAW)) {
+        u8 *pre = NULL;
+        return read_number_raw(ptr, &pre, flg, val, msg);
+    }
+    return read_number(ptr, val, msg);
+}
+
 
 
 /*==============================================================================
@@ -5623,6 +5637,7 @@ static_noinline yyjson_doc *read_root_single(u8 *hdr,
                                              u8 *cur,
                                              u8 *end,
                                              yyjson_alc alc,
+                                             yyjson_read_flag flg,
                                              yyjson_read_err *err) {
     
 #define return_err(_pos, _code, _msg) do { \
@@ -5655,7 +5670,7 @@ static_noinline yyjson_doc *read_root_single(u8 *hdr,
     val = val_hdr + hdr_len;
     
     if (char_is_number(*cur)) {
-        if (likely(read_number(&cur, val, &msg))) goto doc_end;
+        if (likely(read_number_flg(&cur, flg, val, &msg))) goto doc_end;
         goto fail_number;
     }
     if (*cur == '"') {
@@ -5725,6 +5740,7 @@ static_inline yyjson_doc *read_root_minify(u8 *hdr,
                                            u8 *cur,
                                            u8 *end,
                                            yyjson_alc alc,
+                                           yyjson_read_flag flg,
                                            yyjson_read_err *err) {
     
 #define return_err(_pos, _code, _msg) do { \
@@ -5831,7 +5847,7 @@ arr_val_begin:
     if (char_is_number(*cur)) {
         val_incr();
         ctn_len++;
-        if (likely(read_number(&cur, val, &msg))) goto arr_val_end;
+        if (likely(read_number_flg(&cur, flg, val, &msg))) goto arr_val_end;
         goto fail_number;
     }
     if (*cur == '"') {
@@ -5973,7 +5989,7 @@ obj_val_begin:
     if (char_is_number(*cur)) {
         val++;
         ctn_len++;
-        if (likely(read_number(&cur, val, &msg))) goto obj_val_end;
+        if (likely(read_number_flg(&cur, flg, val, &msg))) goto obj_val_end;
         goto fail_number;
     }
     if (*cur == '{') {
@@ -6092,6 +6108,7 @@ static_inline yyjson_doc *read_root_pretty(u8 *hdr,
                                            u8 *cur,
                                            u8 *end,
                                            yyjson_alc alc,
+                                           yyjson_read_flag flg,
                                            yyjson_read_err *err) {
     
 #define return_err(_pos, _code, _msg) do { \
@@ -6211,7 +6228,7 @@ arr_val_begin:
     if (char_is_number(*cur)) {
         val_incr();
         ctn_len++;
-        if (likely(read_number(&cur, val, &msg))) goto arr_val_end;
+        if (likely(read_number_flg(&cur, flg, val, &msg))) goto arr_val_end;
         goto fail_number;
     }
     if (*cur == '"') {
@@ -6373,7 +6390,7 @@ obj_val_begin:
     if (char_is_number(*cur)) {
         val++;
         ctn_len++;
-        if (likely(read_number(&cur, val, &msg))) goto obj_val_end;
+        if (likely(read_number_flg(&cur, flg, val, &msg))) goto obj_val_end;
         goto fail_number;
     }
     if (*cur == '{') {
@@ -6496,6 +6513,7 @@ fail_recursion:
 
 yyjson_doc *yyjson_read_opts(char *dat,
                              usize len,
+                             yyjson_read_flag flg,
                              const yyjson_alc *alc_ptr,
                              yyjson_read_err *err) {
     
@@ -6535,12 +6553,12 @@ yyjson_doc *yyjson_read_opts(char *dat,
     /* read json document */
     if (likely(char_is_container(*cur))) {
         if (char_is_space(cur[1]) && char_is_space(cur[2])) {
-            doc = read_root_pretty(hdr, cur, end, alc, err);
+            doc = read_root_pretty(hdr, cur, end, alc, flg, err);
         } else {
-            doc = read_root_minify(hdr, cur, end, alc, err);
+            doc = read_root_minify(hdr, cur, end, alc, flg, err);
         }
     } else {
-        doc = read_root_single(hdr, cur, end, alc, err);
+        doc = read_root_single(hdr, cur, end, alc, flg, err);
     }
     
     /* check result */
@@ -6661,7 +6679,7 @@ yyjson_doc *yyjson_read_fp(FILE *file,
     /* read JSON */
     memset((u8 *)buf + file_size, 0, YYJSON_PADDING_SIZE);
     flg |= YYJSON_READ_INSITU;
-    doc = yyjson_read_opts((char *)buf, (usize)file_size, &alc, err);
+    doc = yyjson_read_opts((char *)buf, (usize)file_size, flg, &alc, err);
     if (doc) {
         doc->str_pool = (char *)buf;
         return doc;
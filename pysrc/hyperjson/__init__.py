@@ -2,27 +2,85 @@
 # Copyright ijl (2023)
 
 from .hyperjson import *
-from .hyperjson import __version__
+from .hyperjson import __version__, _reset_for_testing
+
+from ._corpus import _run_corpus
+from ._patch import patch_json, unpatch
 
 __all__ = (
     "__version__",
+    "Builder",
+    "collect_stats",
+    "compare_digest_json",
     "dumps",
+    "dumps_batch",
+    "dumps_length",
+    "dumps_msgpack",
+    "edit",
+    "Editor",
+    "equals",
+    "flatten",
     "Fragment",
+    "hash",
     "JSONDecodeError",
     "JSONEncodeError",
+    "join_array",
+    "join_object",
+    "LinesWriter",
     "loads",
+    "loads_msgpack",
+    "loads_numpy",
+    "loads_tracked",
+    "merge3",
+    "merge_patch",
+    "minify",
+    "OPT_APPEND_CRLF",
     "OPT_APPEND_NEWLINE",
+    "OPT_BIG_INTEGER",
+    "OPT_BYTES_KEYS_BASE64",
+    "OPT_BYTES_KEYS_LATIN1",
+    "OPT_COMPAT_V1",
+    "OPT_DEFAULT_PATH",
+    "OPT_ENSURE_ASCII",
     "OPT_INDENT_2",
+    "OPT_INDENT_4",
+    "OPT_INDENT_8",
+    "OPT_INDENT_TAB",
     "OPT_NAIVE_UTC",
+    "OPT_NAMEDTUPLE_AS_OBJECT",
     "OPT_NON_STR_KEYS",
+    "OPT_NULL_UNSUPPORTED",
+    "OPT_OMIT_EMPTY",
+    "OPT_OMIT_EMPTY_DICT",
+    "OPT_OMIT_EMPTY_LIST",
+    "OPT_OMIT_EMPTY_STR",
     "OPT_OMIT_MICROSECONDS",
+    "OPT_OMIT_NULL",
+    "OPT_OMIT_NULL_ON_LOAD",
     "OPT_PASSTHROUGH_DATACLASS",
     "OPT_PASSTHROUGH_DATETIME",
     "OPT_PASSTHROUGH_SUBCLASS",
     "OPT_SERIALIZE_DATACLASS",
     "OPT_SERIALIZE_NUMPY",
     "OPT_SERIALIZE_UUID",
+    "OPT_SKIP_UNSUPPORTED",
     "OPT_SORT_KEYS",
     "OPT_STRICT_INTEGER",
     "OPT_UTC_Z",
+    "patch_json",
+    "pointer",
+    "prettify",
+    "RawNumber",
+    "register_deserializer",
+    "register_serializer",
+    "register_tagged",
+    "register_type",
+    "Schema",
+    "set_compat_mode",
+    "splice",
+    "stats",
+    "unflatten",
+    "unpatch",
+    "validate",
+    "verify_and_loads",
 )
@@ -2,34 +2,276 @@
 # Copyright ijl (2019-2025), Eric Jolibois (2022), Anders Kaseorg (2020)
 
 import json
-from typing import Any, Callable
+import os
+from typing import Any, Callable, Iterable, Literal, Protocol, TypeVar, overload
+
+_T = TypeVar("_T")
 
 __version__: str
 
+def compare_digest_json(__a: Any, __b: Any) -> bool: ...
 def dumps(
     __obj: Any,
-    default: Callable[[Any], Any] | None = ...,
+    default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+    option: int | None = ...,
+    *,
+    exc_class: type[BaseException] | None = ...,
+    length: int | None = ...,
+    float_decimals: int | None = ...,
+    float_format: str | Callable[[float], str] | None = ...,
+    key_order: str | Callable[[str, str], int] | None = ...,
+    non_finite: Literal["null", "literal", "string", "raise"] | None = ...,
+    width: int | None = ...,
+    datetime64_range: Literal["raise", "null", "clamp", "expanded"] | None = ...,
+    field_order: Literal["insertion", "declaration", "sorted"] | None = ...,
+    pandas_orient: Literal["records", "columns"] | None = ...,
+    serialize_arrow: bool = ...,
+    json_schema: dict[str, Any] | bool | None = ...,
+    tagged: bool = ...,
+    extended_json: Literal["relaxed", "canonical"] | None = ...,
+    sort_keys: bool = ...,
+    ensure_ascii: bool = ...,
+    indent: int | str | None = ...,
+    separators: tuple[str, str] | None = ...,
+    cls: type[json.JSONEncoder] | None = ...,
+) -> bytes: ...
+def dumps_batch(
+    __records: Iterable[Any],
+    default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+    option: int | None = ...,
+    *,
+    float_decimals: int | None = ...,
+    float_format: str | Callable[[float], str] | None = ...,
+    key_order: str | Callable[[str, str], int] | None = ...,
+    non_finite: Literal["null", "literal", "string", "raise"] | None = ...,
+    field_order: Literal["insertion", "declaration", "sorted"] | None = ...,
+    pandas_orient: Literal["records", "columns"] | None = ...,
+    serialize_arrow: bool = ...,
+    tagged: bool = ...,
+    extended_json: Literal["relaxed", "canonical"] | None = ...,
+) -> tuple[bytes, list[int]]: ...
+def dumps_length(
+    __obj: Any,
+    default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+    option: int | None = ...,
+    *,
+    float_decimals: int | None = ...,
+    float_format: str | Callable[[float], str] | None = ...,
+    key_order: str | Callable[[str, str], int] | None = ...,
+    non_finite: Literal["null", "literal", "string", "raise"] | None = ...,
+    width: int | None = ...,
+    datetime64_range: Literal["raise", "null", "clamp", "expanded"] | None = ...,
+    field_order: Literal["insertion", "declaration", "sorted"] | None = ...,
+    pandas_orient: Literal["records", "columns"] | None = ...,
+    serialize_arrow: bool = ...,
+    tagged: bool = ...,
+    extended_json: Literal["relaxed", "canonical"] | None = ...,
+) -> int: ...
+def dumps_msgpack(
+    __obj: Any,
+    default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+    option: int | None = ...,
+) -> bytes: ...
+@overload
+def loads(
+    __obj: bytes
+    | bytearray
+    | memoryview
+    | str
+    | list[bytes | bytearray | memoryview]
+    | tuple[bytes | bytearray | memoryview, ...],
+    *,
+    type: type[_T],
+    strict_types: bool = ...,
+    option: int | None = ...,
+    rename: dict[str, str] | None = ...,
+    tagged: bool = ...,
+    extended_json: bool = ...,
+    encoding: str | None = ...,
+    errors: Literal["strict", "replace"] = ...,
+) -> _T: ...
+@overload
+def loads(
+    __obj: bytes
+    | bytearray
+    | memoryview
+    | str
+    | list[bytes | bytearray | memoryview]
+    | tuple[bytes | bytearray | memoryview, ...],
+    *,
     option: int | None = ...,
+    rename: dict[str, str] | None = ...,
+    allow_nan: bool = ...,
+    json5: bool = ...,
+    allow_comments: bool = ...,
+    allow_trailing_commas: bool = ...,
+    duplicate_keys: Literal["last", "first", "error"] = ...,
+    strict: bool = ...,
+    object_pairs_hook: Callable[[list[tuple[str, Any]]], Any] | None = ...,
+    object_hook: Callable[[dict[str, Any]], Any] | None = ...,
+    parse_float: Callable[[str], Any] | None = ...,
+    parse_int: Callable[[str], Any] | None = ...,
+    use_decimal: bool = ...,
+    raw_numbers: bool = ...,
+    int_overflow: Literal["float", "string", "error"] = ...,
+    exact_floats: bool = ...,
+    type: Any = ...,
+    strict_types: bool = ...,
+    tagged: bool = ...,
+    extended_json: bool = ...,
+    cls: type[json.JSONDecoder] | None = ...,
+    encoding: str | None = ...,
+    errors: Literal["strict", "replace"] = ...,
+) -> Any: ...
+def loads_msgpack(__data: bytes | bytearray | memoryview) -> Any: ...
+def loads_tracked(__obj: bytes | bytearray | memoryview | str) -> Any: ...
+def loads_numpy(
+    __data: bytes | bytearray | memoryview | str,
+    dtype: Any = ...,
+) -> Any: ...
+def verify_and_loads(
+    __body: bytes | bytearray | memoryview | str,
+    __signature: bytes | bytearray | memoryview | str,
+    __key: bytes | bytearray | memoryview | str,
+    algo: str = ...,
+) -> Any: ...
+def edit(__data: bytes | bytearray | memoryview | str) -> Editor: ...
+def pointer(__data: bytes | bytearray | memoryview | str, __pointer: str) -> Any: ...
+def splice(
+    __data: bytes | bytearray | memoryview | str,
+    __pointer: str,
+    __value: Any,
+    option: int | None = ...,
+    default: Callable[[Any], Any] | None = ...,
+) -> bytes: ...
+def hash(__obj: Any, algorithm: str = ...) -> bytes: ...
+def validate(__data: bytes | bytearray | memoryview | str) -> None: ...
+def minify(__data: bytes | bytearray | memoryview | str) -> bytes: ...
+def prettify(
+    __data: bytes | bytearray | memoryview | str,
+    indent: int = ...,
 ) -> bytes: ...
-def loads(__obj: bytes | bytearray | memoryview | str) -> Any: ...
+def equals(__a: Any, __b: Any) -> bool: ...
+def flatten(__obj: Any, sep: str = ...) -> dict[str, Any]: ...
+def unflatten(__mapping: Any, sep: str = ...) -> dict[str, Any]: ...
+def join_array(
+    __iterable: Iterable[Fragment | bytes | str],
+    validate: bool = ...,
+) -> bytes: ...
+def join_object(
+    __pairs: Iterable[tuple[str, Fragment | bytes | str]],
+    validate: bool = ...,
+) -> bytes: ...
+def merge3(
+    __base: Any,
+    __ours: Any,
+    __theirs: Any,
+) -> tuple[Any, list[dict[str, Any]]]: ...
+def merge_patch(__target: Any, __patch: Any) -> Any: ...
+def register_deserializer(
+    __type: type,
+    __callable: Callable[[Any], Any] | None,
+) -> None: ...
+def register_serializer(
+    __type: type,
+    __callable: Callable[[Any], Any] | None,
+) -> None: ...
+def register_tagged(__type: type, __tag: str | None = ...) -> None: ...
+def register_type(__type: type) -> None: ...
+def stats(*, reset: bool = ...) -> dict[str, Any]: ...
+def collect_stats(__enabled: bool) -> None: ...
+def set_compat_mode(__enabled: bool) -> None: ...
+def patch_json() -> None: ...
+def unpatch() -> None: ...
+def _reset_for_testing() -> None: ...
+def _run_corpus(
+    path: str | os.PathLike[str],
+    repro_dir: str | os.PathLike[str] | None = ...,
+) -> list[dict[str, Any]]: ...
 
-class JSONDecodeError(json.JSONDecodeError): ...
+class JSONDecodeError(json.JSONDecodeError):
+    code: str | None
 class JSONEncodeError(TypeError): ...
 
+class Builder:
+    def __init__(
+        self,
+        default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+        option: int | None = ...,
+    ) -> None: ...
+    def start_object(self) -> None: ...
+    def end_object(self) -> None: ...
+    def start_array(self) -> None: ...
+    def end_array(self) -> None: ...
+    def key(self, __key: str) -> None: ...
+    def value(self, __obj: Any) -> None: ...
+    def finish(self) -> bytes: ...
+
+class _SupportsWriteBytes(Protocol):
+    def write(self, __data: bytes) -> object: ...
+
+class LinesWriter:
+    def __init__(
+        self,
+        __target: str | os.PathLike[str] | _SupportsWriteBytes,
+        *,
+        max_bytes: int | None = ...,
+        on_rotate: Callable[[Any], Any] | None = ...,
+        buffer_size: int = ...,
+        default: Callable[[Any], Any] | dict[type, Callable[[Any], Any]] | None = ...,
+        option: int | None = ...,
+    ) -> None: ...
+    def write(self, __obj: Any) -> None: ...
+    def flush(self) -> None: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> LinesWriter: ...
+    def __exit__(self, *args: object) -> None: ...
+
+class Editor:
+    def set(self, __pointer: str, __value: Any) -> Editor: ...
+    def delete(self, __pointer: str) -> Editor: ...
+    def bytes(self) -> bytes: ...
+
 class Fragment(tuple):
     contents: bytes | str
 
+class RawNumber(str): ...
+
+class Schema:
+    def __init__(self, __typ: Any) -> None: ...
+    def loads(self, __obj: bytes | bytearray | memoryview | str) -> Any: ...
+    def dumps(self, __obj: Any) -> bytes: ...
+
+OPT_APPEND_CRLF: int
 OPT_APPEND_NEWLINE: int
+OPT_BIG_INTEGER: int
+OPT_BYTES_KEYS_BASE64: int
+OPT_BYTES_KEYS_LATIN1: int
+OPT_COMPAT_V1: int
+OPT_DEFAULT_PATH: int
+OPT_ENSURE_ASCII: int
 OPT_INDENT_2: int
+OPT_INDENT_4: int
+OPT_INDENT_8: int
+OPT_INDENT_TAB: int
 OPT_NAIVE_UTC: int
+OPT_NAMEDTUPLE_AS_OBJECT: int
 OPT_NON_STR_KEYS: int
+OPT_NULL_UNSUPPORTED: int
+OPT_OMIT_EMPTY: int
+OPT_OMIT_EMPTY_DICT: int
+OPT_OMIT_EMPTY_LIST: int
+OPT_OMIT_EMPTY_STR: int
 OPT_OMIT_MICROSECONDS: int
+OPT_OMIT_NULL: int
+OPT_OMIT_NULL_ON_LOAD: int
 OPT_PASSTHROUGH_DATACLASS: int
 OPT_PASSTHROUGH_DATETIME: int
 OPT_PASSTHROUGH_SUBCLASS: int
 OPT_SERIALIZE_DATACLASS: int
 OPT_SERIALIZE_NUMPY: int
 OPT_SERIALIZE_UUID: int
+OPT_SKIP_UNSUPPORTED: int
 OPT_SORT_KEYS: int
 OPT_STRICT_INTEGER: int
 OPT_UTC_Z: int
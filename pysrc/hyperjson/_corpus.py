@@ -0,0 +1,124 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+import lzma
+import os
+from pathlib import Path
+from typing import Any, Optional, Union
+
+from .hyperjson import JSONDecodeError, JSONEncodeError, dumps, loads
+
+
+def _check_loads(name: str, data: Union[bytes, str], value: Any) -> Optional[str]:
+    if name.startswith("n_"):
+        return "loads() accepted an invalid document"
+    if name.startswith("y_"):
+        try:
+            expected = json.loads(data)
+        except ValueError:
+            return None
+        if value != expected:
+            return f"loads() returned {value!r}, json {expected!r}"
+    return None
+
+
+def _check_roundtrip(value: Any) -> Optional[tuple[str, str]]:
+    try:
+        encoded = dumps(value)
+    except JSONEncodeError:
+        # e.g., nesting deeper than dumps() allows
+        return None
+    except Exception as exc:
+        return ("crash", f"dumps() raised {type(exc).__name__}: {exc}")
+    try:
+        decoded = loads(encoded)
+    except Exception as exc:
+        return ("roundtrip", f"loads() of dumps() raised {type(exc).__name__}: {exc}")
+    if decoded != value:
+        return ("roundtrip", f"loads() of dumps() returned {decoded!r}")
+    return None
+
+
+def _check(name: str, data: Union[bytes, str]) -> Optional[tuple[str, str]]:
+    try:
+        value = loads(data)
+    except JSONDecodeError:
+        if name.startswith("y_"):
+            return ("mismatch", "loads() rejected a valid document")
+        return None
+    except Exception as exc:
+        return ("crash", f"loads() raised {type(exc).__name__}: {exc}")
+    mismatch = _check_loads(name, data, value)
+    if mismatch is not None:
+        return ("mismatch", mismatch)
+    return _check_roundtrip(value)
+
+
+def _run_corpus(
+    path: Union[str, "os.PathLike[str]"],
+    repro_dir: Union[str, "os.PathLike[str]", None] = None,
+) -> list[dict[str, Any]]:
+    """
+    Decode each file under path with loads(), as bytes and, if it is UTF-8,
+    as str, and encode the result with dumps(). Files ending in .xz are
+    decompressed first.
+
+    Return a dict for each input that failed, in the order of the files, with
+    "file", "input" ("bytes" or "str"), "kind", "detail", and "repro":
+
+    - "crash": loads() raised other than JSONDecodeError or dumps() other
+      than JSONEncodeError.
+    - "mismatch": a file named y_* was rejected or read other than json.loads()
+      reads it, or a file named n_* was accepted, as in JSONTestSuite.
+    - "roundtrip": loads() of the output of dumps() differs.
+
+    With repro_dir, the contents of each failed file are written there with a
+    .txt file describing the failures, and "repro" is the path written.
+    A crash of the interpreter ends the run.
+    """
+    root = Path(path)
+    if not root.is_dir():
+        raise NotADirectoryError(f"_run_corpus() path is not a directory: {root}")
+    failures: list[dict[str, Any]] = []
+    for file in sorted(each for each in root.rglob("*") if each.is_file()):
+        contents = file.read_bytes()
+        name = file.name
+        if file.suffix == ".xz":
+            contents = lzma.decompress(contents)
+            name = file.stem
+        inputs: list[tuple[str, Union[bytes, str]]] = [("bytes", contents)]
+        try:
+            inputs.append(("str", contents.decode("utf-8")))
+        except UnicodeDecodeError:
+            pass
+        found = []
+        for kind, data in inputs:
+            result = _check(name, data)
+            if result is not None:
+                found.append((kind, *result))
+        if not found:
+            continue
+        repro = None
+        if repro_dir is not None:
+            os.makedirs(repro_dir, exist_ok=True)
+            repro = os.path.join(
+                repro_dir,
+                str(file.relative_to(root).with_name(name)).replace(os.sep, "_"),
+            )
+            with open(repro, "wb") as fh:
+                fh.write(contents)
+            with open(f"{repro}.txt", "w", encoding="utf-8") as fh:
+                for kind, failure, detail in found:
+                    fh.write(f"{file}: {failure} reading {kind}: {detail}\n")
+        for kind, failure, detail in found:
+            failures.append(
+                {
+                    "file": str(file.relative_to(root)),
+                    "input": kind,
+                    "kind": failure,
+                    "detail": detail,
+                    "repro": repro,
+                },
+            )
+    return failures
@@ -0,0 +1,159 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+from typing import IO, Any, Callable, Optional
+
+from .hyperjson import (
+    OPT_NON_STR_KEYS,
+    OPT_PASSTHROUGH_DATACLASS,
+    OPT_PASSTHROUGH_DATETIME,
+    JSONDecodeError,
+    dumps,
+    loads,
+)
+
+# datetime and dataclasses are passed to default= as json passes them
+_OPTION = OPT_NON_STR_KEYS | OPT_PASSTHROUGH_DATACLASS | OPT_PASSTHROUGH_DATETIME
+
+_INDENTS = (None, 2, 4, 8, " " * 2, " " * 4, " " * 8, "\t")
+
+# the functions of json as imported, which the shims call for what
+# hyperjson cannot apply
+_ORIGINALS: dict[str, Callable[..., Any]] = {
+    name: getattr(json, name) for name in ("dumps", "loads", "dump", "load")
+}
+
+
+def _is_plain(cls: Optional[type], base: type, methods: tuple[str, ...]) -> bool:
+    return cls is None or (
+        isinstance(cls, type)
+        and issubclass(cls, base)
+        and all(getattr(cls, name) is getattr(base, name) for name in methods)
+    )
+
+
+def _dumps(
+    obj: Any,
+    *,
+    skipkeys: bool = False,
+    ensure_ascii: bool = True,
+    check_circular: bool = True,
+    allow_nan: bool = True,
+    cls: Optional[type] = None,
+    indent: Any = None,
+    separators: Optional[tuple[str, str]] = None,
+    default: Optional[Callable[[Any], Any]] = None,
+    sort_keys: bool = False,
+    **kw: Any,
+) -> str:
+    written = (",", ":") if indent is None else (",", ": ")
+    if (
+        skipkeys
+        or kw
+        or indent not in _INDENTS
+        or (separators is not None and tuple(separators) != written)
+        or not _is_plain(cls, json.JSONEncoder, ("encode", "iterencode"))
+    ):
+        return _ORIGINALS["dumps"](
+            obj,
+            skipkeys=skipkeys,
+            ensure_ascii=ensure_ascii,
+            check_circular=check_circular,
+            allow_nan=allow_nan,
+            cls=cls,
+            indent=indent,
+            separators=separators,
+            default=default,
+            sort_keys=sort_keys,
+            **kw,
+        )
+    return dumps(
+        obj,
+        default,
+        _OPTION,
+        non_finite="literal" if allow_nan else "raise",
+        sort_keys=sort_keys,
+        ensure_ascii=ensure_ascii,
+        indent=indent,
+        cls=cls,
+    ).decode("utf-8")
+
+
+def _loads(
+    s: Any,
+    *,
+    cls: Optional[type] = None,
+    object_hook: Optional[Callable[[dict[str, Any]], Any]] = None,
+    parse_float: Optional[Callable[[str], Any]] = None,
+    parse_int: Optional[Callable[[str], Any]] = None,
+    parse_constant: Optional[Callable[[str], Any]] = None,
+    object_pairs_hook: Optional[Callable[[list[tuple[str, Any]]], Any]] = None,
+    **kw: Any,
+) -> Any:
+    if (
+        parse_constant is not None
+        or kw
+        or not _is_plain(cls, json.JSONDecoder, ("decode", "raw_decode"))
+    ):
+        return _ORIGINALS["loads"](
+            s,
+            cls=cls,
+            object_hook=object_hook,
+            parse_float=parse_float,
+            parse_int=parse_int,
+            parse_constant=parse_constant,
+            object_pairs_hook=object_pairs_hook,
+            **kw,
+        )
+    kwargs = {
+        "cls": cls,
+        "object_hook": object_hook,
+        "parse_float": parse_float,
+        "parse_int": parse_int,
+        "object_pairs_hook": object_pairs_hook,
+    }
+    if cls is not None or parse_float or parse_int or object_pairs_hook:
+        # these are called while reading, so it is done only once
+        return loads(s, allow_nan=True, **kwargs)
+    try:
+        return loads(s, **kwargs)
+    except JSONDecodeError:
+        # json reads NaN and Infinity, which loads() reads more slowly with
+        # allow_nan=True, so only documents that need it are read again
+        return loads(s, allow_nan=True, **kwargs)
+
+
+def _dump(obj: Any, fp: IO[str], **kw: Any) -> None:
+    fp.write(_dumps(obj, **kw))
+
+
+def _load(fp: IO[Any], **kw: Any) -> Any:
+    return _loads(fp.read(), **kw)
+
+
+_SHIMS = {"dumps": _dumps, "loads": _loads, "dump": _dump, "load": _load}
+
+
+def patch_json() -> None:
+    """
+    Replace json.dumps(), json.loads(), json.dump(), and json.load() with
+    functions using hyperjson, until unpatch().
+
+    They take the arguments json does and call json for those hyperjson
+    cannot apply, such as skipkeys=True, a cls= overriding encode(), or
+    separators= other than hyperjson writes. Output is compact unless indent=
+    is given. Code that imported the functions before patching is unaffected.
+    """
+    for name, shim in _SHIMS.items():
+        setattr(json, name, shim)
+
+
+def unpatch() -> None:
+    """
+    Restore the functions of json that patch_json() replaced, unless they
+    have since been replaced again.
+    """
+    for name, shim in _SHIMS.items():
+        if getattr(json, name) is shim:
+            setattr(json, name, _ORIGINALS[name])
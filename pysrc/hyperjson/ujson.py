@@ -0,0 +1,143 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+"""
+The API of ujson on hyperjson, so that `import hyperjson.ujson as ujson`
+replaces it.
+
+dumps() returns str and, as ujson does by default, escapes non-ASCII
+characters and "/" and writes NaN and Infinity. loads() reads NaN and
+Infinity. Floats are always read and written exactly, so precise_float is
+accepted and ignored.
+"""
+
+from typing import IO, Any, Callable, Optional, Union
+
+from .hyperjson import (
+    OPT_ENSURE_ASCII,
+    OPT_INDENT_2,
+    OPT_SORT_KEYS,
+    JSONDecodeError,
+    JSONEncodeError,
+    dumps as _dumps,
+    loads as _loads,
+)
+
+__all__ = (
+    "JSONDecodeError",
+    "JSONEncodeError",
+    "decode",
+    "dump",
+    "dumps",
+    "encode",
+    "load",
+    "loads",
+)
+
+# "<", ">", and "&" are escaped as ujson escapes them
+_HTML_CHARS = ((b"<", b"\\u003c"), (b">", b"\\u003e"), (b"&", b"\\u0026"))
+
+
+def _decode_bytes(default: Optional[Callable[[Any], Any]]) -> Callable[[Any], Any]:
+    def call(obj: Any) -> Any:
+        if isinstance(obj, bytes):
+            return obj.decode("utf-8")
+        if default is None:
+            raise TypeError(f"{obj!r} is not JSON serializable")
+        return default(obj)
+
+    return call
+
+
+def _reindent(data: bytes, indent: int) -> bytes:
+    # strings are written with newlines escaped, so the spaces at the start
+    # of each line of OPT_INDENT_2 are only indentation
+    lines = data.split(b"\n")
+    for idx, line in enumerate(lines):
+        stripped = line.lstrip(b" ")
+        lines[idx] = b" " * ((len(line) - len(stripped)) // 2 * indent) + stripped
+    return b"\n".join(lines)
+
+
+def dumps(
+    obj: Any,
+    ensure_ascii: bool = True,
+    double_precision: Optional[int] = None,
+    encode_html_chars: bool = False,
+    escape_forward_slashes: bool = True,
+    sort_keys: bool = False,
+    indent: int = 0,
+    allow_nan: bool = True,
+    reject_bytes: bool = True,
+    default: Optional[Callable[[Any], Any]] = None,
+    separators: Optional[tuple[str, str]] = None,
+) -> str:
+    """
+    Serialize obj to a str as ujson.dumps() does.
+
+    double_precision is the number of decimals floats are rounded to, up to
+    15. separators may only be those ujson writes by default, "," and ":",
+    or ": " with indent.
+    """
+    if separators is not None and tuple(separators) not in (
+        (",", ":"),
+        (",", ": "),
+    ):
+        raise JSONEncodeError("ujson.dumps() separators must be ',' and ':' or ': '")
+    if not reject_bytes:
+        default = _decode_bytes(default)
+    option = 0
+    if ensure_ascii:
+        option |= OPT_ENSURE_ASCII
+    if sort_keys:
+        option |= OPT_SORT_KEYS
+    if indent > 0:
+        option |= OPT_INDENT_2
+    float_decimals = None
+    if double_precision is not None and double_precision < 15:
+        float_decimals = double_precision
+    data = _dumps(
+        obj,
+        default,
+        option,
+        float_decimals=float_decimals,
+        non_finite="literal" if allow_nan else "raise",
+    )
+    if indent > 0 and indent != 2:
+        data = _reindent(data, indent)
+    # "/", "<", ">", and "&" are only written within strings, and bytes of
+    # them are never part of another character in UTF-8
+    if escape_forward_slashes:
+        data = data.replace(b"/", b"\\/")
+    if encode_html_chars:
+        for char, escaped in _HTML_CHARS:
+            data = data.replace(char, escaped)
+    return data.decode("utf-8")
+
+
+def loads(
+    s: Union[bytes, bytearray, memoryview, str],
+    precise_float: bool = False,
+) -> Any:
+    """
+    Deserialize s as ujson.loads() does.
+    """
+    return _loads(s, allow_nan=True)
+
+
+def dump(obj: Any, fp: IO[str], **kwargs: Any) -> None:
+    """
+    Serialize obj to the file fp as ujson.dump() does.
+    """
+    fp.write(dumps(obj, **kwargs))
+
+
+def load(fp: IO[Any], precise_float: bool = False) -> Any:
+    """
+    Deserialize the contents of the file fp as ujson.load() does.
+    """
+    return loads(fp.read())
+
+
+encode = dumps
+decode = loads
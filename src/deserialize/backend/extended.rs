@@ -0,0 +1,959 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! A parser of the extensions to JSON that `loads()` accepts by keyword,
+//! used instead of yyjson when one is enabled. yyjson is built with
+//! `YYJSON_DISABLE_NON_STANDARD`, so it cannot read them, and the standard
+//! path does not pay for checking them.
+//!
+//! Errors are reported with yyjson's messages so that they read the same
+//! whichever parser is used.
+
+use crate::deserialize::options::{DuplicateKeys, IntOverflow};
+use crate::deserialize::pyobject::{
+    get_unicode_key, parse_f64, parse_false, parse_i64, parse_none, parse_true, parse_u64,
+};
+use crate::deserialize::{DeserializeError, DeserializeOptions};
+use crate::ffi::PyObject;
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+use core::ptr::NonNull;
+use core::sync::atomic::Ordering;
+use std::borrow::Cow;
+
+/// Nesting deeper than this is an error rather than a risk to the stack.
+const RECURSION_LIMIT: usize = 1024;
+
+type ParseResult = Result<NonNull<PyObject>, DeserializeError<'static>>;
+
+struct Parser<'a, 'b> {
+    data: &'static str,
+    pos: usize,
+    depth: usize,
+    values: usize,
+    state: *const crate::interpreter_state::InterpreterState,
+    options: &'a DeserializeOptions<'b>,
+    /// Build lists and dicts with the public C API, from
+    /// `set_compat_mode()`.
+    compat_mode: bool,
+}
+
+pub(crate) fn deserialize(
+    data: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
+    values: &mut usize,
+) -> ParseResult {
+    let mut parser = Parser {
+        data: data,
+        pos: 0,
+        depth: 0,
+        values: 0,
+        state: interpreter_state,
+        options: options,
+        compat_mode: unsafe { (*interpreter_state).compat_mode.load(Ordering::Relaxed) },
+    };
+    parser.skip_whitespace()?;
+    if parser.pos == data.len() {
+        return Err(parser.error_at("input data is empty", 0));
+    }
+    let val = parser.parse_value()?;
+    if let Err(err) = parser.skip_whitespace() {
+        ffi!(Py_DECREF(val.as_ptr()));
+        return Err(err);
+    }
+    if parser.pos < data.len() {
+        ffi!(Py_DECREF(val.as_ptr()));
+        return Err(parser.error("unexpected content after document"));
+    }
+    *values = parser.values;
+    Ok(val)
+}
+
+#[cold]
+fn release(
+    items: Vec<NonNull<PyObject>>,
+    err: DeserializeError<'static>,
+) -> DeserializeError<'static> {
+    for val in items {
+        ffi!(Py_DECREF(val.as_ptr()));
+    }
+    err
+}
+
+/// Call `parse_float` or `parse_int` with the text of a number.
+#[cold]
+#[inline(never)]
+fn call_number_hook(hook: NonNull<PyObject>, text: &str) -> ParseResult {
+    let text = ffi!(PyUnicode_FromStringAndSize(
+        text.as_ptr().cast::<core::ffi::c_char>(),
+        usize_to_isize(text.len())
+    ));
+    let obj = ffi!(PyObject_CallFunctionObjArgs(
+        hook.as_ptr(),
+        text,
+        core::ptr::null_mut::<PyObject>()
+    ));
+    ffi!(Py_DECREF(text));
+    NonNull::new(obj).ok_or_else(DeserializeError::raised)
+}
+
+/// Whether `val`, read from `text`, is written with the same digits as
+/// `text` has, i.e., nothing was rounded away.
+#[cold]
+fn is_exact(text: &str, val: f64) -> bool {
+    let mut buf = ryu::Buffer::new();
+    let shortest = buf.format_finite(val);
+    match (significant_digits(text), significant_digits(shortest)) {
+        (Some(lhs), Some(rhs)) => lhs == rhs,
+        _ => false,
+    }
+}
+
+/// The digits of a decimal number without leading and trailing zeros and
+/// the position of the decimal point relative to the first, so that `1.50`,
+/// `15e-1`, and `0.15E1` are equal.
+fn significant_digits(text: &str) -> Option<(Vec<u8>, i64)> {
+    let text = text.trim_start_matches(['+', '-']);
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(idx) => (&text[..idx], text[idx + 1..].parse::<i64>().ok()?),
+        None => (text, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let mut point = i64::try_from(int.len()).ok()?.checked_add(exponent)?;
+    let mut digits: Vec<u8> = int.bytes().chain(frac.bytes()).collect();
+    let leading = digits.iter().take_while(|&&ch| ch == b'0').count();
+    if leading == digits.len() {
+        return Some((Vec::new(), 0));
+    }
+    digits.drain(..leading);
+    point -= i64::try_from(leading).ok()?;
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    Some((digits, point))
+}
+
+/// The whitespace JSON5 allows in addition to JSON's, other than `\v` and
+/// `\f`: the Unicode space separators, line terminators, and the BOM.
+fn is_json5_space(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{a0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200a}'
+                | '\u{2028}'
+                | '\u{2029}'
+                | '\u{202f}'
+                | '\u{205f}'
+                | '\u{3000}'
+                | '\u{feff}'
+    )
+}
+
+/// Remove the members of `dict` that are `None`. With `duplicate_keys=`,
+/// they are kept until the object is read so that their keys are compared.
+#[cold]
+fn remove_nulls(dict: NonNull<PyObject>) {
+    let mut nulls = Vec::new();
+    let mut pos = 0;
+    let mut key: *mut PyObject = core::ptr::null_mut();
+    let mut value: *mut PyObject = core::ptr::null_mut();
+    while ffi!(PyDict_Next(dict.as_ptr(), &mut pos, &mut key, &mut value)) == 1 {
+        if core::ptr::eq(value, crate::typeref::get_none()) {
+            nulls.push(key);
+        }
+    }
+    for key in nulls {
+        ffi!(PyDict_DelItem(dict.as_ptr(), key));
+    }
+}
+
+impl Parser<'_, '_> {
+    /// Set item `idx` of a new `list`, stealing `val`.
+    #[inline(always)]
+    fn list_set_item(&self, list: *mut PyObject, idx: usize, val: NonNull<PyObject>) {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyList_SetItem(list, usize_to_isize(idx), val.as_ptr()));
+        } else {
+            ffi!(PyList_SET_ITEM(list, usize_to_isize(idx), val.as_ptr()));
+        }
+    }
+
+    /// Set `key` of a new `dict`, stealing `key` and `val`.
+    #[inline(always)]
+    fn dict_set_item(&self, dict: NonNull<PyObject>, key: PyStr, val: NonNull<PyObject>) {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyDict_SetItem(dict.as_ptr(), key.as_ptr(), val.as_ptr()));
+            ffi!(Py_DECREF(key.as_ptr()));
+            ffi!(Py_DECREF(val.as_ptr()));
+        } else {
+            pydict_setitem!(dict.as_ptr(), key.as_ptr(), val.as_ptr());
+        }
+    }
+
+    #[cold]
+    fn error_at(&self, message: &'static str, pos: usize) -> DeserializeError<'static> {
+        DeserializeError::from_yyjson(Cow::Borrowed(message), pos as i64, self.data)
+    }
+
+    #[cold]
+    fn error(&self, message: &'static str) -> DeserializeError<'static> {
+        self.error_at(message, self.pos)
+    }
+
+    #[cold]
+    fn end_or(&self, message: &'static str) -> DeserializeError<'static> {
+        if self.pos >= self.data.len() {
+            self.error("unexpected end of data")
+        } else {
+            self.error(message)
+        }
+    }
+
+    #[inline(always)]
+    fn trailing_commas(&self) -> bool {
+        self.options.json5 || self.options.allow_trailing_commas
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), DeserializeError<'static>> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r') => self.pos += 1,
+                Some(0x0b | 0x0c) if self.options.json5 => self.pos += 1,
+                Some(b'/') if self.options.json5 => self.skip_comment()?,
+                Some(0x80..) if self.options.json5 => {
+                    let ch = self.data[self.pos..].chars().next().unwrap();
+                    if !is_json5_space(ch) {
+                        return Ok(());
+                    }
+                    self.pos += ch.len_utf8();
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Skip the `//` or `/* */` comment at the `/`.
+    fn skip_comment(&mut self) -> Result<(), DeserializeError<'static>> {
+        let start = self.pos;
+        let rest = &self.data.as_bytes()[start + 1..];
+        match rest.first() {
+            Some(b'/') => {
+                self.pos += rest
+                    .iter()
+                    .position(|&ch| ch == b'\n' || ch == b'\r')
+                    .unwrap_or(rest.len())
+                    + 1;
+                Ok(())
+            }
+            Some(b'*') => match rest[1..].windows(2).position(|pair| pair == b"*/") {
+                Some(end) => {
+                    self.pos += end + 4;
+                    Ok(())
+                }
+                None => Err(self.error_at("unclosed multiline comment", start)),
+            },
+            _ => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn parse_value(&mut self) -> ParseResult {
+        self.values += 1;
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => {
+                let val = self.parse_string(b'"')?;
+                Ok(PyStr::from_str(&val).as_non_null_ptr())
+            }
+            Some(b'\'') if self.options.json5 => {
+                let val = self.parse_string(b'\'')?;
+                Ok(PyStr::from_str(&val).as_non_null_ptr())
+            }
+            Some(b't') => self.parse_literal("true", parse_true),
+            Some(b'f') => self.parse_literal("false", parse_false),
+            Some(b'n') => self.parse_literal("null", parse_none),
+            Some(b'N') if self.options.allow_nan || self.options.json5 => {
+                self.parse_literal("NaN", || parse_f64(f64::NAN))
+            }
+            Some(b'I') if self.options.allow_nan || self.options.json5 => {
+                self.parse_literal("Infinity", || parse_f64(f64::INFINITY))
+            }
+            Some(b'+' | b'-' | b'.' | b'0'..=b'9') if self.options.json5 => {
+                self.parse_json5_number()
+            }
+            Some(b'-')
+                if self.options.allow_nan
+                    && self.data.as_bytes().get(self.pos + 1) == Some(&b'I') =>
+            {
+                self.parse_literal("-Infinity", || parse_f64(f64::NEG_INFINITY))
+            }
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.end_or("unexpected character")),
+        }
+    }
+
+    fn parse_literal(
+        &mut self,
+        literal: &'static str,
+        value: impl FnOnce() -> NonNull<PyObject>,
+    ) -> ParseResult {
+        let rest = &self.data.as_bytes()[self.pos..];
+        if rest.starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(value())
+        } else if literal.as_bytes().starts_with(rest) {
+            Err(self.error_at("unexpected end of data", self.data.len()))
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    /// Whether the container closes with `close` after the `,` at `comma`,
+    /// which is an error unless trailing commas are allowed.
+    fn closes_after_comma(
+        &mut self,
+        comma: usize,
+        close: u8,
+    ) -> Result<bool, DeserializeError<'static>> {
+        if self.peek() != Some(close) {
+            return Ok(false);
+        }
+        if !self.trailing_commas() {
+            return Err(self.error_at("trailing comma is not allowed", comma));
+        }
+        self.pos += 1;
+        Ok(true)
+    }
+
+    fn enter(&mut self) -> Result<(), DeserializeError<'static>> {
+        self.depth += 1;
+        self.pos += 1;
+        if self.depth > RECURSION_LIMIT {
+            return Err(self.error("array and object recursion depth exceeded"));
+        }
+        self.skip_whitespace()
+    }
+
+    fn parse_array(&mut self) -> ParseResult {
+        self.enter()?;
+        let mut items: Vec<NonNull<PyObject>> = Vec::new();
+        if let Err(err) = self.parse_items(&mut items) {
+            return Err(release(items, err));
+        }
+        self.depth -= 1;
+        let list = ffi!(PyList_New(usize_to_isize(items.len())));
+        for (idx, val) in items.into_iter().enumerate() {
+            self.list_set_item(list, idx, val);
+        }
+        Ok(nonnull!(list))
+    }
+
+    /// Read the elements of an array up to and including the `]`.
+    fn parse_items(
+        &mut self,
+        items: &mut Vec<NonNull<PyObject>>,
+    ) -> Result<(), DeserializeError<'static>> {
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',') => {
+                    let comma = self.pos;
+                    self.pos += 1;
+                    self.skip_whitespace()?;
+                    if self.closes_after_comma(comma, b']')? {
+                        return Ok(());
+                    }
+                }
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.end_or("unexpected character")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> ParseResult {
+        self.enter()?;
+        if let Some(hook) = self.options.object_pairs_hook {
+            cold_path!();
+            return self.parse_object_pairs(hook);
+        }
+        let dict = nonnull!(ffi!(PyDict_New()));
+        if let Err(err) = self.parse_members(dict) {
+            ffi!(Py_DECREF(dict.as_ptr()));
+            return Err(err);
+        }
+        self.depth -= 1;
+        Ok(dict)
+    }
+
+    /// Read the members of an object into `dict` up to and including the `}`.
+    fn parse_members(&mut self, dict: NonNull<PyObject>) -> Result<(), DeserializeError<'static>> {
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        let duplicate_keys = self.options.duplicate_keys;
+        loop {
+            let start = self.pos;
+            let (key, val) = self.parse_entry()?;
+            if duplicate_keys != DuplicateKeys::Last
+                && ffi!(PyDict_Contains(dict.as_ptr(), key.as_ptr())) == 1
+            {
+                cold_path!();
+                ffi!(Py_DECREF(key.as_ptr()));
+                ffi!(Py_DECREF(val.as_ptr()));
+                if duplicate_keys == DuplicateKeys::Error {
+                    return Err(self.error_at("duplicate key", start));
+                }
+            } else if self.options.omit_null
+                && duplicate_keys == DuplicateKeys::Last
+                && core::ptr::eq(val.as_ptr(), crate::typeref::get_none())
+            {
+                ffi!(Py_DECREF(key.as_ptr()));
+                release_immortal!(val.as_ptr());
+            } else {
+                self.dict_set_item(dict, key, val);
+            }
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',') => {
+                    let comma = self.pos;
+                    self.pos += 1;
+                    self.skip_whitespace()?;
+                    if self.closes_after_comma(comma, b'}')? {
+                        break;
+                    }
+                }
+                Some(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.end_or("unexpected character")),
+            }
+        }
+        if self.options.omit_null && duplicate_keys != DuplicateKeys::Last {
+            remove_nulls(dict);
+        }
+        Ok(())
+    }
+
+    /// Read an object and call `hook` with a `list` of its members as
+    /// `(key, value)` tuples.
+    #[cold]
+    #[inline(never)]
+    fn parse_object_pairs(&mut self, hook: NonNull<PyObject>) -> ParseResult {
+        // the keys read, which `duplicate_keys=` compares with
+        let seen = if self.options.duplicate_keys != DuplicateKeys::Last {
+            Some(nonnull!(ffi!(PyDict_New())))
+        } else {
+            None
+        };
+        let mut pairs: Vec<NonNull<PyObject>> = Vec::new();
+        let result = self.parse_pairs(&mut pairs, seen);
+        if let Some(seen) = seen {
+            ffi!(Py_DECREF(seen.as_ptr()));
+        }
+        if let Err(err) = result {
+            return Err(release(pairs, err));
+        }
+        self.depth -= 1;
+        let list = ffi!(PyList_New(usize_to_isize(pairs.len())));
+        for (idx, pair) in pairs.into_iter().enumerate() {
+            self.list_set_item(list, idx, pair);
+        }
+        let obj = ffi!(PyObject_CallFunctionObjArgs(
+            hook.as_ptr(),
+            list,
+            core::ptr::null_mut::<PyObject>()
+        ));
+        ffi!(Py_DECREF(list));
+        NonNull::new(obj).ok_or_else(DeserializeError::raised)
+    }
+
+    /// Read the members of an object into `pairs` up to and including the
+    /// `}`, leaving out those `duplicate_keys=` and `omit_null` do.
+    fn parse_pairs(
+        &mut self,
+        pairs: &mut Vec<NonNull<PyObject>>,
+        seen: Option<NonNull<PyObject>>,
+    ) -> Result<(), DeserializeError<'static>> {
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            let start = self.pos;
+            let (key, val) = self.parse_entry()?;
+            let duplicate = seen.is_some_and(|seen| {
+                if ffi!(PyDict_Contains(seen.as_ptr(), key.as_ptr())) == 1 {
+                    return true;
+                }
+                ffi!(PyDict_SetItem(
+                    seen.as_ptr(),
+                    key.as_ptr(),
+                    crate::typeref::get_none()
+                ));
+                false
+            });
+            if duplicate {
+                ffi!(Py_DECREF(key.as_ptr()));
+                ffi!(Py_DECREF(val.as_ptr()));
+                if self.options.duplicate_keys == DuplicateKeys::Error {
+                    return Err(self.error_at("duplicate key", start));
+                }
+            } else if self.options.omit_null
+                && core::ptr::eq(val.as_ptr(), crate::typeref::get_none())
+            {
+                ffi!(Py_DECREF(key.as_ptr()));
+                release_immortal!(val.as_ptr());
+            } else {
+                let pair = ffi!(PyTuple_New(2));
+                ffi!(PyTuple_SET_ITEM(pair, 0, key.as_ptr()));
+                ffi!(PyTuple_SET_ITEM(pair, 1, val.as_ptr()));
+                pairs.push(nonnull!(pair));
+            }
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',') => {
+                    let comma = self.pos;
+                    self.pos += 1;
+                    self.skip_whitespace()?;
+                    if self.closes_after_comma(comma, b'}')? {
+                        return Ok(());
+                    }
+                }
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.end_or("unexpected character")),
+            }
+        }
+    }
+
+    fn parse_entry(&mut self) -> Result<(PyStr, NonNull<PyObject>), DeserializeError<'static>> {
+        self.values += 1;
+        let key_str = match self.peek() {
+            Some(b'"') => self.parse_string(b'"')?,
+            Some(b'\'') if self.options.json5 => self.parse_string(b'\'')?,
+            Some(_) if self.options.json5 => self.parse_identifier()?,
+            _ => return Err(self.end_or("unexpected character")),
+        };
+        let key = match self
+            .options
+            .rename
+            .as_ref()
+            .and_then(|rename| rename.get(&key_str))
+        {
+            Some(renamed) => renamed,
+            None => get_unicode_key(&key_str, self.state),
+        };
+        match self.parse_member_value() {
+            Ok(val) => Ok((key, val)),
+            Err(err) => {
+                ffi!(Py_DECREF(key.as_ptr()));
+                Err(err)
+            }
+        }
+    }
+
+    /// Read the `:` after a key and the value after it.
+    fn parse_member_value(&mut self) -> ParseResult {
+        self.skip_whitespace()?;
+        if self.peek() != Some(b':') {
+            return Err(self.end_or("unexpected character"));
+        }
+        self.pos += 1;
+        self.skip_whitespace()?;
+        self.parse_value()
+    }
+
+    /// Read a JSON5 unquoted key, which is an ECMAScript identifier name.
+    fn parse_identifier(&mut self) -> Result<Cow<'static, str>, DeserializeError<'static>> {
+        let data = self.data;
+        let start = self.pos;
+        for ch in data[start..].chars() {
+            let valid = match ch {
+                '$' | '_' => true,
+                '\u{200c}' | '\u{200d}' => self.pos > start,
+                ch if self.pos > start => ch.is_alphanumeric(),
+                ch => ch.is_alphabetic(),
+            };
+            if !valid {
+                break;
+            }
+            self.pos += ch.len_utf8();
+        }
+        if self.pos == start {
+            return Err(self.end_or("unexpected character"));
+        }
+        Ok(Cow::Borrowed(&data[start..self.pos]))
+    }
+
+    /// Read the string at the opening `quote`, borrowing it from the input if
+    /// it has no escapes.
+    fn parse_string(&mut self, quote: u8) -> Result<Cow<'static, str>, DeserializeError<'static>> {
+        self.pos += 1;
+        let start = self.pos;
+        let bytes = self.data.as_bytes();
+        let mut unescaped: Option<String> = None;
+        let mut run = start;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unexpected end of data")),
+                Some(ch) if ch == quote => {
+                    let end = self.pos;
+                    self.pos += 1;
+                    return Ok(match unescaped {
+                        None => Cow::Borrowed(&self.data[start..end]),
+                        Some(mut val) => {
+                            val.push_str(&self.data[run..end]);
+                            Cow::Owned(val)
+                        }
+                    });
+                }
+                Some(b'\\') => {
+                    let val = unescaped.get_or_insert_with(String::new);
+                    val.push_str(&self.data[run..self.pos]);
+                    let escape = self.pos;
+                    self.pos += 1;
+                    let ch = match bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let ch = self.parse_unicode_escape(escape)?;
+                            val.push(ch);
+                            run = self.pos;
+                            continue;
+                        }
+                        _ if self.options.json5 => {
+                            if let Some(ch) = self.parse_json5_escape(escape)? {
+                                val.push(ch);
+                            }
+                            run = self.pos;
+                            continue;
+                        }
+                        _ => return Err(self.end_or("invalid escaped character in string")),
+                    };
+                    val.push(ch);
+                    self.pos += 1;
+                    run = self.pos;
+                }
+                Some(b'\n' | b'\r') => {
+                    return Err(self.error("unexpected control character in string"));
+                }
+                Some(0..0x20) if !self.options.json5 => {
+                    return Err(self.error("unexpected control character in string"));
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    /// Read the escapes JSON5 adds to JSON's at the character after the
+    /// backslash. A backslash before a line terminator continues the string
+    /// and reads as nothing.
+    fn parse_json5_escape(
+        &mut self,
+        escape: usize,
+    ) -> Result<Option<char>, DeserializeError<'static>> {
+        let bytes = self.data.as_bytes();
+        let ch = match bytes.get(self.pos) {
+            None => return Err(self.error("unexpected end of data")),
+            Some(b'\'') => '\'',
+            Some(b'v') => '\u{b}',
+            Some(b'0') if !matches!(bytes.get(self.pos + 1), Some(b'0'..=b'9')) => '\0',
+            Some(b'0'..=b'9') => {
+                return Err(self.error_at("invalid escaped character in string", escape));
+            }
+            Some(b'x') => {
+                let digits = bytes
+                    .get(self.pos + 1..self.pos + 3)
+                    .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+                    .ok_or_else(|| self.error_at("invalid escaped sequence in string", escape))?;
+                self.pos += 3;
+                let val = u8::from_str_radix(str_from_slice!(digits.as_ptr(), 2), 16).unwrap();
+                return Ok(Some(char::from(val)));
+            }
+            Some(b'\n') => {
+                self.pos += 1;
+                return Ok(None);
+            }
+            Some(b'\r') => {
+                self.pos += 1;
+                if self.peek() == Some(b'\n') {
+                    self.pos += 1;
+                }
+                return Ok(None);
+            }
+            Some(_) => {
+                let ch = self.data[self.pos..].chars().next().unwrap();
+                self.pos += ch.len_utf8();
+                return Ok(match ch {
+                    '\u{2028}' | '\u{2029}' => None,
+                    ch => Some(ch),
+                });
+            }
+        };
+        self.pos += 1;
+        Ok(Some(ch))
+    }
+
+    /// Read `\uXXXX`, or a surrogate pair of them, at the `u`.
+    fn parse_unicode_escape(&mut self, escape: usize) -> Result<char, DeserializeError<'static>> {
+        let high = self
+            .read_hex_u16(self.pos + 1)
+            .ok_or_else(|| self.escape_error("invalid escaped sequence in string", escape, 6))?;
+        self.pos += 5;
+        if !(0xd800..0xe000).contains(&high) {
+            return Ok(char::from_u32(u32::from(high)).unwrap());
+        }
+        if high >= 0xdc00 {
+            return Err(self.error_at("invalid high surrogate in string", escape));
+        }
+        if !self.data.as_bytes()[self.pos..].starts_with(b"\\u") {
+            return Err(self.escape_error("no low surrogate in string", self.pos, 2));
+        }
+        let low = self
+            .read_hex_u16(self.pos + 2)
+            .ok_or_else(|| self.escape_error("invalid escaped sequence in string", self.pos, 6))?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return Err(self.error("invalid low surrogate in string"));
+        }
+        self.pos += 6;
+        let codepoint = 0x10000 + ((u32::from(high) - 0xd800) << 10) + (u32::from(low) - 0xdc00);
+        Ok(char::from_u32(codepoint).unwrap())
+    }
+
+    /// `message` at `pos`, or the end of data if the `\\u` escape of `len`
+    /// bytes there is cut short by it.
+    #[cold]
+    fn escape_error(
+        &self,
+        message: &'static str,
+        pos: usize,
+        len: usize,
+    ) -> DeserializeError<'static> {
+        let rest = &self.data.as_bytes()[pos..];
+        let truncated = rest.len() < len
+            && rest.iter().enumerate().all(|(idx, ch)| match idx {
+                0 => *ch == b'\\',
+                1 => *ch == b'u',
+                _ => ch.is_ascii_hexdigit(),
+            });
+        if truncated {
+            self.error_at("unexpected end of data", self.data.len())
+        } else {
+            self.error_at(message, pos)
+        }
+    }
+
+    fn read_hex_u16(&self, pos: usize) -> Option<u16> {
+        let digits = self.data.as_bytes().get(pos..pos + 4)?;
+        if !digits.iter().all(u8::is_ascii_hexdigit) {
+            return None;
+        }
+        u16::from_str_radix(str_from_slice!(digits.as_ptr(), 4), 16).ok()
+    }
+
+    fn parse_number(&mut self) -> ParseResult {
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => {
+                self.pos += 1;
+                if let Some(b'0'..=b'9') = self.peek() {
+                    return Err(
+                        self.error_at("number with leading zero is not allowed", self.pos - 1)
+                    );
+                }
+            }
+            Some(b'1'..=b'9') => {
+                self.skip_digits();
+            }
+            _ => return Err(self.end_or("no digit after minus sign")),
+        }
+        let mut is_float = false;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if self.skip_digits() == 0 {
+                return Err(self.end_or("no digit after decimal point"));
+            }
+            is_float = true;
+        }
+        if self.parse_exponent()? {
+            is_float = true;
+        }
+        self.number_value(start, negative, is_float)
+    }
+
+    /// Read a JSON5 number, which may also have a leading `+`, a leading or
+    /// trailing decimal point, or be `Infinity`, `NaN`, or hexadecimal.
+    fn parse_json5_number(&mut self) -> ParseResult {
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        if let Some(b'+' | b'-') = self.peek() {
+            self.pos += 1;
+        }
+        let int_digits = match self.peek() {
+            Some(b'I') if negative => {
+                return self.parse_literal("Infinity", || parse_f64(f64::NEG_INFINITY));
+            }
+            Some(b'I') => return self.parse_literal("Infinity", || parse_f64(f64::INFINITY)),
+            Some(b'N') => return self.parse_literal("NaN", || parse_f64(f64::NAN)),
+            Some(b'0') if matches!(self.data.as_bytes().get(self.pos + 1), Some(b'x' | b'X')) => {
+                return self.parse_hex(start, negative);
+            }
+            Some(b'0') => {
+                self.pos += 1;
+                if let Some(b'0'..=b'9') = self.peek() {
+                    return Err(self.error_at("number with leading zero is not allowed", start));
+                }
+                1
+            }
+            Some(b'1'..=b'9') => self.skip_digits(),
+            Some(b'.') => 0,
+            _ => return Err(self.end_or("unexpected character")),
+        };
+        let mut is_float = false;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if self.skip_digits() == 0 && int_digits == 0 {
+                return Err(self.error_at("no digit after decimal point", start));
+            }
+            is_float = true;
+        }
+        if self.parse_exponent()? {
+            is_float = true;
+        }
+        self.number_value(start, negative, is_float)
+    }
+
+    /// Read a hexadecimal integer at the `0x` after its sign.
+    fn parse_hex(&mut self, start: usize, negative: bool) -> ParseResult {
+        self.pos += 2;
+        let digits = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_hexdigit()) {
+            self.pos += 1;
+        }
+        let text = &self.data[digits..self.pos];
+        if text.is_empty() {
+            return Err(self.error_at("no digit after hexadecimal prefix", start));
+        }
+        if let Ok(val) = u64::from_str_radix(text, 16) {
+            if !negative {
+                return Ok(parse_u64(val));
+            }
+            if let Some(val) = 0_i64.checked_sub_unsigned(val) {
+                return Ok(parse_i64(val));
+            }
+        }
+        if let Some(ret) = self.int_overflow(start) {
+            return ret;
+        }
+        let val = text.bytes().fold(0.0_f64, |acc, ch| {
+            acc * 16.0 + f64::from(char::from(ch).to_digit(16).unwrap())
+        });
+        if !val.is_finite() {
+            return Err(self.error_at("number is infinity when parsed as double", start));
+        }
+        Ok(parse_f64(if negative { -val } else { val }))
+    }
+
+    /// Read the integer ending at `pos` that is outside of 64 bits as
+    /// `int_overflow=` has it, or `None` to read it as a float.
+    #[cold]
+    fn int_overflow(&self, start: usize) -> Option<ParseResult> {
+        match self.options.int_overflow {
+            IntOverflow::Float => None,
+            IntOverflow::String => Some(Ok(
+                PyStr::from_str(&self.data[start..self.pos]).as_non_null_ptr()
+            )),
+            IntOverflow::Error => Some(Err(self.error_at("integer exceeds 64-bit range", start))),
+        }
+    }
+
+    /// Read the exponent of a number, if it has one.
+    fn parse_exponent(&mut self) -> Result<bool, DeserializeError<'static>> {
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if self.skip_digits() == 0 {
+                return Err(self.end_or("no digit after exponent sign"));
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn number_value(&self, start: usize, negative: bool, is_float: bool) -> ParseResult {
+        let text = &self.data[start..self.pos];
+        let hook = if is_float {
+            self.options.parse_float
+        } else {
+            self.options.parse_int
+        };
+        if let Some(hook) = hook {
+            cold_path!();
+            return call_number_hook(hook, text);
+        }
+        if !is_float {
+            // integers outside of 64 bits are read as floats as yyjson does
+            // unless `int_overflow=` says otherwise
+            if negative {
+                if let Ok(val) = text.parse::<i64>() {
+                    return Ok(parse_i64(val));
+                }
+            } else if let Ok(val) = text.parse::<u64>() {
+                return Ok(parse_u64(val));
+            }
+            if let Some(ret) = self.int_overflow(start) {
+                return ret;
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(val) if val.is_finite() => {
+                if is_float && self.options.exact_floats && !is_exact(text, val) {
+                    return Err(
+                        self.error_at("number loses precision when parsed as double", start)
+                    );
+                }
+                Ok(parse_f64(val))
+            }
+            _ => Err(self.error_at("number is infinity when parsed as double", start)),
+        }
+    }
+}
@@ -1,7 +1,13 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2024-2025)
 
+mod extended;
 mod ffi;
+mod reformat;
+mod typed;
 mod yyjson;
 
-pub(crate) use yyjson::deserialize;
+pub(crate) use extended::deserialize as deserialize_extended;
+pub(crate) use reformat::reformat;
+pub(crate) use typed::deserialize_typed;
+pub(crate) use yyjson::{Number, deserialize, deserialize_numbers, validate};
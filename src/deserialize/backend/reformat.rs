@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.minify()` and `hyperjson.prettify()`, which write a document
+//! read by yyjson again without creating its objects.
+
+use super::ffi::{YYJSON_READ_NUMBER_AS_RAW, yyjson_val};
+use super::yyjson::{
+    TAG_ARRAY, TAG_FALSE, TAG_NULL, TAG_RAW, TAG_STRING, TAG_TRUE, read_doc,
+    unsafe_yyjson_get_first, unsafe_yyjson_get_len, unsafe_yyjson_get_next_container,
+    unsafe_yyjson_get_next_non_container, unsafe_yyjson_is_ctn, yyjson_doc_get_root,
+};
+use crate::deserialize::DeserializeError;
+use crate::ffi::PyObject;
+use crate::serialize::writer::{BytesWriter, to_writer, to_writer_pretty};
+use core::ptr::NonNull;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::borrow::Cow;
+
+/// The value after `val`.
+#[inline(always)]
+fn next_sibling(val: *mut yyjson_val) -> *mut yyjson_val {
+    if unsafe_yyjson_is_ctn(val) {
+        unsafe_yyjson_get_next_container(val)
+    } else {
+        unsafe_yyjson_get_next_non_container(val)
+    }
+}
+
+#[inline(always)]
+fn val_str<'a>(val: *mut yyjson_val) -> &'a str {
+    str_from_slice!((*val).uni.str_.cast::<u8>(), unsafe_yyjson_get_len(val))
+}
+
+#[repr(transparent)]
+struct Value(*mut yyjson_val);
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let val = self.0;
+        match unsafe { (*val).tag as u8 } {
+            TAG_STRING => serializer.serialize_str(val_str(val)),
+            // the text of a number, which is valid JSON
+            TAG_RAW => serializer.serialize_bytes(val_str(val).as_bytes()),
+            TAG_TRUE => serializer.serialize_bool(true),
+            TAG_FALSE => serializer.serialize_bool(false),
+            TAG_NULL => serializer.serialize_unit(),
+            TAG_ARRAY => {
+                let len = unsafe_yyjson_get_len(val);
+                let mut seq = serializer.serialize_seq(Some(len))?;
+                let mut next = unsafe_yyjson_get_first(val);
+                for _ in 0..len {
+                    seq.serialize_element(&Value(next))?;
+                    next = next_sibling(next);
+                }
+                seq.end()
+            }
+            _ => {
+                let len = unsafe_yyjson_get_len(val);
+                let mut map = serializer.serialize_map(Some(len))?;
+                let mut key = unsafe_yyjson_get_first(val);
+                for _ in 0..len {
+                    let value = unsafe { key.add(1) };
+                    map.serialize_key(val_str(key))?;
+                    map.serialize_value(&Value(value))?;
+                    key = next_sibling(value);
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Read `data` and write it as `bytes`, indented by `indent` spaces per level
+/// of nesting or on one line if `None`. Numbers are read by yyjson as their
+/// text and written as they are in `data`, so none is rounded or out of
+/// range.
+#[cold]
+#[inline(never)]
+pub(crate) fn reformat(
+    data: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    indent: Option<usize>,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let doc = read_doc(data, interpreter_state, YYJSON_READ_NUMBER_AS_RAW)?;
+    let root = Value(yyjson_doc_get_root(doc));
+    let mut writer = BytesWriter::default();
+    let res = match indent {
+        None => to_writer(&mut writer, &root, 0),
+        Some(indent) => to_writer_pretty(&mut writer, &root, indent, b' ', 0, 0, 0),
+    };
+    match res {
+        Ok(()) => Ok(writer.finish(b"")),
+        Err(err) => {
+            writer.abort();
+            Err(DeserializeError::invalid(Cow::Owned(err.to_string())))
+        }
+    }
+}
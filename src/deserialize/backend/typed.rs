@@ -0,0 +1,690 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use super::ffi::{YYJSON_READ_NOFLAG, yyjson_val};
+use super::yyjson::{
+    TAG_ARRAY, TAG_DOUBLE, TAG_FALSE, TAG_INT64, TAG_NULL, TAG_OBJECT, TAG_STRING, TAG_TRUE,
+    TAG_UINT64, buffer_capacity_to_allocate, parse_primitive, parse_yy_string, populate_yy_array,
+    populate_yy_object, read_doc_in, unsafe_yyjson_get_first, unsafe_yyjson_get_len,
+    unsafe_yyjson_get_next_container, unsafe_yyjson_get_next_non_container, unsafe_yyjson_is_ctn,
+    yyjson_doc_get_root,
+};
+use crate::deserialize::pyobject::{
+    get_unicode_key, parse_f64, parse_false, parse_i64, parse_none, parse_true, parse_u64,
+};
+use crate::deserialize::scalar::{parse_date, parse_datetime, parse_uuid};
+use crate::deserialize::typed::{ClassPlan, Kind, Shape, TypePlan};
+use crate::deserialize::{DeserializeError, DeserializeOptions};
+use crate::ffi::PyObject;
+use crate::interpreter_state::InterpreterState;
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+use core::ptr::{NonNull, null_mut};
+use core::sync::atomic::Ordering;
+use std::borrow::Cow;
+
+/// Read `data` as `plan`.
+#[cold]
+#[inline(never)]
+pub(crate) fn deserialize_typed(
+    data: &'static str,
+    interpreter_state: *const InterpreterState,
+    options: &DeserializeOptions,
+    plan: &TypePlan,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    assume!(!data.is_empty());
+    // not the per-interpreter buffer, as `__init__` may call `loads()`
+    let mut buffer: Vec<u64> =
+        Vec::with_capacity(buffer_capacity_to_allocate(data.len()) / size_of::<u64>());
+    let doc = read_doc_in(
+        data,
+        buffer.as_mut_ptr().cast(),
+        buffer.capacity() * size_of::<u64>(),
+        YYJSON_READ_NOFLAG,
+    )?;
+    let reader = TypedReader {
+        plan: plan,
+        state: interpreter_state,
+        options: options,
+        compat_mode: unsafe { (*interpreter_state).compat_mode.load(Ordering::Relaxed) },
+    };
+    reader
+        .read(yyjson_doc_get_root(doc), &plan.root)
+        .map_err(TypedError::into_deserialize_error)
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Why a value could not be read, with the path to it built as the error is
+/// returned through its containers.
+struct TypedError {
+    message: String,
+    /// Innermost first.
+    path: Vec<Segment>,
+    /// `__init__` or a validator raised and its exception is set.
+    raised: bool,
+}
+
+impl TypedError {
+    #[cold]
+    fn new(message: String) -> Self {
+        TypedError {
+            message: message,
+            path: Vec::new(),
+            raised: false,
+        }
+    }
+
+    #[cold]
+    fn raised() -> Self {
+        TypedError {
+            raised: true,
+            ..TypedError::new(String::new())
+        }
+    }
+
+    #[cold]
+    fn at(mut self, segment: Segment) -> Self {
+        self.path.push(segment);
+        self
+    }
+
+    #[cold]
+    fn into_deserialize_error(self) -> DeserializeError<'static> {
+        if self.raised {
+            return DeserializeError::raised();
+        }
+        let mut path = String::from("$");
+        for segment in self.path.iter().rev() {
+            match segment {
+                Segment::Key(key) => {
+                    path.push('.');
+                    path.push_str(key);
+                }
+                Segment::Index(idx) => path.push_str(&format!("[{idx}]")),
+            }
+        }
+        DeserializeError::invalid(Cow::Owned(format!("{} at {}", self.message, path)))
+    }
+}
+
+/// The value after `val`.
+#[inline(always)]
+fn next_sibling(val: *mut yyjson_val) -> *mut yyjson_val {
+    if unsafe_yyjson_is_ctn(val) {
+        unsafe_yyjson_get_next_container(val)
+    } else {
+        unsafe_yyjson_get_next_non_container(val)
+    }
+}
+
+#[inline(always)]
+fn key_str<'a>(key: *mut yyjson_val) -> &'a str {
+    str_from_slice!((*key).uni.str_.cast::<u8>(), unsafe_yyjson_get_len(key))
+}
+
+/// The references of the fields of an object being read.
+struct FieldValues(Vec<*mut PyObject>);
+
+impl Drop for FieldValues {
+    fn drop(&mut self) {
+        for &val in self.0.iter() {
+            ffi!(Py_XDECREF(val));
+        }
+    }
+}
+
+struct TypedReader<'a> {
+    plan: &'a TypePlan,
+    state: *const InterpreterState,
+    options: &'a DeserializeOptions<'a>,
+    /// Build lists, tuples, and dicts with the public C API, from
+    /// `set_compat_mode()`.
+    compat_mode: bool,
+}
+
+impl TypedReader<'_> {
+    /// Read `val` as `kind`, returning a new reference.
+    fn read(&self, val: *mut yyjson_val, kind: &Kind) -> Result<NonNull<PyObject>, TypedError> {
+        let tag = unsafe { (*val).tag as u8 };
+        #[allow(clippy::cast_precision_loss)]
+        match kind {
+            Kind::Any => Ok(self.read_any(val)),
+            Kind::Str if tag == TAG_STRING => Ok(parse_yy_string(val)),
+            Kind::Int if tag == TAG_INT64 => Ok(parse_i64(unsafe { (*val).uni.i64_ })),
+            Kind::Int if tag == TAG_UINT64 => Ok(parse_u64(unsafe { (*val).uni.u64_ })),
+            Kind::Float if tag == TAG_DOUBLE => Ok(parse_f64(unsafe { (*val).uni.f64_ })),
+            Kind::Float if tag == TAG_INT64 => Ok(parse_f64(unsafe { (*val).uni.i64_ } as f64)),
+            Kind::Float if tag == TAG_UINT64 => Ok(parse_f64(unsafe { (*val).uni.u64_ } as f64)),
+            Kind::Bool if tag == TAG_TRUE => Ok(parse_true()),
+            Kind::Bool if tag == TAG_FALSE => Ok(parse_false()),
+            Kind::None if tag == TAG_NULL => Ok(parse_none()),
+            Kind::Datetime if tag == TAG_STRING => self.read_datetime(val),
+            Kind::Date if tag == TAG_STRING => self.read_date(val),
+            Kind::Uuid if tag == TAG_STRING => self.read_uuid(val),
+            Kind::Decimal if tag == TAG_STRING || tag == TAG_INT64 || tag == TAG_UINT64 => {
+                self.read_decimal(val)
+            }
+            Kind::List(item) if tag == TAG_ARRAY => self.read_array(val, false, |_| item),
+            Kind::Tuple(item) if tag == TAG_ARRAY => self.read_array(val, true, |_| item),
+            Kind::FixedTuple(items) if tag == TAG_ARRAY => {
+                if unsafe_yyjson_get_len(val) != items.len() {
+                    return Err(TypedError::new(format!(
+                        "expected {} of {} items",
+                        self.plan.describe(kind),
+                        items.len()
+                    )));
+                }
+                self.read_array(val, true, |idx| &items[idx])
+            }
+            Kind::Dict(value) if tag == TAG_OBJECT => self.read_dict(val, value),
+            Kind::Union(members) => self.read_union(val, kind, members),
+            Kind::Adapter(idx) if tag != TAG_NULL => {
+                let arg = self.read_any(val).as_ptr();
+                let ret = ffi!(PyObject_CallFunctionObjArgs(
+                    self.plan.adapters[*idx].callable,
+                    arg,
+                    null_mut::<PyObject>()
+                ));
+                ffi!(Py_DECREF(arg));
+                NonNull::new(ret).ok_or_else(TypedError::raised)
+            }
+            Kind::Class(idx) if self.plan.classes[*idx].shape == Shape::Pydantic => {
+                self.read_model(val, *idx)
+            }
+            Kind::Class(idx) if tag == TAG_OBJECT => self.read_class(val, *idx),
+            Kind::Class(idx)
+                if tag == TAG_ARRAY && self.plan.classes[*idx].shape == Shape::NamedTuple =>
+            {
+                self.read_namedtuple_array(val, *idx)
+            }
+            _ => Err(TypedError::new(format!(
+                "expected {}",
+                self.plan.describe(kind)
+            ))),
+        }
+    }
+
+    /// Read `val` as `loads()` does.
+    fn read_any(&self, val: *mut yyjson_val) -> NonNull<PyObject> {
+        if !unsafe_yyjson_is_ctn(val) {
+            return parse_primitive(val);
+        }
+        if self.compat_mode {
+            cold_path!();
+            return self.read_any_compat(val);
+        }
+        let len = unsafe_yyjson_get_len(val);
+        if unsafe { (*val).tag as u8 } == TAG_ARRAY {
+            let list = nonnull!(ffi!(PyList_New(usize_to_isize(len))));
+            if len > 0 {
+                populate_yy_array(list.as_ptr(), val, self.state, self.options);
+            }
+            list
+        } else {
+            let dict = nonnull!(ffi!(_PyDict_NewPresized(usize_to_isize(len))));
+            if len > 0 {
+                populate_yy_object(dict.as_ptr(), val, self.state, self.options);
+            }
+            dict
+        }
+    }
+
+    /// Read the container `val` as `loads()` does, building it with the
+    /// public C API.
+    #[cold]
+    #[inline(never)]
+    fn read_any_compat(&self, val: *mut yyjson_val) -> NonNull<PyObject> {
+        let len = unsafe_yyjson_get_len(val);
+        if unsafe { (*val).tag as u8 } == TAG_ARRAY {
+            let list = ffi!(PyList_New(usize_to_isize(len)));
+            let mut next = unsafe_yyjson_get_first(val);
+            for idx in 0..len {
+                let item = next;
+                next = next_sibling(item);
+                self.list_set_item(list, idx, self.read_any(item));
+            }
+            nonnull!(list)
+        } else {
+            let dict = ffi!(PyDict_New());
+            let mut next_key = unsafe_yyjson_get_first(val);
+            for _ in 0..len {
+                let key = next_key;
+                let value = unsafe { key.add(1) };
+                next_key = next_sibling(value);
+                if self.options.omit_null && unsafe { (*value).tag as u8 } == TAG_NULL {
+                    continue;
+                }
+                self.dict_set_item(dict, self.key(key_str(key)), self.read_any(value));
+            }
+            nonnull!(dict)
+        }
+    }
+
+    /// A new, empty `dict` for `len` items.
+    #[inline(always)]
+    fn new_dict(&self, len: usize) -> *mut PyObject {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyDict_New())
+        } else {
+            ffi!(_PyDict_NewPresized(usize_to_isize(len)))
+        }
+    }
+
+    /// Set item `idx` of a new `list`, stealing `val`.
+    #[inline(always)]
+    fn list_set_item(&self, list: *mut PyObject, idx: usize, val: NonNull<PyObject>) {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyList_SetItem(list, usize_to_isize(idx), val.as_ptr()));
+        } else {
+            ffi!(PyList_SET_ITEM(list, usize_to_isize(idx), val.as_ptr()));
+        }
+    }
+
+    /// Set item `idx` of a new `tuple`, stealing `val`.
+    #[inline(always)]
+    fn tuple_set_item(&self, tuple: *mut PyObject, idx: usize, val: NonNull<PyObject>) {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyTuple_SetItem(tuple, usize_to_isize(idx), val.as_ptr()));
+        } else {
+            ffi!(PyTuple_SET_ITEM(tuple, usize_to_isize(idx), val.as_ptr()));
+        }
+    }
+
+    /// Set `key` of a new `dict`, stealing `key` and `val`.
+    #[inline(always)]
+    fn dict_set_item(&self, dict: *mut PyObject, key: PyStr, val: NonNull<PyObject>) {
+        if self.compat_mode {
+            cold_path!();
+            ffi!(PyDict_SetItem(dict, key.as_ptr(), val.as_ptr()));
+            ffi!(Py_DECREF(key.as_ptr()));
+            ffi!(Py_DECREF(val.as_ptr()));
+        } else {
+            pydict_setitem!(dict, key.as_ptr(), val.as_ptr());
+        }
+    }
+
+    /// Read the string `val` as a `datetime`, which is naive if it has no
+    /// offset.
+    fn read_datetime(&self, val: *mut yyjson_val) -> Result<NonNull<PyObject>, TypedError> {
+        let Some(dt) = parse_datetime(key_str(val).as_bytes()) else {
+            return Err(TypedError::new(String::from("invalid datetime")));
+        };
+        let builders = &self.plan.builders;
+        let tzinfo = match dt.offset {
+            None => use_immortal!(crate::typeref::get_none()),
+            Some(0) => {
+                ffi!(Py_INCREF(builders.utc));
+                builders.utc
+            }
+            Some(offset) => {
+                let delta = call(builders.timedelta, &[long(0), long(offset)]);
+                if delta.is_null() {
+                    return Err(TypedError::raised());
+                }
+                let tzinfo = call(builders.timezone, &[delta]);
+                if tzinfo.is_null() {
+                    return Err(TypedError::raised());
+                }
+                tzinfo
+            }
+        };
+        let ret = call(
+            crate::typeref::get_datetime_type_from_state(self.state).cast::<PyObject>(),
+            &[
+                long(dt.date.year),
+                long(dt.date.month),
+                long(dt.date.day),
+                long(dt.hour),
+                long(dt.minute),
+                long(dt.second),
+                long(dt.microsecond),
+                tzinfo,
+            ],
+        );
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+
+    fn read_date(&self, val: *mut yyjson_val) -> Result<NonNull<PyObject>, TypedError> {
+        let Some(date) = parse_date(key_str(val).as_bytes()) else {
+            return Err(TypedError::new(String::from("invalid date")));
+        };
+        let ret = call(
+            crate::typeref::get_date_type_from_state(self.state).cast::<PyObject>(),
+            &[long(date.year), long(date.month), long(date.day)],
+        );
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+
+    /// Read the string `val` as a `UUID`, calling `UUID(int=)` with the
+    /// digits already checked.
+    fn read_uuid(&self, val: *mut yyjson_val) -> Result<NonNull<PyObject>, TypedError> {
+        let Some(hex) = parse_uuid(key_str(val).as_bytes()) else {
+            return Err(TypedError::new(String::from("invalid UUID")));
+        };
+        let mut buf = [0u8; 33];
+        buf[..32].copy_from_slice(&hex);
+        let int = ffi!(PyLong_FromString(
+            buf.as_ptr().cast::<core::ffi::c_char>(),
+            null_mut(),
+            16
+        ));
+        if int.is_null() {
+            return Err(TypedError::raised());
+        }
+        let ret = ffi!(PyObject_Vectorcall(
+            crate::typeref::get_uuid_type_from_state(self.state).cast::<PyObject>(),
+            &raw const int,
+            0,
+            self.plan.builders.uuid_kwnames
+        ));
+        ffi!(Py_DECREF(int));
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+
+    /// Read the string or integer `val` as a `Decimal`.
+    fn read_decimal(&self, val: *mut yyjson_val) -> Result<NonNull<PyObject>, TypedError> {
+        let arg = self.read_any(val).as_ptr();
+        let ret = call(self.plan.builders.decimal, &[arg]);
+        if ret.is_null() {
+            // `decimal.InvalidOperation`
+            ffi!(PyErr_Clear());
+            return Err(TypedError::new(String::from("invalid Decimal")));
+        }
+        Ok(nonnull!(ret))
+    }
+
+    /// Whether `kind` may read a value of `tag`.
+    fn accepts(&self, kind: &Kind, tag: u8) -> bool {
+        match kind {
+            Kind::Any => true,
+            // `null` is `None` rather than the registered type, as for a class
+            Kind::Adapter(_) => tag != TAG_NULL,
+            Kind::Str | Kind::Datetime | Kind::Date | Kind::Uuid => tag == TAG_STRING,
+            Kind::Decimal => tag == TAG_STRING || tag == TAG_INT64 || tag == TAG_UINT64,
+            Kind::Int => tag == TAG_INT64 || tag == TAG_UINT64,
+            Kind::Float => tag == TAG_INT64 || tag == TAG_UINT64 || tag == TAG_DOUBLE,
+            Kind::Bool => tag == TAG_TRUE || tag == TAG_FALSE,
+            Kind::None => tag == TAG_NULL,
+            Kind::List(_) | Kind::Tuple(_) | Kind::FixedTuple(_) => tag == TAG_ARRAY,
+            Kind::Dict(_) => tag == TAG_OBJECT,
+            Kind::Class(idx) => {
+                tag == TAG_OBJECT
+                    || (tag == TAG_ARRAY && self.plan.classes[*idx].shape == Shape::NamedTuple)
+            }
+            Kind::Union(members) => members.iter().any(|member| self.accepts(member, tag)),
+        }
+    }
+
+    /// Read `val` as the first of `members` that reads it. The error of the
+    /// last member that may read a value of its type is kept, so that an
+    /// `Optional` reports what is wrong within it.
+    fn read_union(
+        &self,
+        val: *mut yyjson_val,
+        kind: &Kind,
+        members: &[Kind],
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let tag = unsafe { (*val).tag as u8 };
+        let mut candidates = members
+            .iter()
+            .filter(|member| self.accepts(member, tag))
+            .peekable();
+        while let Some(member) = candidates.next() {
+            if candidates.peek().is_none() {
+                return self.read(val, member);
+            }
+            match self.read(val, member) {
+                Ok(obj) => return Ok(obj),
+                Err(err) if err.raised => return Err(err),
+                Err(_) => {}
+            }
+        }
+        Err(TypedError::new(format!(
+            "expected {}",
+            self.plan.describe(kind)
+        )))
+    }
+
+    fn read_array<'k>(
+        &self,
+        val: *mut yyjson_val,
+        tuple: bool,
+        kind_of: impl Fn(usize) -> &'k Kind,
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let len = unsafe_yyjson_get_len(val);
+        let seq = if tuple {
+            ffi!(PyTuple_New(usize_to_isize(len)))
+        } else {
+            ffi!(PyList_New(usize_to_isize(len)))
+        };
+        let mut next = unsafe_yyjson_get_first(val);
+        for idx in 0..len {
+            let item = next;
+            next = next_sibling(item);
+            match self.read(item, kind_of(idx)) {
+                Ok(obj) if tuple => self.tuple_set_item(seq, idx, obj),
+                Ok(obj) => self.list_set_item(seq, idx, obj),
+                Err(err) => {
+                    ffi!(Py_DECREF(seq));
+                    return Err(err.at(Segment::Index(idx)));
+                }
+            }
+        }
+        Ok(nonnull!(seq))
+    }
+
+    fn read_dict(
+        &self,
+        val: *mut yyjson_val,
+        kind: &Kind,
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let len = unsafe_yyjson_get_len(val);
+        let dict = self.new_dict(len);
+        let mut next_key = unsafe_yyjson_get_first(val);
+        for _ in 0..len {
+            let key = next_key;
+            let value = unsafe { key.add(1) };
+            next_key = next_sibling(value);
+            if self.options.omit_null && unsafe { (*value).tag as u8 } == TAG_NULL {
+                continue;
+            }
+            let key_str = key_str(key);
+            match self.read(value, kind) {
+                Ok(obj) => self.dict_set_item(dict, self.key(key_str), obj),
+                Err(err) => {
+                    ffi!(Py_DECREF(dict));
+                    return Err(err.at(Segment::Key(String::from(key_str))));
+                }
+            }
+        }
+        Ok(nonnull!(dict))
+    }
+
+    /// The key of an object after `rename`, as a new reference.
+    #[inline(always)]
+    fn key(&self, key_str: &str) -> PyStr {
+        match self
+            .options
+            .rename
+            .as_ref()
+            .and_then(|rename| rename.get(key_str))
+        {
+            Some(renamed) => renamed,
+            None => get_unicode_key(key_str, self.state),
+        }
+    }
+
+    /// Read the members of `val` as the fields of `self.plan.classes[idx]`.
+    /// Keys that are not fields are ignored.
+    fn read_class(
+        &self,
+        val: *mut yyjson_val,
+        idx: usize,
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let class = &self.plan.classes[idx];
+        let mut values = FieldValues(vec![null_mut(); class.fields.len()]);
+        let mut hint = 0;
+        let mut next_key = unsafe_yyjson_get_first(val);
+        for _ in 0..unsafe_yyjson_get_len(val) {
+            let key = next_key;
+            let value = unsafe { key.add(1) };
+            next_key = next_sibling(value);
+            if self.options.omit_null && unsafe { (*value).tag as u8 } == TAG_NULL {
+                continue;
+            }
+            let key_str = key_str(key);
+            let found = match self.options.rename.as_ref() {
+                Some(rename) => match rename.get(key_str) {
+                    Some(renamed) => {
+                        ffi!(Py_DECREF(renamed.as_ptr()));
+                        renamed
+                            .to_str()
+                            .and_then(|renamed| class.find(renamed, hint))
+                    }
+                    None => class.find(key_str, hint),
+                },
+                None => class.find(key_str, hint),
+            };
+            let Some(field) = found else {
+                continue;
+            };
+            hint = field + 1;
+            let obj = self
+                .read(value, &class.fields[field].kind)
+                .map_err(|err| err.at(Segment::Key(String::from(key_str))))?;
+            let previous = core::mem::replace(&mut values.0[field], obj.as_ptr());
+            ffi!(Py_XDECREF(previous));
+        }
+        check_required(class, &values.0)?;
+        if class.shape == Shape::TypedDict {
+            let dict = self.new_dict(values.0.len());
+            for (field, &obj) in class.fields.iter().zip(values.0.iter()) {
+                if !obj.is_null() {
+                    ffi!(PyDict_SetItem(dict, field.name, obj));
+                }
+            }
+            return Ok(nonnull!(dict));
+        }
+        let ret = if values.0.iter().all(|obj| !obj.is_null()) {
+            ffi!(PyObject_Vectorcall(
+                class.cls,
+                values.0.as_ptr(),
+                0,
+                class.kwnames
+            ))
+        } else {
+            let mut args = Vec::with_capacity(values.0.len());
+            let mut names = Vec::with_capacity(values.0.len());
+            for (field, &obj) in class.fields.iter().zip(values.0.iter()) {
+                if !obj.is_null() {
+                    args.push(obj);
+                    names.push(field.name);
+                }
+            }
+            let kwnames = ffi!(PyTuple_New(usize_to_isize(names.len())));
+            for (i, &name) in names.iter().enumerate() {
+                ffi!(Py_INCREF(name));
+                self.tuple_set_item(kwnames, i, nonnull!(name));
+            }
+            let ret = ffi!(PyObject_Vectorcall(class.cls, args.as_ptr(), 0, kwnames));
+            ffi!(Py_DECREF(kwnames));
+            ret
+        };
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+
+    /// Read `val` as `loads()` does and validate it as the pydantic model
+    /// `self.plan.classes[idx]`, so that the model validates any models
+    /// within it and its errors locate each one from the outermost.
+    fn read_model(
+        &self,
+        val: *mut yyjson_val,
+        idx: usize,
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let obj = self.read_any(val).as_ptr();
+        let ret = ffi!(PyObject_CallFunctionObjArgs(
+            self.plan.classes[idx].validator,
+            obj,
+            null_mut::<PyObject>()
+        ));
+        ffi!(Py_DECREF(obj));
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+
+    /// Read the items of `val` as the fields of the `NamedTuple`
+    /// `self.plan.classes[idx]`, in order.
+    fn read_namedtuple_array(
+        &self,
+        val: *mut yyjson_val,
+        idx: usize,
+    ) -> Result<NonNull<PyObject>, TypedError> {
+        let class = &self.plan.classes[idx];
+        let len = unsafe_yyjson_get_len(val);
+        if len > class.fields.len() {
+            return Err(TypedError::new(format!(
+                "expected {} of at most {} items",
+                class.name,
+                class.fields.len()
+            )));
+        }
+        let mut values = FieldValues(Vec::with_capacity(class.fields.len()));
+        let mut next = unsafe_yyjson_get_first(val);
+        for (i, field) in class.fields.iter().take(len).enumerate() {
+            let item = next;
+            next = next_sibling(item);
+            let obj = self
+                .read(item, &field.kind)
+                .map_err(|err| err.at(Segment::Index(i)))?;
+            values.0.push(obj.as_ptr());
+        }
+        values.0.resize(class.fields.len(), null_mut());
+        check_required(class, &values.0)?;
+        let ret = ffi!(PyObject_Vectorcall(
+            class.cls,
+            values.0.as_ptr(),
+            len,
+            null_mut()
+        ));
+        NonNull::new(ret).ok_or_else(TypedError::raised)
+    }
+}
+
+/// A new reference to the `int` `val`.
+#[inline(always)]
+fn long(val: i32) -> *mut PyObject {
+    ffi!(PyLong_FromLongLong(i64::from(val)))
+}
+
+/// Call `callable` with `args`, which it takes.
+fn call(callable: *mut PyObject, args: &[*mut PyObject]) -> *mut PyObject {
+    let ret = ffi!(PyObject_Vectorcall(
+        callable,
+        args.as_ptr(),
+        args.len(),
+        null_mut()
+    ));
+    for &arg in args {
+        ffi!(Py_DECREF(arg));
+    }
+    ret
+}
+
+/// Raise for the first field of `class` that is required and not in `values`.
+fn check_required(class: &ClassPlan, values: &[*mut PyObject]) -> Result<(), TypedError> {
+    for (field, obj) in class.fields.iter().zip(values.iter()) {
+        if field.required && obj.is_null() {
+            return Err(TypedError::new(format!(
+                "missing required field \"{}\" of {}",
+                field.key, class.name
+            )));
+        }
+    }
+    Ok(())
+}
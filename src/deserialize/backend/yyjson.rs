@@ -2,13 +2,13 @@
 // Copyright ijl (2022-2025), Anders Kaseorg (2023)
 
 use super::ffi::{
-    YYJSON_READ_SUCCESS, yyjson_alc, yyjson_alc_pool_init, yyjson_doc, yyjson_read_err,
-    yyjson_read_opts, yyjson_val,
+    YYJSON_READ_NOFLAG, YYJSON_READ_SUCCESS, yyjson_alc, yyjson_alc_pool_init, yyjson_doc,
+    yyjson_read_err, yyjson_read_flag, yyjson_read_opts, yyjson_val,
 };
-use crate::deserialize::DeserializeError;
 use crate::deserialize::pyobject::{
     get_unicode_key, parse_f64, parse_false, parse_i64, parse_none, parse_true, parse_u64,
 };
+use crate::deserialize::{DeserializeError, DeserializeOptions};
 use crate::str::PyStr;
 use crate::util::usize_to_isize;
 use core::ffi::c_char;
@@ -19,14 +19,16 @@ const YYJSON_TAG_BIT: u8 = 8;
 
 const YYJSON_VAL_SIZE: usize = core::mem::size_of::<yyjson_val>();
 
-const TAG_ARRAY: u8 = 0b00000110;
-const TAG_DOUBLE: u8 = 0b00010100;
-const TAG_FALSE: u8 = 0b00000011;
-const TAG_INT64: u8 = 0b00001100;
-const TAG_NULL: u8 = 0b00000010;
-const TAG_STRING: u8 = 0b00000101;
-const TAG_TRUE: u8 = 0b00001011;
-const TAG_UINT64: u8 = 0b00000100;
+pub(super) const TAG_ARRAY: u8 = 0b00000110;
+pub(super) const TAG_DOUBLE: u8 = 0b00010100;
+pub(super) const TAG_FALSE: u8 = 0b00000011;
+pub(super) const TAG_INT64: u8 = 0b00001100;
+pub(super) const TAG_NULL: u8 = 0b00000010;
+pub(super) const TAG_OBJECT: u8 = 0b00000111;
+pub(super) const TAG_RAW: u8 = 0b00000001;
+pub(super) const TAG_STRING: u8 = 0b00000101;
+pub(super) const TAG_TRUE: u8 = 0b00001011;
+pub(super) const TAG_UINT64: u8 = 0b00000100;
 
 macro_rules! is_yyjson_tag {
     ($elem:expr, $tag:expr) => {
@@ -34,43 +36,47 @@ macro_rules! is_yyjson_tag {
     };
 }
 
-fn yyjson_doc_get_root(doc: *mut yyjson_doc) -> *mut yyjson_val {
+pub(super) fn yyjson_doc_get_root(doc: *mut yyjson_doc) -> *mut yyjson_val {
     unsafe { (*doc).root }
 }
 
-fn unsafe_yyjson_get_len(val: *mut yyjson_val) -> usize {
+pub(super) fn unsafe_yyjson_get_len(val: *mut yyjson_val) -> usize {
     unsafe { ((*val).tag >> YYJSON_TAG_BIT) as usize }
 }
 
-fn unsafe_yyjson_get_first(ctn: *mut yyjson_val) -> *mut yyjson_val {
+pub(super) fn unsafe_yyjson_get_first(ctn: *mut yyjson_val) -> *mut yyjson_val {
     unsafe { ctn.add(1) }
 }
 
 const MINIMUM_BUFFER_CAPACITY: usize = 4096;
 
-fn buffer_capacity_to_allocate(len: usize) -> usize {
+pub(super) fn buffer_capacity_to_allocate(len: usize) -> usize {
     // The max memory size is (json_size / 2 * 16 * 1.5 + padding).
     (((len / 2) * 24) + 256 + (MINIMUM_BUFFER_CAPACITY - 1)) & !(MINIMUM_BUFFER_CAPACITY - 1)
 }
 
-fn unsafe_yyjson_is_ctn(val: *mut yyjson_val) -> bool {
+pub(super) fn unsafe_yyjson_is_ctn(val: *mut yyjson_val) -> bool {
     unsafe { (*val).tag as u8 & 0b00000110 == 0b00000110 }
 }
 
 #[allow(clippy::cast_ptr_alignment)]
-fn unsafe_yyjson_get_next_container(val: *mut yyjson_val) -> *mut yyjson_val {
+pub(super) fn unsafe_yyjson_get_next_container(val: *mut yyjson_val) -> *mut yyjson_val {
     unsafe { (val.cast::<u8>().add((*val).uni.ofs)).cast::<yyjson_val>() }
 }
 
 #[allow(clippy::cast_ptr_alignment)]
-fn unsafe_yyjson_get_next_non_container(val: *mut yyjson_val) -> *mut yyjson_val {
+pub(super) fn unsafe_yyjson_get_next_non_container(val: *mut yyjson_val) -> *mut yyjson_val {
     unsafe { (val.cast::<u8>().add(YYJSON_VAL_SIZE)).cast::<yyjson_val>() }
 }
 
-pub(crate) fn deserialize(
+/// Parse `data` into the per-interpreter buffer with the yyjson read flags
+/// `flg`. The document is valid until the next parse.
+#[inline(always)]
+pub(super) fn read_doc(
     data: &'static str,
     interpreter_state: *const crate::interpreter_state::InterpreterState,
-) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    flg: yyjson_read_flag,
+) -> Result<*mut yyjson_doc, DeserializeError<'static>> {
     assume!(!data.is_empty());
     let buffer_capacity = buffer_capacity_to_allocate(data.len());
 
@@ -87,6 +93,18 @@ pub(crate) fn deserialize(
             data,
         ));
     }
+    read_doc_in(data, buffer_ptr, actual_capacity, flg)
+}
+
+/// Parse `data` into `buffer_ptr`, which has `capacity` bytes, with the
+/// yyjson read flags `flg`. The document is valid as long as the buffer is.
+#[inline(always)]
+pub(super) fn read_doc_in(
+    data: &'static str,
+    buffer_ptr: *mut core::ffi::c_void,
+    capacity: usize,
+    flg: yyjson_read_flag,
+) -> Result<*mut yyjson_doc, DeserializeError<'static>> {
     let mut alloc = yyjson_alc {
         malloc: None,
         realloc: None,
@@ -94,7 +112,7 @@ pub(crate) fn deserialize(
         ctx: null_mut(),
     };
     unsafe {
-        yyjson_alc_pool_init(&raw mut alloc, buffer_ptr, actual_capacity);
+        yyjson_alc_pool_init(&raw mut alloc, buffer_ptr, capacity);
     }
 
     let mut err = yyjson_read_err {
@@ -107,6 +125,7 @@ pub(crate) fn deserialize(
         yyjson_read_opts(
             data.as_ptr().cast::<c_char>().cast_mut(),
             data.len(),
+            flg,
             &raw const alloc,
             &raw mut err,
         )
@@ -116,6 +135,17 @@ pub(crate) fn deserialize(
         let msg: Cow<str> = unsafe { core::ffi::CStr::from_ptr(err.msg).to_string_lossy() };
         return Err(DeserializeError::from_yyjson(msg, err.pos as i64, data));
     }
+    Ok(doc)
+}
+
+pub(crate) fn deserialize(
+    data: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
+    values: &mut usize,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    let doc = read_doc(data, interpreter_state, YYJSON_READ_NOFLAG)?;
+    *values = unsafe { (*doc).val_read };
     let val = yyjson_doc_get_root(doc);
     let pyval = {
         if !unsafe_yyjson_is_ctn(val) {
@@ -126,14 +156,14 @@ pub(crate) fn deserialize(
             let len = unsafe_yyjson_get_len(val);
             let pyval = nonnull!(ffi!(PyList_New(usize_to_isize(len))));
             if len > 0 {
-                populate_yy_array(pyval.as_ptr(), val, interpreter_state);
+                populate_yy_array(pyval.as_ptr(), val, interpreter_state, options);
             }
             pyval
         } else {
             let len = unsafe_yyjson_get_len(val);
             let pyval = nonnull!(ffi!(_PyDict_NewPresized(usize_to_isize(len))));
             if len > 0 {
-                populate_yy_object(pyval.as_ptr(), val, interpreter_state);
+                populate_yy_object(pyval.as_ptr(), val, interpreter_state, options);
             }
             pyval
         }
@@ -145,7 +175,7 @@ pub(crate) fn deserialize(
 /// Fast primitive parsing with direct tag dispatch
 /// Inlined for performance - handles string/number/bool/null
 #[inline(always)]
-fn parse_primitive(val: *mut yyjson_val) -> NonNull<crate::ffi::PyObject> {
+pub(super) fn parse_primitive(val: *mut yyjson_val) -> NonNull<crate::ffi::PyObject> {
     let tag = unsafe { (*val).tag as u8 };
     // Order by frequency: strings are most common in JSON
     if tag == TAG_STRING {
@@ -168,7 +198,7 @@ fn parse_primitive(val: *mut yyjson_val) -> NonNull<crate::ffi::PyObject> {
 }
 
 #[inline(always)]
-fn parse_yy_string(elem: *mut yyjson_val) -> NonNull<crate::ffi::PyObject> {
+pub(super) fn parse_yy_string(elem: *mut yyjson_val) -> NonNull<crate::ffi::PyObject> {
     PyStr::from_str(str_from_slice!(
         (*elem).uni.str_.cast::<u8>(),
         unsafe_yyjson_get_len(elem)
@@ -201,10 +231,11 @@ macro_rules! append_to_list {
 }
 
 #[inline(never)]
-fn populate_yy_array(
+pub(super) fn populate_yy_array(
     list: *mut crate::ffi::PyObject,
     elem: *mut yyjson_val,
     state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
 ) {
     unsafe {
         let len = unsafe_yyjson_get_len(elem);
@@ -222,13 +253,13 @@ fn populate_yy_array(
                     let pyval = ffi!(PyList_New(usize_to_isize(nested_len)));
                     append_to_list!(dptr, pyval);
                     if nested_len > 0 {
-                        populate_yy_array(pyval, val, state);
+                        populate_yy_array(pyval, val, state, options);
                     }
                 } else {
                     let pyval = ffi!(_PyDict_NewPresized(usize_to_isize(nested_len)));
                     append_to_list!(dptr, pyval);
                     if nested_len > 0 {
-                        populate_yy_object(pyval, val, state);
+                        populate_yy_object(pyval, val, state, options);
                     }
                 }
             } else {
@@ -242,10 +273,11 @@ fn populate_yy_array(
 }
 
 #[inline(never)]
-fn populate_yy_object(
+pub(super) fn populate_yy_object(
     dict: *mut crate::ffi::PyObject,
     elem: *mut yyjson_val,
     state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
 ) {
     unsafe {
         let len = unsafe_yyjson_get_len(elem);
@@ -254,12 +286,25 @@ fn populate_yy_object(
         let mut next_val = next_key.add(1);
         for _ in 0..len {
             let val = next_val;
+            if options.omit_null && is_yyjson_tag!(val, TAG_NULL) {
+                cold_path!();
+                next_key = unsafe_yyjson_get_next_non_container(val);
+                next_val = next_key.add(1);
+                continue;
+            }
             let pykey = {
                 let key_str = str_from_slice!(
                     (*next_key).uni.str_.cast::<u8>(),
                     unsafe_yyjson_get_len(next_key)
                 );
-                get_unicode_key(key_str, state)
+                match options
+                    .rename
+                    .as_ref()
+                    .and_then(|rename| rename.get(key_str))
+                {
+                    Some(renamed) => renamed,
+                    None => get_unicode_key(key_str, state),
+                }
             };
             if unsafe_yyjson_is_ctn(val) {
                 cold_path!();
@@ -270,13 +315,13 @@ fn populate_yy_object(
                     let pyval = ffi!(PyList_New(usize_to_isize(nested_len)));
                     pydict_setitem!(dict, pykey.as_ptr(), pyval);
                     if nested_len > 0 {
-                        populate_yy_array(pyval, val, state);
+                        populate_yy_array(pyval, val, state, options);
                     }
                 } else {
                     let pyval = ffi!(_PyDict_NewPresized(usize_to_isize(nested_len)));
                     pydict_setitem!(dict, pykey.as_ptr(), pyval);
                     if nested_len > 0 {
-                        populate_yy_object(pyval, val, state);
+                        populate_yy_object(pyval, val, state, options);
                     }
                 }
             } else {
@@ -289,3 +334,97 @@ fn populate_yy_object(
         }
     }
 }
+
+/// Parse `data` without reading its values.
+#[inline(never)]
+pub(crate) fn validate(
+    data: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+) -> Result<(), DeserializeError<'static>> {
+    read_doc(data, interpreter_state, YYJSON_READ_NOFLAG).map(|_| ())
+}
+
+/// Read `data`, an array of numbers or of arrays of the same shape, into its
+/// shape and its numbers in C order.
+#[cold]
+#[inline(never)]
+pub(crate) fn deserialize_numbers(
+    data: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+) -> Result<(Vec<usize>, Vec<Number>), DeserializeError<'static>> {
+    let doc = read_doc(data, interpreter_state, YYJSON_READ_NOFLAG)?;
+    let root = yyjson_doc_get_root(doc);
+    if !is_yyjson_tag!(root, TAG_ARRAY) {
+        return Err(DeserializeError::invalid(Cow::Borrowed(
+            "loads_numpy() document must be an array",
+        )));
+    }
+    let mut shape = Vec::new();
+    let mut val = root;
+    while is_yyjson_tag!(val, TAG_ARRAY) {
+        let len = unsafe_yyjson_get_len(val);
+        shape.push(len);
+        if len == 0 {
+            break;
+        }
+        val = unsafe_yyjson_get_first(val);
+    }
+    let mut numbers = Vec::with_capacity(shape.iter().product());
+    read_numbers(root, &shape, &mut numbers)?;
+    Ok((shape, numbers))
+}
+
+/// A number or bool of a document read by `deserialize_numbers()`.
+#[derive(Clone, Copy)]
+pub(crate) enum Number {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+}
+
+fn read_numbers(
+    elem: *mut yyjson_val,
+    shape: &[usize],
+    numbers: &mut Vec<Number>,
+) -> Result<(), DeserializeError<'static>> {
+    if !is_yyjson_tag!(elem, TAG_ARRAY) || unsafe_yyjson_get_len(elem) != shape[0] {
+        return Err(DeserializeError::invalid(Cow::Borrowed(
+            "loads_numpy() array is not rectangular",
+        )));
+    }
+    let mut next = unsafe_yyjson_get_first(elem);
+    for _ in 0..shape[0] {
+        let val = next;
+        if unsafe_yyjson_is_ctn(val) {
+            next = unsafe_yyjson_get_next_container(val);
+            if shape.len() == 1 {
+                return Err(DeserializeError::invalid(Cow::Borrowed(
+                    "loads_numpy() array is not rectangular",
+                )));
+            }
+            read_numbers(val, &shape[1..], numbers)?;
+        } else {
+            next = unsafe_yyjson_get_next_non_container(val);
+            if shape.len() != 1 {
+                return Err(DeserializeError::invalid(Cow::Borrowed(
+                    "loads_numpy() array is not rectangular",
+                )));
+            }
+            let number = match unsafe { (*val).tag as u8 } {
+                TAG_INT64 => Number::I64(unsafe { (*val).uni.i64_ }),
+                TAG_UINT64 => Number::U64(unsafe { (*val).uni.u64_ }),
+                TAG_DOUBLE => Number::F64(unsafe { (*val).uni.f64_ }),
+                TAG_TRUE => Number::Bool(true),
+                TAG_FALSE => Number::Bool(false),
+                _ => {
+                    return Err(DeserializeError::invalid(Cow::Borrowed(
+                        "loads_numpy() array item is not a number or bool",
+                    )));
+                }
+            };
+            numbers.push(number);
+        }
+    }
+    Ok(())
+}
@@ -2,22 +2,7 @@
 // Copyright ijl (2019-2025)
 
 use crate::str::PyStr;
-
-/// FNV-1a 64-bit hash - simple, fast, good distribution for short strings
-/// This is significantly faster than xxhash for short strings (< 64 bytes)
-/// because it has no setup cost and is branch-free
-#[inline(always)]
-pub(crate) fn fnv1a_hash(data: &[u8]) -> u64 {
-    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
-    const FNV_PRIME: u64 = 0x100000001b3;
-
-    let mut hash = FNV_OFFSET;
-    for &byte in data {
-        hash ^= byte as u64;
-        hash = hash.wrapping_mul(FNV_PRIME);
-    }
-    hash
-}
+use crate::util::fnv1a_hash;
 
 /// Simple direct-mapped cache entry
 /// Stores a PyStr with its hash for collision detection
@@ -108,8 +93,9 @@ impl Default for KeyCache {
     }
 }
 
-impl Drop for KeyCache {
-    fn drop(&mut self) {
+impl KeyCache {
+    /// Release every cached key.
+    pub fn clear(&mut self) {
         for entry in &mut self.entries {
             if !entry.ptr.is_null() {
                 ffi!(Py_DECREF(entry.ptr));
@@ -118,3 +104,9 @@ impl Drop for KeyCache {
         }
     }
 }
+
+impl Drop for KeyCache {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
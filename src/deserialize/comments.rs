@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `loads(allow_comments=True)` reads JSON with comments, as in `.jsonc`
+//! files. Comments are blanked out of a copy of the input before it is
+//! parsed, so that the parsers do not check for them, and so that a position
+//! in the copy is the same position in the input.
+
+/// Replace the `//` and `/* */` comments outside of strings in `data` with
+/// spaces, keeping line breaks. Returns `None` if there are no comments, or
+/// the position of a `/*` that is not closed.
+pub(crate) fn strip_comments(data: &[u8]) -> Result<Option<Vec<u8>>, usize> {
+    let mut stripped: Option<Vec<u8>> = None;
+    let mut idx = 0;
+    while idx < data.len() {
+        match (data[idx], data.get(idx + 1)) {
+            (b'"', _) => {
+                idx += 1;
+                while idx < data.len() {
+                    match data[idx] {
+                        b'\\' => idx += 2,
+                        b'"' => {
+                            idx += 1;
+                            break;
+                        }
+                        _ => idx += 1,
+                    }
+                }
+            }
+            (b'/', Some(b'/')) => {
+                let end = data[idx..]
+                    .iter()
+                    .position(|&ch| ch == b'\n' || ch == b'\r')
+                    .map_or(data.len(), |len| idx + len);
+                blank(stripped.get_or_insert_with(|| data.to_vec()), idx, end);
+                idx = end;
+            }
+            (b'/', Some(b'*')) => {
+                let end = match data[idx + 2..].windows(2).position(|pair| pair == b"*/") {
+                    Some(len) => idx + len + 4,
+                    None => return Err(idx),
+                };
+                blank(stripped.get_or_insert_with(|| data.to_vec()), idx, end);
+                idx = end;
+            }
+            _ => idx += 1,
+        }
+    }
+    Ok(stripped)
+}
+
+fn blank(data: &mut [u8], start: usize, end: usize) {
+    for ch in &mut data[start..end] {
+        if *ch != b'\n' && *ch != b'\r' {
+            *ch = b' ';
+        }
+    }
+}
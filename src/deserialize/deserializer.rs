@@ -1,29 +1,165 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025), Aarni Koskela (2021), Eric Jolibois (2021)
 
-use crate::deserialize::DeserializeError;
-use crate::deserialize::utf8::read_input_to_buf;
+use crate::deserialize::comments::strip_comments;
+use crate::deserialize::utf8::{read_chunks_to_buf, read_encoded_to_buf, read_input_to_buf};
+use crate::deserialize::verify::verify_hmac_sha256;
+use crate::deserialize::{DeserializeError, DeserializeOptions, TypePlan};
 // EMPTY_UNICODE now accessed via typeref::get_empty_unicode()
 use core::ptr::NonNull;
+use core::sync::atomic::Ordering;
+use std::borrow::Cow;
 
 pub(crate) fn deserialize(
     ptr: *mut crate::ffi::PyObject,
+    options: &DeserializeOptions,
 ) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
     debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let ob_type = ob_type!(ptr);
+    if is_type!(ob_type, crate::typeref::list_type_ptr())
+        || is_type!(ob_type, crate::typeref::tuple_type_ptr())
+        || options.decodes_input()
+    {
+        cold_path!();
+        return deserialize_copied(ptr, options);
+    }
+    let buffer = read_input_to_buf(ptr)?;
+    let mut values = 0;
+    deserialize_buffer(buffer, options, &mut values)
+}
+
+/// Deserialize a document that may be copied to be read: one split across
+/// a `list` or `tuple` of chunks, or one decoded by `read_encoded_to_buf()`.
+#[cold]
+#[inline(never)]
+fn deserialize_copied(
+    ptr: *mut crate::ffi::PyObject,
+    options: &DeserializeOptions,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    let joined = if options.decodes_input() {
+        read_encoded_to_buf(ptr, options)?
+    } else {
+        read_chunks_to_buf(ptr)?
+    };
+    let buffer: &'static [u8] =
+        unsafe { core::slice::from_raw_parts(joined.as_ptr(), joined.len()) };
+    let mut values = 0;
+    // the error may refer to the joined buffer, which is dropped on return
+    deserialize_buffer(buffer, options, &mut values).map_err(DeserializeError::into_owned)
+}
+
+/// Deserialize as `deserialize()` does and register a `list` or `dict`
+/// result so that `dumps()` of it can write `ptr`'s contents unchanged.
+pub(crate) fn deserialize_tracked(
+    ptr: *mut crate::ffi::PyObject,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let buffer = read_input_to_buf(ptr)?;
+    let mut values = 1;
+    let obj = deserialize_buffer(buffer, &DeserializeOptions::default(), &mut values)?;
+    let ob_type = ob_type!(obj.as_ptr());
+    if is_type!(ob_type, crate::typeref::list_type_ptr())
+        || is_type!(ob_type, crate::typeref::dict_type_ptr())
+    {
+        unsafe {
+            (*crate::runtime::current_state())
+                .decoded
+                .record(obj.as_ptr(), buffer, values);
+        }
+    }
+    Ok(obj)
+}
+
+/// Deserialize as `deserialize()` does, building the objects of `plan`.
+pub(crate) fn deserialize_typed(
+    ptr: *mut crate::ffi::PyObject,
+    options: &DeserializeOptions,
+    plan: &TypePlan,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let buffer = if options.decodes_input() {
+        read_encoded_to_buf(ptr, options)?
+    } else {
+        Cow::Borrowed(read_input_to_buf(ptr)?)
+    };
+    let copied = matches!(buffer, Cow::Owned(_));
+    let buffer_str: &'static str = unsafe {
+        core::str::from_utf8_unchecked(core::slice::from_raw_parts(buffer.as_ptr(), buffer.len()))
+    };
+    let interpreter_state = unsafe { crate::runtime::current_state() };
+    crate::deserialize::backend::deserialize_typed(buffer_str, interpreter_state, options, plan)
+        .map_err(|err| if copied { err.into_owned() } else { err })
+}
+
+/// Deserialize as `deserialize()` does if `signature` is the HMAC-SHA256
+/// with `key` of the bytes that are parsed.
+pub(crate) fn deserialize_verified(
+    ptr: *mut crate::ffi::PyObject,
+    signature: &[u8; 32],
+    key: &[u8],
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let buffer = read_input_to_buf(ptr)?;
+    if !verify_hmac_sha256(buffer, key, signature) {
+        return Err(DeserializeError::invalid(Cow::Borrowed(
+            "verify_and_loads() signature does not match body",
+        )));
+    }
+    let mut values = 0;
+    deserialize_buffer(buffer, &DeserializeOptions::default(), &mut values)
+}
 
+/// Deserialize `buffer`, a value within a document read by
+/// `read_input_to_buf()`, as `deserialize()` does.
+pub(crate) fn deserialize_slice(
+    buffer: &'static [u8],
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    let mut values = 0;
+    deserialize_buffer(buffer, &DeserializeOptions::default(), &mut values)
+}
+
+/// Read the document `ptr` and write it again as `bytes`, indented by
+/// `indent` spaces or on one line if `None`.
+pub(crate) fn reformat(
+    ptr: *mut crate::ffi::PyObject,
+    indent: Option<usize>,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let buffer = read_input_to_buf(ptr)?;
+    let buffer_str = unsafe { core::str::from_utf8_unchecked(buffer) };
+    let interpreter_state = unsafe { crate::runtime::current_state() };
+    crate::deserialize::backend::reformat(buffer_str, interpreter_state, indent)
+}
+
+/// Whether `ptr` is a valid document, without deserializing it.
+pub(crate) fn validate(ptr: *mut crate::ffi::PyObject) -> Result<(), DeserializeError<'static>> {
+    debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
+    let buffer = read_input_to_buf(ptr)?;
+    let buffer_str = unsafe { core::str::from_utf8_unchecked(buffer) };
+    let interpreter_state = unsafe { crate::runtime::current_state() };
+    crate::deserialize::backend::validate(buffer_str, interpreter_state)
+}
+
+/// `values` is set to the number of JSON values read, keys included, unless
+/// the document is trivial.
+#[inline(always)]
+fn deserialize_buffer(
+    buffer: &'static [u8],
+    options: &DeserializeOptions,
+    values: &mut usize,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
     // Cache interpreter state pointer once at the start of deserialization
     // This avoids repeated thread-local lookups during deserialization
-    let interpreter_state = unsafe { crate::interpreter_state::get_current_state() };
+    let interpreter_state = unsafe { crate::runtime::current_state() };
     debug_assert!(!interpreter_state.is_null());
 
-    let buffer = read_input_to_buf(ptr)?;
     debug_assert!(!buffer.is_empty());
 
     if buffer.len() == 2 {
         cold_path!();
         if buffer == b"[]" {
             return Ok(nonnull!(ffi!(PyList_New(0))));
-        } else if buffer == b"{}" {
+        } else if buffer == b"{}" && options.object_pairs_hook.is_none() {
             return Ok(nonnull!(ffi!(PyDict_New())));
         } else if buffer == b"\"\"" {
             unsafe {
@@ -36,5 +172,66 @@ pub(crate) fn deserialize(
 
     let buffer_str = unsafe { core::str::from_utf8_unchecked(buffer) };
 
-    crate::deserialize::backend::deserialize(buffer_str, interpreter_state)
+    if options.allow_comments && !options.json5 {
+        cold_path!();
+        return deserialize_commented(buffer_str, interpreter_state, options, values);
+    }
+    parse(buffer_str, interpreter_state, options, values)
+}
+
+#[inline(always)]
+fn parse(
+    buffer_str: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
+    values: &mut usize,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    if options.is_extended() || unsafe { (*interpreter_state).compat_mode.load(Ordering::Relaxed) }
+    {
+        cold_path!();
+        return crate::deserialize::backend::deserialize_extended(
+            buffer_str,
+            interpreter_state,
+            options,
+            values,
+        );
+    }
+    crate::deserialize::backend::deserialize(buffer_str, interpreter_state, options, values)
+}
+
+/// Parse `buffer_str` with its comments blanked out. Errors refer to
+/// `buffer_str`, which has the same positions.
+#[cold]
+#[inline(never)]
+fn deserialize_commented(
+    buffer_str: &'static str,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    options: &DeserializeOptions,
+    values: &mut usize,
+) -> Result<NonNull<crate::ffi::PyObject>, DeserializeError<'static>> {
+    let stripped = match strip_comments(buffer_str.as_bytes()) {
+        Ok(Some(stripped)) => stripped,
+        Ok(None) => return parse(buffer_str, interpreter_state, options, values),
+        Err(pos) => {
+            return Err(DeserializeError::from_yyjson(
+                Cow::Borrowed("unclosed multiline comment"),
+                pos as i64,
+                buffer_str,
+            ));
+        }
+    };
+    // only whole comments are blanked, so the copy is valid UTF-8
+    let stripped_str: &'static str = unsafe {
+        core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+            stripped.as_ptr(),
+            stripped.len(),
+        ))
+    };
+    parse(stripped_str, interpreter_state, options, values).map_err(|err| DeserializeError {
+        message: Cow::Owned(err.message.into_owned()),
+        data: err.data.map(|_| Cow::Borrowed(buffer_str)),
+        pos: err.pos,
+        code: err.code,
+        raised: err.raised,
+    })
 }
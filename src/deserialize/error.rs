@@ -1,12 +1,18 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2022-2025), Eric Jolibois (2021)
 
+use crate::util::INVALID_STR;
 use std::borrow::Cow;
 
 pub(crate) struct DeserializeError<'a> {
     pub message: Cow<'a, str>,
-    pub data: Option<&'a str>,
+    pub data: Option<Cow<'a, str>>,
     pub pos: i64,
+    /// The `code` attribute of the `JSONDecodeError`, if not `None`.
+    pub code: Option<&'static str>,
+    /// Python code called while reading, e.g., `object_pairs_hook`, raised
+    /// and its exception is already set.
+    pub raised: bool,
 }
 
 impl<'a> DeserializeError<'a> {
@@ -16,6 +22,8 @@ impl<'a> DeserializeError<'a> {
             message: message,
             data: None,
             pos: 0,
+            code: None,
+            raised: false,
         }
     }
 
@@ -23,8 +31,19 @@ impl<'a> DeserializeError<'a> {
     pub fn from_yyjson(message: Cow<'a, str>, pos: i64, data: &'a str) -> Self {
         DeserializeError {
             message: message,
-            data: Some(data),
+            data: Some(Cow::Borrowed(data)),
             pos: pos,
+            code: None,
+            raised: false,
+        }
+    }
+
+    /// The exception set by Python code called while reading.
+    #[cold]
+    pub fn raised() -> Self {
+        DeserializeError {
+            raised: true,
+            ..DeserializeError::invalid(Cow::Borrowed(""))
         }
     }
 
@@ -33,8 +52,41 @@ impl<'a> DeserializeError<'a> {
     #[cfg_attr(feature = "optimize", optimize(size))]
     pub fn pos(&self) -> i64 {
         match self.data {
-            Some(as_str) => as_str[0..self.pos as usize].chars().count() as i64,
+            Some(ref as_str) => as_str[0..self.pos as usize].chars().count() as i64,
             None => 0,
         }
     }
+
+    /// Copy the message and data so that the error outlives the input.
+    #[cold]
+    pub fn into_owned(self) -> DeserializeError<'static> {
+        DeserializeError {
+            message: Cow::Owned(self.message.into_owned()),
+            data: self.data.map(|data| Cow::Owned(data.into_owned())),
+            pos: self.pos,
+            code: self.code,
+            raised: self.raised,
+        }
+    }
+
+    /// Set the code `loads(strict=True)` reports for the message.
+    #[cold]
+    pub fn with_strict_code(mut self) -> Self {
+        self.code = Some(match &*self.message {
+            "duplicate key" => "duplicate_key",
+            "invalid high surrogate in string"
+            | "no low surrogate in string"
+            | "invalid low surrogate in string" => "lone_surrogate",
+            "number with leading zero is not allowed" => "leading_zero",
+            INVALID_STR => "invalid_utf8",
+            "unexpected control character in string" => "control_character",
+            "invalid escaped character in string" | "invalid escaped sequence in string" => {
+                "invalid_escape"
+            }
+            "number is infinity when parsed as double" => "number_out_of_range",
+            "array and object recursion depth exceeded" => "depth_exceeded",
+            _ => "syntax",
+        });
+        self
+    }
 }
@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `loads(extended_json=True)`, MongoDB Extended JSON v2 of either mode.
+//!
+//! The wrappers `dumps(extended_json=)` writes are read as the values they
+//! wrap, as are `"$uuid"` and the legacy `"$binary"` with `"$type"`. Other
+//! wrappers, such as `"$oid"`, are left as objects.
+
+use crate::deserialize::DeserializeError;
+use crate::deserialize::scalar::{parse_datetime, parse_uuid};
+use crate::ffi::PyObject;
+use crate::serialize::{civil_from_days, days_since_epoch};
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+
+use core::ptr::{NonNull, null_mut};
+use std::borrow::Cow;
+
+const MICROSECONDS_PER_DAY: i64 = 86_400_000_000;
+
+fn invalid(key: &str) -> DeserializeError<'static> {
+    DeserializeError::invalid(Cow::Owned(format!("extended JSON {key} is invalid")))
+}
+
+fn as_str(ptr: *mut PyObject) -> Option<&'static str> {
+    if is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        unsafe { PyStr::from_ptr_unchecked(ptr).to_str() }
+    } else {
+        None
+    }
+}
+
+/// The value of the member `name` of the `dict` `ptr`, or null.
+fn member(ptr: *mut PyObject, name: &str) -> *mut PyObject {
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut val: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(ptr, &mut pos, &mut key, &mut val)) == 1 {
+        if as_str(key) == Some(name) {
+            return val;
+        }
+    }
+    null_mut()
+}
+
+/// The bytes of the standard, padded base64 `text`.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    fn sextet(byte: u8) -> Option<u32> {
+        match byte {
+            b'A'..=b'Z' => Some(u32::from(byte - b'A')),
+            b'a'..=b'z' => Some(u32::from(byte - b'a') + 26),
+            b'0'..=b'9' => Some(u32::from(byte - b'0') + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+    let text = text.as_bytes();
+    if text.len() % 4 != 0 {
+        return None;
+    }
+    let padding = text.iter().rev().take_while(|&&byte| byte == b'=').count();
+    if padding > 2 {
+        return None;
+    }
+    let mut ret = Vec::with_capacity(text.len() / 4 * 3);
+    for (idx, chunk) in text.chunks(4).enumerate() {
+        let last = idx == text.len() / 4 - 1;
+        let mut group = 0;
+        for (pos, &byte) in chunk.iter().enumerate() {
+            let val = if last && pos >= 4 - padding {
+                0
+            } else {
+                sextet(byte)?
+            };
+            group = (group << 6) | val;
+        }
+        let len = if last { 3 - padding } else { 3 };
+        ret.extend_from_slice(&group.to_be_bytes()[1..=len]);
+    }
+    Some(ret)
+}
+
+/// The objects built while reviving, looked up on first use.
+struct Reviver {
+    state: *const crate::interpreter_state::InterpreterState,
+    utc: *mut PyObject,
+    uuid_kwnames: *mut PyObject,
+}
+
+impl Drop for Reviver {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.utc));
+        ffi!(Py_XDECREF(self.uuid_kwnames));
+    }
+}
+
+impl Reviver {
+    /// A UTC `datetime` of `microseconds` since the epoch.
+    fn datetime(&mut self, microseconds: i64) -> Result<*mut PyObject, DeserializeError<'static>> {
+        let days = microseconds.div_euclid(MICROSECONDS_PER_DAY);
+        let time = microseconds.rem_euclid(MICROSECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(i128::from(days));
+        if !(1..=9999).contains(&year) {
+            return Err(DeserializeError::invalid(Cow::Borrowed(
+                "extended JSON $date is out of range",
+            )));
+        }
+        if self.utc.is_null() {
+            let module = ffi!(PyImport_ImportModule(c"datetime".as_ptr()));
+            if module.is_null() {
+                return Err(DeserializeError::raised());
+            }
+            let timezone = ffi!(PyObject_GetAttrString(module, c"timezone".as_ptr()));
+            ffi!(Py_DECREF(module));
+            if timezone.is_null() {
+                return Err(DeserializeError::raised());
+            }
+            self.utc = ffi!(PyObject_GetAttrString(timezone, c"utc".as_ptr()));
+            ffi!(Py_DECREF(timezone));
+            if self.utc.is_null() {
+                return Err(DeserializeError::raised());
+            }
+        }
+        let args = [
+            year as i64,
+            i64::from(month),
+            i64::from(day),
+            time / 3_600_000_000,
+            time / 60_000_000 % 60,
+            time / 1_000_000 % 60,
+            time % 1_000_000,
+        ]
+        .map(|val| ffi!(PyLong_FromLongLong(val)));
+        let args = [
+            args[0], args[1], args[2], args[3], args[4], args[5], args[6], self.utc,
+        ];
+        let ret = ffi!(PyObject_Vectorcall(
+            crate::typeref::get_datetime_type_from_state(self.state).cast::<PyObject>(),
+            args.as_ptr(),
+            7 + 1,
+            null_mut()
+        ));
+        for &arg in &args[..7] {
+            ffi!(Py_DECREF(arg));
+        }
+        if ret.is_null() {
+            return Err(DeserializeError::raised());
+        }
+        Ok(ret)
+    }
+
+    /// A `UUID` of the 32 hexadecimal digits `hex`, by `UUID(int=)`.
+    fn uuid(&mut self, hex: &[u8]) -> Result<*mut PyObject, DeserializeError<'static>> {
+        let mut buf = [0u8; 33];
+        buf[..32].copy_from_slice(hex);
+        let int = ffi!(PyLong_FromString(
+            buf.as_ptr().cast::<core::ffi::c_char>(),
+            null_mut(),
+            16
+        ));
+        if int.is_null() {
+            return Err(DeserializeError::raised());
+        }
+        if self.uuid_kwnames.is_null() {
+            self.uuid_kwnames = ffi!(PyTuple_New(1));
+            let name = ffi!(PyUnicode_InternFromString(c"int".as_ptr()));
+            ffi!(PyTuple_SET_ITEM(self.uuid_kwnames, 0, name));
+        }
+        let ret = ffi!(PyObject_Vectorcall(
+            crate::typeref::get_uuid_type_from_state(self.state).cast::<PyObject>(),
+            &raw const int,
+            0,
+            self.uuid_kwnames
+        ));
+        ffi!(Py_DECREF(int));
+        if ret.is_null() {
+            return Err(DeserializeError::raised());
+        }
+        Ok(ret)
+    }
+
+    /// `bytes` of the base64 `data`, or a `UUID` if `sub_type` is `"04"`.
+    fn binary(
+        &mut self,
+        data: *mut PyObject,
+        sub_type: *mut PyObject,
+    ) -> Result<*mut PyObject, DeserializeError<'static>> {
+        let (Some(data), Some(sub_type)) = (as_str(data), as_str(sub_type)) else {
+            return Err(invalid("$binary"));
+        };
+        let Some(data) = decode_base64(data) else {
+            return Err(invalid("$binary"));
+        };
+        if sub_type == "04" {
+            let Ok(data) = <[u8; 16]>::try_from(data.as_slice()) else {
+                return Err(invalid("$binary"));
+            };
+            let hex = format!("{:032x}", u128::from_be_bytes(data));
+            return self.uuid(hex.as_bytes());
+        }
+        if sub_type.is_empty() || sub_type.len() > 2 || u8::from_str_radix(sub_type, 16).is_err() {
+            return Err(invalid("$binary"));
+        }
+        Ok(ffi!(PyBytes_FromStringAndSize(
+            data.as_ptr().cast::<core::ffi::c_char>(),
+            usize_to_isize(data.len())
+        )))
+    }
+
+    /// The value of the `"$date"` member `val`: an ISO-8601 string, UTC if
+    /// it has no offset, or milliseconds since the epoch, which a
+    /// `"$numberLong"` has already been read as.
+    fn date(&mut self, val: *mut PyObject) -> Result<*mut PyObject, DeserializeError<'static>> {
+        if let Some(text) = as_str(val) {
+            let Some(dt) = parse_datetime(text.as_bytes()) else {
+                return Err(invalid("$date"));
+            };
+            let days = days_since_epoch(
+                i64::from(dt.date.year),
+                i64::from(dt.date.month),
+                i64::from(dt.date.day),
+            );
+            let seconds =
+                i64::from(dt.hour) * 3_600 + i64::from(dt.minute) * 60 + i64::from(dt.second)
+                    - i64::from(dt.offset.unwrap_or(0));
+            return self.datetime(
+                days * MICROSECONDS_PER_DAY + seconds * 1_000_000 + i64::from(dt.microsecond),
+            );
+        }
+        if !is_type!(ob_type!(val), crate::typeref::int_type_ptr()) {
+            return Err(invalid("$date"));
+        }
+        let milliseconds = ffi!(PyLong_AsLongLong(val));
+        if milliseconds == -1 && !ffi!(PyErr_Occurred()).is_null() {
+            ffi!(PyErr_Clear());
+            return Err(invalid("$date"));
+        }
+        match milliseconds.checked_mul(1_000) {
+            Some(microseconds) => self.datetime(microseconds),
+            None => Err(invalid("$date")),
+        }
+    }
+
+    /// A new reference to the value of the wrapper `dict` `ptr`, or `None`
+    /// if it is not one.
+    fn unwrap(
+        &mut self,
+        ptr: *mut PyObject,
+    ) -> Result<Option<*mut PyObject>, DeserializeError<'static>> {
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut val: *mut PyObject = null_mut();
+        if ffi!(PyDict_Next(ptr, &mut pos, &mut key, &mut val)) != 1 {
+            return Ok(None);
+        }
+        let Some(key) = as_str(key) else {
+            return Ok(None);
+        };
+        if ffi!(Py_SIZE(ptr)) == 2 {
+            // `{"$binary": <base64>, "$type": <hex>}`
+            let data = member(ptr, "$binary");
+            let sub_type = member(ptr, "$type");
+            if data.is_null() || sub_type.is_null() {
+                return Ok(None);
+            }
+            return self.binary(data, sub_type).map(Some);
+        }
+        if ffi!(Py_SIZE(ptr)) != 1 {
+            return Ok(None);
+        }
+        let ret = match key {
+            "$numberInt" | "$numberLong" => {
+                let parsed = as_str(val).and_then(|text| {
+                    if key == "$numberInt" {
+                        text.parse::<i32>().ok().map(i64::from)
+                    } else {
+                        text.parse::<i64>().ok()
+                    }
+                });
+                match parsed {
+                    Some(parsed) => ffi!(PyLong_FromLongLong(parsed)),
+                    None => return Err(invalid(key)),
+                }
+            }
+            "$numberDouble" => {
+                let parsed = match as_str(val) {
+                    Some("Infinity") => Some(f64::INFINITY),
+                    Some("-Infinity") => Some(f64::NEG_INFINITY),
+                    Some("NaN") => Some(f64::NAN),
+                    Some(text) if text.bytes().all(|b| b"+-.0123456789eE".contains(&b)) => {
+                        text.parse::<f64>().ok()
+                    }
+                    _ => None,
+                };
+                match parsed {
+                    Some(parsed) => ffi!(PyFloat_FromDouble(parsed)),
+                    None => return Err(invalid(key)),
+                }
+            }
+            "$numberDecimal" => {
+                if as_str(val).is_none() {
+                    return Err(invalid(key));
+                }
+                let decimal = crate::typeref::get_decimal_type();
+                if decimal.is_null() {
+                    return Err(DeserializeError::raised());
+                }
+                let ret = ffi!(PyObject_CallFunctionObjArgs(
+                    decimal,
+                    val,
+                    null_mut::<PyObject>()
+                ));
+                if ret.is_null() {
+                    ffi!(PyErr_Clear());
+                    return Err(invalid(key));
+                }
+                ret
+            }
+            "$date" => self.date(val)?,
+            "$binary" => {
+                if !is_type!(ob_type!(val), crate::typeref::dict_type_ptr())
+                    || ffi!(Py_SIZE(val)) != 2
+                {
+                    return Err(invalid(key));
+                }
+                let data = member(val, "base64");
+                let sub_type = member(val, "subType");
+                if data.is_null() || sub_type.is_null() {
+                    return Err(invalid(key));
+                }
+                self.binary(data, sub_type)?
+            }
+            "$uuid" => match as_str(val).and_then(|text| parse_uuid(text.as_bytes())) {
+                Some(hex) => self.uuid(&hex)?,
+                None => return Err(invalid(key)),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(ret))
+    }
+
+    /// Revive the arrays and objects within `ptr` in place and return a new
+    /// reference to the value `ptr` is read as, if it is a wrapper.
+    fn revive(
+        &mut self,
+        ptr: *mut PyObject,
+    ) -> Result<Option<*mut PyObject>, DeserializeError<'static>> {
+        if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) {
+            for idx in 0..ffi!(Py_SIZE(ptr)) {
+                let item = unsafe { crate::ffi::PyList_GET_ITEM(ptr, idx) };
+                if let Some(revived) = self.revive(item)? {
+                    ffi!(PyList_SetItem(ptr, idx, revived));
+                }
+            }
+            return Ok(None);
+        }
+        if !is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr()) {
+            return Ok(None);
+        }
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut value: *mut PyObject = null_mut();
+        while ffi!(PyDict_Next(ptr, &mut pos, &mut key, &mut value)) == 1 {
+            if let Some(revived) = self.revive(value)? {
+                // replacing the value of a key does not change the size of the
+                // dict, so iteration continues
+                ffi!(PyDict_SetItem(ptr, key, revived));
+                ffi!(Py_DECREF(revived));
+            }
+        }
+        self.unwrap(ptr)
+    }
+}
+
+/// Replace each Extended JSON wrapper object of the document by the value it
+/// wraps, for `loads(extended_json=True)`. `ptr` is consumed.
+pub(crate) fn revive_extended_json(
+    ptr: NonNull<PyObject>,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let mut reviver = Reviver {
+        state: unsafe { crate::runtime::current_state() },
+        utc: null_mut(),
+        uuid_kwnames: null_mut(),
+    };
+    match reviver.revive(ptr.as_ptr()) {
+        Ok(None) => Ok(ptr),
+        Ok(Some(revived)) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Ok(nonnull!(revived))
+        }
+        Err(err) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Err(err)
+        }
+    }
+}
@@ -4,10 +4,34 @@
 mod backend;
 #[cfg(not(Py_GIL_DISABLED))]
 pub(crate) mod cache;
+mod comments;
 mod deserializer;
 mod error;
+mod extended_json;
+mod msgpack;
+mod numpy;
+mod object_hook;
+mod options;
 mod pyobject;
+mod rename;
+mod scalar;
+mod tagged;
+mod typed;
 mod utf8;
+mod verify;
 
-pub(crate) use deserializer::deserialize;
+pub(crate) use deserializer::{
+    deserialize, deserialize_slice, deserialize_tracked, deserialize_typed, deserialize_verified,
+    reformat, validate,
+};
 pub(crate) use error::DeserializeError;
+pub(crate) use extended_json::revive_extended_json;
+pub(crate) use msgpack::deserialize_msgpack;
+pub(crate) use numpy::deserialize_numpy;
+pub(crate) use object_hook::apply_object_hook;
+pub(crate) use options::{DeserializeOptions, DuplicateKeys, IntOverflow};
+pub(crate) use rename::KeyRename;
+pub(crate) use tagged::revive_tagged;
+pub(crate) use typed::{Kind, Shape, TypePlan};
+pub(crate) use utf8::{read_bytes_or_str, read_input_to_buf};
+pub(crate) use verify::{Sha256, parse_signature};
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.loads_msgpack()`, MessagePack to the objects `loads()` returns.
+//!
+//! Map keys must be strings and are cached as `loads()` caches object keys.
+//! Binary values are read as `bytes`. Extension types are not supported.
+
+use crate::deserialize::DeserializeError;
+use crate::deserialize::pyobject::{
+    get_unicode_key, parse_f64, parse_false, parse_i64, parse_none, parse_true, parse_u64,
+};
+use crate::deserialize::utf8::bytes_like_to_buf;
+use crate::ffi::PyObject;
+use crate::interpreter_state::InterpreterState;
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+use core::ptr::NonNull;
+use std::borrow::Cow;
+
+const RECURSION_LIMIT: usize = 1024;
+
+fn error(message: &str, pos: usize) -> DeserializeError<'static> {
+    DeserializeError::invalid(Cow::Owned(format!(
+        "loads_msgpack() {message} at byte {pos}"
+    )))
+}
+
+struct Reader {
+    data: &'static [u8],
+    pos: usize,
+    state: *const InterpreterState,
+}
+
+impl Reader {
+    fn take(&mut self, len: usize) -> Result<&'static [u8], DeserializeError<'static>> {
+        if self.data.len() - self.pos < len {
+            return Err(error("data ends within a value", self.data.len()));
+        }
+        let ret = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(ret)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DeserializeError<'static>> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DeserializeError<'static>> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DeserializeError<'static>> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DeserializeError<'static>> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// The length of an array or map of `len` values, which must each be
+    /// at least a byte of the remaining data.
+    fn container_len(&self, len: usize, values: usize) -> Result<usize, DeserializeError<'static>> {
+        if len.saturating_mul(values) > self.data.len() - self.pos {
+            return Err(error("data ends within a value", self.data.len()));
+        }
+        Ok(len)
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<&'static str, DeserializeError<'static>> {
+        let start = self.pos;
+        core::str::from_utf8(self.take(len)?).map_err(|_| error("str is not valid UTF-8", start))
+    }
+
+    /// Read a value, returning a new reference.
+    fn read(&mut self, depth: usize) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        let start = self.pos;
+        let marker = self.read_u8()?;
+        let len = match marker {
+            0x00..=0x7f => return Ok(parse_u64(u64::from(marker))),
+            0xe0..=0xff => return Ok(parse_i64(i64::from(marker as i8))),
+            0xc0 => return Ok(parse_none()),
+            0xc2 => return Ok(parse_false()),
+            0xc3 => return Ok(parse_true()),
+            0xcc => return Ok(parse_u64(u64::from(self.read_u8()?))),
+            0xcd => return Ok(parse_u64(u64::from(self.read_u16()?))),
+            0xce => return Ok(parse_u64(u64::from(self.read_u32()?))),
+            0xcf => return Ok(parse_u64(self.read_u64()?)),
+            0xd0 => return Ok(parse_i64(i64::from(self.read_u8()? as i8))),
+            0xd1 => return Ok(parse_i64(i64::from(self.read_u16()? as i16))),
+            0xd2 => return Ok(parse_i64(i64::from(self.read_u32()? as i32))),
+            0xd3 => return Ok(parse_i64(self.read_u64()? as i64)),
+            0xca => return Ok(parse_f64(f64::from(f32::from_bits(self.read_u32()?)))),
+            0xcb => return Ok(parse_f64(f64::from_bits(self.read_u64()?))),
+            0xa0..=0xbf => usize::from(marker & 0x1f),
+            0xd9 | 0xc4 => usize::from(self.read_u8()?),
+            0xda | 0xc5 | 0xdc | 0xde => usize::from(self.read_u16()?),
+            0xdb | 0xc6 | 0xdd | 0xdf => self.read_u32()? as usize,
+            0x90..=0x9f => usize::from(marker & 0x0f),
+            0x80..=0x8f => usize::from(marker & 0x0f),
+            _ => return Err(error("type is not supported", start)),
+        };
+        match marker {
+            0xa0..=0xbf | 0xd9..=0xdb => Ok(PyStr::from_str(self.read_str(len)?).as_non_null_ptr()),
+            0xc4..=0xc6 => {
+                let buf = self.take(len)?;
+                Ok(nonnull!(ffi!(PyBytes_FromStringAndSize(
+                    buf.as_ptr().cast::<core::ffi::c_char>(),
+                    usize_to_isize(len)
+                ))))
+            }
+            _ => {
+                if depth >= RECURSION_LIMIT {
+                    return Err(error("exceeds recursion limit", start));
+                }
+                if matches!(marker, 0x90..=0x9f | 0xdc | 0xdd) {
+                    self.read_array(len, depth)
+                } else {
+                    self.read_map(len, depth)
+                }
+            }
+        }
+    }
+
+    fn read_array(
+        &mut self,
+        len: usize,
+        depth: usize,
+    ) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        let len = self.container_len(len, 1)?;
+        let list = ffi!(PyList_New(usize_to_isize(len)));
+        for idx in 0..len {
+            match self.read(depth + 1) {
+                Ok(value) => ffi!(PyList_SET_ITEM(list, usize_to_isize(idx), value.as_ptr())),
+                Err(err) => {
+                    // the items not yet set are null
+                    ffi!(Py_DECREF(list));
+                    return Err(err);
+                }
+            }
+        }
+        Ok(nonnull!(list))
+    }
+
+    fn read_map(
+        &mut self,
+        len: usize,
+        depth: usize,
+    ) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        let len = self.container_len(len, 2)?;
+        let dict = ffi!(PyDict_New());
+        for _ in 0..len {
+            let start = self.pos;
+            let marker = self.read_u8().inspect_err(|_| ffi!(Py_DECREF(dict)))?;
+            let key_len = match marker {
+                0xa0..=0xbf => Ok(usize::from(marker & 0x1f)),
+                0xd9 => self.read_u8().map(usize::from),
+                0xda => self.read_u16().map(usize::from),
+                0xdb => self.read_u32().map(|len| len as usize),
+                _ => Err(error("map key must be str", start)),
+            };
+            let key_str = match key_len.and_then(|len| self.read_str(len)) {
+                Ok(key_str) => key_str,
+                Err(err) => {
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            let value = match self.read(depth + 1) {
+                Ok(value) => value,
+                Err(err) => {
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            let key = get_unicode_key(key_str, self.state);
+            pydict_setitem!(dict, key.as_ptr(), value.as_ptr());
+        }
+        Ok(nonnull!(dict))
+    }
+}
+
+/// Read the MessagePack document `ptr`, which is `bytes`, `bytearray`, or
+/// `memoryview`.
+pub(crate) fn deserialize_msgpack(
+    ptr: *mut PyObject,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let Some(data) = bytes_like_to_buf(ptr) else {
+        return Err(DeserializeError::invalid(Cow::Borrowed(
+            "loads_msgpack() argument must be bytes, bytearray, or C contiguous memoryview",
+        )));
+    };
+    if data.is_empty() {
+        return Err(error("data is empty", 0));
+    }
+    let mut reader = Reader {
+        data,
+        pos: 0,
+        state: unsafe { crate::runtime::current_state() },
+    };
+    let obj = reader.read(0)?;
+    if reader.pos != data.len() {
+        ffi!(Py_DECREF(obj.as_ptr()));
+        return Err(error("data continues after the value", reader.pos));
+    }
+    Ok(obj)
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::deserialize::DeserializeError;
+use crate::deserialize::backend::{Number, deserialize_numbers};
+use crate::deserialize::utf8::read_input_to_buf;
+use crate::ffi::PyObject;
+use crate::serialize::{NPY_ARRAY_NOTSWAPPED, PyArrayInterface, PyCapsule};
+use crate::typeref::get_array_struct_str;
+use core::ptr::{NonNull, null_mut};
+use std::borrow::Cow;
+
+const DTYPE_ERROR: &str = "loads_numpy() dtype must be a bool, integer, or float type";
+
+/// Deserialize `ptr`, an array of numbers or of arrays of the same shape,
+/// into a new `numpy.ndarray` of `dtype`, without creating an object for
+/// each number.
+pub(crate) fn deserialize_numpy(
+    ptr: *mut PyObject,
+    dtype: *mut PyObject,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let buffer = read_input_to_buf(ptr)?;
+    let data = unsafe { core::str::from_utf8_unchecked(buffer) };
+    let interpreter_state = unsafe { crate::runtime::current_state() };
+    let (shape, numbers) = deserialize_numbers(data, interpreter_state)?;
+    let array = new_array(&shape, dtype)?;
+    match fill(array.as_ptr(), &numbers) {
+        Ok(()) => Ok(array),
+        Err(msg) => {
+            ffi!(Py_DECREF(array.as_ptr()));
+            Err(DeserializeError::invalid(Cow::Borrowed(msg)))
+        }
+    }
+}
+
+/// Call `numpy.empty(shape, dtype)`.
+#[cold]
+fn new_array(
+    shape: &[usize],
+    dtype: *mut PyObject,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let numpy = ffi!(PyImport_ImportModule(c"numpy".as_ptr()));
+    if numpy.is_null() {
+        return Err(DeserializeError::raised());
+    }
+    let empty = ffi!(PyObject_GetAttrString(numpy, c"empty".as_ptr()));
+    ffi!(Py_DECREF(numpy));
+    if empty.is_null() {
+        return Err(DeserializeError::raised());
+    }
+    let dims = ffi!(PyTuple_New(crate::util::usize_to_isize(shape.len())));
+    for (i, &dim) in shape.iter().enumerate() {
+        ffi!(PyTuple_SET_ITEM(
+            dims,
+            crate::util::usize_to_isize(i),
+            ffi!(PyLong_FromUnsignedLongLong(dim as u64))
+        ));
+    }
+    let array = ffi!(PyObject_CallFunctionObjArgs(
+        empty,
+        dims,
+        dtype,
+        null_mut::<PyObject>()
+    ));
+    ffi!(Py_DECREF(dims));
+    ffi!(Py_DECREF(empty));
+    NonNull::new(array).ok_or_else(DeserializeError::raised)
+}
+
+/// Write `numbers` to the items of `array`, a new array in C order.
+#[cold]
+fn fill(array: *mut PyObject, numbers: &[Number]) -> Result<(), &'static str> {
+    let capsule = ffi!(PyObject_GetAttr(array, get_array_struct_str()));
+    if capsule.is_null() {
+        ffi!(PyErr_Clear());
+        return Err(DTYPE_ERROR);
+    }
+    let iface = unsafe {
+        (*capsule.cast::<PyCapsule>())
+            .pointer
+            .cast::<PyArrayInterface>()
+    };
+    let data = unsafe { (*iface).data.cast::<u8>() };
+    let ret = if unsafe { (*iface).flags } & NPY_ARRAY_NOTSWAPPED != NPY_ARRAY_NOTSWAPPED {
+        Err("loads_numpy() dtype must be native-endian")
+    } else {
+        match unsafe { ((*iface).typekind as u8, (*iface).itemsize) } {
+            (b'b', 1) => write(data, numbers, |number| match number {
+                Number::Bool(val) => Ok(u8::from(val)),
+                _ => Err("loads_numpy() array item is not a bool"),
+            }),
+            (b'i', 1) => write(data, numbers, to_int::<i8>),
+            (b'i', 2) => write(data, numbers, to_int::<i16>),
+            (b'i', 4) => write(data, numbers, to_int::<i32>),
+            (b'i', 8) => write(data, numbers, to_int::<i64>),
+            (b'u', 1) => write(data, numbers, to_int::<u8>),
+            (b'u', 2) => write(data, numbers, to_int::<u16>),
+            (b'u', 4) => write(data, numbers, to_int::<u32>),
+            (b'u', 8) => write(data, numbers, to_int::<u64>),
+            (b'f', 2) => write(data, numbers, |number| {
+                to_float(number).map(|val| half::f16::from_f64(val).to_bits())
+            }),
+            #[allow(clippy::cast_possible_truncation)]
+            (b'f', 4) => write(data, numbers, |number| {
+                to_float(number).map(|val| val as f32)
+            }),
+            (b'f', 8) => write(data, numbers, to_float),
+            _ => Err(DTYPE_ERROR),
+        }
+    };
+    ffi!(Py_DECREF(capsule));
+    ret
+}
+
+#[inline(always)]
+fn write<T>(
+    data: *mut u8,
+    numbers: &[Number],
+    convert: impl Fn(Number) -> Result<T, &'static str>,
+) -> Result<(), &'static str> {
+    let data = data.cast::<T>();
+    for (i, &number) in numbers.iter().enumerate() {
+        let val = convert(number)?;
+        unsafe {
+            data.add(i).write(val);
+        }
+    }
+    Ok(())
+}
+
+fn to_int<T: TryFrom<i64> + TryFrom<u64>>(number: Number) -> Result<T, &'static str> {
+    const RANGE_ERROR: &str = "loads_numpy() integer out of range for dtype";
+    match number {
+        Number::I64(val) => T::try_from(val).map_err(|_| RANGE_ERROR),
+        Number::U64(val) => T::try_from(val).map_err(|_| RANGE_ERROR),
+        Number::F64(_) => Err("loads_numpy() float cannot be stored in an integer dtype"),
+        Number::Bool(_) => Err("loads_numpy() array item is not a number"),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_float(number: Number) -> Result<f64, &'static str> {
+    match number {
+        Number::I64(val) => Ok(val as f64),
+        Number::U64(val) => Ok(val as f64),
+        Number::F64(val) => Ok(val),
+        Number::Bool(_) => Err("loads_numpy() array item is not a number"),
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::deserialize::DeserializeError;
+use crate::ffi::PyObject;
+
+use core::ptr::{NonNull, null_mut};
+
+/// Replace each object of the document by the result of calling `hook` with
+/// it, innermost first, for `loads(object_hook=)` as `json.loads()` does.
+/// `ptr` is consumed.
+pub(crate) fn apply_object_hook(
+    ptr: NonNull<PyObject>,
+    hook: NonNull<PyObject>,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let ret = apply(ptr.as_ptr(), hook.as_ptr());
+    match ret {
+        Ok(None) => Ok(ptr),
+        Ok(Some(replaced)) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Ok(nonnull!(replaced))
+        }
+        Err(err) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Err(err)
+        }
+    }
+}
+
+/// Apply `hook` to the arrays and objects within `ptr` in place and return
+/// a new reference to its result for `ptr`, if it is an object.
+fn apply(
+    ptr: *mut PyObject,
+    hook: *mut PyObject,
+) -> Result<Option<*mut PyObject>, DeserializeError<'static>> {
+    if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) {
+        for idx in 0..ffi!(Py_SIZE(ptr)) {
+            let item = unsafe { crate::ffi::PyList_GET_ITEM(ptr, idx) };
+            if let Some(replaced) = apply(item, hook)? {
+                ffi!(PyList_SetItem(ptr, idx, replaced));
+            }
+        }
+        return Ok(None);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr()) {
+        return Ok(None);
+    }
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(ptr, &mut pos, &mut key, &mut value)) == 1 {
+        if let Some(replaced) = apply(value, hook)? {
+            // replacing the value of a key does not change the size of the
+            // dict, so iteration continues
+            ffi!(PyDict_SetItem(ptr, key, replaced));
+            ffi!(Py_DECREF(replaced));
+        }
+    }
+    let ret = ffi!(PyObject_CallFunctionObjArgs(
+        hook,
+        ptr,
+        null_mut::<PyObject>()
+    ));
+    if ret.is_null() {
+        cold_path!();
+        return Err(DeserializeError::raised());
+    }
+    Ok(Some(ret))
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::deserialize::rename::KeyRename;
+use crate::ffi::PyObject;
+use core::ptr::NonNull;
+
+/// Which of the members of an object with the same key `loads()` keeps, from
+/// `duplicate_keys=`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DuplicateKeys {
+    /// The last, as `dict` assignment does.
+    #[default]
+    Last,
+    First,
+    /// Raise `JSONDecodeError` at the second.
+    Error,
+}
+
+/// How `loads()` reads an integer outside of 64 bits, from `int_overflow=`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum IntOverflow {
+    /// The nearest `float`, as yyjson does.
+    #[default]
+    Float,
+    /// A `str` of its text.
+    String,
+    /// Raise `JSONDecodeError`.
+    Error,
+}
+
+/// The keyword arguments of `loads()`. The default decodes standard JSON as
+/// `loads(obj)` does.
+#[derive(Default)]
+pub(crate) struct DeserializeOptions<'a> {
+    /// Keys replaced as they are interned.
+    pub rename: Option<KeyRename<'a>>,
+    /// Read `NaN`, `Infinity`, and `-Infinity` as floats.
+    pub allow_nan: bool,
+    /// Read JSON5: comments, trailing commas, single-quoted strings, unquoted
+    /// keys, and the numbers ECMAScript writes, including `NaN` and `Infinity`.
+    pub json5: bool,
+    /// Skip `//` and `/* */` comments, which `json5` also does.
+    pub allow_comments: bool,
+    /// Accept a comma after the last element of an array or member of an
+    /// object, which `json5` also does.
+    pub allow_trailing_commas: bool,
+    /// Leave out object members that are `null`, from `OPT_OMIT_NULL_ON_LOAD`.
+    pub omit_null: bool,
+    /// Keys are compared after `rename`.
+    pub duplicate_keys: DuplicateKeys,
+    /// Accept only RFC 8259 and raise with a `code`. Duplicate keys are
+    /// rejected by setting `duplicate_keys` to `Error`; the rest is already
+    /// so.
+    pub strict: bool,
+    /// Called with a `list` of the `(key, value)` pairs of each object, in
+    /// order, and its result used instead of a `dict`.
+    pub object_pairs_hook: Option<NonNull<PyObject>>,
+    /// Called with each `dict`, innermost first, and its result used instead,
+    /// unless `object_pairs_hook` is set.
+    pub object_hook: Option<NonNull<PyObject>>,
+    /// Called with the text of each number with a fraction or exponent and
+    /// its result used instead of a `float`. `use_decimal=True` sets it to
+    /// `decimal.Decimal` and `raw_numbers=True` to `hyperjson.RawNumber`.
+    pub parse_float: Option<NonNull<PyObject>>,
+    /// Called with the text of each other number, except JSON5 hexadecimal,
+    /// and its result used instead of an `int`. `NaN` and `Infinity` are
+    /// passed to neither.
+    pub parse_int: Option<NonNull<PyObject>>,
+    /// Applies to integers not passed to `parse_int`, including JSON5
+    /// hexadecimal.
+    pub int_overflow: IntOverflow,
+    /// Raise for a number with a fraction or exponent whose digits are not
+    /// those of the float it is read as, rather than round it.
+    pub exact_floats: bool,
+    /// The annotation, such as a dataclass, whose objects are built rather
+    /// than a tree of `dict`, from `type=`. It is read by yyjson.
+    pub typ: Option<NonNull<PyObject>>,
+    /// Check that the value returned for `typ` is of it, from
+    /// `strict_types=`, as `__post_init__` or a type registered with
+    /// `register_deserializer()` may build something else.
+    pub strict_types: bool,
+    /// Build instances of the types registered with `register_tagged()` from
+    /// objects with a `"__type__"` member, from `tagged=`.
+    pub tagged: bool,
+    /// Read the wrappers of MongoDB Extended JSON v2, such as `"$date"`, as
+    /// the values they wrap, from `extended_json=`.
+    pub extended_json: bool,
+    /// The codec `bytes` input is decoded from, from `encoding=`, rather
+    /// than read as UTF-8. It is a `str` naming a known codec.
+    pub encoding: Option<NonNull<PyObject>>,
+    /// Replace what is not valid in the input's encoding with U+FFFD rather
+    /// than raise, from `errors="replace"`.
+    pub replace_invalid: bool,
+    /// A `list` of the instance of a `cls=` argument and the hooks read from
+    /// it, owned so that they are not freed while reading.
+    pub owned: Option<NonNull<PyObject>>,
+}
+
+impl Drop for DeserializeOptions<'_> {
+    fn drop(&mut self) {
+        if let Some(owned) = self.owned {
+            ffi!(Py_DECREF(owned.as_ptr()));
+        }
+    }
+}
+
+impl DeserializeOptions<'_> {
+    /// Whether the input is read by `read_encoded_to_buf()` rather than as
+    /// UTF-8.
+    #[inline(always)]
+    pub fn decodes_input(&self) -> bool {
+        self.encoding.is_some() || self.replace_invalid
+    }
+
+    /// Whether the input is read by the extended parser rather than yyjson.
+    #[inline(always)]
+    pub fn is_extended(&self) -> bool {
+        self.allow_nan
+            || self.json5
+            || self.allow_trailing_commas
+            || self.duplicate_keys != DuplicateKeys::Last
+            || self.object_pairs_hook.is_some()
+            || self.parse_float.is_some()
+            || self.parse_int.is_some()
+            || self.int_overflow != IntOverflow::Float
+            || self.exact_floats
+    }
+}
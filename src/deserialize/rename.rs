@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `loads(rename=...)`, which replaces keys as they are interned rather than
+//! rebuilding each `dict` after it is decoded.
+
+use crate::str::PyStr;
+use crate::util::{fnv1a_hash, isize_to_usize};
+
+struct Entry<'a> {
+    hash: u64,
+    from: &'a str,
+    /// Borrowed from the mapping, which outlives the call, with its hash
+    /// already computed.
+    to: PyStr,
+}
+
+/// The keys of a `rename=` mapping, open-addressed by the `fnv1a_hash()` of
+/// the key to replace.
+pub(crate) struct KeyRename<'a> {
+    slots: Vec<Option<Entry<'a>>>,
+    mask: usize,
+}
+
+impl<'a> KeyRename<'a> {
+    /// Read a `dict` of `str` to `str`. The `dict` must not be modified while
+    /// the result is in use.
+    #[cold]
+    pub fn from_dict(ptr: *mut crate::ffi::PyObject) -> Option<KeyRename<'a>> {
+        if !is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr()) {
+            return None;
+        }
+        let len = isize_to_usize(ffi!(Py_SIZE(ptr)));
+        // at least one slot is empty so that a lookup ends
+        let capacity = (len * 2).next_power_of_two().max(2);
+        let mut rename = KeyRename {
+            slots: (0..capacity).map(|_| None).collect(),
+            mask: capacity - 1,
+        };
+
+        let mut pos = 0;
+        let mut key: *mut crate::ffi::PyObject = core::ptr::null_mut();
+        let mut value: *mut crate::ffi::PyObject = core::ptr::null_mut();
+        while unsafe { crate::ffi::PyDict_Next(ptr, &mut pos, &mut key, &mut value) } != 0 {
+            if !is_type!(ob_type!(key), crate::typeref::str_type_ptr())
+                || !is_type!(ob_type!(value), crate::typeref::str_type_ptr())
+            {
+                return None;
+            }
+            let from = unsafe { PyStr::from_ptr_unchecked(key).to_str()? };
+            let mut to = unsafe { PyStr::from_ptr_unchecked(value) };
+            to.to_str()?;
+            to.hash();
+            let hash = fnv1a_hash(from.as_bytes());
+            let mut idx = (hash as usize) & rename.mask;
+            while rename.slots[idx].is_some() {
+                idx = (idx + 1) & rename.mask;
+            }
+            rename.slots[idx] = Some(Entry {
+                hash: hash,
+                from: from,
+                to: to,
+            });
+        }
+        Some(rename)
+    }
+
+    /// Return a new reference to the key replacing `key`, if any.
+    #[inline(always)]
+    pub fn get(&self, key: &str) -> Option<PyStr> {
+        let hash = fnv1a_hash(key.as_bytes());
+        let mut idx = (hash as usize) & self.mask;
+        while let Some(entry) = &self.slots[idx] {
+            if entry.hash == hash && entry.from == key {
+                ffi!(Py_INCREF(entry.to.as_ptr()));
+                return Some(entry.to);
+            }
+            idx = (idx + 1) & self.mask;
+        }
+        None
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Parse the strings that `loads()` with `type=` reads as a `datetime`,
+//! `date`, or `UUID`.
+
+pub(crate) struct Date {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+}
+
+pub(crate) struct DateTime {
+    pub date: Date,
+    pub hour: i32,
+    pub minute: i32,
+    pub second: i32,
+    pub microsecond: i32,
+    /// Seconds east of UTC, or `None` if the string has no offset.
+    pub offset: Option<i32>,
+}
+
+/// The value of the ASCII digits `buf`, if each is a digit.
+#[inline(always)]
+fn digits(buf: &[u8]) -> Option<i32> {
+    let mut val = 0;
+    for &byte in buf {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        val = val * 10 + i32::from(byte - b'0');
+    }
+    Some(val)
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `YYYY-MM-DD`.
+fn parse_date_prefix(buf: &[u8]) -> Option<Date> {
+    if buf.len() < 10 || buf[4] != b'-' || buf[7] != b'-' {
+        return None;
+    }
+    let year = digits(&buf[0..4])?;
+    let month = digits(&buf[5..7])?;
+    let day = digits(&buf[8..10])?;
+    if year == 0 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(Date {
+        year: year,
+        month: month,
+        day: day,
+    })
+}
+
+/// A date as `date.isoformat()` writes it.
+pub(crate) fn parse_date(buf: &[u8]) -> Option<Date> {
+    if buf.len() != 10 {
+        return None;
+    }
+    parse_date_prefix(buf)
+}
+
+/// An RFC 3339 date-time, such as `2025-01-02T03:04:05.678Z`, or one
+/// without an offset, as `datetime.isoformat()` writes a naive `datetime`.
+/// The separator may also be a space and a fraction of more than six digits
+/// is truncated to microseconds.
+pub(crate) fn parse_datetime(buf: &[u8]) -> Option<DateTime> {
+    let date = parse_date_prefix(buf)?;
+    let rest = &buf[10..];
+    if rest.len() < 9
+        || !matches!(rest[0], b'T' | b't' | b' ')
+        || rest[3] != b':'
+        || rest[6] != b':'
+    {
+        return None;
+    }
+    let hour = digits(&rest[1..3])?;
+    let minute = digits(&rest[4..6])?;
+    let second = digits(&rest[7..9])?;
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let mut rest = &rest[9..];
+    let mut microsecond = 0;
+    if let Some((b'.', fraction)) = rest.split_first() {
+        let len = fraction
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if len == 0 {
+            return None;
+        }
+        for &byte in fraction.iter().take(len.min(6)) {
+            microsecond = microsecond * 10 + i32::from(byte - b'0');
+        }
+        for _ in len..6 {
+            microsecond *= 10;
+        }
+        rest = &fraction[len..];
+    }
+    let offset = match rest {
+        [] => None,
+        [b'Z' | b'z'] => Some(0),
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let hours = digits(&[*h1, *h2])?;
+            let minutes = digits(&[*m1, *m2])?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            Some(if *sign == b'-' { -offset } else { offset })
+        }
+        _ => return None,
+    };
+    Some(DateTime {
+        date: date,
+        hour: hour,
+        minute: minute,
+        second: second,
+        microsecond: microsecond,
+        offset: offset,
+    })
+}
+
+/// The 32 hexadecimal digits of a UUID written with or without hyphens, as
+/// `str(uuid)` and `uuid.hex` do, lowercase.
+pub(crate) fn parse_uuid(buf: &[u8]) -> Option<[u8; 32]> {
+    let mut hex = [0u8; 32];
+    let mut len = 0;
+    for (idx, &byte) in buf.iter().enumerate() {
+        if byte == b'-' && buf.len() == 36 && matches!(idx, 8 | 13 | 18 | 23) {
+            continue;
+        }
+        if !byte.is_ascii_hexdigit() || len == 32 {
+            return None;
+        }
+        hex[len] = byte.to_ascii_lowercase();
+        len += 1;
+    }
+    if len != 32 || !(buf.len() == 32 || buf.len() == 36) {
+        return None;
+    }
+    Some(hex)
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::deserialize::DeserializeError;
+use crate::ffi::PyObject;
+use crate::str::PyStr;
+
+use core::ptr::{NonNull, null_mut};
+use std::borrow::Cow;
+
+/// Replace each object of the document with a `"__type__"` member by an
+/// instance of the type registered with `register_tagged()` for it, for
+/// `loads(tagged=True)`. The type is called with the other members as
+/// keyword arguments, and a `TypeError` from it is a `JSONDecodeError`.
+/// `ptr` is consumed.
+pub(crate) fn revive_tagged(
+    ptr: NonNull<PyObject>,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    let types = unsafe { (*crate::runtime::current_state()).types_by_tag };
+    match revive(ptr.as_ptr(), types) {
+        Ok(None) => Ok(ptr),
+        Ok(Some(revived)) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Ok(nonnull!(revived))
+        }
+        Err(err) => {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+            Err(err)
+        }
+    }
+}
+
+/// Revive the arrays and objects within `ptr` in place and return a new
+/// reference to the instance `ptr` is revived as, if it is tagged.
+fn revive(
+    ptr: *mut PyObject,
+    types: *mut PyObject,
+) -> Result<Option<*mut PyObject>, DeserializeError<'static>> {
+    if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) {
+        for idx in 0..ffi!(Py_SIZE(ptr)) {
+            let item = unsafe { crate::ffi::PyList_GET_ITEM(ptr, idx) };
+            if let Some(revived) = revive(item, types)? {
+                ffi!(PyList_SetItem(ptr, idx, revived));
+            }
+        }
+        return Ok(None);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr()) {
+        return Ok(None);
+    }
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(ptr, &mut pos, &mut key, &mut value)) == 1 {
+        if let Some(revived) = revive(value, types)? {
+            // replacing the value of a key does not change the size of the
+            // dict, so iteration continues
+            ffi!(PyDict_SetItem(ptr, key, revived));
+            ffi!(Py_DECREF(revived));
+        }
+    }
+    let type_key = crate::typeref::get_type_key_str();
+    let tag = ffi!(PyDict_GetItem(ptr, type_key));
+    if tag.is_null() || !is_type!(ob_type!(tag), crate::typeref::str_type_ptr()) {
+        return Ok(None);
+    }
+    let typ = ffi!(PyDict_GetItem(types, tag));
+    if typ.is_null() {
+        cold_path!();
+        let tag = unsafe { PyStr::from_ptr_unchecked(tag).to_str() }.unwrap_or("");
+        return Err(DeserializeError::invalid(Cow::Owned(format!(
+            "tagged type is not registered: {tag}"
+        ))));
+    }
+    let kwargs = ffi!(PyDict_Copy(ptr));
+    ffi!(PyDict_DelItem(kwargs, type_key));
+    let args = ffi!(PyTuple_New(0));
+    let instance = ffi!(PyObject_Call(typ, args, kwargs));
+    ffi!(Py_DECREF(args));
+    ffi!(Py_DECREF(kwargs));
+    if instance.is_null() {
+        cold_path!();
+        // the members do not match the parameters of the type
+        if ffi!(PyErr_ExceptionMatches(crate::ffi::PyExc_TypeError)) != 0 {
+            ffi!(PyErr_Clear());
+            let tag = unsafe { PyStr::from_ptr_unchecked(tag).to_str() }.unwrap_or("");
+            return Err(DeserializeError::invalid(Cow::Owned(format!(
+                "tagged type cannot be built from its members: {tag}"
+            ))));
+        }
+        return Err(DeserializeError::raised());
+    }
+    Ok(Some(instance))
+}
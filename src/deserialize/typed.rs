@@ -0,0 +1,1098 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `loads(type=...)`, which builds the objects of a type annotation, such as
+//! a dataclass, from the document rather than from a tree of `dict`.
+
+use crate::deserialize::DeserializeError;
+use crate::ffi::PyObject;
+use crate::str::PyStr;
+use crate::util::{isize_to_usize, usize_to_isize};
+use core::ffi::CStr;
+use core::ptr::{NonNull, null_mut};
+use std::borrow::Cow;
+
+/// What a value of the document is read as.
+pub(crate) enum Kind {
+    /// Whatever `loads()` reads, from `typing.Any` or `object`.
+    Any,
+    Str,
+    Int,
+    /// A number, including an integer.
+    Float,
+    Bool,
+    None,
+    /// An RFC 3339 string, parsed in Rust.
+    Datetime,
+    Date,
+    /// A string of 32 hexadecimal digits, with or without hyphens.
+    Uuid,
+    /// A string or an integer, passed to `decimal.Decimal`.
+    Decimal,
+    List(Box<Kind>),
+    /// `tuple[T, ...]`.
+    Tuple(Box<Kind>),
+    /// `tuple[A, B]`, of exactly as many items.
+    FixedTuple(Vec<Kind>),
+    /// `dict[str, T]`.
+    Dict(Box<Kind>),
+    /// The first of the members that reads the value.
+    Union(Vec<Kind>),
+    /// An index into `TypePlan::classes`.
+    Class(usize),
+    /// An index into `TypePlan::adapters`, of a type registered with
+    /// `register_deserializer()`.
+    Adapter(usize),
+}
+
+impl Kind {
+    /// Whether reading as `self` builds a class, which within a pydantic
+    /// model is only another model.
+    fn builds_class(&self) -> bool {
+        match self {
+            Kind::List(item) | Kind::Tuple(item) | Kind::Dict(item) => item.builds_class(),
+            Kind::FixedTuple(items) => items.iter().any(Kind::builds_class),
+            Kind::Class(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A field of a class, or a key of a `TypedDict`.
+pub(crate) struct Field {
+    /// The attribute, argument, or key of the field in Python.
+    pub name: *mut PyObject,
+    /// The key of the field in the document.
+    pub key: String,
+    pub kind: Kind,
+    /// The field has no default, or the key is required.
+    pub required: bool,
+}
+
+impl Field {
+    /// A field of `Any` named `name`, a new reference that it takes, if
+    /// `name` is a `str`.
+    fn new(name: *mut PyObject, required: bool) -> Option<Field> {
+        let Some(key) = to_string(name) else {
+            ffi!(Py_XDECREF(name));
+            ffi!(PyErr_Clear());
+            return None;
+        };
+        Some(Field {
+            name: name,
+            key: key,
+            kind: Kind::Any,
+            required: required,
+        })
+    }
+}
+
+impl Drop for Field {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.name));
+    }
+}
+
+/// How the fields of a class are read and the class built from them.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Shape {
+    /// Called with the fields as keyword arguments, so that defaults and
+    /// `__post_init__` apply.
+    Dataclass,
+    /// A `dict` of the keys.
+    TypedDict,
+    /// Called as a dataclass is, or with the items of an array as positional
+    /// arguments.
+    NamedTuple,
+    /// The value read as `loads()` reads it, passed to the validator of a
+    /// pydantic model, which validates any models within it as well. The
+    /// fields are only for `Schema.dumps()`.
+    Pydantic,
+}
+
+pub(crate) struct ClassPlan {
+    pub cls: *mut PyObject,
+    pub name: String,
+    pub shape: Shape,
+    pub fields: Vec<Field>,
+    /// The names of all fields, for a call given each of them.
+    pub kwnames: *mut PyObject,
+    /// `__pydantic_validator__.validate_python` of a pydantic model.
+    pub validator: *mut PyObject,
+}
+
+impl ClassPlan {
+    /// The index of the field `key`, checking `hint` first as members are
+    /// usually in the order of the fields.
+    #[inline(always)]
+    pub fn find(&self, key: &str, hint: usize) -> Option<usize> {
+        if hint < self.fields.len() && self.fields[hint].key == key {
+            return Some(hint);
+        }
+        self.fields.iter().position(|field| field.key == key)
+    }
+}
+
+impl Drop for ClassPlan {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.cls));
+        ffi!(Py_XDECREF(self.kwnames));
+        ffi!(Py_XDECREF(self.validator));
+    }
+}
+
+/// A type registered with `register_deserializer()` when the plan was
+/// compiled, and the callable that builds it from the value `loads()` reads.
+pub(crate) struct Adapter {
+    pub cls: *mut PyObject,
+    pub name: String,
+    pub callable: *mut PyObject,
+}
+
+impl Drop for Adapter {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.cls));
+        ffi!(Py_DECREF(self.callable));
+    }
+}
+
+/// The objects that values of `Kind::Datetime`, `Kind::Uuid`, and
+/// `Kind::Decimal` are built with, looked up if the plan has such a kind.
+pub(crate) struct Builders {
+    /// `datetime.timezone`, of a `datetime` with an offset other than zero.
+    pub timezone: *mut PyObject,
+    /// `datetime.timezone.utc`.
+    pub utc: *mut PyObject,
+    pub timedelta: *mut PyObject,
+    /// `("int",)`, the keyword `uuid.UUID` is called with.
+    pub uuid_kwnames: *mut PyObject,
+    pub decimal: *mut PyObject,
+}
+
+impl Builders {
+    fn new() -> Self {
+        Builders {
+            timezone: null_mut(),
+            utc: null_mut(),
+            timedelta: null_mut(),
+            uuid_kwnames: null_mut(),
+            decimal: null_mut(),
+        }
+    }
+}
+
+impl Drop for Builders {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.timezone));
+        ffi!(Py_XDECREF(self.utc));
+        ffi!(Py_XDECREF(self.timedelta));
+        ffi!(Py_XDECREF(self.uuid_kwnames));
+        ffi!(Py_XDECREF(self.decimal));
+    }
+}
+
+/// How to read a document as a type annotation.
+pub(crate) struct TypePlan {
+    pub root: Kind,
+    /// The classes `root` refers to, each once so that a class may refer to
+    /// itself.
+    pub classes: Vec<ClassPlan>,
+    pub adapters: Vec<Adapter>,
+    pub builders: Builders,
+}
+
+impl TypePlan {
+    /// Compile the annotation `typ`. An exception raised by the `typing` or
+    /// `dataclasses` functions this calls is left set.
+    #[cold]
+    #[inline(never)]
+    pub fn new(typ: *mut PyObject) -> Result<TypePlan, String> {
+        let mut compiler = Compiler::new()?;
+        let root = compiler.compile(typ)?;
+        Ok(TypePlan {
+            root: root,
+            classes: core::mem::take(&mut compiler.classes),
+            adapters: core::mem::take(&mut compiler.adapters),
+            builders: core::mem::replace(&mut compiler.builders, Builders::new()),
+        })
+    }
+
+    /// The annotation `kind` was compiled from, for an error.
+    #[cold]
+    pub fn describe(&self, kind: &Kind) -> String {
+        match kind {
+            Kind::Any => String::from("Any"),
+            Kind::Str => String::from("str"),
+            Kind::Int => String::from("int"),
+            Kind::Float => String::from("float"),
+            Kind::Bool => String::from("bool"),
+            Kind::None => String::from("None"),
+            Kind::Datetime => String::from("datetime"),
+            Kind::Date => String::from("date"),
+            Kind::Uuid => String::from("UUID"),
+            Kind::Decimal => String::from("Decimal"),
+            Kind::List(item) => format!("list[{}]", self.describe(item)),
+            Kind::Tuple(item) => format!("tuple[{}, ...]", self.describe(item)),
+            Kind::FixedTuple(items) => format!(
+                "tuple[{}]",
+                items
+                    .iter()
+                    .map(|item| self.describe(item))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Kind::Dict(value) => format!("dict[str, {}]", self.describe(value)),
+            Kind::Union(members) => members
+                .iter()
+                .map(|member| self.describe(member))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Kind::Class(idx) => self.classes[*idx].name.clone(),
+            Kind::Adapter(idx) => self.adapters[*idx].name.clone(),
+        }
+    }
+}
+
+impl TypePlan {
+    /// Check that `ptr`, which `loads()` returned for the plan, is of the
+    /// annotation, for `strict_types=True`. `ptr` is consumed if it is not.
+    #[cold]
+    #[inline(never)]
+    pub fn check(
+        &self,
+        ptr: NonNull<PyObject>,
+    ) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        match self.check_kind(ptr.as_ptr(), &self.root, 0) {
+            Ok(()) => Ok(ptr),
+            Err(err) => {
+                ffi!(Py_DECREF(ptr.as_ptr()));
+                Err(DeserializeError::invalid(Cow::Owned(err.to_string())))
+            }
+        }
+    }
+
+    fn check_kind(&self, ptr: *mut PyObject, kind: &Kind, depth: usize) -> Result<(), Mismatch> {
+        if depth > CHECK_RECURSION_LIMIT {
+            return Err(Mismatch::new(String::from("recursion limit reached")));
+        }
+        let state = unsafe { crate::runtime::current_state() };
+        let matches = match kind {
+            Kind::Any => true,
+            Kind::Str => is_instance(ptr, crate::typeref::str_type_ptr()),
+            Kind::Int => is_instance(ptr, crate::typeref::int_type_ptr()),
+            Kind::Float => {
+                is_instance(ptr, crate::typeref::float_type_ptr())
+                    || is_instance(ptr, crate::typeref::int_type_ptr())
+            }
+            Kind::Bool => is_type!(ob_type!(ptr), crate::typeref::bool_type_ptr()),
+            Kind::None => core::ptr::eq(ptr, crate::typeref::get_none()),
+            Kind::Datetime => is_instance(ptr, crate::typeref::get_datetime_type_from_state(state)),
+            Kind::Date => is_instance(ptr, crate::typeref::get_date_type_from_state(state)),
+            Kind::Uuid => is_instance(ptr, crate::typeref::get_uuid_type_from_state(state)),
+            Kind::Decimal => is_instance(ptr, crate::typeref::get_decimal_type().cast()),
+            Kind::List(item) => {
+                if !is_instance(ptr, crate::typeref::list_type_ptr()) {
+                    return Err(self.mismatch(ptr, kind));
+                }
+                return self.check_items(ptr, |_| Some(item), depth);
+            }
+            Kind::Tuple(item) => {
+                if !is_instance(ptr, crate::typeref::tuple_type_ptr()) {
+                    return Err(self.mismatch(ptr, kind));
+                }
+                return self.check_items(ptr, |_| Some(item), depth);
+            }
+            Kind::FixedTuple(items) => {
+                if !is_instance(ptr, crate::typeref::tuple_type_ptr())
+                    || isize_to_usize(ffi!(Py_SIZE(ptr))) != items.len()
+                {
+                    return Err(self.mismatch(ptr, kind));
+                }
+                return self.check_items(ptr, |idx| items.get(idx), depth);
+            }
+            Kind::Dict(value) => {
+                if !is_instance(ptr, crate::typeref::dict_type_ptr()) {
+                    return Err(self.mismatch(ptr, kind));
+                }
+                return self.check_values(ptr, value, depth);
+            }
+            Kind::Union(members) => members
+                .iter()
+                .any(|member| self.check_kind(ptr, member, depth + 1).is_ok()),
+            Kind::Class(idx) => return self.check_class(ptr, kind, *idx, depth),
+            Kind::Adapter(idx) => is_instance(ptr, self.adapters[*idx].cls.cast()),
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(self.mismatch(ptr, kind))
+        }
+    }
+
+    #[cold]
+    fn mismatch(&self, ptr: *mut PyObject, kind: &Kind) -> Mismatch {
+        let name = unsafe { CStr::from_ptr((*ob_type!(ptr)).tp_name) };
+        Mismatch::new(format!(
+            "expected {}, got {}",
+            self.describe(kind),
+            name.to_string_lossy()
+        ))
+    }
+
+    /// Check the items of the `list` or `tuple` `ptr` as the kind `item_of`
+    /// returns for their index.
+    fn check_items<'k>(
+        &self,
+        ptr: *mut PyObject,
+        item_of: impl Fn(usize) -> Option<&'k Kind>,
+        depth: usize,
+    ) -> Result<(), Mismatch> {
+        let is_list = is_instance(ptr, crate::typeref::list_type_ptr());
+        let mut idx = 0;
+        // the length is read again as a check may run Python code
+        while idx < isize_to_usize(ffi!(Py_SIZE(ptr))) {
+            let Some(kind) = item_of(idx) else {
+                break;
+            };
+            let item = if is_list {
+                ffi!(PyList_GET_ITEM(ptr, usize_to_isize(idx)))
+            } else {
+                ffi!(PyTuple_GET_ITEM(ptr, usize_to_isize(idx)))
+            };
+            ffi!(Py_INCREF(item));
+            let ret = self.check_kind(item, kind, depth + 1);
+            ffi!(Py_DECREF(item));
+            ret.map_err(|err| err.at(format!("[{idx}]")))?;
+            idx += 1;
+        }
+        Ok(())
+    }
+
+    /// Check that the keys of the `dict` `ptr` are `str` and its values of
+    /// `value`.
+    fn check_values(&self, ptr: *mut PyObject, value: &Kind, depth: usize) -> Result<(), Mismatch> {
+        let copy = ffi!(PyDict_Copy(ptr));
+        if copy.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(Mismatch::new(String::from("expected dict")));
+        }
+        let mut ret = Ok(());
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut val: *mut PyObject = null_mut();
+        while ffi!(PyDict_Next(copy, &mut pos, &mut key, &mut val)) == 1 {
+            let Some(key_str) = to_string(key) else {
+                ret = Err(Mismatch::new(String::from("expected str keys")));
+                break;
+            };
+            ret = self
+                .check_kind(val, value, depth + 1)
+                .map_err(|err| err.at(format!(".{key_str}")));
+            if ret.is_err() {
+                break;
+            }
+        }
+        ffi!(Py_DECREF(copy));
+        ret
+    }
+
+    /// Check the instance `ptr` of `self.classes[idx]` and its fields.
+    fn check_class(
+        &self,
+        ptr: *mut PyObject,
+        kind: &Kind,
+        idx: usize,
+        depth: usize,
+    ) -> Result<(), Mismatch> {
+        let class = &self.classes[idx];
+        if class.shape == Shape::TypedDict {
+            if !is_instance(ptr, crate::typeref::dict_type_ptr()) {
+                return Err(self.mismatch(ptr, kind));
+            }
+        } else if !is_instance(ptr, class.cls.cast()) {
+            return Err(self.mismatch(ptr, kind));
+        }
+        if class.shape == Shape::Pydantic {
+            // the model's validator has checked its fields
+            return Ok(());
+        }
+        for field in class.fields.iter() {
+            let value = if class.shape == Shape::TypedDict {
+                let value = ffi!(PyDict_GetItem(ptr, field.name));
+                ffi!(Py_XINCREF(value));
+                value
+            } else {
+                ffi!(PyObject_GetAttr(ptr, field.name))
+            };
+            if value.is_null() {
+                ffi!(PyErr_Clear());
+                if class.shape == Shape::TypedDict && !field.required {
+                    continue;
+                }
+                return Err(Mismatch::new(format!(
+                    "missing field \"{}\" of {}",
+                    field.key, class.name
+                )));
+            }
+            let ret = self.check_kind(value, &field.kind, depth + 1);
+            ffi!(Py_DECREF(value));
+            ret.map_err(|err| err.at(format!(".{}", field.key)))?;
+        }
+        Ok(())
+    }
+}
+
+const CHECK_RECURSION_LIMIT: usize = 1024;
+
+/// Why a value returned with `strict_types=True` is not of its annotation,
+/// with the path to it built as the error is returned through its
+/// containers.
+struct Mismatch {
+    message: String,
+    /// Innermost first.
+    path: Vec<String>,
+}
+
+impl Mismatch {
+    #[cold]
+    fn new(message: String) -> Self {
+        Mismatch {
+            message: message,
+            path: Vec::new(),
+        }
+    }
+
+    #[cold]
+    fn at(mut self, segment: String) -> Self {
+        self.path.push(segment);
+        self
+    }
+}
+
+impl core::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "strict_types: {} at $", self.message)?;
+        for segment in self.path.iter().rev() {
+            f.write_str(segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// `isinstance(ptr, typ)`, false if it raises.
+fn is_instance(ptr: *mut PyObject, typ: *mut crate::ffi::PyTypeObject) -> bool {
+    match ffi!(PyObject_IsInstance(ptr, typ.cast::<PyObject>())) {
+        1 => true,
+        0 => false,
+        _ => {
+            ffi!(PyErr_Clear());
+            false
+        }
+    }
+}
+
+/// Look up `name` in the module `module`, returning a new reference or null
+/// with no exception set.
+fn module_attr(module: &CStr, name: &CStr) -> *mut PyObject {
+    let module = ffi!(PyImport_ImportModule(module.as_ptr()));
+    if module.is_null() {
+        ffi!(PyErr_Clear());
+        return null_mut();
+    }
+    let attr = ffi!(PyObject_GetAttrString(module, name.as_ptr()));
+    ffi!(Py_DECREF(module));
+    if attr.is_null() {
+        ffi!(PyErr_Clear());
+    }
+    attr
+}
+
+fn call_one(func: *mut PyObject, arg: *mut PyObject) -> *mut PyObject {
+    ffi!(PyObject_CallFunctionObjArgs(
+        func,
+        arg,
+        null_mut::<PyObject>()
+    ))
+}
+
+/// Whether `typ` is `decimal.Decimal`, which is looked up only if `typ` is
+/// defined in a module named `decimal` so that other types do not import it.
+fn is_decimal(typ: *mut PyObject) -> bool {
+    let module = ffi!(PyObject_GetAttrString(typ, c"__module__".as_ptr()));
+    let in_decimal = matches!(
+        to_string(module).as_deref(),
+        Some("decimal" | "_decimal" | "_pydecimal")
+    );
+    ffi!(Py_XDECREF(module));
+    if module.is_null() {
+        ffi!(PyErr_Clear());
+    }
+    in_decimal && core::ptr::eq(typ, crate::typeref::get_decimal_type())
+}
+
+/// `str(ptr)` if it is a `str`.
+fn to_string(ptr: *mut PyObject) -> Option<String> {
+    if ptr.is_null() || !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    unsafe { PyStr::from_ptr_unchecked(ptr).to_str().map(String::from) }
+}
+
+#[cold]
+fn unsupported(typ: *mut PyObject) -> String {
+    let repr = ffi!(PyObject_Repr(typ));
+    let msg = match to_string(repr) {
+        Some(repr) => format!("loads() type is not supported: {repr}"),
+        None => {
+            ffi!(PyErr_Clear());
+            String::from("loads() type is not supported")
+        }
+    };
+    ffi!(Py_XDECREF(repr));
+    msg
+}
+
+struct Compiler {
+    get_origin: *mut PyObject,
+    get_args: *mut PyObject,
+    get_type_hints: *mut PyObject,
+    any: *mut PyObject,
+    union: *mut PyObject,
+    /// `types.UnionType`, of `int | None`, which is null before Python 3.10.
+    union_type: *mut PyObject,
+    annotated: *mut PyObject,
+    fields: *mut PyObject,
+    missing: *mut PyObject,
+    classes: Vec<ClassPlan>,
+    adapters: Vec<Adapter>,
+    builders: Builders,
+    /// Compiling the fields of a pydantic model, which are written by
+    /// `Schema.dumps()` but validated by the model's validator.
+    lenient: bool,
+}
+
+impl Compiler {
+    fn new() -> Result<Self, String> {
+        let compiler = Compiler {
+            get_origin: module_attr(c"typing", c"get_origin"),
+            get_args: module_attr(c"typing", c"get_args"),
+            get_type_hints: module_attr(c"typing", c"get_type_hints"),
+            any: module_attr(c"typing", c"Any"),
+            union: module_attr(c"typing", c"Union"),
+            union_type: module_attr(c"types", c"UnionType"),
+            annotated: module_attr(c"typing", c"Annotated"),
+            fields: module_attr(c"dataclasses", c"fields"),
+            missing: module_attr(c"dataclasses", c"MISSING"),
+            classes: Vec::new(),
+            adapters: Vec::new(),
+            builders: Builders::new(),
+            lenient: false,
+        };
+        if compiler.get_origin.is_null()
+            || compiler.get_args.is_null()
+            || compiler.get_type_hints.is_null()
+            || compiler.any.is_null()
+            || compiler.union.is_null()
+            || compiler.annotated.is_null()
+            || compiler.fields.is_null()
+            || compiler.missing.is_null()
+        {
+            return Err(String::from(
+                "loads() type requires the typing and dataclasses modules",
+            ));
+        }
+        Ok(compiler)
+    }
+
+    fn compile(&mut self, typ: *mut PyObject) -> Result<Kind, String> {
+        if !self.lenient {
+            return self.compile_kind(typ);
+        }
+        match self.compile_kind(typ) {
+            Ok(kind) if kind.builds_class() => Ok(kind),
+            Ok(_) => Ok(Kind::Any),
+            Err(_) => {
+                ffi!(PyErr_Clear());
+                Ok(Kind::Any)
+            }
+        }
+    }
+
+    fn compile_kind(&mut self, typ: *mut PyObject) -> Result<Kind, String> {
+        if core::ptr::eq(typ, crate::typeref::none_ptr())
+            || core::ptr::eq(typ, crate::typeref::none_type_ptr().cast::<PyObject>())
+        {
+            return Ok(Kind::None);
+        }
+        if core::ptr::eq(typ, self.any)
+            || core::ptr::eq(
+                typ,
+                (&raw mut crate::ffi::PyBaseObject_Type).cast::<PyObject>(),
+            )
+        {
+            return Ok(Kind::Any);
+        }
+        if let Some(kind) = self.compile_adapter(typ) {
+            return Ok(kind);
+        }
+        let ob_type = typ.cast::<crate::ffi::PyTypeObject>();
+        if core::ptr::eq(ob_type, crate::typeref::str_type_ptr()) {
+            return Ok(Kind::Str);
+        } else if core::ptr::eq(ob_type, crate::typeref::int_type_ptr()) {
+            return Ok(Kind::Int);
+        } else if core::ptr::eq(ob_type, crate::typeref::float_type_ptr()) {
+            return Ok(Kind::Float);
+        } else if core::ptr::eq(ob_type, crate::typeref::bool_type_ptr()) {
+            return Ok(Kind::Bool);
+        } else if core::ptr::eq(ob_type, crate::typeref::list_type_ptr()) {
+            return Ok(Kind::List(Box::new(Kind::Any)));
+        } else if core::ptr::eq(ob_type, crate::typeref::tuple_type_ptr()) {
+            return Ok(Kind::Tuple(Box::new(Kind::Any)));
+        } else if core::ptr::eq(ob_type, crate::typeref::dict_type_ptr()) {
+            return Ok(Kind::Dict(Box::new(Kind::Any)));
+        }
+        let state = unsafe { crate::runtime::current_state() };
+        if core::ptr::eq(ob_type, crate::typeref::get_datetime_type_from_state(state)) {
+            return self.datetime_builders();
+        } else if core::ptr::eq(ob_type, crate::typeref::get_date_type_from_state(state)) {
+            return Ok(Kind::Date);
+        } else if core::ptr::eq(ob_type, crate::typeref::get_uuid_type_from_state(state)) {
+            if self.builders.uuid_kwnames.is_null() {
+                self.builders.uuid_kwnames = ffi!(PyTuple_New(1));
+                let name = ffi!(PyUnicode_InternFromString(c"int".as_ptr()));
+                ffi!(PyTuple_SET_ITEM(self.builders.uuid_kwnames, 0, name));
+            }
+            return Ok(Kind::Uuid);
+        } else if is_decimal(typ) {
+            if self.builders.decimal.is_null() {
+                ffi!(Py_INCREF(typ));
+                self.builders.decimal = typ;
+            }
+            return Ok(Kind::Decimal);
+        }
+        let origin = call_one(self.get_origin, typ);
+        if origin.is_null() {
+            return Err(unsupported_raised(typ));
+        }
+        if !core::ptr::eq(origin, crate::typeref::none_ptr()) {
+            let args = call_one(self.get_args, typ);
+            let ret = if args.is_null() {
+                Err(unsupported_raised(typ))
+            } else {
+                self.compile_generic(typ, origin, args)
+            };
+            ffi!(Py_XDECREF(args));
+            ffi!(Py_DECREF(origin));
+            return ret;
+        }
+        ffi!(Py_DECREF(origin));
+        if ffi!(PyType_Check(typ)) == 0 {
+            return Err(unsupported(typ));
+        }
+        let tp_flags = tp_flags!(ob_type);
+        if ffi!(PyObject_HasAttrString(
+            typ,
+            c"__pydantic_validator__".as_ptr()
+        )) != 0
+            && ffi!(PyObject_HasAttrString(typ, c"model_fields".as_ptr())) != 0
+        {
+            self.compile_class(typ, Shape::Pydantic)
+        } else if self.lenient {
+            Ok(Kind::Any)
+        } else if ffi!(PyObject_HasAttr(
+            typ,
+            crate::typeref::get_dataclass_fields_str_from_state(unsafe {
+                crate::runtime::current_state()
+            })
+        )) != 0
+        {
+            self.compile_class(typ, Shape::Dataclass)
+        } else if is_subclass_by_flag!(tp_flags, Py_TPFLAGS_TUPLE_SUBCLASS)
+            && ffi!(PyObject_HasAttrString(typ, c"_fields".as_ptr())) != 0
+        {
+            self.compile_class(typ, Shape::NamedTuple)
+        } else if is_subclass_by_flag!(tp_flags, Py_TPFLAGS_DICT_SUBCLASS)
+            && ffi!(PyObject_HasAttrString(typ, c"__required_keys__".as_ptr())) != 0
+        {
+            self.compile_class(typ, Shape::TypedDict)
+        } else {
+            Err(unsupported(typ))
+        }
+    }
+
+    /// The adapter of `typ` if it is registered with
+    /// `register_deserializer()`, which takes precedence over how the type
+    /// is otherwise read.
+    fn compile_adapter(&mut self, typ: *mut PyObject) -> Option<Kind> {
+        if let Some(idx) = self
+            .adapters
+            .iter()
+            .position(|adapter| core::ptr::eq(adapter.cls, typ))
+        {
+            return Some(Kind::Adapter(idx));
+        }
+        let deserializers = unsafe { (*crate::runtime::current_state()).deserializers };
+        if ffi!(Py_SIZE(deserializers)) == 0 || ffi!(PyType_Check(typ)) == 0 {
+            return None;
+        }
+        let callable = ffi!(PyDict_GetItem(deserializers, typ));
+        if callable.is_null() {
+            return None;
+        }
+        let name = ffi!(PyObject_GetAttrString(typ, c"__name__".as_ptr()));
+        let name_str = to_string(name).unwrap_or_default();
+        ffi!(Py_XDECREF(name));
+        if name.is_null() {
+            ffi!(PyErr_Clear());
+        }
+        ffi!(Py_INCREF(typ));
+        ffi!(Py_INCREF(callable));
+        self.adapters.push(Adapter {
+            cls: typ,
+            name: name_str,
+            callable: callable,
+        });
+        Some(Kind::Adapter(self.adapters.len() - 1))
+    }
+
+    /// Look up the `datetime.timezone` and `datetime.timedelta` that values of
+    /// `Kind::Datetime` are built with.
+    fn datetime_builders(&mut self) -> Result<Kind, String> {
+        if self.builders.timezone.is_null() {
+            let timezone = module_attr(c"datetime", c"timezone");
+            let timedelta = module_attr(c"datetime", c"timedelta");
+            let utc = if timezone.is_null() {
+                null_mut()
+            } else {
+                ffi!(PyObject_GetAttrString(timezone, c"utc".as_ptr()))
+            };
+            if utc.is_null() || timedelta.is_null() {
+                ffi!(PyErr_Clear());
+                ffi!(Py_XDECREF(timezone));
+                ffi!(Py_XDECREF(timedelta));
+                ffi!(Py_XDECREF(utc));
+                return Err(String::from(
+                    "loads() type datetime requires datetime.timezone",
+                ));
+            }
+            self.builders.timezone = timezone;
+            self.builders.timedelta = timedelta;
+            self.builders.utc = utc;
+        }
+        Ok(Kind::Datetime)
+    }
+
+    /// Compile `typ`, a generic alias such as `list[int]`, of `origin` and
+    /// `args`.
+    fn compile_generic(
+        &mut self,
+        typ: *mut PyObject,
+        origin: *mut PyObject,
+        args: *mut PyObject,
+    ) -> Result<Kind, String> {
+        let nargs = ffi!(Py_SIZE(args));
+        let arg = |idx: isize| unsafe { crate::ffi::PyTuple_GET_ITEM(args, idx) };
+        let ob_type = origin.cast::<crate::ffi::PyTypeObject>();
+        if core::ptr::eq(origin, self.union)
+            || (!self.union_type.is_null() && core::ptr::eq(origin, self.union_type))
+        {
+            if self.lenient {
+                // the validator chooses the member
+                return Ok(Kind::Any);
+            }
+            let mut members = Vec::with_capacity(isize_to_usize(nargs));
+            for idx in 0..nargs {
+                members.push(self.compile(arg(idx))?);
+            }
+            Ok(Kind::Union(members))
+        } else if core::ptr::eq(origin, self.annotated) {
+            self.compile(arg(0))
+        } else if core::ptr::eq(ob_type, crate::typeref::list_type_ptr()) && nargs == 1 {
+            Ok(Kind::List(Box::new(self.compile(arg(0))?)))
+        } else if core::ptr::eq(ob_type, crate::typeref::tuple_type_ptr()) {
+            if nargs == 2 && core::ptr::eq(arg(1), unsafe { crate::ffi::Py_Ellipsis() }) {
+                return Ok(Kind::Tuple(Box::new(self.compile(arg(0))?)));
+            }
+            let mut items = Vec::with_capacity(isize_to_usize(nargs));
+            for idx in 0..nargs {
+                items.push(self.compile(arg(idx))?);
+            }
+            Ok(Kind::FixedTuple(items))
+        } else if core::ptr::eq(ob_type, crate::typeref::dict_type_ptr())
+            && nargs == 2
+            && (core::ptr::eq(arg(0), crate::typeref::str_type_ptr().cast::<PyObject>())
+                || core::ptr::eq(arg(0), self.any))
+        {
+            Ok(Kind::Dict(Box::new(self.compile(arg(1))?)))
+        } else {
+            Err(unsupported(typ))
+        }
+    }
+
+    fn compile_class(&mut self, cls: *mut PyObject, shape: Shape) -> Result<Kind, String> {
+        if let Some(idx) = self
+            .classes
+            .iter()
+            .position(|class| core::ptr::eq(class.cls, cls))
+        {
+            return Ok(Kind::Class(idx));
+        }
+        let name = ffi!(PyObject_GetAttrString(cls, c"__name__".as_ptr()));
+        let name_str = to_string(name).unwrap_or_default();
+        ffi!(Py_XDECREF(name));
+        if name_str.is_empty() {
+            ffi!(PyErr_Clear());
+            return Err(unsupported(cls));
+        }
+        let idx = self.classes.len();
+        ffi!(Py_INCREF(cls));
+        self.classes.push(ClassPlan {
+            cls: cls,
+            name: name_str,
+            shape: shape,
+            fields: Vec::new(),
+            kwnames: null_mut(),
+            validator: null_mut(),
+        });
+        let fields = if shape == Shape::Pydantic {
+            self.pydantic_fields(idx)?
+        } else {
+            self.annotated_fields(idx)?
+        };
+        let kwnames = ffi!(PyTuple_New(usize_to_isize(fields.len())));
+        for (i, field) in fields.iter().enumerate() {
+            ffi!(Py_INCREF(field.name));
+            ffi!(PyTuple_SET_ITEM(kwnames, usize_to_isize(i), field.name));
+        }
+        let class = &mut self.classes[idx];
+        class.fields = fields;
+        class.kwnames = kwnames;
+        Ok(Kind::Class(idx))
+    }
+
+    /// The fields of the dataclass, `TypedDict`, or `NamedTuple`
+    /// `self.classes[idx]`, each read as annotated, or as `Any` if it is not.
+    fn annotated_fields(&mut self, idx: usize) -> Result<Vec<Field>, String> {
+        let cls = self.classes[idx].cls;
+        let hints = call_one(self.get_type_hints, cls);
+        if hints.is_null() {
+            return Err(format!(
+                "loads() type {} has annotations that cannot be resolved",
+                self.classes[idx].name
+            ));
+        }
+        let ret = match self.classes[idx].shape {
+            Shape::TypedDict => typeddict_fields(cls, hints),
+            Shape::NamedTuple => namedtuple_fields(cls),
+            _ => self.dataclass_fields(cls),
+        }
+        .and_then(|mut fields| {
+            for field in fields.iter_mut() {
+                let hint = unsafe { crate::ffi::PyDict_GetItem(hints, field.name) };
+                if !hint.is_null() {
+                    field.kind = self.compile(hint)?;
+                }
+            }
+            Ok(fields)
+        });
+        ffi!(Py_DECREF(hints));
+        ret
+    }
+
+    /// The fields of the pydantic model `self.classes[idx]`, each keyed by
+    /// its alias, and its validator.
+    fn pydantic_fields(&mut self, idx: usize) -> Result<Vec<Field>, String> {
+        let cls = self.classes[idx].cls;
+        let validator = ffi!(PyObject_GetAttrString(
+            cls,
+            c"__pydantic_validator__".as_ptr()
+        ));
+        if validator.is_null() {
+            return Err(unsupported_raised(cls));
+        }
+        self.classes[idx].validator = ffi!(PyObject_GetAttrString(
+            validator,
+            c"validate_python".as_ptr()
+        ));
+        ffi!(Py_DECREF(validator));
+        let model_fields = ffi!(PyObject_GetAttrString(cls, c"model_fields".as_ptr()));
+        if self.classes[idx].validator.is_null()
+            || model_fields.is_null()
+            || !is_type!(ob_type!(model_fields), crate::typeref::dict_type_ptr())
+        {
+            ffi!(Py_XDECREF(model_fields));
+            return Err(unsupported_raised(cls));
+        }
+        let lenient = core::mem::replace(&mut self.lenient, true);
+        let mut ret = Vec::new();
+        let mut pos = 0;
+        let mut name: *mut PyObject = null_mut();
+        let mut info: *mut PyObject = null_mut();
+        while unsafe { crate::ffi::PyDict_Next(model_fields, &mut pos, &mut name, &mut info) } != 0
+        {
+            let key = pydantic_alias(info).unwrap_or_else(|| {
+                ffi!(Py_INCREF(name));
+                name
+            });
+            let Some(mut field) = Field::new(key, false) else {
+                continue;
+            };
+            // the attribute is the name rather than the alias
+            ffi!(Py_INCREF(name));
+            ffi!(Py_DECREF(field.name));
+            field.name = name;
+            let annotation = ffi!(PyObject_GetAttrString(info, c"annotation".as_ptr()));
+            if annotation.is_null() {
+                ffi!(PyErr_Clear());
+            } else {
+                field.kind = self.compile(annotation).unwrap_or(Kind::Any);
+                ffi!(Py_DECREF(annotation));
+            }
+            ret.push(field);
+        }
+        self.lenient = lenient;
+        ffi!(Py_DECREF(model_fields));
+        Ok(ret)
+    }
+
+    /// The fields of a dataclass that are passed to `__init__`.
+    fn dataclass_fields(&self, cls: *mut PyObject) -> Result<Vec<Field>, String> {
+        let fields = call_one(self.fields, cls);
+        if fields.is_null() {
+            return Err(unsupported_raised(cls));
+        }
+        let mut ret = Vec::new();
+        for i in 0..ffi!(Py_SIZE(fields)) {
+            let field = unsafe { crate::ffi::PyTuple_GET_ITEM(fields, i) };
+            let init = ffi!(PyObject_GetAttrString(field, c"init".as_ptr()));
+            let in_init = core::ptr::eq(init, crate::typeref::true_ptr());
+            ffi!(Py_XDECREF(init));
+            if !in_init {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            let name = ffi!(PyObject_GetAttrString(field, c"name".as_ptr()));
+            let required = !self.has_default(field, c"default")
+                && !self.has_default(field, c"default_factory");
+            match Field::new(name, required) {
+                Some(field) => ret.push(field),
+                None => {
+                    ffi!(Py_DECREF(fields));
+                    return Err(unsupported(cls));
+                }
+            }
+        }
+        ffi!(Py_DECREF(fields));
+        Ok(ret)
+    }
+
+    fn has_default(&self, field: *mut PyObject, attr: &CStr) -> bool {
+        let default = ffi!(PyObject_GetAttrString(field, attr.as_ptr()));
+        if default.is_null() {
+            ffi!(PyErr_Clear());
+            return false;
+        }
+        let ret = !core::ptr::eq(default, self.missing);
+        ffi!(Py_DECREF(default));
+        ret
+    }
+}
+
+impl Drop for Compiler {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.get_origin));
+        ffi!(Py_XDECREF(self.get_args));
+        ffi!(Py_XDECREF(self.get_type_hints));
+        ffi!(Py_XDECREF(self.any));
+        ffi!(Py_XDECREF(self.union));
+        ffi!(Py_XDECREF(self.union_type));
+        ffi!(Py_XDECREF(self.annotated));
+        ffi!(Py_XDECREF(self.fields));
+        ffi!(Py_XDECREF(self.missing));
+    }
+}
+
+/// The `validation_alias` or `alias` of a pydantic `FieldInfo` that is a
+/// `str`, as a new reference.
+fn pydantic_alias(info: *mut PyObject) -> Option<*mut PyObject> {
+    for attr in [c"validation_alias", c"alias"] {
+        let alias = ffi!(PyObject_GetAttrString(info, attr.as_ptr()));
+        if alias.is_null() {
+            ffi!(PyErr_Clear());
+        } else if is_type!(ob_type!(alias), crate::typeref::str_type_ptr()) {
+            return Some(alias);
+        } else {
+            ffi!(Py_DECREF(alias));
+        }
+    }
+    None
+}
+
+/// The keys of a `TypedDict`, which are those annotated, each required if in
+/// `__required_keys__`.
+fn typeddict_fields(cls: *mut PyObject, hints: *mut PyObject) -> Result<Vec<Field>, String> {
+    let required_keys = ffi!(PyObject_GetAttrString(cls, c"__required_keys__".as_ptr()));
+    if required_keys.is_null() {
+        return Err(unsupported_raised(cls));
+    }
+    let mut ret = Vec::new();
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while unsafe { crate::ffi::PyDict_Next(hints, &mut pos, &mut key, &mut value) } != 0 {
+        let required = ffi!(PySequence_Contains(required_keys, key)) == 1;
+        ffi!(Py_INCREF(key));
+        match Field::new(key, required) {
+            Some(field) => ret.push(field),
+            None => {
+                ffi!(Py_DECREF(required_keys));
+                return Err(unsupported(cls));
+            }
+        }
+    }
+    ffi!(Py_DECREF(required_keys));
+    Ok(ret)
+}
+
+/// The fields of a `typing.NamedTuple` or `collections.namedtuple`, in
+/// order, each required if not in `_field_defaults`.
+fn namedtuple_fields(cls: *mut PyObject) -> Result<Vec<Field>, String> {
+    let names = ffi!(PyObject_GetAttrString(cls, c"_fields".as_ptr()));
+    let defaults = ffi!(PyObject_GetAttrString(cls, c"_field_defaults".as_ptr()));
+    if names.is_null()
+        || defaults.is_null()
+        || !is_type!(ob_type!(names), crate::typeref::tuple_type_ptr())
+        || !is_type!(ob_type!(defaults), crate::typeref::dict_type_ptr())
+    {
+        ffi!(Py_XDECREF(names));
+        ffi!(Py_XDECREF(defaults));
+        return Err(unsupported_raised(cls));
+    }
+    let mut ret = Vec::new();
+    for i in 0..ffi!(Py_SIZE(names)) {
+        let name = unsafe { crate::ffi::PyTuple_GET_ITEM(names, i) };
+        let required = ffi!(PyDict_Contains(defaults, name)) == 0;
+        ffi!(Py_INCREF(name));
+        match Field::new(name, required) {
+            Some(field) => ret.push(field),
+            None => {
+                ret.clear();
+                break;
+            }
+        }
+    }
+    let len = ffi!(Py_SIZE(names));
+    ffi!(Py_DECREF(names));
+    ffi!(Py_DECREF(defaults));
+    if isize_to_usize(len) != ret.len() {
+        return Err(unsupported(cls));
+    }
+    Ok(ret)
+}
+
+/// As `unsupported()`, when `typing` raised for `typ`.
+#[cold]
+fn unsupported_raised(typ: *mut PyObject) -> String {
+    ffi!(PyErr_Clear());
+    unsupported(typ)
+}
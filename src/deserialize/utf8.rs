@@ -1,13 +1,14 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2021-2025), Aarni Koskela (2021)
 
-use crate::deserialize::DeserializeError;
+use crate::deserialize::{DeserializeError, DeserializeOptions};
 use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE, PyMemoryView_GET_BUFFER};
 use crate::str::PyStr;
 // BYTEARRAY_TYPE, BYTES_TYPE, MEMORYVIEW_TYPE, STR_TYPE now accessed via typeref accessor functions
 use crate::util::INVALID_STR;
-use crate::util::isize_to_usize;
+use crate::util::{isize_to_usize, usize_to_isize};
 use core::ffi::c_char;
+use smallvec::SmallVec;
 use std::borrow::Cow;
 
 #[cfg(all(target_arch = "x86_64", not(target_feature = "avx2")))]
@@ -100,3 +101,159 @@ pub(crate) fn read_input_to_buf(
         Ok(buffer)
     }
 }
+
+/// The contents of a `bytes`, `bytearray`, or C-contiguous `memoryview`.
+pub(super) fn bytes_like_to_buf(ptr: *mut crate::ffi::PyObject) -> Option<&'static [u8]> {
+    let obj_type_ptr = ob_type!(ptr);
+    if is_type!(obj_type_ptr, crate::typeref::bytes_type_ptr()) {
+        Some(unsafe {
+            core::slice::from_raw_parts(
+                PyBytes_AS_STRING(ptr).cast::<u8>(),
+                isize_to_usize(PyBytes_GET_SIZE(ptr)),
+            )
+        })
+    } else if is_type!(obj_type_ptr, crate::typeref::bytearray_type_ptr()) {
+        Some(unsafe {
+            core::slice::from_raw_parts(
+                ffi!(PyByteArray_AsString(ptr)).cast::<u8>().cast_const(),
+                isize_to_usize(ffi!(PyByteArray_Size(ptr))),
+            )
+        })
+    } else if is_type!(obj_type_ptr, crate::typeref::memoryview_type_ptr()) {
+        let membuf = unsafe { PyMemoryView_GET_BUFFER(ptr) };
+        if unsafe { crate::ffi::PyBuffer_IsContiguous(membuf, b'C' as c_char) == 0 } {
+            return None;
+        }
+        Some(unsafe {
+            core::slice::from_raw_parts(
+                (*membuf).buf.cast::<u8>().cast_const(),
+                isize_to_usize((*membuf).len),
+            )
+        })
+    } else {
+        None
+    }
+}
+
+/// The contents of a bytes-like object or the UTF-8 of a `str`.
+pub(crate) fn read_bytes_or_str(ptr: *mut crate::ffi::PyObject) -> Option<&'static [u8]> {
+    if is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        let as_str = unsafe { PyStr::from_ptr_unchecked(ptr).to_str()? };
+        Some(unsafe { core::slice::from_raw_parts(as_str.as_ptr(), as_str.len()) })
+    } else {
+        bytes_like_to_buf(ptr)
+    }
+}
+
+/// Read a `list` or `tuple` of bytes-like chunks as one document. A chunk
+/// may end within a codepoint, so UTF-8 is validated after the chunks are
+/// joined.
+pub(crate) fn read_chunks_to_buf(
+    ptr: *mut crate::ffi::PyObject,
+) -> Result<Cow<'static, [u8]>, DeserializeError<'static>> {
+    let buffer = join_chunks(ptr)?;
+    if !is_valid_utf8(&buffer) {
+        return Err(DeserializeError::invalid(Cow::Borrowed(INVALID_STR)));
+    }
+    Ok(buffer)
+}
+
+/// Join a `list` or `tuple` of bytes-like chunks. A single chunk is
+/// borrowed rather than copied.
+fn join_chunks(
+    ptr: *mut crate::ffi::PyObject,
+) -> Result<Cow<'static, [u8]>, DeserializeError<'static>> {
+    let len = isize_to_usize(ffi!(Py_SIZE(ptr)));
+    let is_list = is_type!(ob_type!(ptr), crate::typeref::list_type_ptr());
+    let mut chunks: SmallVec<[&'static [u8]; 8]> = SmallVec::with_capacity(len);
+    let mut total = 0;
+    for idx in 0..len {
+        #[allow(clippy::cast_possible_wrap)]
+        let idx = idx as isize;
+        let item = if is_list {
+            ffi!(PyList_GET_ITEM(ptr, idx))
+        } else {
+            unsafe { crate::ffi::PyTuple_GET_ITEM(ptr, idx) }
+        };
+        match bytes_like_to_buf(item) {
+            Some(chunk) => {
+                total += chunk.len();
+                chunks.push(chunk);
+            }
+            None => {
+                return Err(DeserializeError::invalid(Cow::Borrowed(
+                    "Input chunks must be bytes, bytearray, or C contiguous memoryview",
+                )));
+            }
+        }
+    }
+    let buffer = if chunks.len() == 1 {
+        Cow::Borrowed(chunks[0])
+    } else {
+        let mut joined = Vec::with_capacity(total);
+        for chunk in chunks.iter() {
+            joined.extend_from_slice(chunk);
+        }
+        Cow::Owned(joined)
+    };
+    if buffer.is_empty() {
+        cold_path!();
+        return Err(DeserializeError::invalid(Cow::Borrowed(
+            "Input is a zero-length, empty document",
+        )));
+    }
+    Ok(buffer)
+}
+
+/// Read `ptr` as `read_input_to_buf()` or `read_chunks_to_buf()` do, but
+/// with bytes decoded from the codec `options.encoding`, if set, rather than
+/// UTF-8, and what is not valid in it replaced with U+FFFD if
+/// `options.replace_invalid`. A `str` is read as is.
+#[cold]
+pub(crate) fn read_encoded_to_buf(
+    ptr: *mut crate::ffi::PyObject,
+    options: &DeserializeOptions,
+) -> Result<Cow<'static, [u8]>, DeserializeError<'static>> {
+    let raw = if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr())
+        || is_type!(ob_type!(ptr), crate::typeref::tuple_type_ptr())
+    {
+        join_chunks(ptr)?
+    } else {
+        match bytes_like_to_buf(ptr) {
+            Some(buffer) if !buffer.is_empty() => Cow::Borrowed(buffer),
+            // a `str`, or input that is not read
+            _ => return read_input_to_buf(ptr).map(Cow::Borrowed),
+        }
+    };
+    let Some(encoding) = options.encoding else {
+        if is_valid_utf8(&raw) {
+            return Ok(raw);
+        }
+        return Ok(Cow::Owned(
+            String::from_utf8_lossy(&raw).into_owned().into_bytes(),
+        ));
+    };
+    let name = unsafe { PyStr::from_ptr_unchecked(encoding.as_ptr()).to_str() }.unwrap_or("");
+    let decoded = unsafe {
+        crate::ffi::PyUnicode_Decode(
+            raw.as_ptr().cast::<c_char>(),
+            usize_to_isize(raw.len()),
+            crate::ffi::PyUnicode_AsUTF8AndSize(encoding.as_ptr(), core::ptr::null_mut()),
+            if options.replace_invalid {
+                c"replace".as_ptr()
+            } else {
+                c"strict".as_ptr()
+            },
+        )
+    };
+    if decoded.is_null() {
+        ffi!(PyErr_Clear());
+        return Err(DeserializeError::invalid(Cow::Owned(format!(
+            "Input is not valid {name}"
+        ))));
+    }
+    let buffer = unsafe { PyStr::from_ptr_unchecked(decoded).to_str() }
+        .map(|as_str| Cow::Owned(as_str.as_bytes().to_vec()));
+    ffi!(Py_DECREF(decoded));
+    buffer.ok_or_else(|| DeserializeError::invalid(Cow::Borrowed(INVALID_STR)))
+}
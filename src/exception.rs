@@ -6,8 +6,8 @@ use core::ptr::null_mut;
 
 use crate::deserialize::DeserializeError;
 use crate::ffi::{
-    Py_DECREF, PyErr_SetObject, PyLong_FromLongLong, PyObject, PyTuple_New,
-    PyUnicode_FromStringAndSize,
+    Py_DECREF, PyErr_SetObject, PyLong_FromLongLong, PyObject, PyObject_Call,
+    PyObject_SetAttrString, PyTuple_New, PyUnicode_FromStringAndSize,
 };
 // EMPTY_UNICODE, JsonDecodeError, JsonEncodeError now accessed via typeref accessor functions
 use crate::util::usize_to_isize;
@@ -16,10 +16,13 @@ use crate::util::usize_to_isize;
 #[inline(never)]
 #[cfg_attr(feature = "optimize", optimize(size))]
 pub(crate) fn raise_loads_exception(err: DeserializeError) -> *mut PyObject {
+    if err.raised {
+        return null_mut();
+    }
     unsafe {
         let err_pos = err.pos();
         let msg = err.message;
-        let doc = match err.data {
+        let doc = match err.data.as_deref() {
             Some(as_str) => PyUnicode_FromStringAndSize(
                 as_str.as_ptr().cast::<c_char>(),
                 usize_to_isize(as_str.len()),
@@ -35,7 +38,22 @@ pub(crate) fn raise_loads_exception(err: DeserializeError) -> *mut PyObject {
         crate::ffi::PyTuple_SET_ITEM(args, 0, err_msg);
         crate::ffi::PyTuple_SET_ITEM(args, 1, doc);
         crate::ffi::PyTuple_SET_ITEM(args, 2, pos);
-        PyErr_SetObject(crate::typeref::get_json_decode_error(), args);
+        match err.code {
+            None => PyErr_SetObject(crate::typeref::get_json_decode_error(), args),
+            Some(code) => {
+                // reading a `str` input may have left a `UnicodeEncodeError`
+                crate::ffi::PyErr_Clear();
+                let exc = PyObject_Call(crate::typeref::get_json_decode_error(), args, null_mut());
+                let code = PyUnicode_FromStringAndSize(
+                    code.as_ptr().cast::<c_char>(),
+                    usize_to_isize(code.len()),
+                );
+                PyObject_SetAttrString(exc, c"code".as_ptr(), code);
+                Py_DECREF(code);
+                PyErr_SetObject(crate::typeref::get_json_decode_error(), exc);
+                Py_DECREF(exc);
+            }
+        }
         Py_DECREF(args);
     }
     null_mut()
@@ -58,15 +76,23 @@ pub(crate) fn raise_dumps_exception_fixed(msg: &str) -> *mut PyObject {
 #[cold]
 #[inline(never)]
 #[cfg_attr(feature = "optimize", optimize(size))]
-#[cfg(Py_3_12)]
 pub(crate) fn raise_dumps_exception_dynamic(err: &str) -> *mut PyObject {
+    raise_dumps_exception_as(err, crate::typeref::get_json_encode_error())
+}
+
+/// Raise `exc_class` with the message `err`, chaining any exception that is
+/// set, such as one raised by `default`, as its cause.
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+#[cfg(Py_3_12)]
+pub(crate) fn raise_dumps_exception_as(err: &str, exc_class: *mut PyObject) -> *mut PyObject {
     unsafe {
         let cause_exc: *mut PyObject = crate::ffi::PyErr_GetRaisedException();
 
         let err_msg =
             PyUnicode_FromStringAndSize(err.as_ptr().cast::<c_char>(), usize_to_isize(err.len()));
-        PyErr_SetObject(crate::typeref::get_json_encode_error(), err_msg);
-        debug_assert!(ffi!(Py_REFCNT(err_msg)) <= 2);
+        PyErr_SetObject(exc_class, err_msg);
         Py_DECREF(err_msg);
 
         if !cause_exc.is_null() {
@@ -82,7 +108,7 @@ pub(crate) fn raise_dumps_exception_dynamic(err: &str) -> *mut PyObject {
 #[inline(never)]
 #[cfg_attr(feature = "optimize", optimize(size))]
 #[cfg(not(Py_3_12))]
-pub(crate) fn raise_dumps_exception_dynamic(err: &str) -> *mut PyObject {
+pub(crate) fn raise_dumps_exception_as(err: &str, exc_class: *mut PyObject) -> *mut PyObject {
     unsafe {
         let mut cause_tp: *mut PyObject = null_mut();
         let mut cause_val: *mut PyObject = null_mut();
@@ -91,8 +117,7 @@ pub(crate) fn raise_dumps_exception_dynamic(err: &str) -> *mut PyObject {
 
         let err_msg =
             PyUnicode_FromStringAndSize(err.as_ptr().cast::<c_char>(), usize_to_isize(err.len()));
-        PyErr_SetObject(crate::typeref::get_json_encode_error(), err_msg);
-        debug_assert!(ffi!(Py_REFCNT(err_msg)) == 2);
+        PyErr_SetObject(exc_class, err_msg);
         Py_DECREF(err_msg);
         let mut tp: *mut PyObject = null_mut();
         let mut val: *mut PyObject = null_mut();
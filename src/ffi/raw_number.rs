@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use core::ptr::null_mut;
+use pyo3_ffi::{
+    Py_DECREF, Py_INCREF, PyDict_New, PyDict_SetItemString, PyObject, PyObject_CallFunctionObjArgs,
+    PyTuple_New, PyTuple_SET_ITEM, PyType_Type, PyTypeObject, PyUnicode_InternFromString,
+    PyUnicode_Type,
+};
+
+/// Create `hyperjson.RawNumber`, a `str` subclass without a `__dict__`
+/// holding the text of a number as read by `loads(raw_numbers=True)`.
+///
+/// It is created by calling `type()` so that it has the layout of `str`.
+#[cold]
+#[inline(never)]
+pub(crate) unsafe fn raw_number_type_new() -> *mut PyTypeObject {
+    unsafe {
+        let name = PyUnicode_InternFromString(c"RawNumber".as_ptr());
+        let bases = PyTuple_New(1);
+        Py_INCREF((&raw mut PyUnicode_Type).cast::<PyObject>());
+        PyTuple_SET_ITEM(bases, 0, (&raw mut PyUnicode_Type).cast::<PyObject>());
+        let dict = PyDict_New();
+        let module = PyUnicode_InternFromString(c"hyperjson".as_ptr());
+        PyDict_SetItemString(dict, c"__module__".as_ptr(), module);
+        Py_DECREF(module);
+        let doc = PyUnicode_InternFromString(
+            c"The text of a JSON number, written as it is by dumps().".as_ptr(),
+        );
+        PyDict_SetItemString(dict, c"__doc__".as_ptr(), doc);
+        Py_DECREF(doc);
+        let slots = PyTuple_New(0);
+        PyDict_SetItemString(dict, c"__slots__".as_ptr(), slots);
+        Py_DECREF(slots);
+        let tp = PyObject_CallFunctionObjArgs(
+            (&raw mut PyType_Type).cast::<PyObject>(),
+            name,
+            bases,
+            dict,
+            null_mut::<PyObject>(),
+        );
+        Py_DECREF(name);
+        Py_DECREF(bases);
+        Py_DECREF(dict);
+        debug_assert!(!tp.is_null());
+        tp.cast::<PyTypeObject>()
+    }
+}
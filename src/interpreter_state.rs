@@ -3,19 +3,21 @@
 
 //! Per-interpreter state management for subinterpreter support.
 //!
-//! This module manages interpreter-specific state to support Python 3.14 subinterpreters.
-//! Each interpreter has its own instance of all PyObject pointers and caches.
+//! This module defines interpreter-specific state to support Python 3.14 subinterpreters.
+//! Each interpreter has its own instance of all PyObject pointers and caches,
+//! owned by the registry in `runtime`.
 
 use core::ffi::CStr;
 use core::ptr::null_mut;
-use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
 
 use crate::deserialize::cache::KeyCache;
 use crate::ffi::{
-    Py_DECREF, Py_INCREF, Py_XDECREF, PyErr_NewException, PyExc_TypeError, PyImport_ImportModule,
-    PyMapping_GetItemString, PyObject, PyObject_GenericGetDict, PyTypeObject,
-    PyUnicode_InternFromString, PyUnicode_New, orjson_fragmenttype_new,
+    Py_DECREF, Py_INCREF, Py_XDECREF, PyDict_New, PyDict_SetItemString, PyErr_NewException,
+    PyExc_TypeError, PyImport_ImportModule, PyMapping_GetItemString, PyObject,
+    PyObject_GenericGetDict, PyTypeObject, PyUnicode_InternFromString, PyUnicode_New,
+    orjson_fragmenttype_new,
 };
 
 /// Per-interpreter state containing all interpreter-specific PyObject pointers and caches.
@@ -67,7 +69,9 @@ impl ParseBuffer {
 impl Drop for ParseBuffer {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
-            unsafe { crate::ffi::PyMem_Free(self.ptr) };
+            unsafe {
+                crate::ffi::PyMem_Free(self.ptr);
+            }
         }
     }
 }
@@ -79,8 +83,52 @@ impl Drop for ParseBuffer {
 /// Only truly per-interpreter data is stored here.
 pub(crate) struct InterpreterState {
     // Keyword argument strings (interned per-interpreter)
+    pub algo: *mut PyObject,
     pub default: *mut PyObject,
+    pub exc_class: *mut PyObject,
+    pub float_decimals: *mut PyObject,
+    pub key_order: *mut PyObject,
+    pub float_format: *mut PyObject,
+    pub non_finite: *mut PyObject,
+    pub length: *mut PyObject,
     pub option: *mut PyObject,
+    pub rename: *mut PyObject,
+    pub allow_nan: *mut PyObject,
+    pub json5: *mut PyObject,
+    pub allow_comments: *mut PyObject,
+    pub allow_trailing_commas: *mut PyObject,
+    pub duplicate_keys: *mut PyObject,
+    pub strict: *mut PyObject,
+    pub object_pairs_hook: *mut PyObject,
+    pub parse_float: *mut PyObject,
+    pub parse_int: *mut PyObject,
+    pub use_decimal: *mut PyObject,
+    pub raw_numbers: *mut PyObject,
+    pub int_overflow: *mut PyObject,
+    pub exact_floats: *mut PyObject,
+    pub validate: *mut PyObject,
+    pub width: *mut PyObject,
+    pub datetime64_range: *mut PyObject,
+    pub field_order: *mut PyObject,
+    pub pandas_orient: *mut PyObject,
+    pub serialize_arrow: *mut PyObject,
+    pub json_schema: *mut PyObject,
+    pub typ: *mut PyObject,
+    pub strict_types: *mut PyObject,
+    pub tagged: *mut PyObject,
+    pub sep: *mut PyObject,
+    pub indent: *mut PyObject,
+    pub algorithm: *mut PyObject,
+    pub extended_json: *mut PyObject,
+    pub sort_keys: *mut PyObject,
+    pub separators: *mut PyObject,
+    pub ensure_ascii: *mut PyObject,
+    pub cls: *mut PyObject,
+    pub object_hook: *mut PyObject,
+    pub encoding: *mut PyObject,
+    pub errors: *mut PyObject,
+    pub type_key_str: *mut PyObject,
+    pub reset: *mut PyObject,
 
     // Empty string singleton (per-interpreter)
     pub empty_unicode: *mut PyObject,
@@ -94,6 +142,11 @@ pub(crate) struct InterpreterState {
     pub enum_type: *mut PyTypeObject,
     pub field_type: *mut PyTypeObject,
     pub fragment_type: *mut PyTypeObject,
+    pub builder_type: *mut PyTypeObject,
+    pub lines_writer_type: *mut PyTypeObject,
+    pub editor_type: *mut PyTypeObject,
+    pub schema_type: *mut PyTypeObject,
+    pub raw_number_type: *mut PyTypeObject,
     pub zoneinfo_type: *mut PyTypeObject,
 
     // Interned strings (per-interpreter)
@@ -104,12 +157,52 @@ pub(crate) struct InterpreterState {
     pub dict_str: *mut PyObject,
     pub dataclass_fields_str: *mut PyObject,
     pub slots_str: *mut PyObject,
+    pub namedtuple_fields_str: *mut PyObject,
     pub field_type_str: *mut PyObject,
     pub array_struct_str: *mut PyObject,
     pub dtype_str: *mut PyObject,
     pub descr_str: *mut PyObject,
+    pub mask_str: *mut PyObject,
+    pub items_str: *mut PyObject,
+    pub to_numpy_str: *mut PyObject,
+    pub arrow_c_array_str: *mut PyObject,
+    pub arrow_c_stream_str: *mut PyObject,
+    pub dlpack_str: *mut PyObject,
+    pub array_interface_str: *mut PyObject,
     pub value_str: *mut PyObject,
     pub int_attr_str: *mut PyObject,
+    pub write_str: *mut PyObject,
+
+    // Callables registered with register_serializer(), keyed by type
+    pub serializers: *mut PyObject,
+
+    // Callables registered with register_deserializer(), keyed by type
+    pub deserializers: *mut PyObject,
+
+    // Classes registered with register_tagged(): the tag of each type, and
+    // the type of each tag
+    pub tags_by_type: *mut PyObject,
+    pub types_by_tag: *mut PyObject,
+
+    // The DataclassPlan capsule of each class registered with
+    // register_type(), keyed by type
+    pub type_plans: *mut PyObject,
+
+    // numpy types, looked up on first use; None if numpy is not installed
+    pub numpy_types: OnceLock<Option<crate::typeref::NumpyTypes>>,
+
+    // pandas types, looked up on first use by dumps(pandas_orient=...); None
+    // if pandas is not installed
+    pub pandas_types: OnceLock<Option<crate::typeref::PandasTypes>>,
+
+    // decimal.Decimal, looked up on first use by loads(use_decimal=True);
+    // null if decimal cannot be imported
+    pub decimal_type: OnceLock<*mut PyObject>,
+
+    // json.JSONEncoder and json.JSONDecoder, looked up on first use by
+    // cls=; null if json cannot be imported
+    pub json_encoder_type: OnceLock<*mut PyObject>,
+    pub json_decoder_type: OnceLock<*mut PyObject>,
 
     // Exception types (per-interpreter)
     pub json_encode_error: *mut PyObject,
@@ -124,6 +217,19 @@ pub(crate) struct InterpreterState {
     // Pre-allocated buffer for yyjson parsing - avoids malloc/free per parse
     // Safe because GIL ensures single-threaded access
     pub parse_buffer: core::cell::UnsafeCell<ParseBuffer>,
+
+    // Results of loads_tracked() that dumps() may write as their input
+    pub decoded: crate::provenance::DecodedRegistry,
+
+    // Counts for hyperjson.stats(), see crate::stats
+    pub stats: crate::stats::Stats,
+
+    // loads() builds and dumps() reads containers with the public C API
+    // only, from hyperjson.set_compat_mode()
+    pub compat_mode: AtomicBool,
+
+    // Number of serializations in progress, see runtime::Serializing
+    pub serializing: AtomicUsize,
 }
 
 unsafe fn look_up_type_object(module_name: &CStr, member_name: &CStr) -> *mut PyTypeObject {
@@ -182,11 +288,53 @@ impl InterpreterState {
     #[cfg_attr(feature = "optimize", optimize(size))]
     pub(crate) unsafe fn new() -> Self {
         unsafe {
-            debug_assert!(crate::opt::MAX_OPT < i32::from(u16::MAX));
-
             let mut state = InterpreterState {
+                algo: null_mut(),
                 default: null_mut(),
+                exc_class: null_mut(),
+                float_decimals: null_mut(),
+                key_order: null_mut(),
+                float_format: null_mut(),
+                non_finite: null_mut(),
+                length: null_mut(),
                 option: null_mut(),
+                rename: null_mut(),
+                allow_nan: null_mut(),
+                json5: null_mut(),
+                allow_comments: null_mut(),
+                allow_trailing_commas: null_mut(),
+                duplicate_keys: null_mut(),
+                strict: null_mut(),
+                object_pairs_hook: null_mut(),
+                parse_float: null_mut(),
+                parse_int: null_mut(),
+                use_decimal: null_mut(),
+                raw_numbers: null_mut(),
+                int_overflow: null_mut(),
+                exact_floats: null_mut(),
+                validate: null_mut(),
+                width: null_mut(),
+                datetime64_range: null_mut(),
+                field_order: null_mut(),
+                pandas_orient: null_mut(),
+                serialize_arrow: null_mut(),
+                json_schema: null_mut(),
+                typ: null_mut(),
+                strict_types: null_mut(),
+                tagged: null_mut(),
+                sep: null_mut(),
+                indent: null_mut(),
+                algorithm: null_mut(),
+                extended_json: null_mut(),
+                sort_keys: null_mut(),
+                separators: null_mut(),
+                ensure_ascii: null_mut(),
+                cls: null_mut(),
+                object_hook: null_mut(),
+                encoding: null_mut(),
+                errors: null_mut(),
+                type_key_str: null_mut(),
+                reset: null_mut(),
                 empty_unicode: PyUnicode_New(0, 255),
                 // Dynamic types - looked up from external modules
                 datetime_type: null_mut(),
@@ -196,6 +344,11 @@ impl InterpreterState {
                 enum_type: null_mut(),
                 field_type: null_mut(),
                 fragment_type: null_mut(),
+                builder_type: null_mut(),
+                lines_writer_type: null_mut(),
+                editor_type: null_mut(),
+                schema_type: null_mut(),
+                raw_number_type: null_mut(),
                 zoneinfo_type: null_mut(),
                 // Interned strings
                 utcoffset_method_str: null_mut(),
@@ -205,12 +358,31 @@ impl InterpreterState {
                 dict_str: null_mut(),
                 dataclass_fields_str: null_mut(),
                 slots_str: null_mut(),
+                namedtuple_fields_str: null_mut(),
                 field_type_str: null_mut(),
                 array_struct_str: null_mut(),
                 dtype_str: null_mut(),
                 descr_str: null_mut(),
+                mask_str: null_mut(),
+                items_str: null_mut(),
+                to_numpy_str: null_mut(),
+                arrow_c_array_str: null_mut(),
+                arrow_c_stream_str: null_mut(),
+                dlpack_str: null_mut(),
+                array_interface_str: null_mut(),
                 value_str: null_mut(),
                 int_attr_str: null_mut(),
+                write_str: null_mut(),
+                serializers: PyDict_New(),
+                deserializers: PyDict_New(),
+                tags_by_type: PyDict_New(),
+                types_by_tag: PyDict_New(),
+                type_plans: PyDict_New(),
+                numpy_types: OnceLock::new(),
+                pandas_types: OnceLock::new(),
+                decimal_type: OnceLock::new(),
+                json_encoder_type: OnceLock::new(),
+                json_decoder_type: OnceLock::new(),
                 // Exceptions
                 json_encode_error: null_mut(),
                 json_decode_error: null_mut(),
@@ -218,6 +390,10 @@ impl InterpreterState {
                 #[cfg(not(Py_GIL_DISABLED))]
                 key_map: core::cell::UnsafeCell::new(Box::new(KeyCache::new())),
                 parse_buffer: core::cell::UnsafeCell::new(ParseBuffer::new()),
+                decoded: crate::provenance::DecodedRegistry::new(),
+                stats: crate::stats::Stats::new(),
+                compat_mode: AtomicBool::new(false),
+                serializing: AtomicUsize::new(0),
             };
 
             // Look up types from external modules
@@ -233,6 +409,11 @@ impl InterpreterState {
             state.field_type = look_up_type_object(c"dataclasses", c"_FIELD");
 
             state.fragment_type = orjson_fragmenttype_new();
+            state.builder_type = crate::serialize::builder_type_new();
+            state.lines_writer_type = crate::serialize::lines_writer_type_new();
+            state.editor_type = crate::serialize::editor_type_new();
+            state.schema_type = crate::serialize::schema_type_new();
+            state.raw_number_type = crate::ffi::raw_number_type_new();
 
             state.int_attr_str = PyUnicode_InternFromString(c"int".as_ptr());
             state.utcoffset_method_str = PyUnicode_InternFromString(c"utcoffset".as_ptr());
@@ -243,98 +424,221 @@ impl InterpreterState {
             state.dataclass_fields_str =
                 PyUnicode_InternFromString(c"__dataclass_fields__".as_ptr());
             state.slots_str = PyUnicode_InternFromString(c"__slots__".as_ptr());
+            state.namedtuple_fields_str = PyUnicode_InternFromString(c"_fields".as_ptr());
             state.field_type_str = PyUnicode_InternFromString(c"_field_type".as_ptr());
             state.array_struct_str = PyUnicode_InternFromString(c"__array_struct__".as_ptr());
             state.dtype_str = PyUnicode_InternFromString(c"dtype".as_ptr());
             state.descr_str = PyUnicode_InternFromString(c"descr".as_ptr());
+            state.mask_str = PyUnicode_InternFromString(c"mask".as_ptr());
+            state.items_str = PyUnicode_InternFromString(c"items".as_ptr());
+            state.to_numpy_str = PyUnicode_InternFromString(c"to_numpy".as_ptr());
+            state.arrow_c_array_str = PyUnicode_InternFromString(c"__arrow_c_array__".as_ptr());
+            state.arrow_c_stream_str = PyUnicode_InternFromString(c"__arrow_c_stream__".as_ptr());
+            state.dlpack_str = PyUnicode_InternFromString(c"__dlpack__".as_ptr());
+            state.array_interface_str = PyUnicode_InternFromString(c"__array_interface__".as_ptr());
             state.value_str = PyUnicode_InternFromString(c"value".as_ptr());
+            state.write_str = PyUnicode_InternFromString(c"write".as_ptr());
+            state.algo = PyUnicode_InternFromString(c"algo".as_ptr());
             state.default = PyUnicode_InternFromString(c"default".as_ptr());
+            state.exc_class = PyUnicode_InternFromString(c"exc_class".as_ptr());
+            state.float_decimals = PyUnicode_InternFromString(c"float_decimals".as_ptr());
+            state.key_order = PyUnicode_InternFromString(c"key_order".as_ptr());
+            state.float_format = PyUnicode_InternFromString(c"float_format".as_ptr());
+            state.non_finite = PyUnicode_InternFromString(c"non_finite".as_ptr());
+            state.length = PyUnicode_InternFromString(c"length".as_ptr());
             state.option = PyUnicode_InternFromString(c"option".as_ptr());
+            state.rename = PyUnicode_InternFromString(c"rename".as_ptr());
+            state.allow_nan = PyUnicode_InternFromString(c"allow_nan".as_ptr());
+            state.json5 = PyUnicode_InternFromString(c"json5".as_ptr());
+            state.allow_comments = PyUnicode_InternFromString(c"allow_comments".as_ptr());
+            state.allow_trailing_commas =
+                PyUnicode_InternFromString(c"allow_trailing_commas".as_ptr());
+            state.duplicate_keys = PyUnicode_InternFromString(c"duplicate_keys".as_ptr());
+            state.strict = PyUnicode_InternFromString(c"strict".as_ptr());
+            state.object_pairs_hook = PyUnicode_InternFromString(c"object_pairs_hook".as_ptr());
+            state.parse_float = PyUnicode_InternFromString(c"parse_float".as_ptr());
+            state.parse_int = PyUnicode_InternFromString(c"parse_int".as_ptr());
+            state.use_decimal = PyUnicode_InternFromString(c"use_decimal".as_ptr());
+            state.raw_numbers = PyUnicode_InternFromString(c"raw_numbers".as_ptr());
+            state.int_overflow = PyUnicode_InternFromString(c"int_overflow".as_ptr());
+            state.exact_floats = PyUnicode_InternFromString(c"exact_floats".as_ptr());
+            state.validate = PyUnicode_InternFromString(c"validate".as_ptr());
+            state.width = PyUnicode_InternFromString(c"width".as_ptr());
+            state.datetime64_range = PyUnicode_InternFromString(c"datetime64_range".as_ptr());
+            state.field_order = PyUnicode_InternFromString(c"field_order".as_ptr());
+            state.pandas_orient = PyUnicode_InternFromString(c"pandas_orient".as_ptr());
+            state.serialize_arrow = PyUnicode_InternFromString(c"serialize_arrow".as_ptr());
+            state.json_schema = PyUnicode_InternFromString(c"json_schema".as_ptr());
+            state.typ = PyUnicode_InternFromString(c"type".as_ptr());
+            state.strict_types = PyUnicode_InternFromString(c"strict_types".as_ptr());
+            state.tagged = PyUnicode_InternFromString(c"tagged".as_ptr());
+            state.sep = PyUnicode_InternFromString(c"sep".as_ptr());
+            state.indent = PyUnicode_InternFromString(c"indent".as_ptr());
+            state.algorithm = PyUnicode_InternFromString(c"algorithm".as_ptr());
+            state.extended_json = PyUnicode_InternFromString(c"extended_json".as_ptr());
+            state.sort_keys = PyUnicode_InternFromString(c"sort_keys".as_ptr());
+            state.separators = PyUnicode_InternFromString(c"separators".as_ptr());
+            state.ensure_ascii = PyUnicode_InternFromString(c"ensure_ascii".as_ptr());
+            state.cls = PyUnicode_InternFromString(c"cls".as_ptr());
+            state.object_hook = PyUnicode_InternFromString(c"object_hook".as_ptr());
+            state.encoding = PyUnicode_InternFromString(c"encoding".as_ptr());
+            state.errors = PyUnicode_InternFromString(c"errors".as_ptr());
+            state.type_key_str = PyUnicode_InternFromString(c"__type__".as_ptr());
+            state.reset = PyUnicode_InternFromString(c"reset".as_ptr());
 
             state.json_encode_error = PyExc_TypeError;
             Py_INCREF(state.json_encode_error);
             let json_jsondecodeerror =
                 look_up_type_object(c"json", c"JSONDecodeError").cast::<PyObject>();
             debug_assert!(!json_jsondecodeerror.is_null());
+            // `code` is set on errors of loads(strict=True)
+            let json_decode_error_dict = PyDict_New();
+            PyDict_SetItemString(
+                json_decode_error_dict,
+                c"code".as_ptr(),
+                crate::typeref::none_ptr(),
+            );
             state.json_decode_error = PyErr_NewException(
                 c"hyperjson.JSONDecodeError".as_ptr(),
                 json_jsondecodeerror,
-                null_mut(),
+                json_decode_error_dict,
             );
             debug_assert!(!state.json_decode_error.is_null());
             Py_XDECREF(json_jsondecodeerror);
+            Py_DECREF(json_decode_error_dict);
 
             state
         }
     }
 }
 
-/// Global registry of interpreter states, keyed by module pointer (as usize for Send+Sync).
-/// Each interpreter has its own module instance, so we use the module pointer as the key.
-/// Using usize is safe because we only compare pointers, never dereference them.
-static INTERPRETER_STATES: OnceLock<Mutex<HashMap<usize, Box<InterpreterState>>>> = OnceLock::new();
-
-/// Get or create the interpreter state for the given module.
-/// The module pointer uniquely identifies the interpreter.
-#[inline(always)]
-pub(crate) unsafe fn get_or_init_state(module: *mut PyObject) -> *const InterpreterState {
-    unsafe {
-        let states = INTERPRETER_STATES.get_or_init(|| Mutex::new(HashMap::new()));
-        let mut guard = states.lock().unwrap();
-
-        // Use entry API for efficient lookup/insert
-        // Convert pointer to usize for HashMap key (safe for comparison only)
-        let module_key = module as usize;
-        let state_ptr = guard
-            .entry(module_key)
-            .or_insert_with(|| Box::new(InterpreterState::new()))
-            .as_ref() as *const InterpreterState;
-
-        // Leak the pointer - the state lives as long as the interpreter
-        state_ptr
+impl InterpreterState {
+    /// Clear the registered serializers, deserializers, tagged types, and
+    /// caches and forget the numpy and pandas types so that they are looked
+    /// up again.
+    ///
+    /// Nothing may be serializing or deserializing.
+    #[cold]
+    pub(crate) unsafe fn reset(&mut self) {
+        unsafe {
+            crate::ffi::PyDict_Clear(self.serializers);
+            crate::ffi::PyDict_Clear(self.deserializers);
+            crate::ffi::PyDict_Clear(self.tags_by_type);
+            crate::ffi::PyDict_Clear(self.types_by_tag);
+            crate::ffi::PyDict_Clear(self.type_plans);
+            self.numpy_types = OnceLock::new();
+            self.pandas_types = OnceLock::new();
+            #[cfg(not(Py_GIL_DISABLED))]
+            self.key_map.get_mut().clear();
+            *self.parse_buffer.get_mut() = ParseBuffer::new();
+            self.decoded.clear();
+            self.stats.clear();
+            self.compat_mode.store(false, Ordering::Relaxed);
+        }
     }
-}
 
-thread_local! {
-    // Cache interpreter ID and state pointer for fast access
-    // Using interpreter ID is much cheaper than PyImport_ImportModule
-    static CACHED_INTERP_ID: std::cell::Cell<i64> = const { std::cell::Cell::new(-1) };
-    static CACHED_STATE: std::cell::Cell<*const InterpreterState> =
-        const { std::cell::Cell::new(null_mut()) };
+    /// Make the state usable in the child of a `fork()`, where only the
+    /// thread that forked exists.
+    #[cold]
+    pub(crate) fn after_fork_in_child(&mut self) {
+        self.decoded.after_fork_in_child();
+    }
 }
 
-/// Get the current interpreter's state.
-///
-/// Uses thread-local caching with interpreter ID for fast detection.
-/// PyInterpreterState_GetID is much cheaper than PyImport_ImportModule.
-#[inline(always)]
-pub(crate) unsafe fn get_current_state() -> *const InterpreterState {
-    unsafe {
-        // Get current interpreter ID - this is very fast
-        let interp = crate::ffi::PyInterpreterState_Get();
-        let interp_id = crate::ffi::PyInterpreterState_GetID(interp);
-
-        // Check if we're in the same interpreter as cached
-        let cached_id = CACHED_INTERP_ID.with(|cell| cell.get());
-        if cached_id == interp_id {
-            // Same interpreter - use cached state
-            return CACHED_STATE.with(|cell| cell.get());
-        }
-
-        // Different interpreter or first call - look up state via module import
-        let module = PyImport_ImportModule(c"hyperjson".as_ptr());
-        if module.is_null() {
-            core::hint::unreachable_unchecked();
+impl Drop for InterpreterState {
+    /// Release the objects created or looked up by `new()`. The key cache,
+    /// parse buffer, and `loads_tracked()` results release theirs when
+    /// dropped. The datetime types are borrowed from its C API on CPython
+    /// and the numpy types from the module. `Fragment`, `Builder`, and
+    /// `LinesWriter` are static types and `RawNumber` a heap type.
+    #[cold]
+    fn drop(&mut self) {
+        let owned = [
+            self.algo,
+            self.default,
+            self.exc_class,
+            self.float_decimals,
+            self.key_order,
+            self.float_format,
+            self.non_finite,
+            self.length,
+            self.option,
+            self.rename,
+            self.allow_nan,
+            self.json5,
+            self.allow_comments,
+            self.allow_trailing_commas,
+            self.duplicate_keys,
+            self.strict,
+            self.object_pairs_hook,
+            self.parse_float,
+            self.parse_int,
+            self.use_decimal,
+            self.raw_numbers,
+            self.int_overflow,
+            self.exact_floats,
+            self.validate,
+            self.width,
+            self.datetime64_range,
+            self.field_order,
+            self.pandas_orient,
+            self.serialize_arrow,
+            self.json_schema,
+            self.typ,
+            self.strict_types,
+            self.tagged,
+            self.sep,
+            self.indent,
+            self.algorithm,
+            self.extended_json,
+            self.sort_keys,
+            self.separators,
+            self.ensure_ascii,
+            self.cls,
+            self.object_hook,
+            self.encoding,
+            self.errors,
+            self.type_key_str,
+            self.reset,
+            self.empty_unicode,
+            self.uuid_type.cast::<PyObject>(),
+            self.enum_type.cast::<PyObject>(),
+            self.field_type.cast::<PyObject>(),
+            self.raw_number_type.cast::<PyObject>(),
+            self.utcoffset_method_str,
+            self.normalize_method_str,
+            self.convert_method_str,
+            self.dst_str,
+            self.dict_str,
+            self.dataclass_fields_str,
+            self.slots_str,
+            self.namedtuple_fields_str,
+            self.field_type_str,
+            self.array_struct_str,
+            self.dtype_str,
+            self.descr_str,
+            self.mask_str,
+            self.items_str,
+            self.to_numpy_str,
+            self.arrow_c_array_str,
+            self.arrow_c_stream_str,
+            self.dlpack_str,
+            self.array_interface_str,
+            self.value_str,
+            self.int_attr_str,
+            self.write_str,
+            self.serializers,
+            self.deserializers,
+            self.tags_by_type,
+            self.types_by_tag,
+            self.type_plans,
+            self.json_encode_error,
+            self.json_decode_error,
+        ];
+        for obj in owned {
+            unsafe {
+                Py_XDECREF(obj);
+            }
         }
-        let state = get_or_init_state(module);
-
-        // Update cache
-        CACHED_INTERP_ID.with(|cell| cell.set(interp_id));
-        CACHED_STATE.with(|cell| cell.set(state));
-
-        // Decref the import reference since sys.modules holds the real reference
-        Py_DECREF(module);
-
-        state
     }
 }
@@ -81,24 +81,31 @@ mod exception;
 mod ffi;
 mod interpreter_state;
 mod opt;
+mod provenance;
+mod runtime;
 mod serialize;
+mod stats;
 mod str;
 mod typeref;
 
-use core::ffi::{c_char, c_int, c_void};
+use core::ffi::{CStr, c_char, c_int, c_void};
 use core::ptr::{NonNull, null, null_mut};
+use std::borrow::Cow;
 
-use crate::deserialize::deserialize;
+use crate::deserialize::{
+    DeserializeError, DeserializeOptions, DuplicateKeys, IntOverflow, TypePlan, apply_object_hook,
+    deserialize, deserialize_typed, revive_extended_json, revive_tagged,
+};
 use crate::exception::{
-    raise_dumps_exception_dynamic, raise_dumps_exception_fixed, raise_loads_exception,
+    raise_dumps_exception_as, raise_dumps_exception_dynamic, raise_dumps_exception_fixed,
+    raise_loads_exception,
 };
 use crate::ffi::{
-    METH_KEYWORDS, METH_O, Py_SIZE, Py_ssize_t, PyCFunction_NewEx, PyErr_Clear, PyErr_Occurred,
-    PyLong_AsLong, PyMethodDef, PyMethodDefPointer, PyModuleDef, PyModuleDef_HEAD_INIT,
-    PyModuleDef_Slot, PyObject, PyUnicode_FromStringAndSize, PyUnicode_InternFromString,
-    PyVectorcall_NARGS,
+    METH_KEYWORDS, METH_O, Py_SIZE, Py_ssize_t, PyCFunction_NewEx, PyMethodDef, PyMethodDefPointer,
+    PyModuleDef, PyModuleDef_HEAD_INIT, PyModuleDef_Slot, PyObject, PyUnicode_FromStringAndSize,
+    PyUnicode_InternFromString, PyVectorcall_NARGS,
 };
-use crate::serialize::serialize;
+use crate::serialize::{JsonSchema, serialize};
 use crate::util::{isize_to_usize, usize_to_isize};
 
 #[cfg(Py_3_13)]
@@ -125,7 +132,8 @@ macro_rules! add {
 macro_rules! opt {
     ($mptr:expr, $name:expr, $opt:expr) => {
         #[cfg(all(not(target_os = "windows"), target_pointer_width = "64"))]
-        crate::ffi::PyModule_AddIntConstant($mptr, $name.as_ptr(), i64::from($opt));
+        #[allow(clippy::cast_possible_wrap)]
+        crate::ffi::PyModule_AddIntConstant($mptr, $name.as_ptr(), $opt as i64);
         #[cfg(all(not(target_os = "windows"), target_pointer_width = "32"))]
         crate::ffi::PyModule_AddIntConstant($mptr, $name.as_ptr(), $opt as i32);
         #[cfg(target_os = "windows")]
@@ -140,8 +148,7 @@ macro_rules! opt {
 #[cfg_attr(feature = "optimize", optimize(size))]
 pub(crate) unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
     unsafe {
-        // Initialize per-interpreter state
-        interpreter_state::get_or_init_state(mptr);
+        runtime::init(mptr);
 
         {
             let version = env!("CARGO_PKG_VERSION");
@@ -153,7 +160,7 @@ pub(crate) unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
         }
 
         {
-            let dumps_doc = c"dumps(obj, /, default=None, option=None)\n--\n\nSerialize Python objects to JSON.";
+            let dumps_doc = c"dumps(obj, /, default=None, option=None, *, exc_class=None, length=None, float_decimals=None, float_format=None, key_order=None, non_finite=None, width=None, datetime64_range=None, field_order=None, pandas_orient=None, serialize_arrow=False, json_schema=None, tagged=False, extended_json=None, sort_keys=False, ensure_ascii=False, indent=None, separators=None, cls=None)\n--\n\nSerialize Python objects to JSON.";
 
             let wrapped_dumps = Box::new(PyMethodDef {
                 ml_name: c"dumps".as_ptr(),
@@ -176,12 +183,64 @@ pub(crate) unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
         }
 
         {
-            let loads_doc = c"loads(obj, /)\n--\n\nDeserialize JSON to Python objects.";
+            let dumps_batch_doc = c"dumps_batch(records, /, default=None, option=None)\n--\n\nSerialize each of records as a line of NDJSON and return the bytes and the offsets of each record.";
+
+            let wrapped_dumps_batch = Box::new(PyMethodDef {
+                ml_name: c"dumps_batch".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: dumps_batch,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: dumps_batch,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: dumps_batch_doc.as_ptr(),
+            });
+
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_dumps_batch),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"dumps_batch", func);
+        }
+
+        {
+            let dumps_length_doc = c"dumps_length(obj, /, default=None, option=None)\n--\n\nReturn the length of the output of dumps() with the same arguments without writing it.";
+
+            let wrapped_dumps_length = Box::new(PyMethodDef {
+                ml_name: c"dumps_length".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: dumps_length,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: dumps_length,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: dumps_length_doc.as_ptr(),
+            });
+
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_dumps_length),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"dumps_length", func);
+        }
+
+        {
+            let loads_doc =
+                c"loads(obj, /, *, option=None, rename=None, allow_nan=False, json5=False, allow_comments=False, allow_trailing_commas=False, duplicate_keys=\"last\", strict=False, object_pairs_hook=None, object_hook=None, cls=None, parse_float=None, parse_int=None, use_decimal=False, raw_numbers=False, int_overflow=\"float\", exact_floats=False, type=None, strict_types=False, tagged=False, extended_json=False, encoding=None, errors=\"strict\")\n--\n\nDeserialize JSON to Python objects.";
 
             let wrapped_loads = Box::new(PyMethodDef {
                 ml_name: c"loads".as_ptr(),
-                ml_meth: PyMethodDefPointer { PyCFunction: loads },
-                ml_flags: METH_O,
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: loads,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: loads,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
                 ml_doc: loads_doc.as_ptr(),
             });
             let func = PyCFunction_NewEx(
@@ -192,199 +251,2943 @@ pub(crate) unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
             add!(mptr, c"loads", func);
         }
 
-        add!(
-            mptr,
-            c"Fragment",
-            typeref::get_fragment_type().cast::<PyObject>()
-        );
-
-        opt!(mptr, c"OPT_APPEND_NEWLINE", opt::APPEND_NEWLINE);
-        opt!(mptr, c"OPT_INDENT_2", opt::INDENT_2);
-        opt!(mptr, c"OPT_NAIVE_UTC", opt::NAIVE_UTC);
-        opt!(mptr, c"OPT_NON_STR_KEYS", opt::NON_STR_KEYS);
-        opt!(mptr, c"OPT_OMIT_MICROSECONDS", opt::OMIT_MICROSECONDS);
-        opt!(
-            mptr,
-            c"OPT_PASSTHROUGH_DATACLASS",
-            opt::PASSTHROUGH_DATACLASS
-        );
-        opt!(mptr, c"OPT_PASSTHROUGH_DATETIME", opt::PASSTHROUGH_DATETIME);
-        opt!(mptr, c"OPT_PASSTHROUGH_SUBCLASS", opt::PASSTHROUGH_SUBCLASS);
-        opt!(mptr, c"OPT_SERIALIZE_DATACLASS", opt::SERIALIZE_DATACLASS);
-        opt!(mptr, c"OPT_SERIALIZE_NUMPY", opt::SERIALIZE_NUMPY);
-        opt!(mptr, c"OPT_SERIALIZE_UUID", opt::SERIALIZE_UUID);
-        opt!(mptr, c"OPT_SORT_KEYS", opt::SORT_KEYS);
-        opt!(mptr, c"OPT_STRICT_INTEGER", opt::STRICT_INTEGER);
-        opt!(mptr, c"OPT_UTC_Z", opt::UTC_Z);
+        {
+            let loads_tracked_doc = c"loads_tracked(obj, /)\n--\n\nDeserialize JSON to Python objects and remember the input so that dumps() of the unmodified result copies it if it is written as dumps() writes it.";
 
-        add!(mptr, c"JSONDecodeError", typeref::get_json_decode_error());
-        add!(mptr, c"JSONEncodeError", typeref::get_json_encode_error());
+            let wrapped_loads_tracked = Box::new(PyMethodDef {
+                ml_name: c"loads_tracked".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: loads_tracked,
+                },
+                ml_flags: METH_O,
+                ml_doc: loads_tracked_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_loads_tracked),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"loads_tracked", func);
+        }
 
-        0
-    }
-}
+        {
+            let loads_numpy_doc = c"loads_numpy(data, /, dtype=None)\n--\n\nDeserialize a JSON array of numbers, or of arrays of the same shape, to a numpy.ndarray of dtype.";
 
-#[allow(non_snake_case)]
-#[unsafe(no_mangle)]
-#[cold]
-#[cfg_attr(feature = "optimize", optimize(size))]
-pub(crate) unsafe extern "C" fn PyInit_hyperjson() -> *mut PyModuleDef {
-    #[cfg(not(Py_3_12))]
-    const PYMODULEDEF_LEN: usize = 2;
-    #[cfg(all(Py_3_12, not(Py_3_13)))]
-    const PYMODULEDEF_LEN: usize = 3;
-    #[cfg(Py_3_13)]
-    const PYMODULEDEF_LEN: usize = 4;
-    unsafe {
-        let mod_slots: Box<[PyModuleDef_Slot; PYMODULEDEF_LEN]> = Box::new([
-            PyModuleDef_Slot {
-                slot: crate::ffi::Py_mod_exec,
-                #[allow(clippy::fn_to_numeric_cast_any, clippy::as_conversions)]
-                value: orjson_init_exec as *mut c_void,
-            },
-            #[cfg(all(Py_3_12, not(Py_3_13)))]
-            PyModuleDef_Slot {
-                slot: crate::ffi::Py_mod_multiple_interpreters,
-                value: crate::ffi::Py_MOD_MULTIPLE_INTERPRETERS_SUPPORTED,
-            },
-            #[cfg(Py_3_13)]
-            PyModuleDef_Slot {
-                slot: crate::ffi::Py_mod_multiple_interpreters,
-                value: crate::ffi::Py_MOD_PER_INTERPRETER_GIL_SUPPORTED,
-            },
-            #[cfg(Py_3_13)]
-            PyModuleDef_Slot {
-                slot: crate::ffi::Py_mod_gil,
-                value: crate::ffi::Py_MOD_GIL_USED,
-            },
-            PyModuleDef_Slot {
-                slot: 0,
-                value: null_mut(),
-            },
-        ]);
+            let wrapped_loads_numpy = Box::new(PyMethodDef {
+                ml_name: c"loads_numpy".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: loads_numpy,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: loads_numpy,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: loads_numpy_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_loads_numpy),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"loads_numpy", func);
+        }
 
-        let init = Box::new(PyModuleDef {
-            m_base: PyModuleDef_HEAD_INIT,
-            m_name: c"hyperjson".as_ptr(),
-            m_doc: null(),
-            m_size: 0,
-            m_methods: null_mut(),
-            m_slots: Box::into_raw(mod_slots).cast::<PyModuleDef_Slot>(),
-            m_traverse: None,
-            m_clear: None,
-            m_free: None,
-        });
-        let init_ptr = Box::into_raw(init);
-        ffi!(PyModuleDef_Init(init_ptr));
-        init_ptr
-    }
-}
+        {
+            let verify_and_loads_doc = c"verify_and_loads(body, signature, key, /, algo=\"hmac-sha256\")\n--\n\nVerify the HMAC signature of body and deserialize it, raising JSONDecodeError if either fails.";
 
-#[unsafe(no_mangle)]
-pub(crate) unsafe extern "C" fn loads(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
-    deserialize(obj).map_or_else(raise_loads_exception, NonNull::as_ptr)
-}
+            let wrapped_verify_and_loads = Box::new(PyMethodDef {
+                ml_name: c"verify_and_loads".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: verify_and_loads,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: verify_and_loads,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: verify_and_loads_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_verify_and_loads),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"verify_and_loads", func);
+        }
 
-#[cfg(CPython)]
-macro_rules! matches_kwarg {
-    ($val:expr, $ref:expr) => {
-        unsafe { core::ptr::eq($val, $ref) }
-    };
-}
+        {
+            let compare_digest_json_doc = c"compare_digest_json(a, b, /)\n--\n\nReturn whether a and b serialize to the same JSON with sorted keys, comparing the output in time that does not depend on where it differs.";
 
-#[cfg(not(CPython))]
-macro_rules! matches_kwarg {
-    ($val:expr, $ref:expr) => {
-        unsafe { crate::ffi::PyObject_Hash($val) == crate::ffi::PyObject_Hash($ref) }
-    };
-}
+            let wrapped_compare_digest_json = Box::new(PyMethodDef {
+                ml_name: c"compare_digest_json".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: compare_digest_json,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: compare_digest_json,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: compare_digest_json_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_compare_digest_json),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"compare_digest_json", func);
+        }
 
-#[unsafe(no_mangle)]
-pub(crate) unsafe extern "C" fn dumps(
-    _self: *mut PyObject,
-    args: *const *mut PyObject,
-    nargs: Py_ssize_t,
-    kwnames: *mut PyObject,
-) -> *mut PyObject {
-    unsafe {
-        let mut default: Option<NonNull<PyObject>> = None;
-        let mut optsptr: Option<NonNull<PyObject>> = None;
+        {
+            let register_serializer_doc = c"register_serializer(type, callable, /)\n--\n\nSerialize instances of type and its subclasses that are not natively supported by calling callable, or unregister type if callable is None.";
 
-        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
-        if num_args == 0 {
-            cold_path!();
-            return raise_dumps_exception_fixed(
-                "dumps() missing 1 required positional argument: 'obj'",
+            let wrapped_register_serializer = Box::new(PyMethodDef {
+                ml_name: c"register_serializer".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: register_serializer,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: register_serializer,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: register_serializer_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_register_serializer),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
             );
-        }
-        if num_args & 2 == 2 {
-            default = Some(NonNull::new_unchecked(*args.offset(1)));
-        }
-        if num_args & 3 == 3 {
-            optsptr = Some(NonNull::new_unchecked(*args.offset(2)));
-        }
-        if !kwnames.is_null() {
-            cold_path!();
-            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
-                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
-                if matches_kwarg!(arg, typeref::get_option()) {
-                    if num_args & 3 == 3 {
-                        cold_path!();
-                        return raise_dumps_exception_fixed(
-                            "dumps() got multiple values for argument: 'option'",
-                        );
-                    }
-                    optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
-                } else if matches_kwarg!(arg, typeref::get_default()) {
-                    if num_args & 2 == 2 {
-                        cold_path!();
-                        return raise_dumps_exception_fixed(
-                            "dumps() got multiple values for argument: 'default'",
-                        );
-                    }
-                    default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
-                } else {
-                    return raise_dumps_exception_fixed(
-                        "dumps() got an unexpected keyword argument",
-                    );
-                }
-            }
+            add!(mptr, c"register_serializer", func);
         }
 
-        let mut optsbits: i32 = 0;
-        if let Some(opts) = optsptr {
-            cold_path!();
-            // Use direct CPython global for int type (zero indirection)
-            if core::ptr::eq((*opts.as_ptr()).ob_type, typeref::int_type_ptr()) {
-                unsafe {
-                    #[allow(clippy::cast_possible_truncation)]
-                    let tmp = PyLong_AsLong(optsptr.unwrap().as_ptr()) as i32; // stmt_expr_attributes
-                    // Check for errors from PyLong_AsLong (e.g., overflow)
-                    // -1 can be a valid return value, but we check PyErr_Occurred to distinguish
-                    // between a legitimate -1 and an error. Since valid option values are 0..=MAX_OPT,
-                    // -1 is never valid, so we can check both conditions.
-                    if tmp == -1 && !PyErr_Occurred().is_null() {
-                        PyErr_Clear();
-                        return raise_dumps_exception_fixed("Invalid opts");
-                    }
-                    optsbits = tmp;
-                    if !(0..=opt::MAX_OPT).contains(&optsbits) {
-                        cold_path!();
-                        return raise_dumps_exception_fixed("Invalid opts");
-                    }
-                }
-            } else if !core::ptr::eq(opts.as_ptr(), typeref::get_none()) {
-                cold_path!();
-                return raise_dumps_exception_fixed("Invalid opts");
-            }
+        {
+            let register_deserializer_doc = c"register_deserializer(type, callable, /)\n--\n\nBuild values annotated as type in loads(type=) by calling callable with the value read from the document, or unregister type if callable is None.";
+
+            let wrapped_register_deserializer = Box::new(PyMethodDef {
+                ml_name: c"register_deserializer".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: register_deserializer,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: register_deserializer,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: register_deserializer_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_register_deserializer),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"register_deserializer", func);
         }
 
-        #[allow(clippy::cast_sign_loss)]
-        let opts = optsbits as opt::Opt;
+        {
+            let register_tagged_doc = c"register_tagged(type, tag=None, /)\n--\n\nWrite instances of type with the member \"__type__\": tag in dumps(tagged=True) and build them from it in loads(tagged=True). The tag is the name of the type if None.";
 
-        serialize(*args, default, opts).map_or_else(
-            |err| raise_dumps_exception_dynamic(err.as_str()),
-            NonNull::as_ptr,
-        )
+            let wrapped_register_tagged = Box::new(PyMethodDef {
+                ml_name: c"register_tagged".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: register_tagged,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: register_tagged,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: register_tagged_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_register_tagged),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"register_tagged", func);
+        }
+
+        {
+            let register_type_doc = c"register_type(type, /)\n--\n\nBuild and cache how dumps() writes the dataclass type: its declared fields in order with their keys escaped and the serializer of each.";
+
+            let wrapped_register_type = Box::new(PyMethodDef {
+                ml_name: c"register_type".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: register_type,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: register_type,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: register_type_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_register_type),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"register_type", func);
+        }
+
+        {
+            let merge_patch_doc = c"merge_patch(target, patch, /)\n--\n\nApply the JSON Merge Patch (RFC 7386) patch to target. Either may be a document as bytes, bytearray, or memoryview or an object. Return bytes if target is a document and the merged object otherwise.";
+
+            let wrapped_merge_patch = Box::new(PyMethodDef {
+                ml_name: c"merge_patch".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: merge_patch,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: merge_patch,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: merge_patch_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_merge_patch),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"merge_patch", func);
+        }
+
+        {
+            let pointer_doc = c"pointer(data, pointer, /)\n--\n\nDeserialize only the value at a JSON Pointer (RFC 6901) in a document. The rest of the document is not validated.";
+
+            let wrapped_pointer = Box::new(PyMethodDef {
+                ml_name: c"pointer".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: pointer,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: pointer,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: pointer_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_pointer),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"pointer", func);
+        }
+
+        {
+            let equals_doc = c"equals(a, b, /)\n--\n\nReturn whether a and b are the same JSON document, regardless of the order of keys and how numbers are written. Each may be a document as bytes, bytearray, or memoryview or an object, which is compared as dumps() writes it.";
+
+            let wrapped_equals = Box::new(PyMethodDef {
+                ml_name: c"equals".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: equals,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: equals,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: equals_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_equals),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"equals", func);
+        }
+
+        {
+            let merge3_doc = c"merge3(base, ours, theirs, /)\n--\n\nMerge the changes ours and theirs make to base. Return a tuple of the merged object and a list of conflicts, each a dict of the path and the values of each side that has one. A conflict keeps ours.";
+
+            let wrapped_merge3 = Box::new(PyMethodDef {
+                ml_name: c"merge3".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFast: merge3,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFast: merge3,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL,
+                ml_doc: merge3_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_merge3),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"merge3", func);
+        }
+
+        {
+            let flatten_doc = c"flatten(obj, /, sep=\".\")\n--\n\nReturn a dict of the values of the object obj, or of a document of one, that are not non-empty objects, keyed by the keys on the way to each joined with sep.";
+
+            let wrapped_flatten = Box::new(PyMethodDef {
+                ml_name: c"flatten".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: flatten,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: flatten,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: flatten_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_flatten),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"flatten", func);
+        }
+
+        {
+            let unflatten_doc = c"unflatten(mapping, /, sep=\".\")\n--\n\nReturn the nested dict of mapping, or of a document of an object, splitting each key on sep into the keys on the way to its value.";
+
+            let wrapped_unflatten = Box::new(PyMethodDef {
+                ml_name: c"unflatten".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: unflatten,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: unflatten,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: unflatten_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_unflatten),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"unflatten", func);
+        }
+
+        {
+            let dumps_msgpack_doc = c"dumps_msgpack(obj, /, default=None, option=None)\n--\n\nSerialize Python objects to MessagePack as dumps() serializes them to JSON.";
+
+            let wrapped_dumps_msgpack = Box::new(PyMethodDef {
+                ml_name: c"dumps_msgpack".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: dumps_msgpack,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: dumps_msgpack,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: dumps_msgpack_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_dumps_msgpack),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"dumps_msgpack", func);
+        }
+
+        {
+            let loads_msgpack_doc = c"loads_msgpack(data, /)\n--\n\nDeserialize MessagePack to the Python objects loads() returns for JSON.";
+
+            let wrapped_loads_msgpack = Box::new(PyMethodDef {
+                ml_name: c"loads_msgpack".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: loads_msgpack,
+                },
+                ml_flags: METH_O,
+                ml_doc: loads_msgpack_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_loads_msgpack),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"loads_msgpack", func);
+        }
+
+        {
+            let hash_doc = c"hash(obj, /, algorithm=\"sha256\")\n--\n\nReturn the digest of obj serialized with sorted keys and without whitespace, hashing the output as it is written.";
+
+            let wrapped_hash = Box::new(PyMethodDef {
+                ml_name: c"hash".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: hash,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: hash,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: hash_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_hash),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"hash", func);
+        }
+
+        {
+            let validate_doc = c"validate(data, /)\n--\n\nReturn None if data is a valid JSON document and raise JSONDecodeError if not, without deserializing it to Python objects.";
+
+            let wrapped_validate = Box::new(PyMethodDef {
+                ml_name: c"validate".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: validate,
+                },
+                ml_flags: METH_O,
+                ml_doc: validate_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_validate),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"validate", func);
+        }
+
+        {
+            let minify_doc = c"minify(data, /)\n--\n\nReturn the JSON document data as bytes without whitespace, without deserializing it to Python objects.";
+
+            let wrapped_minify = Box::new(PyMethodDef {
+                ml_name: c"minify".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: minify,
+                },
+                ml_flags: METH_O,
+                ml_doc: minify_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_minify),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"minify", func);
+        }
+
+        {
+            let prettify_doc = c"prettify(data, /, indent=2)\n--\n\nReturn the JSON document data as bytes indented by indent spaces, without deserializing it to Python objects.";
+
+            let wrapped_prettify = Box::new(PyMethodDef {
+                ml_name: c"prettify".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: prettify,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: prettify,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: prettify_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_prettify),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"prettify", func);
+        }
+
+        {
+            let join_array_doc = c"join_array(iterable, /, validate=False)\n--\n\nAssemble a JSON array from pre-encoded Fragment, bytes, or str pieces.";
+
+            let wrapped_join_array = Box::new(PyMethodDef {
+                ml_name: c"join_array".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: join_array,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: join_array,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: join_array_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_join_array),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"join_array", func);
+        }
+
+        {
+            let join_object_doc = c"join_object(pairs, /, validate=False)\n--\n\nAssemble a JSON object from (str, pre-encoded Fragment, bytes, or str) pairs.";
+
+            let wrapped_join_object = Box::new(PyMethodDef {
+                ml_name: c"join_object".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: join_object,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: join_object,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: join_object_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_join_object),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"join_object", func);
+        }
+
+        {
+            let reset_for_testing_doc = c"_reset_for_testing()\n--\n\nClear registered serializers, caches, and tracked loads_tracked() results of the current interpreter. Not for use in production code.";
+
+            let wrapped_reset_for_testing = Box::new(PyMethodDef {
+                ml_name: c"_reset_for_testing".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: reset_for_testing,
+                },
+                ml_flags: crate::ffi::METH_NOARGS,
+                ml_doc: reset_for_testing_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_reset_for_testing),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"_reset_for_testing", func);
+        }
+
+        {
+            let stats_doc = c"stats(*, reset=False)\n--\n\nReturn the number of calls to each function and of each option flag passed to them, in the current interpreter since collect_stats(True). If reset, zero them afterwards.";
+
+            let wrapped_stats = Box::new(PyMethodDef {
+                ml_name: c"stats".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: stats,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: stats,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: stats_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_stats),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"stats", func);
+        }
+
+        {
+            let collect_stats_doc = c"collect_stats(enabled, /)\n--\n\nStart or stop counting calls and option flags for stats() in the current interpreter.";
+
+            let wrapped_collect_stats = Box::new(PyMethodDef {
+                ml_name: c"collect_stats".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: collect_stats,
+                },
+                ml_flags: METH_O,
+                ml_doc: collect_stats_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_collect_stats),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"collect_stats", func);
+        }
+
+        {
+            let set_compat_mode_doc = c"set_compat_mode(enabled, /)\n--\n\nMake loads() build and dumps() read lists, tuples, and dicts with the public C API only in the current interpreter, for debugging crashes on unusual interpreters. This is slower.";
+
+            let wrapped_set_compat_mode = Box::new(PyMethodDef {
+                ml_name: c"set_compat_mode".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: set_compat_mode,
+                },
+                ml_flags: METH_O,
+                ml_doc: set_compat_mode_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_set_compat_mode),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"set_compat_mode", func);
+        }
+
+        add!(
+            mptr,
+            c"Builder",
+            typeref::get_builder_type().cast::<PyObject>()
+        );
+
+        {
+            let edit_doc = c"edit(data, /)\n--\n\nReturn an Editor of a JSON document that changes values without rewriting the rest of it.";
+
+            let wrapped_edit = Box::new(PyMethodDef {
+                ml_name: c"edit".as_ptr(),
+                ml_meth: PyMethodDefPointer { PyCFunction: edit },
+                ml_flags: METH_O,
+                ml_doc: edit_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_edit),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"edit", func);
+        }
+
+        {
+            let splice_doc = c"splice(data, pointer, value, /, option=None, default=None)\n--\n\nReturn data, the output of dumps(), with the value at the JSON Pointer pointer serialized from value and the rest of data unchanged.";
+
+            let wrapped_splice = Box::new(PyMethodDef {
+                ml_name: c"splice".as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    #[cfg(Py_3_10)]
+                    PyCFunctionFastWithKeywords: splice_json,
+                    #[cfg(not(Py_3_10))]
+                    _PyCFunctionFastWithKeywords: splice_json,
+                },
+                ml_flags: crate::ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: splice_doc.as_ptr(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(wrapped_splice),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            add!(mptr, c"splice", func);
+        }
+
+        add!(
+            mptr,
+            c"Editor",
+            typeref::get_editor_type().cast::<PyObject>()
+        );
+
+        add!(
+            mptr,
+            c"Fragment",
+            typeref::get_fragment_type().cast::<PyObject>()
+        );
+
+        add!(
+            mptr,
+            c"RawNumber",
+            typeref::get_raw_number_type().cast::<PyObject>()
+        );
+
+        add!(
+            mptr,
+            c"LinesWriter",
+            typeref::get_lines_writer_type().cast::<PyObject>()
+        );
+
+        add!(
+            mptr,
+            c"Schema",
+            typeref::get_schema_type().cast::<PyObject>()
+        );
+
+        opt!(mptr, c"OPT_APPEND_CRLF", opt::APPEND_CRLF);
+        opt!(mptr, c"OPT_APPEND_NEWLINE", opt::APPEND_NEWLINE);
+        opt!(mptr, c"OPT_BIG_INTEGER", opt::BIG_INTEGER);
+        opt!(mptr, c"OPT_BYTES_KEYS_BASE64", opt::BYTES_KEYS_BASE64);
+        opt!(mptr, c"OPT_BYTES_KEYS_LATIN1", opt::BYTES_KEYS_LATIN1);
+        opt!(mptr, c"OPT_COMPAT_V1", opt::COMPAT_V1);
+        opt!(mptr, c"OPT_DEFAULT_PATH", opt::DEFAULT_PATH);
+        opt!(mptr, c"OPT_ENSURE_ASCII", opt::ENSURE_ASCII);
+        opt!(mptr, c"OPT_INDENT_2", opt::INDENT_2);
+        opt!(mptr, c"OPT_INDENT_4", opt::INDENT_4);
+        opt!(mptr, c"OPT_INDENT_8", opt::INDENT_8);
+        opt!(mptr, c"OPT_INDENT_TAB", opt::INDENT_TAB);
+        opt!(mptr, c"OPT_NAIVE_UTC", opt::NAIVE_UTC);
+        opt!(mptr, c"OPT_NAMEDTUPLE_AS_OBJECT", opt::NAMEDTUPLE_AS_OBJECT);
+        opt!(mptr, c"OPT_NON_STR_KEYS", opt::NON_STR_KEYS);
+        opt!(mptr, c"OPT_NULL_UNSUPPORTED", opt::NULL_UNSUPPORTED);
+        opt!(mptr, c"OPT_OMIT_EMPTY", opt::OMIT_EMPTY);
+        opt!(mptr, c"OPT_OMIT_EMPTY_DICT", opt::OMIT_EMPTY_DICT);
+        opt!(mptr, c"OPT_OMIT_EMPTY_LIST", opt::OMIT_EMPTY_LIST);
+        opt!(mptr, c"OPT_OMIT_EMPTY_STR", opt::OMIT_EMPTY_STR);
+        opt!(mptr, c"OPT_OMIT_MICROSECONDS", opt::OMIT_MICROSECONDS);
+        opt!(mptr, c"OPT_OMIT_NULL", opt::OMIT_NULL);
+        opt!(mptr, c"OPT_OMIT_NULL_ON_LOAD", opt::OMIT_NULL_ON_LOAD);
+        opt!(
+            mptr,
+            c"OPT_PASSTHROUGH_DATACLASS",
+            opt::PASSTHROUGH_DATACLASS
+        );
+        opt!(mptr, c"OPT_PASSTHROUGH_DATETIME", opt::PASSTHROUGH_DATETIME);
+        opt!(mptr, c"OPT_PASSTHROUGH_SUBCLASS", opt::PASSTHROUGH_SUBCLASS);
+        opt!(mptr, c"OPT_SERIALIZE_DATACLASS", opt::SERIALIZE_DATACLASS);
+        opt!(mptr, c"OPT_SERIALIZE_NUMPY", opt::SERIALIZE_NUMPY);
+        opt!(mptr, c"OPT_SERIALIZE_UUID", opt::SERIALIZE_UUID);
+        opt!(mptr, c"OPT_SKIP_UNSUPPORTED", opt::SKIP_UNSUPPORTED);
+        opt!(mptr, c"OPT_SORT_KEYS", opt::SORT_KEYS);
+        opt!(mptr, c"OPT_STRICT_INTEGER", opt::STRICT_INTEGER);
+        opt!(mptr, c"OPT_UTC_Z", opt::UTC_Z);
+
+        add!(mptr, c"JSONDecodeError", typeref::get_json_decode_error());
+        add!(mptr, c"JSONEncodeError", typeref::get_json_encode_error());
+
+        0
+    }
+}
+
+#[allow(non_snake_case)]
+#[unsafe(no_mangle)]
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) unsafe extern "C" fn PyInit_hyperjson() -> *mut PyModuleDef {
+    #[cfg(not(Py_3_12))]
+    const PYMODULEDEF_LEN: usize = 2;
+    #[cfg(all(Py_3_12, not(Py_3_13)))]
+    const PYMODULEDEF_LEN: usize = 3;
+    #[cfg(Py_3_13)]
+    const PYMODULEDEF_LEN: usize = 4;
+    unsafe {
+        let mod_slots: Box<[PyModuleDef_Slot; PYMODULEDEF_LEN]> = Box::new([
+            PyModuleDef_Slot {
+                slot: crate::ffi::Py_mod_exec,
+                #[allow(clippy::fn_to_numeric_cast_any, clippy::as_conversions)]
+                value: orjson_init_exec as *mut c_void,
+            },
+            #[cfg(all(Py_3_12, not(Py_3_13)))]
+            PyModuleDef_Slot {
+                slot: crate::ffi::Py_mod_multiple_interpreters,
+                value: crate::ffi::Py_MOD_MULTIPLE_INTERPRETERS_SUPPORTED,
+            },
+            #[cfg(Py_3_13)]
+            PyModuleDef_Slot {
+                slot: crate::ffi::Py_mod_multiple_interpreters,
+                value: crate::ffi::Py_MOD_PER_INTERPRETER_GIL_SUPPORTED,
+            },
+            #[cfg(Py_3_13)]
+            PyModuleDef_Slot {
+                slot: crate::ffi::Py_mod_gil,
+                value: crate::ffi::Py_MOD_GIL_USED,
+            },
+            PyModuleDef_Slot {
+                slot: 0,
+                value: null_mut(),
+            },
+        ]);
+
+        let init = Box::new(PyModuleDef {
+            m_base: PyModuleDef_HEAD_INIT,
+            m_name: c"hyperjson".as_ptr(),
+            m_doc: null(),
+            m_size: 0,
+            m_methods: null_mut(),
+            m_slots: Box::into_raw(mod_slots).cast::<PyModuleDef_Slot>(),
+            m_traverse: None,
+            m_clear: None,
+            m_free: Some(runtime::teardown),
+        });
+        let init_ptr = Box::into_raw(init);
+        ffi!(PyModuleDef_Init(init_ptr));
+        init_ptr
+    }
+}
+
+#[cfg(CPython)]
+macro_rules! matches_kwarg {
+    ($val:expr, $ref:expr) => {
+        unsafe { core::ptr::eq($val, $ref) }
+    };
+}
+
+#[cfg(not(CPython))]
+macro_rules! matches_kwarg {
+    ($val:expr, $ref:expr) => {
+        unsafe { crate::ffi::PyObject_Hash($val) == crate::ffi::PyObject_Hash($ref) }
+    };
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn loads(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args != 1 {
+            cold_path!();
+            return raise_dumps_exception_fixed(if num_args == 0 {
+                "loads() missing 1 required positional argument: 'obj'"
+            } else {
+                "loads() takes exactly 1 positional argument"
+            });
+        }
+        if kwnames.is_null() {
+            stats::record(stats::Entry::Loads, 0);
+            return deserialize(*args, &DeserializeOptions::default())
+                .map_or_else(raise_loads_exception, NonNull::as_ptr);
+        }
+        match parse_loads_kwargs(args.offset(num_args), kwnames) {
+            Ok(options) => {
+                let opts = if options.omit_null {
+                    opt::OMIT_NULL_ON_LOAD
+                } else {
+                    0
+                };
+                stats::record(stats::Entry::Loads, opts);
+                let plan = match options.typ {
+                    None => None,
+                    Some(typ) => match TypePlan::new(typ.as_ptr()) {
+                        Ok(plan) => Some(plan),
+                        Err(msg) => return raise_dumps_exception_dynamic(&msg),
+                    },
+                };
+                match &plan {
+                    None => deserialize(*args, &options),
+                    Some(plan) => deserialize_typed(*args, &options, plan),
+                }
+                .and_then(|obj| {
+                    if options.extended_json {
+                        revive_extended_json(obj)
+                    } else {
+                        Ok(obj)
+                    }
+                })
+                .and_then(|obj| {
+                    if options.tagged {
+                        revive_tagged(obj)
+                    } else {
+                        Ok(obj)
+                    }
+                })
+                .and_then(|obj| match options.object_hook {
+                    Some(hook) => apply_object_hook(obj, hook),
+                    None => Ok(obj),
+                })
+                .and_then(|obj| match &plan {
+                    Some(plan) if options.strict_types => plan.check(obj),
+                    _ => Ok(obj),
+                })
+                .map_err(|err| {
+                    if options.strict {
+                        err.with_strict_code()
+                    } else {
+                        err
+                    }
+                })
+                .map_or_else(raise_loads_exception, NonNull::as_ptr)
+            }
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+/// Parse the keyword arguments of `loads()`.
+#[cold]
+#[inline(never)]
+unsafe fn parse_loads_kwargs<'a>(
+    kwargs: *const *mut PyObject,
+    kwnames: *mut PyObject,
+) -> Result<DeserializeOptions<'a>, &'static str> {
+    unsafe {
+        let mut options = DeserializeOptions::default();
+        let mut use_decimal = false;
+        let mut cls = typeref::get_none();
+        let mut raw_numbers = false;
+        for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+            let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+            let value = *kwargs.offset(i);
+            if matches_kwarg!(arg, typeref::get_rename()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    match crate::deserialize::KeyRename::from_dict(value) {
+                        Some(rename) => options.rename = Some(rename),
+                        None => return Err("loads() rename must be a dict of str to str"),
+                    }
+                }
+            } else if matches_kwarg!(arg, typeref::get_option()) {
+                match opt::loads_opts_from_pyobject(value) {
+                    Some(opts) => options.omit_null = opts & opt::OMIT_NULL_ON_LOAD != 0,
+                    None => return Err("loads() option not recognized"),
+                }
+            } else if matches_kwarg!(arg, typeref::get_allow_nan()) {
+                options.allow_nan = bool_arg(value).ok_or("loads() allow_nan must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_json5()) {
+                options.json5 = bool_arg(value).ok_or("loads() json5 must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_allow_comments()) {
+                options.allow_comments =
+                    bool_arg(value).ok_or("loads() allow_comments must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_allow_trailing_commas()) {
+                options.allow_trailing_commas =
+                    bool_arg(value).ok_or("loads() allow_trailing_commas must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_duplicate_keys()) {
+                options.duplicate_keys = duplicate_keys_option(value)
+                    .ok_or("loads() duplicate_keys must be \"last\", \"first\", or \"error\"")?;
+            } else if matches_kwarg!(arg, typeref::get_strict()) {
+                options.strict = bool_arg(value).ok_or("loads() strict must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_object_pairs_hook()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    if ffi!(PyCallable_Check(value)) == 0 {
+                        return Err("loads() object_pairs_hook must be callable or None");
+                    }
+                    options.object_pairs_hook = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_object_hook()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    if ffi!(PyCallable_Check(value)) == 0 {
+                        return Err("loads() object_hook must be callable or None");
+                    }
+                    options.object_hook = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_cls()) {
+                cls = value;
+            } else if matches_kwarg!(arg, typeref::get_parse_float()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    if ffi!(PyCallable_Check(value)) == 0 {
+                        return Err("loads() parse_float must be callable or None");
+                    }
+                    options.parse_float = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_parse_int()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    if ffi!(PyCallable_Check(value)) == 0 {
+                        return Err("loads() parse_int must be callable or None");
+                    }
+                    options.parse_int = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_use_decimal()) {
+                use_decimal = bool_arg(value).ok_or("loads() use_decimal must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_raw_numbers()) {
+                raw_numbers = bool_arg(value).ok_or("loads() raw_numbers must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_int_overflow()) {
+                options.int_overflow = int_overflow_option(value)
+                    .ok_or("loads() int_overflow must be \"float\", \"string\", or \"error\"")?;
+            } else if matches_kwarg!(arg, typeref::get_exact_floats()) {
+                options.exact_floats =
+                    bool_arg(value).ok_or("loads() exact_floats must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_typ()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    options.typ = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_strict_types()) {
+                options.strict_types =
+                    bool_arg(value).ok_or("loads() strict_types must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_tagged()) {
+                options.tagged = bool_arg(value).ok_or("loads() tagged must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_extended_json()) {
+                options.extended_json =
+                    bool_arg(value).ok_or("loads() extended_json must be a bool")?;
+            } else if matches_kwarg!(arg, typeref::get_encoding()) {
+                if !core::ptr::eq(value, typeref::get_none()) {
+                    if !is_type!(ob_type!(value), crate::typeref::str_type_ptr())
+                        || !codec_known(value)
+                    {
+                        return Err("loads() encoding must be the name of a codec or None");
+                    }
+                    options.encoding = NonNull::new(value);
+                }
+            } else if matches_kwarg!(arg, typeref::get_errors()) {
+                options.replace_invalid = errors_option(value)
+                    .ok_or("loads() errors must be \"strict\" or \"replace\"")?;
+            } else {
+                return Err(unsupported_loads_kwarg(arg));
+            }
+        }
+        if !core::ptr::eq(cls, typeref::get_none()) {
+            json_decoder_option(&mut options, cls)?;
+        }
+        if options.object_pairs_hook.is_some() {
+            // as json.loads(), object_pairs_hook takes priority
+            options.object_hook = None;
+        }
+        if options.strict {
+            if options.allow_nan
+                || options.json5
+                || options.allow_comments
+                || options.allow_trailing_commas
+                || options.duplicate_keys == DuplicateKeys::First
+            {
+                return Err(
+                    "loads() strict cannot be combined with allow_nan, json5, allow_comments, allow_trailing_commas, or duplicate_keys=\"first\"",
+                );
+            }
+            options.duplicate_keys = DuplicateKeys::Error;
+        }
+        if use_decimal {
+            if options.parse_float.is_some() {
+                return Err("loads() use_decimal cannot be combined with parse_float");
+            }
+            options.parse_float = Some(
+                NonNull::new(typeref::get_decimal_type())
+                    .ok_or("loads() use_decimal requires the decimal module")?,
+            );
+        }
+        if raw_numbers {
+            if options.parse_float.is_some() || options.parse_int.is_some() {
+                return Err(
+                    "loads() raw_numbers cannot be combined with parse_float, parse_int, or use_decimal",
+                );
+            }
+            let raw_number = NonNull::new(typeref::get_raw_number_type().cast::<PyObject>());
+            options.parse_float = raw_number;
+            options.parse_int = raw_number;
+        }
+        if options.typ.is_some()
+            && (options.is_extended() || options.allow_comments || options.object_hook.is_some())
+        {
+            return Err(
+                "loads() type cannot be combined with allow_nan, json5, allow_comments, allow_trailing_commas, duplicate_keys, strict, object_pairs_hook, object_hook, parse_float, parse_int, use_decimal, raw_numbers, int_overflow, or exact_floats",
+            );
+        }
+        if options.strict_types && options.typ.is_none() {
+            return Err("loads() strict_types requires type");
+        }
+        Ok(options)
+    }
+}
+
+/// The error for the keyword argument `arg` of `dumps()`, which names the
+/// arguments of `json.dumps()` that it does not support.
+#[cold]
+fn unsupported_dumps_kwarg(arg: *mut PyObject) -> &'static str {
+    match unsafe { crate::str::PyStr::from_ptr_unchecked(arg).to_str() } {
+        Some("skipkeys") => "dumps() does not support the json.dumps() argument: 'skipkeys'",
+        Some("allow_nan") => {
+            "dumps() does not support the json.dumps() argument: 'allow_nan'; use non_finite"
+        }
+        Some("check_circular") => {
+            "dumps() does not support the json.dumps() argument: 'check_circular'"
+        }
+        _ => "dumps() got an unexpected keyword argument",
+    }
+}
+
+/// The error for the keyword argument `arg` of `loads()`, which names the
+/// arguments of `json.loads()` that it does not support.
+#[cold]
+fn unsupported_loads_kwarg(arg: *mut PyObject) -> &'static str {
+    match unsafe { crate::str::PyStr::from_ptr_unchecked(arg).to_str() } {
+        Some("parse_constant") => {
+            "loads() does not support the json.loads() argument: 'parse_constant'; use allow_nan"
+        }
+        _ => "loads() got an unexpected keyword argument",
+    }
+}
+
+/// Whether the `str` `ptr` names a codec that `codecs.lookup()` finds.
+fn codec_known(ptr: *mut PyObject) -> bool {
+    let mut len: Py_ssize_t = 0;
+    let name = ffi!(PyUnicode_AsUTF8AndSize(ptr, &mut len));
+    if name.is_null() {
+        ffi!(PyErr_Clear());
+        return false;
+    }
+    ffi!(PyCodec_KnownEncoding(name)) == 1
+}
+
+fn duplicate_keys_option(ptr: *mut PyObject) -> Option<DuplicateKeys> {
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "last" => Some(DuplicateKeys::Last),
+        "first" => Some(DuplicateKeys::First),
+        "error" => Some(DuplicateKeys::Error),
+        _ => None,
+    }
+}
+
+fn int_overflow_option(ptr: *mut PyObject) -> Option<IntOverflow> {
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "float" => Some(IntOverflow::Float),
+        "string" => Some(IntOverflow::String),
+        "error" => Some(IntOverflow::Error),
+        _ => None,
+    }
+}
+
+/// Whether `errors=` replaces invalid input.
+fn errors_option(ptr: *mut PyObject) -> Option<bool> {
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "strict" => Some(false),
+        "replace" => Some(true),
+        _ => None,
+    }
+}
+
+/// Whether `cls` is a subclass of `base` that inherits each of `methods`.
+#[cold]
+fn is_json_subclass(cls: *mut PyObject, base: *mut PyObject, methods: &[&CStr]) -> bool {
+    if base.is_null() || ffi!(PyType_Check(cls)) == 0 || ffi!(PyObject_IsSubclass(cls, base)) != 1 {
+        ffi!(PyErr_Clear());
+        return false;
+    }
+    methods.iter().all(|name| inherits(cls, base, name))
+}
+
+/// Whether the attribute `name` of `cls` is that of `base`.
+fn inherits(cls: *mut PyObject, base: *mut PyObject, name: &CStr) -> bool {
+    let attr = ffi!(PyObject_GetAttrString(cls, name.as_ptr()));
+    let base_attr = ffi!(PyObject_GetAttrString(base, name.as_ptr()));
+    ffi!(PyErr_Clear());
+    let ret = !attr.is_null() && core::ptr::eq(attr, base_attr);
+    ffi!(Py_XDECREF(attr));
+    ffi!(Py_XDECREF(base_attr));
+    ret
+}
+
+/// Read the hooks of an instance of a `cls=` argument into `options` where
+/// not given, as `json.loads()` passes them to it. `cls` is a subclass of
+/// `json.JSONDecoder` that does not override decoding and is called without
+/// arguments.
+#[cold]
+fn json_decoder_option(
+    options: &mut DeserializeOptions,
+    cls: *mut PyObject,
+) -> Result<(), &'static str> {
+    let base = typeref::get_json_decoder_type();
+    if !is_json_subclass(cls, base, &[c"decode", c"raw_decode"]) {
+        return Err(
+            "loads() cls must be a subclass of json.JSONDecoder that does not override decode() or raw_decode()",
+        );
+    }
+    if core::ptr::eq(cls, base) {
+        return Ok(());
+    }
+    let decoder = ffi!(PyObject_CallFunctionObjArgs(cls, null_mut::<PyObject>()));
+    if decoder.is_null() {
+        ffi!(PyErr_Clear());
+        return Err("loads() cls could not be called without arguments");
+    }
+    let owned = ffi!(PyList_New(0));
+    ffi!(PyList_Append(owned, decoder));
+    ffi!(Py_DECREF(decoder));
+    options.owned = NonNull::new(owned);
+    let hooks = [
+        (
+            c"object_hook",
+            &mut options.object_hook,
+            typeref::get_none(),
+        ),
+        (
+            c"object_pairs_hook",
+            &mut options.object_pairs_hook,
+            typeref::get_none(),
+        ),
+        (
+            c"parse_float",
+            &mut options.parse_float,
+            typeref::float_type_ptr().cast::<PyObject>(),
+        ),
+        (
+            c"parse_int",
+            &mut options.parse_int,
+            typeref::int_type_ptr().cast::<PyObject>(),
+        ),
+    ];
+    for (name, hook, default) in hooks {
+        let attr = ffi!(PyObject_GetAttrString(decoder, name.as_ptr()));
+        if attr.is_null() {
+            ffi!(PyErr_Clear());
+            continue;
+        }
+        ffi!(PyList_Append(owned, attr));
+        ffi!(Py_DECREF(attr));
+        if hook.is_none() && !core::ptr::eq(attr, default) {
+            if ffi!(PyCallable_Check(attr)) == 0 {
+                return Err("loads() cls hooks must be callable or None");
+            }
+            *hook = NonNull::new(attr);
+        }
+    }
+    Ok(())
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn loads_tracked(
+    _self: *mut PyObject,
+    obj: *mut PyObject,
+) -> *mut PyObject {
+    stats::record(stats::Entry::LoadsTracked, 0);
+    crate::deserialize::deserialize_tracked(obj).map_or_else(raise_loads_exception, NonNull::as_ptr)
+}
+
+/// Parse the `(data, /, dtype=None)` signature of `loads_numpy()` and
+/// deserialize `data`.
+#[cold]
+unsafe fn loads_numpy_args(
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    unsafe {
+        let invalid = |msg: &'static str| DeserializeError::invalid(Cow::Borrowed(msg));
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            return Err(invalid(
+                "loads_numpy() missing required positional argument: 'data'",
+            ));
+        }
+        if num_args > 2 {
+            return Err(invalid(
+                "loads_numpy() takes at most 2 positional arguments",
+            ));
+        }
+        let mut dtype: Option<*mut PyObject> = None;
+        if num_args == 2 {
+            dtype = Some(*args.offset(1));
+        }
+        if !kwnames.is_null() {
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_dtype_str()) {
+                    if dtype.is_some() {
+                        return Err(invalid(
+                            "loads_numpy() got multiple values for argument: 'dtype'",
+                        ));
+                    }
+                    dtype = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(invalid("loads_numpy() got an unexpected keyword argument"));
+                }
+            }
+        }
+        crate::deserialize::deserialize_numpy(*args, dtype.unwrap_or_else(typeref::get_none))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn loads_numpy(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::LoadsNumpy, 0);
+        loads_numpy_args(args, nargs, kwnames).map_or_else(raise_loads_exception, NonNull::as_ptr)
+    }
+}
+
+/// Parse the `(body, signature, key, /, algo="hmac-sha256")` signature of
+/// `verify_and_loads()` and verify and deserialize `body`.
+#[cold]
+unsafe fn verify_and_loads_args(
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+    unsafe {
+        let invalid = |msg: &'static str| DeserializeError::invalid(Cow::Borrowed(msg));
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args < 3 {
+            return Err(invalid(
+                "verify_and_loads() missing required positional arguments: 'body', 'signature', and 'key'",
+            ));
+        }
+        if num_args > 4 {
+            return Err(invalid(
+                "verify_and_loads() takes at most 4 positional arguments",
+            ));
+        }
+        let mut algo: Option<*mut PyObject> = None;
+        if num_args == 4 {
+            algo = Some(*args.offset(3));
+        }
+        if !kwnames.is_null() {
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_algo()) {
+                    if algo.is_some() {
+                        return Err(invalid(
+                            "verify_and_loads() got multiple values for argument: 'algo'",
+                        ));
+                    }
+                    algo = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(invalid(
+                        "verify_and_loads() got an unexpected keyword argument",
+                    ));
+                }
+            }
+        }
+        if let Some(algo) = algo {
+            let is_hmac_sha256 = is_type!(ob_type!(algo), crate::typeref::str_type_ptr())
+                && crate::str::PyStr::from_ptr_unchecked(algo).to_str() == Some("hmac-sha256");
+            if !is_hmac_sha256 {
+                return Err(invalid("verify_and_loads() algo must be \"hmac-sha256\""));
+            }
+        }
+        let signature = crate::deserialize::read_bytes_or_str(*args.offset(1))
+            .and_then(crate::deserialize::parse_signature)
+            .ok_or_else(|| {
+                invalid("verify_and_loads() signature must be a 32-byte digest or 64 hex digits")
+            })?;
+        let key = crate::deserialize::read_bytes_or_str(*args.offset(2))
+            .ok_or_else(|| invalid("verify_and_loads() key must be bytes-like or str"))?;
+        crate::deserialize::deserialize_verified(*args, &signature, key)
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn verify_and_loads(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::VerifyAndLoads, 0);
+        verify_and_loads_args(args, nargs, kwnames)
+            .map_or_else(raise_loads_exception, NonNull::as_ptr)
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn dumps(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut optsptr: Option<NonNull<PyObject>> = None;
+        let mut exc_class: Option<NonNull<PyObject>> = None;
+        let mut lengthptr: Option<NonNull<PyObject>> = None;
+        let mut decimalsptr: Option<NonNull<PyObject>> = None;
+        let mut keyorderptr: Option<NonNull<PyObject>> = None;
+        let mut floatformatptr: Option<NonNull<PyObject>> = None;
+        let mut nonfiniteptr: Option<NonNull<PyObject>> = None;
+        let mut widthptr: Option<NonNull<PyObject>> = None;
+        let mut datetime64rangeptr: Option<NonNull<PyObject>> = None;
+        let mut fieldorderptr: Option<NonNull<PyObject>> = None;
+        let mut pandasorientptr: Option<NonNull<PyObject>> = None;
+        let mut serializearrowptr: Option<NonNull<PyObject>> = None;
+        let mut jsonschemaptr: Option<NonNull<PyObject>> = None;
+        let mut taggedptr: Option<NonNull<PyObject>> = None;
+        let mut extendedjsonptr: Option<NonNull<PyObject>> = None;
+        let mut sortkeysptr: Option<NonNull<PyObject>> = None;
+        let mut ensureasciiptr: Option<NonNull<PyObject>> = None;
+        let mut indentptr: Option<NonNull<PyObject>> = None;
+        let mut separatorsptr: Option<NonNull<PyObject>> = None;
+        let mut clsptr: Option<NonNull<PyObject>> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "dumps() missing 1 required positional argument: 'obj'",
+            );
+        }
+        if num_args & 2 == 2 {
+            default = Some(NonNull::new_unchecked(*args.offset(1)));
+        }
+        if num_args & 3 == 3 {
+            optsptr = Some(NonNull::new_unchecked(*args.offset(2)));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_option()) {
+                    if num_args & 3 == 3 {
+                        cold_path!();
+                        return raise_dumps_exception_fixed(
+                            "dumps() got multiple values for argument: 'option'",
+                        );
+                    }
+                    optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_default()) {
+                    if num_args & 2 == 2 {
+                        cold_path!();
+                        return raise_dumps_exception_fixed(
+                            "dumps() got multiple values for argument: 'default'",
+                        );
+                    }
+                    default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_exc_class()) {
+                    exc_class = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_length()) {
+                    lengthptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_float_decimals()) {
+                    decimalsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_key_order()) {
+                    keyorderptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_float_format()) {
+                    floatformatptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_non_finite()) {
+                    nonfiniteptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_width()) {
+                    widthptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_datetime64_range()) {
+                    datetime64rangeptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_field_order()) {
+                    fieldorderptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_pandas_orient()) {
+                    pandasorientptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_serialize_arrow()) {
+                    serializearrowptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_json_schema()) {
+                    jsonschemaptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_tagged()) {
+                    taggedptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_extended_json()) {
+                    extendedjsonptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_sort_keys()) {
+                    sortkeysptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_ensure_ascii()) {
+                    ensureasciiptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_indent()) {
+                    indentptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_separators()) {
+                    separatorsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_cls()) {
+                    clsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else {
+                    return raise_dumps_exception_fixed(unsupported_dumps_kwarg(arg));
+                }
+            }
+        }
+
+        let mut exc_type = typeref::get_json_encode_error();
+        if let Some(ptr) = exc_class {
+            cold_path!();
+            if !core::ptr::eq(ptr.as_ptr(), typeref::get_none()) {
+                if crate::ffi::PyExceptionClass_Check(ptr.as_ptr()) == 0 {
+                    return raise_dumps_exception_fixed(
+                        "dumps() exc_class must be an exception type",
+                    );
+                }
+                exc_type = ptr.as_ptr();
+            }
+        }
+
+        let mut opts: opt::Opt = 0;
+        if let Some(optsptr) = optsptr {
+            cold_path!();
+            match opt::opts_from_pyobject(optsptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("Invalid opts"),
+            }
+        }
+        stats::record(stats::Entry::Dumps, opts);
+        if let Some(ptr) = decimalsptr {
+            match float_decimals_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_fixed(
+                        "dumps() float_decimals must be an int from 0 to 15",
+                    );
+                }
+            }
+        }
+        let mut formatter = None;
+        if let Some(ptr) = floatformatptr {
+            match float_format_option(opts, ptr.as_ptr()) {
+                Ok((val, callable)) => {
+                    opts = val;
+                    formatter = callable;
+                }
+                Err(err) => {
+                    return raise_dumps_exception_dynamic(format!("dumps() {err}").as_str());
+                }
+            }
+        }
+        if let Some(ptr) = nonfiniteptr {
+            match non_finite_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {NON_FINITE_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = datetime64rangeptr {
+            match datetime64_range_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {DATETIME64_RANGE_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = fieldorderptr {
+            match field_order_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {FIELD_ORDER_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = pandasorientptr {
+            match pandas_orient_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {PANDAS_ORIENT_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = serializearrowptr {
+            match serialize_arrow_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {SERIALIZE_ARROW_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = taggedptr {
+            match tagged_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {TAGGED_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = extendedjsonptr {
+            match extended_json_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {EXTENDED_JSON_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = sortkeysptr {
+            match bool_option(opts, ptr.as_ptr(), opt::SORT_KEYS) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("dumps() sort_keys must be a bool"),
+            }
+        }
+        if let Some(ptr) = ensureasciiptr {
+            match bool_option(opts, ptr.as_ptr(), opt::ENSURE_ASCII) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("dumps() ensure_ascii must be a bool"),
+            }
+        }
+        if let Some(ptr) = indentptr {
+            match indent_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return raise_dumps_exception_fixed(
+                        "dumps() indent must be None, 2, 4, 8, or a str of that many spaces or a tab",
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = separatorsptr {
+            if !separators_valid(opts, ptr.as_ptr()) {
+                return raise_dumps_exception_fixed(
+                    "dumps() separators must be None, (\",\", \":\"), or (\",\", \": \") with indent",
+                );
+            }
+        }
+        // the `default()` of an instance of `cls`, which `default=` replaces
+        // as json.dumps() passes it to the instance
+        let mut encoder_default = None;
+        if let Some(ptr) = clsptr {
+            match json_encoder_default(ptr.as_ptr()) {
+                Ok(val) => encoder_default = val,
+                Err(err) => return raise_dumps_exception_fixed(err),
+            }
+            if default.is_none_or(|ptr| core::ptr::eq(ptr.as_ptr(), typeref::get_none())) {
+                default = encoder_default.or(default);
+            }
+        }
+        let mut comparator = None;
+        if let Some(ptr) = keyorderptr {
+            match key_order_option(opts, ptr.as_ptr()) {
+                Some((val, callable)) => {
+                    opts = val;
+                    comparator = callable;
+                }
+                None => {
+                    return raise_dumps_exception_dynamic(
+                        format!("dumps() {KEY_ORDER_ERROR}").as_str(),
+                    );
+                }
+            }
+        }
+        let mut width = 0;
+        if let Some(ptr) = widthptr {
+            match width_option(opts, ptr.as_ptr()) {
+                Ok(val) => width = val,
+                Err(err) => {
+                    return raise_dumps_exception_dynamic(format!("dumps() {err}").as_str());
+                }
+            }
+        }
+
+        let mut length: Option<usize> = None;
+        if let Some(ptr) = lengthptr {
+            cold_path!();
+            if !core::ptr::eq(ptr.as_ptr(), typeref::get_none()) {
+                match non_negative_int(ptr.as_ptr()) {
+                    Some(val) => length = Some(val),
+                    None => {
+                        return raise_dumps_exception_fixed(
+                            "dumps() length must be a non-negative int",
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut json_schema = None;
+        if let Some(ptr) = jsonschemaptr {
+            cold_path!();
+            if !core::ptr::eq(ptr.as_ptr(), typeref::get_none()) {
+                match JsonSchema::new(ptr.as_ptr()) {
+                    Ok(schema) => json_schema = Some(schema),
+                    Err(err) => {
+                        return raise_dumps_exception_dynamic(format!("dumps() {err}").as_str());
+                    }
+                }
+            }
+        }
+
+        let ret = serialize(*args, default, opts, length, width, comparator, formatter)
+            .and_then(|output| match &json_schema {
+                Some(schema) => match schema.check_output(output.as_ptr()) {
+                    Ok(()) => Ok(output),
+                    Err(err) => {
+                        ffi!(Py_DECREF(output.as_ptr()));
+                        Err(err)
+                    }
+                },
+                None => Ok(output),
+            })
+            .map_or_else(
+                |err| raise_dumps_exception_as(err.as_str(), exc_type),
+                NonNull::as_ptr,
+            );
+        if let Some(ptr) = encoder_default {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+        }
+        ret
+    }
+}
+
+/// Parse a non-negative `int` argument.
+#[cold]
+fn non_negative_int(ptr: *mut PyObject) -> Option<usize> {
+    if !is_type!(ob_type!(ptr), crate::typeref::int_type_ptr()) {
+        return None;
+    }
+    let val = ffi!(PyLong_AsLongLong(ptr));
+    if val == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        ffi!(PyErr_Clear());
+        return None;
+    }
+    usize::try_from(val).ok()
+}
+
+/// Apply a `float_decimals=` argument, which is `None` or an `int` from 0 to
+/// `MAX_FLOAT_DECIMALS`, to `opts`.
+#[cold]
+fn float_decimals_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    let decimals = u8::try_from(non_negative_int(ptr)?).ok()?;
+    (decimals <= opt::MAX_FLOAT_DECIMALS).then(|| opt::with_float_decimals(opts, decimals))
+}
+
+/// Parse a `width=` argument, which is `None` or a positive `int` and
+/// requires indented output, to the formatter's width, 0 being none.
+#[cold]
+fn width_option(opts: opt::Opt, ptr: *mut PyObject) -> Result<usize, &'static str> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Ok(0);
+    }
+    match non_negative_int(ptr) {
+        Some(width) if width > 0 => {
+            if opt_disabled!(opts, opt::INDENT) {
+                Err("width requires OPT_INDENT_2, OPT_INDENT_4, OPT_INDENT_8, or OPT_INDENT_TAB")
+            } else {
+                Ok(width)
+            }
+        }
+        _ => Err("width must be a positive int"),
+    }
+}
+
+/// Apply a `float_format=` argument, which is `None`, `".Nf"` with `N` from 0
+/// to `MAX_FLOAT_DECIMALS`, or a callable, to `opts`, returning the callable
+/// if given.
+#[cold]
+fn float_format_option(
+    opts: opt::Opt,
+    ptr: *mut PyObject,
+) -> Result<(opt::Opt, Option<NonNull<PyObject>>), &'static str> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Ok((opts, None));
+    }
+    if opts & opt::FLOAT_DECIMALS != 0 {
+        return Err("float_format cannot be combined with float_decimals");
+    }
+    if is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        fixed_decimals(ptr)
+            .map(|decimals| (opt::with_float_format_fixed(opts, decimals), None))
+            .ok_or(FLOAT_FORMAT_ERROR)
+    } else if ffi!(PyCallable_Check(ptr)) != 0 {
+        Ok((
+            opts & !opt::FLOAT_FORMAT | opt::FLOAT_FORMAT_CALLABLE | opt::FLOAT_FORMAT_ANY,
+            Some(nonnull!(ptr)),
+        ))
+    } else {
+        Err(FLOAT_FORMAT_ERROR)
+    }
+}
+
+/// Parse `".Nf"`, `N` being at most `MAX_FLOAT_DECIMALS`.
+fn fixed_decimals(ptr: *mut PyObject) -> Option<u8> {
+    let spec = unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? };
+    let digits = spec.strip_prefix('.')?.strip_suffix('f')?;
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let decimals = digits.parse::<u8>().ok()?;
+    (decimals <= opt::MAX_FLOAT_DECIMALS).then_some(decimals)
+}
+
+const FLOAT_FORMAT_ERROR: &str =
+    "float_format must be \".Nf\" with N from 0 to 15, a callable, or None";
+
+/// Apply a `key_order=` argument, which is `None`, one of the orderings by
+/// name, or a comparator, to `opts`, returning the comparator if given.
+#[cold]
+fn key_order_option(
+    opts: opt::Opt,
+    ptr: *mut PyObject,
+) -> Option<(opt::Opt, Option<NonNull<PyObject>>)> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some((opts, None));
+    }
+    let opts = opts & !opt::KEY_ORDER | opt::SORT_KEYS;
+    if is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+            "codepoint" => Some((opts, None)),
+            "case_insensitive" => Some((opts | opt::KEY_ORDER_CASE_INSENSITIVE, None)),
+            "natural" => Some((opts | opt::KEY_ORDER_NATURAL, None)),
+            _ => None,
+        }
+    } else if ffi!(PyCallable_Check(ptr)) != 0 {
+        Some((opts | opt::KEY_ORDER_CALLABLE, Some(nonnull!(ptr))))
+    } else {
+        None
+    }
+}
+
+/// Apply a `non_finite=` argument, which is `None` or how `NaN`, `Infinity`,
+/// and `-Infinity` are written by name, to `opts`.
+#[cold]
+fn non_finite_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    let policy = match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "null" => 0,
+        "literal" => opt::NON_FINITE_LITERAL,
+        "string" => opt::NON_FINITE_STRING,
+        "raise" => opt::NON_FINITE_RAISE,
+        _ => return None,
+    };
+    Some(opts & !opt::NON_FINITE | policy)
+}
+
+const NON_FINITE_ERROR: &str = "non_finite must be \"null\", \"literal\", \"string\", or \"raise\"";
+
+/// Apply a `datetime64_range=` argument, which is `None` or how `NaT` and
+/// `numpy.datetime64` values outside of years 1 to 9999 are written, to
+/// `opts`.
+#[cold]
+fn datetime64_range_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    let policy = match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "raise" => 0,
+        "null" => opt::DATETIME64_RANGE_NULL,
+        "clamp" => opt::DATETIME64_RANGE_CLAMP,
+        "expanded" => opt::DATETIME64_RANGE_EXPANDED,
+        _ => return None,
+    };
+    Some(opts & !opt::DATETIME64_RANGE | policy)
+}
+
+const DATETIME64_RANGE_ERROR: &str =
+    "datetime64_range must be \"raise\", \"null\", \"clamp\", or \"expanded\"";
+
+/// Apply a `field_order=` argument, which is `None` or the order the fields
+/// of a dataclass are written in by name, to `opts`.
+#[cold]
+fn field_order_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    let order = match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "insertion" => 0,
+        "declaration" => opt::FIELD_ORDER_DECLARATION,
+        "sorted" => opt::FIELD_ORDER_SORTED,
+        _ => return None,
+    };
+    Some(opts & !opt::FIELD_ORDER | order)
+}
+
+const FIELD_ORDER_ERROR: &str = "field_order must be \"insertion\", \"declaration\", or \"sorted\"";
+
+/// Apply a `pandas_orient=` argument, which is `None` or how a pandas
+/// `DataFrame` is written, to `opts`.
+#[cold]
+fn pandas_orient_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts & !opt::PANDAS_ORIENT);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    let orient = match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "records" => opt::PANDAS_ORIENT_RECORDS,
+        "columns" => opt::PANDAS_ORIENT_COLUMNS,
+        _ => return None,
+    };
+    Some(opts & !opt::PANDAS_ORIENT | orient)
+}
+
+const PANDAS_ORIENT_ERROR: &str = "pandas_orient must be \"records\", \"columns\", or None";
+
+/// Apply a `serialize_arrow=` argument, a bool, to `opts`.
+#[cold]
+fn serialize_arrow_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::true_ptr()) {
+        Some(opts | opt::SERIALIZE_ARROW)
+    } else if core::ptr::eq(ptr, typeref::false_ptr()) {
+        Some(opts & !opt::SERIALIZE_ARROW)
+    } else {
+        None
+    }
+}
+
+const SERIALIZE_ARROW_ERROR: &str = "serialize_arrow must be a bool";
+
+/// Apply a `tagged=` argument, a bool, to `opts`.
+#[cold]
+fn tagged_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::true_ptr()) {
+        Some(opts | opt::TAGGED)
+    } else if core::ptr::eq(ptr, typeref::false_ptr()) {
+        Some(opts & !opt::TAGGED)
+    } else {
+        None
+    }
+}
+
+const TAGGED_ERROR: &str = "tagged must be a bool";
+
+/// Apply an `extended_json=` argument, which is `None`, `"relaxed"`, or
+/// `"canonical"`, to `opts`.
+#[cold]
+fn extended_json_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    let mode = match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+        "relaxed" => opt::EXTENDED_JSON,
+        "canonical" => opt::EXTENDED_JSON | opt::EXTENDED_JSON_CANONICAL,
+        _ => return None,
+    };
+    Some(opts & !(opt::EXTENDED_JSON | opt::EXTENDED_JSON_CANONICAL) | mode)
+}
+
+const EXTENDED_JSON_ERROR: &str = "extended_json must be \"relaxed\" or \"canonical\"";
+
+/// Apply a bool argument that sets `flag`, such as `sort_keys=`, to `opts`.
+#[cold]
+fn bool_option(opts: opt::Opt, ptr: *mut PyObject, flag: opt::Opt) -> Option<opt::Opt> {
+    if bool_arg(ptr)? {
+        Some(opts | flag)
+    } else {
+        Some(opts & !flag)
+    }
+}
+
+/// The value of a bool argument, such as `strict=`, or `None` if it is not a
+/// bool.
+#[cold]
+fn bool_arg(ptr: *mut PyObject) -> Option<bool> {
+    if core::ptr::eq(ptr, typeref::true_ptr()) {
+        Some(true)
+    } else if core::ptr::eq(ptr, typeref::false_ptr()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Apply an `indent=` argument of `json.dumps()` to `opts`: `None`, 2, 4, or
+/// 8, or a `str` of that many spaces or a tab.
+#[cold]
+fn indent_option(opts: opt::Opt, ptr: *mut PyObject) -> Option<opt::Opt> {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return Some(opts);
+    }
+    let width = if is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        match unsafe { crate::str::PyStr::from_ptr_unchecked(ptr).to_str()? } {
+            "\t" => return Some(opts & !opt::INDENT | opt::INDENT_TAB),
+            spaces if spaces.bytes().all(|byte| byte == b' ') => spaces.len(),
+            _ => return None,
+        }
+    } else {
+        non_negative_int(ptr)?
+    };
+    let indent = match width {
+        2 => opt::INDENT_2,
+        4 => opt::INDENT_4,
+        8 => opt::INDENT_8,
+        _ => return None,
+    };
+    Some(opts & !opt::INDENT | indent)
+}
+
+/// Whether a `separators=` argument of `json.dumps()` is what `dumps()`
+/// writes with `opts`: `(",", ":")`, or `(",", ": ")` when indenting.
+#[cold]
+fn separators_valid(opts: opt::Opt, ptr: *mut PyObject) -> bool {
+    if core::ptr::eq(ptr, typeref::get_none()) {
+        return true;
+    }
+    let is_tuple = is_type!(ob_type!(ptr), crate::typeref::tuple_type_ptr());
+    if !(is_tuple || is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()))
+        || ffi!(Py_SIZE(ptr)) != 2
+    {
+        return false;
+    }
+    let item = |idx| {
+        let item = if is_tuple {
+            unsafe { crate::ffi::PyTuple_GET_ITEM(ptr, idx) }
+        } else {
+            unsafe { crate::ffi::PyList_GET_ITEM(ptr, idx) }
+        };
+        if is_type!(ob_type!(item), crate::typeref::str_type_ptr()) {
+            unsafe { crate::str::PyStr::from_ptr_unchecked(item).to_str() }
+        } else {
+            None
+        }
+    };
+    let key_separator = if opt_enabled!(opts, opt::INDENT) {
+        ": "
+    } else {
+        ":"
+    };
+    item(0) == Some(",") && item(1) == Some(key_separator)
+}
+
+/// The bound `default()` of an instance of a `cls=` argument of
+/// `json.dumps()`, if it overrides it, or `None`. `cls` is a subclass of
+/// `json.JSONEncoder` that does not override encoding and is called without
+/// arguments.
+#[cold]
+fn json_encoder_default(cls: *mut PyObject) -> Result<Option<NonNull<PyObject>>, &'static str> {
+    if core::ptr::eq(cls, typeref::get_none()) {
+        return Ok(None);
+    }
+    let base = typeref::get_json_encoder_type();
+    if !is_json_subclass(cls, base, &[c"encode", c"iterencode"]) {
+        return Err(
+            "dumps() cls must be a subclass of json.JSONEncoder that does not override encode() or iterencode()",
+        );
+    }
+    if inherits(cls, base, c"default") {
+        return Ok(None);
+    }
+    let encoder = ffi!(PyObject_CallFunctionObjArgs(cls, null_mut::<PyObject>()));
+    if encoder.is_null() {
+        ffi!(PyErr_Clear());
+        return Err("dumps() cls could not be called without arguments");
+    }
+    let default = ffi!(PyObject_GetAttrString(encoder, c"default".as_ptr()));
+    ffi!(Py_DECREF(encoder));
+    ffi!(PyErr_Clear());
+    Ok(NonNull::new(default))
+}
+
+const KEY_ORDER_ERROR: &str =
+    "key_order must be \"codepoint\", \"case_insensitive\", \"natural\", or a callable";
+
+/// Arguments of `dumps_batch()` and `dumps_length()`.
+struct DefaultOptionArgs {
+    obj: *mut PyObject,
+    default: Option<NonNull<PyObject>>,
+    opts: opt::Opt,
+    /// 0 if not given, see `serialize()`.
+    width: usize,
+    comparator: Option<NonNull<PyObject>>,
+    formatter: Option<NonNull<PyObject>>,
+}
+
+/// Parse the `(obj, /, default=None, option=None, *, float_decimals=None,
+/// float_format=None, key_order=None, non_finite=None, width=None,
+/// datetime64_range=None, field_order=None, pandas_orient=None,
+/// serialize_arrow=False, tagged=False, extended_json=None)`
+/// signature shared by
+/// `dumps_batch()` and `dumps_length()`.
+#[inline(always)]
+unsafe fn parse_default_option_args(
+    entry: stats::Entry,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<DefaultOptionArgs, String> {
+    unsafe {
+        let name = entry.name();
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut optsptr: Option<NonNull<PyObject>> = None;
+        let mut decimalsptr: Option<NonNull<PyObject>> = None;
+        let mut keyorderptr: Option<NonNull<PyObject>> = None;
+        let mut floatformatptr: Option<NonNull<PyObject>> = None;
+        let mut nonfiniteptr: Option<NonNull<PyObject>> = None;
+        let mut widthptr: Option<NonNull<PyObject>> = None;
+        let mut datetime64rangeptr: Option<NonNull<PyObject>> = None;
+        let mut fieldorderptr: Option<NonNull<PyObject>> = None;
+        let mut pandasorientptr: Option<NonNull<PyObject>> = None;
+        let mut serializearrowptr: Option<NonNull<PyObject>> = None;
+        let mut taggedptr: Option<NonNull<PyObject>> = None;
+        let mut extendedjsonptr: Option<NonNull<PyObject>> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return Err(format!("{name}() missing 1 required positional argument"));
+        }
+        if num_args > 3 {
+            cold_path!();
+            return Err(format!("{name}() takes at most 3 positional arguments"));
+        }
+        if num_args >= 2 {
+            default = Some(NonNull::new_unchecked(*args.offset(1)));
+        }
+        if num_args == 3 {
+            optsptr = Some(NonNull::new_unchecked(*args.offset(2)));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_option()) {
+                    if num_args == 3 {
+                        cold_path!();
+                        return Err(format!(
+                            "{name}() got multiple values for argument: 'option'"
+                        ));
+                    }
+                    optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_default()) {
+                    if num_args >= 2 {
+                        cold_path!();
+                        return Err(format!(
+                            "{name}() got multiple values for argument: 'default'"
+                        ));
+                    }
+                    default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_float_decimals()) {
+                    decimalsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_key_order()) {
+                    keyorderptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_float_format()) {
+                    floatformatptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_non_finite()) {
+                    nonfiniteptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_width()) {
+                    widthptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_datetime64_range()) {
+                    datetime64rangeptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_field_order()) {
+                    fieldorderptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_pandas_orient()) {
+                    pandasorientptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_serialize_arrow()) {
+                    serializearrowptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_tagged()) {
+                    taggedptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_extended_json()) {
+                    extendedjsonptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else {
+                    return Err(format!("{name}() got an unexpected keyword argument"));
+                }
+            }
+        }
+
+        let mut opts: opt::Opt = 0;
+        if let Some(optsptr) = optsptr {
+            cold_path!();
+            match opt::opts_from_pyobject(optsptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(String::from("Invalid opts")),
+            }
+        }
+        stats::record(entry, opts);
+        if let Some(ptr) = decimalsptr {
+            match float_decimals_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => {
+                    return Err(format!(
+                        "{name}() float_decimals must be an int from 0 to 15"
+                    ));
+                }
+            }
+        }
+        let mut formatter = None;
+        if let Some(ptr) = floatformatptr {
+            let (val, callable) =
+                float_format_option(opts, ptr.as_ptr()).map_err(|err| format!("{name}() {err}"))?;
+            opts = val;
+            formatter = callable;
+        }
+        if let Some(ptr) = nonfiniteptr {
+            match non_finite_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {NON_FINITE_ERROR}")),
+            }
+        }
+        if let Some(ptr) = datetime64rangeptr {
+            match datetime64_range_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {DATETIME64_RANGE_ERROR}")),
+            }
+        }
+        if let Some(ptr) = fieldorderptr {
+            match field_order_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {FIELD_ORDER_ERROR}")),
+            }
+        }
+        if let Some(ptr) = pandasorientptr {
+            match pandas_orient_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {PANDAS_ORIENT_ERROR}")),
+            }
+        }
+        if let Some(ptr) = serializearrowptr {
+            match serialize_arrow_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {SERIALIZE_ARROW_ERROR}")),
+            }
+        }
+        if let Some(ptr) = taggedptr {
+            match tagged_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {TAGGED_ERROR}")),
+            }
+        }
+        if let Some(ptr) = extendedjsonptr {
+            match extended_json_option(opts, ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return Err(format!("{name}() {EXTENDED_JSON_ERROR}")),
+            }
+        }
+        let mut comparator = None;
+        if let Some(ptr) = keyorderptr {
+            match key_order_option(opts, ptr.as_ptr()) {
+                Some((val, callable)) => {
+                    opts = val;
+                    comparator = callable;
+                }
+                None => return Err(format!("{name}() {KEY_ORDER_ERROR}")),
+            }
+        }
+        let mut width = 0;
+        if let Some(ptr) = widthptr {
+            width = width_option(opts, ptr.as_ptr()).map_err(|err| format!("{name}() {err}"))?;
+        }
+        Ok(DefaultOptionArgs {
+            obj: *args,
+            default: default,
+            opts: opts,
+            width: width,
+            comparator: comparator,
+            formatter: formatter,
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn dumps_batch(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        match parse_default_option_args(stats::Entry::DumpsBatch, args, nargs, kwnames) {
+            // width requires indented output, which is not supported
+            Ok(args) => crate::serialize::serialize_batch(
+                args.obj,
+                args.default,
+                args.opts,
+                args.comparator,
+                args.formatter,
+            )
+            .map_or_else(
+                |err| raise_dumps_exception_dynamic(err.as_str()),
+                NonNull::as_ptr,
+            ),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn dumps_length(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        match parse_default_option_args(stats::Entry::DumpsLength, args, nargs, kwnames) {
+            Ok(args) => {
+                match crate::serialize::serialize_length(
+                    args.obj,
+                    args.default,
+                    args.opts,
+                    args.width,
+                    args.comparator,
+                    args.formatter,
+                ) {
+                    #[allow(clippy::cast_possible_wrap)]
+                    Ok(len) => crate::ffi::PyLong_FromLongLong(len as i64),
+                    Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+                }
+            }
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+/// Parse the `(iterable, /, validate=False)` signature shared by
+/// `join_array()` and `join_object()`.
+#[inline(always)]
+unsafe fn parse_join_args(
+    name: &str,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<(*mut PyObject, bool), String> {
+    unsafe {
+        let mut validate: Option<*mut PyObject> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return Err(format!("{name}() missing 1 required positional argument"));
+        }
+        if num_args > 2 {
+            cold_path!();
+            return Err(format!("{name}() takes at most 2 positional arguments"));
+        }
+        if num_args == 2 {
+            validate = Some(*args.offset(1));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_validate()) {
+                    if validate.is_some() {
+                        cold_path!();
+                        return Err(format!(
+                            "{name}() got multiple values for argument: 'validate'"
+                        ));
+                    }
+                    validate = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(format!("{name}() got an unexpected keyword argument"));
+                }
+            }
+        }
+        let validate = match validate.map_or(Some(false), bool_arg) {
+            Some(validate) => validate,
+            None => {
+                cold_path!();
+                return Err(format!("{name}() validate must be a bool"));
+            }
+        };
+        Ok((*args, validate))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn compare_digest_json(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "compare_digest_json() takes exactly 2 positional arguments",
+            );
+        }
+        stats::record(stats::Entry::CompareDigestJson, 0);
+        match crate::serialize::compare_digest_json(*args, *args.offset(1)) {
+            Ok(true) => use_immortal!(typeref::true_ptr()),
+            Ok(false) => use_immortal!(typeref::false_ptr()),
+            Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn register_serializer(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "register_serializer() takes exactly 2 positional arguments",
+            );
+        }
+        match crate::serialize::register_serializer(*args, *args.offset(1)) {
+            Ok(()) => use_immortal!(typeref::get_none()),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn register_deserializer(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "register_deserializer() takes exactly 2 positional arguments",
+            );
+        }
+        match crate::serialize::register_deserializer(*args, *args.offset(1)) {
+            Ok(()) => use_immortal!(typeref::get_none()),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn register_tagged(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        if nargs != 1 && nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "register_tagged() takes 1 or 2 positional arguments",
+            );
+        }
+        let tag = if nargs == 2 {
+            *args.offset(1)
+        } else {
+            typeref::get_none()
+        };
+        match crate::serialize::register_tagged(*args, tag) {
+            Ok(()) => use_immortal!(typeref::get_none()),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn register_type(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        if nargs != 1 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "register_type() takes exactly 1 positional argument",
+            );
+        }
+        match crate::serialize::register_type(*args) {
+            Ok(()) => use_immortal!(typeref::get_none()),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn reset_for_testing(
+    _self: *mut PyObject,
+    _args: *mut PyObject,
+) -> *mut PyObject {
+    match runtime::reset() {
+        Ok(()) => use_immortal!(typeref::get_none()),
+        Err(err) => raise_dumps_exception_fixed(err),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn stats(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut reset = false;
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args != 0 {
+            cold_path!();
+            return raise_dumps_exception_fixed("stats() takes no positional arguments");
+        }
+        if !kwnames.is_null() {
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if !matches_kwarg!(arg, typeref::get_reset()) {
+                    return raise_dumps_exception_fixed(
+                        "stats() got an unexpected keyword argument",
+                    );
+                }
+                reset = match bool_arg(*args.offset(i)) {
+                    Some(reset) => reset,
+                    None => return raise_dumps_exception_fixed("stats() reset must be a bool"),
+                };
+            }
+        }
+        let stats = &(*runtime::current_state()).stats;
+        let dict = stats.to_dict();
+        if reset {
+            stats.zero();
+        }
+        dict
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn collect_stats(
+    _self: *mut PyObject,
+    enabled: *mut PyObject,
+) -> *mut PyObject {
+    let enabled = match bool_arg(enabled) {
+        Some(enabled) => enabled,
+        None => return raise_dumps_exception_fixed("collect_stats() enabled must be a bool"),
+    };
+    unsafe { (*runtime::current_state()).stats.set_enabled(enabled) }
+    use_immortal!(typeref::get_none())
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn set_compat_mode(
+    _self: *mut PyObject,
+    enabled: *mut PyObject,
+) -> *mut PyObject {
+    let enabled = match bool_arg(enabled) {
+        Some(enabled) => enabled,
+        None => return raise_dumps_exception_fixed("set_compat_mode() enabled must be a bool"),
+    };
+    unsafe {
+        (*runtime::current_state())
+            .compat_mode
+            .store(enabled, core::sync::atomic::Ordering::Relaxed);
+    }
+    use_immortal!(typeref::get_none())
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn equals(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Equals, 0);
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed("equals() takes exactly 2 positional arguments");
+        }
+        crate::serialize::equals(*args, *args.offset(1))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn merge3(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Merge3, 0);
+        if nargs != 3 {
+            cold_path!();
+            return raise_dumps_exception_fixed("merge3() takes exactly 3 positional arguments");
+        }
+        crate::serialize::merge3(*args, *args.offset(1), *args.offset(2))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn merge_patch(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::MergePatch, 0);
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "merge_patch() takes exactly 2 positional arguments",
+            );
+        }
+        crate::serialize::merge_patch(*args, *args.offset(1))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn pointer(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Pointer, 0);
+        if nargs != 2 {
+            cold_path!();
+            return raise_dumps_exception_fixed("pointer() takes exactly 2 positional arguments");
+        }
+        crate::serialize::pointer(*args, *args.offset(1))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn edit(_self: *mut PyObject, data: *mut PyObject) -> *mut PyObject {
+    crate::serialize::edit(data)
+}
+
+// not `splice`, which is exported by libc
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn splice_json(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut optsptr: Option<NonNull<PyObject>> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args != 3 {
+            cold_path!();
+            return raise_dumps_exception_fixed("splice() takes exactly 3 positional arguments");
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_option()) {
+                    optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_default()) {
+                    default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else {
+                    return raise_dumps_exception_fixed(
+                        "splice() got an unexpected keyword argument",
+                    );
+                }
+            }
+        }
+        let mut opts: opt::Opt = 0;
+        if let Some(optsptr) = optsptr {
+            match opt::opts_from_pyobject(optsptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("Invalid opts"),
+            }
+        }
+        stats::record(stats::Entry::Splice, opts);
+        crate::serialize::splice(*args, *args.offset(1), *args.offset(2), opts, default)
+            .map_or_else(
+                |err| raise_dumps_exception_dynamic(err.as_str()),
+                NonNull::as_ptr,
+            )
+    }
+}
+
+unsafe fn parse_flatten_args(
+    name: &str,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<(*mut PyObject, Option<*mut PyObject>), String> {
+    unsafe {
+        let mut sep: Option<*mut PyObject> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return Err(format!("{name}() missing 1 required positional argument"));
+        }
+        if num_args > 2 {
+            cold_path!();
+            return Err(format!("{name}() takes at most 2 positional arguments"));
+        }
+        if num_args == 2 {
+            sep = Some(*args.offset(1));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_sep()) {
+                    if sep.is_some() {
+                        cold_path!();
+                        return Err(format!("{name}() got multiple values for argument: 'sep'"));
+                    }
+                    sep = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(format!("{name}() got an unexpected keyword argument"));
+                }
+            }
+        }
+        Ok((*args, sep))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn flatten(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Flatten, 0);
+        match parse_flatten_args("flatten", args, nargs, kwnames) {
+            Ok((obj, sep)) => crate::serialize::flatten(obj, sep),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn unflatten(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Unflatten, 0);
+        match parse_flatten_args("unflatten", args, nargs, kwnames) {
+            Ok((mapping, sep)) => crate::serialize::unflatten(mapping, sep),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn dumps_msgpack(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut optsptr: Option<NonNull<PyObject>> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "dumps_msgpack() missing 1 required positional argument: 'obj'",
+            );
+        }
+        if num_args > 3 {
+            cold_path!();
+            return raise_dumps_exception_fixed(
+                "dumps_msgpack() takes at most 3 positional arguments",
+            );
+        }
+        if num_args >= 2 {
+            default = Some(NonNull::new_unchecked(*args.offset(1)));
+        }
+        if num_args == 3 {
+            optsptr = Some(NonNull::new_unchecked(*args.offset(2)));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_option()) {
+                    if num_args == 3 {
+                        cold_path!();
+                        return raise_dumps_exception_fixed(
+                            "dumps_msgpack() got multiple values for argument: 'option'",
+                        );
+                    }
+                    optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else if matches_kwarg!(arg, typeref::get_default()) {
+                    if num_args >= 2 {
+                        cold_path!();
+                        return raise_dumps_exception_fixed(
+                            "dumps_msgpack() got multiple values for argument: 'default'",
+                        );
+                    }
+                    default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+                } else {
+                    return raise_dumps_exception_fixed(
+                        "dumps_msgpack() got an unexpected keyword argument",
+                    );
+                }
+            }
+        }
+        let mut opts: opt::Opt = 0;
+        if let Some(optsptr) = optsptr {
+            match opt::opts_from_pyobject(optsptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("Invalid opts"),
+            }
+        }
+        stats::record(stats::Entry::DumpsMsgpack, opts);
+        crate::serialize::serialize_msgpack(*args, default, opts).map_or_else(
+            |err| raise_dumps_exception_dynamic(err.as_str()),
+            NonNull::as_ptr,
+        )
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn loads_msgpack(
+    _self: *mut PyObject,
+    data: *mut PyObject,
+) -> *mut PyObject {
+    stats::record(stats::Entry::LoadsMsgpack, 0);
+    crate::deserialize::deserialize_msgpack(data)
+        .map_or_else(raise_loads_exception, NonNull::as_ptr)
+}
+
+/// Parse the `(obj, /, algorithm="sha256")` signature of `hash()`.
+#[cold]
+unsafe fn parse_hash_args(
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<*mut PyObject, String> {
+    unsafe {
+        let mut algorithm: Option<*mut PyObject> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return Err(String::from(
+                "hash() missing 1 required positional argument",
+            ));
+        }
+        if num_args > 2 {
+            cold_path!();
+            return Err(String::from("hash() takes at most 2 positional arguments"));
+        }
+        if num_args == 2 {
+            algorithm = Some(*args.offset(1));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_algorithm()) {
+                    if algorithm.is_some() {
+                        cold_path!();
+                        return Err(String::from(
+                            "hash() got multiple values for argument: 'algorithm'",
+                        ));
+                    }
+                    algorithm = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(String::from("hash() got an unexpected keyword argument"));
+                }
+            }
+        }
+        if let Some(algorithm) = algorithm {
+            let is_sha256 = is_type!(ob_type!(algorithm), crate::typeref::str_type_ptr())
+                && crate::str::PyStr::from_ptr_unchecked(algorithm).to_str() == Some("sha256");
+            if !is_sha256 {
+                return Err(String::from("hash() algorithm must be \"sha256\""));
+            }
+        }
+        Ok(*args)
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn hash(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Hash, 0);
+        let digest = parse_hash_args(args, nargs, kwnames).and_then(crate::serialize::hash_json);
+        match digest {
+            Ok(digest) => crate::ffi::PyBytes_FromStringAndSize(
+                digest.as_ptr().cast::<core::ffi::c_char>(),
+                usize_to_isize(digest.len()),
+            ),
+            Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn validate(
+    _self: *mut PyObject,
+    data: *mut PyObject,
+) -> *mut PyObject {
+    stats::record(stats::Entry::Validate, 0);
+    match crate::deserialize::validate(data) {
+        Ok(()) => use_immortal!(typeref::get_none()),
+        Err(err) => raise_loads_exception(err),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn minify(_self: *mut PyObject, data: *mut PyObject) -> *mut PyObject {
+    stats::record(stats::Entry::Minify, 0);
+    crate::deserialize::reformat(data, None).map_or_else(raise_loads_exception, NonNull::as_ptr)
+}
+
+/// Parse the `(data, /, indent=2)` signature of `prettify()`.
+#[cold]
+unsafe fn parse_prettify_args(
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> Result<(*mut PyObject, usize), String> {
+    unsafe {
+        let mut indent: Option<*mut PyObject> = None;
+
+        let num_args = PyVectorcall_NARGS(isize_to_usize(nargs));
+        if num_args == 0 {
+            cold_path!();
+            return Err(String::from(
+                "prettify() missing 1 required positional argument",
+            ));
+        }
+        if num_args > 2 {
+            cold_path!();
+            return Err(String::from(
+                "prettify() takes at most 2 positional arguments",
+            ));
+        }
+        if num_args == 2 {
+            indent = Some(*args.offset(1));
+        }
+        if !kwnames.is_null() {
+            cold_path!();
+            for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
+                let arg = crate::ffi::PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+                if matches_kwarg!(arg, typeref::get_indent()) {
+                    if indent.is_some() {
+                        cold_path!();
+                        return Err(String::from(
+                            "prettify() got multiple values for argument: 'indent'",
+                        ));
+                    }
+                    indent = Some(*args.offset(num_args + i));
+                } else {
+                    return Err(String::from(
+                        "prettify() got an unexpected keyword argument",
+                    ));
+                }
+            }
+        }
+        let Some(indent) = indent else {
+            return Ok((*args, 2));
+        };
+        match non_negative_int(indent) {
+            Some(indent) => Ok((*args, indent)),
+            None => Err(String::from("prettify() indent must be a non-negative int")),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn prettify(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::Prettify, 0);
+        match parse_prettify_args(args, nargs, kwnames) {
+            Ok((data, indent)) => crate::deserialize::reformat(data, Some(indent))
+                .map_or_else(raise_loads_exception, NonNull::as_ptr),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn join_array(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::JoinArray, 0);
+        match parse_join_args("join_array", args, nargs, kwnames) {
+            Ok((obj, validate)) => crate::serialize::join_array(obj, validate).map_or_else(
+                |err| raise_dumps_exception_dynamic(err.as_str()),
+                NonNull::as_ptr,
+            ),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub(crate) unsafe extern "C" fn join_object(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        stats::record(stats::Entry::JoinObject, 0);
+        match parse_join_args("join_object", args, nargs, kwnames) {
+            Ok((obj, validate)) => crate::serialize::join_object(obj, validate).map_or_else(
+                |err| raise_dumps_exception_dynamic(err.as_str()),
+                NonNull::as_ptr,
+            ),
+            Err(err) => raise_dumps_exception_fixed(err.as_str()),
+        }
     }
 }
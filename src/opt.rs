@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2020-2025)
 
-pub(crate) type Opt = u32;
+pub(crate) type Opt = u64;
 
 pub(crate) const INDENT_2: Opt = 1;
 pub(crate) const NAIVE_UTC: Opt = 1 << 1;
@@ -15,6 +15,26 @@ pub(crate) const PASSTHROUGH_SUBCLASS: Opt = 1 << 8;
 pub(crate) const PASSTHROUGH_DATETIME: Opt = 1 << 9;
 pub(crate) const APPEND_NEWLINE: Opt = 1 << 10;
 pub(crate) const PASSTHROUGH_DATACLASS: Opt = 1 << 11;
+pub(crate) const NAMEDTUPLE_AS_OBJECT: Opt = 1 << 12;
+pub(crate) const APPEND_CRLF: Opt = 1 << 13;
+pub(crate) const DEFAULT_PATH: Opt = 1 << 14;
+pub(crate) const SKIP_UNSUPPORTED: Opt = 1 << 15;
+pub(crate) const NULL_UNSUPPORTED: Opt = 1 << 16;
+pub(crate) const BYTES_KEYS_LATIN1: Opt = 1 << 17;
+pub(crate) const BYTES_KEYS_BASE64: Opt = 1 << 18;
+pub(crate) const INDENT_4: Opt = 1 << 19;
+pub(crate) const INDENT_8: Opt = 1 << 20;
+pub(crate) const INDENT_TAB: Opt = 1 << 21;
+pub(crate) const ENSURE_ASCII: Opt = 1 << 22;
+pub(crate) const OMIT_NULL: Opt = 1 << 23;
+pub(crate) const OMIT_EMPTY_DICT: Opt = 1 << 25;
+pub(crate) const OMIT_EMPTY_LIST: Opt = 1 << 26;
+pub(crate) const OMIT_EMPTY_STR: Opt = 1 << 27;
+pub(crate) const BIG_INTEGER: Opt = 1 << 28;
+pub(crate) const COMPAT_V1: Opt = 1 << 29;
+
+// options of loads(), which are not in MAX_OPT
+pub(crate) const OMIT_NULL_ON_LOAD: Opt = 1 << 24;
 
 // deprecated
 pub(crate) const SERIALIZE_DATACLASS: Opt = 0;
@@ -22,21 +42,223 @@ pub(crate) const SERIALIZE_UUID: Opt = 0;
 
 pub(crate) const SORT_OR_NON_STR_KEYS: Opt = SORT_KEYS | NON_STR_KEYS;
 
+pub(crate) const INDENT: Opt = INDENT_2 | INDENT_4 | INDENT_8 | INDENT_TAB;
+
+pub(crate) const OMIT_EMPTY: Opt = OMIT_EMPTY_DICT | OMIT_EMPTY_LIST | OMIT_EMPTY_STR;
+
 pub(crate) const NOT_PASSTHROUGH: Opt =
     !(PASSTHROUGH_DATETIME | PASSTHROUGH_DATACLASS | PASSTHROUGH_SUBCLASS);
 
-#[allow(clippy::cast_possible_wrap)]
-pub(crate) const MAX_OPT: i32 = (APPEND_NEWLINE
+// set from the datetime64_range= keyword argument rather than option=, so
+// not in MAX_OPT; neither is raise. Below bit 48 so that SerializerState::opts()
+// keeps it.
+const DATETIME64_RANGE_SHIFT: u32 = 30;
+pub(crate) const DATETIME64_RANGE: Opt = 0b11 << DATETIME64_RANGE_SHIFT;
+pub(crate) const DATETIME64_RANGE_NULL: Opt = 1 << DATETIME64_RANGE_SHIFT;
+pub(crate) const DATETIME64_RANGE_CLAMP: Opt = 2 << DATETIME64_RANGE_SHIFT;
+pub(crate) const DATETIME64_RANGE_EXPANDED: Opt = 3 << DATETIME64_RANGE_SHIFT;
+
+// set from the pandas_orient= keyword argument rather than option=, so not
+// in MAX_OPT; a pandas object is passed to default= without it
+const PANDAS_ORIENT_SHIFT: u32 = 32;
+pub(crate) const PANDAS_ORIENT: Opt = 0b11 << PANDAS_ORIENT_SHIFT;
+pub(crate) const PANDAS_ORIENT_RECORDS: Opt = 1 << PANDAS_ORIENT_SHIFT;
+pub(crate) const PANDAS_ORIENT_COLUMNS: Opt = 2 << PANDAS_ORIENT_SHIFT;
+
+// set from the serialize_arrow= keyword argument rather than option=, as
+// option= has no bit left below the keyword arguments
+pub(crate) const SERIALIZE_ARROW: Opt = 1 << 34;
+
+// set from the tagged= keyword argument rather than option=, so not in
+// MAX_OPT
+pub(crate) const TAGGED: Opt = 1 << 35;
+
+// set from the field_order= keyword argument rather than option=, so not in
+// MAX_OPT; neither is insertion
+const FIELD_ORDER_SHIFT: u32 = 36;
+pub(crate) const FIELD_ORDER: Opt = 0b11 << FIELD_ORDER_SHIFT;
+pub(crate) const FIELD_ORDER_DECLARATION: Opt = 1 << FIELD_ORDER_SHIFT;
+pub(crate) const FIELD_ORDER_SORTED: Opt = 2 << FIELD_ORDER_SHIFT;
+
+// set from the non_finite= keyword argument rather than option=, so not in
+// MAX_OPT; neither is null
+const NON_FINITE_SHIFT: u32 = 38;
+pub(crate) const NON_FINITE: Opt = 0b11 << NON_FINITE_SHIFT;
+pub(crate) const NON_FINITE_LITERAL: Opt = 1 << NON_FINITE_SHIFT;
+pub(crate) const NON_FINITE_STRING: Opt = 2 << NON_FINITE_SHIFT;
+pub(crate) const NON_FINITE_RAISE: Opt = 3 << NON_FINITE_SHIFT;
+
+// set from the key_order= keyword argument rather than option=, with
+// SORT_KEYS, so not in MAX_OPT
+const KEY_ORDER_SHIFT: u32 = 40;
+pub(crate) const KEY_ORDER: Opt = 0b11 << KEY_ORDER_SHIFT;
+pub(crate) const KEY_ORDER_CASE_INSENSITIVE: Opt = 1 << KEY_ORDER_SHIFT;
+pub(crate) const KEY_ORDER_NATURAL: Opt = 2 << KEY_ORDER_SHIFT;
+pub(crate) const KEY_ORDER_CALLABLE: Opt = 3 << KEY_ORDER_SHIFT;
+
+// set from the float_decimals= keyword argument rather than option=, so not
+// in MAX_OPT; the decimal places are above the bits SerializerState::opts()
+// keeps, as only the writer reads them
+pub(crate) const FLOAT_DECIMALS: Opt = 1 << 42;
+const FLOAT_DECIMALS_SHIFT: u32 = 56;
+const FLOAT_DECIMALS_MASK: Opt = 0b1111;
+pub(crate) const MAX_FLOAT_DECIMALS: u8 = 15;
+
+// set from the extended_json= keyword argument of dumps() rather than
+// option=, so not in MAX_OPT
+pub(crate) const EXTENDED_JSON: Opt = 1 << 43;
+// with EXTENDED_JSON, canonical rather than relaxed mode, which is installed
+// for the call
+pub(crate) const EXTENDED_JSON_CANONICAL: Opt = 1 << 44;
+
+// set by SerializerState::new() when Python code other than default= may run
+// while serializing, such as a key_order= or float_format= callable or a
+// serializer registered with register_serializer()
+pub(crate) const CALLBACKS: Opt = 1 << 45;
+
+// set by dumps_msgpack() rather than option=, so not in MAX_OPT, for the
+// types only MessagePack can represent, such as bytes
+pub(crate) const MSGPACK: Opt = 1 << 46;
+
+// set from the float_format= keyword argument rather than option=, so not
+// in MAX_OPT
+const FLOAT_FORMAT_SHIFT: u32 = 49;
+pub(crate) const FLOAT_FORMAT: Opt = 0b11 << FLOAT_FORMAT_SHIFT;
+pub(crate) const FLOAT_FORMAT_FIXED: Opt = 1 << FLOAT_FORMAT_SHIFT;
+pub(crate) const FLOAT_FORMAT_CALLABLE: Opt = 2 << FLOAT_FORMAT_SHIFT;
+const FLOAT_FORMAT_FIXED_SHIFT: u32 = 51;
+// set with FLOAT_FORMAT, which SerializerState::opts() does not keep, so that
+// a serializer writing floats without the writer can tell
+pub(crate) const FLOAT_FORMAT_ANY: Opt = 1 << 47;
+const FLOAT_FORMAT_FIXED_MASK: Opt = 0b1111;
+
+/// Options under which `dumps()` of a `loads_tracked()` result would differ
+/// from its input by more than formatting, or must be formatted as a profile
+/// writes it, so it is serialized instead.
+pub(crate) const NOT_DECODED_PASSTHROUGH: Opt = COMPAT_V1
+    | ENSURE_ASCII
+    | EXTENDED_JSON
+    | FLOAT_DECIMALS
+    | FLOAT_FORMAT
+    | INDENT
+    | OMIT_EMPTY
+    | OMIT_NULL
+    | SORT_KEYS
+    | STRICT_INTEGER;
+
+/// Set `FLOAT_DECIMALS` with floats rounded to `decimals` decimal places,
+/// which is at most `MAX_FLOAT_DECIMALS`.
+pub(crate) const fn with_float_decimals(opts: Opt, decimals: u8) -> Opt {
+    debug_assert!(decimals <= MAX_FLOAT_DECIMALS);
+    (opts & !(FLOAT_DECIMALS_MASK << FLOAT_DECIMALS_SHIFT))
+        | FLOAT_DECIMALS
+        | ((decimals as Opt) << FLOAT_DECIMALS_SHIFT)
+}
+
+/// The decimal places floats are rounded to, if `FLOAT_DECIMALS` is set.
+#[inline(always)]
+pub(crate) const fn float_decimals(opts: Opt) -> Option<u8> {
+    if opts & FLOAT_DECIMALS == 0 {
+        None
+    } else {
+        #[allow(clippy::cast_possible_truncation)]
+        Some(((opts >> FLOAT_DECIMALS_SHIFT) & FLOAT_DECIMALS_MASK) as u8)
+    }
+}
+
+/// Set `FLOAT_FORMAT_FIXED` with floats written with `decimals` digits after
+/// the decimal point, which is at most `MAX_FLOAT_DECIMALS`.
+pub(crate) const fn with_float_format_fixed(opts: Opt, decimals: u8) -> Opt {
+    debug_assert!(decimals <= MAX_FLOAT_DECIMALS);
+    (opts & !FLOAT_FORMAT & !(FLOAT_FORMAT_FIXED_MASK << FLOAT_FORMAT_FIXED_SHIFT))
+        | FLOAT_FORMAT_FIXED
+        | FLOAT_FORMAT_ANY
+        | ((decimals as Opt) << FLOAT_FORMAT_FIXED_SHIFT)
+}
+
+/// The digits after the decimal point of `FLOAT_FORMAT_FIXED`.
+#[inline(always)]
+pub(crate) const fn float_format_fixed(opts: Opt) -> u8 {
+    #[allow(clippy::cast_possible_truncation)]
+    let decimals = ((opts >> FLOAT_FORMAT_FIXED_SHIFT) & FLOAT_FORMAT_FIXED_MASK) as u8;
+    decimals
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+pub(crate) const MAX_OPT: i32 = (APPEND_CRLF
+    | APPEND_NEWLINE
+    | BIG_INTEGER
+    | BYTES_KEYS_BASE64
+    | BYTES_KEYS_LATIN1
+    | COMPAT_V1
+    | DEFAULT_PATH
+    | ENSURE_ASCII
     | INDENT_2
+    | INDENT_4
+    | INDENT_8
+    | INDENT_TAB
     | NAIVE_UTC
+    | NAMEDTUPLE_AS_OBJECT
     | NON_STR_KEYS
+    | NULL_UNSUPPORTED
+    | OMIT_EMPTY
     | OMIT_MICROSECONDS
+    | OMIT_NULL
     | PASSTHROUGH_DATETIME
     | PASSTHROUGH_DATACLASS
     | PASSTHROUGH_SUBCLASS
     | SERIALIZE_DATACLASS
     | SERIALIZE_NUMPY
     | SERIALIZE_UUID
+    | SKIP_UNSUPPORTED
     | SORT_KEYS
     | STRICT_INTEGER
     | UTC_Z) as i32;
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+pub(crate) const MAX_LOADS_OPT: i32 = OMIT_NULL_ON_LOAD as i32;
+
+/// Convert an `option=` argument, which is an `int` or `None`, to `Opt`.
+///
+/// Returns `None` if the argument is of another type or has bits outside
+/// `MAX_OPT` set.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn opts_from_pyobject(ptr: *mut crate::ffi::PyObject) -> Option<Opt> {
+    opts_in_range(ptr, MAX_OPT)
+}
+
+/// Convert the `option=` argument of `loads()` as `opts_from_pyobject()`
+/// does, with `MAX_LOADS_OPT`.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn loads_opts_from_pyobject(ptr: *mut crate::ffi::PyObject) -> Option<Opt> {
+    opts_in_range(ptr, MAX_LOADS_OPT)
+}
+
+fn opts_in_range(ptr: *mut crate::ffi::PyObject, max: i32) -> Option<Opt> {
+    unsafe {
+        // Use direct CPython global for int type (zero indirection)
+        if core::ptr::eq((*ptr).ob_type, crate::typeref::int_type_ptr()) {
+            #[allow(clippy::cast_possible_truncation)]
+            let tmp = crate::ffi::PyLong_AsLong(ptr) as i32; // stmt_expr_attributes
+            // Check for errors from PyLong_AsLong (e.g., overflow)
+            // -1 can be a valid return value, but we check PyErr_Occurred to distinguish
+            // between a legitimate -1 and an error. Since valid option values are 0..=max,
+            // -1 is never valid, so we can check both conditions.
+            if tmp == -1 && !crate::ffi::PyErr_Occurred().is_null() {
+                crate::ffi::PyErr_Clear();
+                return None;
+            }
+            if !(0..=max).contains(&tmp) || tmp & !max != 0 {
+                return None;
+            }
+            #[allow(clippy::cast_sign_loss)]
+            Some(tmp as Opt)
+        } else if core::ptr::eq(ptr, crate::typeref::get_none()) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
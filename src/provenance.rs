@@ -0,0 +1,306 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Provenance of documents returned by `loads_tracked()`.
+//!
+//! The registry remembers the input of recent `loads_tracked()` calls along
+//! with a snapshot of every object in the result. `dumps()` of a tracked
+//! result compares the object graph against the snapshot and, if nothing was
+//! replaced, added, or removed, writes the original input instead of
+//! serializing it again. The snapshot holds a reference to each object, so
+//! a matching pointer is the same immutable `str`, `int`, `float`, `bool`, or
+//! `None` that was decoded.
+//!
+//! Only input that is already written as `dumps()` writes it is tracked, so
+//! the output does not depend on how the input was spelled. A result is
+//! tracked until it is freed by its user, which is noticed the next time the
+//! registry is used.
+
+use crate::ffi::PyObject;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::VecDeque;
+use std::sync::{Mutex, TryLockError};
+
+/// Number of results tracked at once. The oldest is released first.
+const DECODED_REGISTRY_CAPACITY: usize = 8;
+
+pub(crate) struct DecodedEntry {
+    source: Vec<u8>,
+    /// Every object of the result in pre-order, with dict keys preceding
+    /// their value. The first is the root.
+    snapshot: Vec<*mut PyObject>,
+}
+
+// Only created, compared, and released while attached to the interpreter.
+unsafe impl Send for DecodedEntry {}
+
+impl DecodedEntry {
+    fn new(root: *mut PyObject, source: &[u8]) -> Self {
+        let mut snapshot = Vec::new();
+        snapshot_object(root, &mut snapshot);
+        DecodedEntry {
+            source: source.to_vec(),
+            snapshot: snapshot,
+        }
+    }
+
+    /// Return whether `root` still holds the objects it was decoded with.
+    pub fn matches(&self, root: *mut PyObject) -> bool {
+        let mut idx = 0;
+        matches_object(root, &self.snapshot, &mut idx) && idx == self.snapshot.len()
+    }
+
+    /// Whether only the snapshot refers to the result.
+    fn is_released(&self) -> bool {
+        ffi!(Py_REFCNT(self.snapshot[0])) == 1
+    }
+}
+
+impl Drop for DecodedEntry {
+    fn drop(&mut self) {
+        for obj in self.snapshot.iter() {
+            ffi!(Py_DECREF(*obj));
+        }
+    }
+}
+
+fn snapshot_object(obj: *mut PyObject, snapshot: &mut Vec<*mut PyObject>) {
+    ffi!(Py_INCREF(obj));
+    snapshot.push(obj);
+    let ob_type = ob_type!(obj);
+    if is_type!(ob_type, crate::typeref::list_type_ptr()) {
+        for idx in 0..ffi!(Py_SIZE(obj)) {
+            snapshot_object(ffi!(PyList_GET_ITEM(obj, idx)), snapshot);
+        }
+    } else if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+        let mut pos = 0;
+        let mut key: *mut PyObject = core::ptr::null_mut();
+        let mut value: *mut PyObject = core::ptr::null_mut();
+        while pydict_next!(obj, &mut pos, &mut key, &mut value) == 1 {
+            ffi!(Py_INCREF(key));
+            snapshot.push(key);
+            snapshot_object(value, snapshot);
+        }
+    }
+}
+
+fn matches_object(obj: *mut PyObject, snapshot: &[*mut PyObject], idx: &mut usize) -> bool {
+    if snapshot.get(*idx) != Some(&obj) {
+        return false;
+    }
+    *idx += 1;
+    let ob_type = ob_type!(obj);
+    if is_type!(ob_type, crate::typeref::list_type_ptr()) {
+        for item in 0..ffi!(Py_SIZE(obj)) {
+            if !matches_object(ffi!(PyList_GET_ITEM(obj, item)), snapshot, idx) {
+                return false;
+            }
+        }
+    } else if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+        let mut pos = 0;
+        let mut key: *mut PyObject = core::ptr::null_mut();
+        let mut value: *mut PyObject = core::ptr::null_mut();
+        while pydict_next!(obj, &mut pos, &mut key, &mut value) == 1 {
+            if snapshot.get(*idx) != Some(&key) {
+                return false;
+            }
+            *idx += 1;
+            if !matches_object(value, snapshot, idx) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Whether `source` is written as `dumps()` writes the objects of
+/// `snapshot` by default: with no whitespace, only the escapes `dumps()`
+/// writes, no `-0`, integers that fit in 64 bits, and floats as `ryu`
+/// formats them.
+fn is_canonical(source: &[u8], snapshot: &[*mut PyObject]) -> bool {
+    // the snapshot index of the next value or key
+    let mut idx = 0;
+    let mut pos = 0;
+    while pos < source.len() {
+        match source[pos] {
+            b'[' | b'{' => {
+                idx += 1;
+                pos += 1;
+            }
+            b']' | b'}' | b',' | b':' => pos += 1,
+            b'"' => match canonical_string_end(source, pos + 1) {
+                Some(end) => {
+                    idx += 1;
+                    pos = end;
+                }
+                None => return false,
+            },
+            b't' | b'n' => {
+                idx += 1;
+                pos += 4;
+            }
+            b'f' => {
+                idx += 1;
+                pos += 5;
+            }
+            b'-' | b'0'..=b'9' => {
+                let len = source[pos..]
+                    .iter()
+                    .position(|byte| {
+                        !matches!(byte, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                    })
+                    .unwrap_or(source.len() - pos);
+                let token = &source[pos..pos + len];
+                let canonical = if token.iter().any(|byte| matches!(byte, b'.' | b'e' | b'E')) {
+                    canonical_float(token, snapshot[idx])
+                } else {
+                    token != b"-0" && token.len() <= 19
+                };
+                if !canonical {
+                    return false;
+                }
+                idx += 1;
+                pos += len;
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// The position after the closing quote of the string starting at `pos`,
+/// if it has only the escapes `dumps()` writes.
+fn canonical_string_end(source: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        match *source.get(pos)? {
+            b'"' => return Some(pos + 1),
+            b'\\' => match *source.get(pos + 1)? {
+                b'"' | b'\\' | b'b' | b't' | b'n' | b'f' | b'r' => pos += 2,
+                b'u' => match source.get(pos + 2..pos + 6)? {
+                    // control characters without a short escape, in lowercase
+                    [
+                        b'0',
+                        b'0',
+                        high @ (b'0' | b'1'),
+                        low @ (b'0'..=b'9' | b'a'..=b'f'),
+                    ] if !(*high == b'0' && matches!(low, b'8' | b'9' | b'a' | b'c' | b'd')) => {
+                        pos += 6;
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            _ => pos += 1,
+        }
+    }
+}
+
+fn canonical_float(token: &[u8], obj: *mut PyObject) -> bool {
+    if !is_type!(ob_type!(obj), crate::typeref::float_type_ptr()) {
+        return false;
+    }
+    let value = ffi!(PyFloat_AS_DOUBLE(obj));
+    value.is_finite() && ryu::Buffer::new().format_finite(value).as_bytes() == token
+}
+
+pub(crate) struct DecodedRegistry {
+    len: AtomicUsize,
+    entries: Mutex<VecDeque<DecodedEntry>>,
+}
+
+impl DecodedRegistry {
+    pub fn new() -> Self {
+        DecodedRegistry {
+            len: AtomicUsize::new(0),
+            entries: Mutex::new(VecDeque::with_capacity(DECODED_REGISTRY_CAPACITY)),
+        }
+    }
+
+    /// Track `root`, a `list` or `dict` decoded from `source` by reading
+    /// `values` JSON values, keys included.
+    ///
+    /// A document with duplicate keys decodes to fewer objects than it has
+    /// values and is not tracked, as writing it again would not round-trip.
+    /// Nor is one not written as `dumps()` writes it.
+    #[cold]
+    pub fn record(&self, root: *mut PyObject, source: &[u8], values: usize) {
+        let entry = DecodedEntry::new(root, source);
+        if entry.snapshot.len() != values || !is_canonical(source, &entry.snapshot) {
+            return;
+        }
+        let evicted = {
+            let mut entries = self.entries.lock().unwrap();
+            let mut evicted = self.release(&mut entries);
+            if entries.len() == DECODED_REGISTRY_CAPACITY {
+                evicted.extend(entries.pop_front());
+            }
+            entries.push_back(entry);
+            self.len.store(entries.len(), Ordering::Relaxed);
+            evicted
+        };
+        drop(evicted);
+    }
+
+    /// Call `f` with the input `root` was decoded from if it is tracked and
+    /// unmodified. The result stays tracked, so each `dumps()` of it writes
+    /// the same.
+    #[inline]
+    pub fn with_source<R>(&self, root: *mut PyObject, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        if self.len.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        self.with_source_slow(root, f)
+    }
+
+    /// Release every tracked result.
+    #[cold]
+    pub fn clear(&self) {
+        let entries = {
+            let mut entries = self.entries.lock().unwrap();
+            self.len.store(0, Ordering::Relaxed);
+            core::mem::take(&mut *entries)
+        };
+        drop(entries);
+    }
+
+    /// Start over in the child of a `fork()` if a thread the child does not
+    /// have held the lock. The entries it was changing are leaked.
+    #[cold]
+    pub fn after_fork_in_child(&mut self) {
+        let held = matches!(self.entries.try_lock(), Err(TryLockError::WouldBlock));
+        if held {
+            core::mem::forget(core::mem::replace(self, DecodedRegistry::new()));
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn with_source_slow<R>(&self, root: *mut PyObject, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let mut entries = self.entries.lock().unwrap();
+        let released = self.release(&mut entries);
+        let ret = entries
+            .iter()
+            .find(|entry| entry.snapshot[0] == root)
+            .filter(|entry| entry.matches(root))
+            .map(|entry| f(&entry.source));
+        drop(entries);
+        drop(released);
+        ret
+    }
+
+    /// Remove the entries of results that have been freed by their user, to
+    /// be dropped once the lock is released.
+    fn release(&self, entries: &mut VecDeque<DecodedEntry>) -> Vec<DecodedEntry> {
+        let mut released = Vec::new();
+        let mut idx = 0;
+        while idx < entries.len() {
+            if entries[idx].is_released() {
+                released.extend(entries.remove(idx));
+            } else {
+                idx += 1;
+            }
+        }
+        self.len.store(entries.len(), Ordering::Relaxed);
+        released
+    }
+}
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2018-2025)
+
+//! Ownership of the state hyperjson keeps between calls.
+//!
+//! Everything that outlives a call, i.e., type objects, interned strings,
+//! the key cache, the parse buffer, the numpy types, registered serializers,
+//! and `loads_tracked()` results, is a field of an `InterpreterState`. The
+//! registry here owns one per module object and is the only process-wide
+//! mutable static. A thread caches the state of the interpreter it last ran
+//! in, tagged with the registry generation, so that a state dropped or
+//! replaced is never returned from the cache.
+//!
+//! The escape cache and `OPT_DEFAULT_PATH` stack are thread-local too, but
+//! are installed and restored by the call using them and hold nothing
+//! between calls.
+//!
+//! `init()` runs from the module exec slot and `teardown()` from `m_free`,
+//! so an embedder that finalizes and initializes Python again gets a new
+//! state rather than one whose objects belonged to the previous interpreter.
+//!
+//! `init()` also registers handlers with `os.register_at_fork()`. The thread
+//! calling `os.fork()` holds the registry lock across the fork, so that the
+//! child does not inherit it locked by a thread it does not have. The child
+//! then drops the states of the interpreters that did not survive the fork
+//! and empties the thread-local cache.
+
+use core::cell::{Cell, RefCell};
+use core::ffi::c_void;
+use core::ptr::{null, null_mut};
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::ffi::{
+    Py_DECREF, Py_XDECREF, PyCFunction_NewEx, PyDict_New, PyDict_SetItemString, PyErr_Clear,
+    PyImport_ImportModule, PyInterpreterState_Get, PyInterpreterState_GetID, PyMethodDef,
+    PyMethodDefPointer, PyObject, PyObject_Call, PyObject_GetAttrString, PyTuple_New,
+    PyUnicode_InternFromString,
+};
+use crate::interpreter_state::InterpreterState;
+
+struct Entry {
+    /// The interpreter the module was initialized in.
+    interp_id: i64,
+    state: Box<InterpreterState>,
+}
+
+struct Registry {
+    /// States keyed by module pointer. The key is only compared, never
+    /// dereferenced.
+    states: HashMap<usize, Entry>,
+    /// The module most recently initialized in each interpreter, keyed by
+    /// interpreter ID. A module imported again after being removed from
+    /// `sys.modules` replaces the previous one here, while the previous
+    /// state stays in `states` until its module is freed.
+    current: HashMap<i64, usize>,
+}
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+/// Incremented whenever a state is dropped or replaced, invalidating the
+/// state cached by every thread.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static CACHED_INTERP_ID: Cell<i64> = const { Cell::new(-1) };
+    static CACHED_GENERATION: Cell<u64> = const { Cell::new(0) };
+    static CACHED_STATE: Cell<*const InterpreterState> = const { Cell::new(null()) };
+    /// The registry lock held by this thread from before it forks until
+    /// after, in the parent and in the child.
+    static FORK_GUARD: RefCell<Option<MutexGuard<'static, Registry>>> = const { RefCell::new(None) };
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    REGISTRY.get_or_init(|| {
+        Mutex::new(Registry {
+            states: HashMap::new(),
+            current: HashMap::new(),
+        })
+    })
+}
+
+#[inline(always)]
+fn current_interp_id() -> i64 {
+    unsafe { PyInterpreterState_GetID(PyInterpreterState_Get()) }
+}
+
+/// Create the state of `module`, called once per module object from its
+/// exec slot.
+#[cold]
+pub(crate) unsafe fn init(module: *mut PyObject) {
+    let interp_id = current_interp_id();
+    let state = Box::new(unsafe { InterpreterState::new() });
+    let stale = {
+        let mut registry = registry().lock().unwrap();
+        registry.current.insert(interp_id, module as usize);
+        registry
+            .states
+            .insert(module as usize, Entry { interp_id, state })
+    };
+    GENERATION.fetch_add(1, Ordering::Release);
+    if let Some(stale) = stale {
+        // A module of a finalized interpreter at the same address whose
+        // m_free did not run. Its objects are gone, so nothing it holds
+        // can be released.
+        cold_path!();
+        core::mem::forget(stale);
+    }
+    unsafe { register_at_fork() }
+}
+
+/// Drop the state of `module`, releasing what it holds. This is `m_free`.
+#[cold]
+pub(crate) unsafe extern "C" fn teardown(module: *mut c_void) {
+    let state = {
+        let mut registry = registry().lock().unwrap();
+        registry
+            .current
+            .retain(|_, current| *current != module as usize);
+        registry.states.remove(&(module as usize))
+    };
+    GENERATION.fetch_add(1, Ordering::Release);
+    drop(state);
+}
+
+/// Get the state of the current interpreter.
+#[inline(always)]
+pub(crate) unsafe fn current_state() -> *const InterpreterState {
+    let interp_id = current_interp_id();
+    let generation = GENERATION.load(Ordering::Acquire);
+    if CACHED_INTERP_ID.with(Cell::get) == interp_id
+        && CACHED_GENERATION.with(Cell::get) == generation
+    {
+        return CACHED_STATE.with(Cell::get);
+    }
+    current_state_slow(interp_id, generation)
+}
+
+#[cold]
+#[inline(never)]
+fn current_state_slow(interp_id: i64, generation: u64) -> *const InterpreterState {
+    let state = {
+        let registry = registry().lock().unwrap();
+        registry
+            .current
+            .get(&interp_id)
+            .and_then(|module| registry.states.get(module))
+            .map_or(null(), |entry| &raw const *entry.state)
+    };
+    // only called from the module or objects it created, so it has been
+    // initialized in this interpreter and not freed
+    debug_assert!(!state.is_null());
+    CACHED_INTERP_ID.with(|cell| cell.set(interp_id));
+    CACHED_GENERATION.with(|cell| cell.set(generation));
+    CACHED_STATE.with(|cell| cell.set(state));
+    state
+}
+
+/// Marks the current interpreter as serializing for as long as it is held,
+/// as serializing may call back into Python code that calls `reset()`.
+pub(crate) struct Serializing {
+    active: &'static AtomicUsize,
+}
+
+impl Serializing {
+    #[inline(always)]
+    pub fn enter() -> Self {
+        let active = unsafe { &(*current_state()).serializing };
+        active.fetch_add(1, Ordering::Relaxed);
+        Serializing { active }
+    }
+}
+
+impl Drop for Serializing {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Return the state of the current interpreter to what it was after import,
+/// for `hyperjson._reset_for_testing()`.
+#[cold]
+pub(crate) fn reset() -> Result<(), &'static str> {
+    let current = unsafe { current_state() };
+    // released before resetting, as what the state releases may run
+    // Python code that calls into hyperjson
+    let state: *mut InterpreterState = registry()
+        .lock()
+        .unwrap()
+        .states
+        .values_mut()
+        .map(|entry| &raw mut *entry.state)
+        .find(|state| core::ptr::eq(*state, current))
+        .unwrap();
+    unsafe {
+        if (*state).serializing.load(Ordering::Relaxed) != 0 {
+            return Err("_reset_for_testing() cannot be called while serializing");
+        }
+        (*state).reset();
+    }
+    Ok(())
+}
+
+/// Register `before_fork()`, `after_fork_in_parent()`, and
+/// `after_fork_in_child()` with `os.register_at_fork()` of the current
+/// interpreter. The handlers do nothing when called again for the same fork,
+/// so a module imported again registering them again is harmless.
+#[cold]
+unsafe fn register_at_fork() {
+    unsafe {
+        let os = PyImport_ImportModule(c"os".as_ptr());
+        if os.is_null() {
+            PyErr_Clear();
+            return;
+        }
+        // not available on Windows
+        let register = PyObject_GetAttrString(os, c"register_at_fork".as_ptr());
+        Py_DECREF(os);
+        if register.is_null() {
+            PyErr_Clear();
+            return;
+        }
+        let kwargs = PyDict_New();
+        for (name, handler) in [
+            (c"before", before_fork as ForkHandler),
+            (c"after_in_parent", after_fork_in_parent),
+            (c"after_in_child", after_fork_in_child),
+        ] {
+            let def = Box::new(PyMethodDef {
+                ml_name: name.as_ptr(),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: handler,
+                },
+                ml_flags: crate::ffi::METH_NOARGS,
+                ml_doc: null(),
+            });
+            let func = PyCFunction_NewEx(
+                Box::into_raw(def),
+                null_mut(),
+                PyUnicode_InternFromString(c"hyperjson".as_ptr()),
+            );
+            PyDict_SetItemString(kwargs, name.as_ptr(), func);
+            Py_DECREF(func);
+        }
+        let args = PyTuple_New(0);
+        let res = PyObject_Call(register, args, kwargs);
+        if res.is_null() {
+            // e.g., an interpreter that cannot fork
+            PyErr_Clear();
+        }
+        Py_XDECREF(res);
+        Py_DECREF(args);
+        Py_DECREF(kwargs);
+        Py_DECREF(register);
+    }
+}
+
+type ForkHandler = unsafe extern "C" fn(*mut PyObject, *mut PyObject) -> *mut PyObject;
+
+/// Lock the registry until the fork completes.
+#[cold]
+unsafe extern "C" fn before_fork(_self: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    FORK_GUARD.with(|guard| {
+        let mut guard = guard.borrow_mut();
+        if guard.is_none() {
+            *guard = Some(registry().lock().unwrap());
+        }
+    });
+    use_immortal!(crate::typeref::get_none())
+}
+
+#[cold]
+unsafe extern "C" fn after_fork_in_parent(
+    _self: *mut PyObject,
+    _args: *mut PyObject,
+) -> *mut PyObject {
+    drop(FORK_GUARD.with(RefCell::take));
+    use_immortal!(crate::typeref::get_none())
+}
+
+/// Drop the states of interpreters other than the one that forked, as only
+/// it exists in the child, and release the registry. The objects of the
+/// dropped states are gone, so nothing they hold can be released.
+#[cold]
+unsafe extern "C" fn after_fork_in_child(
+    _self: *mut PyObject,
+    _args: *mut PyObject,
+) -> *mut PyObject {
+    if let Some(mut registry) = FORK_GUARD.with(RefCell::take) {
+        let interp_id = current_interp_id();
+        registry.current.retain(|id, _| *id == interp_id);
+        let modules: Vec<usize> = registry
+            .states
+            .iter()
+            .filter(|(_, entry)| entry.interp_id != interp_id)
+            .map(|(module, _)| *module)
+            .collect();
+        for module in modules {
+            core::mem::forget(registry.states.remove(&module));
+        }
+        for entry in registry.states.values_mut() {
+            entry.state.after_fork_in_child();
+        }
+        GENERATION.fetch_add(1, Ordering::Release);
+    }
+    CACHED_INTERP_ID.with(|cell| cell.set(-1));
+    CACHED_STATE.with(|cell| cell.set(null()));
+    use_immortal!(crate::typeref::get_none())
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Append the standard, padded base64 encoding of `data` to `buf`.
+pub(crate) fn encode_to_string(data: &[u8], buf: &mut String) {
+    buf.reserve(data.len().div_ceil(3) * 4);
+    let mut chunks = data.chunks_exact(3);
+    for chunk in chunks.by_ref() {
+        let val = (u32::from(chunk[0]) << 16) | (u32::from(chunk[1]) << 8) | u32::from(chunk[2]);
+        buf.push(char::from(ALPHABET[(val >> 18) as usize & 63]));
+        buf.push(char::from(ALPHABET[(val >> 12) as usize & 63]));
+        buf.push(char::from(ALPHABET[(val >> 6) as usize & 63]));
+        buf.push(char::from(ALPHABET[val as usize & 63]));
+    }
+    match *chunks.remainder() {
+        [first] => {
+            let val = u32::from(first) << 16;
+            buf.push(char::from(ALPHABET[(val >> 18) as usize & 63]));
+            buf.push(char::from(ALPHABET[(val >> 12) as usize & 63]));
+            buf.push_str("==");
+        }
+        [first, second] => {
+            let val = (u32::from(first) << 16) | (u32::from(second) << 8);
+            buf.push(char::from(ALPHABET[(val >> 18) as usize & 63]));
+            buf.push(char::from(ALPHABET[(val >> 12) as usize & 63]));
+            buf.push(char::from(ALPHABET[(val >> 6) as usize & 63]));
+            buf.push('=');
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.dumps_batch()`, for serializing records as NDJSON.
+//!
+//! The offsets of each record are recorded as it is written so that a
+//! producer can split the output, e.g., by partition, without scanning it
+//! for newlines.
+
+use crate::ffi::PyObject;
+use crate::opt::{
+    APPEND_CRLF, DEFAULT_PATH, ENSURE_ASCII, EXTENDED_JSON, EXTENDED_JSON_CANONICAL, INDENT, Opt,
+};
+use crate::runtime::Serializing;
+use crate::serialize::escape_cache::{self, EscapeCache};
+use crate::serialize::join::FastSequence;
+use crate::serialize::key_order;
+use crate::serialize::number_format;
+use crate::serialize::path::{self, DefaultPaths};
+use crate::serialize::per_type::extended_json;
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::{BytesWriter, WriteExt, to_writer};
+use crate::util::usize_to_isize;
+use bytes::BufMut;
+use core::ptr::NonNull;
+
+/// Serialize each item of `ptr` followed by a newline and return a tuple
+/// of the `bytes` and a `list` of `len + 1` offsets, record `i` being
+/// `output[offsets[i]:offsets[i + 1]]`.
+pub(crate) fn serialize_batch(
+    ptr: *mut PyObject,
+    default: Option<NonNull<PyObject>>,
+    opts: Opt,
+    comparator: Option<NonNull<PyObject>>,
+    formatter: Option<NonNull<PyObject>>,
+) -> Result<NonNull<PyObject>, String> {
+    if opt_enabled!(opts, INDENT) {
+        return Err(String::from(
+            "dumps_batch() does not support indented output",
+        ));
+    }
+    let seq = FastSequence::new(ptr, c"dumps_batch() argument must be iterable")?;
+    let line_ending: &[u8] = if opt_enabled!(opts, APPEND_CRLF) {
+        b"\r\n"
+    } else {
+        b"\n"
+    };
+    let ensure_ascii = opt_enabled!(opts, ENSURE_ASCII);
+
+    let mut offsets: Vec<usize> = Vec::with_capacity(seq.len() + 1);
+    offsets.push(0);
+    let mut buf = BytesWriter::default();
+    let mut cache = EscapeCache::new();
+    let previous = if ensure_ascii {
+        escape_cache::suspend()
+    } else {
+        escape_cache::install(&mut cache)
+    };
+    let previous_comparator = comparator.map(|ptr| key_order::install(ptr.as_ptr()));
+    let previous_formatter = formatter.map(|ptr| number_format::install(ptr.as_ptr()));
+    let previous_extended_json = opt_enabled!(opts, EXTENDED_JSON)
+        .then(|| extended_json::install(opt_enabled!(opts, EXTENDED_JSON_CANONICAL)));
+    let serializing = Serializing::enter();
+    let mut res = Ok(());
+    let mut idx = 0;
+    // default may change the length of a list
+    while idx < seq.len() {
+        let record = seq.get(idx);
+        let obj = PyObjectSerializer::new(record, SerializerState::new(opts), default);
+        let record_res = if opt_enabled!(opts, DEFAULT_PATH) {
+            // paths are relative to the record
+            let mut paths = DefaultPaths::new(record);
+            let previous_paths = path::install(&mut paths);
+            let record_res = to_writer(&mut buf, &obj, opts);
+            path::restore(previous_paths);
+            record_res
+        } else {
+            to_writer(&mut buf, &obj, opts)
+        };
+        if let Err(err) = record_res {
+            res = Err(format!("dumps_batch() record {idx}: {err}"));
+            break;
+        }
+        let mut writer = &mut buf;
+        writer.reserve(line_ending.len());
+        writer.put_slice(line_ending);
+        offsets.push(buf.len());
+        idx += 1;
+    }
+    if let Some(previous_extended_json) = previous_extended_json {
+        extended_json::restore(previous_extended_json);
+    }
+    if let Some(previous_formatter) = previous_formatter {
+        number_format::restore(previous_formatter);
+    }
+    if let Some(previous_comparator) = previous_comparator {
+        key_order::restore(previous_comparator);
+    }
+    escape_cache::restore(previous);
+    drop(serializing);
+    if let Err(err) = res {
+        buf.abort();
+        return Err(err);
+    }
+
+    let list = ffi!(PyList_New(usize_to_isize(offsets.len())));
+    if list.is_null() {
+        cold_path!();
+        buf.abort();
+        ffi!(PyErr_Clear());
+        return Err(String::from("dumps_batch() failed to allocate offsets"));
+    }
+    for (idx, offset) in offsets.iter().enumerate() {
+        #[allow(clippy::cast_possible_wrap)]
+        let item = ffi!(PyLong_FromLongLong(*offset as i64));
+        ffi!(PyList_SET_ITEM(list, usize_to_isize(idx), item));
+    }
+    let bytes = buf.finish(b"");
+    let tuple = ffi!(PyTuple_New(2));
+    unsafe {
+        crate::ffi::PyTuple_SET_ITEM(tuple, 0, bytes.as_ptr());
+        crate::ffi::PyTuple_SET_ITEM(tuple, 1, list);
+    }
+    Ok(nonnull!(tuple))
+}
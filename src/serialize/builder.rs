@@ -0,0 +1,581 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.Builder`, for writing a document incrementally.
+//!
+//! Containers are opened and closed with method calls and values are
+//! serialized as they are passed in, directly into the `BytesWriter` that
+//! becomes the output, so callers generating JSON procedurally don't need to
+//! build an intermediate object.
+
+use crate::exception::{raise_dumps_exception_dynamic, raise_dumps_exception_fixed};
+use crate::ffi::{
+    METH_NOARGS, METH_O, Py_EQ, Py_TPFLAGS_DEFAULT, Py_TPFLAGS_HAVE_GC, PyMethodDef,
+    PyMethodDefPointer, PyObject, PyType_Ready, PyType_Type, PyTypeObject, PyVarObject, visitproc,
+};
+use crate::opt::{DEFAULT_PATH, ENSURE_ASCII, INDENT, Opt};
+use crate::runtime::Serializing;
+use crate::serialize::escape_cache;
+use crate::serialize::path::{self, DefaultPaths};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::{BytesWriter, WriteExt, to_writer};
+use crate::str::PyStr;
+use bytes::BufMut;
+use core::ffi::{c_int, c_void};
+use core::ptr::{NonNull, null_mut};
+use smallvec::SmallVec;
+
+#[cfg(Py_GIL_DISABLED)]
+use crate::ffi::atomiculong::AtomicCULong;
+#[cfg(Py_GIL_DISABLED)]
+use core::sync::atomic::{AtomicIsize, AtomicU32};
+
+#[cfg(Py_GIL_DISABLED)]
+macro_rules! pymutex_new {
+    () => {
+        unsafe { core::mem::zeroed() }
+    };
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Container {
+    Array,
+    Object,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Position {
+    /// Nothing has been written in the current container.
+    First,
+    /// At least one value has been written in the current container.
+    Rest,
+    /// A key was written and its value has not been.
+    AfterKey,
+    /// The top-level value is complete.
+    Done,
+}
+
+#[repr(C)]
+pub(crate) struct Builder {
+    ob_base: PyObject,
+    writer: Option<BytesWriter>,
+    stack: SmallVec<[Container; 16]>,
+    position: Position,
+    opts: Opt,
+    default: Option<NonNull<PyObject>>,
+    /// `value()` is serializing, and `default` may call back into the
+    /// builder, which raises.
+    in_value: bool,
+}
+
+impl Builder {
+    fn writer(&mut self) -> &mut BytesWriter {
+        self.writer.get_or_insert_with(BytesWriter::default)
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        let mut writer = self.writer();
+        writer.reserve(1);
+        writer.put_u8(byte);
+    }
+
+    /// Check a value may be written here and write the separator before it.
+    fn begin_value(&mut self) -> Result<(), &'static str> {
+        match (self.stack.last(), self.position) {
+            (_, Position::Done) => Err("Builder already holds a complete document"),
+            (Some(Container::Object), Position::First | Position::Rest) => {
+                Err("Builder expected key() inside an object")
+            }
+            (Some(Container::Array), Position::Rest) => {
+                self.write_byte(b',');
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn end_value(&mut self) {
+        self.position = if self.stack.is_empty() {
+            Position::Done
+        } else {
+            Position::Rest
+        };
+    }
+
+    fn start(&mut self, container: Container, open: u8) -> Result<(), &'static str> {
+        self.begin_value()?;
+        self.write_byte(open);
+        self.stack.push(container);
+        self.position = Position::First;
+        Ok(())
+    }
+
+    fn end(&mut self, container: Container, close: u8) -> Result<(), &'static str> {
+        if self.stack.last() != Some(&container) {
+            return Err(match container {
+                Container::Array => "Builder end_array() does not match an open array",
+                Container::Object => "Builder end_object() does not match an open object",
+            });
+        }
+        if self.position == Position::AfterKey {
+            return Err("Builder expected value() after key()");
+        }
+        self.write_byte(close);
+        self.stack.pop();
+        self.end_value();
+        Ok(())
+    }
+
+    fn key(&mut self, key: *mut PyObject) -> Result<(), &'static str> {
+        if self.stack.last() != Some(&Container::Object) {
+            return Err("Builder key() must be called inside an object");
+        }
+        if self.position == Position::AfterKey {
+            return Err("Builder expected value() after key()");
+        }
+        if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+            return Err("Dict key must be str");
+        }
+        let key_as_str = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+            Some(uni) => uni,
+            None => return Err(crate::util::INVALID_STR),
+        };
+        if self.position == Position::Rest {
+            self.write_byte(b',');
+        }
+        let opts = self.opts;
+        to_writer(self.writer(), key_as_str, opts).unwrap();
+        self.write_byte(b':');
+        self.position = Position::AfterKey;
+        Ok(())
+    }
+
+    fn value(&mut self, value: *mut PyObject) -> Result<(), String> {
+        let before = self.writer().len();
+        self.begin_value().map_err(String::from)?;
+        let opts = self.opts;
+        // the writer is taken so that nothing else refers to it while
+        // `default` runs
+        let mut writer = self.writer.take().unwrap_or_else(BytesWriter::default);
+        self.in_value = true;
+        let res = write_value(&mut writer, value, opts, self.default);
+        self.in_value = false;
+        match res {
+            Ok(()) => {
+                self.writer = Some(writer);
+                self.end_value();
+                Ok(())
+            }
+            Err(err) => {
+                writer.truncate(before);
+                self.writer = Some(writer);
+                Err(err)
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Result<NonNull<PyObject>, &'static str> {
+        if self.position != Position::Done {
+            return Err("Builder document is incomplete");
+        }
+        self.position = Position::First;
+        Ok(self.writer.take().unwrap().finish(b""))
+    }
+}
+
+fn write_value(
+    writer: &mut BytesWriter,
+    value: *mut PyObject,
+    opts: Opt,
+    default: Option<NonNull<PyObject>>,
+) -> Result<(), String> {
+    let _serializing = Serializing::enter();
+    let obj = PyObjectSerializer::new(value, SerializerState::new(opts), default);
+    let ensure_ascii = opt_enabled!(opts, ENSURE_ASCII);
+    let previous_cache = ensure_ascii.then(escape_cache::suspend);
+    let res = if opt_enabled!(opts, DEFAULT_PATH) {
+        // paths are relative to the value
+        let mut paths = DefaultPaths::new(value);
+        let previous = path::install(&mut paths);
+        let res = to_writer(&mut *writer, &obj, opts);
+        path::restore(previous);
+        res
+    } else {
+        to_writer(&mut *writer, &obj, opts)
+    };
+    if let Some(previous) = previous_cache {
+        escape_cache::restore(previous);
+    }
+    res.map_err(|err| err.to_string())
+}
+
+/// The `Builder` `slf`, unless `value()` is serializing with it, e.g., when
+/// called from `default`.
+#[inline(always)]
+unsafe fn builder<'a>(slf: *mut PyObject) -> Result<&'a mut Builder, &'static str> {
+    let builder = slf.cast::<Builder>();
+    unsafe {
+        if (*builder).in_value {
+            cold_path!();
+            return Err("Builder cannot be used while value() is serializing");
+        }
+        Ok(&mut *builder)
+    }
+}
+
+#[inline(always)]
+unsafe fn none() -> *mut PyObject {
+    use_immortal!(crate::typeref::get_none())
+}
+
+macro_rules! builder_method_noargs {
+    ($name:ident, $body:expr) => {
+        #[cold]
+        #[cfg_attr(feature = "optimize", optimize(size))]
+        unsafe extern "C" fn $name(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+            unsafe {
+                #[allow(clippy::redundant_closure_call)]
+                match builder(slf).and_then($body) {
+                    Ok(()) => none(),
+                    Err(err) => raise_dumps_exception_fixed(err),
+                }
+            }
+        }
+    };
+}
+
+builder_method_noargs!(builder_start_object, |b: &mut Builder| b
+    .start(Container::Object, b'{'));
+builder_method_noargs!(builder_end_object, |b: &mut Builder| b
+    .end(Container::Object, b'}'));
+builder_method_noargs!(builder_start_array, |b: &mut Builder| b
+    .start(Container::Array, b'['));
+builder_method_noargs!(builder_end_array, |b: &mut Builder| b
+    .end(Container::Array, b']'));
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_key(slf: *mut PyObject, key: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        match builder(slf).and_then(|b| b.key(key)) {
+            Ok(()) => none(),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+unsafe extern "C" fn builder_value(slf: *mut PyObject, value: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        match builder(slf)
+            .map_err(String::from)
+            .and_then(|b| b.value(value))
+        {
+            Ok(()) => none(),
+            Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+        }
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_finish(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        match builder(slf).and_then(Builder::finish) {
+            Ok(bytes) => bytes.as_ptr(),
+            Err(err) => raise_dumps_exception_fixed(err),
+        }
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_tp_new(
+    subtype: *mut PyTypeObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut optsptr: Option<NonNull<PyObject>> = None;
+
+        let num_args = ffi!(Py_SIZE(args));
+        if num_args > 2 {
+            return raise_dumps_exception_fixed("Builder() takes at most 2 positional arguments");
+        }
+        if num_args >= 1 {
+            default = NonNull::new(crate::ffi::PyTuple_GET_ITEM(args, 0));
+        }
+        if num_args == 2 {
+            optsptr = NonNull::new(crate::ffi::PyTuple_GET_ITEM(args, 1));
+        }
+        if !kwds.is_null() {
+            let mut pos = 0;
+            let mut key: *mut PyObject = null_mut();
+            let mut value: *mut PyObject = null_mut();
+            while crate::ffi::PyDict_Next(kwds, &mut pos, &mut key, &mut value) != 0 {
+                if crate::ffi::PyObject_RichCompareBool(key, crate::typeref::get_default(), Py_EQ)
+                    == 1
+                {
+                    if default.is_some() {
+                        return raise_dumps_exception_fixed(
+                            "Builder() got multiple values for argument: 'default'",
+                        );
+                    }
+                    default = NonNull::new(value);
+                } else if crate::ffi::PyObject_RichCompareBool(
+                    key,
+                    crate::typeref::get_option(),
+                    Py_EQ,
+                ) == 1
+                {
+                    if optsptr.is_some() {
+                        return raise_dumps_exception_fixed(
+                            "Builder() got multiple values for argument: 'option'",
+                        );
+                    }
+                    optsptr = NonNull::new(value);
+                } else {
+                    return raise_dumps_exception_fixed(
+                        "Builder() got an unexpected keyword argument",
+                    );
+                }
+            }
+        }
+        if let Some(ptr) = default {
+            if core::ptr::eq(ptr.as_ptr(), crate::typeref::get_none()) {
+                default = None;
+            }
+        }
+        let mut opts: Opt = 0;
+        if let Some(ptr) = optsptr {
+            match crate::opt::opts_from_pyobject(ptr.as_ptr()) {
+                Some(val) => opts = val,
+                None => return raise_dumps_exception_fixed("Invalid opts"),
+            }
+        }
+        if opt_enabled!(opts, INDENT) {
+            return raise_dumps_exception_fixed("Builder does not support indented output");
+        }
+        crate::stats::record(crate::stats::Entry::Builder, opts);
+
+        let obj = crate::ffi::PyType_GenericAlloc(subtype, 0);
+        if obj.is_null() {
+            return null_mut();
+        }
+        if let Some(ptr) = default {
+            ffi!(Py_INCREF(ptr.as_ptr()));
+        }
+        let builder = obj.cast::<Builder>();
+        core::ptr::write(&raw mut (*builder).writer, None);
+        core::ptr::write(&raw mut (*builder).stack, SmallVec::new());
+        (*builder).position = Position::First;
+        (*builder).opts = opts;
+        (*builder).default = default;
+        (*builder).in_value = false;
+        obj
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_dealloc(object: *mut PyObject) {
+    unsafe {
+        crate::ffi::PyObject_GC_UnTrack(object.cast::<c_void>());
+        let builder = object.cast::<Builder>();
+        if let Some(mut writer) = (*builder).writer.take() {
+            writer.abort();
+        }
+        core::ptr::drop_in_place(&raw mut (*builder).stack);
+        if let Some(ptr) = (*builder).default.take() {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+        }
+        crate::ffi::PyObject_GC_Del(object.cast::<c_void>());
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_traverse(
+    object: *mut PyObject,
+    visit: visitproc,
+    arg: *mut c_void,
+) -> c_int {
+    unsafe {
+        match (*object.cast::<Builder>()).default {
+            Some(ptr) => visit(ptr.as_ptr(), arg),
+            None => 0,
+        }
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn builder_clear(object: *mut PyObject) -> c_int {
+    unsafe {
+        if let Some(ptr) = (*object.cast::<Builder>()).default.take() {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+        }
+    }
+    0
+}
+
+macro_rules! method_def {
+    ($name:expr, $func:ident, $flags:expr, $doc:expr) => {
+        PyMethodDef {
+            ml_name: $name.as_ptr(),
+            ml_meth: PyMethodDefPointer { PyCFunction: $func },
+            ml_flags: $flags,
+            ml_doc: $doc.as_ptr(),
+        }
+    };
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) unsafe fn builder_type_new() -> *mut PyTypeObject {
+    unsafe {
+        let methods = Box::new([
+            method_def!(
+                c"start_object",
+                builder_start_object,
+                METH_NOARGS,
+                c"Open an object."
+            ),
+            method_def!(
+                c"end_object",
+                builder_end_object,
+                METH_NOARGS,
+                c"Close the innermost open object."
+            ),
+            method_def!(
+                c"start_array",
+                builder_start_array,
+                METH_NOARGS,
+                c"Open an array."
+            ),
+            method_def!(
+                c"end_array",
+                builder_end_array,
+                METH_NOARGS,
+                c"Close the innermost open array."
+            ),
+            method_def!(
+                c"key",
+                builder_key,
+                METH_O,
+                c"Write the key of the next object member."
+            ),
+            method_def!(
+                c"value",
+                builder_value,
+                METH_O,
+                c"Serialize a value as the next array element or object member value."
+            ),
+            method_def!(
+                c"finish",
+                builder_finish,
+                METH_NOARGS,
+                c"Return the document as bytes and reset the builder."
+            ),
+            PyMethodDef::zeroed(),
+        ]);
+
+        #[cfg(Py_GIL_DISABLED)]
+        let tp_flags: AtomicCULong = AtomicCULong::new(
+            Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE,
+        );
+        #[cfg(all(Py_3_10, not(Py_GIL_DISABLED)))]
+        let tp_flags: core::ffi::c_ulong =
+            Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE;
+        #[cfg(not(Py_3_10))]
+        let tp_flags: core::ffi::c_ulong = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC;
+        let ob = Box::new(PyTypeObject {
+            ob_base: PyVarObject {
+                ob_base: PyObject {
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_tid: 0,
+                    #[cfg(all(Py_GIL_DISABLED, Py_3_14))]
+                    ob_flags: 0,
+                    #[cfg(all(Py_GIL_DISABLED, not(Py_3_14)))]
+                    _padding: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_mutex: pymutex_new!(),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_gc_bits: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_local: AtomicU32::new(crate::ffi::compat::_Py_IMMORTAL_REFCNT_LOCAL),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_shared: AtomicIsize::new(0),
+                    #[cfg(all(Py_3_12, not(Py_GIL_DISABLED)))]
+                    ob_refcnt: crate::ffi::PyObjectObRefcnt { ob_refcnt: 0 },
+                    #[cfg(not(Py_3_12))]
+                    ob_refcnt: 0,
+                    #[cfg(PyPy)]
+                    ob_pypy_link: 0,
+                    ob_type: &raw mut PyType_Type,
+                },
+                #[cfg(not(GraalPy))]
+                ob_size: 0,
+                #[cfg(GraalPy)]
+                _ob_size_graalpy: 0,
+            },
+            tp_name: c"hyperjson.Builder".as_ptr(),
+            tp_basicsize: core::mem::size_of::<Builder>() as isize,
+            tp_itemsize: 0,
+            tp_dealloc: Some(builder_dealloc),
+            tp_init: None,
+            tp_new: Some(builder_tp_new),
+            tp_flags: tp_flags,
+            // ...
+            tp_bases: null_mut(),
+            tp_cache: null_mut(),
+            tp_del: None,
+            tp_finalize: None,
+            tp_free: None,
+            tp_is_gc: None,
+            tp_mro: null_mut(),
+            tp_subclasses: null_mut(),
+            tp_vectorcall: None,
+            tp_version_tag: 0,
+            tp_weaklist: null_mut(),
+            tp_vectorcall_offset: 0,
+            tp_getattr: None,
+            tp_setattr: None,
+            tp_as_async: null_mut(),
+            tp_repr: None,
+            tp_as_number: null_mut(),
+            tp_as_sequence: null_mut(),
+            tp_as_mapping: null_mut(),
+            tp_hash: None,
+            tp_call: None,
+            tp_str: None,
+            tp_getattro: None,
+            tp_setattro: None,
+            tp_as_buffer: null_mut(),
+            tp_doc:
+                c"Builder(default=None, option=None)\n--\n\nWrite a JSON document incrementally."
+                    .as_ptr(),
+            tp_traverse: Some(builder_traverse),
+            tp_clear: Some(builder_clear),
+            tp_richcompare: None,
+            tp_weaklistoffset: 0,
+            tp_iter: None,
+            tp_iternext: None,
+            tp_methods: Box::into_raw(methods).cast::<PyMethodDef>(),
+            tp_members: null_mut(),
+            tp_getset: null_mut(),
+            tp_base: null_mut(),
+            tp_dict: null_mut(),
+            tp_descr_get: None,
+            tp_descr_set: None,
+            tp_dictoffset: 0,
+            tp_alloc: None,
+            #[cfg(Py_3_12)]
+            tp_watched: 0,
+        });
+        let ob_ptr = Box::into_raw(ob);
+        PyType_Ready(ob_ptr);
+        ob_ptr
+    }
+}
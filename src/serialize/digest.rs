@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.compare_digest_json()`, for comparing documents containing
+//! secrets, and `hyperjson.hash()`, for hashing documents.
+//!
+//! Both documents are serialized with sorted keys and without whitespace so
+//! that equal documents have equal output, and the output is compared
+//! without returning at the first difference. The time taken by the
+//! comparison depends only on the length of the second document's output.
+//! Serializing is not constant-time. `hash()` hashes the same output as it
+//! is written rather than keeping it.
+
+use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE, PyObject};
+use crate::opt::SORT_KEYS;
+use crate::serialize::serializer::{serialize, serialize_sha256};
+use crate::util::{constant_time_eq, isize_to_usize};
+
+/// Return whether `a` and `b` serialize to the same canonical output.
+pub(crate) fn compare_digest_json(a: *mut PyObject, b: *mut PyObject) -> Result<bool, String> {
+    let left = serialize(a, None, SORT_KEYS, None, 0, None, None)
+        .map_err(|err| format!("compare_digest_json() argument 1: {err}"))?;
+    let right = match serialize(b, None, SORT_KEYS, None, 0, None, None) {
+        Ok(right) => right,
+        Err(err) => {
+            ffi!(Py_DECREF(left.as_ptr()));
+            return Err(format!("compare_digest_json() argument 2: {err}"));
+        }
+    };
+    let equal = constant_time_eq(
+        bytes_as_slice(left.as_ptr()),
+        bytes_as_slice(right.as_ptr()),
+    );
+    ffi!(Py_DECREF(left.as_ptr()));
+    ffi!(Py_DECREF(right.as_ptr()));
+    Ok(equal)
+}
+
+fn bytes_as_slice<'a>(ptr: *mut PyObject) -> &'a [u8] {
+    unsafe {
+        core::slice::from_raw_parts(
+            PyBytes_AS_STRING(ptr).cast::<u8>(),
+            isize_to_usize(PyBytes_GET_SIZE(ptr)),
+        )
+    }
+}
+
+/// Return the SHA-256 of the canonical output of `obj`.
+pub(crate) fn hash_json(obj: *mut PyObject) -> Result<[u8; 32], String> {
+    serialize_sha256(obj, SORT_KEYS)
+}
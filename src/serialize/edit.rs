@@ -0,0 +1,746 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.edit()`, for changing values in a document while keeping the
+//! rest of its bytes as they are.
+//!
+//! The document is validated once when the editor is created. `set()` and
+//! `delete()` then scan the bytes for the value their JSON Pointer (RFC
+//! 6901) refers to and splice in the change, so whitespace, key order, and
+//! duplicate keys elsewhere are kept exactly. A value replaced by one of the
+//! same length is overwritten in place.
+//!
+//! With duplicate keys, a pointer refers to the last member with the key, as
+//! `loads()` reads it, and `delete()` removes every member with the key.
+//!
+//! `hyperjson.pointer()` reads the value a pointer refers to the same way
+//! and deserializes only it.
+//!
+//! `hyperjson.splice()` makes one such change to the output of `dumps()`
+//! without validating it first, serializing the new value with the options
+//! the document was written with.
+
+use crate::deserialize::{
+    DeserializeError, DeserializeOptions, deserialize, deserialize_slice, read_bytes_or_str,
+    read_input_to_buf,
+};
+use crate::exception::{raise_dumps_exception_dynamic, raise_dumps_exception_fixed};
+use crate::ffi::{
+    METH_NOARGS, METH_O, METH_VARARGS, Py_TPFLAGS_DEFAULT, PyMethodDef, PyMethodDefPointer,
+    PyObject, PyType_Ready, PyType_Type, PyTypeObject, PyVarObject,
+};
+use crate::opt::{ENSURE_ASCII, Opt};
+use crate::runtime::Serializing;
+use crate::serialize::serializer::{PyObjectSerializer, serialize_nested};
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::{BytesWriter, to_writer};
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+use core::ptr::{NonNull, null_mut};
+use serde::Serialize;
+use std::borrow::Cow;
+
+#[cfg(Py_GIL_DISABLED)]
+use crate::ffi::atomiculong::AtomicCULong;
+#[cfg(Py_GIL_DISABLED)]
+use core::sync::atomic::{AtomicIsize, AtomicU32};
+
+#[cfg(Py_GIL_DISABLED)]
+macro_rules! pymutex_new {
+    () => {
+        unsafe { core::mem::zeroed() }
+    };
+}
+
+#[repr(C)]
+pub(crate) struct Editor {
+    ob_base: PyObject,
+    doc: Document,
+}
+
+/// The bytes of a document being changed. The scanning functions return
+/// `None` rather than read past the end of a document that is not valid.
+struct Document {
+    data: Vec<u8>,
+}
+
+fn skip_whitespace(data: &[u8], mut pos: usize) -> usize {
+    while let Some(b' ' | b'\t' | b'\n' | b'\r') = data.get(pos) {
+        pos += 1;
+    }
+    pos
+}
+
+/// The end of the string at `pos`, after its closing quote.
+fn skip_string(data: &[u8], mut pos: usize) -> Option<usize> {
+    pos += 1;
+    loop {
+        match data.get(pos)? {
+            b'"' => return Some(pos + 1),
+            b'\\' => pos += 2,
+            _ => pos += 1,
+        }
+    }
+}
+
+/// The end of the value at `pos`.
+fn skip_value(data: &[u8], mut pos: usize) -> Option<usize> {
+    match data.get(pos)? {
+        b'"' => skip_string(data, pos),
+        b'{' | b'[' => {
+            let mut depth = 0_usize;
+            loop {
+                match data.get(pos)? {
+                    b'"' => {
+                        pos = skip_string(data, pos)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(pos + 1);
+                        }
+                    }
+                    _ => (),
+                }
+                pos += 1;
+            }
+        }
+        _ => {
+            while !matches!(
+                data.get(pos),
+                None | Some(b' ' | b'\t' | b'\n' | b'\r' | b',' | b']' | b'}')
+            ) {
+                pos += 1;
+            }
+            Some(pos)
+        }
+    }
+}
+
+/// A member of an object or an element of an array.
+struct Member {
+    /// The start and end of the key, including its quotes.
+    key: Option<(usize, usize)>,
+    /// The start of the key or, in an array, the value.
+    start: usize,
+    value: usize,
+    end: usize,
+}
+
+struct Container {
+    object: bool,
+    open: usize,
+    close: usize,
+    members: Vec<Member>,
+}
+
+impl Container {
+    /// Read the container at `open`, or `None` if the value there is not one.
+    fn read(data: &[u8], open: usize) -> Option<Self> {
+        let object = match data.get(open)? {
+            b'{' => true,
+            b'[' => false,
+            _ => return None,
+        };
+        let mut members = Vec::new();
+        let mut pos = skip_whitespace(data, open + 1);
+        if !matches!(data.get(pos)?, b'}' | b']') {
+            loop {
+                let start = pos;
+                let mut key = None;
+                if object {
+                    if data.get(pos)? != &b'"' {
+                        return None;
+                    }
+                    let key_end = skip_string(data, pos)?;
+                    key = Some((pos, key_end));
+                    // past the `:`
+                    pos = skip_whitespace(data, skip_whitespace(data, key_end) + 1);
+                }
+                let end = skip_value(data, pos)?;
+                members.push(Member {
+                    key: key,
+                    start: start,
+                    value: pos,
+                    end: end,
+                });
+                pos = skip_whitespace(data, end);
+                if data.get(pos)? != &b',' {
+                    break;
+                }
+                pos = skip_whitespace(data, pos + 1);
+            }
+        }
+        if data.get(pos)? != if object { &b'}' } else { &b']' } {
+            return None;
+        }
+        Some(Container {
+            object: object,
+            open: open,
+            close: pos,
+            members: members,
+        })
+    }
+
+    /// The index of the member `token` refers to.
+    fn find(&self, data: &[u8], token: &str) -> Option<usize> {
+        if self.object {
+            self.members
+                .iter()
+                .rposition(|member| key_is(data, member.key.unwrap(), token))
+        } else {
+            array_index(token).filter(|idx| *idx < self.members.len())
+        }
+    }
+}
+
+/// Whether the key at `start..end`, including its quotes, is `token`.
+fn key_is(data: &[u8], (start, end): (usize, usize), token: &str) -> bool {
+    let raw = &data[start + 1..end - 1];
+    if raw.contains(&b'\\') {
+        unescape(str_from_slice!(raw.as_ptr(), raw.len())) == token
+    } else {
+        raw == token.as_bytes()
+    }
+}
+
+/// Read the escapes of a string, which was validated when the editor was
+/// created.
+fn unescape(raw: &str) -> String {
+    fn hex(chars: &mut core::str::Chars) -> u32 {
+        (0..4).fold(0, |acc, _| {
+            acc * 16 + chars.next().and_then(|ch| ch.to_digit(16)).unwrap_or(0)
+        })
+    }
+    let mut val = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            val.push(ch);
+            continue;
+        }
+        val.push(match chars.next() {
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let mut codepoint = hex(&mut chars);
+                if (0xd800..0xdc00).contains(&codepoint) {
+                    // `\u` of the low surrogate
+                    chars.nth(1);
+                    codepoint = 0x10000 + ((codepoint - 0xd800) << 10) + (hex(&mut chars) - 0xdc00);
+                }
+                char::from_u32(codepoint).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            Some(ch) => ch,
+            None => break,
+        });
+    }
+    val
+}
+
+/// Read an array index, which has no leading zeros.
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Split a JSON Pointer into its unescaped reference tokens. `name` begins
+/// the error messages.
+fn parse_pointer(ptr: *mut PyObject, name: &str) -> Result<(&'static str, Vec<String>), String> {
+    if !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return Err(format!("{name} pointer must be a str"));
+    }
+    let pointer =
+        unsafe { PyStr::from_ptr_unchecked(ptr).to_str() }.ok_or(crate::util::INVALID_STR)?;
+    if pointer.is_empty() {
+        return Ok((pointer, Vec::new()));
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("{name} pointer must be empty or start with '/'"));
+    };
+    let tokens = rest
+        .split('/')
+        .map(|token| {
+            if token
+                .split('~')
+                .skip(1)
+                .any(|after| !after.starts_with(['0', '1']))
+            {
+                return Err(format!("{name} pointer has '~' not followed by '0' or '1'"));
+            }
+            Ok(token.replace("~1", "/").replace("~0", "~"))
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    Ok((pointer, tokens))
+}
+
+/// Serialize `value` as `dumps()` does with `opts`, which has no indent.
+fn serialize<T: Serialize + ?Sized>(value: &T, opts: Opt) -> Result<Vec<u8>, String> {
+    let mut writer = BytesWriter::default();
+    let res = to_writer(&mut writer, value, opts)
+        .map(|()| writer.as_slice().to_vec())
+        .map_err(|err| err.to_string());
+    writer.abort();
+    res
+}
+
+/// The start and end of the document in `data`.
+fn root(data: &[u8]) -> Option<(usize, usize)> {
+    let start = skip_whitespace(data, 0);
+    Some((start, skip_value(data, start)?))
+}
+
+/// The start and end of the value `tokens` refers to in `data`.
+fn locate(data: &[u8], tokens: &[String]) -> Option<(usize, usize)> {
+    let mut span = root(data)?;
+    for token in tokens {
+        let container = Container::read(data, span.0)?;
+        let member = &container.members[container.find(data, token)?];
+        span = (member.value, member.end);
+    }
+    Some(span)
+}
+
+impl Document {
+    fn root(&self) -> Option<(usize, usize)> {
+        root(&self.data)
+    }
+
+    fn locate(&self, tokens: &[String]) -> Option<(usize, usize)> {
+        locate(&self.data, tokens)
+    }
+
+    fn replace(&mut self, start: usize, end: usize, value: &[u8]) {
+        if end - start == value.len() {
+            self.data[start..end].copy_from_slice(value);
+        } else {
+            self.data.splice(start..end, value.iter().copied());
+        }
+    }
+
+    /// Add a member after the last of `container`, indented and separated as
+    /// the last is.
+    fn insert(&mut self, container: &Container, key: Option<&[u8]>, value: &[u8]) {
+        let data = &self.data;
+        let mut entry = Vec::with_capacity(value.len() + 16);
+        let mut separator: &[u8] = b":";
+        let at = match container.members.split_last() {
+            None => container.open + 1,
+            Some((last, rest)) => {
+                let indent = match rest.last() {
+                    Some(previous) => {
+                        previous.end
+                            + data[previous.end..last.start]
+                                .iter()
+                                .position(|ch| *ch == b',')
+                                .unwrap()
+                            + 1
+                    }
+                    None => container.open + 1,
+                };
+                entry.push(b',');
+                entry.extend_from_slice(&data[indent..last.start]);
+                if let Some((_, key_end)) = last.key {
+                    separator = &data[key_end..last.value];
+                }
+                last.end
+            }
+        };
+        if let Some(key) = key {
+            entry.extend_from_slice(key);
+            entry.extend_from_slice(separator);
+        }
+        entry.extend_from_slice(value);
+        self.data.splice(at..at, entry);
+    }
+
+    /// Replace the value `tokens` refers to, or add it to its object or
+    /// array, with what `encode` returns given its depth. A key added is
+    /// escaped as `ENSURE_ASCII` in `opts` has it. Returns `Ok(false)` if
+    /// neither is found.
+    fn set(
+        &mut self,
+        tokens: &[String],
+        opts: Opt,
+        encode: impl FnOnce(usize) -> Result<Vec<u8>, String>,
+    ) -> Result<bool, String> {
+        let Some((last, parents)) = tokens.split_last() else {
+            let Some((start, end)) = self.root() else {
+                return Ok(false);
+            };
+            let encoded = encode(0)?;
+            self.replace(start, end, &encoded);
+            return Ok(true);
+        };
+        let Some(container) = self
+            .locate(parents)
+            .and_then(|(open, _)| Container::read(&self.data, open))
+        else {
+            return Ok(false);
+        };
+        if let Some(idx) = container.find(&self.data, last) {
+            let encoded = encode(tokens.len())?;
+            let member = &container.members[idx];
+            self.replace(member.value, member.end, &encoded);
+        } else if container.object {
+            let key = serialize(last.as_str(), opts & ENSURE_ASCII)?;
+            let encoded = encode(tokens.len())?;
+            self.insert(&container, Some(&key), &encoded);
+        } else if last == "-" || array_index(last) == Some(container.members.len()) {
+            let encoded = encode(tokens.len())?;
+            self.insert(&container, None, &encoded);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Remove the value `tokens` refers to, which is not the document, or
+    /// each member with its key. Returns `false` if there is none.
+    fn delete(&mut self, tokens: &[String]) -> bool {
+        let Some((last, parents)) = tokens.split_last() else {
+            return false;
+        };
+        let Some((open, _)) = self.locate(parents) else {
+            return false;
+        };
+        let mut deleted = false;
+        // each member with the key, from the last
+        while let Some(container) = Container::read(&self.data, open) {
+            let Some(idx) = container.find(&self.data, last) else {
+                break;
+            };
+            let members = &container.members;
+            let range = if members.len() == 1 {
+                container.open + 1..container.close
+            } else if idx + 1 < members.len() {
+                members[idx].start..members[idx + 1].start
+            } else {
+                members[idx - 1].end..members[idx].end
+            };
+            self.data.drain(range);
+            deleted = true;
+            if !container.object {
+                break;
+            }
+        }
+        deleted
+    }
+}
+
+impl Editor {
+    fn set(&mut self, pointer: *mut PyObject, value: *mut PyObject) -> Result<(), String> {
+        let (pointer, tokens) = parse_pointer(pointer, "Editor")?;
+        let encode = |_| {
+            let _serializing = Serializing::enter();
+            serialize(
+                &PyObjectSerializer::new(value, SerializerState::new(0), None),
+                0,
+            )
+        };
+        if self.doc.set(&tokens, 0, encode)? {
+            Ok(())
+        } else {
+            Err(format!("Editor set() path not found: {pointer}"))
+        }
+    }
+
+    fn delete(&mut self, pointer: *mut PyObject) -> Result<(), String> {
+        let (pointer, tokens) = parse_pointer(pointer, "Editor")?;
+        if tokens.is_empty() {
+            Err(String::from("Editor delete() cannot delete the document"))
+        } else if self.doc.delete(&tokens) {
+            Ok(())
+        } else {
+            Err(format!("Editor delete() path not found: {pointer}"))
+        }
+    }
+}
+
+/// `splice(data, pointer, value, /, option=None, default=None)`: return
+/// `data`, the output of `dumps()` with `opts`, with the value at `pointer`
+/// replaced by or added as `value` serialized with `opts` and `default`.
+///
+/// `data` is not validated; only the arrays and objects on the way to the
+/// value are read.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn splice(
+    data: *mut PyObject,
+    pointer: *mut PyObject,
+    value: *mut PyObject,
+    opts: Opt,
+    default: Option<NonNull<PyObject>>,
+) -> Result<NonNull<PyObject>, String> {
+    let buffer = read_input_to_buf(data).map_err(|err| err.message.into_owned())?;
+    let (pointer, tokens) = parse_pointer(pointer, "splice()")?;
+    let mut doc = Document {
+        data: buffer.to_vec(),
+    };
+    let encode = |depth| serialize_nested(value, default, opts, depth);
+    if !doc.set(&tokens, opts, encode)? {
+        return Err(format!("splice() path not found: {pointer}"));
+    }
+    Ok(nonnull!(ffi!(PyBytes_FromStringAndSize(
+        doc.data.as_ptr().cast::<core::ffi::c_char>(),
+        usize_to_isize(doc.data.len())
+    ))))
+}
+
+/// `pointer(data, pointer, /)`: deserialize only the value at `pointer` in
+/// `data`. The rest of the document is scanned for the value but not
+/// validated.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn pointer(data: *mut PyObject, pointer: *mut PyObject) -> *mut PyObject {
+    let (pointer, tokens) = match parse_pointer(pointer, "pointer()") {
+        Ok(parsed) => parsed,
+        Err(err) => return raise_dumps_exception_dynamic(err.as_str()),
+    };
+    let buffer = match read_input_to_buf(data) {
+        Ok(buffer) => buffer,
+        Err(err) => return crate::exception::raise_loads_exception(err),
+    };
+    let value = match locate(buffer, &tokens) {
+        Some((start, end)) if start < end => &buffer[start..end],
+        _ => {
+            return crate::exception::raise_loads_exception(DeserializeError::invalid(Cow::Owned(
+                format!("pointer() path not found: {pointer}"),
+            )));
+        }
+    };
+    match deserialize_slice(value) {
+        Ok(obj) => obj.as_ptr(),
+        Err(err) => crate::exception::raise_loads_exception(err),
+    }
+}
+
+/// `edit(data, /)`: create an `Editor` of a copy of `data`, which must be a
+/// valid document.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn edit(data: *mut PyObject) -> *mut PyObject {
+    crate::stats::record(crate::stats::Entry::Edit, 0);
+    match deserialize(data, &DeserializeOptions::default()) {
+        Ok(obj) => ffi!(Py_DECREF(obj.as_ptr())),
+        Err(err) => return crate::exception::raise_loads_exception(err),
+    }
+    // a list or tuple of chunks is not supported
+    let Some(buffer) = read_bytes_or_str(data) else {
+        return raise_dumps_exception_fixed(
+            "edit() data must be bytes, bytearray, memoryview, or str",
+        );
+    };
+    let obj = ffi!(PyType_GenericAlloc(crate::typeref::get_editor_type(), 0));
+    if obj.is_null() {
+        return null_mut();
+    }
+    unsafe {
+        core::ptr::write(
+            &raw mut (*obj.cast::<Editor>()).doc,
+            Document {
+                data: buffer.to_vec(),
+            },
+        );
+    }
+    obj
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn editor_set(slf: *mut PyObject, args: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        if ffi!(Py_SIZE(args)) != 2 {
+            return raise_dumps_exception_fixed("Editor set() takes exactly 2 arguments");
+        }
+        let pointer = crate::ffi::PyTuple_GET_ITEM(args, 0);
+        let value = crate::ffi::PyTuple_GET_ITEM(args, 1);
+        match (*slf.cast::<Editor>()).set(pointer, value) {
+            Ok(()) => {
+                ffi!(Py_INCREF(slf));
+                slf
+            }
+            Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+        }
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn editor_delete(slf: *mut PyObject, pointer: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        match (*slf.cast::<Editor>()).delete(pointer) {
+            Ok(()) => {
+                ffi!(Py_INCREF(slf));
+                slf
+            }
+            Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+        }
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn editor_bytes(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let data = &(*slf.cast::<Editor>()).doc.data;
+        ffi!(PyBytes_FromStringAndSize(
+            data.as_ptr().cast::<core::ffi::c_char>(),
+            usize_to_isize(data.len())
+        ))
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn editor_dealloc(object: *mut PyObject) {
+    unsafe {
+        core::ptr::drop_in_place(&raw mut (*object.cast::<Editor>()).doc);
+        crate::ffi::PyObject_Free(object.cast::<core::ffi::c_void>());
+    }
+}
+
+macro_rules! method_def {
+    ($name:expr, $func:ident, $flags:expr, $doc:expr) => {
+        PyMethodDef {
+            ml_name: $name.as_ptr(),
+            ml_meth: PyMethodDefPointer { PyCFunction: $func },
+            ml_flags: $flags,
+            ml_doc: $doc.as_ptr(),
+        }
+    };
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) unsafe fn editor_type_new() -> *mut PyTypeObject {
+    unsafe {
+        let methods = Box::new([
+            method_def!(
+                c"set",
+                editor_set,
+                METH_VARARGS,
+                c"set(pointer, value, /)\n--\n\nReplace the value at a JSON Pointer, or add it to its object or array, and return the editor."
+            ),
+            method_def!(
+                c"delete",
+                editor_delete,
+                METH_O,
+                c"delete(pointer, /)\n--\n\nRemove the value at a JSON Pointer and return the editor."
+            ),
+            method_def!(
+                c"bytes",
+                editor_bytes,
+                METH_NOARGS,
+                c"Return the edited document."
+            ),
+            PyMethodDef::zeroed(),
+        ]);
+
+        #[cfg(Py_GIL_DISABLED)]
+        let tp_flags: AtomicCULong =
+            AtomicCULong::new(Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE);
+        #[cfg(all(Py_3_10, not(Py_GIL_DISABLED)))]
+        let tp_flags: core::ffi::c_ulong =
+            Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE;
+        #[cfg(not(Py_3_10))]
+        let tp_flags: core::ffi::c_ulong = Py_TPFLAGS_DEFAULT;
+        let ob = Box::new(PyTypeObject {
+            ob_base: PyVarObject {
+                ob_base: PyObject {
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_tid: 0,
+                    #[cfg(all(Py_GIL_DISABLED, Py_3_14))]
+                    ob_flags: 0,
+                    #[cfg(all(Py_GIL_DISABLED, not(Py_3_14)))]
+                    _padding: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_mutex: pymutex_new!(),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_gc_bits: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_local: AtomicU32::new(crate::ffi::compat::_Py_IMMORTAL_REFCNT_LOCAL),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_shared: AtomicIsize::new(0),
+                    #[cfg(all(Py_3_12, not(Py_GIL_DISABLED)))]
+                    ob_refcnt: crate::ffi::PyObjectObRefcnt { ob_refcnt: 0 },
+                    #[cfg(not(Py_3_12))]
+                    ob_refcnt: 0,
+                    #[cfg(PyPy)]
+                    ob_pypy_link: 0,
+                    ob_type: &raw mut PyType_Type,
+                },
+                #[cfg(not(GraalPy))]
+                ob_size: 0,
+                #[cfg(GraalPy)]
+                _ob_size_graalpy: 0,
+            },
+            tp_name: c"hyperjson.Editor".as_ptr(),
+            tp_basicsize: core::mem::size_of::<Editor>() as isize,
+            tp_itemsize: 0,
+            tp_dealloc: Some(editor_dealloc),
+            tp_init: None,
+            // created by edit()
+            tp_new: None,
+            tp_flags: tp_flags,
+            // ...
+            tp_bases: null_mut(),
+            tp_cache: null_mut(),
+            tp_del: None,
+            tp_finalize: None,
+            tp_free: None,
+            tp_is_gc: None,
+            tp_mro: null_mut(),
+            tp_subclasses: null_mut(),
+            tp_vectorcall: None,
+            tp_version_tag: 0,
+            tp_weaklist: null_mut(),
+            tp_vectorcall_offset: 0,
+            tp_getattr: None,
+            tp_setattr: None,
+            tp_as_async: null_mut(),
+            tp_repr: None,
+            tp_as_number: null_mut(),
+            tp_as_sequence: null_mut(),
+            tp_as_mapping: null_mut(),
+            tp_hash: None,
+            tp_call: None,
+            tp_str: None,
+            tp_getattro: None,
+            tp_setattro: None,
+            tp_as_buffer: null_mut(),
+            tp_doc: c"Edit a JSON document in place, keeping the bytes of what is not changed."
+                .as_ptr(),
+            tp_traverse: None,
+            tp_clear: None,
+            tp_richcompare: None,
+            tp_weaklistoffset: 0,
+            tp_iter: None,
+            tp_iternext: None,
+            tp_methods: Box::into_raw(methods).cast::<PyMethodDef>(),
+            tp_members: null_mut(),
+            tp_getset: null_mut(),
+            tp_base: null_mut(),
+            tp_dict: null_mut(),
+            tp_descr_get: None,
+            tp_descr_set: None,
+            tp_dictoffset: 0,
+            tp_alloc: None,
+            #[cfg(Py_3_12)]
+            tp_watched: 0,
+        });
+        let ob_ptr = Box::into_raw(ob);
+        PyType_Ready(ob_ptr);
+        ob_ptr
+    }
+}
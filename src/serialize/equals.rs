@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.equals()`, for comparing documents by what they contain.
+//!
+//! Both documents are read as `loads()` reads them, and an object is first
+//! serialized as `dumps()` does, so a `tuple` is equal to the `list` and a
+//! `datetime` to the string it is written as. Objects are compared without
+//! regard to the order of their keys and numbers by value, so `1`, `1.0`,
+//! and `1e0` are equal. `true` and `false` are not equal to `1` and `0`.
+
+use crate::deserialize::{DeserializeOptions, deserialize};
+use crate::exception::raise_dumps_exception_dynamic;
+use crate::ffi::PyObject;
+use crate::serialize::serializer::serialize;
+
+use core::ptr::null_mut;
+
+/// A new reference to the document `ptr` read, or `ptr` serialized and read
+/// again if it is not a document.
+pub(crate) fn read(ptr: *mut PyObject) -> *mut PyObject {
+    let ob_type = ob_type!(ptr);
+    let is_document = is_type!(ob_type, crate::typeref::bytes_type_ptr())
+        || is_type!(ob_type, crate::typeref::bytearray_type_ptr())
+        || is_type!(ob_type, crate::typeref::memoryview_type_ptr());
+    if is_document {
+        return match deserialize(ptr, &DeserializeOptions::default()) {
+            Ok(obj) => obj.as_ptr(),
+            Err(err) => crate::exception::raise_loads_exception(err),
+        };
+    }
+    let bytes = match serialize(ptr, None, 0, None, 0, None, None) {
+        Ok(bytes) => bytes.as_ptr(),
+        Err(err) => return raise_dumps_exception_dynamic(err.as_str()),
+    };
+    let ret = match deserialize(bytes, &DeserializeOptions::default()) {
+        Ok(obj) => obj.as_ptr(),
+        Err(err) => crate::exception::raise_loads_exception(err),
+    };
+    ffi!(Py_DECREF(bytes));
+    ret
+}
+
+fn is_number(ptr: *mut PyObject) -> bool {
+    let ob_type = ob_type!(ptr);
+    is_type!(ob_type, crate::typeref::int_type_ptr())
+        || is_type!(ob_type, crate::typeref::float_type_ptr())
+}
+
+/// Whether the values `a` and `b`, as `loads()` returns them, are equal.
+pub(crate) fn equal(a: *mut PyObject, b: *mut PyObject) -> bool {
+    if core::ptr::eq(a, b) {
+        return true;
+    }
+    let ob_type = ob_type!(a);
+    if is_number(a) && is_number(b) {
+        return ffi!(PyObject_RichCompareBool(a, b, crate::ffi::Py_EQ)) == 1;
+    }
+    if !core::ptr::eq(ob_type, ob_type!(b)) {
+        return false;
+    }
+    if is_type!(ob_type, crate::typeref::str_type_ptr()) {
+        ffi!(PyObject_RichCompareBool(a, b, crate::ffi::Py_EQ)) == 1
+    } else if is_type!(ob_type, crate::typeref::list_type_ptr()) {
+        let len = ffi!(Py_SIZE(a));
+        len == ffi!(Py_SIZE(b))
+            && (0..len)
+                .all(|idx| equal(ffi!(PyList_GET_ITEM(a, idx)), ffi!(PyList_GET_ITEM(b, idx))))
+    } else if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+        if ffi!(Py_SIZE(a)) != ffi!(Py_SIZE(b)) {
+            return false;
+        }
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut value: *mut PyObject = null_mut();
+        while ffi!(PyDict_Next(a, &mut pos, &mut key, &mut value)) == 1 {
+            let other = ffi!(PyDict_GetItem(b, key));
+            if other.is_null() || !equal(value, other) {
+                return false;
+            }
+        }
+        true
+    } else {
+        // `None`, `True`, and `False` are equal only to themselves
+        false
+    }
+}
+
+/// `equals(a, b, /)`: return whether `a` and `b` are the same document.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn equals(a: *mut PyObject, b: *mut PyObject) -> *mut PyObject {
+    let left = read(a);
+    if left.is_null() {
+        return null_mut();
+    }
+    let right = read(b);
+    if right.is_null() {
+        ffi!(Py_DECREF(left));
+        return null_mut();
+    }
+    let ret = equal(left, right);
+    ffi!(Py_DECREF(left));
+    ffi!(Py_DECREF(right));
+    if ret {
+        use_immortal!(crate::typeref::true_ptr())
+    } else {
+        use_immortal!(crate::typeref::false_ptr())
+    }
+}
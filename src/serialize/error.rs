@@ -9,18 +9,30 @@ pub(crate) enum SerializeError {
     DefaultRecursionLimit,
     Integer53Bits,
     Integer64Bits,
+    IntegerDigits,
     InvalidStr,
     InvalidFragment,
+    InvalidFragmentJson,
+    InvalidRawNumber,
     KeyMustBeStr,
+    KeyOrderComparator,
+    MemoryViewNotContiguous,
+    NamedTupleMalformed,
+    NonFiniteFloat,
     RecursionLimit,
     TimeHasTzinfo,
+    DictBytesKeyInvalidUtf8(NonNull<crate::ffi::PyObject>),
     DictIntegerKey64Bit,
     DictKeyInvalidType,
+    FloatFormat,
     NumpyMalformed,
-    NumpyNotCContiguous,
     NumpyNotNativeEndian,
     NumpyUnsupportedDatatype,
+    PandasMalformed,
+    ArrowMalformed,
+    ArrowUnsupportedDatatype,
     UnsupportedType(NonNull<crate::ffi::PyObject>),
+    UnsupportedMsgpackType(NonNull<crate::ffi::PyObject>),
 }
 
 impl core::fmt::Display for SerializeError {
@@ -37,6 +49,10 @@ impl core::fmt::Display for SerializeError {
             }
             SerializeError::Integer53Bits => write!(f, "Integer exceeds 53-bit range"),
             SerializeError::Integer64Bits => write!(f, "Integer exceeds 64-bit range"),
+            SerializeError::IntegerDigits => write!(
+                f,
+                "Integer exceeds the limit for integer string conversion; use sys.set_int_max_str_digits()"
+            ),
             SerializeError::InvalidStr => write!(f, "{}", crate::util::INVALID_STR),
             SerializeError::InvalidFragment => {
                 write!(
@@ -44,31 +60,74 @@ impl core::fmt::Display for SerializeError {
                     "hyperjson.Fragment's content is not of type bytes or str"
                 )
             }
+            SerializeError::InvalidFragmentJson => {
+                write!(f, "hyperjson.Fragment's content is not valid JSON")
+            }
+            SerializeError::InvalidRawNumber => {
+                write!(f, "hyperjson.RawNumber's content is not a JSON number")
+            }
             SerializeError::KeyMustBeStr => write!(f, "Dict key must be str"),
+            SerializeError::KeyOrderComparator => {
+                write!(f, "key_order comparator must return an int")
+            }
+            SerializeError::MemoryViewNotContiguous => {
+                write!(f, "memoryview must be a C contiguous buffer")
+            }
+            SerializeError::NamedTupleMalformed => {
+                write!(f, "namedtuple _fields does not match its values")
+            }
+            SerializeError::NonFiniteFloat => {
+                write!(f, "float is not finite; use non_finite= to serialize it")
+            }
             SerializeError::RecursionLimit => write!(f, "Recursion limit reached"),
             SerializeError::TimeHasTzinfo => write!(f, "datetime.time must not have tzinfo set"),
+            SerializeError::DictBytesKeyInvalidUtf8(ptr) => {
+                let key = unsafe {
+                    core::slice::from_raw_parts(
+                        crate::ffi::PyBytes_AS_STRING(ptr.as_ptr()).cast::<u8>(),
+                        crate::util::isize_to_usize(crate::ffi::PyBytes_GET_SIZE(ptr.as_ptr())),
+                    )
+                };
+                write!(
+                    f,
+                    "Dict bytes key is not valid UTF-8: b'{}'; use OPT_BYTES_KEYS_LATIN1 or OPT_BYTES_KEYS_BASE64",
+                    key.escape_ascii()
+                )
+            }
             SerializeError::DictIntegerKey64Bit => {
                 write!(f, "Dict integer key must be within 64-bit range")
             }
             SerializeError::DictKeyInvalidType => {
                 write!(f, "Dict key must a type serializable with OPT_NON_STR_KEYS")
             }
+            SerializeError::FloatFormat => {
+                write!(
+                    f,
+                    "float_format callable must return a str that is a JSON number"
+                )
+            }
             SerializeError::NumpyMalformed => write!(f, "numpy array is malformed"),
-            SerializeError::NumpyNotCContiguous => write!(
-                f,
-                "numpy array is not C contiguous; use ndarray.tolist() in default"
-            ),
             SerializeError::NumpyNotNativeEndian => {
                 write!(f, "numpy array is not native-endianness")
             }
             SerializeError::NumpyUnsupportedDatatype => {
                 write!(f, "unsupported datatype in numpy array")
             }
+            SerializeError::PandasMalformed => write!(f, "pandas object is malformed"),
+            SerializeError::ArrowMalformed => write!(f, "arrow data is malformed"),
+            SerializeError::ArrowUnsupportedDatatype => {
+                write!(f, "unsupported datatype in arrow data")
+            }
             SerializeError::UnsupportedType(ptr) => {
                 let name =
                     unsafe { CStr::from_ptr((*ob_type!(ptr.as_ptr())).tp_name).to_string_lossy() };
                 write!(f, "Type is not JSON serializable: {name}")
             }
+            SerializeError::UnsupportedMsgpackType(ptr) => {
+                let name =
+                    unsafe { CStr::from_ptr((*ob_type!(ptr.as_ptr())).tp_name).to_string_lossy() };
+                write!(f, "Type is not MessagePack serializable: {name}")
+            }
         }
     }
 }
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::PyObject;
+use crate::serialize::writer::format_escaped_str_to_vec;
+use core::cell::Cell;
+use core::ptr::null_mut;
+
+/// Strings shorter than this are escaped directly; the lookup would cost
+/// about as much as the escape.
+pub(crate) const ESCAPE_CACHE_MIN_LEN: usize = 16;
+
+const ESCAPE_CACHE_SLOTS: usize = 256;
+
+/// Once this many escaped bytes are held, no new strings are cached.
+const ESCAPE_CACHE_ARENA_LIMIT: usize = 1024 * 1024;
+
+#[derive(Clone, Copy)]
+enum SlotState {
+    Empty,
+    /// Seen once. The pointer is not owned and may have been reused, which
+    /// only means a different string gets cached on its second sighting.
+    Seen,
+    /// The pointer is owned and `arena[start..start + len]` holds its
+    /// quoted, escaped form.
+    Cached {
+        start: usize,
+        len: usize,
+    },
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    ptr: *mut PyObject,
+    state: SlotState,
+}
+
+const EMPTY_SLOT: Slot = Slot {
+    ptr: null_mut(),
+    state: SlotState::Empty,
+};
+
+/// Direct-mapped cache of escaped `str` values keyed by object identity,
+/// scoped to a single `dumps()` call.
+pub(crate) struct EscapeCache {
+    slots: Option<Box<[Slot; ESCAPE_CACHE_SLOTS]>>,
+    arena: Vec<u8>,
+}
+
+impl EscapeCache {
+    pub fn new() -> Self {
+        EscapeCache {
+            slots: None,
+            arena: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn slot_index(ptr: *mut PyObject) -> usize {
+        // objects are at least 16-byte aligned
+        ((ptr as usize) >> 4) % ESCAPE_CACHE_SLOTS
+    }
+
+    fn lookup(&mut self, ptr: *mut PyObject, value: &str) -> Option<&[u8]> {
+        let slots = self
+            .slots
+            .get_or_insert_with(|| Box::new([EMPTY_SLOT; ESCAPE_CACHE_SLOTS]));
+        let slot = &mut slots[Self::slot_index(ptr)];
+        match slot.state {
+            SlotState::Cached { start, len } if slot.ptr == ptr => {
+                Some(&self.arena[start..start + len])
+            }
+            SlotState::Seen if slot.ptr == ptr => {
+                if self.arena.len() >= ESCAPE_CACHE_ARENA_LIMIT {
+                    cold_path!();
+                    return None;
+                }
+                let start = self.arena.len();
+                format_escaped_str_to_vec(&mut self.arena, value, false);
+                let len = self.arena.len() - start;
+                ffi!(Py_INCREF(ptr));
+                slot.state = SlotState::Cached {
+                    start: start,
+                    len: len,
+                };
+                Some(&self.arena[start..start + len])
+            }
+            SlotState::Cached { .. } => None,
+            _ => {
+                slot.ptr = ptr;
+                slot.state = SlotState::Seen;
+                None
+            }
+        }
+    }
+}
+
+impl Drop for EscapeCache {
+    fn drop(&mut self) {
+        if let Some(slots) = self.slots.as_ref() {
+            for slot in slots.iter() {
+                if let SlotState::Cached { .. } = slot.state {
+                    ffi!(Py_DECREF(slot.ptr));
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    // The cache of the innermost `dumps()` call on this thread, or null
+    // outside of one.
+    static ACTIVE: Cell<*mut EscapeCache> = const { Cell::new(null_mut()) };
+}
+
+/// Make `cache` the active cache for the current thread and return the
+/// previously active cache, which must be restored with `restore()`.
+pub(crate) fn install(cache: &mut EscapeCache) -> *mut EscapeCache {
+    ACTIVE.with(|active| active.replace(core::ptr::from_mut(cache)))
+}
+
+/// Leave no cache active for the current thread, as for `OPT_ENSURE_ASCII`,
+/// whose output differs from what is cached, and return the previously
+/// active cache, which must be restored with `restore()`.
+pub(crate) fn suspend() -> *mut EscapeCache {
+    ACTIVE.with(|active| active.replace(null_mut()))
+}
+
+pub(crate) fn restore(previous: *mut EscapeCache) {
+    ACTIVE.with(|active| active.set(previous));
+}
+
+/// Return the escaped form of `value`, the contents of the `str` at `ptr`,
+/// if it is cached in the active cache. The slice is only valid until the
+/// next call.
+#[inline(never)]
+pub(crate) fn escaped<'a>(ptr: *mut PyObject, value: &'a str) -> Option<&'a [u8]> {
+    let cache = ACTIVE.with(Cell::get);
+    if cache.is_null() {
+        return None;
+    }
+    unsafe {
+        (*cache)
+            .lookup(ptr, value)
+            .map(|buf| &*core::ptr::from_ref(buf))
+    }
+}
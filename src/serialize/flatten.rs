@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.flatten()` and `hyperjson.unflatten()`, between nested objects
+//! and objects of keys joined with a separator.
+//!
+//! Only objects are flattened: an array, like any other value, and an empty
+//! object are values of the flat object. `unflatten()` therefore reverses
+//! `flatten()` unless a key contains the separator.
+
+use crate::deserialize::{DeserializeOptions, deserialize};
+use crate::exception::raise_dumps_exception_dynamic;
+use crate::ffi::PyObject;
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+
+use core::ptr::null_mut;
+use std::collections::HashSet;
+
+const RECURSION_LIMIT: usize = 1024;
+
+fn is_dict(ptr: *mut PyObject) -> bool {
+    is_subclass_by_flag!(tp_flags!(ob_type!(ptr)), Py_TPFLAGS_DICT_SUBCLASS)
+}
+
+/// A new reference to the `dict` `ptr` or the document `ptr` read, which
+/// must be an object.
+fn read_object(ptr: *mut PyObject, name: &str) -> Result<*mut PyObject, *mut PyObject> {
+    let ob_type = ob_type!(ptr);
+    let obj = if is_type!(ob_type, crate::typeref::bytes_type_ptr())
+        || is_type!(ob_type, crate::typeref::bytearray_type_ptr())
+        || is_type!(ob_type, crate::typeref::memoryview_type_ptr())
+    {
+        match deserialize(ptr, &DeserializeOptions::default()) {
+            Ok(obj) => obj.as_ptr(),
+            Err(err) => return Err(crate::exception::raise_loads_exception(err)),
+        }
+    } else {
+        ffi!(Py_INCREF(ptr));
+        ptr
+    };
+    if !is_dict(obj) {
+        ffi!(Py_DECREF(obj));
+        return Err(raise_dumps_exception_dynamic(&format!(
+            "{name}() argument must be a dict or a document of an object"
+        )));
+    }
+    Ok(obj)
+}
+
+/// The separator, which must be a non-empty `str`, or `"."`.
+fn read_sep(sep: Option<*mut PyObject>, name: &str) -> Result<&'static str, *mut PyObject> {
+    let Some(sep) = sep else {
+        return Ok(".");
+    };
+    if is_type!(ob_type!(sep), crate::typeref::str_type_ptr()) {
+        if let Some(sep) = unsafe { PyStr::from_ptr_unchecked(sep).to_str() } {
+            if !sep.is_empty() {
+                return Ok(sep);
+            }
+        }
+    }
+    Err(raise_dumps_exception_dynamic(&format!(
+        "{name}() sep must be a non-empty str"
+    )))
+}
+
+fn key_str(key: *mut PyObject, name: &str) -> Result<&'static str, String> {
+    if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+        return Err(format!("{name}() keys must be str"));
+    }
+    unsafe { PyStr::from_ptr_unchecked(key).to_str() }
+        .ok_or_else(|| String::from(crate::util::INVALID_STR))
+}
+
+fn new_str(val: &str) -> *mut PyObject {
+    ffi!(PyUnicode_FromStringAndSize(
+        val.as_ptr().cast::<core::ffi::c_char>(),
+        usize_to_isize(val.len())
+    ))
+}
+
+fn flatten_into(
+    result: *mut PyObject,
+    dict: *mut PyObject,
+    prefix: &mut String,
+    sep: &str,
+    depth: usize,
+) -> Result<(), String> {
+    if depth > RECURSION_LIMIT {
+        cold_path!();
+        return Err(String::from("flatten() exceeds recursion limit"));
+    }
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(dict, &mut pos, &mut key, &mut value)) == 1 {
+        let key_as_str = key_str(key, "flatten")?;
+        let len = prefix.len();
+        prefix.push_str(key_as_str);
+        if is_dict(value) && ffi!(Py_SIZE(value)) != 0 {
+            prefix.push_str(sep);
+            flatten_into(result, value, prefix, sep, depth + 1)?;
+        } else {
+            // a key of the outermost object is already a `str`
+            let flat_key = if len == 0 {
+                ffi!(Py_INCREF(key));
+                key
+            } else {
+                new_str(prefix)
+            };
+            let ret = ffi!(PyDict_SetItem(result, flat_key, value));
+            ffi!(Py_DECREF(flat_key));
+            if ret == -1 {
+                ffi!(PyErr_Clear());
+                return Err(String::from("flatten() could not set key"));
+            }
+        }
+        prefix.truncate(len);
+    }
+    Ok(())
+}
+
+/// `flatten(obj, /, sep=".")`: a `dict` of the values of `obj` that are not
+/// non-empty objects, keyed by the keys on the way to each joined with `sep`.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn flatten(obj: *mut PyObject, sep: Option<*mut PyObject>) -> *mut PyObject {
+    let sep = match read_sep(sep, "flatten") {
+        Ok(sep) => sep,
+        Err(err) => return err,
+    };
+    let obj = match read_object(obj, "flatten") {
+        Ok(obj) => obj,
+        Err(err) => return err,
+    };
+    let result = ffi!(PyDict_New());
+    let ret = flatten_into(result, obj, &mut String::new(), sep, 0);
+    ffi!(Py_DECREF(obj));
+    match ret {
+        Ok(()) => result,
+        Err(err) => {
+            ffi!(Py_DECREF(result));
+            raise_dumps_exception_dynamic(err.as_str())
+        }
+    }
+}
+
+/// Set the value of the flat key `key` in `result`, adding the objects on
+/// the way to it. `created` is the objects added, as only those are added to.
+fn unflatten_key(
+    result: *mut PyObject,
+    key: &str,
+    value: *mut PyObject,
+    sep: &str,
+    created: &mut HashSet<usize>,
+) -> Result<(), String> {
+    let conflict = || format!("unflatten() key conflicts with another key: {key}");
+    let mut parts = key.split(sep).peekable();
+    let mut dict = result;
+    while let Some(part) = parts.next() {
+        let part_key = new_str(part);
+        let existing = ffi!(PyDict_GetItem(dict, part_key));
+        if parts.peek().is_none() {
+            let ret = if existing.is_null() {
+                ffi!(PyDict_SetItem(dict, part_key, value))
+            } else {
+                -2
+            };
+            ffi!(Py_DECREF(part_key));
+            return match ret {
+                0 => Ok(()),
+                -2 => Err(conflict()),
+                _ => {
+                    ffi!(PyErr_Clear());
+                    Err(String::from("unflatten() could not set key"))
+                }
+            };
+        }
+        if existing.is_null() {
+            let child = ffi!(PyDict_New());
+            created.insert(child as usize);
+            let _ = ffi!(PyDict_SetItem(dict, part_key, child));
+            ffi!(Py_DECREF(child));
+            dict = child;
+        } else if created.contains(&(existing as usize)) {
+            dict = existing;
+        } else {
+            ffi!(Py_DECREF(part_key));
+            return Err(conflict());
+        }
+        ffi!(Py_DECREF(part_key));
+    }
+    Ok(())
+}
+
+/// `unflatten(mapping, /, sep=".")`: the nested `dict` of `mapping`, whose
+/// keys are split on `sep` into the keys on the way to each value.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn unflatten(mapping: *mut PyObject, sep: Option<*mut PyObject>) -> *mut PyObject {
+    let sep = match read_sep(sep, "unflatten") {
+        Ok(sep) => sep,
+        Err(err) => return err,
+    };
+    let mapping = match read_object(mapping, "unflatten") {
+        Ok(mapping) => mapping,
+        Err(err) => return err,
+    };
+    let result = ffi!(PyDict_New());
+    let mut created = HashSet::new();
+    let mut ret = Ok(());
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(mapping, &mut pos, &mut key, &mut value)) == 1 {
+        ret = key_str(key, "unflatten")
+            .and_then(|key| unflatten_key(result, key, value, sep, &mut created));
+        if ret.is_err() {
+            break;
+        }
+    }
+    ffi!(Py_DECREF(mapping));
+    match ret {
+        Ok(()) => result,
+        Err(err) => {
+            ffi!(Py_DECREF(result));
+            raise_dumps_exception_dynamic(err.as_str())
+        }
+    }
+}
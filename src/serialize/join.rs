@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::{Fragment, PyObject};
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::fragment_contents;
+use crate::serialize::writer::{BytesWriter, to_writer};
+use crate::str::PyStr;
+use crate::util::isize_to_usize;
+use core::ffi::CStr;
+use core::ptr::NonNull;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+/// Space reserved past the exact output length so that the writer's own
+/// `reserve()` calls never trigger a reallocation.
+const JOIN_SLACK: usize = 64;
+
+/// A piece passed to `join_array()` or `join_object()` that is already
+/// encoded JSON.
+struct RawPiece {
+    contents: *mut PyObject,
+    buffer: &'static [u8],
+}
+
+impl RawPiece {
+    /// Accept a `hyperjson.Fragment`, `bytes`, or `str`.
+    fn from_ptr(ptr: *mut PyObject) -> Result<Self, SerializeError> {
+        let ob_type = ob_type!(ptr);
+        let contents = if is_type!(ob_type, crate::typeref::get_fragment_type()) {
+            unsafe { (*ptr.cast::<Fragment>()).contents }
+        } else {
+            ptr
+        };
+        Ok(RawPiece {
+            contents: contents,
+            buffer: fragment_contents(contents)?,
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match crate::deserialize::deserialize(
+            self.contents,
+            &crate::deserialize::DeserializeOptions::default(),
+        ) {
+            Ok(obj) => {
+                ffi!(Py_DECREF(obj.as_ptr()));
+                Ok(())
+            }
+            Err(err) => Err(err.message.into_owned()),
+        }
+    }
+}
+
+impl Serialize for RawPiece {
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.buffer)
+    }
+}
+
+struct JoinArraySerializer<'a> {
+    pieces: &'a [RawPiece],
+}
+
+impl Serialize for JoinArraySerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for piece in self.pieces.iter() {
+            seq.serialize_element(piece).unwrap();
+        }
+        seq.end()
+    }
+}
+
+struct JoinObjectSerializer<'a> {
+    entries: &'a [(&'static str, RawPiece)],
+}
+
+impl Serialize for JoinObjectSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None).unwrap();
+        for (key, piece) in self.entries.iter() {
+            map.serialize_key(key).unwrap();
+            map.serialize_value(piece)?;
+        }
+        map.end()
+    }
+}
+
+/// A `list` or `tuple` borrowed from `PySequence_Fast()`.
+pub(crate) struct FastSequence {
+    ptr: *mut PyObject,
+}
+
+impl FastSequence {
+    pub fn new(ptr: *mut PyObject, msg: &'static CStr) -> Result<Self, String> {
+        let seq = ffi!(PySequence_Fast(ptr, msg.as_ptr()));
+        if seq.is_null() {
+            cold_path!();
+            return Err(String::from(msg.to_str().unwrap()));
+        }
+        Ok(FastSequence { ptr: seq })
+    }
+
+    pub fn len(&self) -> usize {
+        isize_to_usize(ffi!(Py_SIZE(self.ptr)))
+    }
+
+    pub fn get(&self, idx: usize) -> *mut PyObject {
+        debug_assert!(idx < self.len());
+        #[allow(clippy::cast_possible_wrap)]
+        let idx = idx as isize;
+        if is_type!(ob_type!(self.ptr), crate::typeref::list_type_ptr()) {
+            ffi!(PyList_GET_ITEM(self.ptr, idx))
+        } else {
+            unsafe { crate::ffi::PyTuple_GET_ITEM(self.ptr, idx) }
+        }
+    }
+}
+
+impl Drop for FastSequence {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.ptr));
+    }
+}
+
+fn finish(buf: &mut BytesWriter, res: serde_json::Result<()>) -> Result<NonNull<PyObject>, String> {
+    match res {
+        Ok(()) => Ok(buf.finish(b"")),
+        Err(err) => {
+            buf.abort();
+            Err(err.to_string())
+        }
+    }
+}
+
+/// Assemble a JSON array from already-encoded pieces without parsing them,
+/// unless `validate` is set.
+pub(crate) fn join_array(ptr: *mut PyObject, validate: bool) -> Result<NonNull<PyObject>, String> {
+    let seq = FastSequence::new(ptr, c"join_array() argument must be iterable")?;
+    let len = seq.len();
+    let mut pieces: Vec<RawPiece> = Vec::with_capacity(len);
+    let mut size = 2 + len;
+    for idx in 0..len {
+        let piece = RawPiece::from_ptr(seq.get(idx)).map_err(|err| err.to_string())?;
+        if validate {
+            piece
+                .validate()
+                .map_err(|err| format!("join_array() item {idx} is not valid JSON: {err}"))?;
+        }
+        size += piece.buffer.len();
+        pieces.push(piece);
+    }
+    let mut buf = BytesWriter::with_capacity(size + JOIN_SLACK);
+    let res = to_writer(&mut buf, &JoinArraySerializer { pieces: &pieces }, 0);
+    finish(&mut buf, res)
+}
+
+/// Assemble a JSON object from `(str, piece)` pairs whose values are
+/// already-encoded pieces. Keys are escaped; values are written as-is
+/// unless `validate` is set.
+pub(crate) fn join_object(ptr: *mut PyObject, validate: bool) -> Result<NonNull<PyObject>, String> {
+    let seq = FastSequence::new(ptr, c"join_object() argument must be iterable")?;
+    let len = seq.len();
+    let mut entries: Vec<(&'static str, RawPiece)> = Vec::with_capacity(len);
+    let mut size = 2 + len * 4;
+    let mut max_key = 0;
+    for idx in 0..len {
+        let pair = seq.get(idx);
+        if !is_type!(ob_type!(pair), crate::typeref::tuple_type_ptr()) || ffi!(Py_SIZE(pair)) != 2 {
+            cold_path!();
+            return Err(String::from(
+                "join_object() items must be (key, value) tuples",
+            ));
+        }
+        let key_ptr = unsafe { crate::ffi::PyTuple_GET_ITEM(pair, 0) };
+        if !is_type!(ob_type!(key_ptr), crate::typeref::str_type_ptr()) {
+            cold_path!();
+            return Err(SerializeError::KeyMustBeStr.to_string());
+        }
+        let key = match unsafe { PyStr::from_ptr_unchecked(key_ptr).to_str() } {
+            Some(uni) => uni,
+            None => return Err(SerializeError::InvalidStr.to_string()),
+        };
+        let piece = RawPiece::from_ptr(unsafe { crate::ffi::PyTuple_GET_ITEM(pair, 1) })
+            .map_err(|err| err.to_string())?;
+        if validate {
+            piece.validate().map_err(|err| {
+                format!("join_object() value for key {key:?} is not valid JSON: {err}")
+            })?;
+        }
+        size += key.len() + piece.buffer.len();
+        max_key = max_key.max(key.len());
+        entries.push((key, piece));
+    }
+    // escaping reserves up to 8 bytes per input byte of a key
+    let mut buf = BytesWriter::with_capacity(size + max_key * 8 + JOIN_SLACK);
+    let res = to_writer(&mut buf, &JoinObjectSerializer { entries: &entries }, 0);
+    finish(&mut buf, res)
+}
@@ -0,0 +1,917 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `dumps(json_schema=...)`, which checks what `dumps()` writes against a
+//! JSON Schema.
+//!
+//! The schema is compiled from its `dict` before the object is serialized.
+//! The output is then read back and checked, so that the values written for
+//! a dataclass, one returned by `default`, and any other type are checked as
+//! the JSON they are written as. `$ref` is resolved within the schema.
+//! Keywords asserting something that is not implemented, such as `pattern`,
+//! are an error rather than ignored.
+
+use crate::deserialize::{DeserializeOptions, deserialize};
+use crate::ffi::PyObject;
+use crate::str::PyStr;
+use crate::util::isize_to_usize;
+use core::ffi::CStr;
+use core::ptr::null_mut;
+
+const RECURSION_LIMIT: usize = 1024;
+
+const TYPE_NULL: u8 = 1;
+const TYPE_BOOLEAN: u8 = 1 << 1;
+const TYPE_INTEGER: u8 = 1 << 2;
+const TYPE_NUMBER: u8 = 1 << 3;
+const TYPE_STRING: u8 = 1 << 4;
+const TYPE_ARRAY: u8 = 1 << 5;
+const TYPE_OBJECT: u8 = 1 << 6;
+
+/// Keywords that assert something this does not implement.
+const UNSUPPORTED: [&str; 8] = [
+    "pattern",
+    "patternProperties",
+    "contains",
+    "dependentSchemas",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "$dynamicRef",
+    "$recursiveRef",
+];
+
+/// An index into `JsonSchema::nodes`.
+type NodeId = usize;
+
+enum Node {
+    /// `true` or `false`.
+    Bool(bool),
+    Keywords(Box<Keywords>),
+}
+
+#[derive(Default)]
+struct Keywords {
+    /// The `TYPE_*` bits of `type`, or 0 for any type.
+    types: u8,
+    enumeration: Option<Vec<*mut PyObject>>,
+    constant: Option<*mut PyObject>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    multiple_of: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    items: Option<NodeId>,
+    prefix_items: Vec<NodeId>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    unique_items: bool,
+    properties: Vec<(*mut PyObject, NodeId)>,
+    /// `additionalProperties`, of members not in `properties`.
+    additional: Option<NodeId>,
+    property_names: Option<NodeId>,
+    required: Vec<*mut PyObject>,
+    dependent_required: Vec<(*mut PyObject, Vec<*mut PyObject>)>,
+    min_properties: Option<usize>,
+    max_properties: Option<usize>,
+    all_of: Vec<NodeId>,
+    any_of: Vec<NodeId>,
+    one_of: Vec<NodeId>,
+    not: Option<NodeId>,
+    if_then_else: Option<(NodeId, Option<NodeId>, Option<NodeId>)>,
+    reference: Option<NodeId>,
+}
+
+impl Drop for Keywords {
+    fn drop(&mut self) {
+        for ptr in self.enumeration.iter().flatten() {
+            ffi!(Py_DECREF(*ptr));
+        }
+        if let Some(ptr) = self.constant {
+            ffi!(Py_DECREF(ptr));
+        }
+        for (name, _) in self.properties.iter() {
+            ffi!(Py_DECREF(*name));
+        }
+        for name in self.required.iter() {
+            ffi!(Py_DECREF(*name));
+        }
+        for (name, names) in self.dependent_required.iter() {
+            ffi!(Py_DECREF(*name));
+            for name in names.iter() {
+                ffi!(Py_DECREF(*name));
+            }
+        }
+    }
+}
+
+/// A compiled JSON Schema.
+pub(crate) struct JsonSchema {
+    nodes: Vec<Node>,
+}
+
+/// `ptr` as a `str`, if it is one.
+fn to_str<'a>(ptr: *mut PyObject) -> Option<&'a str> {
+    if ptr.is_null() || !is_type!(ob_type!(ptr), crate::typeref::str_type_ptr()) {
+        return None;
+    }
+    unsafe { PyStr::from_ptr_unchecked(ptr).to_str() }
+}
+
+/// The value of `ptr`, an `int` or `float`, as `f64`.
+fn to_f64(ptr: *mut PyObject) -> Option<f64> {
+    let ob_type = ob_type!(ptr);
+    if is_type!(ob_type, crate::typeref::float_type_ptr()) {
+        Some(ffi!(PyFloat_AS_DOUBLE(ptr)))
+    } else if is_type!(ob_type, crate::typeref::int_type_ptr()) {
+        let val = ffi!(PyLong_AsDouble(ptr));
+        if val == -1.0 && !ffi!(PyErr_Occurred()).is_null() {
+            ffi!(PyErr_Clear());
+            let mut overflow = 0;
+            ffi!(PyLong_AsLongLongAndOverflow(ptr, &mut overflow));
+            ffi!(PyErr_Clear());
+            return Some(f64::INFINITY.copysign(f64::from(overflow)));
+        }
+        Some(val)
+    } else {
+        None
+    }
+}
+
+/// Compiles the nodes of a schema, resolving `$ref` from `root`.
+struct Compiler {
+    root: *mut PyObject,
+    nodes: Vec<Node>,
+    refs: Vec<(String, NodeId)>,
+}
+
+impl Compiler {
+    fn compile(&mut self, ptr: *mut PyObject) -> Result<NodeId, String> {
+        if core::ptr::eq(ptr, crate::typeref::true_ptr()) {
+            self.nodes.push(Node::Bool(true));
+            return Ok(self.nodes.len() - 1);
+        }
+        if core::ptr::eq(ptr, crate::typeref::false_ptr()) {
+            self.nodes.push(Node::Bool(false));
+            return Ok(self.nodes.len() - 1);
+        }
+        if !is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr()) {
+            return Err(String::from("json_schema must be a dict or bool"));
+        }
+        // reserved first so that a `$ref` may refer to it
+        let id = self.nodes.len();
+        self.nodes.push(Node::Bool(true));
+        let mut keywords = Box::new(Keywords::default());
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut value: *mut PyObject = null_mut();
+        while unsafe { crate::ffi::PyDict_Next(ptr, &mut pos, &mut key, &mut value) } != 0 {
+            let Some(keyword) = to_str(key) else {
+                continue;
+            };
+            self.keyword(ptr, &mut keywords, keyword, value)?;
+        }
+        self.nodes[id] = Node::Keywords(keywords);
+        Ok(id)
+    }
+
+    fn keyword(
+        &mut self,
+        parent: *mut PyObject,
+        keywords: &mut Keywords,
+        keyword: &str,
+        value: *mut PyObject,
+    ) -> Result<(), String> {
+        let invalid = || format!("json_schema has an invalid {keyword}");
+        match keyword {
+            "type" => {
+                keywords.types = if is_type!(ob_type!(value), crate::typeref::list_type_ptr()) {
+                    let mut types = 0;
+                    for item in list_items(value) {
+                        types |= type_bit(item).ok_or_else(invalid)?;
+                    }
+                    types
+                } else {
+                    type_bit(value).ok_or_else(invalid)?
+                };
+            }
+            "enum" => {
+                if !is_type!(ob_type!(value), crate::typeref::list_type_ptr()) {
+                    return Err(invalid());
+                }
+                let items = list_items(value).collect::<Vec<_>>();
+                for item in items.iter() {
+                    ffi!(Py_INCREF(*item));
+                }
+                keywords.enumeration = Some(items);
+            }
+            "const" => {
+                ffi!(Py_INCREF(value));
+                keywords.constant = Some(value);
+            }
+            "minimum" => keywords.minimum = Some(to_f64(value).ok_or_else(invalid)?),
+            "maximum" => keywords.maximum = Some(to_f64(value).ok_or_else(invalid)?),
+            "exclusiveMinimum" => {
+                keywords.exclusive_minimum = Some(to_f64(value).ok_or_else(invalid)?);
+            }
+            "exclusiveMaximum" => {
+                keywords.exclusive_maximum = Some(to_f64(value).ok_or_else(invalid)?);
+            }
+            "multipleOf" => match to_f64(value) {
+                Some(val) if val > 0.0 => keywords.multiple_of = Some(val),
+                _ => return Err(invalid()),
+            },
+            "minLength" => keywords.min_length = Some(to_count(value).ok_or_else(invalid)?),
+            "maxLength" => keywords.max_length = Some(to_count(value).ok_or_else(invalid)?),
+            "minItems" => keywords.min_items = Some(to_count(value).ok_or_else(invalid)?),
+            "maxItems" => keywords.max_items = Some(to_count(value).ok_or_else(invalid)?),
+            "minProperties" => {
+                keywords.min_properties = Some(to_count(value).ok_or_else(invalid)?);
+            }
+            "maxProperties" => {
+                keywords.max_properties = Some(to_count(value).ok_or_else(invalid)?);
+            }
+            "uniqueItems" => {
+                keywords.unique_items = core::ptr::eq(value, crate::typeref::true_ptr())
+            }
+            "items" => {
+                if is_type!(ob_type!(value), crate::typeref::list_type_ptr()) {
+                    // the form of `prefixItems` before draft 2020-12
+                    keywords.prefix_items = self.compile_list(value)?;
+                } else {
+                    keywords.items = Some(self.compile(value)?);
+                }
+            }
+            "prefixItems" => {
+                if !is_type!(ob_type!(value), crate::typeref::list_type_ptr()) {
+                    return Err(invalid());
+                }
+                keywords.prefix_items = self.compile_list(value)?;
+            }
+            "properties" => {
+                if !is_type!(ob_type!(value), crate::typeref::dict_type_ptr()) {
+                    return Err(invalid());
+                }
+                let mut pos = 0;
+                let mut name: *mut PyObject = null_mut();
+                let mut schema: *mut PyObject = null_mut();
+                while unsafe { crate::ffi::PyDict_Next(value, &mut pos, &mut name, &mut schema) }
+                    != 0
+                {
+                    if to_str(name).is_none() {
+                        return Err(invalid());
+                    }
+                    let node = self.compile(schema)?;
+                    ffi!(Py_INCREF(name));
+                    keywords.properties.push((name, node));
+                }
+            }
+            "additionalProperties" => keywords.additional = Some(self.compile(value)?),
+            "propertyNames" => keywords.property_names = Some(self.compile(value)?),
+            "required" => keywords.required = str_list(value).ok_or_else(invalid)?,
+            "dependentRequired" => {
+                if !is_type!(ob_type!(value), crate::typeref::dict_type_ptr()) {
+                    return Err(invalid());
+                }
+                let mut pos = 0;
+                let mut name: *mut PyObject = null_mut();
+                let mut names: *mut PyObject = null_mut();
+                while unsafe { crate::ffi::PyDict_Next(value, &mut pos, &mut name, &mut names) }
+                    != 0
+                {
+                    let names = str_list(names).ok_or_else(invalid)?;
+                    ffi!(Py_INCREF(name));
+                    keywords.dependent_required.push((name, names));
+                }
+            }
+            "allOf" => keywords.all_of = self.compile_list(value)?,
+            "anyOf" => keywords.any_of = self.compile_list(value)?,
+            "oneOf" => keywords.one_of = self.compile_list(value)?,
+            "not" => keywords.not = Some(self.compile(value)?),
+            "if" => {
+                let then = self.member(parent, c"then")?;
+                let otherwise = self.member(parent, c"else")?;
+                keywords.if_then_else = Some((self.compile(value)?, then, otherwise));
+            }
+            "then" | "else" => {
+                // compiled with `if`
+            }
+            "$ref" => {
+                let Some(reference) = to_str(value) else {
+                    return Err(invalid());
+                };
+                keywords.reference = Some(self.resolve(reference)?);
+            }
+            _ if UNSUPPORTED.contains(&keyword) => {
+                return Err(format!("json_schema keyword is not supported: {keyword}"));
+            }
+            _ => {
+                // annotations and keywords of other vocabularies
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_list(&mut self, ptr: *mut PyObject) -> Result<Vec<NodeId>, String> {
+        if !is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) || ffi!(Py_SIZE(ptr)) == 0 {
+            return Err(String::from("json_schema has an invalid list of schemas"));
+        }
+        let mut nodes = Vec::with_capacity(isize_to_usize(ffi!(Py_SIZE(ptr))));
+        for item in list_items(ptr) {
+            nodes.push(self.compile(item)?);
+        }
+        Ok(nodes)
+    }
+
+    /// Resolve `reference`, a JSON Pointer within the schema, as `#` or
+    /// `#/$defs/name`.
+    fn resolve(&mut self, reference: &str) -> Result<NodeId, String> {
+        if let Some((_, id)) = self.refs.iter().find(|(known, _)| known == reference) {
+            return Ok(*id);
+        }
+        let unresolved = || format!("json_schema $ref cannot be resolved: {reference}");
+        let Some(pointer) = reference.strip_prefix('#') else {
+            return Err(unresolved());
+        };
+        let mut target = self.root;
+        if !pointer.is_empty() {
+            let Some(pointer) = pointer.strip_prefix('/') else {
+                return Err(unresolved());
+            };
+            for token in pointer.split('/') {
+                let token = token.replace("~1", "/").replace("~0", "~");
+                target = pointer_step(target, &token).ok_or_else(unresolved)?;
+            }
+        }
+        // compiled again from the target rather than shared with the node
+        // that contains it, so that the id is known before compiling
+        self.refs.push((String::from(reference), self.nodes.len()));
+        self.compile(target)
+    }
+
+    /// The schema `name` of the schema `parent`, if it has one.
+    fn member(&mut self, parent: *mut PyObject, name: &CStr) -> Result<Option<NodeId>, String> {
+        let ptr = ffi!(PyDict_GetItemString(parent, name.as_ptr()));
+        if ptr.is_null() {
+            return Ok(None);
+        }
+        self.compile(ptr).map(Some)
+    }
+}
+
+/// The items of `ptr`, a `list`.
+fn list_items(ptr: *mut PyObject) -> impl Iterator<Item = *mut PyObject> {
+    (0..ffi!(Py_SIZE(ptr))).map(move |idx| unsafe { crate::ffi::PyList_GET_ITEM(ptr, idx) })
+}
+
+/// A `list` of `str` as new references.
+fn str_list(ptr: *mut PyObject) -> Option<Vec<*mut PyObject>> {
+    if !is_type!(ob_type!(ptr), crate::typeref::list_type_ptr())
+        || list_items(ptr).any(|item| to_str(item).is_none())
+    {
+        return None;
+    }
+    let items = list_items(ptr).collect::<Vec<_>>();
+    for item in items.iter() {
+        ffi!(Py_INCREF(*item));
+    }
+    Some(items)
+}
+
+/// A non-negative `int`.
+fn to_count(ptr: *mut PyObject) -> Option<usize> {
+    if !is_type!(ob_type!(ptr), crate::typeref::int_type_ptr()) {
+        return None;
+    }
+    let val = ffi!(PyLong_AsLongLong(ptr));
+    if val == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        ffi!(PyErr_Clear());
+        return None;
+    }
+    usize::try_from(val).ok()
+}
+
+fn type_bit(ptr: *mut PyObject) -> Option<u8> {
+    match to_str(ptr)? {
+        "null" => Some(TYPE_NULL),
+        "boolean" => Some(TYPE_BOOLEAN),
+        "integer" => Some(TYPE_INTEGER),
+        "number" => Some(TYPE_NUMBER),
+        "string" => Some(TYPE_STRING),
+        "array" => Some(TYPE_ARRAY),
+        "object" => Some(TYPE_OBJECT),
+        _ => None,
+    }
+}
+
+/// The member or item `token` of `ptr`, for a JSON Pointer.
+fn pointer_step(ptr: *mut PyObject, token: &str) -> Option<*mut PyObject> {
+    let ob_type = ob_type!(ptr);
+    if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+        let key = ffi!(PyUnicode_FromStringAndSize(
+            token.as_ptr().cast::<core::ffi::c_char>(),
+            crate::util::usize_to_isize(token.len())
+        ));
+        let value = ffi!(PyDict_GetItem(ptr, key));
+        ffi!(Py_DECREF(key));
+        (!value.is_null()).then_some(value)
+    } else if is_type!(ob_type, crate::typeref::list_type_ptr()) {
+        let idx = token.parse::<usize>().ok()?;
+        list_items(ptr).nth(idx)
+    } else {
+        None
+    }
+}
+
+/// A value as JSON Schema sees it.
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    Null,
+    Bool,
+    Number(f64, bool),
+    Str(&'a str),
+    /// A `list` or `tuple` and its length.
+    Array(usize),
+    Object,
+    /// Not written natively, so not checked.
+    Other,
+}
+
+impl Value<'_> {
+    fn of(ptr: *mut PyObject) -> Self {
+        let ob_type = ob_type!(ptr);
+        if core::ptr::eq(ptr, crate::typeref::none_ptr()) {
+            Value::Null
+        } else if is_type!(ob_type, crate::typeref::bool_type_ptr()) {
+            Value::Bool
+        } else if is_type!(ob_type, crate::typeref::float_type_ptr()) {
+            let val = ffi!(PyFloat_AS_DOUBLE(ptr));
+            Value::Number(val, val.is_finite() && val.fract() == 0.0)
+        } else if is_type!(ob_type, crate::typeref::int_type_ptr()) {
+            Value::Number(to_f64(ptr).unwrap_or(f64::NAN), true)
+        } else if is_type!(ob_type, crate::typeref::str_type_ptr()) {
+            to_str(ptr).map_or(Value::Other, Value::Str)
+        } else if is_type!(ob_type, crate::typeref::list_type_ptr())
+            || is_type!(ob_type, crate::typeref::tuple_type_ptr())
+        {
+            Value::Array(isize_to_usize(ffi!(Py_SIZE(ptr))))
+        } else if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+            Value::Object
+        } else {
+            Value::Other
+        }
+    }
+
+    fn has_type(self, types: u8) -> bool {
+        let bits = match self {
+            Value::Null => TYPE_NULL,
+            Value::Bool => TYPE_BOOLEAN,
+            Value::Number(_, true) => TYPE_INTEGER | TYPE_NUMBER,
+            Value::Number(_, false) => TYPE_NUMBER,
+            Value::Str(_) => TYPE_STRING,
+            Value::Array(_) => TYPE_ARRAY,
+            Value::Object => TYPE_OBJECT,
+            Value::Other => return true,
+        };
+        types & bits != 0
+    }
+}
+
+/// The item `idx` of `ptr`, a `list` or `tuple`.
+fn array_item(ptr: *mut PyObject, idx: usize) -> *mut PyObject {
+    let idx = crate::util::usize_to_isize(idx);
+    unsafe {
+        if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) {
+            crate::ffi::PyList_GET_ITEM(ptr, idx)
+        } else {
+            crate::ffi::PyTuple_GET_ITEM(ptr, idx)
+        }
+    }
+}
+
+/// Whether `a` and `b` are equal as JSON, for `enum`, `const`, and
+/// `uniqueItems`: `True` is not `1`, and a `list` may equal a `tuple`.
+fn json_equal(a: *mut PyObject, b: *mut PyObject, depth: usize) -> bool {
+    if depth > RECURSION_LIMIT {
+        return false;
+    }
+    match (Value::of(a), Value::of(b)) {
+        (Value::Null, Value::Null) | (Value::Bool, Value::Bool) => core::ptr::eq(a, b),
+        (Value::Number(..), Value::Number(..)) | (Value::Other, Value::Other) => {
+            let ret = ffi!(PyObject_RichCompareBool(a, b, crate::ffi::Py_EQ));
+            if ret < 0 {
+                ffi!(PyErr_Clear());
+            }
+            ret == 1
+        }
+        (Value::Str(a), Value::Str(b)) => a == b,
+        (Value::Array(len), Value::Array(other)) => {
+            len == other
+                && (0..len).all(|idx| json_equal(array_item(a, idx), array_item(b, idx), depth + 1))
+        }
+        (Value::Object, Value::Object) => {
+            if ffi!(Py_SIZE(a)) != ffi!(Py_SIZE(b)) {
+                return false;
+            }
+            let mut pos = 0;
+            let mut key: *mut PyObject = null_mut();
+            let mut value: *mut PyObject = null_mut();
+            while unsafe { crate::ffi::PyDict_Next(a, &mut pos, &mut key, &mut value) } != 0 {
+                let other = ffi!(PyDict_GetItem(b, key));
+                if other.is_null() || !json_equal(value, other, depth + 1) {
+                    return false;
+                }
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Why a value does not match, and the path to it, innermost first.
+struct Mismatch {
+    message: String,
+    path: Vec<Segment>,
+    /// The recursion limit was reached, which has no useful path.
+    recursion: bool,
+}
+
+impl Mismatch {
+    #[cold]
+    fn new(message: String) -> Self {
+        Mismatch {
+            message: message,
+            path: Vec::new(),
+            recursion: false,
+        }
+    }
+
+    #[cold]
+    fn at(mut self, segment: Segment) -> Self {
+        if !self.recursion {
+            self.path.push(segment);
+        }
+        self
+    }
+}
+
+impl core::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.recursion {
+            return write!(f, "json_schema: {}", self.message);
+        }
+        write!(f, "json_schema: {} at $", self.message)?;
+        for segment in self.path.iter().rev() {
+            match segment {
+                Segment::Key(key) => write!(f, ".{key}")?,
+                Segment::Index(idx) => write!(f, "[{idx}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn describe_types(types: u8) -> String {
+    [
+        (TYPE_NULL, "null"),
+        (TYPE_BOOLEAN, "boolean"),
+        (TYPE_INTEGER, "integer"),
+        (TYPE_NUMBER, "number"),
+        (TYPE_STRING, "string"),
+        (TYPE_ARRAY, "array"),
+        (TYPE_OBJECT, "object"),
+    ]
+    .iter()
+    .filter(|(bit, _)| types & bit != 0)
+    .map(|(_, name)| *name)
+    .collect::<Vec<_>>()
+    .join(" or ")
+}
+
+impl JsonSchema {
+    /// Compile the schema `ptr`, a `dict` or `bool`.
+    #[cold]
+    pub fn new(ptr: *mut PyObject) -> Result<Self, String> {
+        let mut compiler = Compiler {
+            root: ptr,
+            nodes: Vec::new(),
+            refs: vec![(String::from("#"), 0)],
+        };
+        compiler.compile(ptr)?;
+        Ok(JsonSchema {
+            nodes: compiler.nodes,
+        })
+    }
+
+    /// Check the document `dumps()` wrote to `output` against the schema,
+    /// returning why it does not match.
+    #[cold]
+    pub fn check_output(&self, output: *mut PyObject) -> Result<(), String> {
+        let mut options = DeserializeOptions::default();
+        // `non_finite="literal"` writes `NaN` and `Infinity`
+        options.allow_nan = true;
+        let obj = deserialize(output, &options).map_err(|err| {
+            ffi!(PyErr_Clear());
+            format!("json_schema: output cannot be read: {}", err.message)
+        })?;
+        let ret = self.check(obj.as_ptr());
+        ffi!(Py_DECREF(obj.as_ptr()));
+        ret
+    }
+
+    /// Check `ptr` against the schema, returning why it does not match.
+    pub fn check(&self, ptr: *mut PyObject) -> Result<(), String> {
+        self.check_node(0, ptr, 0).map_err(|err| err.to_string())
+    }
+
+    fn check_node(&self, id: NodeId, ptr: *mut PyObject, depth: usize) -> Result<(), Mismatch> {
+        if depth > RECURSION_LIMIT {
+            let mut err = Mismatch::new(String::from("recursion limit reached"));
+            err.recursion = true;
+            return Err(err);
+        }
+        let keywords = match &self.nodes[id] {
+            Node::Bool(true) => return Ok(()),
+            Node::Bool(false) => return Err(Mismatch::new(String::from("value is not allowed"))),
+            Node::Keywords(keywords) => keywords,
+        };
+        let value = Value::of(ptr);
+        if let Value::Other = value {
+            return Ok(());
+        }
+        if let Some(reference) = keywords.reference {
+            self.check_node(reference, ptr, depth + 1)?;
+        }
+        if keywords.types != 0 && !value.has_type(keywords.types) {
+            return Err(Mismatch::new(format!(
+                "expected {}",
+                describe_types(keywords.types)
+            )));
+        }
+        if let Some(items) = &keywords.enumeration {
+            if !items.iter().any(|item| json_equal(ptr, *item, depth)) {
+                return Err(Mismatch::new(String::from("value is not one of enum")));
+            }
+        }
+        if let Some(constant) = keywords.constant {
+            if !json_equal(ptr, constant, depth) {
+                return Err(Mismatch::new(String::from("value is not const")));
+            }
+        }
+        match value {
+            Value::Number(val, _) => check_number(keywords, val)?,
+            Value::Str(val) => {
+                let len = val.chars().count();
+                if keywords.min_length.is_some_and(|min| len < min) {
+                    return Err(Mismatch::new(format!(
+                        "string is shorter than minLength {}",
+                        keywords.min_length.unwrap()
+                    )));
+                }
+                if keywords.max_length.is_some_and(|max| len > max) {
+                    return Err(Mismatch::new(format!(
+                        "string is longer than maxLength {}",
+                        keywords.max_length.unwrap()
+                    )));
+                }
+            }
+            Value::Array(len) => self.check_array(keywords, ptr, len, depth)?,
+            Value::Object => self.check_object(keywords, ptr, depth)?,
+            _ => {}
+        }
+        self.check_applicators(keywords, ptr, depth)
+    }
+
+    fn check_array(
+        &self,
+        keywords: &Keywords,
+        ptr: *mut PyObject,
+        len: usize,
+        depth: usize,
+    ) -> Result<(), Mismatch> {
+        if keywords.min_items.is_some_and(|min| len < min) {
+            return Err(Mismatch::new(format!(
+                "array has fewer than minItems {} items",
+                keywords.min_items.unwrap()
+            )));
+        }
+        if keywords.max_items.is_some_and(|max| len > max) {
+            return Err(Mismatch::new(format!(
+                "array has more than maxItems {} items",
+                keywords.max_items.unwrap()
+            )));
+        }
+        for idx in 0..len {
+            let node = match keywords.prefix_items.get(idx) {
+                Some(node) => *node,
+                None => match keywords.items {
+                    Some(node) => node,
+                    None => break,
+                },
+            };
+            self.check_node(node, array_item(ptr, idx), depth + 1)
+                .map_err(|err| err.at(Segment::Index(idx)))?;
+        }
+        if keywords.unique_items {
+            for idx in 1..len {
+                for other in 0..idx {
+                    if json_equal(array_item(ptr, idx), array_item(ptr, other), depth) {
+                        return Err(Mismatch::new(String::from(
+                            "array has items that are not unique",
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_object(
+        &self,
+        keywords: &Keywords,
+        ptr: *mut PyObject,
+        depth: usize,
+    ) -> Result<(), Mismatch> {
+        let len = isize_to_usize(ffi!(Py_SIZE(ptr)));
+        if keywords.min_properties.is_some_and(|min| len < min) {
+            return Err(Mismatch::new(format!(
+                "object has fewer than minProperties {} members",
+                keywords.min_properties.unwrap()
+            )));
+        }
+        if keywords.max_properties.is_some_and(|max| len > max) {
+            return Err(Mismatch::new(format!(
+                "object has more than maxProperties {} members",
+                keywords.max_properties.unwrap()
+            )));
+        }
+        for name in keywords.required.iter() {
+            if ffi!(PyDict_Contains(ptr, *name)) != 1 {
+                ffi!(PyErr_Clear());
+                return Err(Mismatch::new(format!(
+                    "missing required property \"{}\"",
+                    to_str(*name).unwrap_or_default()
+                )));
+            }
+        }
+        for (name, names) in keywords.dependent_required.iter() {
+            if ffi!(PyDict_Contains(ptr, *name)) != 1 {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            for required in names.iter() {
+                if ffi!(PyDict_Contains(ptr, *required)) != 1 {
+                    ffi!(PyErr_Clear());
+                    return Err(Mismatch::new(format!(
+                        "missing property \"{}\" required by \"{}\"",
+                        to_str(*required).unwrap_or_default(),
+                        to_str(*name).unwrap_or_default()
+                    )));
+                }
+            }
+        }
+        for (name, node) in keywords.properties.iter() {
+            let value = ffi!(PyDict_GetItem(ptr, *name));
+            if !value.is_null() {
+                self.check_node(*node, value, depth + 1).map_err(|err| {
+                    err.at(Segment::Key(String::from(
+                        to_str(*name).unwrap_or_default(),
+                    )))
+                })?;
+            }
+        }
+        if keywords.additional.is_none() && keywords.property_names.is_none() {
+            return Ok(());
+        }
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut value: *mut PyObject = null_mut();
+        while unsafe { crate::ffi::PyDict_Next(ptr, &mut pos, &mut key, &mut value) } != 0 {
+            // a key that is not a `str` is written by `OPT_NON_STR_KEYS`
+            let Some(key_str) = to_str(key) else {
+                continue;
+            };
+            if let Some(node) = keywords.property_names {
+                self.check_node(node, key, depth + 1).map_err(|err| {
+                    Mismatch::new(format!("property name \"{key_str}\": {}", err.message))
+                })?;
+            }
+            let Some(node) = keywords.additional else {
+                continue;
+            };
+            if keywords
+                .properties
+                .iter()
+                .any(|(name, _)| to_str(*name) == Some(key_str))
+            {
+                continue;
+            }
+            if let Node::Bool(false) = self.nodes[node] {
+                return Err(Mismatch::new(format!(
+                    "property \"{key_str}\" is not allowed"
+                )));
+            }
+            self.check_node(node, value, depth + 1)
+                .map_err(|err| err.at(Segment::Key(String::from(key_str))))?;
+        }
+        Ok(())
+    }
+
+    fn check_applicators(
+        &self,
+        keywords: &Keywords,
+        ptr: *mut PyObject,
+        depth: usize,
+    ) -> Result<(), Mismatch> {
+        for node in keywords.all_of.iter() {
+            self.check_node(*node, ptr, depth + 1)?;
+        }
+        if !keywords.any_of.is_empty()
+            && !keywords
+                .any_of
+                .iter()
+                .any(|node| self.check_node(*node, ptr, depth + 1).is_ok())
+        {
+            return Err(Mismatch::new(String::from(
+                "value does not match any of anyOf",
+            )));
+        }
+        if !keywords.one_of.is_empty() {
+            let matched = keywords
+                .one_of
+                .iter()
+                .filter(|node| self.check_node(**node, ptr, depth + 1).is_ok())
+                .count();
+            if matched == 0 {
+                return Err(Mismatch::new(String::from("value does not match oneOf")));
+            } else if matched > 1 {
+                return Err(Mismatch::new(String::from(
+                    "value matches more than one of oneOf",
+                )));
+            }
+        }
+        if let Some(node) = keywords.not {
+            if self.check_node(node, ptr, depth + 1).is_ok() {
+                return Err(Mismatch::new(String::from("value matches not")));
+            }
+        }
+        if let Some((condition, then, otherwise)) = keywords.if_then_else {
+            let branch = if self.check_node(condition, ptr, depth + 1).is_ok() {
+                then
+            } else {
+                otherwise
+            };
+            if let Some(node) = branch {
+                self.check_node(node, ptr, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_number(keywords: &Keywords, val: f64) -> Result<(), Mismatch> {
+    if let Some(min) = keywords.minimum {
+        if val < min {
+            return Err(Mismatch::new(format!("number is less than minimum {min}")));
+        }
+    }
+    if let Some(max) = keywords.maximum {
+        if val > max {
+            return Err(Mismatch::new(format!(
+                "number is greater than maximum {max}"
+            )));
+        }
+    }
+    if let Some(min) = keywords.exclusive_minimum {
+        if val <= min {
+            return Err(Mismatch::new(format!(
+                "number is not greater than exclusiveMinimum {min}"
+            )));
+        }
+    }
+    if let Some(max) = keywords.exclusive_maximum {
+        if val >= max {
+            return Err(Mismatch::new(format!(
+                "number is not less than exclusiveMaximum {max}"
+            )));
+        }
+    }
+    if let Some(divisor) = keywords.multiple_of {
+        let quotient = val / divisor;
+        // within rounding of the quotient, so that 0.3 is a multiple of 0.1
+        if !quotient.is_finite()
+            || (quotient - quotient.round()).abs() > 1e-9 * quotient.abs().max(1.0)
+        {
+            return Err(Mismatch::new(format!(
+                "number is not a multiple of multipleOf {divisor}"
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! The order `OPT_SORT_KEYS` writes keys in, set with `key_order=`.
+//!
+//! The orderings other than the default compare keys equal under them by
+//! codepoint, so that the output does not depend on the order of the dict.
+//! A comparator given as callable is installed for the duration of the call
+//! as the escape cache is.
+//!
+//! With `OPT_NON_STR_KEYS` and the default order, keys that were not `str`
+//! sort as their original value does: `int` and `float` numerically and
+//! `datetime.date` and `datetime.datetime` chronologically, before the
+//! other keys, which sort by the string written.
+
+use crate::ffi::PyObject;
+use crate::opt::{
+    KEY_ORDER, KEY_ORDER_CALLABLE, KEY_ORDER_CASE_INSENSITIVE, KEY_ORDER_NATURAL, Opt,
+};
+use crate::serialize::error::SerializeError;
+use crate::str::PyStr;
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::ptr::null_mut;
+
+thread_local! {
+    // The `key_order=` callable of the innermost call on this thread, or
+    // null outside of one.
+    static COMPARATOR: Cell<*mut PyObject> = const { Cell::new(null_mut()) };
+}
+
+/// Make `comparator` the comparator for the current thread and return the
+/// previous one, which must be restored with `restore()`.
+pub(crate) fn install(comparator: *mut PyObject) -> *mut PyObject {
+    COMPARATOR.with(|active| active.replace(comparator))
+}
+
+pub(crate) fn restore(previous: *mut PyObject) {
+    COMPARATOR.with(|active| active.set(previous));
+}
+
+/// Sort the items of a dict by key in the order `opts` selects.
+#[inline(never)]
+pub(crate) fn sort_items(
+    items: &mut [(&str, *mut PyObject)],
+    opts: Opt,
+) -> Result<(), SerializeError> {
+    match opts & KEY_ORDER {
+        0 => items.sort_unstable_by(|a, b| a.0.cmp(b.0)),
+        KEY_ORDER_CASE_INSENSITIVE => {
+            items.sort_unstable_by(|a, b| case_insensitive_cmp(a.0, b.0).then_with(|| a.0.cmp(b.0)))
+        }
+        KEY_ORDER_NATURAL => {
+            items.sort_unstable_by(|a, b| natural_cmp(a.0, b.0).then_with(|| a.0.cmp(b.0)));
+        }
+        KEY_ORDER_CALLABLE => return sort_by_comparator(items),
+        _ => unreachable_unchecked!(),
+    }
+    Ok(())
+}
+
+/// The order of a key as its original value sorts, used to sort the keys of
+/// a dict with `OPT_NON_STR_KEYS` in the default order.
+#[derive(Clone, Copy)]
+pub(crate) enum NaturalKey {
+    /// An `int` or `float`, with the exact value of an `int`.
+    Number(f64, Option<i128>),
+    /// A `date` or `datetime` in microseconds since 0001-01-01, at UTC if it
+    /// has a timezone and as if UTC if it is naive.
+    Instant(i64),
+    /// Any other key, which sorts by the string written.
+    Text,
+}
+
+impl NaturalKey {
+    fn rank(self) -> u8 {
+        match self {
+            NaturalKey::Number(..) => 0,
+            NaturalKey::Instant(_) => 1,
+            NaturalKey::Text => 2,
+        }
+    }
+
+    /// A total order: numbers equal as `f64` sort `float` before `int` and
+    /// `int` by its exact value, and ties are broken by the string written.
+    fn cmp(self, other: Self) -> Ordering {
+        match (self, other) {
+            (NaturalKey::Number(a, a_int), NaturalKey::Number(b, b_int)) => a
+                .total_cmp(&b)
+                .then_with(|| a_int.is_some().cmp(&b_int.is_some()))
+                .then_with(|| a_int.cmp(&b_int)),
+            (NaturalKey::Instant(a), NaturalKey::Instant(b)) => a.cmp(&b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+/// Sort the items of a dict with `OPT_NON_STR_KEYS` in the default order.
+#[inline(never)]
+pub(crate) fn sort_items_natural(items: &mut [(String, NaturalKey, *mut PyObject)]) {
+    items.sort_unstable_by(|a, b| a.1.cmp(b.1).then_with(|| a.0.cmp(&b.0)));
+}
+
+fn case_insensitive_cmp(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Compare runs of ASCII digits by their value and everything else by
+/// codepoint, so that `item2` is before `item10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (a_digits, a_rest) = split_digits(a);
+                let (b_digits, b_rest) = split_digits(b);
+                let a_value = trim_leading_zeros(a_digits);
+                let b_value = trim_leading_zeros(b_digits);
+                let ordering = a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(x), Some(y)) => {
+                // UTF-8 orders by codepoint bytewise
+                if x != y {
+                    return x.cmp(y);
+                }
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+    }
+}
+
+fn split_digits(val: &[u8]) -> (&[u8], &[u8]) {
+    let len = val.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    val.split_at(len)
+}
+
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|byte| **byte == b'0').count();
+    &digits[zeros..]
+}
+
+/// Sort with the installed comparator, which is called as `cmp(a, b)` and
+/// returns a negative, zero, or positive `int` as `functools.cmp_to_key()`
+/// expects. A merge sort is used as the comparator need not be consistent.
+#[cold]
+#[inline(never)]
+fn sort_by_comparator(items: &mut [(&str, *mut PyObject)]) -> Result<(), SerializeError> {
+    let comparator = COMPARATOR.with(Cell::get);
+    debug_assert!(!comparator.is_null());
+    let keys: Vec<PyStr> = items.iter().map(|item| PyStr::from_str(item.0)).collect();
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let mut failed = false;
+    merge_sort(&mut order, &mut |a, b| {
+        if failed {
+            return Ordering::Equal;
+        }
+        match call_comparator(comparator, keys[a].as_ptr(), keys[b].as_ptr()) {
+            Some(ordering) => ordering,
+            None => {
+                failed = true;
+                Ordering::Equal
+            }
+        }
+    });
+    for key in keys {
+        ffi!(Py_DECREF(key.as_ptr()));
+    }
+    if failed {
+        return Err(SerializeError::KeyOrderComparator);
+    }
+    let sorted: Vec<(&str, *mut PyObject)> = order.iter().map(|idx| items[*idx]).collect();
+    items.copy_from_slice(&sorted);
+    Ok(())
+}
+
+fn call_comparator(
+    comparator: *mut PyObject,
+    a: *mut PyObject,
+    b: *mut PyObject,
+) -> Option<Ordering> {
+    let result = ffi!(PyObject_CallFunctionObjArgs(
+        comparator,
+        a,
+        b,
+        null_mut::<PyObject>()
+    ));
+    if result.is_null() {
+        return None;
+    }
+    let ordering = if is_type!(ob_type!(result), crate::typeref::int_type_ptr()) {
+        // the sign of an int outside the range of i64 is that of overflow
+        let mut overflow = 0;
+        let val = ffi!(PyLong_AsLongLongAndOverflow(result, &raw mut overflow));
+        Some(if overflow == 0 {
+            val.cmp(&0)
+        } else {
+            overflow.cmp(&0)
+        })
+    } else {
+        None
+    };
+    ffi!(Py_DECREF(result));
+    ordering
+}
+
+fn merge_sort(order: &mut [usize], cmp: &mut impl FnMut(usize, usize) -> Ordering) {
+    if order.len() <= 1 {
+        return;
+    }
+    let mid = order.len() / 2;
+    merge_sort(&mut order[..mid], cmp);
+    merge_sort(&mut order[mid..], cmp);
+    let mut merged = Vec::with_capacity(order.len());
+    let (mut left, mut right) = (0, mid);
+    while left < mid && right < order.len() {
+        if cmp(order[right], order[left]) == Ordering::Less {
+            merged.push(order[right]);
+            right += 1;
+        } else {
+            merged.push(order[left]);
+            left += 1;
+        }
+    }
+    merged.extend_from_slice(&order[left..mid]);
+    merged.extend_from_slice(&order[right..]);
+    order.copy_from_slice(&merged);
+}
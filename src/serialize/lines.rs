@@ -0,0 +1,693 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.LinesWriter`, for appending newline-delimited JSON records to
+//! a file.
+//!
+//! Records are serialized into a buffer that is written out once it holds
+//! `buffer_size` bytes, on `flush()`, and on `close()`. With `max_bytes`, a
+//! record that would take the current file past the limit is preceded by a
+//! rotation: the records before it are written out, the file is closed, and
+//! `on_rotate` is called with the path or file object. It may return another
+//! path or file object to continue with.
+
+use crate::exception::{raise_dumps_exception_dynamic, raise_dumps_exception_fixed};
+use crate::ffi::{
+    METH_NOARGS, METH_O, METH_VARARGS, Py_TPFLAGS_DEFAULT, PyMethodDef, PyMethodDefPointer,
+    PyObject, PyType_Ready, PyType_Type, PyTypeObject, PyVarObject,
+};
+use crate::opt::{APPEND_CRLF, DEFAULT_PATH, ENSURE_ASCII, INDENT, Opt};
+use crate::runtime::Serializing;
+use crate::serialize::escape_cache;
+use crate::serialize::path::{self, DefaultPaths};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::{BytesWriter, WriteExt, to_writer};
+use crate::str::PyStr;
+use crate::util::usize_to_isize;
+use bytes::BufMut;
+use core::ffi::CStr;
+use core::ptr::{NonNull, null_mut};
+use std::io::Write;
+
+#[cfg(Py_GIL_DISABLED)]
+use crate::ffi::atomiculong::AtomicCULong;
+#[cfg(Py_GIL_DISABLED)]
+use core::sync::atomic::{AtomicIsize, AtomicU32};
+
+#[cfg(Py_GIL_DISABLED)]
+macro_rules! pymutex_new {
+    () => {
+        unsafe { core::mem::zeroed() }
+    };
+}
+
+const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+enum Target {
+    /// A file opened from a path, and the path as it was given.
+    Path(std::fs::File, NonNull<PyObject>),
+    /// A file object opened in binary mode.
+    Stream(NonNull<PyObject>),
+    Closed,
+}
+
+impl Target {
+    /// The path or file object, borrowed.
+    fn as_ptr(&self) -> *mut PyObject {
+        match self {
+            Target::Path(_, path) => path.as_ptr(),
+            Target::Stream(stream) => stream.as_ptr(),
+            Target::Closed => null_mut(),
+        }
+    }
+}
+
+enum LinesError {
+    Fixed(&'static str),
+    Dynamic(String),
+    /// The exception is set.
+    Raised,
+}
+
+fn raise(err: LinesError) -> *mut PyObject {
+    match err {
+        LinesError::Fixed(msg) => raise_dumps_exception_fixed(msg),
+        LinesError::Dynamic(msg) => raise_dumps_exception_dynamic(msg.as_str()),
+        LinesError::Raised => null_mut(),
+    }
+}
+
+/// Raise `OSError` for `err`, which `OSError.__new__()` maps to a subclass
+/// such as `FileNotFoundError` by its errno.
+#[cold]
+fn raise_os_error(err: &std::io::Error, filename: *mut PyObject) -> LinesError {
+    let msg = err.to_string();
+    // std appends " (os error N)" to the description
+    let msg = msg
+        .rsplit_once(" (os error ")
+        .map_or(msg.as_str(), |(desc, _)| desc);
+    let msg_obj = ffi!(PyUnicode_FromStringAndSize(
+        msg.as_ptr().cast::<core::ffi::c_char>(),
+        usize_to_isize(msg.len())
+    ));
+    unsafe {
+        match err.raw_os_error() {
+            Some(errno) => {
+                let args = crate::ffi::PyTuple_New(3);
+                crate::ffi::PyTuple_SET_ITEM(
+                    args,
+                    0,
+                    crate::ffi::PyLong_FromLongLong(errno.into()),
+                );
+                crate::ffi::PyTuple_SET_ITEM(args, 1, msg_obj);
+                ffi!(Py_INCREF(filename));
+                crate::ffi::PyTuple_SET_ITEM(args, 2, filename);
+                crate::ffi::PyErr_SetObject(crate::ffi::PyExc_OSError, args);
+                ffi!(Py_DECREF(args));
+            }
+            None => {
+                crate::ffi::PyErr_SetObject(crate::ffi::PyExc_OSError, msg_obj);
+                ffi!(Py_DECREF(msg_obj));
+            }
+        }
+    }
+    LinesError::Raised
+}
+
+/// Run `f` detached from the interpreter, e.g., for file I/O.
+fn allow_threads<T, F>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let tstate = ffi!(PyEval_SaveThread());
+    let res = f();
+    ffi!(PyEval_RestoreThread(tstate));
+    res
+}
+
+/// Open `obj`, a path or a file object, returning the number of bytes
+/// already in the file.
+#[cold]
+fn open_target(obj: *mut PyObject) -> Result<(Target, u64), LinesError> {
+    let fspath = ffi!(PyOS_FSPath(obj));
+    if fspath.is_null() {
+        ffi!(PyErr_Clear());
+        if ffi!(PyObject_HasAttr(obj, crate::typeref::get_write_str())) == 0 {
+            return Err(LinesError::Fixed(
+                "LinesWriter target must be a path or a file object with write()",
+            ));
+        }
+        ffi!(Py_INCREF(obj));
+        return Ok((Target::Stream(nonnull!(obj)), 0));
+    }
+    let res = open_path(obj, fspath);
+    ffi!(Py_DECREF(fspath));
+    res
+}
+
+fn open_path(obj: *mut PyObject, fspath: *mut PyObject) -> Result<(Target, u64), LinesError> {
+    if !is_type!(ob_type!(fspath), crate::typeref::str_type_ptr()) {
+        return Err(LinesError::Fixed(
+            "LinesWriter path must be str or os.PathLike[str]",
+        ));
+    }
+    let path = match unsafe { PyStr::from_ptr_unchecked(fspath).to_str() } {
+        Some(path) => path,
+        None => return Err(LinesError::Fixed(crate::util::INVALID_STR)),
+    };
+    let res = allow_threads(|| {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let len = file.metadata()?.len();
+        Ok((file, len))
+    });
+    match res {
+        Ok((file, len)) => {
+            ffi!(Py_INCREF(obj));
+            Ok((Target::Path(file, nonnull!(obj)), len))
+        }
+        Err(err) => Err(raise_os_error(&err, obj)),
+    }
+}
+
+#[repr(C)]
+pub(crate) struct LinesWriter {
+    ob_base: PyObject,
+    target: Target,
+    buffer: BytesWriter,
+    /// Bytes in the current file, not counting the buffer.
+    written: u64,
+    max_bytes: Option<u64>,
+    buffer_size: usize,
+    on_rotate: Option<NonNull<PyObject>>,
+    default: Option<NonNull<PyObject>>,
+    opts: Opt,
+    /// Set while a method runs, as writing may call back into Python.
+    busy: bool,
+}
+
+impl LinesWriter {
+    fn check_open(&self) -> Result<(), LinesError> {
+        if matches!(self.target, Target::Closed) {
+            return Err(LinesError::Fixed("LinesWriter is closed"));
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, value: *mut PyObject) -> Result<(), LinesError> {
+        self.check_open()?;
+        let before = self.buffer.len();
+        let opts = self.opts;
+        let _serializing = Serializing::enter();
+        let obj = PyObjectSerializer::new(value, SerializerState::new(opts), self.default);
+        let ensure_ascii = opt_enabled!(opts, ENSURE_ASCII);
+        let previous_cache = ensure_ascii.then(escape_cache::suspend);
+        let res = if opt_enabled!(opts, DEFAULT_PATH) {
+            let mut paths = DefaultPaths::new(value);
+            let previous = path::install(&mut paths);
+            let res = to_writer(&mut self.buffer, &obj, opts);
+            path::restore(previous);
+            res
+        } else {
+            to_writer(&mut self.buffer, &obj, opts)
+        };
+        if let Some(previous) = previous_cache {
+            escape_cache::restore(previous);
+        }
+        if let Err(err) = res {
+            self.buffer.truncate(before);
+            return Err(LinesError::Dynamic(err.to_string()));
+        }
+        let line_ending: &[u8] = if opt_enabled!(opts, APPEND_CRLF) {
+            b"\r\n"
+        } else {
+            b"\n"
+        };
+        let mut writer = &mut self.buffer;
+        writer.reserve(line_ending.len());
+        writer.put_slice(line_ending);
+
+        if let Some(max_bytes) = self.max_bytes {
+            let current = self.written + before as u64;
+            let record = (self.buffer.len() - before) as u64;
+            if current > 0 && current + record > max_bytes {
+                self.write_out(before)?;
+                self.rotate()?;
+            }
+        }
+        if self.buffer.len() >= self.buffer_size {
+            self.write_out(self.buffer.len())?;
+        }
+        Ok(())
+    }
+
+    /// Write the first `len` bytes of the buffer to the current file.
+    fn write_out(&mut self, len: usize) -> Result<(), LinesError> {
+        if len == 0 {
+            return Ok(());
+        }
+        let data = &self.buffer.as_slice()[..len];
+        match &mut self.target {
+            Target::Path(file, path) => {
+                if let Err(err) = allow_threads(|| file.write_all(data)) {
+                    return Err(raise_os_error(&err, path.as_ptr()));
+                }
+            }
+            Target::Stream(stream) => {
+                let bytes = ffi!(PyBytes_FromStringAndSize(
+                    data.as_ptr().cast::<core::ffi::c_char>(),
+                    usize_to_isize(len)
+                ));
+                if bytes.is_null() {
+                    return Err(LinesError::Raised);
+                }
+                let res = call_method!(stream.as_ptr(), crate::typeref::get_write_str(), bytes);
+                ffi!(Py_DECREF(bytes));
+                if res.is_null() {
+                    return Err(LinesError::Raised);
+                }
+                ffi!(Py_DECREF(res));
+            }
+            Target::Closed => unreachable!(),
+        }
+        self.buffer.consume(len);
+        self.written += len as u64;
+        Ok(())
+    }
+
+    /// Close the current file, call `on_rotate`, and open what it returns or,
+    /// if it returns `None`, the same path or file object again.
+    ///
+    /// If `on_rotate` raises, the writer is closed.
+    #[cold]
+    fn rotate(&mut self) -> Result<(), LinesError> {
+        let current = self.release_target();
+        let on_rotate = self.on_rotate.unwrap().as_ptr();
+        let res = ffi!(PyObject_CallFunctionObjArgs(
+            on_rotate,
+            current,
+            null_mut::<PyObject>()
+        ));
+        if res.is_null() {
+            ffi!(Py_DECREF(current));
+            return Err(LinesError::Raised);
+        }
+        let next = if core::ptr::eq(res, crate::typeref::get_none()) {
+            current
+        } else {
+            res
+        };
+        let opened = open_target(next);
+        ffi!(Py_DECREF(current));
+        ffi!(Py_DECREF(res));
+        let (target, written) = opened?;
+        self.target = target;
+        self.written = written;
+        Ok(())
+    }
+
+    /// Close the current file and return a new reference to its path or
+    /// file object.
+    fn release_target(&mut self) -> *mut PyObject {
+        match core::mem::replace(&mut self.target, Target::Closed) {
+            Target::Path(file, path) => {
+                drop(file);
+                path.as_ptr()
+            }
+            Target::Stream(stream) => stream.as_ptr(),
+            Target::Closed => unreachable!(),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), LinesError> {
+        self.check_open()?;
+        self.write_out(self.buffer.len())
+    }
+
+    /// Write out the buffer and close the file. A file object passed in is
+    /// not closed.
+    fn close(&mut self) -> Result<(), LinesError> {
+        if matches!(self.target, Target::Closed) {
+            return Ok(());
+        }
+        let res = self.write_out(self.buffer.len());
+        self.buffer.truncate(0);
+        ffi!(Py_DECREF(self.release_target()));
+        res
+    }
+}
+
+#[inline(always)]
+unsafe fn none() -> *mut PyObject {
+    use_immortal!(crate::typeref::get_none())
+}
+
+/// Run `f` on the writer unless another call on it is in progress.
+#[inline(always)]
+unsafe fn with_writer<F>(slf: *mut PyObject, f: F) -> *mut PyObject
+where
+    F: FnOnce(&mut LinesWriter) -> Result<(), LinesError>,
+{
+    unsafe {
+        let writer = &mut *slf.cast::<LinesWriter>();
+        if writer.busy {
+            return raise_dumps_exception_fixed("LinesWriter is already writing");
+        }
+        writer.busy = true;
+        let res = f(&mut *writer);
+        writer.busy = false;
+        match res {
+            Ok(()) => none(),
+            Err(err) => raise(err),
+        }
+    }
+}
+
+unsafe extern "C" fn lines_writer_write(slf: *mut PyObject, value: *mut PyObject) -> *mut PyObject {
+    unsafe { with_writer(slf, |writer| writer.write(value)) }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_flush(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    unsafe { with_writer(slf, LinesWriter::flush) }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_close(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    unsafe { with_writer(slf, LinesWriter::close) }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_enter(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    ffi!(Py_INCREF(slf));
+    slf
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_exit(slf: *mut PyObject, _args: *mut PyObject) -> *mut PyObject {
+    unsafe { with_writer(slf, LinesWriter::close) }
+}
+
+fn kwarg_is(key: *mut PyObject, name: &CStr) -> bool {
+    ffi!(PyUnicode_CompareWithASCIIString(key, name.as_ptr())) == 0
+}
+
+/// Parse a positive `int` argument.
+fn positive_int(value: *mut PyObject) -> Option<u64> {
+    if !is_type!(ob_type!(value), crate::typeref::int_type_ptr()) {
+        return None;
+    }
+    let val = ffi!(PyLong_AsLongLong(value));
+    if val == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        ffi!(PyErr_Clear());
+        return None;
+    }
+    u64::try_from(val).ok().filter(|val| *val > 0)
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_tp_new(
+    subtype: *mut PyTypeObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let mut max_bytes: Option<u64> = None;
+        let mut buffer_size = DEFAULT_BUFFER_SIZE;
+        let mut on_rotate: Option<NonNull<PyObject>> = None;
+        let mut default: Option<NonNull<PyObject>> = None;
+        let mut opts: Opt = 0;
+
+        if ffi!(Py_SIZE(args)) != 1 {
+            return raise_dumps_exception_fixed(
+                "LinesWriter() takes exactly 1 positional argument",
+            );
+        }
+        let target_obj = crate::ffi::PyTuple_GET_ITEM(args, 0);
+        if !kwds.is_null() {
+            let mut pos = 0;
+            let mut key: *mut PyObject = null_mut();
+            let mut value: *mut PyObject = null_mut();
+            while crate::ffi::PyDict_Next(kwds, &mut pos, &mut key, &mut value) != 0 {
+                let is_none = core::ptr::eq(value, crate::typeref::get_none());
+                if kwarg_is(key, c"max_bytes") {
+                    if !is_none {
+                        match positive_int(value) {
+                            Some(val) => max_bytes = Some(val),
+                            None => {
+                                return raise_dumps_exception_fixed(
+                                    "LinesWriter() max_bytes must be a positive int",
+                                );
+                            }
+                        }
+                    }
+                } else if kwarg_is(key, c"buffer_size") {
+                    match positive_int(value).and_then(|val| usize::try_from(val).ok()) {
+                        Some(val) => buffer_size = val,
+                        None => {
+                            return raise_dumps_exception_fixed(
+                                "LinesWriter() buffer_size must be a positive int",
+                            );
+                        }
+                    }
+                } else if kwarg_is(key, c"on_rotate") {
+                    if !is_none {
+                        if crate::ffi::PyCallable_Check(value) == 0 {
+                            return raise_dumps_exception_fixed(
+                                "LinesWriter() on_rotate must be callable",
+                            );
+                        }
+                        on_rotate = NonNull::new(value);
+                    }
+                } else if kwarg_is(key, c"default") {
+                    if !is_none {
+                        default = NonNull::new(value);
+                    }
+                } else if kwarg_is(key, c"option") {
+                    if !is_none {
+                        match crate::opt::opts_from_pyobject(value) {
+                            Some(val) => opts = val,
+                            None => return raise_dumps_exception_fixed("Invalid opts"),
+                        }
+                    }
+                } else {
+                    return raise_dumps_exception_fixed(
+                        "LinesWriter() got an unexpected keyword argument",
+                    );
+                }
+            }
+        }
+        if opt_enabled!(opts, INDENT) {
+            return raise_dumps_exception_fixed("LinesWriter does not support indented output");
+        }
+        if max_bytes.is_some() && on_rotate.is_none() {
+            return raise_dumps_exception_fixed("LinesWriter() max_bytes requires on_rotate");
+        }
+        crate::stats::record(crate::stats::Entry::LinesWriter, opts);
+
+        let (target, written) = match open_target(target_obj) {
+            Ok(opened) => opened,
+            Err(err) => return raise(err),
+        };
+        let obj = crate::ffi::PyType_GenericAlloc(subtype, 0);
+        if obj.is_null() {
+            return null_mut();
+        }
+        if let Some(ptr) = on_rotate {
+            ffi!(Py_INCREF(ptr.as_ptr()));
+        }
+        if let Some(ptr) = default {
+            ffi!(Py_INCREF(ptr.as_ptr()));
+        }
+        let writer = obj.cast::<LinesWriter>();
+        core::ptr::write(&raw mut (*writer).target, target);
+        core::ptr::write(&raw mut (*writer).buffer, BytesWriter::default());
+        (*writer).written = written;
+        (*writer).max_bytes = max_bytes;
+        (*writer).buffer_size = buffer_size;
+        (*writer).on_rotate = on_rotate;
+        (*writer).default = default;
+        (*writer).opts = opts;
+        (*writer).busy = false;
+        obj
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn lines_writer_dealloc(object: *mut PyObject) {
+    unsafe {
+        let writer = &mut *object.cast::<LinesWriter>();
+        if !matches!(writer.target, Target::Closed) {
+            if writer.buffer.len() > 0 {
+                // records that were not flushed are written, as a file would
+                #[cfg(Py_3_12)]
+                let exc = crate::ffi::PyErr_GetRaisedException();
+                #[cfg(not(Py_3_12))]
+                let (mut tp, mut val, mut tb) = (null_mut(), null_mut(), null_mut());
+                #[cfg(not(Py_3_12))]
+                crate::ffi::PyErr_Fetch(&mut tp, &mut val, &mut tb);
+                if writer.write_out(writer.buffer.len()).is_err() {
+                    crate::ffi::PyErr_WriteUnraisable(writer.target.as_ptr());
+                }
+                #[cfg(Py_3_12)]
+                crate::ffi::PyErr_SetRaisedException(exc);
+                #[cfg(not(Py_3_12))]
+                crate::ffi::PyErr_Restore(tp, val, tb);
+            }
+            ffi!(Py_DECREF(writer.release_target()));
+        }
+        writer.buffer.abort();
+        if let Some(ptr) = writer.on_rotate {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+        }
+        if let Some(ptr) = writer.default {
+            ffi!(Py_DECREF(ptr.as_ptr()));
+        }
+        crate::ffi::PyObject_Free(object.cast::<core::ffi::c_void>());
+    }
+}
+
+macro_rules! method_def {
+    ($name:expr, $func:ident, $flags:expr, $doc:expr) => {
+        PyMethodDef {
+            ml_name: $name.as_ptr(),
+            ml_meth: PyMethodDefPointer { PyCFunction: $func },
+            ml_flags: $flags,
+            ml_doc: $doc.as_ptr(),
+        }
+    };
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) unsafe fn lines_writer_type_new() -> *mut PyTypeObject {
+    unsafe {
+        let methods = Box::new([
+            method_def!(
+                c"write",
+                lines_writer_write,
+                METH_O,
+                c"Serialize a value as the next record."
+            ),
+            method_def!(
+                c"flush",
+                lines_writer_flush,
+                METH_NOARGS,
+                c"Write buffered records to the file."
+            ),
+            method_def!(
+                c"close",
+                lines_writer_close,
+                METH_NOARGS,
+                c"Write buffered records and close the file."
+            ),
+            method_def!(c"__enter__", lines_writer_enter, METH_NOARGS, c""),
+            method_def!(c"__exit__", lines_writer_exit, METH_VARARGS, c""),
+            PyMethodDef::zeroed(),
+        ]);
+
+        #[cfg(Py_GIL_DISABLED)]
+        let tp_flags: AtomicCULong =
+            AtomicCULong::new(Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE);
+        #[cfg(all(Py_3_10, not(Py_GIL_DISABLED)))]
+        let tp_flags: core::ffi::c_ulong =
+            Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE;
+        #[cfg(not(Py_3_10))]
+        let tp_flags: core::ffi::c_ulong = Py_TPFLAGS_DEFAULT;
+        let ob = Box::new(PyTypeObject {
+            ob_base: PyVarObject {
+                ob_base: PyObject {
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_tid: 0,
+                    #[cfg(all(Py_GIL_DISABLED, Py_3_14))]
+                    ob_flags: 0,
+                    #[cfg(all(Py_GIL_DISABLED, not(Py_3_14)))]
+                    _padding: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_mutex: pymutex_new!(),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_gc_bits: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_local: AtomicU32::new(crate::ffi::compat::_Py_IMMORTAL_REFCNT_LOCAL),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_shared: AtomicIsize::new(0),
+                    #[cfg(all(Py_3_12, not(Py_GIL_DISABLED)))]
+                    ob_refcnt: crate::ffi::PyObjectObRefcnt { ob_refcnt: 0 },
+                    #[cfg(not(Py_3_12))]
+                    ob_refcnt: 0,
+                    #[cfg(PyPy)]
+                    ob_pypy_link: 0,
+                    ob_type: &raw mut PyType_Type,
+                },
+                #[cfg(not(GraalPy))]
+                ob_size: 0,
+                #[cfg(GraalPy)]
+                _ob_size_graalpy: 0,
+            },
+            tp_name: c"hyperjson.LinesWriter".as_ptr(),
+            tp_basicsize: core::mem::size_of::<LinesWriter>() as isize,
+            tp_itemsize: 0,
+            tp_dealloc: Some(lines_writer_dealloc),
+            tp_init: None,
+            tp_new: Some(lines_writer_tp_new),
+            tp_flags: tp_flags,
+            // ...
+            tp_bases: null_mut(),
+            tp_cache: null_mut(),
+            tp_del: None,
+            tp_finalize: None,
+            tp_free: None,
+            tp_is_gc: None,
+            tp_mro: null_mut(),
+            tp_subclasses: null_mut(),
+            tp_vectorcall: None,
+            tp_version_tag: 0,
+            tp_weaklist: null_mut(),
+            tp_vectorcall_offset: 0,
+            tp_getattr: None,
+            tp_setattr: None,
+            tp_as_async: null_mut(),
+            tp_repr: None,
+            tp_as_number: null_mut(),
+            tp_as_sequence: null_mut(),
+            tp_as_mapping: null_mut(),
+            tp_hash: None,
+            tp_call: None,
+            tp_str: None,
+            tp_getattro: None,
+            tp_setattro: None,
+            tp_as_buffer: null_mut(),
+            tp_doc: c"LinesWriter(target, /, *, max_bytes=None, on_rotate=None, buffer_size=65536, default=None, option=None)\n--\n\nAppend newline-delimited JSON records to a file."
+                .as_ptr(),
+            tp_traverse: None,
+            tp_clear: None,
+            tp_richcompare: None,
+            tp_weaklistoffset: 0,
+            tp_iter: None,
+            tp_iternext: None,
+            tp_methods: Box::into_raw(methods).cast::<PyMethodDef>(),
+            tp_members: null_mut(),
+            tp_getset: null_mut(),
+            tp_base: null_mut(),
+            tp_dict: null_mut(),
+            tp_descr_get: None,
+            tp_descr_set: None,
+            tp_dictoffset: 0,
+            tp_alloc: None,
+            #[cfg(Py_3_12)]
+            tp_watched: 0,
+        });
+        let ob_ptr = Box::into_raw(ob);
+        PyType_Ready(ob_ptr);
+        ob_ptr
+    }
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.merge3()`, a three-way merge of documents.
+//!
+//! A value changed on one side only takes that side's value, and one changed
+//! the same way on both sides is kept. Objects changed on both sides are
+//! merged member by member. Any other value changed on both sides is a
+//! conflict: the merge keeps `ours` and reports the path and the three
+//! values. Arrays are merged as whole values.
+
+use crate::ffi::PyObject;
+use crate::serialize::equals::{equal, read};
+use crate::str::PyStr;
+
+use core::ptr::null_mut;
+
+fn is_dict(ptr: *mut PyObject) -> bool {
+    !ptr.is_null() && is_type!(ob_type!(ptr), crate::typeref::dict_type_ptr())
+}
+
+/// `ptr` as a new reference, which may be null for a missing value.
+fn keep(ptr: *mut PyObject) -> *mut PyObject {
+    ffi!(Py_XINCREF(ptr));
+    ptr
+}
+
+/// The JSON Pointer of `key` within `path`.
+fn child_path(path: &str, key: *mut PyObject) -> String {
+    let key = unsafe { PyStr::from_ptr_unchecked(key).to_str() }.unwrap_or("");
+    format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+struct Merge {
+    /// A `list` of a `dict` for each conflict.
+    conflicts: *mut PyObject,
+}
+
+impl Merge {
+    fn conflict(
+        &self,
+        path: &str,
+        base: *mut PyObject,
+        ours: *mut PyObject,
+        theirs: *mut PyObject,
+    ) -> Result<(), ()> {
+        let entry = ffi!(PyDict_New());
+        let path = ffi!(PyUnicode_FromStringAndSize(
+            path.as_ptr().cast::<core::ffi::c_char>(),
+            crate::util::usize_to_isize(path.len())
+        ));
+        let mut ret = ffi!(PyDict_SetItemString(entry, c"path".as_ptr(), path));
+        ffi!(Py_DECREF(path));
+        for (name, value) in [(c"base", base), (c"ours", ours), (c"theirs", theirs)] {
+            // a side that does not have the member is left out
+            if ret == 0 && !value.is_null() {
+                ret = ffi!(PyDict_SetItemString(entry, name.as_ptr(), value));
+            }
+        }
+        if ret == 0 {
+            ret = ffi!(PyList_Append(self.conflicts, entry));
+        }
+        ffi!(Py_DECREF(entry));
+        if ret == -1 { Err(()) } else { Ok(()) }
+    }
+
+    /// A new reference to the merge of the values at `path`, or null if the
+    /// merge does not have the member. Each value is null where that side
+    /// does not have it.
+    fn merge(
+        &self,
+        path: &str,
+        base: *mut PyObject,
+        ours: *mut PyObject,
+        theirs: *mut PyObject,
+    ) -> Result<*mut PyObject, ()> {
+        let same = |a: *mut PyObject, b: *mut PyObject| {
+            a.is_null() && b.is_null() || !a.is_null() && !b.is_null() && equal(a, b)
+        };
+        if same(ours, theirs) || same(base, theirs) {
+            return Ok(keep(ours));
+        }
+        if same(base, ours) {
+            return Ok(keep(theirs));
+        }
+        if is_dict(ours) && is_dict(theirs) && (base.is_null() || is_dict(base)) {
+            return self.merge_objects(path, base, ours, theirs);
+        }
+        self.conflict(path, base, ours, theirs)?;
+        Ok(keep(ours))
+    }
+
+    fn merge_objects(
+        &self,
+        path: &str,
+        base: *mut PyObject,
+        ours: *mut PyObject,
+        theirs: *mut PyObject,
+    ) -> Result<*mut PyObject, ()> {
+        let get = |dict: *mut PyObject, key: *mut PyObject| {
+            if dict.is_null() {
+                null_mut()
+            } else {
+                ffi!(PyDict_GetItem(dict, key))
+            }
+        };
+        let result = ffi!(PyDict_New());
+        // the keys of ours in order, then those only theirs has
+        for (side, other) in [(ours, null_mut()), (theirs, ours)] {
+            let mut pos = 0;
+            let mut key: *mut PyObject = null_mut();
+            let mut value: *mut PyObject = null_mut();
+            while ffi!(PyDict_Next(side, &mut pos, &mut key, &mut value)) == 1 {
+                if !get(other, key).is_null() {
+                    continue;
+                }
+                let merged = self.merge(
+                    &child_path(path, key),
+                    get(base, key),
+                    get(ours, key),
+                    get(theirs, key),
+                );
+                let ret = match merged {
+                    Ok(merged) if merged.is_null() => 0,
+                    Ok(merged) => {
+                        let ret = ffi!(PyDict_SetItem(result, key, merged));
+                        ffi!(Py_DECREF(merged));
+                        ret
+                    }
+                    Err(()) => -1,
+                };
+                if ret == -1 {
+                    ffi!(Py_DECREF(result));
+                    return Err(());
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// `merge3(base, ours, theirs, /)`: return a `tuple` of the merge of the
+/// changes `ours` and `theirs` make to `base` and a `list` of the conflicts.
+/// Each argument is read as by `equals()`.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn merge3(
+    base: *mut PyObject,
+    ours: *mut PyObject,
+    theirs: *mut PyObject,
+) -> *mut PyObject {
+    let mut docs = [null_mut(); 3];
+    for (idx, ptr) in [base, ours, theirs].into_iter().enumerate() {
+        docs[idx] = read(ptr);
+        if docs[idx].is_null() {
+            for doc in docs {
+                ffi!(Py_XDECREF(doc));
+            }
+            return null_mut();
+        }
+    }
+    let merge = Merge {
+        conflicts: ffi!(PyList_New(0)),
+    };
+    let merged = merge.merge("", docs[0], docs[1], docs[2]);
+    for doc in docs {
+        ffi!(Py_DECREF(doc));
+    }
+    match merged {
+        Ok(merged) => {
+            let ret = ffi!(PyTuple_New(2));
+            ffi!(PyTuple_SET_ITEM(ret, 0, merged));
+            ffi!(PyTuple_SET_ITEM(ret, 1, merge.conflicts));
+            ret
+        }
+        Err(()) => {
+            ffi!(Py_DECREF(merge.conflicts));
+            null_mut()
+        }
+    }
+}
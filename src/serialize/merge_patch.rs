@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.merge_patch()`, JSON Merge Patch as in RFC 7386.
+
+use crate::deserialize::{DeserializeOptions, deserialize};
+use crate::exception::{raise_dumps_exception_dynamic, raise_dumps_exception_fixed};
+use crate::ffi::PyObject;
+use crate::serialize::serializer::serialize;
+
+use core::ptr::null_mut;
+
+const RECURSION_LIMIT: usize = 1024;
+
+/// Whether `ptr` is read as a document rather than used as a value.
+fn is_document(ptr: *mut PyObject) -> bool {
+    let ob_type = ob_type!(ptr);
+    is_type!(ob_type, crate::typeref::bytes_type_ptr())
+        || is_type!(ob_type, crate::typeref::bytearray_type_ptr())
+        || is_type!(ob_type, crate::typeref::memoryview_type_ptr())
+}
+
+/// A new reference to `ptr` deserialized if it is a document, or to `ptr`.
+fn read(ptr: *mut PyObject) -> *mut PyObject {
+    if is_document(ptr) {
+        match deserialize(ptr, &DeserializeOptions::default()) {
+            Ok(obj) => obj.as_ptr(),
+            Err(err) => crate::exception::raise_loads_exception(err),
+        }
+    } else {
+        ffi!(Py_INCREF(ptr));
+        ptr
+    }
+}
+
+fn is_dict(ptr: *mut PyObject) -> bool {
+    is_subclass_by_flag!(tp_flags!(ob_type!(ptr)), Py_TPFLAGS_DICT_SUBCLASS)
+}
+
+/// A new reference to `target` with `patch` applied. Neither is modified:
+/// each object of the result that the patch changes is a new `dict`, and the
+/// values that are not objects are shared with `target` or `patch`.
+fn apply(target: *mut PyObject, patch: *mut PyObject, depth: usize) -> Result<*mut PyObject, ()> {
+    if !is_dict(patch) {
+        ffi!(Py_INCREF(patch));
+        return Ok(patch);
+    }
+    if depth > RECURSION_LIMIT {
+        cold_path!();
+        raise_dumps_exception_fixed("merge_patch() exceeds recursion limit");
+        return Err(());
+    }
+    let result = if !target.is_null() && is_dict(target) {
+        ffi!(PyDict_Copy(target))
+    } else {
+        ffi!(PyDict_New())
+    };
+    if result.is_null() {
+        return Err(());
+    }
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while ffi!(PyDict_Next(patch, &mut pos, &mut key, &mut value)) == 1 {
+        let ret = if core::ptr::eq(value, crate::typeref::get_none()) {
+            if ffi!(PyDict_Contains(result, key)) == 1 {
+                ffi!(PyDict_DelItem(result, key))
+            } else {
+                0
+            }
+        } else {
+            match apply(ffi!(PyDict_GetItem(result, key)), value, depth + 1) {
+                Ok(merged) => {
+                    let ret = ffi!(PyDict_SetItem(result, key, merged));
+                    ffi!(Py_DECREF(merged));
+                    ret
+                }
+                Err(()) => -1,
+            }
+        };
+        if ret == -1 {
+            cold_path!();
+            ffi!(Py_DECREF(result));
+            return Err(());
+        }
+    }
+    Ok(result)
+}
+
+/// `merge_patch(target, patch, /)`: apply `patch` to `target`. Either may
+/// be a document as `bytes`, `bytearray`, or `memoryview`, which is
+/// deserialized, or an object. The result is serialized if `target` is a
+/// document.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn merge_patch(target: *mut PyObject, patch: *mut PyObject) -> *mut PyObject {
+    let target_obj = read(target);
+    if target_obj.is_null() {
+        return null_mut();
+    }
+    let patch_obj = read(patch);
+    if patch_obj.is_null() {
+        ffi!(Py_DECREF(target_obj));
+        return null_mut();
+    }
+    let merged = apply(target_obj, patch_obj, 0);
+    ffi!(Py_DECREF(target_obj));
+    ffi!(Py_DECREF(patch_obj));
+    let Ok(merged) = merged else {
+        return null_mut();
+    };
+    if !is_document(target) {
+        return merged;
+    }
+    let ret = serialize(merged, None, 0, None, 0, None, None);
+    ffi!(Py_DECREF(merged));
+    match ret {
+        Ok(bytes) => bytes.as_ptr(),
+        Err(err) => raise_dumps_exception_dynamic(err.as_str()),
+    }
+}
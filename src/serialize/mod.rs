@@ -1,12 +1,49 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2021-2025)
 
+mod base64;
+mod batch;
 mod buffer;
+mod builder;
+mod digest;
+mod edit;
+mod equals;
 mod error;
+mod escape_cache;
+mod flatten;
+mod join;
+mod json_schema;
+mod key_order;
+mod lines;
+mod merge3;
+mod merge_patch;
+mod number_format;
 mod obtype;
+mod path;
 mod per_type;
+mod registry;
+mod schema;
 mod serializer;
+mod snapshot;
 mod state;
 pub(crate) mod writer;
 
-pub(crate) use serializer::serialize;
+pub(crate) use batch::serialize_batch;
+pub(crate) use builder::builder_type_new;
+pub(crate) use digest::{compare_digest_json, hash_json};
+pub(crate) use edit::{edit, editor_type_new, pointer, splice};
+pub(crate) use equals::equals;
+pub(crate) use flatten::{flatten, unflatten};
+pub(crate) use join::{join_array, join_object};
+pub(crate) use json_schema::JsonSchema;
+pub(crate) use lines::lines_writer_type_new;
+pub(crate) use merge_patch::merge_patch;
+pub(crate) use merge3::merge3;
+pub(crate) use per_type::{
+    NPY_ARRAY_NOTSWAPPED, PyArrayInterface, PyCapsule, civil_from_days, days_since_epoch,
+};
+pub(crate) use registry::{
+    register_deserializer, register_serializer, register_tagged, register_type,
+};
+pub(crate) use schema::schema_type_new;
+pub(crate) use serializer::{serialize, serialize_length, serialize_msgpack};
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! How finite floats are written, set with `float_format=`.
+//!
+//! `".Nf"` writes `N` digits after the decimal point, as `format()` does,
+//! and a callable is given each `float` and returns the text of the number.
+//! The callable is installed for the duration of the call as the `key_order=`
+//! comparator is.
+
+use crate::ffi::PyObject;
+use crate::opt::{FLOAT_FORMAT_CALLABLE, Opt, float_format_fixed};
+use crate::serialize::error::SerializeError;
+use crate::str::PyStr;
+use core::cell::Cell;
+use core::ptr::null_mut;
+use std::io::Write;
+
+thread_local! {
+    // The `float_format=` callable of the innermost call on this thread, or
+    // null outside of one.
+    static FORMATTER: Cell<*mut PyObject> = const { Cell::new(null_mut()) };
+}
+
+/// Make `formatter` the `float_format=` callable for the current thread and
+/// return the previous one, which must be restored with `restore()`.
+pub(crate) fn install(formatter: *mut PyObject) -> *mut PyObject {
+    FORMATTER.with(|active| active.replace(formatter))
+}
+
+pub(crate) fn restore(previous: *mut PyObject) {
+    FORMATTER.with(|active| active.set(previous));
+}
+
+/// Writes a finite float in place of its shortest representation.
+pub(crate) trait NumberFormat {
+    /// Append `value` to `buf` as a JSON number.
+    fn write_f64(&self, value: f64, buf: &mut Vec<u8>) -> Result<(), SerializeError>;
+}
+
+/// `".Nf"`: a fixed number of digits after the decimal point, e.g., `1.50`.
+pub(crate) struct FixedDecimals(pub u8);
+
+impl NumberFormat for FixedDecimals {
+    fn write_f64(&self, value: f64, buf: &mut Vec<u8>) -> Result<(), SerializeError> {
+        let _ = write!(buf, "{:.*}", usize::from(self.0), value);
+        Ok(())
+    }
+}
+
+/// A callable returning a `str` that must be a JSON number.
+pub(crate) struct CallableFormat(pub *mut PyObject);
+
+impl NumberFormat for CallableFormat {
+    fn write_f64(&self, value: f64, buf: &mut Vec<u8>) -> Result<(), SerializeError> {
+        let arg = ffi!(PyFloat_FromDouble(value));
+        let result = ffi!(PyObject_CallFunctionObjArgs(
+            self.0,
+            arg,
+            null_mut::<PyObject>()
+        ));
+        ffi!(Py_DECREF(arg));
+        if result.is_null() {
+            // chained as the cause
+            return Err(SerializeError::FloatFormat);
+        }
+        let ret = if is_type!(ob_type!(result), crate::typeref::str_type_ptr()) {
+            match unsafe { PyStr::from_ptr_unchecked(result).to_str() } {
+                Some(text) if is_json_number(text.as_bytes()) => {
+                    buf.extend_from_slice(text.as_bytes());
+                    Ok(())
+                }
+                _ => Err(SerializeError::FloatFormat),
+            }
+        } else {
+            Err(SerializeError::FloatFormat)
+        };
+        ffi!(Py_DECREF(result));
+        ret
+    }
+}
+
+/// Append finite `value` to `buf` as the `float_format=` in `opts` writes it.
+#[cold]
+#[inline(never)]
+pub(crate) fn write_f64(opts: Opt, value: f64, buf: &mut Vec<u8>) -> Result<(), SerializeError> {
+    if opt_enabled!(opts, FLOAT_FORMAT_CALLABLE) {
+        let formatter = FORMATTER.with(Cell::get);
+        debug_assert!(!formatter.is_null());
+        CallableFormat(formatter).write_f64(value, buf)
+    } else {
+        FixedDecimals(float_format_fixed(opts)).write_f64(value, buf)
+    }
+}
+
+/// Whether `text` is a number as RFC 8259 has it.
+pub(crate) fn is_json_number(text: &[u8]) -> bool {
+    let digits = |pos: usize| {
+        text[pos..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+    let mut pos = usize::from(text.first() == Some(&b'-'));
+    match text.get(pos) {
+        Some(b'0') => pos += 1,
+        Some(b'1'..=b'9') => pos += digits(pos),
+        _ => return false,
+    }
+    if text.get(pos) == Some(&b'.') {
+        let len = digits(pos + 1);
+        if len == 0 {
+            return false;
+        }
+        pos += 1 + len;
+    }
+    if let Some(b'e' | b'E') = text.get(pos) {
+        pos += 1;
+        if let Some(b'+' | b'-') = text.get(pos) {
+            pos += 1;
+        }
+        let len = digits(pos);
+        if len == 0 {
+            return false;
+        }
+        pos += len;
+    }
+    pos == text.len()
+}
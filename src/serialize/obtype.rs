@@ -2,9 +2,13 @@
 // Copyright ijl (2020-2025), Aviram Hassan (2020)
 
 use crate::opt::{
-    Opt, PASSTHROUGH_DATACLASS, PASSTHROUGH_DATETIME, PASSTHROUGH_SUBCLASS, SERIALIZE_NUMPY,
+    EXTENDED_JSON, MSGPACK, NAMEDTUPLE_AS_OBJECT, Opt, PANDAS_ORIENT, PASSTHROUGH_DATACLASS,
+    PASSTHROUGH_DATETIME, PASSTHROUGH_SUBCLASS, SERIALIZE_ARROW, SERIALIZE_NUMPY, TAGGED,
 };
-use crate::serialize::per_type::{is_numpy_array, is_numpy_scalar};
+use crate::serialize::per_type::{
+    is_array_like, is_arrow, is_bytes_like, is_dlpack, is_numpy_array, is_numpy_scalar, is_pandas,
+};
+use crate::serialize::registry::lookup_tag;
 // Type constants now accessed via typeref accessor functions
 
 #[repr(u32)]
@@ -24,9 +28,16 @@ pub(crate) enum ObType {
     Dataclass,
     NumpyScalar,
     NumpyArray,
+    Pandas,
+    Arrow,
     Enum,
     StrSubclass,
     Fragment,
+    RawNumber,
+    NamedTuple,
+    Tagged,
+    ExtendedJson,
+    Bytes,
     Unknown,
 }
 
@@ -40,13 +51,21 @@ pub(crate) fn pyobject_to_obtype(
     if is_class_by_type!(ob_type, crate::typeref::str_type_ptr()) {
         ObType::Str
     } else if is_class_by_type!(ob_type, crate::typeref::int_type_ptr()) {
-        ObType::Int
+        if opt_enabled!(opts, EXTENDED_JSON) {
+            ObType::ExtendedJson
+        } else {
+            ObType::Int
+        }
     } else if is_class_by_type!(ob_type, crate::typeref::bool_type_ptr()) {
         ObType::Bool
     } else if is_class_by_type!(ob_type, crate::typeref::none_type_ptr()) {
         ObType::None
     } else if is_class_by_type!(ob_type, crate::typeref::float_type_ptr()) {
-        ObType::Float
+        if opt_enabled!(opts, EXTENDED_JSON) {
+            ObType::ExtendedJson
+        } else {
+            ObType::Float
+        }
     } else if is_class_by_type!(ob_type, crate::typeref::list_type_ptr()) {
         ObType::List
     } else if is_class_by_type!(ob_type, crate::typeref::dict_type_ptr()) {
@@ -56,7 +75,11 @@ pub(crate) fn pyobject_to_obtype(
         crate::typeref::get_datetime_type_from_state(interpreter_state)
     ) && opt_disabled!(opts, PASSTHROUGH_DATETIME)
     {
-        ObType::Datetime
+        if opt_enabled!(opts, EXTENDED_JSON) {
+            ObType::ExtendedJson
+        } else {
+            ObType::Datetime
+        }
     } else {
         pyobject_to_obtype_unlikely(ob_type, opts, interpreter_state)
     }
@@ -69,6 +92,14 @@ pub(crate) fn pyobject_to_obtype_unlikely(
     opts: Opt,
     interpreter_state: *const crate::interpreter_state::InterpreterState,
 ) -> ObType {
+    if opt_enabled!(opts, EXTENDED_JSON) && is_extended_json(ob_type, interpreter_state) {
+        return ObType::ExtendedJson;
+    }
+
+    if opt_enabled!(opts, MSGPACK) && is_bytes_like(ob_type) {
+        return ObType::Bytes;
+    }
+
     if is_class_by_type!(
         ob_type,
         crate::typeref::get_uuid_type_from_state(interpreter_state)
@@ -82,6 +113,11 @@ pub(crate) fn pyobject_to_obtype_unlikely(
         crate::typeref::get_fragment_type_from_state(interpreter_state)
     ) {
         return ObType::Fragment;
+    } else if is_class_by_type!(
+        ob_type,
+        crate::typeref::get_raw_number_type_from_state(interpreter_state)
+    ) {
+        return ObType::RawNumber;
     }
 
     if opt_disabled!(opts, PASSTHROUGH_DATETIME) {
@@ -112,6 +148,10 @@ pub(crate) fn pyobject_to_obtype_unlikely(
         }
     }
 
+    if opt_enabled!(opts, TAGGED) && lookup_tag(interpreter_state, ob_type).is_some() {
+        return ObType::Tagged;
+    }
+
     if is_subclass_by_type!(
         ob_type,
         crate::typeref::get_enum_type_from_state(interpreter_state)
@@ -128,14 +168,54 @@ pub(crate) fn pyobject_to_obtype_unlikely(
         return ObType::Dataclass;
     }
 
+    if opt_enabled!(opts, NAMEDTUPLE_AS_OBJECT)
+        && is_subclass_by_flag!(tp_flags, Py_TPFLAGS_TUPLE_SUBCLASS)
+        && ffi!(PyObject_HasAttr(
+            ob_type.cast::<crate::ffi::PyObject>(),
+            crate::typeref::get_namedtuple_fields_str_from_state(interpreter_state)
+        )) == 1
+    {
+        return ObType::NamedTuple;
+    }
+
     if opt_enabled!(opts, SERIALIZE_NUMPY) {
         cold_path!();
-        if is_numpy_scalar(ob_type) {
+        if is_numpy_scalar(ob_type, interpreter_state) {
             return ObType::NumpyScalar;
-        } else if is_numpy_array(ob_type) {
+        } else if is_numpy_array(ob_type, interpreter_state)
+            || is_dlpack(ob_type)
+            || is_array_like(ob_type)
+        {
             return ObType::NumpyArray;
         }
     }
 
+    if opt_enabled!(opts, PANDAS_ORIENT) && is_pandas(ob_type, interpreter_state) {
+        cold_path!();
+        return ObType::Pandas;
+    }
+
+    if opt_enabled!(opts, SERIALIZE_ARROW) && is_arrow(ob_type) {
+        cold_path!();
+        return ObType::Arrow;
+    }
+
     ObType::Unknown
 }
+
+/// Whether `dumps(extended_json=)` wraps `ob_type`, other than the types
+/// `pyobject_to_obtype()` checks first.
+#[cold]
+fn is_extended_json(
+    ob_type: *mut crate::ffi::PyTypeObject,
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+) -> bool {
+    is_class_by_type!(
+        ob_type,
+        crate::typeref::get_uuid_type_from_state(interpreter_state)
+    ) || is_class_by_type!(ob_type, crate::typeref::bytes_type_ptr())
+        || is_class_by_type!(
+            ob_type,
+            crate::typeref::get_decimal_type().cast::<crate::ffi::PyTypeObject>()
+        )
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Paths passed to `default` with `OPT_DEFAULT_PATH`.
+//!
+//! Containers do not record where they are while serializing. When `default`
+//! is first called, the object being serialized is indexed by identity, each
+//! reachable object mapped to its parent and its key or index in it, and the
+//! path is read from the index. Objects returned by `default` are indexed
+//! separately, with the path of the object they replaced as a prefix.
+
+use crate::ffi::PyObject;
+use crate::interpreter_state::InterpreterState;
+use core::cell::Cell;
+use core::ptr::{NonNull, null_mut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+enum Segment {
+    /// A dict key or dataclass field name. Borrowed from the container.
+    Key(*mut PyObject),
+    Index(isize),
+}
+
+struct Frame {
+    root: *mut PyObject,
+    prefix: Vec<Segment>,
+    /// Each object reachable from `root` mapped to its parent and where it
+    /// is in it. The root maps to null.
+    index: Option<HashMap<*mut PyObject, (*mut PyObject, Segment)>>,
+}
+
+impl Frame {
+    fn new(root: *mut PyObject, prefix: Vec<Segment>) -> Self {
+        Frame {
+            root: root,
+            prefix: prefix,
+            index: None,
+        }
+    }
+
+    fn path_of(
+        &mut self,
+        ptr: *mut PyObject,
+        interpreter_state: *const InterpreterState,
+    ) -> Vec<Segment> {
+        let root = self.root;
+        let index = self
+            .index
+            .get_or_insert_with(|| build_index(root, interpreter_state));
+        let mut segments = Vec::new();
+        let mut current = ptr;
+        while let Some(&(parent, segment)) = index.get(&current) {
+            if parent.is_null() {
+                break;
+            }
+            segments.push(segment);
+            current = parent;
+        }
+        let mut path = self.prefix.clone();
+        path.extend(segments.iter().rev());
+        path
+    }
+}
+
+fn build_index(
+    root: *mut PyObject,
+    interpreter_state: *const InterpreterState,
+) -> HashMap<*mut PyObject, (*mut PyObject, Segment)> {
+    let mut index = HashMap::new();
+    index.insert(root, (null_mut(), Segment::Index(0)));
+    let mut pending = vec![root];
+    while let Some(parent) = pending.pop() {
+        let mut visit = |child: *mut PyObject, segment: Segment| {
+            if let std::collections::hash_map::Entry::Vacant(entry) = index.entry(child) {
+                entry.insert((parent, segment));
+                pending.push(child);
+            }
+        };
+        let ob_type = ob_type!(parent);
+        let tp_flags = tp_flags!(ob_type);
+        if is_subclass_by_flag!(tp_flags, Py_TPFLAGS_LIST_SUBCLASS) {
+            for idx in 0..ffi!(Py_SIZE(parent)) {
+                visit(ffi!(PyList_GET_ITEM(parent, idx)), Segment::Index(idx));
+            }
+        } else if is_subclass_by_flag!(tp_flags, Py_TPFLAGS_TUPLE_SUBCLASS) {
+            for idx in 0..ffi!(Py_SIZE(parent)) {
+                let item = unsafe { crate::ffi::PyTuple_GET_ITEM(parent, idx) };
+                visit(item, Segment::Index(idx));
+            }
+        } else if is_subclass_by_flag!(tp_flags, Py_TPFLAGS_DICT_SUBCLASS) {
+            visit_dict(parent, &mut visit);
+        } else if pydict_contains!(
+            ob_type,
+            crate::typeref::get_dataclass_fields_str_from_state(interpreter_state)
+        ) {
+            let dict = ffi!(PyObject_GetAttr(
+                parent,
+                crate::typeref::get_dict_str_from_state(interpreter_state)
+            ));
+            if dict.is_null() {
+                ffi!(PyErr_Clear());
+            } else {
+                // the instance holds another reference to its __dict__
+                ffi!(Py_DECREF(dict));
+                visit_dict(dict, &mut visit);
+            }
+        }
+    }
+    index
+}
+
+fn visit_dict<F>(dict: *mut PyObject, visit: &mut F)
+where
+    F: FnMut(*mut PyObject, Segment),
+{
+    let mut pos = 0;
+    let mut key: *mut PyObject = null_mut();
+    let mut value: *mut PyObject = null_mut();
+    while pydict_next!(dict, &mut pos, &mut key, &mut value) == 1 {
+        visit(value, Segment::Key(key));
+    }
+}
+
+pub(crate) struct DefaultPaths {
+    frames: Vec<Frame>,
+}
+
+impl DefaultPaths {
+    pub fn new(root: *mut PyObject) -> Self {
+        DefaultPaths {
+            frames: vec![Frame::new(root, Vec::new())],
+        }
+    }
+}
+
+thread_local! {
+    // The paths of the innermost `dumps()` call with `OPT_DEFAULT_PATH` on
+    // this thread, or null.
+    static ACTIVE: Cell<*mut DefaultPaths> = const { Cell::new(null_mut()) };
+}
+
+/// Make `paths` active for the current thread and return the previously
+/// active paths, which must be restored with `restore()`.
+pub(crate) fn install(paths: &mut DefaultPaths) -> *mut DefaultPaths {
+    ACTIVE.with(|active| active.replace(core::ptr::from_mut(paths)))
+}
+
+pub(crate) fn restore(previous: *mut DefaultPaths) {
+    ACTIVE.with(|active| active.set(previous));
+}
+
+/// The path of `ptr` in the document, pushed as a frame for the object
+/// `default` returns for it.
+pub(crate) struct PendingPath {
+    segments: Vec<Segment>,
+}
+
+impl PendingPath {
+    /// Find the path of `ptr` in the innermost frame.
+    #[cold]
+    pub fn find(ptr: *mut PyObject, interpreter_state: *const InterpreterState) -> Option<Self> {
+        let paths = ACTIVE.with(Cell::get);
+        if paths.is_null() {
+            return None;
+        }
+        let frame = unsafe { (*paths).frames.last_mut()? };
+        Some(PendingPath {
+            segments: frame.path_of(ptr, interpreter_state),
+        })
+    }
+
+    /// Return a new `list` of the keys and indices of the path.
+    pub fn to_list(&self) -> Option<NonNull<PyObject>> {
+        #[allow(clippy::cast_possible_wrap)]
+        let list = ffi!(PyList_New(self.segments.len() as isize));
+        if list.is_null() {
+            cold_path!();
+            ffi!(PyErr_Clear());
+            return None;
+        }
+        for (idx, segment) in self.segments.iter().enumerate() {
+            let item = match *segment {
+                Segment::Key(key) => {
+                    ffi!(Py_INCREF(key));
+                    key
+                }
+                #[allow(clippy::cast_lossless)]
+                Segment::Index(index) => ffi!(PyLong_FromLongLong(index as i64)),
+            };
+            #[allow(clippy::cast_possible_wrap)]
+            let idx = idx as isize;
+            ffi!(PyList_SET_ITEM(list, idx, item));
+        }
+        NonNull::new(list)
+    }
+
+    /// Index `obj`, returned by `default`, under this path until `pop()`.
+    pub fn push(&self, obj: *mut PyObject) {
+        let paths = ACTIVE.with(Cell::get);
+        debug_assert!(!paths.is_null());
+        unsafe {
+            (*paths).frames.push(Frame::new(obj, self.segments.clone()));
+        }
+    }
+
+    pub fn pop() {
+        let paths = ACTIVE.with(Cell::get);
+        debug_assert!(!paths.is_null());
+        unsafe {
+            (*paths).frames.pop();
+        }
+    }
+}
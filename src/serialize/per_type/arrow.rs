@@ -0,0 +1,748 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Serialize objects that export the Arrow C data interface, such as
+//! `pyarrow.Array`, `RecordBatch`, `ChunkedArray`, and `Table`, from their
+//! buffers.
+//!
+//! https://arrow.apache.org/docs/format/CDataInterface.html
+//! https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html
+
+use crate::ffi::{PyObject, PyTypeObject};
+use crate::opt::Opt;
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::numpy::{NumpyDatetimeUnit, civil_from_days};
+use crate::serialize::per_type::{DefaultSerializer, ItemType, NumpyItem};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::typeref::{get_arrow_c_array_str, get_arrow_c_stream_str};
+
+use core::ffi::{CStr, c_char, c_int, c_void};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+#[repr(C)]
+struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+/// A schema or array from `get_schema()` or `get_next()`, which is released
+/// when dropped. Those of a capsule are released by its destructor.
+struct Owned<T: Release>(T);
+
+trait Release {
+    fn release(&mut self);
+}
+
+impl Release for ArrowSchema {
+    fn release(&mut self) {
+        if let Some(release) = self.release {
+            unsafe {
+                release(self);
+            }
+        }
+    }
+}
+
+impl Release for ArrowArray {
+    fn release(&mut self) {
+        if let Some(release) = self.release {
+            unsafe {
+                release(self);
+            }
+        }
+    }
+}
+
+impl<T: Release> Drop for Owned<T> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+/// Whether `ob_type` exports `__arrow_c_array__` or `__arrow_c_stream__`.
+#[cold]
+pub(crate) fn is_arrow(ob_type: *mut PyTypeObject) -> bool {
+    let ob_type = ob_type.cast::<PyObject>();
+    ffi!(PyObject_HasAttr(ob_type, get_arrow_c_array_str())) == 1
+        || ffi!(PyObject_HasAttr(ob_type, get_arrow_c_stream_str())) == 1
+}
+
+enum ArrowError {
+    Malformed,
+    UnsupportedDataType,
+    RecursionLimit,
+}
+
+impl ArrowError {
+    fn into_serialize_error(self) -> SerializeError {
+        match self {
+            ArrowError::Malformed => SerializeError::ArrowMalformed,
+            ArrowError::UnsupportedDataType => SerializeError::ArrowUnsupportedDatatype,
+            ArrowError::RecursionLimit => SerializeError::RecursionLimit,
+        }
+    }
+}
+
+/// Nesting of lists, structs, and dictionaries, as of `dict` and `list`.
+const MAX_DEPTH: usize = 255;
+
+/// Serialize an array as an array of its values, and a record batch or
+/// table, whose rows are a struct, as an array of an object per row. A
+/// `__arrow_c_stream__` is preferred to `__arrow_c_array__` so that a table
+/// is read one batch at a time.
+#[repr(transparent)]
+pub(crate) struct ArrowSerializer<'a> {
+    previous: &'a PyObjectSerializer,
+}
+
+impl<'a> ArrowSerializer<'a> {
+    pub fn new(previous: &'a PyObjectSerializer) -> Self {
+        Self { previous: previous }
+    }
+}
+
+impl Serialize for ArrowSerializer<'_> {
+    #[cold]
+    #[inline(never)]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match ArrowSource::new(self.previous.ptr) {
+            Ok(source) => source.serialize(self.previous.state.opts(), serializer),
+            Err(ArrowError::UnsupportedDataType) if self.previous.default.is_some() => {
+                DefaultSerializer::new(self.previous).serialize(serializer)
+            }
+            Err(err) => err!(err.into_serialize_error()),
+        }
+    }
+}
+
+/// The exported data of an object and its schema, read before anything is
+/// written so that an unsupported type can be passed to `default=`.
+enum ArrowSource {
+    Array {
+        /// The `(schema, array)` tuple of capsules.
+        capsules: *mut PyObject,
+        array: *mut ArrowArray,
+        field: Field,
+    },
+    Stream {
+        capsule: *mut PyObject,
+        stream: *mut ArrowArrayStream,
+        field: Field,
+        _schema: Owned<ArrowSchema>,
+    },
+}
+
+impl ArrowSource {
+    #[cold]
+    fn new(ptr: *mut PyObject) -> Result<Self, ArrowError> {
+        if ffi!(PyObject_HasAttr(ptr, get_arrow_c_stream_str())) == 1 {
+            Self::from_stream(ptr)
+        } else {
+            Self::from_array(ptr)
+        }
+    }
+
+    fn from_stream(ptr: *mut PyObject) -> Result<Self, ArrowError> {
+        let capsule = call_method!(ptr, get_arrow_c_stream_str());
+        if capsule.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(ArrowError::Malformed);
+        }
+        let stream = ffi!(PyCapsule_GetPointer(
+            capsule,
+            c"arrow_array_stream".as_ptr()
+        ))
+        .cast::<ArrowArrayStream>();
+        if stream.is_null() || unsafe { (*stream).release.is_none() } {
+            ffi!(PyErr_Clear());
+            ffi!(Py_DECREF(capsule));
+            return Err(ArrowError::Malformed);
+        }
+        let mut schema = Owned(ArrowSchema::empty());
+        let ret = unsafe {
+            match (*stream).get_schema {
+                Some(get_schema) if get_schema(stream, &mut schema.0) == 0 => {
+                    Field::from_schema(&schema.0, 0)
+                }
+                _ => Err(ArrowError::Malformed),
+            }
+        };
+        match ret {
+            Ok(field) => Ok(ArrowSource::Stream {
+                capsule: capsule,
+                stream: stream,
+                field: field,
+                _schema: schema,
+            }),
+            Err(err) => {
+                drop(schema);
+                ffi!(Py_DECREF(capsule));
+                Err(err)
+            }
+        }
+    }
+
+    fn from_array(ptr: *mut PyObject) -> Result<Self, ArrowError> {
+        let capsules = call_method!(ptr, get_arrow_c_array_str());
+        if capsules.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(ArrowError::Malformed);
+        }
+        if !is_type!(ob_type!(capsules), crate::typeref::tuple_type_ptr())
+            || ffi!(Py_SIZE(capsules)) != 2
+        {
+            ffi!(Py_DECREF(capsules));
+            return Err(ArrowError::Malformed);
+        }
+        let schema = ffi!(PyCapsule_GetPointer(
+            crate::ffi::PyTuple_GET_ITEM(capsules, 0),
+            c"arrow_schema".as_ptr()
+        ))
+        .cast::<ArrowSchema>();
+        let array = ffi!(PyCapsule_GetPointer(
+            crate::ffi::PyTuple_GET_ITEM(capsules, 1),
+            c"arrow_array".as_ptr()
+        ))
+        .cast::<ArrowArray>();
+        if schema.is_null() || array.is_null() {
+            ffi!(PyErr_Clear());
+            ffi!(Py_DECREF(capsules));
+            return Err(ArrowError::Malformed);
+        }
+        let ret = Field::from_schema(schema, 0)
+            .and_then(|field| field.check(unsafe { &*array }, 0).map(|()| field));
+        match ret {
+            Ok(field) => Ok(ArrowSource::Array {
+                capsules: capsules,
+                array: array,
+                field: field,
+            }),
+            Err(err) => {
+                ffi!(Py_DECREF(capsules));
+                Err(err)
+            }
+        }
+    }
+
+    fn serialize<S>(&self, opts: Opt, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        match self {
+            ArrowSource::Array { array, field, .. } => {
+                let array = unsafe { &**array };
+                for index in 0..array.length as usize {
+                    seq.serialize_element(&Value {
+                        field: field,
+                        array: array,
+                        index: index,
+                        opts: opts,
+                    })?;
+                }
+            }
+            ArrowSource::Stream { stream, field, .. } => loop {
+                let mut batch = Owned(ArrowArray::empty());
+                let ret = unsafe {
+                    match (**stream).get_next {
+                        Some(get_next) => get_next(*stream, &mut batch.0),
+                        None => -1,
+                    }
+                };
+                if ret != 0 {
+                    err!(SerializeError::ArrowMalformed)
+                }
+                if batch.0.release.is_none() {
+                    break;
+                }
+                if let Err(err) = field.check(&batch.0, 0) {
+                    err!(err.into_serialize_error())
+                }
+                for index in 0..batch.0.length as usize {
+                    seq.serialize_element(&Value {
+                        field: field,
+                        array: &batch.0,
+                        index: index,
+                        opts: opts,
+                    })?;
+                }
+            },
+        }
+        seq.end()
+    }
+}
+
+impl Drop for ArrowSource {
+    fn drop(&mut self) {
+        match self {
+            ArrowSource::Array { capsules, .. } => ffi!(Py_DECREF(*capsules)),
+            ArrowSource::Stream { capsule, .. } => ffi!(Py_DECREF(*capsule)),
+        }
+    }
+}
+
+impl ArrowSchema {
+    const fn empty() -> Self {
+        ArrowSchema {
+            format: core::ptr::null(),
+            name: core::ptr::null(),
+            metadata: core::ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: core::ptr::null_mut(),
+            dictionary: core::ptr::null_mut(),
+            release: None,
+            private_data: core::ptr::null_mut(),
+        }
+    }
+}
+
+impl ArrowArray {
+    const fn empty() -> Self {
+        ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: core::ptr::null_mut(),
+            children: core::ptr::null_mut(),
+            dictionary: core::ptr::null_mut(),
+            release: None,
+            private_data: core::ptr::null_mut(),
+        }
+    }
+
+    #[inline(always)]
+    fn buffer(&self, idx: usize) -> *const u8 {
+        unsafe { (*self.buffers.add(idx)).cast::<u8>() }
+    }
+
+    #[inline(always)]
+    fn child(&self, idx: usize) -> &ArrowArray {
+        unsafe { &**self.children.add(idx) }
+    }
+
+    #[inline(always)]
+    fn is_valid(&self, pos: usize) -> bool {
+        let validity = self.buffer(0);
+        validity.is_null() || unsafe { *validity.add(pos / 8) } & (1 << (pos % 8)) != 0
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Null,
+    Bool,
+    /// A fixed-width item, read as an item of a numpy array of `ItemType`,
+    /// and its width in bytes.
+    Item(ItemType, usize),
+    /// Days since the epoch as `i32`.
+    Date32,
+    /// Milliseconds since the epoch as `i64`.
+    Date64,
+    Utf8,
+    LargeUtf8,
+    List,
+    LargeList,
+    FixedSizeList(usize),
+    Struct,
+}
+
+impl Kind {
+    fn from_format(format: &str) -> Option<Self> {
+        let kind = match format {
+            "n" => Kind::Null,
+            "b" => Kind::Bool,
+            "c" => Kind::Item(ItemType::I8, 1),
+            "C" => Kind::Item(ItemType::U8, 1),
+            "s" => Kind::Item(ItemType::I16, 2),
+            "S" => Kind::Item(ItemType::U16, 2),
+            "i" => Kind::Item(ItemType::I32, 4),
+            "I" => Kind::Item(ItemType::U32, 4),
+            "l" => Kind::Item(ItemType::I64, 8),
+            "L" => Kind::Item(ItemType::U64, 8),
+            "e" => Kind::Item(ItemType::F16, 2),
+            "f" => Kind::Item(ItemType::F32, 4),
+            "g" => Kind::Item(ItemType::F64, 8),
+            "u" => Kind::Utf8,
+            "U" => Kind::LargeUtf8,
+            "tdD" => Kind::Date32,
+            "tdm" => Kind::Date64,
+            "tDs" => Kind::Item(ItemType::TIMEDELTA64(NumpyDatetimeUnit::Seconds), 8),
+            "tDm" => Kind::Item(ItemType::TIMEDELTA64(NumpyDatetimeUnit::Milliseconds), 8),
+            "tDu" => Kind::Item(ItemType::TIMEDELTA64(NumpyDatetimeUnit::Microseconds), 8),
+            "tDn" => Kind::Item(ItemType::TIMEDELTA64(NumpyDatetimeUnit::Nanoseconds), 8),
+            // a timestamp with a timezone is not naive, as numpy.datetime64 is
+            "tss:" => Kind::Item(ItemType::DATETIME64(NumpyDatetimeUnit::Seconds), 8),
+            "tsm:" => Kind::Item(ItemType::DATETIME64(NumpyDatetimeUnit::Milliseconds), 8),
+            "tsu:" => Kind::Item(ItemType::DATETIME64(NumpyDatetimeUnit::Microseconds), 8),
+            "tsn:" => Kind::Item(ItemType::DATETIME64(NumpyDatetimeUnit::Nanoseconds), 8),
+            "+l" => Kind::List,
+            "+L" => Kind::LargeList,
+            "+s" => Kind::Struct,
+            _ => {
+                let size = format.strip_prefix("+w:")?.parse::<usize>().ok()?;
+                Kind::FixedSizeList(size)
+            }
+        };
+        Some(kind)
+    }
+
+    /// The number of buffers of an array, including the validity bitmap.
+    const fn buffers(self) -> usize {
+        match self {
+            Kind::Null => 0,
+            Kind::FixedSizeList(_) | Kind::Struct => 1,
+            Kind::Utf8 | Kind::LargeUtf8 => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// The type of an array from its `ArrowSchema`.
+struct Field {
+    name: String,
+    kind: Kind,
+    children: Vec<Field>,
+    /// The type of the values of a dictionary-encoded array, whose `kind` is
+    /// that of its indices.
+    dictionary: Option<Box<Field>>,
+}
+
+impl Field {
+    #[cold]
+    fn from_schema(schema: *const ArrowSchema, depth: usize) -> Result<Self, ArrowError> {
+        if depth > MAX_DEPTH {
+            return Err(ArrowError::RecursionLimit);
+        }
+        if schema.is_null() || unsafe { (*schema).format.is_null() } {
+            return Err(ArrowError::Malformed);
+        }
+        let schema = unsafe { &*schema };
+        let format = unsafe { CStr::from_ptr(schema.format) }
+            .to_str()
+            .map_err(|_| ArrowError::Malformed)?;
+        let name = if schema.name.is_null() {
+            String::new()
+        } else {
+            String::from(
+                unsafe { CStr::from_ptr(schema.name) }
+                    .to_str()
+                    .map_err(|_| ArrowError::Malformed)?,
+            )
+        };
+        let kind = Kind::from_format(format).ok_or(ArrowError::UnsupportedDataType)?;
+        let num_children = usize::try_from(schema.n_children).map_err(|_| ArrowError::Malformed)?;
+        let expected = match kind {
+            Kind::List | Kind::LargeList | Kind::FixedSizeList(_) => 1,
+            Kind::Struct => num_children,
+            _ => 0,
+        };
+        if num_children != expected || (num_children > 0 && schema.children.is_null()) {
+            return Err(ArrowError::Malformed);
+        }
+        let mut children = Vec::with_capacity(num_children);
+        for idx in 0..num_children {
+            let child = unsafe { *schema.children.add(idx) };
+            children.push(Field::from_schema(child, depth + 1)?);
+        }
+        let mut dictionary = None;
+        if !schema.dictionary.is_null() {
+            if !matches!(
+                kind,
+                Kind::Item(
+                    ItemType::I8
+                        | ItemType::U8
+                        | ItemType::I16
+                        | ItemType::U16
+                        | ItemType::I32
+                        | ItemType::U32
+                        | ItemType::I64
+                        | ItemType::U64,
+                    _
+                )
+            ) {
+                return Err(ArrowError::Malformed);
+            }
+            dictionary = Some(Box::new(Field::from_schema(schema.dictionary, depth + 1)?));
+        }
+        Ok(Field {
+            name: name,
+            kind: kind,
+            children: children,
+            dictionary: dictionary,
+        })
+    }
+
+    /// Check that `array` has the buffers and children of this type, so that
+    /// its items can be read.
+    fn check(&self, array: &ArrowArray, depth: usize) -> Result<(), ArrowError> {
+        if depth > MAX_DEPTH {
+            return Err(ArrowError::RecursionLimit);
+        }
+        let num_buffers = self.kind.buffers();
+        if array.release.is_none()
+            || array.length < 0
+            || array.offset < 0
+            || usize::try_from(array.n_buffers).ok() != Some(num_buffers)
+            || usize::try_from(array.n_children).ok() != Some(self.children.len())
+            || (num_buffers > 0 && array.buffers.is_null())
+            || (!self.children.is_empty() && array.children.is_null())
+            || self.dictionary.is_some() == array.dictionary.is_null()
+        {
+            return Err(ArrowError::Malformed);
+        }
+        // the data of a string array without characters may be null
+        let required = if matches!(self.kind, Kind::Utf8 | Kind::LargeUtf8) {
+            2
+        } else {
+            num_buffers
+        };
+        if array.length > 0 && (1..required).any(|idx| array.buffer(idx).is_null()) {
+            return Err(ArrowError::Malformed);
+        }
+        for (idx, child) in self.children.iter().enumerate() {
+            let child_array = unsafe { *array.children.add(idx) };
+            if child_array.is_null() {
+                return Err(ArrowError::Malformed);
+            }
+            let child_array = unsafe { &*child_array };
+            child.check(child_array, depth + 1)?;
+            if matches!(self.kind, Kind::Struct) && child_array.length < array.offset + array.length
+            {
+                return Err(ArrowError::Malformed);
+            }
+        }
+        if let Some(dictionary) = &self.dictionary {
+            dictionary.check(unsafe { &*array.dictionary }, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The item at `index` of `array`.
+struct Value<'a> {
+    field: &'a Field,
+    array: &'a ArrowArray,
+    index: usize,
+    opts: Opt,
+}
+
+impl Value<'_> {
+    #[inline(always)]
+    fn read<T>(&self, buffer: usize, pos: usize) -> T {
+        unsafe {
+            core::ptr::read_unaligned(
+                self.array
+                    .buffer(buffer)
+                    .add(pos * core::mem::size_of::<T>())
+                    .cast::<T>(),
+            )
+        }
+    }
+
+    /// The range of the child of a list at `pos`, checked to be in it.
+    fn list_range(&self, pos: usize) -> Option<(usize, usize)> {
+        let (start, end) = match self.field.kind {
+            Kind::List => (
+                usize::try_from(self.read::<i32>(1, pos)).ok()?,
+                usize::try_from(self.read::<i32>(1, pos + 1)).ok()?,
+            ),
+            Kind::LargeList => (
+                usize::try_from(self.read::<i64>(1, pos)).ok()?,
+                usize::try_from(self.read::<i64>(1, pos + 1)).ok()?,
+            ),
+            Kind::FixedSizeList(size) => (pos * size, (pos + 1) * size),
+            _ => unreachable!(),
+        };
+        let child_len = usize::try_from(self.array.child(0).length).ok()?;
+        if start > end || end > child_len {
+            return None;
+        }
+        Some((start, end))
+    }
+
+    /// The index into the dictionary of a dictionary-encoded item.
+    fn dictionary_index(&self, pos: usize) -> Option<usize> {
+        let index = match self.field.kind {
+            Kind::Item(ItemType::I8, _) => i64::from(self.read::<i8>(1, pos)),
+            Kind::Item(ItemType::U8, _) => i64::from(self.read::<u8>(1, pos)),
+            Kind::Item(ItemType::I16, _) => i64::from(self.read::<i16>(1, pos)),
+            Kind::Item(ItemType::U16, _) => i64::from(self.read::<u16>(1, pos)),
+            Kind::Item(ItemType::I32, _) => i64::from(self.read::<i32>(1, pos)),
+            Kind::Item(ItemType::U32, _) => i64::from(self.read::<u32>(1, pos)),
+            Kind::Item(ItemType::I64, _) => self.read::<i64>(1, pos),
+            Kind::Item(ItemType::U64, _) => i64::try_from(self.read::<u64>(1, pos)).ok()?,
+            _ => unreachable!(),
+        };
+        let index = usize::try_from(index).ok()?;
+        let len = unsafe { (*self.array.dictionary).length };
+        if index >= usize::try_from(len).ok()? {
+            return None;
+        }
+        Some(index)
+    }
+
+    fn serialize_str<S>(&self, pos: usize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let range = match self.field.kind {
+            Kind::Utf8 => (
+                usize::try_from(self.read::<i32>(1, pos)),
+                usize::try_from(self.read::<i32>(1, pos + 1)),
+            ),
+            _ => (
+                usize::try_from(self.read::<i64>(1, pos)),
+                usize::try_from(self.read::<i64>(1, pos + 1)),
+            ),
+        };
+        let (start, end) = match range {
+            (Ok(start), Ok(end)) if start <= end => (start, end),
+            _ => err!(SerializeError::ArrowMalformed),
+        };
+        if start == end {
+            return serializer.serialize_str("");
+        }
+        let data = self.array.buffer(2);
+        if data.is_null() {
+            err!(SerializeError::ArrowMalformed)
+        }
+        let bytes = unsafe { core::slice::from_raw_parts(data.add(start), end - start) };
+        match core::str::from_utf8(bytes) {
+            Ok(val) => serializer.serialize_str(val),
+            Err(_) => err!(SerializeError::InvalidStr),
+        }
+    }
+}
+
+impl Serialize for Value<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[allow(clippy::cast_sign_loss)]
+        let pos = self.array.offset as usize + self.index;
+        if matches!(self.field.kind, Kind::Null) || !self.array.is_valid(pos) {
+            return serializer.serialize_unit();
+        }
+        if let Some(dictionary) = &self.field.dictionary {
+            let index = match self.dictionary_index(pos) {
+                Some(val) => val,
+                None => err!(SerializeError::ArrowMalformed),
+            };
+            return Value {
+                field: dictionary,
+                array: unsafe { &*self.array.dictionary },
+                index: index,
+                opts: self.opts,
+            }
+            .serialize(serializer);
+        }
+        match self.field.kind {
+            Kind::Null => unreachable!(),
+            Kind::Bool => {
+                let byte = unsafe { *self.array.buffer(1).add(pos / 8) };
+                serializer.serialize_bool(byte & (1 << (pos % 8)) != 0)
+            }
+            Kind::Item(kind, width) => NumpyItem {
+                data: unsafe { self.array.buffer(1).add(pos * width) },
+                kind: kind,
+                opts: self.opts,
+                position: &[],
+                #[allow(clippy::cast_possible_wrap)]
+                index: self.index as isize,
+            }
+            .serialize(serializer),
+            Kind::Date32 => ArrowDate(i128::from(self.read::<i32>(1, pos))).serialize(serializer),
+            Kind::Date64 => ArrowDate(i128::from(self.read::<i64>(1, pos).div_euclid(86_400_000)))
+                .serialize(serializer),
+            Kind::Utf8 | Kind::LargeUtf8 => self.serialize_str(pos, serializer),
+            Kind::List | Kind::LargeList | Kind::FixedSizeList(_) => {
+                let (start, end) = match self.list_range(pos) {
+                    Some(val) => val,
+                    None => err!(SerializeError::ArrowMalformed),
+                };
+                let child = &self.field.children[0];
+                let child_array = self.array.child(0);
+                let mut seq = serializer.serialize_seq(None).unwrap();
+                for index in start..end {
+                    seq.serialize_element(&Value {
+                        field: child,
+                        array: child_array,
+                        index: index,
+                        opts: self.opts,
+                    })?;
+                }
+                seq.end()
+            }
+            Kind::Struct => {
+                let mut map = serializer.serialize_map(None).unwrap();
+                for (idx, child) in self.field.children.iter().enumerate() {
+                    map.serialize_key(child.name.as_str()).unwrap();
+                    map.serialize_value(&Value {
+                        field: child,
+                        array: self.array.child(idx),
+                        index: pos,
+                        opts: self.opts,
+                    })?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// A date as days since the epoch, written as `YYYY-MM-DD` as
+/// `datetime.date` is, with the sign and six digits of year of ISO 8601
+/// expanded representation outside of 1 to 9999.
+struct ArrowDate(i128);
+
+impl Serialize for ArrowDate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (year, month, day) = civil_from_days(self.0);
+        if (1..=9999).contains(&year) {
+            serializer.serialize_str(&format!("{year:04}-{month:02}-{day:02}"))
+        } else {
+            serializer.serialize_str(&format!("{year:+07}-{month:02}-{day:02}"))
+        }
+    }
+}
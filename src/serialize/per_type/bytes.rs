@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE, PyMemoryView_GET_BUFFER};
+use crate::serialize::error::SerializeError;
+use crate::util::isize_to_usize;
+
+use serde::ser::{Serialize, Serializer};
+
+/// Writes `bytes`, `bytearray`, or a C-contiguous `memoryview` as binary
+/// data, which only `dumps_msgpack()` serializes.
+#[repr(transparent)]
+pub(crate) struct BytesSerializer {
+    ptr: *mut crate::ffi::PyObject,
+}
+
+impl BytesSerializer {
+    pub fn new(ptr: *mut crate::ffi::PyObject) -> Self {
+        BytesSerializer { ptr: ptr }
+    }
+}
+
+impl Serialize for BytesSerializer {
+    #[cold]
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        debug_assert!(!serializer.is_human_readable());
+        let ob_type = ob_type!(self.ptr);
+        let buffer = if is_type!(ob_type, crate::typeref::bytes_type_ptr()) {
+            unsafe {
+                core::slice::from_raw_parts(
+                    PyBytes_AS_STRING(self.ptr).cast::<u8>(),
+                    isize_to_usize(PyBytes_GET_SIZE(self.ptr)),
+                )
+            }
+        } else if is_type!(ob_type, crate::typeref::bytearray_type_ptr()) {
+            unsafe {
+                core::slice::from_raw_parts(
+                    ffi!(PyByteArray_AsString(self.ptr)).cast::<u8>(),
+                    isize_to_usize(ffi!(PyByteArray_Size(self.ptr))),
+                )
+            }
+        } else {
+            let membuf = unsafe { PyMemoryView_GET_BUFFER(self.ptr) };
+            if unsafe { crate::ffi::PyBuffer_IsContiguous(membuf, b'C' as core::ffi::c_char) } == 0
+            {
+                err!(SerializeError::MemoryViewNotContiguous)
+            }
+            unsafe {
+                core::slice::from_raw_parts(
+                    (*membuf).buf.cast::<u8>().cast_const(),
+                    isize_to_usize((*membuf).len),
+                )
+            }
+        };
+        serializer.serialize_bytes(buffer)
+    }
+}
+
+/// Whether `ob_type` is serialized by `BytesSerializer`.
+#[inline(always)]
+pub(crate) fn is_bytes_like(ob_type: *mut crate::ffi::PyTypeObject) -> bool {
+    is_type!(ob_type, crate::typeref::bytes_type_ptr())
+        || is_type!(ob_type, crate::typeref::bytearray_type_ptr())
+        || is_type!(ob_type, crate::typeref::memoryview_type_ptr())
+}
@@ -1,15 +1,21 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
+use crate::opt::{FIELD_ORDER, FIELD_ORDER_SORTED};
 use crate::serialize::error::SerializeError;
+use crate::serialize::key_order::sort_items;
 use crate::serialize::per_type::dict::ZeroDictSerializer;
+use crate::serialize::per_type::{DataclassPlanSerializer, omit_value, resolve_if_unsupported};
+use crate::serialize::registry::lookup_type_plan;
 use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::snapshot::{self, DictEntries, Snapshot};
 use crate::serialize::state::SerializerState;
 use crate::str::PyStr;
 // DATACLASS_FIELDS_STR, DICT_STR, FIELD_TYPE, FIELD_TYPE_STR, SLOTS_STR, STR_TYPE now accessed via typeref accessor functions
 use crate::util::isize_to_usize;
 
 use serde::ser::{Serialize, SerializeMap, Serializer};
+use smallvec::SmallVec;
 
 use core::ptr::NonNull;
 
@@ -33,6 +39,33 @@ impl Serialize for DataclassGenericSerializer<'_> {
         if self.previous.state.recursion_limit() {
             err!(SerializeError::RecursionLimit)
         }
+        let opts = self.previous.state.opts();
+        if opts & FIELD_ORDER != FIELD_ORDER_SORTED {
+            if let Some(capsule) = lookup_type_plan(
+                self.previous.state.interpreter_state(),
+                ob_type!(self.previous.ptr),
+            ) {
+                // a class registered with `register_type()` writes its
+                // declared fields unless the instance has other attributes
+                return DataclassPlanSerializer::new(
+                    self.previous.ptr,
+                    capsule,
+                    self.previous.state,
+                    self.previous.default,
+                )
+                .serialize(serializer);
+            }
+        }
+        if opts & FIELD_ORDER != 0 {
+            // `field_order=` other than insertion writes the declared fields
+            cold_path!();
+            return DataclassFallbackSerializer::new(
+                self.previous.ptr,
+                self.previous.state,
+                self.previous.default,
+            )
+            .serialize(serializer);
+        }
         let interpreter_state = self.previous.state.interpreter_state();
         let dict = ffi!(PyObject_GetAttr(
             self.previous.ptr,
@@ -61,15 +94,31 @@ impl Serialize for DataclassGenericSerializer<'_> {
             ffi!(Py_DECREF(dict));
             ret
         } else {
-            let ret =
-                DataclassFastSerializer::new(dict, self.previous.state, self.previous.default)
-                    .serialize(serializer);
+            let ret = serialize_instance_dict(
+                dict,
+                self.previous.state,
+                self.previous.default,
+                serializer,
+            );
             ffi!(Py_DECREF(dict));
             ret
         }
     }
 }
 
+/// Serialize the `__dict__` of a dataclass instance in insertion order.
+pub(crate) fn serialize_instance_dict<S>(
+    dict: *mut crate::ffi::PyObject,
+    state: SerializerState,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    DataclassFastSerializer::new(dict, state, default).serialize(serializer)
+}
+
 pub(crate) struct DataclassFastSerializer {
     ptr: *mut crate::ffi::PyObject,
     state: SerializerState,
@@ -90,50 +139,83 @@ impl DataclassFastSerializer {
     }
 }
 
+impl DataclassFastSerializer {
+    #[inline(always)]
+    fn serialize_entry<M>(
+        &self,
+        map: &mut M,
+        key: *mut crate::ffi::PyObject,
+        value: *mut crate::ffi::PyObject,
+    ) -> Result<(), M::Error>
+    where
+        M: SerializeMap,
+    {
+        let key_as_str = {
+            let key_ob_type = ob_type!(key);
+            // Use direct CPython global for str type (zero indirection)
+            if !is_class_by_type!(key_ob_type, crate::typeref::str_type_ptr()) {
+                cold_path!();
+                err!(SerializeError::KeyMustBeStr)
+            }
+            match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                Some(uni) => uni,
+                None => err!(SerializeError::InvalidStr),
+            }
+        };
+        if key_as_str.as_bytes()[0] == b'_' {
+            cold_path!();
+            return Ok(());
+        }
+        if omit_value(value, self.state.opts()) {
+            return Ok(());
+        }
+        let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
+        if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+            if resolved.skip() {
+                return Ok(());
+            }
+            map.serialize_key(key_as_str).unwrap();
+            return map.serialize_value(&resolved);
+        }
+        map.serialize_key(key_as_str).unwrap();
+        map.serialize_value(&pyvalue)
+    }
+}
+
 impl Serialize for DataclassFastSerializer {
     #[inline(never)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
+        let compat = self.state.compat_mode();
+        let len = isize_to_usize(pydict_len!(compat, self.ptr));
         if len == 0 {
             cold_path!();
             return ZeroDictSerializer::new().serialize(serializer);
         }
         let mut map = serializer.serialize_map(None).unwrap();
 
+        if snapshot::is_required(self.state.opts(), self.default) {
+            for (key, value) in DictEntries::new(self.ptr, compat) {
+                self.serialize_entry(&mut map, key.as_ptr(), value.as_ptr())?;
+            }
+            return map.end();
+        }
+
         let mut pos = 0;
         let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
         let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
 
-        pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+        pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
         for _ in 0..len {
             let key = next_key;
             let value = next_value;
 
-            pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+            pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
-            let key_as_str = {
-                let key_ob_type = ob_type!(key);
-                // Use direct CPython global for str type (zero indirection)
-                if !is_class_by_type!(key_ob_type, crate::typeref::str_type_ptr()) {
-                    cold_path!();
-                    err!(SerializeError::KeyMustBeStr)
-                }
-                match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
-                    Some(uni) => uni,
-                    None => err!(SerializeError::InvalidStr),
-                }
-            };
-            if key_as_str.as_bytes()[0] == b'_' {
-                cold_path!();
-                continue;
-            }
-            let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
-            map.serialize_key(key_as_str).unwrap();
-            map.serialize_value(&pyvalue)?;
+            self.serialize_entry(&mut map, key, value)?;
         }
         map.end()
     }
@@ -173,24 +255,29 @@ impl Serialize for DataclassFallbackSerializer {
         ));
         debug_assert!(ffi!(Py_REFCNT(fields)) >= 2);
         ffi!(Py_DECREF(fields));
-        let len = isize_to_usize(ffi!(Py_SIZE(fields)));
+        let compat = self.state.compat_mode();
+        let len = isize_to_usize(pydict_len!(compat, fields));
         if len == 0 {
             cold_path!();
             return ZeroDictSerializer::new().serialize(serializer);
         }
-        let mut map = serializer.serialize_map(None).unwrap();
+        let opts = self.state.opts();
+        let owned = snapshot::is_required(opts, self.default);
+        let mut items: SmallVec<[(&str, *mut crate::ffi::PyObject); 8]> =
+            SmallVec::with_capacity(len);
+        let mut snapshots: SmallVec<[Snapshot; 8]> = SmallVec::new();
 
         let mut pos = 0;
         let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
         let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
 
-        pydict_next!(fields, &mut pos, &mut next_key, &mut next_value);
+        pydict_next_compat!(compat, fields, &mut pos, &mut next_key, &mut next_value);
 
         for _ in 0..len {
             let attr = next_key;
             let field = next_value;
 
-            pydict_next!(fields, &mut pos, &mut next_key, &mut next_value);
+            pydict_next_compat!(compat, fields, &mut pos, &mut next_key, &mut next_value);
 
             let field_type = ffi!(PyObject_GetAttr(
                 field,
@@ -218,10 +305,36 @@ impl Serialize for DataclassFallbackSerializer {
 
             let value = ffi!(PyObject_GetAttr(self.ptr, attr));
             debug_assert!(ffi!(Py_REFCNT(value)) >= 2);
-            ffi!(Py_DECREF(value));
-            let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
+            if owned {
+                snapshots.push(Snapshot::new(value));
+            } else {
+                ffi!(Py_DECREF(value));
+            }
+            if omit_value(value, opts) {
+                continue;
+            }
+            items.push((key_as_str, value));
+        }
 
-            map.serialize_key(key_as_str).unwrap();
+        if opts & FIELD_ORDER == FIELD_ORDER_SORTED {
+            if let Err(err) = sort_items(&mut items, opts) {
+                err!(err)
+            }
+        }
+
+        let mut map = serializer.serialize_map(None).unwrap();
+        for (key, value) in items.iter() {
+            let pyvalue = PyObjectSerializer::new(*value, self.state, self.default);
+            if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+                if resolved.skip() {
+                    continue;
+                }
+                map.serialize_key(key).unwrap();
+                map.serialize_value(&resolved)?;
+                continue;
+            }
+
+            map.serialize_key(key).unwrap();
             map.serialize_value(&pyvalue)?;
         }
         map.end()
@@ -0,0 +1,402 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::PyObject;
+use crate::opt::{ENSURE_ASCII, EXTENDED_JSON, FIELD_ORDER};
+use crate::serialize::per_type::dataclass::serialize_instance_dict;
+use crate::serialize::per_type::dict::EscapedKey;
+use crate::serialize::per_type::{
+    BoolSerializer, FloatSerializer, IntSerializer, StrSerializer, omit_value,
+    resolve_if_unsupported,
+};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::format_escaped_str_to_vec;
+use crate::str::PyStr;
+use crate::util::isize_to_usize;
+
+use core::ptr::{NonNull, null_mut};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+const CAPSULE_NAME: &core::ffi::CStr = c"hyperjson.DataclassPlan";
+
+/// The annotation of a field, where it is a type written without dispatch.
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind {
+    Str,
+    Int,
+    Float,
+    Bool,
+    Any,
+}
+
+impl FieldKind {
+    fn of(annotation: *mut PyObject) -> Self {
+        let annotation = annotation.cast::<crate::ffi::PyTypeObject>();
+        if core::ptr::eq(annotation, crate::typeref::str_type_ptr()) {
+            FieldKind::Str
+        } else if core::ptr::eq(annotation, crate::typeref::int_type_ptr()) {
+            FieldKind::Int
+        } else if core::ptr::eq(annotation, crate::typeref::float_type_ptr()) {
+            FieldKind::Float
+        } else if core::ptr::eq(annotation, crate::typeref::bool_type_ptr()) {
+            FieldKind::Bool
+        } else {
+            FieldKind::Any
+        }
+    }
+
+    /// Whether `value` is the type of the annotation, so that it is written
+    /// directly.
+    #[inline(always)]
+    fn matches(self, value: *mut PyObject) -> bool {
+        let ob_type = ob_type!(value);
+        match self {
+            FieldKind::Str => is_type!(ob_type, crate::typeref::str_type_ptr()),
+            FieldKind::Int => is_type!(ob_type, crate::typeref::int_type_ptr()),
+            FieldKind::Float => is_type!(ob_type, crate::typeref::float_type_ptr()),
+            FieldKind::Bool => is_type!(ob_type, crate::typeref::bool_type_ptr()),
+            FieldKind::Any => false,
+        }
+    }
+}
+
+struct PlanField {
+    name: *mut PyObject,
+    kind: FieldKind,
+    key: core::ops::Range<usize>,
+    ascii_key: core::ops::Range<usize>,
+}
+
+/// The key of a field, written escaped unless the output is not JSON.
+struct PlanKey<'a> {
+    name: *mut PyObject,
+    escaped: &'a [u8],
+}
+
+impl Serialize for PlanKey<'_> {
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            EscapedKey(self.escaped).serialize(serializer)
+        } else {
+            StrSerializer::new(self.name).serialize(serializer)
+        }
+    }
+}
+
+/// The fields of a dataclass registered with `register_type()`: the public
+/// fields in declaration order with their keys already escaped.
+pub(crate) struct DataclassPlan {
+    fields: Vec<PlanField>,
+    keys: Vec<u8>,
+    slots: bool,
+}
+
+impl DataclassPlan {
+    /// The plan of the dataclass `ob_type`, or `None` if it has no
+    /// `__dataclass_fields__` dict.
+    pub fn new(ob_type: *mut PyObject) -> Option<Self> {
+        let interpreter_state = unsafe { crate::runtime::current_state() };
+        let fields = ffi!(PyObject_GetAttr(
+            ob_type,
+            crate::typeref::get_dataclass_fields_str_from_state(interpreter_state)
+        ));
+        if fields.is_null() || !is_type!(ob_type!(fields), crate::typeref::dict_type_ptr()) {
+            cold_path!();
+            ffi!(PyErr_Clear());
+            ffi!(Py_XDECREF(fields));
+            return None;
+        }
+        let mut plan = DataclassPlan {
+            fields: Vec::with_capacity(isize_to_usize(ffi!(Py_SIZE(fields)))),
+            keys: Vec::new(),
+            slots: pydict_contains!(
+                ob_type.cast::<crate::ffi::PyTypeObject>(),
+                crate::typeref::get_slots_str_from_state(interpreter_state)
+            ),
+        };
+        let mut pos = 0;
+        let mut name: *mut PyObject = null_mut();
+        let mut field: *mut PyObject = null_mut();
+        while ffi!(PyDict_Next(fields, &mut pos, &mut name, &mut field)) == 1 {
+            let field_type = ffi!(PyObject_GetAttr(
+                field,
+                crate::typeref::get_field_type_str()
+            ));
+            ffi!(Py_XDECREF(field_type));
+            if !core::ptr::eq(
+                field_type.cast::<crate::ffi::PyTypeObject>(),
+                crate::typeref::get_field_type(),
+            ) {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            if !is_type!(ob_type!(name), crate::typeref::str_type_ptr()) {
+                continue;
+            }
+            let uni = match unsafe { PyStr::from_ptr_unchecked(name).to_str() } {
+                Some(uni) if !uni.starts_with('_') => uni,
+                _ => continue,
+            };
+            let annotation = ffi!(PyObject_GetAttrString(field, c"type".as_ptr()));
+            let kind = if annotation.is_null() {
+                ffi!(PyErr_Clear());
+                FieldKind::Any
+            } else {
+                let kind = FieldKind::of(annotation);
+                ffi!(Py_DECREF(annotation));
+                kind
+            };
+            let start = plan.keys.len();
+            format_escaped_str_to_vec(&mut plan.keys, uni, false);
+            let ascii_start = plan.keys.len();
+            format_escaped_str_to_vec(&mut plan.keys, uni, true);
+            ffi!(Py_INCREF(name));
+            plan.fields.push(PlanField {
+                name: name,
+                kind: kind,
+                key: start..ascii_start,
+                ascii_key: ascii_start..plan.keys.len(),
+            });
+        }
+        ffi!(Py_DECREF(fields));
+        Some(plan)
+    }
+
+    /// Whether the public keys of the instance `__dict__` are exactly the
+    /// fields of the plan in order, so that writing the fields writes what
+    /// writing `dict` would.
+    fn matches_dict(&self, dict: *mut PyObject) -> bool {
+        let mut idx = 0;
+        let mut pos = 0;
+        let mut key: *mut PyObject = null_mut();
+        let mut value: *mut PyObject = null_mut();
+        while ffi!(PyDict_Next(dict, &mut pos, &mut key, &mut value)) == 1 {
+            let field = self.fields.get(idx);
+            if field.is_some_and(|field| core::ptr::eq(field.name, key)) {
+                idx += 1;
+                continue;
+            }
+            if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                return false;
+            }
+            let uni = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                Some(uni) => uni,
+                None => return false,
+            };
+            if uni.starts_with('_') {
+                // private keys are neither written nor fields of the plan
+                continue;
+            }
+            match field {
+                Some(field)
+                    if unsafe { PyStr::from_ptr_unchecked(field.name).to_str() } == Some(uni) =>
+                {
+                    idx += 1;
+                }
+                _ => return false,
+            }
+        }
+        idx == self.fields.len()
+    }
+
+    /// Wrap the plan in a capsule that frees it.
+    pub fn into_capsule(self) -> *mut PyObject {
+        let ptr = Box::into_raw(Box::new(self));
+        let capsule = ffi!(PyCapsule_New(
+            ptr.cast::<core::ffi::c_void>(),
+            CAPSULE_NAME.as_ptr(),
+            Some(free_capsule)
+        ));
+        if capsule.is_null() {
+            cold_path!();
+            drop(unsafe { Box::from_raw(ptr) });
+        }
+        capsule
+    }
+
+    /// The plan in a capsule made by `into_capsule()`.
+    ///
+    /// # Safety
+    ///
+    /// The capsule must outlive the reference.
+    unsafe fn from_capsule<'a>(capsule: *mut PyObject) -> &'a Self {
+        unsafe {
+            &*ffi!(PyCapsule_GetPointer(capsule, CAPSULE_NAME.as_ptr())).cast::<DataclassPlan>()
+        }
+    }
+}
+
+impl Drop for DataclassPlan {
+    fn drop(&mut self) {
+        for field in self.fields.iter() {
+            ffi!(Py_DECREF(field.name));
+        }
+    }
+}
+
+unsafe extern "C" fn free_capsule(capsule: *mut PyObject) {
+    unsafe {
+        let ptr = crate::ffi::PyCapsule_GetPointer(capsule, CAPSULE_NAME.as_ptr());
+        drop(Box::from_raw(ptr.cast::<DataclassPlan>()));
+    }
+}
+
+/// Serialize a dataclass with its `DataclassPlan`: the declared fields are
+/// read by name and those annotated as `str`, `int`, `float`, or `bool` are
+/// written without dispatching on their type when they are one.
+pub(crate) struct DataclassPlanSerializer {
+    ptr: *mut PyObject,
+    capsule: *mut PyObject,
+    state: SerializerState,
+    default: Option<NonNull<PyObject>>,
+}
+
+impl DataclassPlanSerializer {
+    pub fn new(
+        ptr: *mut PyObject,
+        capsule: NonNull<PyObject>,
+        state: SerializerState,
+        default: Option<NonNull<PyObject>>,
+    ) -> Self {
+        DataclassPlanSerializer {
+            ptr: ptr,
+            capsule: capsule.as_ptr(),
+            state: state.copy_for_recursive_call(),
+            default: default,
+        }
+    }
+
+    /// A new reference to the value of `field`, or `None` if it is not set.
+    #[inline(always)]
+    fn value(&self, dict: *mut PyObject, field: &PlanField) -> Option<*mut PyObject> {
+        if !dict.is_null() {
+            let value = ffi!(PyDict_GetItem(dict, field.name));
+            if !value.is_null() {
+                ffi!(Py_INCREF(value));
+                return Some(value);
+            }
+        }
+        let value = ffi!(PyObject_GetAttr(self.ptr, field.name));
+        if value.is_null() {
+            cold_path!();
+            ffi!(PyErr_Clear());
+            return None;
+        }
+        Some(value)
+    }
+
+    #[inline(always)]
+    fn serialize_field<M>(
+        &self,
+        map: &mut M,
+        key: &PlanKey,
+        kind: FieldKind,
+        value: *mut PyObject,
+    ) -> Result<(), M::Error>
+    where
+        M: SerializeMap,
+    {
+        let opts = self.state.opts();
+        if omit_value(value, opts) {
+            return Ok(());
+        }
+        if kind.matches(value) && opt_disabled!(opts, EXTENDED_JSON) {
+            map.serialize_key(key).unwrap();
+            return match kind {
+                FieldKind::Str => map.serialize_value(&StrSerializer::new(value)),
+                FieldKind::Int => map.serialize_value(&IntSerializer::new(value, opts)),
+                FieldKind::Float => map.serialize_value(&FloatSerializer::new(value)),
+                FieldKind::Bool => map.serialize_value(&BoolSerializer::new(value)),
+                FieldKind::Any => unreachable!(),
+            };
+        }
+        let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
+        if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+            if resolved.skip() {
+                return Ok(());
+            }
+            map.serialize_key(key).unwrap();
+            return map.serialize_value(&resolved);
+        }
+        map.serialize_key(key).unwrap();
+        map.serialize_value(&pyvalue)
+    }
+}
+
+impl Serialize for DataclassPlanSerializer {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // the capsule is referenced so that the plan outlives registering
+        // the type again while serializing
+        ffi!(Py_INCREF(self.capsule));
+        let plan = unsafe { DataclassPlan::from_capsule(self.capsule) };
+        let dict = if plan.slots {
+            null_mut()
+        } else {
+            let dict = ffi!(PyObject_GetAttr(
+                self.ptr,
+                crate::typeref::get_dict_str_from_state(self.state.interpreter_state())
+            ));
+            if dict.is_null() {
+                cold_path!();
+                ffi!(PyErr_Clear());
+            }
+            dict
+        };
+        if !dict.is_null() && self.state.opts() & FIELD_ORDER == 0 && !plan.matches_dict(dict) {
+            // insertion order writes the instance `__dict__`, which has
+            // attributes other than the fields
+            cold_path!();
+            let ret = serialize_instance_dict(dict, self.state, self.default, serializer);
+            ffi!(Py_DECREF(dict));
+            ffi!(Py_DECREF(self.capsule));
+            return ret;
+        }
+        let ensure_ascii = opt_enabled!(self.state.opts(), ENSURE_ASCII);
+        let mut ret = Ok(());
+        let mut map = serializer.serialize_map(None).unwrap();
+        for field in plan.fields.iter() {
+            let value = match self.value(dict, field) {
+                Some(value) => value,
+                None => continue,
+            };
+            let key = PlanKey {
+                name: field.name,
+                escaped: if ensure_ascii {
+                    &plan.keys[field.ascii_key.clone()]
+                } else {
+                    &plan.keys[field.key.clone()]
+                },
+            };
+            ret = self.serialize_field(&mut map, &key, field.kind, value);
+            ffi!(Py_DECREF(value));
+            if ret.is_err() {
+                break;
+            }
+        }
+        ffi!(Py_XDECREF(dict));
+        ffi!(Py_DECREF(self.capsule));
+        match ret {
+            Ok(()) => map.end(),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Whether `ob_type` is a dataclass, for `register_type()`.
+pub(crate) fn is_dataclass(ob_type: *mut PyObject) -> bool {
+    pydict_contains!(
+        ob_type.cast::<crate::ffi::PyTypeObject>(),
+        crate::typeref::get_dataclass_fields_str_from_state(unsafe {
+            crate::runtime::current_state()
+        })
+    )
+}
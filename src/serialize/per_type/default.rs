@@ -1,9 +1,16 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
+use crate::opt::{DEFAULT_PATH, NULL_UNSUPPORTED, SKIP_UNSUPPORTED};
 use crate::serialize::error::SerializeError;
+use crate::serialize::obtype::{ObType, pyobject_to_obtype};
+use crate::serialize::path::PendingPath;
+use crate::serialize::per_type::{NumpyScalar, NumpySerializer, is_numpy_array, is_numpy_scalar};
+use crate::serialize::registry::{lookup_serializer, lookup_type_mapping};
 use crate::serialize::serializer::PyObjectSerializer;
+use crate::typeref::numpy_is_imported;
 
+use core::ptr::NonNull;
 use serde::ser::{Serialize, Serializer};
 
 #[repr(transparent)]
@@ -24,44 +31,246 @@ impl Serialize for DefaultSerializer<'_> {
     where
         S: Serializer,
     {
-        match self.previous.default {
-            Some(callable) => {
-                if self.previous.state.default_calls_limit() {
-                    cold_path!();
-                    err!(SerializeError::DefaultRecursionLimit)
+        ResolvedDefault::new(self.previous).serialize(serializer)
+    }
+}
+
+enum Replacement {
+    /// A new reference to the object returned by the callable, and the path
+    /// of the replaced object if the callable was `default` and
+    /// `OPT_DEFAULT_PATH` is set.
+    Object(NonNull<crate::ffi::PyObject>, Option<PendingPath>),
+    /// There is no callable for the type or it raised, in which case the
+    /// exception is set.
+    Unsupported,
+    /// There is no callable for a numpy scalar or array, which is written
+    /// because numpy is imported.
+    NumpyScalar,
+    NumpyArray,
+    RecursionLimit,
+}
+
+/// An object of a type that is not natively supported, with the callable
+/// registered for its type or `default` already called.
+///
+/// Containers resolve the object before writing its key or separator so
+/// that, with `OPT_SKIP_UNSUPPORTED`, it can be left out.
+pub(crate) struct ResolvedDefault<'a> {
+    previous: &'a PyObjectSerializer,
+    replacement: Replacement,
+}
+
+impl<'a> ResolvedDefault<'a> {
+    #[cold]
+    #[inline(never)]
+    pub fn new(previous: &'a PyObjectSerializer) -> Self {
+        ResolvedDefault {
+            previous: previous,
+            replacement: replace(previous),
+        }
+    }
+
+    /// Return whether the object is left out of its container, clearing
+    /// the `TypeError` raised by the callable, if any.
+    #[inline]
+    pub fn skip(&self) -> bool {
+        if opt_enabled!(self.previous.state.opts(), SKIP_UNSUPPORTED) && self.is_unsupported() {
+            ffi!(PyErr_Clear());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether there is no callable for the object or it raised `TypeError`,
+    /// as opposed to failing in some other way, which is propagated even
+    /// with `OPT_SKIP_UNSUPPORTED` or `OPT_NULL_UNSUPPORTED`.
+    fn is_unsupported(&self) -> bool {
+        matches!(self.replacement, Replacement::Unsupported)
+            && (ffi!(PyErr_Occurred()).is_null()
+                || ffi!(PyErr_ExceptionMatches(crate::ffi::PyExc_TypeError)) != 0)
+    }
+}
+
+/// With `OPT_SKIP_UNSUPPORTED`, resolve `previous` if it is of a type that
+/// is not natively supported, for containers that otherwise serialize their
+/// values with `PyObjectSerializer`.
+#[inline(always)]
+pub(crate) fn resolve_if_unsupported(previous: &PyObjectSerializer) -> Option<ResolvedDefault<'_>> {
+    let opts = previous.state.opts();
+    if opt_disabled!(opts, SKIP_UNSUPPORTED) {
+        return None;
+    }
+    match pyobject_to_obtype(previous.ptr, opts, previous.state.interpreter_state()) {
+        ObType::Unknown => Some(ResolvedDefault::new(previous)),
+        _ => None,
+    }
+}
+
+impl Drop for ResolvedDefault<'_> {
+    fn drop(&mut self) {
+        if let Replacement::Object(obj, _) = self.replacement {
+            ffi!(Py_DECREF(obj.as_ptr()));
+        }
+    }
+}
+
+impl Serialize for ResolvedDefault<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.replacement {
+            Replacement::Object(obj, path) => {
+                if let Some(path) = path {
+                    path.push(obj.as_ptr());
                 }
-                #[cfg(not(Py_3_10))]
-                let default_obj = ffi!(PyObject_CallFunctionObjArgs(
-                    callable.as_ptr(),
-                    self.previous.ptr,
-                    core::ptr::null_mut::<crate::ffi::PyObject>()
-                ));
-                #[cfg(Py_3_10)]
-                #[allow(clippy::cast_sign_loss)]
-                let nargs = ffi!(PyVectorcall_NARGS(1)) as usize;
-                #[cfg(Py_3_10)]
-                let default_obj = unsafe {
-                    crate::ffi::PyObject_Vectorcall(
-                        callable.as_ptr(),
-                        &raw const self.previous.ptr,
-                        nargs,
-                        core::ptr::null_mut(),
-                    )
-                };
-                if default_obj.is_null() {
+                let res = PyObjectSerializer::new(
+                    obj.as_ptr(),
+                    self.previous.state.copy_for_default_call(),
+                    self.previous.default,
+                )
+                .serialize(serializer);
+                if path.is_some() {
+                    PendingPath::pop();
+                }
+                res
+            }
+            Replacement::Unsupported => {
+                if opt_enabled!(self.previous.state.opts(), NULL_UNSUPPORTED)
+                    && self.is_unsupported()
+                {
+                    ffi!(PyErr_Clear());
+                    serializer.serialize_unit()
+                } else if serializer.is_human_readable() {
                     err!(SerializeError::UnsupportedType(nonnull!(self.previous.ptr)))
                 } else {
-                    let res = PyObjectSerializer::new(
-                        default_obj,
-                        self.previous.state.copy_for_default_call(),
-                        self.previous.default,
-                    )
-                    .serialize(serializer);
-                    ffi!(Py_DECREF(default_obj));
+                    err!(SerializeError::UnsupportedMsgpackType(nonnull!(
+                        self.previous.ptr
+                    )))
+                }
+            }
+            Replacement::NumpyScalar => {
+                NumpyScalar::new(self.previous.ptr, self.previous.state.opts())
+                    .serialize(serializer)
+            }
+            Replacement::NumpyArray => {
+                // default has already been found not to handle the array
+                let previous =
+                    PyObjectSerializer::new(self.previous.ptr, self.previous.state, None);
+                NumpySerializer::new(&previous).serialize(serializer)
+            }
+            Replacement::RecursionLimit => err!(SerializeError::DefaultRecursionLimit),
+        }
+    }
+}
+
+fn replace(previous: &PyObjectSerializer) -> Replacement {
+    // callables registered with register_serializer() take precedence
+    if let Some(callable) =
+        lookup_serializer(previous.state.interpreter_state(), ob_type!(previous.ptr))
+    {
+        let res = replace_with(previous, callable, false);
+        ffi!(Py_DECREF(callable.as_ptr()));
+        return res;
+    }
+    let with_path = opt_enabled!(previous.state.opts(), DEFAULT_PATH);
+    match previous.default {
+        Some(mapping) if is_type!(ob_type!(mapping.as_ptr()), crate::typeref::dict_type_ptr()) => {
+            match lookup_type_mapping(mapping.as_ptr(), ob_type!(previous.ptr)) {
+                Some(callable) => {
+                    let res = replace_with(previous, callable, with_path);
+                    ffi!(Py_DECREF(callable.as_ptr()));
                     res
                 }
+                None => unsupported_or_numpy(previous),
             }
-            None => err!(SerializeError::UnsupportedType(nonnull!(self.previous.ptr))),
         }
+        Some(callable) => replace_with(previous, callable, with_path),
+        None => unsupported_or_numpy(previous),
     }
 }
+
+/// Without `OPT_SERIALIZE_NUMPY`, numpy types are written once numpy is
+/// imported, but only if `default` does not handle them.
+fn unsupported_or_numpy(previous: &PyObjectSerializer) -> Replacement {
+    let interpreter_state = previous.state.interpreter_state();
+    if numpy_is_imported(interpreter_state) {
+        let ob_type = ob_type!(previous.ptr);
+        if is_numpy_scalar(ob_type, interpreter_state) {
+            return Replacement::NumpyScalar;
+        } else if is_numpy_array(ob_type, interpreter_state) {
+            return Replacement::NumpyArray;
+        }
+    }
+    Replacement::Unsupported
+}
+
+/// Call `callable` with the object and, if `with_path`, its path in the
+/// document.
+fn replace_with(
+    previous: &PyObjectSerializer,
+    callable: NonNull<crate::ffi::PyObject>,
+    with_path: bool,
+) -> Replacement {
+    if previous.state.default_calls_limit() {
+        cold_path!();
+        return Replacement::RecursionLimit;
+    }
+    let path = if with_path {
+        PendingPath::find(previous.ptr, previous.state.interpreter_state())
+    } else {
+        None
+    };
+    let default_obj = match path.as_ref() {
+        Some(path) => match path.to_list() {
+            Some(path_list) => {
+                let args = [previous.ptr, path_list.as_ptr()];
+                let default_obj = call(callable, &args);
+                ffi!(Py_DECREF(path_list.as_ptr()));
+                default_obj
+            }
+            None => return Replacement::Unsupported,
+        },
+        None => call(callable, &[previous.ptr]),
+    };
+    match NonNull::new(default_obj) {
+        Some(obj) => Replacement::Object(obj, path),
+        None => Replacement::Unsupported,
+    }
+}
+
+/// Call `callable` with the positional `args`.
+fn call(
+    callable: NonNull<crate::ffi::PyObject>,
+    args: &[*mut crate::ffi::PyObject],
+) -> *mut crate::ffi::PyObject {
+    #[cfg(not(Py_3_10))]
+    let default_obj = if args.len() == 1 {
+        ffi!(PyObject_CallFunctionObjArgs(
+            callable.as_ptr(),
+            args[0],
+            core::ptr::null_mut::<crate::ffi::PyObject>()
+        ))
+    } else {
+        ffi!(PyObject_CallFunctionObjArgs(
+            callable.as_ptr(),
+            args[0],
+            args[1],
+            core::ptr::null_mut::<crate::ffi::PyObject>()
+        ))
+    };
+    #[cfg(Py_3_10)]
+    #[allow(clippy::cast_sign_loss)]
+    let nargs = ffi!(PyVectorcall_NARGS(args.len())) as usize;
+    #[cfg(Py_3_10)]
+    let default_obj = unsafe {
+        crate::ffi::PyObject_Vectorcall(
+            callable.as_ptr(),
+            args.as_ptr(),
+            nargs,
+            core::ptr::null_mut(),
+        )
+    };
+    default_obj
+}
@@ -1,19 +1,29 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025), Aviram Hassan (2020)
 
-use crate::opt::{NON_STR_KEYS, NOT_PASSTHROUGH, SORT_KEYS, SORT_OR_NON_STR_KEYS};
+use crate::opt::{
+    BYTES_KEYS_BASE64, BYTES_KEYS_LATIN1, ENSURE_ASCII, EXTENDED_JSON, KEY_ORDER, MSGPACK,
+    NON_FINITE, NON_FINITE_RAISE, NON_STR_KEYS, NOT_PASSTHROUGH, OMIT_EMPTY_DICT, OMIT_EMPTY_LIST,
+    OMIT_EMPTY_STR, OMIT_NULL, SORT_KEYS, SORT_OR_NON_STR_KEYS,
+};
+use crate::serialize::base64;
 use crate::serialize::buffer::SmallFixedBuffer;
 use crate::serialize::error::SerializeError;
+use crate::serialize::key_order::{NaturalKey, sort_items, sort_items_natural};
 use crate::serialize::obtype::{ObType, pyobject_to_obtype};
 use crate::serialize::per_type::datetimelike::DateTimeLike;
 use crate::serialize::per_type::{
-    BoolSerializer, DataclassGenericSerializer, Date, DateTime, DefaultSerializer, EnumSerializer,
-    FloatSerializer, FragmentSerializer, IntSerializer, ListTupleSerializer, NoneSerializer,
-    NumpyScalar, NumpySerializer, StrSerializer, StrSubclassSerializer, Time, UUID,
-    ZeroListSerializer,
+    ArrowSerializer, BoolSerializer, BytesSerializer, DataclassGenericSerializer, Date, DateTime,
+    EnumSerializer, ExtendedJsonSerializer, FloatSerializer, FragmentSerializer, IntSerializer,
+    ListTupleSerializer, NamedTupleSerializer, NoneSerializer, NumpyScalar, NumpySerializer,
+    PandasSerializer, RawNumberSerializer, ResolvedDefault, StrSerializer, StrSubclassSerializer,
+    TaggedSerializer, Time, UUID, ZeroListSerializer, omit_empty, omit_value,
+    resolve_if_unsupported,
 };
 use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::snapshot::{self, DictEntries, Snapshot};
 use crate::serialize::state::SerializerState;
+use crate::serialize::writer::format_escaped_str_to_vec;
 use crate::str::{PyStr, PyStrSubclass};
 // STR_TYPE, TRUE, VALUE_STR now accessed via typeref accessor functions
 use crate::util::isize_to_usize;
@@ -35,14 +45,17 @@ impl Serialize for ZeroDictSerializer {
     where
         S: Serializer,
     {
-        serializer.serialize_bytes(b"{}")
+        if serializer.is_human_readable() {
+            serializer.serialize_bytes(b"{}")
+        } else {
+            serializer.serialize_map(Some(0))?.end()
+        }
     }
 }
 
 pub(crate) struct DictGenericSerializer {
     ptr: *mut crate::ffi::PyObject,
     state: SerializerState,
-    #[allow(dead_code)]
     default: Option<NonNull<crate::ffi::PyObject>>,
 }
 
@@ -71,10 +84,36 @@ impl Serialize for DictGenericSerializer {
             err!(SerializeError::RecursionLimit)
         }
 
-        if ffi!(Py_SIZE(self.ptr)) == 0 {
+        if pydict_len!(self.state.compat_mode(), self.ptr) == 0 {
             cold_path!();
             ZeroDictSerializer::new().serialize(serializer)
-        } else if opt_disabled!(self.state.opts(), SORT_OR_NON_STR_KEYS) {
+        } else if snapshot::is_required(self.state.opts(), self.default) {
+            if opt_disabled!(self.state.opts(), SORT_OR_NON_STR_KEYS) {
+                return unsafe {
+                    (*(core::ptr::from_ref::<DictGenericSerializer>(self)).cast::<DictHeld>())
+                        .serialize(serializer)
+                };
+            }
+            let copy = Snapshot::of_dict(self.ptr);
+            DictGenericSerializer {
+                ptr: copy.as_ptr(),
+                state: self.state,
+                default: self.default,
+            }
+            .serialize_entries(serializer)
+        } else {
+            self.serialize_entries(serializer)
+        }
+    }
+}
+
+impl DictGenericSerializer {
+    #[inline(always)]
+    fn serialize_entries<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if opt_disabled!(self.state.opts(), SORT_OR_NON_STR_KEYS) {
             unsafe {
                 (*(core::ptr::from_ref::<DictGenericSerializer>(self)).cast::<Dict>())
                     .serialize(serializer)
@@ -97,6 +136,11 @@ macro_rules! impl_serialize_entry {
     ($map:expr, $self:expr, $key:expr, $value:expr) => {
         match pyobject_to_obtype($value, $self.state.opts(), $self.state.interpreter_state()) {
             ObType::Str => {
+                if opt_enabled!($self.state.opts(), OMIT_EMPTY_STR)
+                    && omit_empty($value, $self.state.opts())
+                {
+                    continue;
+                }
                 $map.serialize_key($key).unwrap();
                 $map.serialize_value(&StrSerializer::new($value))?;
             }
@@ -109,6 +153,9 @@ macro_rules! impl_serialize_entry {
                 $map.serialize_value(&IntSerializer::new($value, $self.state.opts()))?;
             }
             ObType::None => {
+                if opt_enabled!($self.state.opts(), OMIT_NULL) {
+                    continue;
+                }
                 $map.serialize_key($key).unwrap();
                 $map.serialize_value(&NoneSerializer::new()).unwrap();
             }
@@ -137,12 +184,20 @@ macro_rules! impl_serialize_entry {
                 $map.serialize_value(&UUID::new($value)).unwrap();
             }
             ObType::Dict => {
+                if opt_enabled!($self.state.opts(), OMIT_EMPTY_DICT)
+                    && omit_empty($value, $self.state.opts())
+                {
+                    continue;
+                }
                 let pyvalue = DictGenericSerializer::new($value, $self.state, $self.default);
                 $map.serialize_key($key).unwrap();
                 $map.serialize_value(&pyvalue)?;
             }
             ObType::List => {
                 if ffi!(Py_SIZE($value)) == 0 {
+                    if opt_enabled!($self.state.opts(), OMIT_EMPTY_LIST) {
+                        continue;
+                    }
                     $map.serialize_key($key).unwrap();
                     $map.serialize_value(&ZeroListSerializer::new()).unwrap();
                 } else {
@@ -154,6 +209,9 @@ macro_rules! impl_serialize_entry {
             }
             ObType::Tuple => {
                 if ffi!(Py_SIZE($value)) == 0 {
+                    if opt_enabled!($self.state.opts(), OMIT_EMPTY_LIST) {
+                        continue;
+                    }
                     $map.serialize_key($key).unwrap();
                     $map.serialize_value(&ZeroListSerializer::new()).unwrap();
                 } else {
@@ -191,18 +249,64 @@ macro_rules! impl_serialize_entry {
                 $map.serialize_key($key).unwrap();
                 $map.serialize_value(&NumpyScalar::new($value, $self.state.opts()))?;
             }
+            ObType::Pandas => {
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&PandasSerializer::new(&PyObjectSerializer::new(
+                    $value,
+                    $self.state,
+                    $self.default,
+                )))?;
+            }
+            ObType::Arrow => {
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&ArrowSerializer::new(&PyObjectSerializer::new(
+                    $value,
+                    $self.state,
+                    $self.default,
+                )))?;
+            }
             ObType::Fragment => {
                 $map.serialize_key($key).unwrap();
                 $map.serialize_value(&FragmentSerializer::new($value))?;
             }
-            ObType::Unknown => {
+            ObType::RawNumber => {
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&RawNumberSerializer::new($value))?;
+            }
+            ObType::NamedTuple => {
+                let pyvalue = NamedTupleSerializer::new($value, $self.state, $self.default);
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&pyvalue)?;
+            }
+            ObType::Tagged => {
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&TaggedSerializer::new(&PyObjectSerializer::new(
+                    $value,
+                    $self.state,
+                    $self.default,
+                )))?;
+            }
+            ObType::ExtendedJson => {
                 $map.serialize_key($key).unwrap();
-                $map.serialize_value(&DefaultSerializer::new(&PyObjectSerializer::new(
+                $map.serialize_value(&ExtendedJsonSerializer::new(&PyObjectSerializer::new(
                     $value,
                     $self.state,
                     $self.default,
                 )))?;
             }
+            ObType::Bytes => {
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&BytesSerializer::new($value))?;
+            }
+            ObType::Unknown => {
+                let pyvalue = PyObjectSerializer::new($value, $self.state, $self.default);
+                let resolved = ResolvedDefault::new(&pyvalue);
+                if resolved.skip() {
+                    continue;
+                }
+                $map.serialize_key($key).unwrap();
+                $map.serialize_value(&resolved)?;
+            }
         }
     };
 }
@@ -219,22 +323,23 @@ impl Serialize for Dict {
     where
         S: Serializer,
     {
+        let compat = self.state.compat_mode();
         let mut pos = 0;
         let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
         let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
 
-        pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+        pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
         let mut map = serializer.serialize_map(None).unwrap();
 
-        let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
+        let len = isize_to_usize(pydict_len!(compat, self.ptr));
         assume!(len > 0);
 
         for _ in 0..len {
             let key = next_key;
             let value = next_value;
 
-            pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+            pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
             // key - use direct CPython global for str type (zero indirection)
             let key_ob_type = ob_type!(key);
@@ -256,27 +361,281 @@ impl Serialize for Dict {
     }
 }
 
-pub(crate) struct DictSortedKey {
+/// A dict that a callback may change, holding each entry while it is
+/// written.
+pub(crate) struct DictHeld {
     ptr: *mut crate::ffi::PyObject,
     state: SerializerState,
     default: Option<NonNull<crate::ffi::PyObject>>,
 }
 
-impl Serialize for DictSortedKey {
+impl Serialize for DictHeld {
     #[inline(never)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        let mut map = serializer.serialize_map(None).unwrap();
+
+        for (held_key, held_value) in DictEntries::new(self.ptr, self.state.compat_mode()) {
+            let key = held_key.as_ptr();
+            let value = held_value.as_ptr();
+
+            if !is_class_by_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                err!(SerializeError::KeyMustBeStr)
+            }
+            let key_as_str = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                Some(uni) => uni,
+                None => err!(SerializeError::InvalidStr),
+            };
+
+            impl_serialize_entry!(map, self, key_as_str, value);
+        }
+
+        map.end()
+    }
+}
+
+/// The keys object of a split-table (PEP 412) dict, such as an instance
+/// `__dict__`, or null if the dict has a combined table.
+#[inline(always)]
+fn shared_keys_of(ptr: *mut crate::ffi::PyObject) -> *mut core::ffi::c_void {
+    #[cfg(CPython)]
+    unsafe {
+        let dict = ptr.cast::<crate::ffi::PyDictObject>();
+        if (*dict).ma_values.is_null() {
+            core::ptr::null_mut()
+        } else {
+            (*dict).ma_keys.cast::<core::ffi::c_void>()
+        }
+    }
+    #[cfg(not(CPython))]
+    {
+        let _ = ptr;
+        core::ptr::null_mut()
+    }
+}
+
+struct SharedKeyEntry {
+    key: *mut crate::ffi::PyObject,
+    start: usize,
+    len: usize,
+}
+
+/// Pre-escaped keys of the dicts in a list that share one keys object, so
+/// each key is escaped once rather than once per row.
+pub(crate) struct SharedKeys {
+    keys: *mut core::ffi::c_void,
+    candidate: *mut core::ffi::c_void,
+    entries: SmallVec<[SharedKeyEntry; 8]>,
+    buf: Vec<u8>,
+}
+
+impl SharedKeys {
+    pub fn new() -> Self {
+        SharedKeys {
+            keys: core::ptr::null_mut(),
+            candidate: core::ptr::null_mut(),
+            entries: SmallVec::new(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Return whether `ptr` shares the keys object that is prepared,
+    /// preparing it from `ptr` only when a second dict with the same keys
+    /// object is seen.
+    #[inline]
+    pub fn accept(&mut self, ptr: *mut crate::ffi::PyObject, opts: crate::opt::Opt) -> bool {
+        if opt_enabled!(opts, SORT_OR_NON_STR_KEYS) || ffi!(Py_SIZE(ptr)) == 0 {
+            return false;
+        }
+        let keys = shared_keys_of(ptr);
+        if keys.is_null() {
+            false
+        } else if keys == self.keys {
+            true
+        } else if keys == self.candidate {
+            self.prepare(ptr, keys, opt_enabled!(opts, ENSURE_ASCII))
+        } else {
+            self.candidate = keys;
+            false
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn prepare(
+        &mut self,
+        ptr: *mut crate::ffi::PyObject,
+        keys: *mut core::ffi::c_void,
+        ensure_ascii: bool,
+    ) -> bool {
+        self.clear();
+        let mut pos = 0;
+        let mut key: *mut crate::ffi::PyObject = core::ptr::null_mut();
+        let mut value: *mut crate::ffi::PyObject = core::ptr::null_mut();
+        while pydict_next!(ptr, &mut pos, &mut key, &mut value) == 1 {
+            if !is_class_by_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                self.clear();
+                return false;
+            }
+            let uni = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                Some(uni) => uni,
+                None => {
+                    self.clear();
+                    return false;
+                }
+            };
+            let start = self.buf.len();
+            format_escaped_str_to_vec(&mut self.buf, uni, ensure_ascii);
+            ffi!(Py_INCREF(key));
+            self.entries.push(SharedKeyEntry {
+                key: key,
+                start: start,
+                len: self.buf.len() - start,
+            });
+        }
+        self.keys = keys;
+        self.candidate = core::ptr::null_mut();
+        true
+    }
+
+    fn clear(&mut self) {
+        for entry in self.entries.iter() {
+            ffi!(Py_DECREF(entry.key));
+        }
+        self.entries.clear();
+        self.buf.clear();
+        self.keys = core::ptr::null_mut();
+    }
+
+    /// The escaped form of `key`, checking the entry at `hint` first since
+    /// rows usually iterate in the same order.
+    #[inline(always)]
+    fn get(&self, key: *mut crate::ffi::PyObject, hint: usize) -> Option<&[u8]> {
+        let entry = match self.entries.get(hint) {
+            Some(entry) if entry.key == key => entry,
+            _ => self.entries.iter().find(|entry| entry.key == key)?,
+        };
+        Some(&self.buf[entry.start..entry.start + entry.len])
+    }
+}
+
+impl Drop for SharedKeys {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// A key that is already quoted and escaped.
+#[repr(transparent)]
+pub(crate) struct EscapedKey<'a>(pub &'a [u8]);
+
+impl Serialize for EscapedKey<'_> {
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// A dict whose keys object was accepted by `SharedKeys`.
+pub(crate) struct DictSharedKeys<'a> {
+    ptr: *mut crate::ffi::PyObject,
+    state: SerializerState,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    keys: &'a SharedKeys,
+}
+
+impl<'a> DictSharedKeys<'a> {
+    pub fn new(
+        ptr: *mut crate::ffi::PyObject,
+        state: SerializerState,
+        default: Option<NonNull<crate::ffi::PyObject>>,
+        keys: &'a SharedKeys,
+    ) -> Self {
+        DictSharedKeys {
+            ptr: ptr,
+            state: state.copy_for_recursive_call(),
+            default: default,
+            keys: keys,
+        }
+    }
+}
+
+impl Serialize for DictSharedKeys<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.state.recursion_limit() {
+            cold_path!();
+            err!(SerializeError::RecursionLimit)
+        }
+
         let mut pos = 0;
         let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
         let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
 
         pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
 
+        let mut map = serializer.serialize_map(None).unwrap();
+
         let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
         assume!(len > 0);
 
+        for idx in 0..len {
+            let key = next_key;
+            let value = next_value;
+
+            pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+
+            match self.keys.get(key, idx) {
+                Some(escaped) => {
+                    impl_serialize_entry!(map, self, &EscapedKey(escaped), value);
+                }
+                None => {
+                    if !is_class_by_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                        err!(SerializeError::KeyMustBeStr)
+                    }
+                    let key_as_str = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                        Some(uni) => uni,
+                        None => err!(SerializeError::InvalidStr),
+                    };
+                    impl_serialize_entry!(map, self, key_as_str, value);
+                }
+            }
+        }
+
+        map.end()
+    }
+}
+
+pub(crate) struct DictSortedKey {
+    ptr: *mut crate::ffi::PyObject,
+    state: SerializerState,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+}
+
+impl Serialize for DictSortedKey {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let compat = self.state.compat_mode();
+        let mut pos = 0;
+        let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
+        let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
+
+        pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
+
+        let len = isize_to_usize(pydict_len!(compat, self.ptr));
+        assume!(len > 0);
+
         let mut items: SmallVec<[(&str, *mut crate::ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
 
@@ -284,7 +643,7 @@ impl Serialize for DictSortedKey {
             let key = next_key;
             let value = next_value;
 
-            pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+            pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
             // Use direct CPython global for str type (zero indirection)
             if unsafe { !core::ptr::eq(ob_type!(key), crate::typeref::str_type_ptr()) } {
@@ -297,14 +656,27 @@ impl Serialize for DictSortedKey {
             }
             let key_as_str = uni.unwrap();
 
+            if omit_value(value, self.state.opts()) {
+                continue;
+            }
             items.push((key_as_str, value));
         }
 
-        sort_dict_items(&mut items);
+        if let Err(err) = sort_items(&mut items, self.state.opts()) {
+            err!(err)
+        }
 
         let mut map = serializer.serialize_map(None).unwrap();
         for (key, val) in items.iter() {
             let pyvalue = PyObjectSerializer::new(*val, self.state, self.default);
+            if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+                if resolved.skip() {
+                    continue;
+                }
+                map.serialize_key(key).unwrap();
+                map.serialize_value(&resolved)?;
+                continue;
+            }
             map.serialize_key(key).unwrap();
             map.serialize_value(&pyvalue)?;
         }
@@ -315,22 +687,18 @@ impl Serialize for DictSortedKey {
 #[inline(never)]
 fn non_str_str(key: *mut crate::ffi::PyObject) -> Result<String, SerializeError> {
     // because of ObType::Enum
-    let uni = unsafe { PyStr::from_ptr_unchecked(key).to_str() };
-    if uni.is_none() {
-        Err(SerializeError::InvalidStr)
-    } else {
-        Ok(String::from(uni.unwrap()))
+    match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+        Some(uni) => Ok(String::from(uni)),
+        None => Err(SerializeError::InvalidStr),
     }
 }
 
 #[cold]
 #[inline(never)]
 fn non_str_str_subclass(key: *mut crate::ffi::PyObject) -> Result<String, SerializeError> {
-    let uni = unsafe { PyStrSubclass::from_ptr_unchecked(key).to_str() };
-    if uni.is_none() {
-        Err(SerializeError::InvalidStr)
-    } else {
-        Ok(String::from(uni.unwrap()))
+    match unsafe { PyStrSubclass::from_ptr_unchecked(key).to_str() } {
+        Some(uni) => Ok(String::from(uni)),
+        None => Err(SerializeError::InvalidStr),
     }
 }
 
@@ -381,13 +749,22 @@ fn non_str_uuid(key: *mut crate::ffi::PyObject) -> Result<String, SerializeError
     Ok(String::from(key_as_str))
 }
 
-#[allow(clippy::unnecessary_wraps)]
 #[cold]
 #[inline(never)]
-fn non_str_float(key: *mut crate::ffi::PyObject) -> Result<String, SerializeError> {
+fn non_str_float(
+    key: *mut crate::ffi::PyObject,
+    opts: crate::opt::Opt,
+) -> Result<String, SerializeError> {
     let val = ffi!(PyFloat_AS_DOUBLE(key));
     if !val.is_finite() {
-        Ok(String::from("null"))
+        // a key is a string already, so is the same with either
+        match opts & NON_FINITE {
+            0 => Ok(String::from("null")),
+            NON_FINITE_RAISE => Err(SerializeError::NonFiniteFloat),
+            _ if val.is_nan() => Ok(String::from("NaN")),
+            _ if val > 0.0 => Ok(String::from("Infinity")),
+            _ => Ok(String::from("-Infinity")),
+        }
     } else {
         Ok(String::from(ryu::Buffer::new().format_finite(val)))
     }
@@ -410,9 +787,85 @@ fn non_str_int(key: *mut crate::ffi::PyObject) -> Result<String, SerializeError>
     }
 }
 
+#[cold]
 #[inline(never)]
-fn sort_dict_items(items: &mut SmallVec<[(&str, *mut crate::ffi::PyObject); 8]>) {
-    items.sort_unstable_by(|a, b| a.0.cmp(b.0));
+fn non_str_bytes(
+    key: *mut crate::ffi::PyObject,
+    opts: crate::opt::Opt,
+) -> Result<String, SerializeError> {
+    let data = unsafe {
+        core::slice::from_raw_parts(
+            crate::ffi::PyBytes_AS_STRING(key).cast::<u8>(),
+            isize_to_usize(crate::ffi::PyBytes_GET_SIZE(key)),
+        )
+    };
+    if opt_enabled!(opts, BYTES_KEYS_BASE64) {
+        let mut key_as_str = String::new();
+        base64::encode_to_string(data, &mut key_as_str);
+        Ok(key_as_str)
+    } else if opt_enabled!(opts, BYTES_KEYS_LATIN1) {
+        Ok(data.iter().copied().map(char::from).collect())
+    } else {
+        match core::str::from_utf8(data) {
+            Ok(key_as_str) => Ok(String::from(key_as_str)),
+            Err(_) => Err(SerializeError::DictBytesKeyInvalidUtf8(nonnull!(key))),
+        }
+    }
+}
+
+/// Days from 0001-01-01 in the proleptic Gregorian calendar.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 306
+}
+
+#[cold]
+#[inline(never)]
+fn natural_datetime(key: *mut crate::ffi::PyObject, opts: crate::opt::Opt) -> NaturalKey {
+    let dt = DateTime::new(key, opts);
+    let Ok(offset) = dt.offset() else {
+        return NaturalKey::Text;
+    };
+    let seconds = days_from_civil(
+        i64::from(dt.year()),
+        i64::from(dt.month()),
+        i64::from(dt.day()),
+    ) * 86_400
+        + i64::from(dt.hour()) * 3_600
+        + i64::from(dt.minute()) * 60
+        + i64::from(dt.second())
+        - (i64::from(offset.day) * 86_400 + i64::from(offset.second));
+    NaturalKey::Instant(seconds * 1_000_000 + i64::from(dt.microsecond()))
+}
+
+#[cold]
+#[inline(never)]
+fn natural_date(key: *mut crate::ffi::PyObject) -> NaturalKey {
+    let days = days_from_civil(
+        i64::from(ffi!(PyDateTime_GET_YEAR(key))),
+        i64::from(ffi!(PyDateTime_GET_MONTH(key))),
+        i64::from(ffi!(PyDateTime_GET_DAY(key))),
+    );
+    NaturalKey::Instant(days * 86_400_000_000)
+}
+
+#[cold]
+#[inline(never)]
+fn natural_int(key: *mut crate::ffi::PyObject) -> NaturalKey {
+    // a key outside of 64 bits has already failed
+    let ival = ffi!(PyLong_AsLongLong(key));
+    let val = if ival == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        ffi!(PyErr_Clear());
+        i128::from(ffi!(PyLong_AsUnsignedLongLong(key)))
+    } else {
+        i128::from(ival)
+    };
+    #[allow(clippy::cast_precision_loss)]
+    NaturalKey::Number(val as f64, Some(val))
 }
 
 pub(crate) struct DictNonStrKey {
@@ -437,7 +890,7 @@ impl DictNonStrKey {
                 }
             }
             ObType::Int => non_str_int(key),
-            ObType::Float => non_str_float(key),
+            ObType::Float => non_str_float(key, opts),
             ObType::Datetime => non_str_datetime(key, opts),
             ObType::Date => non_str_date(key),
             ObType::Time => non_str_time(key, opts),
@@ -450,15 +903,55 @@ impl DictNonStrKey {
                 ret
             }
             ObType::Str => non_str_str(key),
-            ObType::StrSubclass => non_str_str_subclass(key),
+            ObType::StrSubclass | ObType::RawNumber => non_str_str_subclass(key),
+            // keys are written as without extended_json=
+            ObType::ExtendedJson => {
+                Self::pyobject_to_string(key, opts & !EXTENDED_JSON, interpreter_state)
+            }
+            ObType::Bytes => Self::pyobject_to_string(key, opts & !MSGPACK, interpreter_state),
             ObType::Tuple
             | ObType::NumpyScalar
             | ObType::NumpyArray
+            | ObType::Pandas
+            | ObType::Arrow
             | ObType::Dict
             | ObType::List
             | ObType::Dataclass
             | ObType::Fragment
-            | ObType::Unknown => Err(SerializeError::DictKeyInvalidType),
+            | ObType::NamedTuple
+            | ObType::Tagged => Err(SerializeError::DictKeyInvalidType),
+            ObType::Unknown => {
+                if is_type!(ob_type!(key), crate::typeref::bytes_type_ptr()) {
+                    non_str_bytes(key, opts)
+                } else {
+                    Err(SerializeError::DictKeyInvalidType)
+                }
+            }
+        }
+    }
+
+    /// The order of a key for `sort_items_natural()`, called after it has
+    /// been written as a string.
+    fn pyobject_to_natural_key(
+        key: *mut crate::ffi::PyObject,
+        opts: crate::opt::Opt,
+        interpreter_state: *const crate::interpreter_state::InterpreterState,
+    ) -> NaturalKey {
+        match pyobject_to_obtype(key, opts, interpreter_state) {
+            ObType::Int => natural_int(key),
+            ObType::Float => NaturalKey::Number(ffi!(PyFloat_AS_DOUBLE(key)), None),
+            ObType::ExtendedJson => {
+                Self::pyobject_to_natural_key(key, opts & !EXTENDED_JSON, interpreter_state)
+            }
+            ObType::Datetime => natural_datetime(key, opts),
+            ObType::Date => natural_date(key),
+            ObType::Enum => {
+                let value = ffi!(PyObject_GetAttr(key, crate::typeref::get_value_str()));
+                let ret = Self::pyobject_to_natural_key(value, opts, interpreter_state);
+                ffi!(Py_DECREF(value));
+                ret
+            }
+            _ => NaturalKey::Text,
         }
     }
 }
@@ -469,55 +962,85 @@ impl Serialize for DictNonStrKey {
     where
         S: Serializer,
     {
+        let compat = self.state.compat_mode();
         let mut pos = 0;
         let mut next_key: *mut crate::ffi::PyObject = core::ptr::null_mut();
         let mut next_value: *mut crate::ffi::PyObject = core::ptr::null_mut();
 
-        pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+        pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
         let opts = self.state.opts() & NOT_PASSTHROUGH;
 
-        let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
+        let len = isize_to_usize(pydict_len!(compat, self.ptr));
         assume!(len > 0);
 
-        let mut items: SmallVec<[(String, *mut crate::ffi::PyObject); 8]> =
+        // keys that were not str sort as their original value in the default
+        // order
+        let natural = opt_enabled!(opts, SORT_KEYS) && opts & KEY_ORDER == 0;
+
+        let mut items: SmallVec<[(String, NaturalKey, *mut crate::ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
 
         for _ in 0..len {
             let key = next_key;
             let value = next_value;
 
-            pydict_next!(self.ptr, &mut pos, &mut next_key, &mut next_value);
+            pydict_next_compat!(compat, self.ptr, &mut pos, &mut next_key, &mut next_value);
 
+            if omit_value(value, opts) {
+                continue;
+            }
             // Use direct CPython global for str type (zero indirection)
             if is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
                 match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
                     Some(uni) => {
-                        items.push((String::from(uni), value));
+                        items.push((String::from(uni), NaturalKey::Text, value));
                     }
                     None => err!(SerializeError::InvalidStr),
                 }
             } else {
-                match Self::pyobject_to_string(key, opts, self.state.interpreter_state()) {
-                    Ok(key_as_str) => items.push((key_as_str, value)),
+                let interpreter_state = self.state.interpreter_state();
+                match Self::pyobject_to_string(key, opts, interpreter_state) {
+                    Ok(key_as_str) => {
+                        let order = if natural {
+                            Self::pyobject_to_natural_key(key, opts, interpreter_state)
+                        } else {
+                            NaturalKey::Text
+                        };
+                        items.push((key_as_str, order, value));
+                    }
                     Err(err) => err!(err),
                 }
             }
         }
 
+        if natural {
+            sort_items_natural(&mut items);
+        }
+
         let mut items_as_str: SmallVec<[(&str, *mut crate::ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
         items
             .iter()
-            .for_each(|(key, val)| items_as_str.push(((*key).as_str(), *val)));
+            .for_each(|(key, _, val)| items_as_str.push(((*key).as_str(), *val)));
 
-        if opt_enabled!(opts, SORT_KEYS) {
-            sort_dict_items(&mut items_as_str);
+        if opt_enabled!(opts, SORT_KEYS) && !natural {
+            if let Err(err) = sort_items(&mut items_as_str, opts) {
+                err!(err)
+            }
         }
 
         let mut map = serializer.serialize_map(None).unwrap();
         for (key, val) in items_as_str.iter() {
             let pyvalue = PyObjectSerializer::new(*val, self.state, self.default);
+            if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+                if resolved.skip() {
+                    continue;
+                }
+                map.serialize_key(key).unwrap();
+                map.serialize_value(&resolved)?;
+                continue;
+            }
             map.serialize_key(key).unwrap();
             map.serialize_value(&pyvalue)?;
         }
@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `dumps(extended_json=)`, MongoDB Extended JSON v2.
+//!
+//! Relaxed mode writes numbers as `dumps()` does, except floats that are not
+//! finite, and a `datetime` from 1970 to 9999 as an ISO-8601 `"$date"`.
+//! Canonical mode wraps each number in its type and writes each `datetime` as
+//! milliseconds since the epoch. Both write `bytes` and `UUID` as `"$binary"`
+//! and `Decimal` as `"$numberDecimal"`. The mode is installed for the call
+//! as the `key_order=` comparator is.
+
+use crate::serialize::base64::encode_to_string;
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::dict::days_from_civil;
+use crate::serialize::per_type::numpy::civil_from_days;
+use crate::serialize::per_type::{DateTime, DateTimeLike, FloatSerializer, IntSerializer};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::str::PyStr;
+
+use core::cell::Cell;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+thread_local! {
+    // Whether the innermost call on this thread writes canonical mode.
+    static CANONICAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Make `canonical` the mode for the current thread and return the previous
+/// one, which must be restored with `restore()`.
+pub(crate) fn install(canonical: bool) -> bool {
+    CANONICAL.with(|active| active.replace(canonical))
+}
+
+pub(crate) fn restore(previous: bool) {
+    CANONICAL.with(|active| active.set(previous));
+}
+
+/// 1970-01-01 as `days_from_civil()` counts.
+const EPOCH_DAYS: i64 = 719_162;
+
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
+
+/// Days from 1970-01-01 in the proleptic Gregorian calendar, the inverse of
+/// `civil_from_days()`.
+pub(crate) fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    days_from_civil(year, month, day) - EPOCH_DAYS
+}
+
+/// An object of the one member `key`, a string.
+struct Wrapped<'a>(&'static str, &'a str);
+
+impl Serialize for Wrapped<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_key(self.0)?;
+        map.serialize_value(self.1)?;
+        map.end()
+    }
+}
+
+/// `{"$binary": {"base64": ..., "subType": ...}}`.
+struct Binary<'a> {
+    data: &'a [u8],
+    sub_type: &'static str,
+}
+
+impl Serialize for Binary<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_key("$binary")?;
+        map.serialize_value(&BinaryFields {
+            data: self.data,
+            sub_type: self.sub_type,
+        })?;
+        map.end()
+    }
+}
+
+struct BinaryFields<'a> {
+    data: &'a [u8],
+    sub_type: &'static str,
+}
+
+impl Serialize for BinaryFields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_key("base64")?;
+        map.serialize_value(&Base64(self.data))?;
+        map.serialize_key("subType")?;
+        map.serialize_value(self.sub_type)?;
+        map.end()
+    }
+}
+
+/// Input bytes encoded per write of `Base64`, a multiple of 3 so that only
+/// the last chunk is padded.
+const BASE64_CHUNK_LEN: usize = 3 * 1024;
+
+/// The base64 of `bytes`, written to the output in chunks of
+/// `BASE64_CHUNK_LEN` bytes rather than encoded whole first.
+struct Base64<'a>(&'a [u8]);
+
+impl core::fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut encoded = String::with_capacity(BASE64_CHUNK_LEN / 3 * 4);
+        for chunk in self.0.chunks(BASE64_CHUNK_LEN) {
+            encoded.clear();
+            encode_to_string(chunk, &mut encoded);
+            f.write_str(&encoded)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Base64<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// `{"$date": ...}` of either form.
+struct Date<'a> {
+    iso: Option<&'a str>,
+    milliseconds: &'a str,
+}
+
+impl Serialize for Date<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_key("$date")?;
+        match self.iso {
+            Some(iso) => map.serialize_value(iso)?,
+            None => map.serialize_value(&Wrapped("$numberLong", self.milliseconds))?,
+        }
+        map.end()
+    }
+}
+
+/// Milliseconds since the epoch of `ptr`, a `datetime` that is UTC if naive,
+/// truncating microseconds.
+fn datetime_milliseconds(ptr: *mut crate::ffi::PyObject) -> Result<i64, SerializeError> {
+    let dt = DateTime::new(ptr, 0);
+    let offset = if dt.has_tz() {
+        match dt.offset() {
+            Ok(offset) => i64::from(offset.day) * 86_400 + i64::from(offset.second),
+            Err(_) => return Err(SerializeError::DatetimeLibraryUnsupported),
+        }
+    } else {
+        0
+    };
+    let days = days_since_epoch(
+        i64::from(dt.year()),
+        i64::from(dt.month()),
+        i64::from(dt.day()),
+    );
+    let seconds =
+        i64::from(dt.hour()) * 3_600 + i64::from(dt.minute()) * 60 + i64::from(dt.second())
+            - offset;
+    Ok(days * MILLISECONDS_PER_DAY + seconds * 1_000 + i64::from(dt.microsecond() / 1_000))
+}
+
+/// The relaxed `"$date"` string of `milliseconds`, if from 1970 to 9999.
+fn iso_date(milliseconds: i64) -> Option<String> {
+    if milliseconds < 0 {
+        return None;
+    }
+    let (year, month, day) = civil_from_days(i128::from(milliseconds / MILLISECONDS_PER_DAY));
+    if year > 9999 {
+        return None;
+    }
+    let time = milliseconds % MILLISECONDS_PER_DAY;
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        time / 3_600_000,
+        time / 60_000 % 60,
+        time / 1_000 % 60,
+        time % 1_000
+    ))
+}
+
+/// The 16 bytes of the `UUID` `ptr`, most significant first.
+fn uuid_bytes(ptr: *mut crate::ffi::PyObject) -> [u8; 16] {
+    let py_int = ffi!(PyObject_GetAttr(ptr, crate::typeref::get_int_attr_str()));
+    ffi!(Py_DECREF(py_int));
+    let mut buffer = [0u8; 16];
+    unsafe {
+        crate::ffi::PyLong_AsByteArray(
+            py_int.cast::<crate::ffi::PyLongObject>(),
+            buffer.as_mut_ptr(),
+            16,
+            0, // big endian
+            0, // is_signed
+        );
+    }
+    buffer
+}
+
+/// Serialize an `int`, `float`, `datetime`, `UUID`, `bytes`, or `Decimal` in
+/// Extended JSON, for `dumps(extended_json=)`.
+#[repr(transparent)]
+pub(crate) struct ExtendedJsonSerializer<'a> {
+    previous: &'a PyObjectSerializer,
+}
+
+impl<'a> ExtendedJsonSerializer<'a> {
+    pub fn new(previous: &'a PyObjectSerializer) -> Self {
+        Self { previous: previous }
+    }
+}
+
+impl Serialize for ExtendedJsonSerializer<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let ptr = self.previous.ptr;
+        let ob_type = ob_type!(ptr);
+        let interpreter_state = self.previous.state.interpreter_state();
+        let canonical = CANONICAL.with(Cell::get);
+        if is_type!(ob_type, crate::typeref::int_type_ptr()) {
+            if !canonical {
+                return IntSerializer::new(ptr, self.previous.state.opts()).serialize(serializer);
+            }
+            let val = ffi!(PyLong_AsLongLong(ptr));
+            if val == -1 && !ffi!(PyErr_Occurred()).is_null() {
+                ffi!(PyErr_Clear());
+                err!(SerializeError::Integer64Bits)
+            }
+            let key = if i32::try_from(val).is_ok() {
+                "$numberInt"
+            } else {
+                "$numberLong"
+            };
+            Wrapped(key, itoa::Buffer::new().format(val)).serialize(serializer)
+        } else if is_type!(ob_type, crate::typeref::float_type_ptr()) {
+            let val = ffi!(PyFloat_AS_DOUBLE(ptr));
+            if val.is_nan() {
+                Wrapped("$numberDouble", "NaN").serialize(serializer)
+            } else if val.is_infinite() {
+                let text = if val > 0.0 { "Infinity" } else { "-Infinity" };
+                Wrapped("$numberDouble", text).serialize(serializer)
+            } else if canonical {
+                Wrapped("$numberDouble", ryu::Buffer::new().format_finite(val))
+                    .serialize(serializer)
+            } else {
+                FloatSerializer::new(ptr).serialize(serializer)
+            }
+        } else if is_type!(
+            ob_type,
+            crate::typeref::get_datetime_type_from_state(interpreter_state)
+        ) {
+            let milliseconds = match datetime_milliseconds(ptr) {
+                Ok(milliseconds) => milliseconds,
+                Err(err) => err!(err),
+            };
+            let iso = if canonical {
+                None
+            } else {
+                iso_date(milliseconds)
+            };
+            Date {
+                iso: iso.as_deref(),
+                milliseconds: itoa::Buffer::new().format(milliseconds),
+            }
+            .serialize(serializer)
+        } else if is_type!(ob_type, crate::typeref::bytes_type_ptr()) {
+            let data = unsafe {
+                core::slice::from_raw_parts(
+                    crate::ffi::PyBytes_AS_STRING(ptr).cast::<u8>(),
+                    crate::util::isize_to_usize(crate::ffi::Py_SIZE(ptr)),
+                )
+            };
+            Binary {
+                data: data,
+                sub_type: "00",
+            }
+            .serialize(serializer)
+        } else if is_type!(
+            ob_type,
+            crate::typeref::get_uuid_type_from_state(interpreter_state)
+        ) {
+            Binary {
+                data: &uuid_bytes(ptr),
+                sub_type: "04",
+            }
+            .serialize(serializer)
+        } else {
+            // `Decimal`
+            let text = ffi!(PyObject_Str(ptr));
+            if text.is_null() {
+                cold_path!();
+                ffi!(PyErr_Clear());
+                err!(SerializeError::UnsupportedType(nonnull!(ptr)))
+            }
+            let ret = match unsafe { PyStr::from_ptr_unchecked(text).to_str() } {
+                Some(text) => Wrapped("$numberDecimal", text).serialize(serializer),
+                None => Err(serde::ser::Error::custom(SerializeError::InvalidStr)),
+            };
+            ffi!(Py_DECREF(text));
+            ret
+        }
+    }
+}
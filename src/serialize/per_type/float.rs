@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
-use serde::ser::{Serialize, Serializer};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
 
 #[repr(transparent)]
 pub(crate) struct FloatSerializer {
@@ -23,3 +23,160 @@ impl Serialize for FloatSerializer {
         serializer.serialize_f64(ffi!(PyFloat_AS_DOUBLE(self.ptr)))
     }
 }
+
+/// Floats written per `serialize_bytes()` call by `FloatArraySerializer`, so
+/// that the writer is bounds checked once per batch rather than per float.
+pub(crate) const FLOAT_BATCH_LEN: usize = 16;
+
+/// "-1.7976931348623157e308" and a comma, with room to spare.
+const FLOAT_BATCH_ITEM_LEN: usize = 25;
+
+pub(crate) trait BatchFloat: Copy {
+    fn is_finite(self) -> bool;
+
+    /// Write finite `self` to `ptr`, which has room for
+    /// `FLOAT_BATCH_ITEM_LEN` bytes, and return the length.
+    unsafe fn write_to_ptr(self, ptr: *mut u8) -> usize;
+
+    fn serialize_one<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+impl BatchFloat for f64 {
+    #[inline(always)]
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    #[inline(always)]
+    unsafe fn write_to_ptr(self, ptr: *mut u8) -> usize {
+        unsafe { ryu::raw::format64(self, ptr) }
+    }
+
+    #[inline(always)]
+    fn serialize_one<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self)
+    }
+}
+
+impl BatchFloat for f32 {
+    #[inline(always)]
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    #[inline(always)]
+    unsafe fn write_to_ptr(self, ptr: *mut u8) -> usize {
+        unsafe { ryu::raw::format32(self, ptr) }
+    }
+
+    #[inline(always)]
+    fn serialize_one<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f32(self)
+    }
+}
+
+/// Up to `FLOAT_BATCH_LEN` finite floats written as a single element of a
+/// compact array, e.g., `1.0,2.5`.
+#[repr(transparent)]
+pub(crate) struct FloatBatchSerializer<'a, T> {
+    data: &'a [T],
+}
+
+impl<'a, T> FloatBatchSerializer<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        FloatBatchSerializer { data: data }
+    }
+}
+
+impl<T> Serialize for FloatBatchSerializer<'_, T>
+where
+    T: BatchFloat,
+{
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        debug_assert!(!self.data.is_empty() && self.data.len() <= FLOAT_BATCH_LEN);
+        let mut buf =
+            [core::mem::MaybeUninit::<u8>::uninit(); FLOAT_BATCH_LEN * FLOAT_BATCH_ITEM_LEN];
+        let start = buf.as_mut_ptr().cast::<u8>();
+        let mut len = 0;
+        for (idx, &each) in self.data.iter().enumerate() {
+            unsafe {
+                if idx != 0 {
+                    core::ptr::write(start.add(len), b',');
+                    len += 1;
+                }
+                len += each.write_to_ptr(start.add(len));
+            }
+        }
+        serializer.serialize_bytes(unsafe { core::slice::from_raw_parts(start, len) })
+    }
+}
+
+#[repr(transparent)]
+struct FloatItemSerializer<T> {
+    value: T,
+}
+
+impl<T> Serialize for FloatItemSerializer<T>
+where
+    T: BatchFloat,
+{
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize_one(serializer)
+    }
+}
+
+/// A compact array of floats written in batches. The writer is only
+/// bypassed for floats it would write as `ryu` does, so it must not be used
+/// with `float_decimals=` or `float_format=`, and a batch holding a NaN or
+/// infinity is written per float.
+#[repr(transparent)]
+pub(crate) struct FloatArraySerializer<'a, T> {
+    data: &'a [T],
+}
+
+impl<'a, T> FloatArraySerializer<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        FloatArraySerializer { data: data }
+    }
+}
+
+impl<T> Serialize for FloatArraySerializer<'_, T>
+where
+    T: BatchFloat,
+{
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for chunk in self.data.chunks(FLOAT_BATCH_LEN) {
+            if chunk.iter().all(|each| each.is_finite()) {
+                seq.serialize_element(&FloatBatchSerializer::new(chunk))
+                    .unwrap();
+            } else {
+                cold_path!();
+                for &each in chunk {
+                    seq.serialize_element(&FloatItemSerializer { value: each })?;
+                }
+            }
+        }
+        seq.end()
+    }
+}
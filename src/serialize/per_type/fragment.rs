@@ -2,7 +2,10 @@
 // Copyright ijl (2018-2025)
 
 use crate::ffi::{Fragment, PyBytes_AS_STRING, PyBytes_GET_SIZE};
+use crate::opt::MSGPACK;
 use crate::serialize::error::SerializeError;
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
 use crate::str::PyStr;
 // BYTES_TYPE, STR_TYPE now accessed via typeref accessor functions
 use crate::util::isize_to_usize;
@@ -20,6 +23,33 @@ impl FragmentSerializer {
     }
 }
 
+/// Borrow the encoded JSON held by a `Fragment`, i.e., its `bytes` or `str`
+/// contents.
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) fn fragment_contents(
+    contents: *mut crate::ffi::PyObject,
+) -> Result<&'static [u8], SerializeError> {
+    unsafe {
+        let ob_type = ob_type!(contents);
+        if core::ptr::eq(ob_type, crate::typeref::bytes_type_ptr()) {
+            Ok(core::slice::from_raw_parts(
+                PyBytes_AS_STRING(contents).cast::<u8>(),
+                isize_to_usize(PyBytes_GET_SIZE(contents)),
+            ))
+        // Use direct CPython global for str type (zero indirection)
+        } else if core::ptr::eq(ob_type, crate::typeref::str_type_ptr()) {
+            match unsafe { PyStr::from_ptr_unchecked(contents).to_str() } {
+                Some(uni) => Ok(core::slice::from_raw_parts(uni.as_ptr(), uni.len())),
+                None => Err(SerializeError::InvalidStr),
+            }
+        } else {
+            Err(SerializeError::InvalidFragment)
+        }
+    }
+}
+
 impl Serialize for FragmentSerializer {
     #[cold]
     #[inline(never)]
@@ -28,25 +58,34 @@ impl Serialize for FragmentSerializer {
     where
         S: Serializer,
     {
-        let buffer: &[u8];
-        unsafe {
-            let fragment: *mut Fragment = self.ptr.cast::<Fragment>();
-            let ob_type = ob_type!((*fragment).contents);
-            if core::ptr::eq(ob_type, crate::typeref::bytes_type_ptr()) {
-                buffer = core::slice::from_raw_parts(
-                    PyBytes_AS_STRING((*fragment).contents).cast::<u8>(),
-                    isize_to_usize(PyBytes_GET_SIZE((*fragment).contents)),
-                );
-            // Use direct CPython global for str type (zero indirection)
-            } else if core::ptr::eq(ob_type, crate::typeref::str_type_ptr()) {
-                match unsafe { PyStr::from_ptr_unchecked((*fragment).contents).to_str() } {
-                    Some(uni) => buffer = uni.as_bytes(),
-                    None => err!(SerializeError::InvalidStr),
-                }
-            } else {
-                err!(SerializeError::InvalidFragment)
-            }
+        let contents = unsafe { (*self.ptr.cast::<Fragment>()).contents };
+        match fragment_contents(contents) {
+            Ok(buffer) if serializer.is_human_readable() => serializer.serialize_bytes(buffer),
+            Ok(_) => serialize_fragment_values(contents, serializer),
+            Err(err) => err!(err),
         }
-        serializer.serialize_bytes(buffer)
     }
 }
+
+/// Write the values of the JSON `contents` for an output that is not JSON.
+#[cold]
+#[inline(never)]
+fn serialize_fragment_values<S>(
+    contents: *mut crate::ffi::PyObject,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let obj = match crate::deserialize::deserialize(
+        contents,
+        &crate::deserialize::DeserializeOptions::default(),
+    ) {
+        Ok(obj) => obj.as_ptr(),
+        Err(_) => err!(SerializeError::InvalidFragmentJson),
+    };
+    let ret =
+        PyObjectSerializer::new(obj, SerializerState::new(MSGPACK), None).serialize(serializer);
+    ffi!(Py_DECREF(obj));
+    ret
+}
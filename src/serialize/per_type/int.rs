@@ -1,9 +1,10 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
-use crate::opt::{Opt, STRICT_INTEGER};
+use crate::opt::{BIG_INTEGER, Opt, STRICT_INTEGER};
 use crate::serialize::error::SerializeError;
-use serde::ser::{Serialize, Serializer};
+use crate::str::PyStr;
+use serde::ser::{Serialize, SerializeSeq, Serializer};
 
 // https://tools.ietf.org/html/rfc7159#section-6
 // "[-(2**53)+1, (2**53)-1]"
@@ -33,6 +34,9 @@ impl Serialize for IntSerializer {
     {
         unsafe {
             if crate::ffi::pylong_is_zero(self.ptr) {
+                if !serializer.is_human_readable() {
+                    return serializer.serialize_u64(0);
+                }
                 return serializer.serialize_bytes(b"0");
             }
             let is_signed = i32::from(!crate::ffi::pylong_is_unsigned(self.ptr));
@@ -56,7 +60,7 @@ impl Serialize for IntSerializer {
                     cold_path!();
                     #[cfg(not(Py_3_13))]
                     ffi!(PyErr_Clear());
-                    err!(SerializeError::Integer64Bits)
+                    return serialize_big_int(self.ptr, self.opts, serializer);
                 }
                 if is_signed == 0 {
                     let val = u64::from_ne_bytes(buffer);
@@ -88,7 +92,7 @@ impl Serialize for IntSerializer {
                 let val = ffi!(PyLong_AsUnsignedLongLong(self.ptr));
                 if val == u64::MAX && !ffi!(PyErr_Occurred()).is_null() {
                     ffi!(PyErr_Clear());
-                    err!(SerializeError::Integer64Bits)
+                    serialize_big_int(self.ptr, self.opts, serializer)
                 } else if opt_enabled!(self.opts, STRICT_INTEGER) && val > STRICT_INT_MAX as u64 {
                     err!(SerializeError::Integer53Bits)
                 } else {
@@ -98,7 +102,7 @@ impl Serialize for IntSerializer {
                 let val = ffi!(PyLong_AsLongLong(self.ptr));
                 if val == -1 && !ffi!(PyErr_Occurred()).is_null() {
                     ffi!(PyErr_Clear());
-                    err!(SerializeError::Integer64Bits)
+                    serialize_big_int(self.ptr, self.opts, serializer)
                 } else if opt_enabled!(self.opts, STRICT_INTEGER)
                     && !(STRICT_INT_MIN..=STRICT_INT_MAX).contains(&val)
                 {
@@ -110,3 +114,137 @@ impl Serialize for IntSerializer {
         }
     }
 }
+
+/// An `int` outside of 64 bits, which is written as its decimal digits with
+/// `OPT_BIG_INTEGER` and is otherwise an error.
+#[cold]
+#[inline(never)]
+fn serialize_big_int<S>(
+    ptr: *mut crate::ffi::PyObject,
+    opts: Opt,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !opt_enabled!(opts, BIG_INTEGER) {
+        err!(SerializeError::Integer64Bits)
+    }
+    if opt_enabled!(opts, STRICT_INTEGER) {
+        err!(SerializeError::Integer53Bits)
+    }
+    if !serializer.is_human_readable() {
+        // MessagePack has no integer wider than 64 bits
+        err!(SerializeError::Integer64Bits)
+    }
+    // not str(), which a subclass may override
+    let digits = ffi!(PyNumber_ToBase(ptr, 10));
+    if digits.is_null() {
+        // sys.get_int_max_str_digits()
+        ffi!(PyErr_Clear());
+        err!(SerializeError::IntegerDigits)
+    }
+    let ret = match unsafe { PyStr::from_ptr_unchecked(digits).to_str() } {
+        Some(val) => serializer.serialize_bytes(val.as_bytes()),
+        None => Err(serde::ser::Error::custom(SerializeError::InvalidStr)),
+    };
+    ffi!(Py_DECREF(digits));
+    ret
+}
+
+/// Integers written per `serialize_bytes()` call by `IntArraySerializer`, so
+/// that the writer is bounds checked once per batch rather than per integer.
+pub(crate) const INT_BATCH_LEN: usize = 16;
+
+/// "-9223372036854775808" or "18446744073709551615" and a comma.
+const INT_BATCH_ITEM_LEN: usize = 21;
+
+/// Up to `INT_BATCH_LEN` integers written as a single element of a compact
+/// array, e.g., `1,2,3`.
+#[repr(transparent)]
+pub(crate) struct IntBatchSerializer<'a, T> {
+    data: &'a [T],
+}
+
+impl<'a, T> IntBatchSerializer<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        debug_assert!(!data.is_empty() && data.len() <= INT_BATCH_LEN);
+        IntBatchSerializer { data: data }
+    }
+}
+
+impl<T> Serialize for IntBatchSerializer<'_, T>
+where
+    T: itoap::Integer + Copy,
+{
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [core::mem::MaybeUninit::<u8>::uninit(); INT_BATCH_LEN * INT_BATCH_ITEM_LEN];
+        let start = buf.as_mut_ptr().cast::<u8>();
+        let mut len = 0;
+        for (idx, &each) in self.data.iter().enumerate() {
+            unsafe {
+                if idx != 0 {
+                    core::ptr::write(start.add(len), b',');
+                    len += 1;
+                }
+                len += itoap::write_to_ptr(start.add(len), each);
+            }
+        }
+        serializer.serialize_bytes(unsafe { core::slice::from_raw_parts(start, len) })
+    }
+}
+
+/// A compact array of integers written in batches.
+#[repr(transparent)]
+pub(crate) struct IntArraySerializer<'a, T> {
+    data: &'a [T],
+}
+
+impl<'a, T> IntArraySerializer<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        IntArraySerializer { data: data }
+    }
+}
+
+impl<T> Serialize for IntArraySerializer<'_, T>
+where
+    T: itoap::Integer + Copy,
+{
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for chunk in self.data.chunks(INT_BATCH_LEN) {
+            seq.serialize_element(&IntBatchSerializer::new(chunk))
+                .unwrap();
+        }
+        seq.end()
+    }
+}
+
+/// Read the values of the leading `int` items of `items` that fit in one
+/// digit, at most `INT_BATCH_LEN`, into `values` and return how many.
+#[cfg(feature = "inline_int")]
+#[inline(always)]
+pub(crate) fn read_small_int_run(
+    items: &[*mut crate::ffi::PyObject],
+    values: &mut [i64; INT_BATCH_LEN],
+) -> usize {
+    let mut len = 0;
+    for &item in items.iter().take(INT_BATCH_LEN) {
+        if !is_class_by_type!(ob_type!(item), crate::typeref::int_type_ptr())
+            || !crate::ffi::pylong_fits_in_i32(item)
+        {
+            break;
+        }
+        values[len] = crate::ffi::pylong_get_inline_value(item);
+        len += 1;
+    }
+    len
+}
@@ -1,17 +1,24 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
+use crate::opt::{EXTENDED_JSON, FLOAT_DECIMALS, FLOAT_FORMAT_ANY, INDENT, MSGPACK};
 use crate::serialize::error::SerializeError;
 use crate::serialize::obtype::{ObType, pyobject_to_obtype};
+use crate::serialize::per_type::float::{FLOAT_BATCH_LEN, FloatBatchSerializer};
+#[cfg(feature = "inline_int")]
+use crate::serialize::per_type::int::{INT_BATCH_LEN, IntBatchSerializer, read_small_int_run};
 use crate::serialize::per_type::{
-    BoolSerializer, DataclassGenericSerializer, Date, DateTime, DefaultSerializer,
-    DictGenericSerializer, EnumSerializer, FloatSerializer, FragmentSerializer, IntSerializer,
-    NoneSerializer, NumpyScalar, NumpySerializer, StrSerializer, StrSubclassSerializer, Time, UUID,
+    ArrowSerializer, BoolSerializer, BytesSerializer, DataclassGenericSerializer, Date, DateTime,
+    DictGenericSerializer, DictSharedKeys, EnumSerializer, ExtendedJsonSerializer, FloatSerializer,
+    FragmentSerializer, IntSerializer, NamedTupleSerializer, NoneSerializer, NumpyScalar,
+    NumpySerializer, PandasSerializer, RawNumberSerializer, ResolvedDefault, SharedKeys,
+    StrSerializer, StrSubclassSerializer, TaggedSerializer, Time, UUID,
 };
 use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::snapshot::{self, Snapshot};
 use crate::serialize::state::SerializerState;
 // LIST_TYPE, TUPLE_TYPE now accessed via typeref accessor functions
-use crate::util::isize_to_usize;
+use crate::util::{isize_to_usize, usize_to_isize};
 
 use core::ptr::NonNull;
 use serde::ser::{Serialize, SerializeSeq, Serializer};
@@ -30,15 +37,37 @@ impl Serialize for ZeroListSerializer {
     where
         S: Serializer,
     {
-        serializer.serialize_bytes(b"[]")
+        if serializer.is_human_readable() {
+            serializer.serialize_bytes(b"[]")
+        } else {
+            serializer.serialize_seq(Some(0))?.end()
+        }
     }
 }
 
+/// Lists and tuples at least this long are checked for items all of one
+/// primitive type, which are then serialized without finding the type of
+/// each.
+const HOMOGENEOUS_MIN_LEN: usize = 16;
+
+/// The type of every item of a homogeneous list or tuple.
+#[derive(Clone, Copy)]
+enum Homogeneous {
+    Str,
+    Int,
+    Float,
+}
+
 pub(crate) struct ListTupleSerializer {
+    /// The items, or null if they are read with `PyList_GetItem()` or
+    /// `PyTuple_GetItem()` in compat mode.
     data_ptr: *const *mut crate::ffi::PyObject,
+    ptr: *mut crate::ffi::PyObject,
     state: SerializerState,
     default: Option<NonNull<crate::ffi::PyObject>>,
     len: usize,
+    /// The list, or null for a tuple, which cannot change while serialized.
+    list: *mut crate::ffi::PyObject,
 }
 
 impl ListTupleSerializer {
@@ -51,13 +80,19 @@ impl ListTupleSerializer {
             is_type!(ob_type!(ptr), crate::typeref::list_type_ptr())
                 || is_subclass_by_flag!(tp_flags!(ob_type!(ptr)), Py_TPFLAGS_LIST_SUBCLASS)
         );
-        let data_ptr = unsafe { (*ptr.cast::<crate::ffi::PyListObject>()).ob_item };
+        let data_ptr = if state.compat_mode() {
+            core::ptr::null()
+        } else {
+            unsafe { (*ptr.cast::<crate::ffi::PyListObject>()).ob_item }
+        };
         let len = isize_to_usize(ffi!(Py_SIZE(ptr)));
         Self {
             data_ptr: data_ptr,
+            ptr: ptr,
             len: len,
             state: state.copy_for_recursive_call(),
             default: default,
+            list: ptr,
         }
     }
 
@@ -70,17 +105,127 @@ impl ListTupleSerializer {
             is_type!(ob_type!(ptr), crate::typeref::tuple_type_ptr())
                 || is_subclass_by_flag!(tp_flags!(ob_type!(ptr)), Py_TPFLAGS_TUPLE_SUBCLASS)
         );
-        let data_ptr = unsafe { (*ptr.cast::<crate::ffi::PyTupleObject>()).ob_item.as_ptr() };
+        let data_ptr = if state.compat_mode() {
+            core::ptr::null()
+        } else {
+            unsafe { (*ptr.cast::<crate::ffi::PyTupleObject>()).ob_item.as_ptr() }
+        };
         let len = isize_to_usize(ffi!(Py_SIZE(ptr)));
         Self {
             data_ptr: data_ptr,
+            ptr: ptr,
             len: len,
             state: state.copy_for_recursive_call(),
             default: default,
+            list: core::ptr::null_mut(),
         }
     }
 }
 
+impl ListTupleSerializer {
+    /// The type of the items if all are exactly `str`, `int`, or `float`.
+    #[inline]
+    fn homogeneous(&self) -> Option<Homogeneous> {
+        if self.len < HOMOGENEOUS_MIN_LEN || self.data_ptr.is_null() {
+            return None;
+        }
+        let items = unsafe { core::slice::from_raw_parts(self.data_ptr, self.len) };
+        let ob_type = ob_type!(items[0]);
+        let kind = if is_class_by_type!(ob_type, crate::typeref::str_type_ptr()) {
+            Homogeneous::Str
+        } else if opt_enabled!(self.state.opts(), EXTENDED_JSON) {
+            return None;
+        } else if is_class_by_type!(ob_type, crate::typeref::int_type_ptr()) {
+            Homogeneous::Int
+        } else if is_class_by_type!(ob_type, crate::typeref::float_type_ptr()) {
+            Homogeneous::Float
+        } else {
+            return None;
+        };
+        if items
+            .iter()
+            .all(|&item| is_class_by_type!(ob_type!(item), ob_type))
+        {
+            Some(kind)
+        } else {
+            None
+        }
+    }
+
+    /// Borrow item `idx` with the public C API, for compat mode.
+    #[cold]
+    fn get_item(&self, idx: usize) -> *mut crate::ffi::PyObject {
+        if self.list.is_null() {
+            ffi!(PyTuple_GetItem(self.ptr, usize_to_isize(idx)))
+        } else {
+            ffi!(PyList_GetItem(self.ptr, usize_to_isize(idx)))
+        }
+    }
+
+    #[inline(never)]
+    fn serialize_homogeneous<S>(&self, kind: Homogeneous, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let items = unsafe { core::slice::from_raw_parts(self.data_ptr, self.len) };
+        let opts = self.state.opts();
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        match kind {
+            Homogeneous::Str => {
+                for &item in items {
+                    seq.serialize_element(&StrSerializer::new(item))?;
+                }
+            }
+            Homogeneous::Int => {
+                #[cfg(feature = "inline_int")]
+                let batch_ints = opt_disabled!(opts, INDENT | MSGPACK);
+                #[cfg(feature = "inline_int")]
+                let mut int_values = [0_i64; INT_BATCH_LEN];
+                let mut idx = 0;
+                while idx < items.len() {
+                    #[cfg(feature = "inline_int")]
+                    if batch_ints {
+                        let run = read_small_int_run(&items[idx..], &mut int_values);
+                        if run > 1 {
+                            seq.serialize_element(&IntBatchSerializer::new(&int_values[..run]))
+                                .unwrap();
+                            idx += run;
+                            continue;
+                        }
+                    }
+                    seq.serialize_element(&IntSerializer::new(items[idx], opts))?;
+                    idx += 1;
+                }
+            }
+            Homogeneous::Float => {
+                if opt_disabled!(opts, INDENT | MSGPACK | FLOAT_DECIMALS | FLOAT_FORMAT_ANY) {
+                    let mut float_values = [0.0_f64; FLOAT_BATCH_LEN];
+                    for chunk in items.chunks(FLOAT_BATCH_LEN) {
+                        for (value, &item) in float_values.iter_mut().zip(chunk) {
+                            *value = ffi!(PyFloat_AS_DOUBLE(item));
+                        }
+                        let values = &float_values[..chunk.len()];
+                        if values.iter().all(|value| value.is_finite()) {
+                            seq.serialize_element(&FloatBatchSerializer::new(values))
+                                .unwrap();
+                        } else {
+                            cold_path!();
+                            for &item in chunk {
+                                seq.serialize_element(&FloatSerializer::new(item))?;
+                            }
+                        }
+                    }
+                } else {
+                    for &item in items {
+                        seq.serialize_element(&FloatSerializer::new(item))?;
+                    }
+                }
+            }
+        }
+        seq.end()
+    }
+}
+
 impl Serialize for ListTupleSerializer {
     #[inline(never)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -92,9 +237,49 @@ impl Serialize for ListTupleSerializer {
             err!(SerializeError::RecursionLimit)
         }
         debug_assert!(self.len >= 1);
+        // a list is read again before each item if a callback could change it
+        let held = !self.list.is_null() && snapshot::is_required(self.state.opts(), self.default);
+        if let Some(kind) = self.homogeneous() {
+            // floats are read in place unless float_format= may be callable
+            let float_format = matches!(kind, Homogeneous::Float)
+                && opt_enabled!(self.state.opts(), FLOAT_FORMAT_ANY);
+            if !(held && float_format) {
+                return self.serialize_homogeneous(kind, serializer);
+            }
+        }
+        let compat = self.data_ptr.is_null();
+        // dicts with shared keys are read in place, which a callback could
+        // change, and their keys are written as JSON
+        let share_keys = !snapshot::is_required(self.state.opts(), self.default)
+            && opt_disabled!(self.state.opts(), MSGPACK)
+            && !compat;
+        let mut shared_keys = SharedKeys::new();
+        #[cfg(feature = "inline_int")]
+        let batch_ints = opt_disabled!(self.state.opts(), INDENT | MSGPACK) && !compat;
+        #[cfg(feature = "inline_int")]
+        let mut int_values = [0_i64; INT_BATCH_LEN];
         let mut seq = serializer.serialize_seq(None).unwrap();
-        for idx in 0..self.len {
-            let value = unsafe { *((self.data_ptr).add(idx)) };
+        let mut data_ptr = self.data_ptr;
+        let mut len = self.len;
+        let mut idx = 0;
+        loop {
+            if held {
+                if !compat {
+                    data_ptr = unsafe { (*self.list.cast::<crate::ffi::PyListObject>()).ob_item };
+                }
+                len = len.min(isize_to_usize(ffi!(Py_SIZE(self.list))));
+            }
+            if idx >= len {
+                break;
+            }
+            let value = if compat {
+                cold_path!();
+                self.get_item(idx)
+            } else {
+                unsafe { *(data_ptr.add(idx)) }
+            };
+            idx += 1;
+            let _held = held.then(|| Snapshot::of(value));
             match pyobject_to_obtype(value, self.state.opts(), self.state.interpreter_state()) {
                 ObType::Str => {
                     seq.serialize_element(&StrSerializer::new(value))?;
@@ -103,6 +288,19 @@ impl Serialize for ListTupleSerializer {
                     seq.serialize_element(&StrSubclassSerializer::new(value))?;
                 }
                 ObType::Int => {
+                    #[cfg(feature = "inline_int")]
+                    if batch_ints {
+                        let items = unsafe {
+                            core::slice::from_raw_parts(data_ptr.add(idx - 1), len - idx + 1)
+                        };
+                        let run = read_small_int_run(items, &mut int_values);
+                        if run > 1 {
+                            seq.serialize_element(&IntBatchSerializer::new(&int_values[..run]))
+                                .unwrap();
+                            idx += run - 1;
+                            continue;
+                        }
+                    }
                     seq.serialize_element(&IntSerializer::new(value, self.state.opts()))?;
                 }
                 ObType::None => {
@@ -127,8 +325,14 @@ impl Serialize for ListTupleSerializer {
                     seq.serialize_element(&UUID::new(value)).unwrap();
                 }
                 ObType::Dict => {
-                    let pyvalue = DictGenericSerializer::new(value, self.state, self.default);
-                    seq.serialize_element(&pyvalue)?;
+                    if share_keys && shared_keys.accept(value, self.state.opts()) {
+                        let pyvalue =
+                            DictSharedKeys::new(value, self.state, self.default, &shared_keys);
+                        seq.serialize_element(&pyvalue)?;
+                    } else {
+                        let pyvalue = DictGenericSerializer::new(value, self.state, self.default);
+                        seq.serialize_element(&pyvalue)?;
+                    }
                 }
                 ObType::List => {
                     if ffi!(Py_SIZE(value)) == 0 {
@@ -170,16 +374,55 @@ impl Serialize for ListTupleSerializer {
                 ObType::NumpyScalar => {
                     seq.serialize_element(&NumpyScalar::new(value, self.state.opts()))?;
                 }
+                ObType::Pandas => {
+                    seq.serialize_element(&PandasSerializer::new(&PyObjectSerializer::new(
+                        value,
+                        self.state,
+                        self.default,
+                    )))?;
+                }
+                ObType::Arrow => {
+                    seq.serialize_element(&ArrowSerializer::new(&PyObjectSerializer::new(
+                        value,
+                        self.state,
+                        self.default,
+                    )))?;
+                }
                 ObType::Fragment => {
                     seq.serialize_element(&FragmentSerializer::new(value))?;
                 }
-                ObType::Unknown => {
-                    seq.serialize_element(&DefaultSerializer::new(&PyObjectSerializer::new(
+                ObType::RawNumber => {
+                    seq.serialize_element(&RawNumberSerializer::new(value))?;
+                }
+                ObType::NamedTuple => {
+                    let pyvalue = NamedTupleSerializer::new(value, self.state, self.default);
+                    seq.serialize_element(&pyvalue)?;
+                }
+                ObType::Tagged => {
+                    seq.serialize_element(&TaggedSerializer::new(&PyObjectSerializer::new(
+                        value,
+                        self.state,
+                        self.default,
+                    )))?;
+                }
+                ObType::ExtendedJson => {
+                    seq.serialize_element(&ExtendedJsonSerializer::new(&PyObjectSerializer::new(
                         value,
                         self.state,
                         self.default,
                     )))?;
                 }
+                ObType::Bytes => {
+                    seq.serialize_element(&BytesSerializer::new(value))?;
+                }
+                ObType::Unknown => {
+                    let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
+                    let resolved = ResolvedDefault::new(&pyvalue);
+                    if resolved.skip() {
+                        continue;
+                    }
+                    seq.serialize_element(&resolved)?;
+                }
             }
         }
         seq.end()
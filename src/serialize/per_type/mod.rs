@@ -1,35 +1,54 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2020-2025), Ben Sully (2021)
 
+mod arrow;
+mod bytes;
 mod dataclass;
+mod dataclass_plan;
 mod datetime;
 mod pybool;
 #[macro_use]
 mod datetimelike;
 mod default;
 mod dict;
+pub(crate) mod extended_json;
 mod float;
 mod fragment;
-mod int;
+pub(crate) mod int;
 mod list;
+mod namedtuple;
 mod none;
 mod numpy;
+mod omit;
+mod pandas;
 mod pyenum;
+mod tagged;
 mod unicode;
 mod uuid;
 
+pub(crate) use arrow::{ArrowSerializer, is_arrow};
+pub(crate) use bytes::{BytesSerializer, is_bytes_like};
 pub(crate) use dataclass::DataclassGenericSerializer;
+pub(crate) use dataclass_plan::{DataclassPlan, DataclassPlanSerializer, is_dataclass};
 pub(crate) use datetime::{Date, DateTime, Time};
 pub(crate) use datetimelike::{DateTimeError, DateTimeLike, Offset};
-pub(crate) use default::DefaultSerializer;
-pub(crate) use dict::DictGenericSerializer;
-pub(crate) use float::FloatSerializer;
-pub(crate) use fragment::FragmentSerializer;
-pub(crate) use int::IntSerializer;
+pub(crate) use default::{DefaultSerializer, ResolvedDefault, resolve_if_unsupported};
+pub(crate) use dict::{DictGenericSerializer, DictSharedKeys, SharedKeys};
+pub(crate) use extended_json::{ExtendedJsonSerializer, days_since_epoch};
+pub(crate) use float::{FloatArraySerializer, FloatSerializer};
+pub(crate) use fragment::{FragmentSerializer, fragment_contents};
+pub(crate) use int::{IntArraySerializer, IntSerializer};
 pub(crate) use list::{ListTupleSerializer, ZeroListSerializer};
+pub(crate) use namedtuple::NamedTupleSerializer;
 pub(crate) use none::NoneSerializer;
-pub(crate) use numpy::{NumpyScalar, NumpySerializer, is_numpy_array, is_numpy_scalar};
+pub(crate) use numpy::{
+    ItemType, NPY_ARRAY_NOTSWAPPED, NumpyItem, NumpyScalar, NumpySerializer, PyArrayInterface,
+    PyCapsule, civil_from_days, is_array_like, is_dlpack, is_numpy_array, is_numpy_scalar,
+};
+pub(crate) use omit::{omit_empty, omit_value};
+pub(crate) use pandas::{PandasSerializer, is_pandas};
 pub(crate) use pybool::BoolSerializer;
 pub(crate) use pyenum::EnumSerializer;
-pub(crate) use unicode::{StrSerializer, StrSubclassSerializer};
+pub(crate) use tagged::TaggedSerializer;
+pub(crate) use unicode::{RawNumberSerializer, StrSerializer, StrSubclassSerializer};
 pub(crate) use uuid::UUID;
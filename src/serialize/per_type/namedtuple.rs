@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::dict::ZeroDictSerializer;
+use crate::serialize::per_type::resolve_if_unsupported;
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::str::PyStr;
+use crate::util::isize_to_usize;
+
+use core::ptr::NonNull;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Serialize a `collections.namedtuple` or `typing.NamedTuple` as an object
+/// keyed by its `_fields`.
+pub(crate) struct NamedTupleSerializer {
+    ptr: *mut crate::ffi::PyObject,
+    state: SerializerState,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+}
+
+impl NamedTupleSerializer {
+    pub fn new(
+        ptr: *mut crate::ffi::PyObject,
+        state: SerializerState,
+        default: Option<NonNull<crate::ffi::PyObject>>,
+    ) -> Self {
+        NamedTupleSerializer {
+            ptr: ptr,
+            state: state.copy_for_recursive_call(),
+            default: default,
+        }
+    }
+}
+
+impl Serialize for NamedTupleSerializer {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.state.recursion_limit() {
+            cold_path!();
+            err!(SerializeError::RecursionLimit)
+        }
+        let fields = ffi!(PyObject_GetAttr(
+            self.ptr,
+            crate::typeref::get_namedtuple_fields_str_from_state(self.state.interpreter_state())
+        ));
+        if fields.is_null() {
+            cold_path!();
+            ffi!(PyErr_Clear());
+            err!(SerializeError::NamedTupleMalformed)
+        }
+        // `_fields` may be a property returning a new tuple, so it is held
+        // until its keys are written
+        let ret = self.serialize_fields(fields, serializer);
+        ffi!(Py_DECREF(fields));
+        ret
+    }
+}
+
+impl NamedTupleSerializer {
+    fn serialize_fields<S>(
+        &self,
+        fields: *mut crate::ffi::PyObject,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
+        if !is_type!(ob_type!(fields), crate::typeref::tuple_type_ptr())
+            || isize_to_usize(ffi!(Py_SIZE(fields))) != len
+        {
+            cold_path!();
+            err!(SerializeError::NamedTupleMalformed)
+        }
+        if len == 0 {
+            cold_path!();
+            return ZeroDictSerializer::new().serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(None).unwrap();
+        for idx in 0..len {
+            #[allow(clippy::cast_possible_wrap)]
+            let key = unsafe { crate::ffi::PyTuple_GET_ITEM(fields, idx as isize) };
+            #[allow(clippy::cast_possible_wrap)]
+            let value = unsafe { crate::ffi::PyTuple_GET_ITEM(self.ptr, idx as isize) };
+            if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                cold_path!();
+                err!(SerializeError::KeyMustBeStr)
+            }
+            let key_as_str = match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+                Some(uni) => uni,
+                None => err!(SerializeError::InvalidStr),
+            };
+            let pyvalue = PyObjectSerializer::new(value, self.state, self.default);
+            if let Some(resolved) = resolve_if_unsupported(&pyvalue) {
+                if resolved.skip() {
+                    continue;
+                }
+                map.serialize_key(key_as_str).unwrap();
+                map.serialize_value(&resolved)?;
+                continue;
+            }
+            map.serialize_key(key_as_str).unwrap();
+            map.serialize_value(&pyvalue)?;
+        }
+        map.end()
+    }
+}
@@ -2,22 +2,27 @@
 // Copyright ijl (2018-2025), Ben Sully (2021), Nazar Kostetskyi (2022), Aviram Hassan (2020-2021)
 
 use crate::ffi::{Py_intptr_t, Py_ssize_t, PyObject, PyTypeObject};
-use crate::opt::Opt;
+use crate::interpreter_state::InterpreterState;
+use crate::opt::{
+    DATETIME64_RANGE, DATETIME64_RANGE_CLAMP, DATETIME64_RANGE_NULL, FLOAT_DECIMALS,
+    FLOAT_FORMAT_ANY, INDENT, MSGPACK, Opt,
+};
 use crate::serialize::buffer::SmallFixedBuffer;
 use crate::serialize::error::SerializeError;
 use crate::serialize::per_type::{
-    DateTimeError, DateTimeLike, DefaultSerializer, Offset, ZeroListSerializer,
+    DateTimeError, DateTimeLike, DefaultSerializer, FloatArraySerializer, IntArraySerializer,
+    Offset, ZeroListSerializer,
 };
 use crate::serialize::serializer::PyObjectSerializer;
 use crate::str::PyStr;
 use crate::typeref::{
-    NUMPY_TYPES, get_array_struct_str, get_descr_str, get_dtype_str, load_numpy_types,
+    NumpyTypes, get_array_interface_str, get_array_struct_str, get_descr_str, get_dlpack_str,
+    get_dtype_str, get_mask_str, get_numpy_types_from_state,
 };
 use crate::util::isize_to_usize;
+use bytes::BufMut;
 use core::ffi::{c_char, c_int, c_void};
-use jiff::Timestamp;
-use jiff::civil::DateTime;
-use serde::ser::{self, Serialize, SerializeSeq, Serializer};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
 use std::fmt;
 
 #[repr(transparent)]
@@ -39,17 +44,22 @@ impl Serialize for NumpySerializer<'_> {
     where
         S: Serializer,
     {
-        match NumpyArray::new(self.previous.ptr, self.previous.state.opts()) {
+        let ob_type = ob_type!(self.previous.ptr);
+        let types = get_numpy_types_from_state(self.previous.state.interpreter_state());
+        let array = if types.is_some_and(|types| is_numpy_array_type(ob_type, types)) {
+            let masked = types.is_some_and(|types| core::ptr::eq(ob_type, types.masked_array));
+            NumpyArray::new(self.previous.ptr, self.previous.state.opts(), masked)
+        } else if is_dlpack(ob_type) {
+            NumpyArray::from_dlpack(self.previous.ptr, self.previous.state.opts())
+        } else {
+            NumpyArray::from_array_like(self.previous.ptr, self.previous.state.opts(), types)
+        };
+        match array {
             Ok(val) => val.serialize(serializer),
             Err(PyArrayError::Malformed) => err!(SerializeError::NumpyMalformed),
-            Err(PyArrayError::NotContiguous | PyArrayError::UnsupportedDataType)
-                if self.previous.default.is_some() =>
-            {
+            Err(PyArrayError::UnsupportedDataType) if self.previous.default.is_some() => {
                 DefaultSerializer::new(self.previous).serialize(serializer)
             }
-            Err(PyArrayError::NotContiguous) => {
-                err!(SerializeError::NumpyNotCContiguous)
-            }
             Err(PyArrayError::NotNativeEndian) => {
                 err!(SerializeError::NumpyNotNativeEndian)
             }
@@ -67,39 +77,75 @@ macro_rules! slice {
 }
 
 #[cold]
-pub(crate) fn is_numpy_scalar(ob_type: *mut PyTypeObject) -> bool {
-    let numpy_types = unsafe { NUMPY_TYPES.get_or_init(load_numpy_types) };
-    if numpy_types.is_none() {
-        false
-    } else {
-        let scalar_types = unsafe { numpy_types.unwrap().as_ref() };
-        core::ptr::eq(ob_type, scalar_types.float64)
-            || core::ptr::eq(ob_type, scalar_types.float32)
-            || core::ptr::eq(ob_type, scalar_types.float16)
-            || core::ptr::eq(ob_type, scalar_types.int64)
-            || core::ptr::eq(ob_type, scalar_types.int16)
-            || core::ptr::eq(ob_type, scalar_types.int32)
-            || core::ptr::eq(ob_type, scalar_types.int8)
-            || core::ptr::eq(ob_type, scalar_types.uint64)
-            || core::ptr::eq(ob_type, scalar_types.uint32)
-            || core::ptr::eq(ob_type, scalar_types.uint8)
-            || core::ptr::eq(ob_type, scalar_types.uint16)
-            || core::ptr::eq(ob_type, scalar_types.bool_)
-            || core::ptr::eq(ob_type, scalar_types.datetime64)
+pub(crate) fn is_numpy_scalar(
+    ob_type: *mut PyTypeObject,
+    interpreter_state: *const InterpreterState,
+) -> bool {
+    match get_numpy_types_from_state(interpreter_state) {
+        None => false,
+        Some(scalar_types) => {
+            core::ptr::eq(ob_type, scalar_types.float64)
+                || core::ptr::eq(ob_type, scalar_types.float32)
+                || core::ptr::eq(ob_type, scalar_types.float16)
+                || core::ptr::eq(ob_type, scalar_types.complex128)
+                || core::ptr::eq(ob_type, scalar_types.complex64)
+                || core::ptr::eq(ob_type, scalar_types.int64)
+                || core::ptr::eq(ob_type, scalar_types.int16)
+                || core::ptr::eq(ob_type, scalar_types.int32)
+                || core::ptr::eq(ob_type, scalar_types.int8)
+                || core::ptr::eq(ob_type, scalar_types.uint64)
+                || core::ptr::eq(ob_type, scalar_types.uint32)
+                || core::ptr::eq(ob_type, scalar_types.uint8)
+                || core::ptr::eq(ob_type, scalar_types.uint16)
+                || core::ptr::eq(ob_type, scalar_types.bool_)
+                || core::ptr::eq(ob_type, scalar_types.datetime64)
+                || core::ptr::eq(ob_type, scalar_types.timedelta64)
+        }
     }
 }
 
 #[cold]
-pub(crate) fn is_numpy_array(ob_type: *mut PyTypeObject) -> bool {
-    let numpy_types = unsafe { NUMPY_TYPES.get_or_init(load_numpy_types) };
-    if numpy_types.is_none() {
-        false
-    } else {
-        let scalar_types = unsafe { numpy_types.unwrap().as_ref() };
-        unsafe { core::ptr::eq(ob_type, scalar_types.array) }
+pub(crate) fn is_numpy_array(
+    ob_type: *mut PyTypeObject,
+    interpreter_state: *const InterpreterState,
+) -> bool {
+    match get_numpy_types_from_state(interpreter_state) {
+        None => false,
+        Some(types) => is_numpy_array_type(ob_type, types),
     }
 }
 
+fn is_numpy_array_type(ob_type: *mut PyTypeObject, types: &NumpyTypes) -> bool {
+    core::ptr::eq(ob_type, types.array)
+        || core::ptr::eq(ob_type, types.recarray)
+        || core::ptr::eq(ob_type, types.masked_array)
+}
+
+/// Whether `ob_type` exports `__dlpack__`, as a tensor of PyTorch, JAX, or
+/// CuPy does. It is serialized as a numpy array if in host memory.
+#[cold]
+pub(crate) fn is_dlpack(ob_type: *mut PyTypeObject) -> bool {
+    ffi!(PyObject_HasAttr(
+        ob_type.cast::<PyObject>(),
+        get_dlpack_str()
+    )) == 1
+}
+
+/// Whether `ob_type` exports `__array_struct__` or `__array_interface__`,
+/// as a subclass of `numpy.ndarray` or an array of another library does. It
+/// is serialized as the `numpy.asarray()` of it.
+#[cold]
+pub(crate) fn is_array_like(ob_type: *mut PyTypeObject) -> bool {
+    ffi!(PyObject_HasAttr(
+        ob_type.cast::<PyObject>(),
+        get_array_struct_str()
+    )) == 1
+        || ffi!(PyObject_HasAttr(
+            ob_type.cast::<PyObject>(),
+            get_array_interface_str()
+        )) == 1
+}
+
 #[repr(C)]
 pub(crate) struct PyCapsule {
     pub ob_refcnt: Py_ssize_t,
@@ -112,8 +158,8 @@ pub(crate) struct PyCapsule {
 
 // https://docs.scipy.org/doc/numpy/reference/arrays.interface.html#c.__array_struct__
 
-const NPY_ARRAY_C_CONTIGUOUS: c_int = 0x1;
-const NPY_ARRAY_NOTSWAPPED: c_int = 0x200;
+const NPY_ARRAY_ALIGNED: c_int = 0x100;
+pub(crate) const NPY_ARRAY_NOTSWAPPED: c_int = 0x200;
 
 #[repr(C)]
 pub(crate) struct PyArrayInterface {
@@ -131,6 +177,8 @@ pub(crate) struct PyArrayInterface {
 #[derive(Clone, Copy)]
 pub(crate) enum ItemType {
     BOOL,
+    C64,
+    C128,
     DATETIME64(NumpyDatetimeUnit),
     F16,
     F32,
@@ -143,12 +191,17 @@ pub(crate) enum ItemType {
     U16,
     U32,
     U64,
+    TIMEDELTA64(NumpyDatetimeUnit),
+    /// A structured dtype, whose fields are in `NumpyArray::fields`.
+    RECORD,
 }
 
 impl ItemType {
-    fn find(array: *mut PyArrayInterface, ptr: *mut PyObject) -> Option<ItemType> {
+    pub fn find(array: *mut PyArrayInterface, ptr: *mut PyObject) -> Option<ItemType> {
         match unsafe { ((*array).typekind, (*array).itemsize) } {
             (098, 1) => Some(ItemType::BOOL),
+            (099, 8) => Some(ItemType::C64),
+            (099, 16) => Some(ItemType::C128),
             (077, 8) => {
                 let unit = NumpyDatetimeUnit::from_pyobject(ptr);
                 Some(ItemType::DATETIME64(unit))
@@ -164,6 +217,10 @@ impl ItemType {
             (117, 2) => Some(ItemType::U16),
             (117, 4) => Some(ItemType::U32),
             (117, 8) => Some(ItemType::U64),
+            (109, 8) => {
+                let unit = NumpyDatetimeUnit::from_pyobject(ptr);
+                Some(ItemType::TIMEDELTA64(unit))
+            }
             _ => None,
         }
     }
@@ -171,7 +228,6 @@ impl ItemType {
 
 pub(crate) enum PyArrayError {
     Malformed,
-    NotContiguous,
     NotNativeEndian,
     UnsupportedDataType,
 }
@@ -190,6 +246,14 @@ pub(crate) struct NumpyArray {
     depth: usize,
     capsule: *mut PyCapsule,
     kind: ItemType,
+    /// Empty unless `kind` is `RECORD`.
+    fields: Vec<RecordField>,
+    /// The `__array_struct__` of the mask of a `numpy.ma.MaskedArray`, or
+    /// null if no item is masked.
+    mask: *mut PyCapsule,
+    /// The interface `array` points to if read from a DLPack tensor, whose
+    /// capsule is `capsule`.
+    dlpack: Option<Box<DlpackInterface>>,
     opts: Opt,
 }
 
@@ -197,7 +261,7 @@ impl NumpyArray {
     #[cold]
     #[inline(never)]
     #[cfg_attr(feature = "optimize", optimize(size))]
-    pub fn new(ptr: *mut PyObject, opts: Opt) -> Result<Self, PyArrayError> {
+    pub fn new(ptr: *mut PyObject, opts: Opt, masked: bool) -> Result<Self, PyArrayError> {
         let capsule = ffi!(PyObject_GetAttr(ptr, get_array_struct_str()));
         debug_assert!(!capsule.is_null());
         let array = unsafe {
@@ -206,13 +270,15 @@ impl NumpyArray {
                 .cast::<PyArrayInterface>()
         };
         debug_assert!(!array.is_null());
+        // each field of a record has its byte order checked, as the flags of a
+        // structured array are not reliable
+        let is_record = unsafe { (*array).typekind } == b'V' as c_char;
         if unsafe { (*array).two != 2 } {
             ffi!(Py_DECREF(capsule));
             Err(PyArrayError::Malformed)
-        } else if unsafe { (*array).flags } & NPY_ARRAY_C_CONTIGUOUS != NPY_ARRAY_C_CONTIGUOUS {
-            ffi!(Py_DECREF(capsule));
-            Err(PyArrayError::NotContiguous)
-        } else if unsafe { (*array).flags } & NPY_ARRAY_NOTSWAPPED != NPY_ARRAY_NOTSWAPPED {
+        } else if !is_record
+            && unsafe { (*array).flags } & NPY_ARRAY_NOTSWAPPED != NPY_ARRAY_NOTSWAPPED
+        {
             ffi!(Py_DECREF(capsule));
             Err(PyArrayError::NotNativeEndian)
         } else {
@@ -223,12 +289,37 @@ impl NumpyArray {
                 ffi!(Py_DECREF(capsule));
                 return Err(PyArrayError::UnsupportedDataType);
             }
-            match ItemType::find(array, ptr) {
+            let mut fields = Vec::new();
+            if is_record {
+                match RecordField::from_pyobject(ptr) {
+                    Ok(val) => fields = val,
+                    Err(err) => {
+                        ffi!(Py_DECREF(capsule));
+                        return Err(err);
+                    }
+                }
+            }
+            let kind = if is_record {
+                Some(ItemType::RECORD)
+            } else {
+                ItemType::find(array, ptr)
+            };
+            match kind {
                 None => {
                     ffi!(Py_DECREF(capsule));
                     Err(PyArrayError::UnsupportedDataType)
                 }
                 Some(kind) => {
+                    let mut mask = core::ptr::null_mut();
+                    if masked {
+                        match mask_from_pyobject(ptr, array) {
+                            Ok(val) => mask = val,
+                            Err(err) => {
+                                ffi!(Py_DECREF(capsule));
+                                return Err(err);
+                            }
+                        }
+                    }
                     let mut pyarray = NumpyArray {
                         array: array,
                         position: vec![0; num_dimensions],
@@ -236,6 +327,9 @@ impl NumpyArray {
                         depth: 0,
                         capsule: capsule.cast::<PyCapsule>(),
                         kind: kind,
+                        fields: fields,
+                        mask: mask,
+                        dlpack: None,
                         opts,
                     };
                     if pyarray.dimensions() > 1 {
@@ -247,6 +341,92 @@ impl NumpyArray {
         }
     }
 
+    /// Read the tensor of the `__dlpack__()` of `ptr`, which must be in host
+    /// memory, as a numpy array.
+    #[cold]
+    #[inline(never)]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    pub fn from_dlpack(ptr: *mut PyObject, opts: Opt) -> Result<Self, PyArrayError> {
+        let capsule = call_method!(ptr, get_dlpack_str());
+        if capsule.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(PyArrayError::Malformed);
+        }
+        let managed = ffi!(PyCapsule_GetPointer(capsule, c"dltensor".as_ptr()));
+        if managed.is_null() {
+            ffi!(PyErr_Clear());
+            ffi!(Py_DECREF(capsule));
+            return Err(PyArrayError::Malformed);
+        }
+        // the capsule is not renamed to "used_dltensor", so its destructor
+        // calls the deleter of the tensor
+        let mut dlpack = match DlpackInterface::new(managed.cast::<DLTensor>()) {
+            Ok(val) => Box::new(val),
+            Err(err) => {
+                ffi!(Py_DECREF(capsule));
+                return Err(err);
+            }
+        };
+        let array: *mut PyArrayInterface = &mut dlpack.interface;
+        let kind = match ItemType::find(array, ptr) {
+            Some(kind) => kind,
+            None => {
+                ffi!(Py_DECREF(capsule));
+                return Err(PyArrayError::UnsupportedDataType);
+            }
+        };
+        let num_dimensions = dlpack.shape.len();
+        let mut pyarray = NumpyArray {
+            array: array,
+            position: vec![0; num_dimensions],
+            children: Vec::with_capacity(num_dimensions),
+            depth: 0,
+            capsule: capsule.cast::<PyCapsule>(),
+            kind: kind,
+            fields: Vec::new(),
+            mask: core::ptr::null_mut(),
+            dlpack: Some(dlpack),
+            opts,
+        };
+        if pyarray.dimensions() > 1 {
+            pyarray.build();
+        }
+        Ok(pyarray)
+    }
+
+    /// Read `numpy.asarray(ptr)` of an object that exports the array
+    /// interface but is not a `numpy.ndarray`.
+    #[cold]
+    #[inline(never)]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    pub fn from_array_like(
+        ptr: *mut PyObject,
+        opts: Opt,
+        types: Option<&NumpyTypes>,
+    ) -> Result<Self, PyArrayError> {
+        let types = match types {
+            Some(types) if !types.asarray.is_null() => types,
+            _ => return Err(PyArrayError::UnsupportedDataType),
+        };
+        let array = ffi!(PyObject_CallFunctionObjArgs(
+            types.asarray,
+            ptr,
+            core::ptr::null_mut::<PyObject>()
+        ));
+        if array.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(PyArrayError::UnsupportedDataType);
+        }
+        // the `__array_struct__` capsule holds a reference to the array
+        let ret = if core::ptr::eq(ob_type!(array), types.array) {
+            NumpyArray::new(array, opts, false)
+        } else {
+            Err(PyArrayError::UnsupportedDataType)
+        };
+        ffi!(Py_DECREF(array));
+        ret
+    }
+
     #[cfg_attr(feature = "optimize", optimize(size))]
     fn child_from_parent(&self, position: Vec<isize>, num_children: usize) -> Self {
         let mut arr = NumpyArray {
@@ -256,6 +436,9 @@ impl NumpyArray {
             depth: self.depth + 1,
             capsule: self.capsule,
             kind: self.kind,
+            fields: self.fields.clone(),
+            mask: self.mask,
+            dlpack: None,
             opts: self.opts,
         };
         arr.build();
@@ -291,6 +474,34 @@ impl NumpyArray {
         unsafe { (*self.array).data.offset(offset) }
     }
 
+    /// Whether the items of `data()` can be read as a slice. Otherwise, as for
+    /// a view such as `array[:, ::2]` or `array.T`, they are read by stride.
+    /// Records are always read by stride.
+    fn is_contiguous(&self) -> bool {
+        matches!(self.kind, ItemType::RECORD)
+            || (unsafe { (*self.array).flags } & NPY_ARRAY_ALIGNED == NPY_ARRAY_ALIGNED
+                && self.strides()[self.dimensions() - 1]
+                    == unsafe { (*self.array).itemsize } as isize)
+    }
+
+    /// The mask of the items of `data()`, which is laid out by its own strides.
+    fn mask_data(&self) -> *const u8 {
+        let mask = unsafe { (*self.mask).pointer.cast::<PyArrayInterface>() };
+        let strides = slice!((*mask).strides.cast_const(), self.dimensions());
+        let offset = strides
+            .iter()
+            .zip(self.position.iter().copied())
+            .take(self.depth)
+            .map(|(a, b)| a * b)
+            .sum::<isize>();
+        unsafe { (*mask).data.cast::<u8>().cast_const().offset(offset) }
+    }
+
+    fn mask_stride(&self) -> isize {
+        let mask = unsafe { (*self.mask).pointer.cast::<PyArrayInterface>() };
+        slice!((*mask).strides.cast_const(), self.dimensions())[self.dimensions() - 1]
+    }
+
     fn num_items(&self) -> usize {
         isize_to_usize(self.shape()[self.shape().len() - 1])
     }
@@ -314,8 +525,13 @@ impl NumpyArray {
 impl Drop for NumpyArray {
     fn drop(&mut self) {
         if self.depth == 0 {
-            ffi!(Py_DECREF(self.array.cast::<PyObject>()));
+            if self.dlpack.is_none() {
+                ffi!(Py_DECREF(self.array.cast::<PyObject>()));
+            }
             ffi!(Py_DECREF(self.capsule.cast::<PyObject>()));
+            if !self.mask.is_null() {
+                ffi!(Py_DECREF(self.mask.cast::<PyObject>()));
+            }
         }
     }
 }
@@ -338,51 +554,125 @@ impl Serialize for NumpyArray {
                 seq.serialize_element(child).unwrap();
             }
             seq.end()
+        } else if !self.mask.is_null() || !self.is_contiguous() {
+            cold_path!();
+            let (mask, mask_stride) = if self.mask.is_null() {
+                (core::ptr::null(), 0)
+            } else {
+                (self.mask_data(), self.mask_stride())
+            };
+            NumpyStridedArray {
+                data: self.data().cast::<u8>(),
+                stride: self.strides()[self.dimensions() - 1],
+                mask: mask,
+                mask_stride: mask_stride,
+                len: self.num_items(),
+                kind: self.kind,
+                opts: self.opts,
+                position: &self.position[..self.depth],
+            }
+            .serialize(serializer)
         } else {
             match self.kind {
                 ItemType::F64 => {
-                    NumpyF64Array::new(slice!(self.data().cast::<f64>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<f64>(), self.num_items());
+                    if opt_disabled!(
+                        self.opts,
+                        INDENT | MSGPACK | FLOAT_DECIMALS | FLOAT_FORMAT_ANY
+                    ) {
+                        FloatArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyF64Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::F32 => {
-                    NumpyF32Array::new(slice!(self.data().cast::<f32>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<f32>(), self.num_items());
+                    if opt_disabled!(
+                        self.opts,
+                        INDENT | MSGPACK | FLOAT_DECIMALS | FLOAT_FORMAT_ANY
+                    ) {
+                        FloatArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyF32Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::F16 => {
                     NumpyF16Array::new(slice!(self.data().cast::<u16>(), self.num_items()))
                         .serialize(serializer)
                 }
+                ItemType::C128 => NumpyComplexArray::new(slice!(
+                    self.data().cast::<Complex<f64>>(),
+                    self.num_items()
+                ))
+                .serialize(serializer),
+                ItemType::C64 => NumpyComplexArray::new(slice!(
+                    self.data().cast::<Complex<f32>>(),
+                    self.num_items()
+                ))
+                .serialize(serializer),
                 ItemType::U64 => {
-                    NumpyU64Array::new(slice!(self.data().cast::<u64>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<u64>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyU64Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::U32 => {
-                    NumpyU32Array::new(slice!(self.data().cast::<u32>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<u32>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyU32Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::U16 => {
-                    NumpyU16Array::new(slice!(self.data().cast::<u16>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<u16>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyU16Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::U8 => {
-                    NumpyU8Array::new(slice!(self.data().cast::<u8>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<u8>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyU8Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::I64 => {
-                    NumpyI64Array::new(slice!(self.data().cast::<i64>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<i64>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyI64Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::I32 => {
-                    NumpyI32Array::new(slice!(self.data().cast::<i32>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<i32>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyI32Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::I16 => {
-                    NumpyI16Array::new(slice!(self.data().cast::<i16>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<i16>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyI16Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::I8 => {
-                    NumpyI8Array::new(slice!(self.data().cast::<i8>(), self.num_items()))
-                        .serialize(serializer)
+                    let data = slice!(self.data().cast::<i8>(), self.num_items());
+                    if opt_disabled!(self.opts, INDENT | MSGPACK) {
+                        IntArraySerializer::new(data).serialize(serializer)
+                    } else {
+                        NumpyI8Array::new(data).serialize(serializer)
+                    }
                 }
                 ItemType::BOOL => {
                     NumpyBoolArray::new(slice!(self.data().cast::<u8>(), self.num_items()))
@@ -392,10 +682,212 @@ impl Serialize for NumpyArray {
                     slice!(self.data().cast::<i64>(), self.num_items()),
                     unit,
                     self.opts,
+                    &self.position[..self.depth],
                 )
                 .serialize(serializer),
+                ItemType::TIMEDELTA64(unit) => NumpyTimedelta64Array {
+                    data: slice!(self.data().cast::<i64>(), self.num_items()),
+                    unit: unit,
+                    opts: self.opts,
+                    position: &self.position[..self.depth],
+                }
+                .serialize(serializer),
+                ItemType::RECORD => NumpyRecordArray {
+                    data: self.data().cast::<u8>(),
+                    len: self.num_items(),
+                    stride: self.strides()[self.dimensions() - 1],
+                    fields: &self.fields,
+                    opts: self.opts,
+                    position: &self.position[..self.depth],
+                }
+                .serialize(serializer),
+            }
+        }
+    }
+}
+
+// https://dmlc.github.io/dlpack/latest/c_api.html
+
+const DL_CPU: i32 = 1;
+const DL_CUDA_HOST: i32 = 3;
+
+#[repr(C)]
+struct DLTensor {
+    data: *mut c_void,
+    device_type: i32,
+    device_id: i32,
+    ndim: i32,
+    dtype_code: u8,
+    dtype_bits: u8,
+    dtype_lanes: u16,
+    shape: *const i64,
+    /// In items rather than bytes, or null if C-contiguous.
+    strides: *const i64,
+    byte_offset: u64,
+}
+
+/// A `PyArrayInterface` that describes a `DLTensor`, and the shape and
+/// strides in bytes it points to.
+struct DlpackInterface {
+    interface: PyArrayInterface,
+    shape: Vec<Py_intptr_t>,
+    strides: Vec<Py_intptr_t>,
+}
+
+impl DlpackInterface {
+    #[cold]
+    fn new(tensor: *const DLTensor) -> Result<Self, PyArrayError> {
+        let tensor = unsafe { &*tensor };
+        if tensor.device_type != DL_CPU && tensor.device_type != DL_CUDA_HOST {
+            return Err(PyArrayError::UnsupportedDataType);
+        }
+        let typekind = match (tensor.dtype_code, tensor.dtype_bits) {
+            (0, 8 | 16 | 32 | 64) => b'i',
+            (1, 8 | 16 | 32 | 64) => b'u',
+            (2, 16 | 32 | 64) => b'f',
+            (5, 64 | 128) => b'c',
+            (6, 8) => b'b',
+            _ => return Err(PyArrayError::UnsupportedDataType),
+        };
+        if tensor.dtype_lanes != 1 || tensor.ndim <= 0 {
+            return Err(PyArrayError::UnsupportedDataType);
+        }
+        if tensor.shape.is_null() {
+            return Err(PyArrayError::Malformed);
+        }
+        #[allow(clippy::cast_sign_loss)]
+        let ndim = tensor.ndim as usize;
+        let itemsize = isize::from(tensor.dtype_bits / 8);
+        #[allow(clippy::cast_possible_truncation)]
+        let shape = slice!(tensor.shape, ndim)
+            .iter()
+            .map(|dim| *dim as isize)
+            .collect::<Vec<isize>>();
+        let mut strides = vec![itemsize; ndim];
+        if tensor.strides.is_null() {
+            for idx in (0..ndim - 1).rev() {
+                strides[idx] = strides[idx + 1] * shape[idx + 1];
+            }
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            for (stride, items) in strides.iter_mut().zip(slice!(tensor.strides, ndim)) {
+                *stride = *items as isize * itemsize;
+            }
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let data = unsafe {
+            tensor
+                .data
+                .cast::<u8>()
+                .add(tensor.byte_offset as usize)
+                .cast::<c_void>()
+        };
+        let aligned = (data as usize) % (itemsize as usize) == 0;
+        let mut ret = DlpackInterface {
+            interface: PyArrayInterface {
+                two: 2,
+                nd: tensor.ndim,
+                typekind: typekind as c_char,
+                itemsize: c_int::from(tensor.dtype_bits / 8),
+                flags: NPY_ARRAY_NOTSWAPPED | if aligned { NPY_ARRAY_ALIGNED } else { 0 },
+                shape: core::ptr::null_mut(),
+                strides: core::ptr::null_mut(),
+                data: data,
+                descr: core::ptr::null_mut(),
+            },
+            shape: shape,
+            strides: strides,
+        };
+        ret.interface.shape = ret.shape.as_mut_ptr();
+        ret.interface.strides = ret.strides.as_mut_ptr();
+        Ok(ret)
+    }
+}
+
+/// The `__array_struct__` of the `mask` of the `numpy.ma.MaskedArray` `ptr`,
+/// whose `__array_struct__` is `array`, or null if it has no mask.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn mask_from_pyobject(
+    ptr: *mut PyObject,
+    array: *mut PyArrayInterface,
+) -> Result<*mut PyCapsule, PyArrayError> {
+    let mask = ffi!(PyObject_GetAttr(ptr, get_mask_str()));
+    if mask.is_null() {
+        ffi!(PyErr_Clear());
+        return Err(PyArrayError::Malformed);
+    }
+    // `numpy.ma.nomask` is a scalar
+    let capsule = ffi!(PyObject_GetAttr(mask, get_array_struct_str()));
+    ffi!(Py_DECREF(mask));
+    if capsule.is_null() {
+        ffi!(PyErr_Clear());
+        return Ok(core::ptr::null_mut());
+    }
+    let mask_array = unsafe {
+        (*capsule.cast::<PyCapsule>())
+            .pointer
+            .cast::<PyArrayInterface>()
+    };
+    let nd = unsafe { (*array).nd };
+    if unsafe { (*mask_array).nd } == 0 {
+        ffi!(Py_DECREF(capsule));
+        return Ok(core::ptr::null_mut());
+    }
+    #[allow(clippy::cast_sign_loss)]
+    let same_shape = unsafe {
+        (*mask_array).nd == nd
+            && slice!((*mask_array).shape.cast_const(), nd as usize)
+                == slice!((*array).shape.cast_const(), nd as usize)
+    };
+    // a structured array has a structured mask, with one item per field
+    if unsafe { (*mask_array).two != 2 || (*mask_array).typekind != b'b' as c_char } || !same_shape
+    {
+        ffi!(Py_DECREF(capsule));
+        return Err(PyArrayError::UnsupportedDataType);
+    }
+    Ok(capsule.cast::<PyCapsule>())
+}
+
+/// The items of an array that is not contiguous or has a mask, as of a
+/// `numpy.ma.MaskedArray`. Masked items are written as `null`.
+struct NumpyStridedArray<'a> {
+    data: *const u8,
+    stride: isize,
+    /// Null if no item is masked.
+    mask: *const u8,
+    mask_stride: isize,
+    len: usize,
+    kind: ItemType,
+    opts: Opt,
+    /// The position of the array in its parent, for errors.
+    position: &'a [isize],
+}
+
+impl Serialize for NumpyStridedArray<'_> {
+    #[cold]
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for idx in 0..self.len {
+            #[allow(clippy::cast_possible_wrap)]
+            let idx = idx as isize;
+            if !self.mask.is_null() && unsafe { *self.mask.offset(idx * self.mask_stride) } != 0 {
+                seq.serialize_element(&()).unwrap();
+            } else {
+                seq.serialize_element(&NumpyItem {
+                    data: unsafe { self.data.offset(idx * self.stride) },
+                    kind: self.kind,
+                    opts: self.opts,
+                    position: self.position,
+                    index: idx,
+                })?;
             }
         }
+        seq.end()
     }
 }
 
@@ -524,6 +1016,54 @@ impl Serialize for DataTypeF16 {
     }
 }
 
+/// `numpy.complex64` and `numpy.complex128`, which are written as
+/// `[real, imag]`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct Complex<T> {
+    real: T,
+    imag: T,
+}
+
+impl<T: Serialize> Serialize for Complex<T> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        seq.serialize_element(&self.real)?;
+        seq.serialize_element(&self.imag)?;
+        seq.end()
+    }
+}
+
+#[repr(transparent)]
+struct NumpyComplexArray<'a, T> {
+    data: &'a [Complex<T>],
+}
+
+impl<'a, T> NumpyComplexArray<'a, T> {
+    fn new(data: &'a [Complex<T>]) -> Self {
+        Self { data }
+    }
+}
+
+impl<T: Serialize> Serialize for NumpyComplexArray<'_, T> {
+    #[cold]
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for each in self.data.iter() {
+            seq.serialize_element(each)?;
+        }
+        seq.end()
+    }
+}
+
 #[repr(transparent)]
 struct NumpyU64Array<'a> {
     data: &'a [u64],
@@ -913,14 +1453,21 @@ impl Serialize for NumpyScalar {
     {
         unsafe {
             let ob_type = ob_type!(self.ptr);
-            let scalar_types =
-                unsafe { NUMPY_TYPES.get_or_init(load_numpy_types).unwrap().as_ref() };
+            let scalar_types = get_numpy_types_from_state(crate::runtime::current_state()).unwrap();
             if core::ptr::eq(ob_type, scalar_types.float64) {
                 (*(self.ptr.cast::<NumpyFloat64>())).serialize(serializer)
             } else if core::ptr::eq(ob_type, scalar_types.float32) {
                 (*(self.ptr.cast::<NumpyFloat32>())).serialize(serializer)
             } else if core::ptr::eq(ob_type, scalar_types.float16) {
                 (*(self.ptr.cast::<NumpyFloat16>())).serialize(serializer)
+            } else if core::ptr::eq(ob_type, scalar_types.complex128) {
+                (*(self.ptr.cast::<NumpyComplex<f64>>()))
+                    .value
+                    .serialize(serializer)
+            } else if core::ptr::eq(ob_type, scalar_types.complex64) {
+                (*(self.ptr.cast::<NumpyComplex<f32>>()))
+                    .value
+                    .serialize(serializer)
             } else if core::ptr::eq(ob_type, scalar_types.int64) {
                 (*(self.ptr.cast::<NumpyInt64>())).serialize(serializer)
             } else if core::ptr::eq(ob_type, scalar_types.int32) {
@@ -944,8 +1491,16 @@ impl Serialize for NumpyScalar {
                 let obj = &*self.ptr.cast::<NumpyDatetime64>();
                 let dt = unit
                     .datetime(obj.value, self.opts)
-                    .map_err(NumpyDateTimeError::into_serde_err)?;
+                    .map_err(|err| err.into_serde_err(None))?;
                 dt.serialize(serializer)
+            } else if core::ptr::eq(ob_type, scalar_types.timedelta64) {
+                let unit = NumpyDatetimeUnit::from_pyobject(self.ptr);
+                // the same layout as `numpy.datetime64`
+                let obj = &*self.ptr.cast::<NumpyDatetime64>();
+                let td = unit
+                    .timedelta(obj.value, self.opts)
+                    .map_err(|err| err.into_serde_err(None))?;
+                td.serialize(serializer)
             } else {
                 unreachable!()
             }
@@ -1141,6 +1696,13 @@ impl Serialize for NumpyFloat64 {
     }
 }
 
+#[repr(C)]
+pub(crate) struct NumpyComplex<T> {
+    ob_refcnt: Py_ssize_t,
+    ob_type: *mut PyTypeObject,
+    value: Complex<T>,
+}
+
 #[repr(C)]
 pub(crate) struct NumpyBool {
     ob_refcnt: Py_ssize_t,
@@ -1208,33 +1770,53 @@ impl fmt::Display for NumpyDatetimeUnit {
 #[derive(Clone, Copy)]
 enum NumpyDateTimeError {
     UnsupportedUnit(NumpyDatetimeUnit),
-    Unrepresentable { unit: NumpyDatetimeUnit, val: i64 },
+    NaT,
+    OutOfRange { unit: NumpyDatetimeUnit, val: i64 },
+    UnsupportedTimedeltaUnit(NumpyDatetimeUnit),
+    TimedeltaNaT,
 }
 
 impl NumpyDateTimeError {
+    /// `index` is the position of the value in its array, if any.
     #[cold]
-    fn into_serde_err<T: ser::Error>(self) -> T {
+    fn into_serde_err<T: ser::Error>(self, index: Option<&[isize]>) -> T {
+        let at = match index {
+            Some(index) => format!(" at index {index:?}"),
+            None => String::new(),
+        };
         let err = match self {
             Self::UnsupportedUnit(unit) => format!("unsupported numpy.datetime64 unit: {unit}"),
-            Self::Unrepresentable { unit, val } => {
-                format!("unrepresentable numpy.datetime64: {val} {unit}")
+            Self::NaT => format!("numpy.datetime64 NaT{at}"),
+            Self::OutOfRange { unit, val } => {
+                format!("numpy.datetime64 out of range{at}: {val} {unit}")
             }
+            Self::UnsupportedTimedeltaUnit(unit) => {
+                format!("unsupported numpy.timedelta64 unit: {unit}")
+            }
+            Self::TimedeltaNaT => format!("numpy.timedelta64 NaT{at}"),
         };
         ser::Error::custom(err)
     }
 }
 
-macro_rules! to_jiff_datetime {
-    ($timestamp:expr, $self:expr, $val:expr) => {
-        Ok(
-            ($timestamp.map_err(|_| NumpyDateTimeError::Unrepresentable {
-                unit: $self,
-                val: $val,
-            })?)
-            .to_zoned(jiff::tz::TimeZone::UTC)
-            .datetime(),
-        )
-    };
+const NANOSECONDS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// Convert days since 1970-01-01 to a proleptic Gregorian year, month, and
+/// day, for any number of days.
+///
+/// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn civil_from_days(days: i128) -> (i128, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + i128::from(month <= 2);
+    (year, month, day)
 }
 
 impl NumpyDatetimeUnit {
@@ -1255,12 +1837,20 @@ impl NumpyDatetimeUnit {
         let el0 = ffi!(PyList_GET_ITEM(descr, 0));
         let descr_str = ffi!(PyTuple_GET_ITEM(el0, 1));
         let uni = unsafe { PyStr::from_ptr_unchecked(descr_str).to_str().unwrap() };
+        let ret = Self::from_typestr(uni).unwrap();
+        ffi!(Py_DECREF(dtype));
+        ffi!(Py_DECREF(descr));
+        ret
+    }
+
+    /// The unit of a `datetime64` type string such as `<M8[us]`.
+    fn from_typestr(uni: &str) -> Option<Self> {
         if uni.len() < 5 {
-            return Self::NaT;
+            return Some(Self::NaT);
         }
         // unit descriptions are found at
         // https://github.com/numpy/numpy/blob/b235f9e701e14ed6f6f6dcba885f7986a833743f/numpy/core/src/multiarray/datetime.c#L79-L96.
-        let ret = match &uni[4..uni.len() - 1] {
+        let ret = match uni.get(4..uni.len() - 1)? {
             "Y" => Self::Years,
             "M" => Self::Months,
             "W" => Self::Weeks,
@@ -1275,61 +1865,146 @@ impl NumpyDatetimeUnit {
             "fs" => Self::Femtoseconds,
             "as" => Self::Attoseconds,
             "generic" => Self::Generic,
-            _ => unreachable!(),
+            _ => return None,
         };
-        ffi!(Py_DECREF(dtype));
-        ffi!(Py_DECREF(descr));
-        ret
+        Some(ret)
     }
 
-    /// Return a `NumpyDatetime64Repr` for a value in array with this unit.
+    /// The length of this unit in nanoseconds, if it is at least one
+    /// nanosecond and fixed.
+    fn nanoseconds(self) -> Option<i128> {
+        match self {
+            Self::Weeks => Some(7 * NANOSECONDS_PER_DAY),
+            Self::Days => Some(NANOSECONDS_PER_DAY),
+            Self::Hours => Some(3_600_000_000_000),
+            Self::Minutes => Some(60_000_000_000),
+            Self::Seconds => Some(1_000_000_000),
+            Self::Milliseconds => Some(1_000_000),
+            Self::Microseconds => Some(1_000),
+            Self::Nanoseconds => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Return a `NumpyDatetime64Repr` for a value in array with this unit, or
+    /// `None` if it is to be written as `null`.
     ///
-    /// Returns an `Err(NumpyDateTimeError)` if the value is invalid for this unit.
+    /// `NaT` and values outside of years 1 to 9999 are handled as set by
+    /// `datetime64_range=` in `opts`.
+    /// The units finer than nanoseconds and the generic unit are not
+    /// supported.
     #[cold]
     #[cfg_attr(feature = "optimize", optimize(size))]
-    fn datetime(self, val: i64, opts: Opt) -> Result<NumpyDatetime64Repr, NumpyDateTimeError> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn datetime(
+        self,
+        val: i64,
+        opts: Opt,
+    ) -> Result<Option<NumpyDatetime64Repr>, NumpyDateTimeError> {
+        let policy = opts & DATETIME64_RANGE;
+        if !matches!(self, Self::NaT | Self::Years | Self::Months) && self.nanoseconds().is_none() {
+            return Err(NumpyDateTimeError::UnsupportedUnit(self));
+        }
+        if matches!(self, Self::NaT) || val == i64::MIN {
+            return if policy == 0 {
+                Err(NumpyDateTimeError::NaT)
+            } else {
+                Ok(None)
+            };
+        }
+        let mut repr = NumpyDatetime64Repr {
+            year: 0,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+            opts,
+        };
         match self {
-            Self::Years => Ok(DateTime::new(
-                (val + 1970)
-                    .try_into()
-                    .map_err(|_| NumpyDateTimeError::Unrepresentable { unit: self, val })?,
-                1,
-                1,
-                0,
-                0,
-                0,
-                0,
-            )
-            .unwrap()),
-            Self::Months => Ok(DateTime::new(
-                (val / 12 + 1970)
-                    .try_into()
-                    .map_err(|_| NumpyDateTimeError::Unrepresentable { unit: self, val })?,
-                (val % 12 + 1)
-                    .try_into()
-                    .map_err(|_| NumpyDateTimeError::Unrepresentable { unit: self, val })?,
-                1,
-                0,
-                0,
-                0,
-                0,
-            )
-            .unwrap()),
-            Self::Weeks => {
-                to_jiff_datetime!(Timestamp::from_second(val * 7 * 24 * 60 * 60), self, val)
+            Self::Years => repr.year = 1970 + i128::from(val),
+            Self::Months => {
+                repr.year = 1970 + i128::from(val).div_euclid(12);
+                repr.month = i128::from(val).rem_euclid(12) as u8 + 1;
             }
-            Self::Days => to_jiff_datetime!(Timestamp::from_second(val * 24 * 60 * 60), self, val),
-            Self::Hours => to_jiff_datetime!(Timestamp::from_second(val * 60 * 60), self, val),
-            Self::Minutes => to_jiff_datetime!(Timestamp::from_second(val * 60), self, val),
-            Self::Seconds => to_jiff_datetime!(Timestamp::from_second(val), self, val),
-            Self::Milliseconds => to_jiff_datetime!(Timestamp::from_millisecond(val), self, val),
-            Self::Microseconds => to_jiff_datetime!(Timestamp::from_microsecond(val), self, val),
-            Self::Nanoseconds => {
-                to_jiff_datetime!(Timestamp::from_nanosecond(i128::from(val)), self, val)
+            _ => {
+                let nanos = i128::from(val) * self.nanoseconds().unwrap();
+                (repr.year, repr.month, repr.day) =
+                    civil_from_days(nanos.div_euclid(NANOSECONDS_PER_DAY));
+                let nanos = nanos.rem_euclid(NANOSECONDS_PER_DAY);
+                let seconds = nanos / 1_000_000_000;
+                repr.hour = (seconds / 3_600) as u8;
+                repr.minute = (seconds / 60 % 60) as u8;
+                repr.second = (seconds % 60) as u8;
+                repr.nanosecond = (nanos % 1_000_000_000) as u32;
             }
-            _ => Err(NumpyDateTimeError::UnsupportedUnit(self)),
         }
-        .map(|dt| NumpyDatetime64Repr { dt, opts })
+        if (1..=9999).contains(&repr.year) {
+            return Ok(Some(repr));
+        }
+        match policy {
+            0 => Err(NumpyDateTimeError::OutOfRange { unit: self, val }),
+            DATETIME64_RANGE_NULL => Ok(None),
+            DATETIME64_RANGE_CLAMP if repr.year < 1 => Ok(Some(NumpyDatetime64Repr {
+                year: 1,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+                nanosecond: 0,
+                opts,
+            })),
+            DATETIME64_RANGE_CLAMP => Ok(Some(NumpyDatetime64Repr {
+                year: 9999,
+                month: 12,
+                day: 31,
+                hour: 23,
+                minute: 59,
+                second: 59,
+                nanosecond: 999_999_999,
+                opts,
+            })),
+            _ => Ok(Some(repr)),
+        }
+    }
+
+    /// Return a `NumpyTimedelta64Repr` for a value in array with this unit,
+    /// or `None` if it is to be written as `null`.
+    ///
+    /// `NaT` is handled as set by `datetime64_range=` in `opts`. A duration in
+    /// years or months is written in that unit, as its length varies. The
+    /// units finer than nanoseconds and the generic unit, which has no
+    /// length, are not supported.
+    #[cold]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    fn timedelta(
+        self,
+        val: i64,
+        opts: Opt,
+    ) -> Result<Option<NumpyTimedelta64Repr>, NumpyDateTimeError> {
+        if val == i64::MIN {
+            return if opts & DATETIME64_RANGE == 0 {
+                Err(NumpyDateTimeError::TimedeltaNaT)
+            } else {
+                Ok(None)
+            };
+        }
+        match self {
+            Self::Years | Self::Months => Ok(Some(NumpyTimedelta64Repr::Calendar {
+                val: val,
+                unit: self,
+            })),
+            // `numpy.timedelta64(1)` has no unit
+            Self::NaT => Err(NumpyDateTimeError::UnsupportedTimedeltaUnit(Self::Generic)),
+            _ => match self.nanoseconds() {
+                Some(nanoseconds) => Ok(Some(NumpyTimedelta64Repr::Fixed(
+                    i128::from(val) * nanoseconds,
+                ))),
+                None => Err(NumpyDateTimeError::UnsupportedTimedeltaUnit(self)),
+            },
+        }
     }
 }
 
@@ -1337,11 +2012,18 @@ struct NumpyDatetime64Array<'a> {
     data: &'a [i64],
     unit: NumpyDatetimeUnit,
     opts: Opt,
+    /// The position of the array in its parent, for errors.
+    position: &'a [isize],
 }
 
 impl<'a> NumpyDatetime64Array<'a> {
-    fn new(data: &'a [i64], unit: NumpyDatetimeUnit, opts: Opt) -> Self {
-        Self { data, unit, opts }
+    fn new(data: &'a [i64], unit: NumpyDatetimeUnit, opts: Opt, position: &'a [isize]) -> Self {
+        Self {
+            data,
+            unit,
+            opts,
+            position,
+        }
     }
 }
 
@@ -1352,11 +2034,13 @@ impl Serialize for NumpyDatetime64Array<'_> {
         S: Serializer,
     {
         let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            let dt = self
-                .unit
-                .datetime(each, self.opts)
-                .map_err(NumpyDateTimeError::into_serde_err)?;
+        for (i, &each) in self.data.iter().enumerate() {
+            let dt = self.unit.datetime(each, self.opts).map_err(|err| {
+                let mut index = self.position.to_vec();
+                #[allow(clippy::cast_possible_wrap)]
+                index.push(i as isize);
+                err.into_serde_err(Some(&index))
+            })?;
             seq.serialize_element(&dt).unwrap();
         }
         seq.end()
@@ -1370,35 +2054,49 @@ pub(crate) struct NumpyDatetime64 {
     value: i64,
 }
 
-macro_rules! forward_inner {
-    ($meth: ident, $ty: ident) => {
-        fn $meth(&self) -> $ty {
-            debug_assert!(self.dt.$meth() >= 0);
-            #[allow(clippy::cast_sign_loss)]
-            let ret = self.dt.$meth() as $ty; // stmt_expr_attributes
-            ret
-        }
-    };
-}
-
+/// A `numpy.datetime64` in UTC. `year` is outside of 1 to 9999 only with
+/// `datetime64_range="expanded"`.
 struct NumpyDatetime64Repr {
-    dt: DateTime,
+    year: i128,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
     opts: Opt,
 }
 
 impl DateTimeLike for NumpyDatetime64Repr {
-    forward_inner!(year, i32);
-    forward_inner!(month, u8);
-    forward_inner!(day, u8);
-    forward_inner!(hour, u8);
-    forward_inner!(minute, u8);
-    forward_inner!(second, u8);
+    fn year(&self) -> i32 {
+        debug_assert!((1..=9999).contains(&self.year));
+        #[allow(clippy::cast_possible_truncation)]
+        let ret = self.year as i32; // stmt_expr_attributes
+        ret
+    }
+
+    fn month(&self) -> u8 {
+        self.month
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    fn second(&self) -> u8 {
+        self.second
+    }
 
     fn nanosecond(&self) -> u32 {
-        debug_assert!(self.dt.subsec_nanosecond() >= 0);
-        #[allow(clippy::cast_sign_loss)]
-        let ret = self.dt.subsec_nanosecond() as u32; // stmt_expr_attributes
-        ret
+        self.nanosecond
     }
 
     fn microsecond(&self) -> u32 {
@@ -1418,6 +2116,28 @@ impl DateTimeLike for NumpyDatetime64Repr {
     }
 }
 
+impl NumpyDatetime64Repr {
+    /// Write the ISO 8601 expanded representation, a sign and at least six
+    /// digits of year, for a year outside of 1 to 9999.
+    #[cold]
+    fn write_expanded(&self, buf: &mut SmallFixedBuffer) {
+        buf.put_u8(if self.year < 0 { b'-' } else { b'+' });
+        let mut yearbuf = itoa::Buffer::new();
+        let formatted = yearbuf.format(self.year.unsigned_abs());
+        if formatted.len() < 6 {
+            buf.put_slice(&b"000000"[..(6 - formatted.len())]);
+        }
+        buf.put_slice(formatted.as_bytes());
+        let rest = NumpyDatetime64Repr {
+            year: 2000,
+            ..*self
+        };
+        let mut restbuf = SmallFixedBuffer::new();
+        let _ = rest.write_buf(&mut restbuf, self.opts);
+        buf.put_slice(&slice!(restbuf.as_ptr(), restbuf.len())[4..]);
+    }
+}
+
 impl Serialize for NumpyDatetime64Repr {
     #[cold]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1425,7 +2145,364 @@ impl Serialize for NumpyDatetime64Repr {
         S: Serializer,
     {
         let mut buf = SmallFixedBuffer::new();
-        let _ = self.write_buf(&mut buf, self.opts);
+        if (1..=9999).contains(&self.year) {
+            let _ = self.write_buf(&mut buf, self.opts);
+        } else {
+            self.write_expanded(&mut buf);
+        }
         serializer.collect_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
+
+struct NumpyTimedelta64Array<'a> {
+    data: &'a [i64],
+    unit: NumpyDatetimeUnit,
+    opts: Opt,
+    /// The position of the array in its parent, for errors.
+    position: &'a [isize],
+}
+
+impl Serialize for NumpyTimedelta64Array<'_> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for (i, &each) in self.data.iter().enumerate() {
+            let td = self.unit.timedelta(each, self.opts).map_err(|err| {
+                let mut index = self.position.to_vec();
+                #[allow(clippy::cast_possible_wrap)]
+                index.push(i as isize);
+                err.into_serde_err(Some(&index))
+            })?;
+            seq.serialize_element(&td).unwrap();
+        }
+        seq.end()
+    }
+}
+
+/// A `numpy.timedelta64`, written as an ISO 8601 duration such as
+/// `P1DT2H30M` or `-PT0.5S`.
+enum NumpyTimedelta64Repr {
+    /// A number of years or months.
+    Calendar { val: i64, unit: NumpyDatetimeUnit },
+    /// A number of nanoseconds.
+    Fixed(i128),
+}
+
+impl fmt::Display for NumpyTimedelta64Repr {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Calendar { val, unit } => {
+                let sign = if val < 0 { "-" } else { "" };
+                let designator = if matches!(unit, NumpyDatetimeUnit::Years) {
+                    'Y'
+                } else {
+                    'M'
+                };
+                write!(f, "{sign}P{}{designator}", val.unsigned_abs())
+            }
+            Self::Fixed(nanos) => {
+                if nanos < 0 {
+                    f.write_str("-")?;
+                }
+                let nanos = nanos.unsigned_abs();
+                let days = nanos / NANOSECONDS_PER_DAY.unsigned_abs();
+                let rest = nanos % NANOSECONDS_PER_DAY.unsigned_abs();
+                f.write_str("P")?;
+                if days != 0 {
+                    write!(f, "{days}D")?;
+                    if rest == 0 {
+                        return Ok(());
+                    }
+                }
+                f.write_str("T")?;
+                let seconds = rest / 1_000_000_000;
+                let fraction = rest % 1_000_000_000;
+                if seconds >= 3_600 {
+                    write!(f, "{}H", seconds / 3_600)?;
+                }
+                if seconds % 3_600 >= 60 {
+                    write!(f, "{}M", seconds / 60 % 60)?;
+                }
+                if seconds % 60 != 0 || fraction != 0 || seconds == 0 {
+                    write!(f, "{}", seconds % 60)?;
+                    if fraction != 0 {
+                        let digits = format!("{fraction:09}");
+                        write!(f, ".{}", digits.trim_end_matches('0'))?;
+                    }
+                    f.write_str("S")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Serialize for NumpyTimedelta64Repr {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// A named field of a structured dtype.
+#[derive(Clone)]
+pub(crate) struct RecordField {
+    name: String,
+    /// From the start of the record.
+    offset: usize,
+    kind: ItemType,
+}
+
+impl RecordField {
+    /// The named fields of the structured dtype of the array `ptr`, in order.
+    /// A field that is itself structured or a subarray is not supported.
+    #[cold]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    fn from_pyobject(ptr: *mut PyObject) -> Result<Vec<Self>, PyArrayError> {
+        let dtype = ffi!(PyObject_GetAttr(ptr, get_dtype_str()));
+        if dtype.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(PyArrayError::Malformed);
+        }
+        let descr = ffi!(PyObject_GetAttr(dtype, get_descr_str()));
+        ffi!(Py_DECREF(dtype));
+        if descr.is_null() {
+            // e.g., fields that overlap
+            ffi!(PyErr_Clear());
+            return Err(PyArrayError::UnsupportedDataType);
+        }
+        let ret = Self::from_descr(descr);
+        ffi!(Py_DECREF(descr));
+        ret
+    }
+
+    /// Read `dtype.descr`, a list of `(name, typestr)` in which padding has
+    /// an empty name and a name may be a `(title, name)` tuple.
+    fn from_descr(descr: *mut PyObject) -> Result<Vec<Self>, PyArrayError> {
+        if !is_type!(ob_type!(descr), crate::typeref::list_type_ptr()) {
+            return Err(PyArrayError::UnsupportedDataType);
+        }
+        let mut fields = Vec::new();
+        let mut offset = 0;
+        for idx in 0..ffi!(Py_SIZE(descr)) {
+            let item = ffi!(PyList_GET_ITEM(descr, idx));
+            if !is_type!(ob_type!(item), crate::typeref::tuple_type_ptr())
+                || ffi!(Py_SIZE(item)) != 2
+            {
+                return Err(PyArrayError::UnsupportedDataType);
+            }
+            let mut name = unsafe { crate::ffi::PyTuple_GET_ITEM(item, 0) };
+            if is_type!(ob_type!(name), crate::typeref::tuple_type_ptr())
+                && ffi!(Py_SIZE(name)) == 2
+            {
+                name = unsafe { crate::ffi::PyTuple_GET_ITEM(name, 1) };
+            }
+            let typestr = unsafe { crate::ffi::PyTuple_GET_ITEM(item, 1) };
+            if !is_type!(ob_type!(name), crate::typeref::str_type_ptr())
+                || !is_type!(ob_type!(typestr), crate::typeref::str_type_ptr())
+            {
+                return Err(PyArrayError::UnsupportedDataType);
+            }
+            let name = unsafe { PyStr::from_ptr_unchecked(name).to_str() }
+                .ok_or(PyArrayError::UnsupportedDataType)?;
+            let typestr = unsafe { PyStr::from_ptr_unchecked(typestr).to_str() }
+                .ok_or(PyArrayError::UnsupportedDataType)?;
+            let (kind, size) = Self::parse_typestr(typestr)?;
+            match kind {
+                Some(kind) => fields.push(RecordField {
+                    name: String::from(name),
+                    offset: offset,
+                    kind: kind,
+                }),
+                None if name.is_empty() => {}
+                None => return Err(PyArrayError::UnsupportedDataType),
+            }
+            offset += size;
+        }
+        Ok(fields)
+    }
+
+    /// The type and size of a typestr such as `<f8`, or no type for padding.
+    fn parse_typestr(typestr: &str) -> Result<(Option<ItemType>, usize), PyArrayError> {
+        let bytes = typestr.as_bytes();
+        let (Some(&order), Some(&kind)) = (bytes.first(), bytes.get(1)) else {
+            return Err(PyArrayError::UnsupportedDataType);
+        };
+        let swapped = if cfg!(target_endian = "little") {
+            b'>'
+        } else {
+            b'<'
+        };
+        if order == swapped {
+            return Err(PyArrayError::NotNativeEndian);
+        }
+        let digits = bytes[2..]
+            .iter()
+            .take_while(|ch| ch.is_ascii_digit())
+            .count();
+        let size: usize = typestr[2..2 + digits]
+            .parse()
+            .map_err(|_| PyArrayError::UnsupportedDataType)?;
+        let kind = match (kind, size) {
+            (b'V', _) => None,
+            (b'b', 1) => Some(ItemType::BOOL),
+            (b'c', 8) => Some(ItemType::C64),
+            (b'c', 16) => Some(ItemType::C128),
+            (b'M', 8) => Some(ItemType::DATETIME64(
+                NumpyDatetimeUnit::from_typestr(typestr)
+                    .ok_or(PyArrayError::UnsupportedDataType)?,
+            )),
+            (b'm', 8) => Some(ItemType::TIMEDELTA64(
+                NumpyDatetimeUnit::from_typestr(typestr)
+                    .ok_or(PyArrayError::UnsupportedDataType)?,
+            )),
+            (b'f', 2) => Some(ItemType::F16),
+            (b'f', 4) => Some(ItemType::F32),
+            (b'f', 8) => Some(ItemType::F64),
+            (b'i', 1) => Some(ItemType::I8),
+            (b'i', 2) => Some(ItemType::I16),
+            (b'i', 4) => Some(ItemType::I32),
+            (b'i', 8) => Some(ItemType::I64),
+            (b'u', 1) => Some(ItemType::U8),
+            (b'u', 2) => Some(ItemType::U16),
+            (b'u', 4) => Some(ItemType::U32),
+            (b'u', 8) => Some(ItemType::U64),
+            _ => return Err(PyArrayError::UnsupportedDataType),
+        };
+        Ok((kind, size))
+    }
+}
+
+/// The records of a structured array, each written as an object.
+struct NumpyRecordArray<'a> {
+    data: *const u8,
+    len: usize,
+    /// Between records, which need not be `itemsize`.
+    stride: isize,
+    fields: &'a [RecordField],
+    opts: Opt,
+    /// The position of the array in its parent, for errors.
+    position: &'a [isize],
+}
+
+impl Serialize for NumpyRecordArray<'_> {
+    #[cold]
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for idx in 0..self.len {
+            #[allow(clippy::cast_possible_wrap)]
+            let record = NumpyRecord {
+                data: unsafe { self.data.offset(idx as isize * self.stride) },
+                array: self,
+                index: idx as isize,
+            };
+            seq.serialize_element(&record)?;
+        }
+        seq.end()
+    }
+}
+
+struct NumpyRecord<'a> {
+    data: *const u8,
+    array: &'a NumpyRecordArray<'a>,
+    index: isize,
+}
+
+impl Serialize for NumpyRecord<'_> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None).unwrap();
+        for field in self.array.fields {
+            map.serialize_key(field.name.as_str()).unwrap();
+            map.serialize_value(&NumpyItem {
+                data: unsafe { self.data.add(field.offset) },
+                kind: field.kind,
+                opts: self.array.opts,
+                position: self.array.position,
+                index: self.index,
+            })?;
+        }
+        map.end()
+    }
+}
+
+/// One item of an array read at any alignment, as in a record or beside a
+/// mask.
+pub(crate) struct NumpyItem<'a> {
+    pub data: *const u8,
+    pub kind: ItemType,
+    pub opts: Opt,
+    /// The position of the array in its parent, for errors.
+    pub position: &'a [isize],
+    /// The index of the item in its array, for errors.
+    pub index: isize,
+}
+
+impl NumpyItem<'_> {
+    #[inline(always)]
+    fn read<T>(&self) -> T {
+        unsafe { core::ptr::read_unaligned(self.data.cast::<T>()) }
+    }
+}
+
+impl Serialize for NumpyItem<'_> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.kind {
+            ItemType::BOOL => serializer.serialize_bool(self.read::<u8>() != 0),
+            ItemType::C64 => self.read::<Complex<f32>>().serialize(serializer),
+            ItemType::C128 => self.read::<Complex<f64>>().serialize(serializer),
+            ItemType::DATETIME64(unit) => {
+                let dt = unit
+                    .datetime(self.read::<i64>(), self.opts)
+                    .map_err(|err| {
+                        let mut index = self.position.to_vec();
+                        index.push(self.index);
+                        err.into_serde_err(Some(&index))
+                    })?;
+                dt.serialize(serializer)
+            }
+            ItemType::TIMEDELTA64(unit) => {
+                let td = unit
+                    .timedelta(self.read::<i64>(), self.opts)
+                    .map_err(|err| {
+                        let mut index = self.position.to_vec();
+                        index.push(self.index);
+                        err.into_serde_err(Some(&index))
+                    })?;
+                td.serialize(serializer)
+            }
+            ItemType::F16 => {
+                serializer.serialize_f32(half::f16::from_bits(self.read::<u16>()).to_f32())
+            }
+            ItemType::F32 => serializer.serialize_f32(self.read::<f32>()),
+            ItemType::F64 => serializer.serialize_f64(self.read::<f64>()),
+            ItemType::I8 => serializer.serialize_i64(i64::from(self.read::<i8>())),
+            ItemType::I16 => serializer.serialize_i64(i64::from(self.read::<i16>())),
+            ItemType::I32 => serializer.serialize_i64(i64::from(self.read::<i32>())),
+            ItemType::I64 => serializer.serialize_i64(self.read::<i64>()),
+            ItemType::U8 => serializer.serialize_u64(u64::from(self.read::<u8>())),
+            ItemType::U16 => serializer.serialize_u64(u64::from(self.read::<u16>())),
+            ItemType::U32 => serializer.serialize_u64(u64::from(self.read::<u32>())),
+            ItemType::U64 => serializer.serialize_u64(self.read::<u64>()),
+            ItemType::RECORD => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `OPT_OMIT_NULL` and `OPT_OMIT_EMPTY`, which leave entries out of the dict
+//! or dataclass they are in by their value.
+
+use crate::ffi::{PyASCIIObject, PyObject};
+use crate::opt::{OMIT_EMPTY, OMIT_EMPTY_DICT, OMIT_EMPTY_LIST, OMIT_EMPTY_STR, OMIT_NULL, Opt};
+
+/// Return whether `ptr` is left out of the dict or dataclass it is a value
+/// of.
+#[inline(always)]
+pub(crate) fn omit_value(ptr: *mut PyObject, opts: Opt) -> bool {
+    opts & (OMIT_NULL | OMIT_EMPTY) != 0 && is_omitted(ptr, opts)
+}
+
+/// Return whether `ptr` is an empty `str`, `dict`, `list`, or `tuple` left
+/// out by `opts`. Subclasses are not, as what they serialize to may not be
+/// empty.
+#[inline(always)]
+pub(crate) fn omit_empty(ptr: *mut PyObject, opts: Opt) -> bool {
+    let ob_type = ob_type!(ptr);
+    if is_type!(ob_type, crate::typeref::str_type_ptr()) {
+        opt_enabled!(opts, OMIT_EMPTY_STR) && unsafe { (*ptr.cast::<PyASCIIObject>()).length == 0 }
+    } else if is_type!(ob_type, crate::typeref::dict_type_ptr()) {
+        opt_enabled!(opts, OMIT_EMPTY_DICT) && ffi!(PyDict_Size(ptr)) == 0
+    } else if is_type!(ob_type, crate::typeref::list_type_ptr())
+        || is_type!(ob_type, crate::typeref::tuple_type_ptr())
+    {
+        opt_enabled!(opts, OMIT_EMPTY_LIST) && ffi!(Py_SIZE(ptr)) == 0
+    } else {
+        false
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn is_omitted(ptr: *mut PyObject, opts: Opt) -> bool {
+    if core::ptr::eq(ptr, crate::typeref::get_none()) {
+        opt_enabled!(opts, OMIT_NULL)
+    } else {
+        omit_empty(ptr, opts)
+    }
+}
@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::{PyObject, PyTypeObject};
+use crate::interpreter_state::InterpreterState;
+use crate::opt::{PANDAS_ORIENT, PANDAS_ORIENT_COLUMNS};
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::{
+    ItemType, NPY_ARRAY_NOTSWAPPED, NumpyItem, PyArrayInterface, PyCapsule,
+};
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::str::PyStr;
+use crate::typeref::{
+    get_array_struct_str, get_items_str, get_pandas_types_from_state, get_to_numpy_str,
+};
+use crate::util::isize_to_usize;
+
+use core::ffi::c_char;
+use core::ptr::NonNull;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+/// Whether `ob_type` is `pandas.DataFrame` or `pandas.Series`. pandas is
+/// imported the first time this is called with `pandas_orient=` given.
+#[cold]
+pub(crate) fn is_pandas(
+    ob_type: *mut PyTypeObject,
+    interpreter_state: *const InterpreterState,
+) -> bool {
+    match get_pandas_types_from_state(interpreter_state) {
+        None => false,
+        Some(types) => {
+            core::ptr::eq(ob_type, types.dataframe) || core::ptr::eq(ob_type, types.series)
+        }
+    }
+}
+
+/// Serialize a `pandas.Series` as an array of its values and a
+/// `pandas.DataFrame` as `pandas_orient=` selects: an array of an object per
+/// row, or an object of an array per column. Each column is read from the
+/// buffer of its `to_numpy()` array rather than as Python objects, except
+/// for a column of dtype `object`.
+pub(crate) struct PandasSerializer {
+    ptr: *mut PyObject,
+    state: SerializerState,
+    default: Option<NonNull<PyObject>>,
+}
+
+impl PandasSerializer {
+    pub fn new(previous: &PyObjectSerializer) -> Self {
+        Self {
+            ptr: previous.ptr,
+            state: previous.state.copy_for_recursive_call(),
+            default: previous.default,
+        }
+    }
+}
+
+impl Serialize for PandasSerializer {
+    #[cold]
+    #[inline(never)]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.state.recursion_limit() {
+            err!(SerializeError::RecursionLimit)
+        }
+        let types = get_pandas_types_from_state(self.state.interpreter_state()).unwrap();
+        if core::ptr::eq(ob_type!(self.ptr), types.series) {
+            let column = match PandasColumn::new(self.ptr) {
+                Ok(val) => val,
+                Err(err) => err!(err),
+            };
+            return ColumnSerializer {
+                column: &column,
+                state: self.state,
+                default: self.default,
+            }
+            .serialize(serializer);
+        }
+        let frame = match PandasFrame::new(self.ptr) {
+            Ok(val) => val,
+            Err(err) => err!(err),
+        };
+        if self.state.opts() & PANDAS_ORIENT == PANDAS_ORIENT_COLUMNS {
+            let mut map = serializer.serialize_map(None).unwrap();
+            for (name, column) in frame.columns.iter() {
+                map.serialize_key(name.as_str()).unwrap();
+                map.serialize_value(&ColumnSerializer {
+                    column: column,
+                    state: self.state,
+                    default: self.default,
+                })?;
+            }
+            map.end()
+        } else {
+            let mut seq = serializer.serialize_seq(None).unwrap();
+            for row in 0..frame.rows {
+                seq.serialize_element(&RowSerializer {
+                    frame: &frame,
+                    row: row,
+                    state: self.state,
+                    default: self.default,
+                })?;
+            }
+            seq.end()
+        }
+    }
+}
+
+/// The columns of a `pandas.DataFrame` by name.
+struct PandasFrame {
+    columns: Vec<(String, PandasColumn)>,
+    rows: usize,
+}
+
+impl PandasFrame {
+    #[cold]
+    fn new(ptr: *mut PyObject) -> Result<Self, SerializeError> {
+        let rows = ffi!(PyObject_Size(ptr));
+        if rows < 0 {
+            ffi!(PyErr_Clear());
+            return Err(SerializeError::PandasMalformed);
+        }
+        let items = call_method!(ptr, get_items_str());
+        if items.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(SerializeError::PandasMalformed);
+        }
+        let iter = ffi!(PyObject_GetIter(items));
+        ffi!(Py_DECREF(items));
+        if iter.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(SerializeError::PandasMalformed);
+        }
+        let mut frame = PandasFrame {
+            columns: Vec::new(),
+            rows: isize_to_usize(rows),
+        };
+        let ret = frame.read_columns(iter);
+        ffi!(Py_DECREF(iter));
+        ret.map(|()| frame)
+    }
+
+    fn read_columns(&mut self, iter: *mut PyObject) -> Result<(), SerializeError> {
+        loop {
+            let item = ffi!(PyIter_Next(iter));
+            if item.is_null() {
+                if !ffi!(PyErr_Occurred()).is_null() {
+                    ffi!(PyErr_Clear());
+                    return Err(SerializeError::PandasMalformed);
+                }
+                return Ok(());
+            }
+            let ret = self.read_column(item);
+            ffi!(Py_DECREF(item));
+            ret?;
+        }
+    }
+
+    fn read_column(&mut self, item: *mut PyObject) -> Result<(), SerializeError> {
+        if !is_type!(ob_type!(item), crate::typeref::tuple_type_ptr()) || ffi!(Py_SIZE(item)) != 2 {
+            return Err(SerializeError::PandasMalformed);
+        }
+        let name = unsafe { crate::ffi::PyTuple_GET_ITEM(item, 0) };
+        let series = unsafe { crate::ffi::PyTuple_GET_ITEM(item, 1) };
+        if !is_type!(ob_type!(name), crate::typeref::str_type_ptr()) {
+            return Err(SerializeError::KeyMustBeStr);
+        }
+        let name = match unsafe { PyStr::from_ptr_unchecked(name).to_str() } {
+            Some(uni) => String::from(uni),
+            None => return Err(SerializeError::InvalidStr),
+        };
+        let column = PandasColumn::new(series)?;
+        if column.len != self.rows {
+            return Err(SerializeError::PandasMalformed);
+        }
+        self.columns.push((name, column));
+        Ok(())
+    }
+}
+
+enum ColumnKind {
+    Item(ItemType),
+    /// Items are `PyObject *`, as of dtype `object`.
+    Object,
+}
+
+/// The one-dimensional `to_numpy()` array of a `pandas.Series`.
+struct PandasColumn {
+    array: *mut PyObject,
+    capsule: *mut PyCapsule,
+    data: *const u8,
+    stride: isize,
+    len: usize,
+    kind: ColumnKind,
+}
+
+impl PandasColumn {
+    #[cold]
+    fn new(series: *mut PyObject) -> Result<Self, SerializeError> {
+        let array = call_method!(series, get_to_numpy_str());
+        if array.is_null() {
+            ffi!(PyErr_Clear());
+            return Err(SerializeError::PandasMalformed);
+        }
+        let capsule = ffi!(PyObject_GetAttr(array, get_array_struct_str()));
+        if capsule.is_null() {
+            ffi!(PyErr_Clear());
+            ffi!(Py_DECREF(array));
+            return Err(SerializeError::PandasMalformed);
+        }
+        let mut column = PandasColumn {
+            array: array,
+            capsule: capsule.cast::<PyCapsule>(),
+            data: core::ptr::null(),
+            stride: 0,
+            len: 0,
+            kind: ColumnKind::Object,
+        };
+        let interface = unsafe { (*column.capsule).pointer.cast::<PyArrayInterface>() };
+        unsafe {
+            if (*interface).two != 2 || (*interface).nd != 1 {
+                return Err(SerializeError::NumpyMalformed);
+            }
+            if (*interface).flags & NPY_ARRAY_NOTSWAPPED != NPY_ARRAY_NOTSWAPPED {
+                return Err(SerializeError::NumpyNotNativeEndian);
+            }
+            column.data = (*interface).data.cast::<u8>().cast_const();
+            column.stride = if (*interface).strides.is_null() {
+                (*interface).itemsize as isize
+            } else {
+                *(*interface).strides
+            };
+            column.len = isize_to_usize(*(*interface).shape);
+            if (*interface).typekind != b'O' as c_char {
+                match ItemType::find(interface, array) {
+                    Some(kind) => column.kind = ColumnKind::Item(kind),
+                    None => return Err(SerializeError::NumpyUnsupportedDatatype),
+                }
+            }
+        }
+        Ok(column)
+    }
+}
+
+impl Drop for PandasColumn {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.capsule.cast::<PyObject>()));
+        ffi!(Py_DECREF(self.array));
+    }
+}
+
+/// One item of a column.
+struct CellSerializer<'a> {
+    column: &'a PandasColumn,
+    row: usize,
+    state: SerializerState,
+    default: Option<NonNull<PyObject>>,
+}
+
+impl Serialize for CellSerializer<'_> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[allow(clippy::cast_possible_wrap)]
+        let data = unsafe {
+            self.column
+                .data
+                .offset(self.row as isize * self.column.stride)
+        };
+        match self.column.kind {
+            ColumnKind::Item(kind) => NumpyItem {
+                data: data,
+                kind: kind,
+                opts: self.state.opts(),
+                position: &[],
+                #[allow(clippy::cast_possible_wrap)]
+                index: self.row as isize,
+            }
+            .serialize(serializer),
+            ColumnKind::Object => {
+                let value = unsafe { core::ptr::read_unaligned(data.cast::<*mut PyObject>()) };
+                PyObjectSerializer::new(value, self.state, self.default).serialize(serializer)
+            }
+        }
+    }
+}
+
+struct ColumnSerializer<'a> {
+    column: &'a PandasColumn,
+    state: SerializerState,
+    default: Option<NonNull<PyObject>>,
+}
+
+impl Serialize for ColumnSerializer<'_> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None).unwrap();
+        for row in 0..self.column.len {
+            seq.serialize_element(&CellSerializer {
+                column: self.column,
+                row: row,
+                state: self.state,
+                default: self.default,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct RowSerializer<'a> {
+    frame: &'a PandasFrame,
+    row: usize,
+    state: SerializerState,
+    default: Option<NonNull<PyObject>>,
+}
+
+impl Serialize for RowSerializer<'_> {
+    #[cold]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None).unwrap();
+        for (name, column) in self.frame.columns.iter() {
+            map.serialize_key(name.as_str()).unwrap();
+            map.serialize_value(&CellSerializer {
+                column: column,
+                row: self.row,
+                state: self.state,
+                default: self.default,
+            })?;
+        }
+        map.end()
+    }
+}
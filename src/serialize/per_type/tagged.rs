@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::PyObject;
+use crate::interpreter_state::InterpreterState;
+use crate::serialize::error::SerializeError;
+use crate::serialize::per_type::DictGenericSerializer;
+use crate::serialize::registry::lookup_tag;
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::str::PyStr;
+
+use serde::ser::{Serialize, Serializer};
+
+/// Serialize an instance of a type registered with `register_tagged()` as an
+/// object of `"__type__"` and its fields, for `dumps(tagged=True)`.
+#[repr(transparent)]
+pub(crate) struct TaggedSerializer<'a> {
+    previous: &'a PyObjectSerializer,
+}
+
+impl<'a> TaggedSerializer<'a> {
+    pub fn new(previous: &'a PyObjectSerializer) -> Self {
+        Self { previous: previous }
+    }
+}
+
+/// Whether the member `key` is written: fields that start with an underscore
+/// are private, as for dataclasses.
+fn is_public(key: *mut PyObject) -> bool {
+    if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+        return false;
+    }
+    match unsafe { PyStr::from_ptr_unchecked(key).to_str() } {
+        Some(uni) => !uni.starts_with('_'),
+        None => false,
+    }
+}
+
+/// Copy the fields of `ptr` into `dict`: the declared fields of a dataclass
+/// or the public attributes in `__dict__` of any other object.
+fn copy_fields(
+    ptr: *mut PyObject,
+    dict: *mut PyObject,
+    interpreter_state: *const InterpreterState,
+) -> Result<(), SerializeError> {
+    let ob_type = ob_type!(ptr);
+    let dataclass_fields = crate::typeref::get_dataclass_fields_str_from_state(interpreter_state);
+    let is_dataclass = pydict_contains!(ob_type, dataclass_fields);
+    let source = if is_dataclass {
+        ffi!(PyObject_GetAttr(ptr, dataclass_fields))
+    } else {
+        ffi!(PyObject_GetAttr(
+            ptr,
+            crate::typeref::get_dict_str_from_state(interpreter_state)
+        ))
+    };
+    if source.is_null() || !is_type!(ob_type!(source), crate::typeref::dict_type_ptr()) {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        ffi!(Py_XDECREF(source));
+        return Err(SerializeError::UnsupportedType(nonnull!(ptr)));
+    }
+    let mut pos = 0;
+    let mut key: *mut PyObject = core::ptr::null_mut();
+    let mut value: *mut PyObject = core::ptr::null_mut();
+    let mut ret = Ok(());
+    while ffi!(PyDict_Next(source, &mut pos, &mut key, &mut value)) == 1 {
+        if !is_public(key) {
+            continue;
+        }
+        if is_dataclass {
+            let field_type = ffi!(PyObject_GetAttr(
+                value,
+                crate::typeref::get_field_type_str()
+            ));
+            ffi!(Py_XDECREF(field_type));
+            if !core::ptr::eq(
+                field_type.cast::<crate::ffi::PyTypeObject>(),
+                crate::typeref::get_field_type(),
+            ) {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            let attr = ffi!(PyObject_GetAttr(ptr, key));
+            if attr.is_null() {
+                cold_path!();
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            let _ = ffi!(PyDict_SetItem(dict, key, attr));
+            ffi!(Py_DECREF(attr));
+        } else if ffi!(PyDict_SetItem(dict, key, value)) == -1 {
+            cold_path!();
+            ffi!(PyErr_Clear());
+            ret = Err(SerializeError::UnsupportedType(nonnull!(ptr)));
+            break;
+        }
+    }
+    ffi!(Py_DECREF(source));
+    ret
+}
+
+impl Serialize for TaggedSerializer<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.previous.state.recursion_limit() {
+            cold_path!();
+            err!(SerializeError::RecursionLimit)
+        }
+        let ptr = self.previous.ptr;
+        let interpreter_state = self.previous.state.interpreter_state();
+        let tag = match lookup_tag(interpreter_state, ob_type!(ptr)) {
+            Some(tag) => tag.as_ptr(),
+            None => err!(SerializeError::UnsupportedType(nonnull!(ptr))),
+        };
+        let dict = ffi!(PyDict_New());
+        let _ = ffi!(PyDict_SetItem(
+            dict,
+            crate::typeref::get_type_key_str(),
+            tag
+        ));
+        if let Err(err) = copy_fields(ptr, dict, interpreter_state) {
+            ffi!(Py_DECREF(dict));
+            err!(err)
+        }
+        let ret = DictGenericSerializer::new(dict, self.previous.state, self.previous.default)
+            .serialize(serializer);
+        ffi!(Py_DECREF(dict));
+        ret
+    }
+}
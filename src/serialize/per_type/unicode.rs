@@ -2,6 +2,8 @@
 // Copyright ijl (2018-2025)
 
 use crate::serialize::error::SerializeError;
+use crate::serialize::escape_cache::{ESCAPE_CACHE_MIN_LEN, escaped};
+use crate::serialize::number_format::is_json_number;
 use crate::str::{PyStr, PyStrSubclass};
 
 use serde::ser::{Serialize, Serializer};
@@ -24,7 +26,14 @@ impl Serialize for StrSerializer {
         S: Serializer,
     {
         match unsafe { PyStr::from_ptr_unchecked(self.ptr).to_str() } {
-            Some(uni) => serializer.serialize_str(uni),
+            Some(uni) => {
+                if uni.len() >= ESCAPE_CACHE_MIN_LEN {
+                    if let Some(buf) = escaped(self.ptr, uni) {
+                        return serializer.serialize_bytes(buf);
+                    }
+                }
+                serializer.serialize_str(uni)
+            }
             None => err!(SerializeError::InvalidStr),
         }
     }
@@ -53,3 +62,56 @@ impl Serialize for StrSubclassSerializer {
         }
     }
 }
+
+/// Writes a `hyperjson.RawNumber` as it is, unquoted.
+#[repr(transparent)]
+pub(crate) struct RawNumberSerializer {
+    ptr: *mut crate::ffi::PyObject,
+}
+
+impl RawNumberSerializer {
+    pub fn new(ptr: *mut crate::ffi::PyObject) -> Self {
+        RawNumberSerializer { ptr: ptr }
+    }
+}
+
+impl Serialize for RawNumberSerializer {
+    #[cold]
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match unsafe { PyStrSubclass::from_ptr_unchecked(self.ptr).to_str() } {
+            Some(uni) if is_json_number(uni.as_bytes()) => {
+                if serializer.is_human_readable() {
+                    serializer.serialize_bytes(uni.as_bytes())
+                } else {
+                    serialize_number_value(uni, serializer)
+                }
+            }
+            Some(_) => err!(SerializeError::InvalidRawNumber),
+            None => err!(SerializeError::InvalidStr),
+        }
+    }
+}
+
+/// Write the value of the JSON number `number` for an output that is not
+/// JSON.
+fn serialize_number_value<S>(number: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if number.contains(['.', 'e', 'E']) {
+        match number.parse::<f64>() {
+            Ok(value) => serializer.serialize_f64(value),
+            Err(_) => err!(SerializeError::InvalidRawNumber),
+        }
+    } else if let Ok(value) = number.parse::<i64>() {
+        serializer.serialize_i64(value)
+    } else if let Ok(value) = number.parse::<u64>() {
+        serializer.serialize_u64(value)
+    } else {
+        err!(SerializeError::Integer64Bits)
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::ffi::{PyObject, PyTypeObject};
+use crate::serialize::per_type::{DataclassPlan, is_dataclass};
+use core::ptr::NonNull;
+
+/// Register `callable` to serialize instances of `ob_type` and its
+/// subclasses, or remove the registration if `callable` is `None`.
+pub(crate) fn register_serializer(
+    ob_type: *mut PyObject,
+    callable: *mut PyObject,
+) -> Result<(), &'static str> {
+    if ffi!(PyType_Check(ob_type)) == 0 {
+        cold_path!();
+        return Err("register_serializer() first argument must be a type");
+    }
+    let serializers = unsafe { (*crate::runtime::current_state()).serializers };
+    if core::ptr::eq(callable, crate::typeref::get_none()) {
+        if ffi!(PyDict_Contains(serializers, ob_type)) == 1 {
+            let _ = ffi!(PyDict_DelItem(serializers, ob_type));
+        }
+        return Ok(());
+    }
+    if ffi!(PyCallable_Check(callable)) == 0 {
+        cold_path!();
+        return Err("register_serializer() second argument must be callable or None");
+    }
+    if ffi!(PyDict_SetItem(serializers, ob_type, callable)) == -1 {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        return Err("register_serializer() could not register type");
+    }
+    Ok(())
+}
+
+/// Register `callable` to build instances of `ob_type` from the value
+/// `loads()` reads for a field annotated as `ob_type` in `type=`, or remove
+/// the registration if `callable` is `None`.
+pub(crate) fn register_deserializer(
+    ob_type: *mut PyObject,
+    callable: *mut PyObject,
+) -> Result<(), &'static str> {
+    if ffi!(PyType_Check(ob_type)) == 0 {
+        cold_path!();
+        return Err("register_deserializer() first argument must be a type");
+    }
+    let deserializers = unsafe { (*crate::runtime::current_state()).deserializers };
+    if core::ptr::eq(callable, crate::typeref::get_none()) {
+        if ffi!(PyDict_Contains(deserializers, ob_type)) == 1 {
+            let _ = ffi!(PyDict_DelItem(deserializers, ob_type));
+        }
+        return Ok(());
+    }
+    if ffi!(PyCallable_Check(callable)) == 0 {
+        cold_path!();
+        return Err("register_deserializer() second argument must be callable or None");
+    }
+    if ffi!(PyDict_SetItem(deserializers, ob_type, callable)) == -1 {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        return Err("register_deserializer() could not register type");
+    }
+    Ok(())
+}
+
+/// Register `ob_type` to be written with the member `"__type__": tag` by
+/// `dumps(tagged=True)` and built from an object with it by
+/// `loads(tagged=True)`. The tag is `__name__` of the type if `tag` is `None`.
+pub(crate) fn register_tagged(
+    ob_type: *mut PyObject,
+    tag: *mut PyObject,
+) -> Result<(), &'static str> {
+    if ffi!(PyType_Check(ob_type)) == 0 {
+        cold_path!();
+        return Err("register_tagged() first argument must be a type");
+    }
+    let tag = if core::ptr::eq(tag, crate::typeref::get_none()) {
+        ffi!(PyObject_GetAttrString(ob_type, c"__name__".as_ptr()))
+    } else {
+        ffi!(Py_INCREF(tag));
+        tag
+    };
+    if tag.is_null() || !is_type!(ob_type!(tag), crate::typeref::str_type_ptr()) {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        ffi!(Py_XDECREF(tag));
+        return Err("register_tagged() tag must be a str or None");
+    }
+    let ret = unsafe {
+        let state = crate::runtime::current_state();
+        let registered = ffi!(PyDict_GetItem((*state).types_by_tag, tag));
+        if !registered.is_null() && !core::ptr::eq(registered, ob_type) {
+            Err("register_tagged() tag is registered to another type")
+        } else {
+            // a type has one tag
+            let previous = ffi!(PyDict_GetItem((*state).tags_by_type, ob_type));
+            if !previous.is_null() {
+                let _ = ffi!(PyDict_DelItem((*state).types_by_tag, previous));
+            }
+            if ffi!(PyDict_SetItem((*state).tags_by_type, ob_type, tag)) == -1
+                || ffi!(PyDict_SetItem((*state).types_by_tag, tag, ob_type)) == -1
+            {
+                ffi!(PyErr_Clear());
+                Err("register_tagged() could not register type")
+            } else {
+                Ok(())
+            }
+        }
+    };
+    ffi!(Py_DECREF(tag));
+    ret
+}
+
+/// Build the `DataclassPlan` of the dataclass `ob_type` that `dumps()` uses
+/// for its instances, replacing any plan built before.
+pub(crate) fn register_type(ob_type: *mut PyObject) -> Result<(), &'static str> {
+    if ffi!(PyType_Check(ob_type)) == 0 || !is_dataclass(ob_type) {
+        cold_path!();
+        return Err("register_type() argument must be a dataclass");
+    }
+    let capsule = match DataclassPlan::new(ob_type) {
+        Some(plan) => plan.into_capsule(),
+        None => core::ptr::null_mut(),
+    };
+    if capsule.is_null() {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        return Err("register_type() could not register type");
+    }
+    let plans = unsafe { (*crate::runtime::current_state()).type_plans };
+    let ret = ffi!(PyDict_SetItem(plans, ob_type, capsule));
+    ffi!(Py_DECREF(capsule));
+    if ret == -1 {
+        cold_path!();
+        ffi!(PyErr_Clear());
+        return Err("register_type() could not register type");
+    }
+    Ok(())
+}
+
+/// The `DataclassPlan` capsule registered for exactly `ob_type`, borrowed,
+/// as a subclass may declare other fields.
+#[inline(always)]
+pub(crate) fn lookup_type_plan(
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    ob_type: *mut PyTypeObject,
+) -> Option<NonNull<PyObject>> {
+    let plans = unsafe { (*interpreter_state).type_plans };
+    if ffi!(Py_SIZE(plans)) == 0 {
+        return None;
+    }
+    NonNull::new(ffi!(PyDict_GetItem(plans, ob_type.cast::<PyObject>())))
+}
+
+/// The tag registered for exactly `ob_type`, borrowed, as a subclass is
+/// built as the type it was registered as.
+#[inline(never)]
+pub(crate) fn lookup_tag(
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    ob_type: *mut PyTypeObject,
+) -> Option<NonNull<PyObject>> {
+    let tags = unsafe { (*interpreter_state).tags_by_type };
+    if ffi!(Py_SIZE(tags)) == 0 {
+        return None;
+    }
+    NonNull::new(ffi!(PyDict_GetItem(tags, ob_type.cast::<PyObject>())))
+}
+
+/// Return a new reference to the callable registered for `ob_type` or the
+/// nearest of its bases.
+#[cold]
+#[inline(never)]
+pub(crate) fn lookup_serializer(
+    interpreter_state: *const crate::interpreter_state::InterpreterState,
+    ob_type: *mut PyTypeObject,
+) -> Option<NonNull<PyObject>> {
+    let serializers = unsafe { (*interpreter_state).serializers };
+    if ffi!(Py_SIZE(serializers)) == 0 {
+        return None;
+    }
+    lookup_type_mapping(serializers, ob_type)
+}
+
+/// Return a new reference to the value `mapping`, a `dict` keyed by type,
+/// holds for `ob_type` or the nearest of its bases.
+#[cold]
+#[inline(never)]
+pub(crate) fn lookup_type_mapping(
+    mapping: *mut PyObject,
+    ob_type: *mut PyTypeObject,
+) -> Option<NonNull<PyObject>> {
+    if let Some(value) = get_item(mapping, ob_type.cast::<PyObject>()) {
+        return Some(value);
+    }
+    let mro = unsafe { (*ob_type).tp_mro };
+    if mro.is_null() {
+        cold_path!();
+        return None;
+    }
+    // the first entry of the MRO is the type itself
+    for idx in 1..ffi!(Py_SIZE(mro)) {
+        let base = unsafe { crate::ffi::PyTuple_GET_ITEM(mro, idx) };
+        if let Some(value) = get_item(mapping, base) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(Py_3_13)]
+fn get_item(dict: *mut PyObject, key: *mut PyObject) -> Option<NonNull<PyObject>> {
+    let mut value: *mut PyObject = core::ptr::null_mut();
+    if ffi!(PyDict_GetItemRef(dict, key, &raw mut value)) == 1 {
+        NonNull::new(value)
+    } else {
+        ffi!(PyErr_Clear());
+        None
+    }
+}
+
+#[cfg(not(Py_3_13))]
+fn get_item(dict: *mut PyObject, key: *mut PyObject) -> Option<NonNull<PyObject>> {
+    let value = ffi!(PyDict_GetItem(dict, key));
+    if value.is_null() {
+        None
+    } else {
+        ffi!(Py_INCREF(value));
+        NonNull::new(value)
+    }
+}
@@ -0,0 +1,436 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! `hyperjson.Schema`, which compiles a type annotation once for reading
+//! and writing documents of it.
+//!
+//! `Schema.loads()` reads as `loads(type=...)` does without introspecting
+//! the annotation on each call. `Schema.dumps()` writes the fields of the
+//! compiled classes by their keys in the document, so that a pydantic model
+//! or a field alias is written as it is read.
+
+use crate::deserialize::{DeserializeOptions, Kind, Shape, TypePlan, deserialize_typed};
+use crate::exception::{
+    raise_dumps_exception_dynamic, raise_dumps_exception_fixed, raise_loads_exception,
+};
+use crate::ffi::{
+    METH_O, Py_TPFLAGS_DEFAULT, PyMethodDef, PyMethodDefPointer, PyObject, PyType_Ready,
+    PyType_Type, PyTypeObject, PyVarObject,
+};
+use crate::runtime::Serializing;
+use crate::serialize::error::SerializeError;
+use crate::serialize::serializer::PyObjectSerializer;
+use crate::serialize::state::SerializerState;
+use crate::serialize::writer::{BytesWriter, to_writer};
+use crate::str::PyStr;
+use crate::util::isize_to_usize;
+use core::ptr::{NonNull, null_mut};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+#[cfg(Py_GIL_DISABLED)]
+use crate::ffi::atomiculong::AtomicCULong;
+#[cfg(Py_GIL_DISABLED)]
+use core::sync::atomic::{AtomicIsize, AtomicU32};
+
+#[cfg(Py_GIL_DISABLED)]
+macro_rules! pymutex_new {
+    () => {
+        unsafe { core::mem::zeroed() }
+    };
+}
+
+#[repr(C)]
+pub(crate) struct Schema {
+    ob_base: PyObject,
+    plan: TypePlan,
+}
+
+fn is_instance(ptr: *mut PyObject, cls: *mut PyObject) -> bool {
+    let ret = ffi!(PyObject_IsInstance(ptr, cls));
+    if ret < 0 {
+        ffi!(PyErr_Clear());
+    }
+    ret == 1
+}
+
+/// Whether `ptr` is a value of `kind`, to choose the member of a union.
+fn matches(plan: &TypePlan, kind: &Kind, ptr: *mut PyObject) -> bool {
+    let ob_type = ob_type!(ptr);
+    let state = unsafe { crate::runtime::current_state() };
+    match kind {
+        Kind::Any => true,
+        Kind::Str => is_type!(ob_type, crate::typeref::str_type_ptr()),
+        Kind::Int => is_type!(ob_type, crate::typeref::int_type_ptr()),
+        Kind::Float => {
+            is_type!(ob_type, crate::typeref::float_type_ptr())
+                || is_type!(ob_type, crate::typeref::int_type_ptr())
+        }
+        Kind::Bool => is_type!(ob_type, crate::typeref::bool_type_ptr()),
+        Kind::None => core::ptr::eq(ptr, crate::typeref::none_ptr()),
+        Kind::Datetime => is_type!(ob_type, crate::typeref::get_datetime_type_from_state(state)),
+        Kind::Date => is_type!(ob_type, crate::typeref::get_date_type_from_state(state)),
+        Kind::Uuid => is_type!(ob_type, crate::typeref::get_uuid_type_from_state(state)),
+        Kind::Decimal => core::ptr::eq(ob_type.cast::<PyObject>(), plan.builders.decimal),
+        Kind::List(_) | Kind::Tuple(_) => {
+            is_type!(ob_type, crate::typeref::list_type_ptr())
+                || is_type!(ob_type, crate::typeref::tuple_type_ptr())
+        }
+        Kind::FixedTuple(items) => {
+            (is_type!(ob_type, crate::typeref::list_type_ptr())
+                || is_type!(ob_type, crate::typeref::tuple_type_ptr()))
+                && isize_to_usize(ffi!(Py_SIZE(ptr))) == items.len()
+        }
+        Kind::Dict(_) => is_type!(ob_type, crate::typeref::dict_type_ptr()),
+        Kind::Union(members) => members.iter().any(|member| matches(plan, member, ptr)),
+        Kind::Class(idx) => {
+            let class = &plan.classes[*idx];
+            if class.shape == Shape::TypedDict {
+                is_type!(ob_type, crate::typeref::dict_type_ptr())
+            } else {
+                is_instance(ptr, class.cls)
+            }
+        }
+        Kind::Adapter(idx) => is_instance(ptr, plan.adapters[*idx].cls),
+    }
+}
+
+/// The item `idx` of `ptr`, a `list` or `tuple`.
+#[inline(always)]
+fn sequence_item(ptr: *mut PyObject, idx: usize) -> *mut PyObject {
+    #[allow(clippy::cast_possible_wrap)]
+    let idx = idx as isize;
+    unsafe {
+        if is_type!(ob_type!(ptr), crate::typeref::list_type_ptr()) {
+            crate::ffi::PyList_GET_ITEM(ptr, idx)
+        } else {
+            crate::ffi::PyTuple_GET_ITEM(ptr, idx)
+        }
+    }
+}
+
+/// Write `ptr` as `kind`. A value that is not of `kind` is written as
+/// `dumps()` writes it.
+struct PlanSerializer<'a> {
+    ptr: *mut PyObject,
+    kind: &'a Kind,
+    plan: &'a TypePlan,
+    state: SerializerState,
+}
+
+impl<'a> PlanSerializer<'a> {
+    fn nested(&self, ptr: *mut PyObject, kind: &'a Kind) -> Self {
+        PlanSerializer {
+            ptr: ptr,
+            kind: kind,
+            plan: self.plan,
+            state: self.state.copy_for_recursive_call(),
+        }
+    }
+}
+
+impl Serialize for PlanSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.state.recursion_limit() {
+            err!(SerializeError::RecursionLimit)
+        }
+        let kind = match self.kind {
+            Kind::Union(members) => members
+                .iter()
+                .find(|member| matches(self.plan, member, self.ptr))
+                .unwrap_or(&Kind::Any),
+            kind => kind,
+        };
+        if !matches(self.plan, kind, self.ptr) {
+            return PyObjectSerializer::new(self.ptr, self.state, None).serialize(serializer);
+        }
+        match kind {
+            Kind::List(item) | Kind::Tuple(item) => {
+                let len = isize_to_usize(ffi!(Py_SIZE(self.ptr)));
+                let mut seq = serializer.serialize_seq(Some(len)).unwrap();
+                for idx in 0..len {
+                    seq.serialize_element(&self.nested(sequence_item(self.ptr, idx), item))?;
+                }
+                seq.end()
+            }
+            Kind::FixedTuple(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len())).unwrap();
+                for (idx, item) in items.iter().enumerate() {
+                    seq.serialize_element(&self.nested(sequence_item(self.ptr, idx), item))?;
+                }
+                seq.end()
+            }
+            Kind::Dict(value) => {
+                let mut map = serializer.serialize_map(None).unwrap();
+                let mut pos = 0;
+                let mut key: *mut PyObject = null_mut();
+                let mut val: *mut PyObject = null_mut();
+                while pydict_next!(self.ptr, &mut pos, &mut key, &mut val) != 0 {
+                    if !is_type!(ob_type!(key), crate::typeref::str_type_ptr()) {
+                        err!(SerializeError::KeyMustBeStr)
+                    }
+                    let Some(key_as_str) = (unsafe { PyStr::from_ptr_unchecked(key).to_str() })
+                    else {
+                        err!(SerializeError::InvalidStr)
+                    };
+                    map.serialize_key(key_as_str).unwrap();
+                    map.serialize_value(&self.nested(val, value))?;
+                }
+                map.end()
+            }
+            Kind::Class(idx) => self.serialize_class(*idx, serializer),
+            Kind::Decimal => {
+                // as a string, which `Schema.loads()` reads exactly
+                let text = ffi!(PyObject_Str(self.ptr));
+                if text.is_null() {
+                    ffi!(PyErr_Clear());
+                    err!(SerializeError::UnsupportedType(nonnull!(self.ptr)))
+                }
+                let ret = match unsafe { PyStr::from_ptr_unchecked(text).to_str() } {
+                    Some(text) => serializer.serialize_str(text),
+                    None => Err(serde::ser::Error::custom(SerializeError::InvalidStr)),
+                };
+                ffi!(Py_DECREF(text));
+                ret
+            }
+            _ => PyObjectSerializer::new(self.ptr, self.state, None).serialize(serializer),
+        }
+    }
+}
+
+impl PlanSerializer<'_> {
+    fn serialize_class<S>(&self, idx: usize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let class = &self.plan.classes[idx];
+        if class.shape == Shape::NamedTuple {
+            let mut seq = serializer.serialize_seq(Some(class.fields.len())).unwrap();
+            for (idx, field) in class.fields.iter().enumerate() {
+                seq.serialize_element(&self.nested(sequence_item(self.ptr, idx), &field.kind))?;
+            }
+            return seq.end();
+        }
+        let mut map = serializer.serialize_map(None).unwrap();
+        for field in class.fields.iter() {
+            let value = if class.shape == Shape::TypedDict {
+                // borrowed, and absent for a key that is not required
+                let value = ffi!(PyDict_GetItem(self.ptr, field.name));
+                if value.is_null() {
+                    continue;
+                }
+                ffi!(Py_INCREF(value));
+                value
+            } else {
+                let value = ffi!(PyObject_GetAttr(self.ptr, field.name));
+                if value.is_null() {
+                    ffi!(PyErr_Clear());
+                    continue;
+                }
+                value
+            };
+            map.serialize_key(field.key.as_str()).unwrap();
+            let ret = map.serialize_value(&self.nested(value, &field.kind));
+            ffi!(Py_DECREF(value));
+            ret?;
+        }
+        map.end()
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn schema_tp_new(
+    subtype: *mut PyTypeObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        if ffi!(Py_SIZE(args)) != 1 || (!kwds.is_null() && ffi!(Py_SIZE(kwds)) != 0) {
+            return raise_dumps_exception_fixed("Schema() takes exactly 1 positional argument");
+        }
+        let plan = match TypePlan::new(crate::ffi::PyTuple_GET_ITEM(args, 0)) {
+            Ok(plan) => plan,
+            Err(msg) => return raise_dumps_exception_dynamic(&msg),
+        };
+        let obj = crate::ffi::PyType_GenericAlloc(subtype, 0);
+        if obj.is_null() {
+            return null_mut();
+        }
+        core::ptr::write(&raw mut (*obj.cast::<Schema>()).plan, plan);
+        obj
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe extern "C" fn schema_dealloc(object: *mut PyObject) {
+    unsafe {
+        core::ptr::drop_in_place(&raw mut (*object.cast::<Schema>()).plan);
+        crate::ffi::PyObject_Free(object.cast::<core::ffi::c_void>());
+    }
+}
+
+unsafe extern "C" fn schema_loads(slf: *mut PyObject, data: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        crate::stats::record(crate::stats::Entry::SchemaLoads, 0);
+        let plan = &(*slf.cast::<Schema>()).plan;
+        deserialize_typed(data, &DeserializeOptions::default(), plan)
+            .map_or_else(raise_loads_exception, NonNull::as_ptr)
+    }
+}
+
+unsafe extern "C" fn schema_dumps(slf: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        crate::stats::record(crate::stats::Entry::SchemaDumps, 0);
+        let plan = &(*slf.cast::<Schema>()).plan;
+        let mut writer = BytesWriter::default();
+        let _serializing = Serializing::enter();
+        let res = to_writer(
+            &mut writer,
+            &PlanSerializer {
+                ptr: obj,
+                kind: &plan.root,
+                plan: plan,
+                state: SerializerState::new(0),
+            },
+            0,
+        );
+        match res {
+            Ok(()) => writer.finish(b"").as_ptr(),
+            Err(err) => {
+                writer.abort();
+                raise_dumps_exception_dynamic(err.to_string().as_str())
+            }
+        }
+    }
+}
+
+macro_rules! method_def {
+    ($name:expr, $func:ident, $flags:expr, $doc:expr) => {
+        PyMethodDef {
+            ml_name: $name.as_ptr(),
+            ml_meth: PyMethodDefPointer { PyCFunction: $func },
+            ml_flags: $flags,
+            ml_doc: $doc.as_ptr(),
+        }
+    };
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub(crate) unsafe fn schema_type_new() -> *mut PyTypeObject {
+    unsafe {
+        let methods = Box::new([
+            method_def!(
+                c"loads",
+                schema_loads,
+                METH_O,
+                c"Deserialize JSON to the type of the schema."
+            ),
+            method_def!(
+                c"dumps",
+                schema_dumps,
+                METH_O,
+                c"Serialize a value of the type of the schema to JSON."
+            ),
+            PyMethodDef::zeroed(),
+        ]);
+
+        #[cfg(Py_GIL_DISABLED)]
+        let tp_flags: AtomicCULong =
+            AtomicCULong::new(Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE);
+        #[cfg(all(Py_3_10, not(Py_GIL_DISABLED)))]
+        let tp_flags: core::ffi::c_ulong =
+            Py_TPFLAGS_DEFAULT | crate::ffi::Py_TPFLAGS_IMMUTABLETYPE;
+        #[cfg(not(Py_3_10))]
+        let tp_flags: core::ffi::c_ulong = Py_TPFLAGS_DEFAULT;
+        let ob = Box::new(PyTypeObject {
+            ob_base: PyVarObject {
+                ob_base: PyObject {
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_tid: 0,
+                    #[cfg(all(Py_GIL_DISABLED, Py_3_14))]
+                    ob_flags: 0,
+                    #[cfg(all(Py_GIL_DISABLED, not(Py_3_14)))]
+                    _padding: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_mutex: pymutex_new!(),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_gc_bits: 0,
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_local: AtomicU32::new(crate::ffi::compat::_Py_IMMORTAL_REFCNT_LOCAL),
+                    #[cfg(Py_GIL_DISABLED)]
+                    ob_ref_shared: AtomicIsize::new(0),
+                    #[cfg(all(Py_3_12, not(Py_GIL_DISABLED)))]
+                    ob_refcnt: crate::ffi::PyObjectObRefcnt { ob_refcnt: 0 },
+                    #[cfg(not(Py_3_12))]
+                    ob_refcnt: 0,
+                    #[cfg(PyPy)]
+                    ob_pypy_link: 0,
+                    ob_type: &raw mut PyType_Type,
+                },
+                #[cfg(not(GraalPy))]
+                ob_size: 0,
+                #[cfg(GraalPy)]
+                _ob_size_graalpy: 0,
+            },
+            tp_name: c"hyperjson.Schema".as_ptr(),
+            tp_basicsize: core::mem::size_of::<Schema>() as isize,
+            tp_itemsize: 0,
+            tp_dealloc: Some(schema_dealloc),
+            tp_vectorcall_offset: 0,
+            tp_getattr: None,
+            tp_setattr: None,
+            tp_as_async: null_mut(),
+            tp_repr: None,
+            tp_as_number: null_mut(),
+            tp_as_sequence: null_mut(),
+            tp_as_mapping: null_mut(),
+            tp_hash: None,
+            tp_call: None,
+            tp_str: None,
+            tp_getattro: None,
+            tp_setattro: None,
+            tp_as_buffer: null_mut(),
+            tp_flags: tp_flags,
+            tp_doc: c"Schema(typ)\n--\n\nCompile a type annotation to read and write JSON of it."
+                .as_ptr(),
+            tp_traverse: None,
+            tp_clear: None,
+            tp_richcompare: None,
+            tp_weaklistoffset: 0,
+            tp_iter: None,
+            tp_iternext: None,
+            tp_methods: Box::into_raw(methods).cast::<PyMethodDef>(),
+            tp_members: null_mut(),
+            tp_getset: null_mut(),
+            tp_base: null_mut(),
+            tp_dict: null_mut(),
+            tp_descr_get: None,
+            tp_descr_set: None,
+            tp_dictoffset: 0,
+            tp_init: None,
+            tp_alloc: None,
+            tp_new: Some(schema_tp_new),
+            tp_free: None,
+            tp_is_gc: None,
+            tp_bases: null_mut(),
+            tp_mro: null_mut(),
+            tp_cache: null_mut(),
+            tp_subclasses: null_mut(),
+            tp_weaklist: null_mut(),
+            tp_del: None,
+            tp_version_tag: 0,
+            tp_finalize: None,
+            tp_vectorcall: None,
+            #[cfg(Py_3_12)]
+            tp_watched: 0,
+        });
+        let ob_ptr = Box::into_raw(ob);
+        PyType_Ready(ob_ptr);
+        ob_ptr
+    }
+}
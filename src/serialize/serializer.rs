@@ -1,33 +1,63 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 // Copyright ijl (2018-2025)
 
-use crate::opt::{APPEND_NEWLINE, INDENT_2, Opt};
+use crate::opt::{
+    APPEND_CRLF, APPEND_NEWLINE, DEFAULT_PATH, ENSURE_ASCII, EXTENDED_JSON,
+    EXTENDED_JSON_CANONICAL, INDENT, INDENT_4, INDENT_8, INDENT_TAB, MSGPACK,
+    NOT_DECODED_PASSTHROUGH, Opt,
+};
+use crate::runtime::Serializing;
+use crate::serialize::escape_cache::{self, EscapeCache};
+use crate::serialize::key_order;
+use crate::serialize::number_format;
 use crate::serialize::obtype::{ObType, pyobject_to_obtype};
+use crate::serialize::path::{self, DefaultPaths};
+use crate::serialize::per_type::extended_json;
 use crate::serialize::per_type::{
-    BoolSerializer, DataclassGenericSerializer, Date, DateTime, DefaultSerializer,
-    DictGenericSerializer, EnumSerializer, FloatSerializer, FragmentSerializer, IntSerializer,
-    ListTupleSerializer, NoneSerializer, NumpyScalar, NumpySerializer, StrSerializer,
-    StrSubclassSerializer, Time, UUID, ZeroListSerializer,
+    ArrowSerializer, BoolSerializer, BytesSerializer, DataclassGenericSerializer, Date, DateTime,
+    DefaultSerializer, DictGenericSerializer, EnumSerializer, ExtendedJsonSerializer,
+    FloatSerializer, FragmentSerializer, IntSerializer, ListTupleSerializer, NamedTupleSerializer,
+    NoneSerializer, NumpyScalar, NumpySerializer, PandasSerializer, RawNumberSerializer,
+    StrSerializer, StrSubclassSerializer, TaggedSerializer, Time, UUID, ZeroListSerializer,
 };
 use crate::serialize::state::SerializerState;
-use crate::serialize::writer::{BytesWriter, to_writer, to_writer_pretty};
+use crate::serialize::writer::{
+    BytesWriter, HashWriter, LengthCounter, MsgpackSerializer, WriteExt, to_writer,
+    to_writer_pretty,
+};
+use bytes::BufMut;
 use core::ptr::NonNull;
 use serde::ser::{Serialize, Serializer};
 
+/// Serialize `ptr`. `length`, if given, is the length of the output as
+/// measured by `serialize_length()`, and the output is allocated to fit it.
+/// `width`, if not 0, is the column indented output keeps arrays and objects
+/// on one line within. `comparator` is the `key_order=` callable and
+/// `formatter` the `float_format=` callable, if any.
 pub(crate) fn serialize(
     ptr: *mut crate::ffi::PyObject,
     default: Option<NonNull<crate::ffi::PyObject>>,
     opts: Opt,
+    length: Option<usize>,
+    width: usize,
+    comparator: Option<NonNull<crate::ffi::PyObject>>,
+    formatter: Option<NonNull<crate::ffi::PyObject>>,
 ) -> Result<NonNull<crate::ffi::PyObject>, String> {
-    let mut buf = BytesWriter::default();
-    let obj = PyObjectSerializer::new(ptr, SerializerState::new(opts), default);
-    let res = if opt_disabled!(opts, INDENT_2) {
-        to_writer(&mut buf, &obj)
-    } else {
-        to_writer_pretty(&mut buf, &obj)
+    if opt_disabled!(opts, NOT_DECODED_PASSTHROUGH) {
+        if let Some(bytes) = serialize_decoded(ptr, opts) {
+            return Ok(bytes);
+        }
+    }
+    let mut buf = match length {
+        Some(len) => BytesWriter::with_exact_capacity(len),
+        None => BytesWriter::default(),
     };
-    match res {
-        Ok(()) => Ok(buf.finish(opt_enabled!(opts, APPEND_NEWLINE))),
+    let layout = Layout {
+        width: width,
+        depth: 0,
+    };
+    match write_obj(&mut buf, ptr, default, opts, layout, comparator, formatter) {
+        Ok(()) => Ok(buf.finish(line_ending(opts))),
         Err(err) => {
             buf.abort();
             Err(err.to_string())
@@ -35,6 +65,255 @@ pub(crate) fn serialize(
     }
 }
 
+/// Return the length of the output of `serialize()` without keeping it.
+/// `default` is called as it would be by `serialize()`.
+pub(crate) fn serialize_length(
+    ptr: *mut crate::ffi::PyObject,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    opts: Opt,
+    width: usize,
+    comparator: Option<NonNull<crate::ffi::PyObject>>,
+    formatter: Option<NonNull<crate::ffi::PyObject>>,
+) -> Result<usize, String> {
+    if opt_disabled!(opts, NOT_DECODED_PASSTHROUGH) {
+        if let Some(len) = decoded_len(ptr) {
+            return Ok(len + line_ending(opts).len());
+        }
+    }
+    if width != 0 {
+        // the formatter rewrites what it has written
+        let mut buf = BytesWriter::default();
+        let layout = Layout {
+            width: width,
+            depth: 0,
+        };
+        let res = write_obj(&mut buf, ptr, default, opts, layout, comparator, formatter);
+        let len = buf.len();
+        buf.abort();
+        return match res {
+            Ok(()) => Ok(len + line_ending(opts).len()),
+            Err(err) => Err(err.to_string()),
+        };
+    }
+    let mut counter = LengthCounter::new();
+    let layout = Layout::default();
+    match write_obj(
+        &mut counter,
+        ptr,
+        default,
+        opts,
+        layout,
+        comparator,
+        formatter,
+    ) {
+        Ok(()) => Ok(counter.len() + line_ending(opts).len()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Return the SHA-256 of the output of `serialize()` without keeping it.
+pub(crate) fn serialize_sha256(
+    ptr: *mut crate::ffi::PyObject,
+    opts: Opt,
+) -> Result<[u8; 32], String> {
+    let mut hasher = HashWriter::new();
+    match write_obj(&mut hasher, ptr, None, opts, Layout::default(), None, None) {
+        Ok(()) => {
+            hasher.put_slice(line_ending(opts));
+            Ok(hasher.finish())
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Serialize `ptr` as MessagePack with the serializers `serialize()` uses.
+/// Options that only format JSON, such as indenting, have no effect.
+pub(crate) fn serialize_msgpack(
+    ptr: *mut crate::ffi::PyObject,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    opts: Opt,
+) -> Result<NonNull<crate::ffi::PyObject>, String> {
+    // cached strings are escaped JSON, which would be read again
+    let previous = escape_cache::suspend();
+    let _serializing = Serializing::enter();
+    let obj = PyObjectSerializer::new(ptr, SerializerState::new(opts | MSGPACK), default);
+    let mut ser = MsgpackSerializer::new();
+    let res = if opt_enabled!(opts, DEFAULT_PATH) {
+        cold_path!();
+        let mut paths = DefaultPaths::new(ptr);
+        let previous_paths = path::install(&mut paths);
+        let res = obj.serialize(&mut ser);
+        path::restore(previous_paths);
+        res
+    } else {
+        obj.serialize(&mut ser)
+    };
+    escape_cache::restore(previous);
+    match res {
+        Ok(()) => {
+            let buf = ser.as_slice();
+            Ok(nonnull!(ffi!(PyBytes_FromStringAndSize(
+                buf.as_ptr().cast::<core::ffi::c_char>(),
+                crate::util::usize_to_isize(buf.len())
+            ))))
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Serialize `ptr` as the value `depth` levels deep in a document is
+/// written, without a line ending, for `splice()`.
+pub(crate) fn serialize_nested(
+    ptr: *mut crate::ffi::PyObject,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    opts: Opt,
+    depth: usize,
+) -> Result<Vec<u8>, String> {
+    let mut buf = BytesWriter::default();
+    let layout = Layout {
+        width: 0,
+        depth: depth,
+    };
+    let res = write_obj(&mut buf, ptr, default, opts, layout, None, None)
+        .map(|()| buf.as_slice().to_vec())
+        .map_err(|err| err.to_string());
+    buf.abort();
+    res
+}
+
+/// Where indented output is written.
+#[derive(Clone, Copy, Default)]
+struct Layout {
+    /// The column arrays and objects are kept on one line within, or 0.
+    width: usize,
+    /// The nesting of the value, which is 0 for a document.
+    depth: usize,
+}
+
+#[inline(always)]
+fn write_obj<W>(
+    writer: W,
+    ptr: *mut crate::ffi::PyObject,
+    default: Option<NonNull<crate::ffi::PyObject>>,
+    opts: Opt,
+    layout: Layout,
+    comparator: Option<NonNull<crate::ffi::PyObject>>,
+    formatter: Option<NonNull<crate::ffi::PyObject>>,
+) -> serde_json::Result<()>
+where
+    W: WriteExt + BufMut,
+{
+    let ensure_ascii = opt_enabled!(opts, ENSURE_ASCII);
+    let mut cache = EscapeCache::new();
+    let previous = if ensure_ascii {
+        escape_cache::suspend()
+    } else {
+        escape_cache::install(&mut cache)
+    };
+    let previous_comparator = comparator.map(|ptr| key_order::install(ptr.as_ptr()));
+    let previous_formatter = formatter.map(|ptr| number_format::install(ptr.as_ptr()));
+    let previous_extended_json = opt_enabled!(opts, EXTENDED_JSON)
+        .then(|| extended_json::install(opt_enabled!(opts, EXTENDED_JSON_CANONICAL)));
+    let _serializing = Serializing::enter();
+    let obj = PyObjectSerializer::new(ptr, SerializerState::new(opts), default);
+    let res = if opt_enabled!(opts, DEFAULT_PATH) {
+        cold_path!();
+        let mut paths = DefaultPaths::new(ptr);
+        let previous_paths = path::install(&mut paths);
+        let res = if opt_disabled!(opts, INDENT) {
+            to_writer(writer, &obj, opts)
+        } else {
+            let (indent, fill) = indent(opts);
+            to_writer_pretty(writer, &obj, indent, fill, layout.width, layout.depth, opts)
+        };
+        path::restore(previous_paths);
+        res
+    } else if opt_disabled!(opts, INDENT) {
+        to_writer(writer, &obj, opts)
+    } else {
+        let (indent, fill) = indent(opts);
+        to_writer_pretty(writer, &obj, indent, fill, layout.width, layout.depth, opts)
+    };
+    if let Some(previous_extended_json) = previous_extended_json {
+        extended_json::restore(previous_extended_json);
+    }
+    if let Some(previous_formatter) = previous_formatter {
+        number_format::restore(previous_formatter);
+    }
+    if let Some(previous_comparator) = previous_comparator {
+        key_order::restore(previous_comparator);
+    }
+    escape_cache::restore(previous);
+    res
+}
+
+/// The bytes appended to the output.
+#[inline(always)]
+fn line_ending(opts: Opt) -> &'static [u8] {
+    if opt_enabled!(opts, APPEND_CRLF) {
+        b"\r\n"
+    } else if opt_enabled!(opts, APPEND_NEWLINE) {
+        b"\n"
+    } else {
+        b""
+    }
+}
+
+/// The number of bytes per level of nesting and the byte to indent with.
+/// `OPT_INDENT_TAB` takes precedence, then the widest indent set.
+#[inline(always)]
+fn indent(opts: Opt) -> (usize, u8) {
+    if opt_enabled!(opts, INDENT_TAB) {
+        (1, b'\t')
+    } else if opt_enabled!(opts, INDENT_8) {
+        (8, b' ')
+    } else if opt_enabled!(opts, INDENT_4) {
+        (4, b' ')
+    } else {
+        (2, b' ')
+    }
+}
+
+/// Write a `loads_tracked()` result that was not modified as its input.
+#[inline(always)]
+fn serialize_decoded(
+    ptr: *mut crate::ffi::PyObject,
+    opts: Opt,
+) -> Option<NonNull<crate::ffi::PyObject>> {
+    let ob_type = ob_type!(ptr);
+    if !is_type!(ob_type, crate::typeref::list_type_ptr())
+        && !is_type!(ob_type, crate::typeref::dict_type_ptr())
+    {
+        return None;
+    }
+    unsafe {
+        (*crate::runtime::current_state())
+            .decoded
+            .with_source(ptr, |source| {
+                let mut buf = BytesWriter::with_capacity(source.len() + 3);
+                buf.put_slice(source);
+                buf.finish(line_ending(opts))
+            })
+    }
+}
+
+/// The length of a `loads_tracked()` result that was not modified, which
+/// `serialize_decoded()` writes as its input.
+#[inline(always)]
+fn decoded_len(ptr: *mut crate::ffi::PyObject) -> Option<usize> {
+    let ob_type = ob_type!(ptr);
+    if !is_type!(ob_type, crate::typeref::list_type_ptr())
+        && !is_type!(ob_type, crate::typeref::dict_type_ptr())
+    {
+        return None;
+    }
+    unsafe {
+        (*crate::runtime::current_state())
+            .decoded
+            .with_source(ptr, <[u8]>::len)
+    }
+}
+
 pub(crate) struct PyObjectSerializer {
     pub ptr: *mut crate::ffi::PyObject,
     pub state: SerializerState,
@@ -93,10 +372,19 @@ impl Serialize for PyObjectSerializer {
             ObType::Dataclass => DataclassGenericSerializer::new(self).serialize(serializer),
             ObType::Enum => EnumSerializer::new(self).serialize(serializer),
             ObType::NumpyArray => NumpySerializer::new(self).serialize(serializer),
+            ObType::Pandas => PandasSerializer::new(self).serialize(serializer),
+            ObType::Arrow => ArrowSerializer::new(self).serialize(serializer),
             ObType::NumpyScalar => {
                 NumpyScalar::new(self.ptr, self.state.opts()).serialize(serializer)
             }
             ObType::Fragment => FragmentSerializer::new(self.ptr).serialize(serializer),
+            ObType::RawNumber => RawNumberSerializer::new(self.ptr).serialize(serializer),
+            ObType::NamedTuple => {
+                NamedTupleSerializer::new(self.ptr, self.state, self.default).serialize(serializer)
+            }
+            ObType::Tagged => TaggedSerializer::new(self).serialize(serializer),
+            ObType::ExtendedJson => ExtendedJsonSerializer::new(self).serialize(serializer),
+            ObType::Bytes => BytesSerializer::new(self.ptr).serialize(serializer),
             ObType::Unknown => DefaultSerializer::new(self).serialize(serializer),
         }
     }
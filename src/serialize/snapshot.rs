@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Containers are serialized from borrowed references to their items, which
+//! is sound only while no Python code runs. `default`, a `key_order=`
+//! comparator, a `float_format=` callable and the callables registered with
+//! `register_serializer()` are Python code, and may mutate a container that
+//! is being serialized and free the items it borrows.
+//!
+//! When any is given, the item of a dict or list that is being written is
+//! held by a strong reference, and the list is read again before each item,
+//! so a callback cannot free what is being written. A container is written
+//! as it is when each of its items is reached: items a callback removes
+//! before they are reached are not written, and no more items are written
+//! than it had when it was reached. Sorted keys are collected before any is written, so a dict
+//! with sorted or non-str keys is serialized from a shallow copy taken when
+//! its serialization begins. Attributes read from a dataclass are owned until
+//! they are written.
+
+use crate::ffi::PyObject;
+use crate::opt::{CALLBACKS, Opt};
+use crate::util::isize_to_usize;
+use core::ptr::NonNull;
+
+/// Whether Python code may run while serializing with `opts` and `default`.
+#[inline(always)]
+pub(crate) fn is_required(opts: Opt, default: Option<NonNull<PyObject>>) -> bool {
+    default.is_some() || opts & CALLBACKS != 0
+}
+
+/// A strong reference, released when dropped.
+pub(crate) struct Snapshot {
+    ptr: NonNull<PyObject>,
+}
+
+impl Snapshot {
+    /// Take ownership of the new reference `ptr`.
+    pub fn new(ptr: *mut PyObject) -> Self {
+        Snapshot { ptr: nonnull!(ptr) }
+    }
+
+    /// A new strong reference to `ptr`.
+    #[inline(always)]
+    pub fn of(ptr: *mut PyObject) -> Self {
+        ffi!(Py_INCREF(ptr));
+        Self::new(ptr)
+    }
+
+    /// A copy of the dict `ptr` with the same keys and values.
+    pub fn of_dict(ptr: *mut PyObject) -> Self {
+        Self::new(ffi!(PyDict_Copy(ptr)))
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *mut PyObject {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.ptr.as_ptr()));
+    }
+}
+
+/// The entries of a dict that a callback may change, each held while it is
+/// written. No more entries are returned than the dict had when iteration
+/// began.
+pub(crate) struct DictEntries {
+    ptr: *mut PyObject,
+    pos: isize,
+    remaining: usize,
+    compat: bool,
+}
+
+impl DictEntries {
+    pub fn new(ptr: *mut PyObject, compat: bool) -> Self {
+        DictEntries {
+            ptr: ptr,
+            pos: 0,
+            remaining: isize_to_usize(pydict_len!(compat, ptr)),
+            compat: compat,
+        }
+    }
+}
+
+impl Iterator for DictEntries {
+    type Item = (Snapshot, Snapshot);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let mut key: *mut PyObject = core::ptr::null_mut();
+        let mut value: *mut PyObject = core::ptr::null_mut();
+        if pydict_next_compat!(self.compat, self.ptr, &mut self.pos, &mut key, &mut value) == 0 {
+            self.remaining = 0;
+            return None;
+        }
+        Some((Snapshot::of(key), Snapshot::of(value)))
+    }
+}
@@ -2,20 +2,24 @@
 // Copyright ijl (2024-2025)
 
 use crate::interpreter_state::InterpreterState;
-use crate::opt::Opt;
+use crate::opt::{
+    CALLBACKS, FLOAT_FORMAT, FLOAT_FORMAT_CALLABLE, KEY_ORDER, KEY_ORDER_CALLABLE, Opt,
+};
 
-const RECURSION_SHIFT: usize = 24;
-const RECURSION_MASK: u32 = 255 << RECURSION_SHIFT;
+const RECURSION_SHIFT: usize = 56;
+const RECURSION_MASK: u64 = 255 << RECURSION_SHIFT;
 
-const DEFAULT_SHIFT: usize = 16;
-const DEFAULT_MASK: u32 = 255 << DEFAULT_SHIFT;
+const DEFAULT_SHIFT: usize = 48;
+const DEFAULT_MASK: u64 = 255 << DEFAULT_SHIFT;
+
+const OPT_MASK: u64 = (1 << DEFAULT_SHIFT) - 1;
 
 #[derive(Copy, Clone)]
 pub(crate) struct SerializerState {
     // recursion: u8,
     // default_calls: u8,
-    // opts: u16,
-    state: u32,
+    // opts: u48,
+    state: u64,
     // Cached interpreter state pointer for fast access during serialization
     // Valid for the lifetime of the serialization call (GIL is held)
     interpreter_state: *const InterpreterState,
@@ -24,20 +28,42 @@ pub(crate) struct SerializerState {
 impl SerializerState {
     #[inline(always)]
     pub fn new(opts: Opt) -> Self {
-        debug_assert!(opts < u32::from(u16::MAX));
         // Get interpreter state pointer once at the start of serialization
         // This avoids repeated thread-local lookups during serialization
-        let interpreter_state = unsafe { crate::interpreter_state::get_current_state() };
+        let interpreter_state = unsafe { crate::runtime::current_state() };
         debug_assert!(!interpreter_state.is_null());
+        // a callable given for the call or registered with
+        // register_serializer() may change what is being serialized
+        let callbacks = if opts & KEY_ORDER == KEY_ORDER_CALLABLE
+            || opts & FLOAT_FORMAT == FLOAT_FORMAT_CALLABLE
+            || ffi!(Py_SIZE(unsafe { (*interpreter_state).serializers })) != 0
+        {
+            CALLBACKS
+        } else {
+            0
+        };
         Self {
-            state: opts,
+            // keyword arguments above the options, such as float_format=,
+            // are read by the writer and would be counted as default calls
+            state: (opts & OPT_MASK) | callbacks,
             interpreter_state,
         }
     }
 
     #[inline(always)]
-    pub fn opts(self) -> u32 {
-        self.state
+    pub fn opts(self) -> Opt {
+        self.state & OPT_MASK
+    }
+
+    /// Whether lists, tuples, and dicts are read with the public C API only,
+    /// from `set_compat_mode()`.
+    #[inline(always)]
+    pub fn compat_mode(self) -> bool {
+        unsafe {
+            (*self.interpreter_state)
+                .compat_mode
+                .load(core::sync::atomic::Ordering::Relaxed)
+        }
     }
 
     #[inline(always)]
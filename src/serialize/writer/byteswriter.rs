@@ -13,9 +13,15 @@ const BUFFER_LENGTH: usize = 1024;
 #[cfg(not(CPython))]
 const BUFFER_LENGTH: usize = 4096;
 
+/// Space past a measured length for the writer's own `reserve()` calls,
+/// the largest fixed one being `reserve_minimum!()`.
+const EXACT_SLACK: usize = 256;
+
 pub(crate) struct BytesWriter {
     cap: usize,
     len: usize,
+    /// Grow only as much as needed rather than doubling.
+    exact: bool,
     #[cfg(CPython)]
     bytes: *mut crate::ffi::PyBytesObject,
     #[cfg(not(CPython))]
@@ -25,19 +31,37 @@ pub(crate) struct BytesWriter {
 impl BytesWriter {
     #[inline]
     pub fn default() -> Self {
+        BytesWriter::with_capacity(BUFFER_LENGTH)
+    }
+
+    /// Create a writer whose initial allocation is `cap` bytes. Callers that
+    /// know the output length up front use this to avoid growing the buffer.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
         BytesWriter {
-            cap: BUFFER_LENGTH,
+            cap: cap,
             len: 0,
+            exact: false,
             #[cfg(CPython)]
             bytes: unsafe {
-                PyBytes_FromStringAndSize(core::ptr::null_mut(), usize_to_isize(BUFFER_LENGTH))
+                PyBytes_FromStringAndSize(core::ptr::null_mut(), usize_to_isize(cap))
                     .cast::<crate::ffi::PyBytesObject>()
             },
             #[cfg(not(CPython))]
-            bytes: unsafe { crate::ffi::PyMem_Malloc(BUFFER_LENGTH).cast::<u8>() },
+            bytes: unsafe { crate::ffi::PyMem_Malloc(cap).cast::<u8>() },
         }
     }
 
+    /// Create a writer for output measured to be `len` bytes. Reservations
+    /// for strings assume the worst case of escaping, so one near the end of
+    /// the output may still grow the buffer, but only by what it reserves.
+    #[inline]
+    pub fn with_exact_capacity(len: usize) -> Self {
+        let mut writer = BytesWriter::with_capacity(len + EXACT_SLACK);
+        writer.exact = true;
+        writer
+    }
+
     #[cfg(CPython)]
     pub fn abort(&mut self) {
         ffi!(Py_DECREF(self.bytes.cast::<PyObject>()));
@@ -50,11 +74,12 @@ impl BytesWriter {
         }
     }
 
-    fn append_and_terminate(&mut self, append: bool) {
+    fn append_and_terminate(&mut self, append: &[u8]) {
         unsafe {
-            if append {
-                core::ptr::write(self.buffer_ptr(), b'\n');
-                self.len += 1;
+            if !append.is_empty() {
+                (&mut *self).reserve(append.len());
+                core::ptr::copy_nonoverlapping(append.as_ptr(), self.buffer_ptr(), append.len());
+                self.len += append.len();
             }
             #[cfg(CPython)]
             core::ptr::write(self.buffer_ptr(), 0);
@@ -63,7 +88,7 @@ impl BytesWriter {
 
     #[cfg(CPython)]
     #[inline]
-    pub fn finish(&mut self, append: bool) -> NonNull<PyObject> {
+    pub fn finish(&mut self, append: &[u8]) -> NonNull<PyObject> {
         unsafe {
             self.append_and_terminate(append);
             crate::ffi::Py_SET_SIZE(
@@ -77,7 +102,7 @@ impl BytesWriter {
 
     #[cfg(not(CPython))]
     #[inline]
-    pub fn finish(&mut self, append: bool) -> NonNull<PyObject> {
+    pub fn finish(&mut self, append: &[u8]) -> NonNull<PyObject> {
         unsafe {
             self.append_and_terminate(append);
             let bytes = PyBytes_FromStringAndSize(
@@ -126,10 +151,44 @@ impl BytesWriter {
         }
     }
 
+    /// Number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Discard everything written after `len`, e.g., the partial output of
+    /// a value that failed to serialize.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        debug_assert!(len <= self.len);
+        self.len = len;
+    }
+
+    /// The bytes written so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.buffer_ptr().sub(self.len), self.len) }
+    }
+
+    /// Discard the first `len` bytes written, moving the rest to the start.
+    #[inline]
+    pub fn consume(&mut self, len: usize) {
+        debug_assert!(len <= self.len);
+        unsafe {
+            let start = self.buffer_ptr().sub(self.len);
+            core::ptr::copy(start.add(len), start, self.len - len);
+        }
+        self.len -= len;
+    }
+
     #[cold]
     #[inline(never)]
     fn grow(&mut self, len: usize) {
         let mut cap = self.cap;
+        if self.exact {
+            cap = len + 1;
+        }
         while len >= cap {
             cap *= 2;
         }
@@ -197,6 +256,19 @@ pub(crate) trait WriteExt {
     fn reserve(&mut self, len: usize) {
         let _ = len;
     }
+
+    /// The output written so far, for a formatter that rewrites it. A
+    /// writer that does not keep its output returns an empty slice.
+    #[inline]
+    fn written_mut(&mut self) -> &mut [u8] {
+        &mut []
+    }
+
+    /// Discard the output after `len` bytes.
+    #[inline]
+    fn rewind(&mut self, len: usize) {
+        let _ = len;
+    }
 }
 
 impl WriteExt for &mut BytesWriter {
@@ -213,4 +285,14 @@ impl WriteExt for &mut BytesWriter {
             self.grow(end_length);
         }
     }
+
+    #[inline]
+    fn written_mut(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.buffer_ptr().sub(self.len), self.len) }
+    }
+
+    #[inline]
+    fn rewind(&mut self, len: usize) {
+        self.truncate(len);
+    }
 }
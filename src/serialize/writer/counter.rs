@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::serialize::writer::WriteExt;
+use bytes::{BufMut, buf::UninitSlice};
+
+/// A writer that counts the bytes written instead of keeping them.
+///
+/// Writes through `as_mut_buffer_ptr()`, such as escaped strings and
+/// formatted numbers, go to the start of a scratch buffer that is only as
+/// large as the largest reservation, so measuring a document does not
+/// allocate its length.
+pub(crate) struct LengthCounter {
+    len: usize,
+    scratch: Vec<u8>,
+}
+
+impl LengthCounter {
+    pub fn new() -> Self {
+        LengthCounter {
+            len: 0,
+            scratch: Vec::with_capacity(1024),
+        }
+    }
+
+    /// Number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+unsafe impl BufMut for LengthCounter {
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.len += cnt;
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::uninit(self.scratch.spare_capacity_mut())
+    }
+
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.scratch.capacity()
+    }
+
+    #[inline]
+    fn put_u8(&mut self, _value: u8) {
+        self.len += 1;
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, _val: u8, cnt: usize) {
+        self.len += cnt;
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.len += src.len();
+    }
+}
+
+impl WriteExt for &mut LengthCounter {
+    #[inline(always)]
+    fn as_mut_buffer_ptr(&mut self) -> *mut u8 {
+        self.scratch.as_mut_ptr()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, len: usize) {
+        if len > self.scratch.capacity() {
+            cold_path!();
+            self.scratch.reserve(len);
+        }
+    }
+}
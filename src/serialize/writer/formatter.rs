@@ -232,16 +232,130 @@ impl Formatter for CompactFormatter {}
 pub(crate) struct PrettyFormatter {
     current_indent: usize,
     has_value: bool,
+    /// Number of `fill` bytes per level of nesting.
+    indent: usize,
+    /// Byte each level of nesting is indented with, a space or a tab.
+    fill: u8,
+    /// Column, in bytes, that an array or object is written on one line
+    /// within if it fits, or 0 to write each value on its own line.
+    width: usize,
+    /// Arrays and objects being written if `width` is set, innermost last.
+    open: Vec<OpenContainer>,
+}
+
+struct OpenContainer {
+    /// Offset of the opening bracket in the output.
+    start: usize,
+    /// Whether every array or object in it fit on one line.
+    fits: bool,
 }
 
 impl PrettyFormatter {
-    #[allow(clippy::new_without_default)]
-    pub const fn new() -> Self {
+    /// `depth` is the nesting of the value written, which is 0 for a
+    /// document.
+    pub const fn new(indent: usize, fill: u8, width: usize, depth: usize) -> Self {
         PrettyFormatter {
-            current_indent: 0,
+            current_indent: depth,
             has_value: false,
+            indent: indent,
+            fill: fill,
+            width: width,
+            open: Vec::new(),
+        }
+    }
+
+    #[cold]
+    fn open_container<W>(&mut self, writer: &mut W)
+    where
+        W: ?Sized + WriteExt,
+    {
+        let start = writer.written_mut().len();
+        self.open.push(OpenContainer {
+            start: start,
+            fits: true,
+        });
+    }
+
+    /// Rewrite the array or object just closed on one line if it and
+    /// everything in it fits within `width`.
+    #[cold]
+    fn close_container<W>(&mut self, writer: &mut W)
+    where
+        W: ?Sized + WriteExt,
+    {
+        let container = self.open.pop().unwrap();
+        if container.fits {
+            let output = writer.written_mut();
+            debug_assert!(container.start < output.len());
+            let line_start = output[..container.start]
+                .iter()
+                .rposition(|&byte| byte == b'\n')
+                .map_or(0, |idx| idx + 1);
+            let available = self.width.saturating_sub(container.start - line_start);
+            let span = &mut output[container.start..];
+            if single_line_len(span, self.fill, available).is_some() {
+                let len = join_lines(span, self.fill);
+                writer.rewind(container.start + len);
+                return;
+            }
+        }
+        if let Some(parent) = self.open.last_mut() {
+            parent.fits = false;
+        }
+    }
+}
+
+/// The length of `span`, pretty-printed output, on one line, or `None` if
+/// it is longer than `available`. A line break and the indentation after it
+/// are removed, or replaced by a space after a comma.
+fn single_line_len(span: &[u8], fill: u8, available: usize) -> Option<usize> {
+    let mut len = 0;
+    let mut previous = 0;
+    let mut idx = 0;
+    while idx < span.len() {
+        let byte = span[idx];
+        idx += 1;
+        if byte == b'\n' {
+            while idx < span.len() && span[idx] == fill {
+                idx += 1;
+            }
+            if previous == b',' {
+                len += 1;
+            }
+        } else {
+            len += 1;
+            previous = byte;
+        }
+        if len > available {
+            return None;
+        }
+    }
+    Some(len)
+}
+
+/// Rewrite `span` on one line in place as `single_line_len()` measures it
+/// and return its new length. No byte is written after the one it is read
+/// from, so the rewrite only moves bytes back.
+fn join_lines(span: &mut [u8], fill: u8) -> usize {
+    let mut len = 0;
+    let mut idx = 0;
+    while idx < span.len() {
+        let byte = span[idx];
+        idx += 1;
+        if byte == b'\n' {
+            while idx < span.len() && span[idx] == fill {
+                idx += 1;
+            }
+            if len > 0 && span[len - 1] == b',' {
+                span[len] = b' ';
+                len += 1;
+            }
+        } else {
+            span[len] = byte;
+            len += 1;
         }
     }
+    len
 }
 
 impl Formatter for PrettyFormatter {
@@ -252,6 +366,9 @@ impl Formatter for PrettyFormatter {
     {
         self.current_indent += 1;
         self.has_value = false;
+        if self.width != 0 {
+            self.open_container(writer);
+        }
         reserve_minimum!(writer);
         unsafe {
             writer.put_u8(b'[');
@@ -265,17 +382,20 @@ impl Formatter for PrettyFormatter {
         W: ?Sized + WriteExt + bytes::BufMut,
     {
         self.current_indent -= 1;
-        let num_spaces = self.current_indent * 2;
-        reserve_pretty!(writer, num_spaces);
+        let indent_len = self.current_indent * self.indent;
+        reserve_pretty!(writer, indent_len);
 
         unsafe {
             if self.has_value {
                 writer.put_u8(b'\n');
-                writer.put_bytes(b' ', num_spaces);
+                writer.put_bytes(self.fill, indent_len);
             }
             writer.put_u8(b']');
-            Ok(())
         }
+        if self.width != 0 {
+            self.close_container(writer);
+        }
+        Ok(())
     }
 
     #[inline]
@@ -283,12 +403,12 @@ impl Formatter for PrettyFormatter {
     where
         W: ?Sized + WriteExt + bytes::BufMut,
     {
-        let num_spaces = self.current_indent * 2;
-        reserve_pretty!(writer, num_spaces);
+        let indent_len = self.current_indent * self.indent;
+        reserve_pretty!(writer, indent_len);
 
         unsafe {
             writer.put_slice(if first { b"\n" } else { b",\n" });
-            writer.put_bytes(b' ', num_spaces);
+            writer.put_bytes(self.fill, indent_len);
         };
         Ok(())
     }
@@ -309,6 +429,9 @@ impl Formatter for PrettyFormatter {
     {
         self.current_indent += 1;
         self.has_value = false;
+        if self.width != 0 {
+            self.open_container(writer);
+        }
 
         reserve_minimum!(writer);
         unsafe {
@@ -323,18 +446,21 @@ impl Formatter for PrettyFormatter {
         W: ?Sized + WriteExt + bytes::BufMut,
     {
         self.current_indent -= 1;
-        let num_spaces = self.current_indent * 2;
-        reserve_pretty!(writer, num_spaces);
+        let indent_len = self.current_indent * self.indent;
+        reserve_pretty!(writer, indent_len);
 
         unsafe {
             if self.has_value {
                 writer.put_u8(b'\n');
-                writer.put_bytes(b' ', num_spaces);
+                writer.put_bytes(self.fill, indent_len);
             }
 
             writer.put_u8(b'}');
-            Ok(())
         }
+        if self.width != 0 {
+            self.close_container(writer);
+        }
+        Ok(())
     }
 
     #[inline]
@@ -342,11 +468,11 @@ impl Formatter for PrettyFormatter {
     where
         W: ?Sized + WriteExt + bytes::BufMut,
     {
-        let num_spaces = self.current_indent * 2;
-        reserve_pretty!(writer, num_spaces);
+        let indent_len = self.current_indent * self.indent;
+        reserve_pretty!(writer, indent_len);
         unsafe {
             writer.put_slice(if first { b"\n" } else { b",\n" });
-            writer.put_bytes(b' ', num_spaces);
+            writer.put_bytes(self.fill, indent_len);
         }
         Ok(())
     }
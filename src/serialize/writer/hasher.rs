@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+use crate::deserialize::Sha256;
+use crate::serialize::writer::WriteExt;
+use bytes::{BufMut, buf::UninitSlice};
+
+/// A writer that hashes the bytes written instead of keeping them.
+///
+/// Writes through `as_mut_buffer_ptr()` go to the start of a scratch buffer,
+/// as with `LengthCounter`, and are hashed when the writer is advanced.
+pub(crate) struct HashWriter {
+    hasher: Sha256,
+    scratch: Vec<u8>,
+}
+
+impl HashWriter {
+    pub fn new() -> Self {
+        HashWriter {
+            hasher: Sha256::new(),
+            scratch: Vec::with_capacity(1024),
+        }
+    }
+
+    /// The SHA-256 of the bytes written.
+    pub fn finish(self) -> [u8; 32] {
+        self.hasher.finish()
+    }
+}
+
+unsafe impl BufMut for HashWriter {
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        debug_assert!(cnt <= self.scratch.capacity());
+        let written = unsafe { core::slice::from_raw_parts(self.scratch.as_ptr(), cnt) };
+        self.hasher.update(written);
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::uninit(self.scratch.spare_capacity_mut())
+    }
+
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.scratch.capacity()
+    }
+
+    #[inline]
+    fn put_u8(&mut self, value: u8) {
+        self.hasher.update(&[value]);
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, val: u8, cnt: usize) {
+        for _ in 0..cnt {
+            self.hasher.update(&[val]);
+        }
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.hasher.update(src);
+    }
+}
+
+impl WriteExt for &mut HashWriter {
+    #[inline(always)]
+    fn as_mut_buffer_ptr(&mut self) -> *mut u8 {
+        self.scratch.as_mut_ptr()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, len: usize) {
+        if len > self.scratch.capacity() {
+            cold_path!();
+            self.scratch.reserve(len);
+        }
+    }
+}
@@ -2,14 +2,25 @@
 // Copyright ijl (2022-2025)
 // This is an adaptation of `src/value/ser.rs` from serde-json.
 
+use crate::opt::{
+    ENSURE_ASCII, FLOAT_FORMAT, NON_FINITE, NON_FINITE_LITERAL, NON_FINITE_RAISE,
+    NON_FINITE_STRING, Opt, float_decimals,
+};
+use crate::serialize::error::SerializeError;
+use crate::serialize::number_format;
 use crate::serialize::writer::WriteExt;
 use crate::serialize::writer::formatter::{CompactFormatter, Formatter, PrettyFormatter};
+use crate::serialize::writer::profile::Profile;
 use serde::ser::{self, Impossible, Serialize};
 use serde_json::error::{Error, Result};
 
 pub(crate) struct Serializer<W, F = CompactFormatter> {
     writer: W,
     formatter: F,
+    ensure_ascii: bool,
+    float_decimals: Option<u8>,
+    float_format: Opt,
+    non_finite: Opt,
 }
 
 impl<W> Serializer<W>
@@ -17,8 +28,8 @@ where
     W: WriteExt + bytes::BufMut,
 {
     #[inline]
-    pub fn new(writer: W) -> Self {
-        Serializer::with_formatter(writer, CompactFormatter)
+    pub fn new(writer: W, opts: Opt) -> Self {
+        Serializer::with_formatter(writer, CompactFormatter, opts)
     }
 }
 
@@ -27,8 +38,19 @@ where
     W: WriteExt + bytes::BufMut,
 {
     #[inline]
-    pub fn pretty(writer: W) -> Self {
-        Serializer::with_formatter(writer, PrettyFormatter::new())
+    pub fn pretty(
+        writer: W,
+        indent: usize,
+        fill: u8,
+        width: usize,
+        depth: usize,
+        opts: Opt,
+    ) -> Self {
+        Serializer::with_formatter(
+            writer,
+            PrettyFormatter::new(indent, fill, width, depth),
+            opts,
+        )
     }
 }
 
@@ -37,9 +59,61 @@ where
     W: WriteExt + bytes::BufMut,
     F: Formatter,
 {
+    /// `opts` selects how strings and floats are written; the formatter
+    /// decides everything else.
     #[inline]
-    pub fn with_formatter(writer: W, formatter: F) -> Self {
-        Serializer { writer, formatter }
+    pub fn with_formatter(writer: W, formatter: F, opts: Opt) -> Self {
+        Serializer {
+            writer,
+            formatter,
+            ensure_ascii: opt_enabled!(opts, ENSURE_ASCII),
+            float_decimals: float_decimals(opts),
+            // with the digits of FLOAT_FORMAT_FIXED
+            float_format: if opts & FLOAT_FORMAT == 0 { 0 } else { opts },
+            non_finite: opts & NON_FINITE,
+        }
+    }
+
+    /// Write `NaN`, `Infinity`, or `-Infinity` as `non_finite=` selects.
+    #[cold]
+    #[inline(never)]
+    fn serialize_non_finite(&mut self, value: f64) -> Result<()> {
+        let literal: &[u8] = match (self.non_finite, value.is_nan(), value > 0.0) {
+            (0, _, _) => {
+                return self
+                    .formatter
+                    .write_null(&mut self.writer)
+                    .map_err(Error::io);
+            }
+            (NON_FINITE_RAISE, _, _) => {
+                return Err(ser::Error::custom(SerializeError::NonFiniteFloat));
+            }
+            (NON_FINITE_LITERAL, true, _) => b"NaN",
+            (NON_FINITE_LITERAL, false, true) => b"Infinity",
+            (NON_FINITE_LITERAL, false, false) => b"-Infinity",
+            (NON_FINITE_STRING, true, _) => b"\"NaN\"",
+            (NON_FINITE_STRING, false, true) => b"\"Infinity\"",
+            (NON_FINITE_STRING, false, false) => b"\"-Infinity\"",
+            _ => unreachable_unchecked!(),
+        };
+        self.writer.reserve(literal.len());
+        unsafe {
+            self.writer.put_slice(literal);
+        }
+        Ok(())
+    }
+
+    /// Write finite `value` as `float_format=` selects.
+    #[cold]
+    #[inline(never)]
+    fn serialize_formatted(&mut self, value: f64) -> Result<()> {
+        let mut buf = Vec::with_capacity(32);
+        number_format::write_f64(self.float_format, value, &mut buf).map_err(ser::Error::custom)?;
+        self.writer.reserve(buf.len());
+        unsafe {
+            self.writer.put_slice(&buf);
+        }
+        Ok(())
     }
 }
 
@@ -122,7 +196,18 @@ where
     fn serialize_f32(self, value: f32) -> Result<()> {
         if value.is_infinite() || value.is_nan() {
             cold_path!();
-            self.serialize_unit()
+            self.serialize_non_finite(f64::from(value))
+        } else if self.float_format != 0 {
+            cold_path!();
+            self.serialize_formatted(f64::from(value))
+        } else if let Some(decimals) = self.float_decimals {
+            cold_path!();
+            self.formatter
+                .write_f64(
+                    &mut self.writer,
+                    round_to_decimals(f64::from(value), decimals),
+                )
+                .map_err(Error::io)
         } else {
             self.formatter
                 .write_f32(&mut self.writer, value)
@@ -133,7 +218,15 @@ where
     fn serialize_f64(self, value: f64) -> Result<()> {
         if value.is_infinite() || value.is_nan() {
             cold_path!();
-            self.serialize_unit()
+            self.serialize_non_finite(value)
+        } else if self.float_format != 0 {
+            cold_path!();
+            self.serialize_formatted(value)
+        } else if let Some(decimals) = self.float_decimals {
+            cold_path!();
+            self.formatter
+                .write_f64(&mut self.writer, round_to_decimals(value, decimals))
+                .map_err(Error::io)
         } else {
             self.formatter
                 .write_f64(&mut self.writer, value)
@@ -147,7 +240,36 @@ where
 
     #[inline(always)]
     fn serialize_str(self, value: &str) -> Result<()> {
-        format_escaped_str(&mut self.writer, value);
+        if self.ensure_ascii && !value.is_ascii() {
+            cold_path!();
+            format_escaped_str_ascii(&mut self.writer, value);
+        } else {
+            format_escaped_str(&mut self.writer, value);
+        }
+        Ok(())
+    }
+
+    /// Write `value` as a string as it is formatted, rather than formatting
+    /// it to a `String` first, so that a long value such as base64 is not
+    /// held twice.
+    fn collect_str<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + core::fmt::Display,
+    {
+        reserve_minimum!(self.writer);
+        unsafe {
+            self.writer.put_u8(b'"');
+        }
+        let mut contents = EscapedContents {
+            writer: &mut self.writer,
+            ensure_ascii: self.ensure_ascii,
+        };
+        core::fmt::write(&mut contents, format_args!("{value}"))
+            .map_err(|_| ser::Error::custom("a value could not be formatted"))?;
+        reserve_minimum!(self.writer);
+        unsafe {
+            self.writer.put_u8(b'"');
+        }
         Ok(())
     }
 
@@ -481,8 +603,10 @@ where
         unreachable!();
     }
 
-    fn serialize_bytes(self, _value: &[u8]) -> Result<()> {
-        unreachable!();
+    /// A key that is already quoted and escaped.
+    #[inline(always)]
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.ser.serialize_bytes(value)
     }
 
     fn serialize_unit(self) -> Result<()> {
@@ -562,34 +686,70 @@ where
     }
 }
 
+/// Round `value` to the double nearest a number of at most `decimals`
+/// decimal places, which is then written in at most that many. A value too
+/// large to have that many decimal places is unchanged.
+#[inline(never)]
+fn round_to_decimals(value: f64, decimals: u8) -> f64 {
+    // integers above 2^53 are not all representable
+    const EXACT_LIMIT: f64 = 9007199254740992.0;
+    let scale = 10f64.powi(i32::from(decimals));
+    let scaled = value * scale;
+    if scaled.abs() >= EXACT_LIMIT {
+        value
+    } else {
+        scaled.round() / scale
+    }
+}
+
 /// Reserve space for escaped string output
 #[inline(always)]
 fn reserve_str<W: ?Sized + WriteExt>(writer: &mut W, value: &str) {
     writer.reserve(value.len() * 8 + 32);
 }
 
-/// Format escaped string - x86_64 uses SSE2 implementation
+/// Escape `value` to `dst`, which must have room for `value.len() * 8 + 32`
+/// bytes, and return the number of bytes written.
+///
+/// x86_64 uses the SSE2 implementation.
 #[cfg(target_arch = "x86_64")]
 #[inline(always)]
-fn format_escaped_str<W>(writer: &mut W, value: &str)
-where
-    W: ?Sized + WriteExt + bytes::BufMut,
-{
+unsafe fn format_escaped_str_to_ptr(dst: *mut u8, value: &str) -> usize {
     unsafe {
-        reserve_str(writer, value);
+        crate::serialize::writer::str::format_escaped_str_impl_sse2_128(
+            dst,
+            value.as_bytes().as_ptr(),
+            value.len(),
+        )
+    }
+}
 
-        let written = crate::serialize::writer::str::format_escaped_str_impl_sse2_128(
-            writer.as_mut_buffer_ptr(),
+/// Non-x86_64 with generic SIMD
+#[cfg(all(not(target_arch = "x86_64"), feature = "generic_simd"))]
+#[inline(always)]
+unsafe fn format_escaped_str_to_ptr(dst: *mut u8, value: &str) -> usize {
+    unsafe {
+        crate::serialize::writer::str::format_escaped_str_impl_generic_128(
+            dst,
             value.as_bytes().as_ptr(),
             value.len(),
-        );
+        )
+    }
+}
 
-        writer.advance_mut(written);
+/// Non-x86_64 scalar fallback
+#[cfg(all(not(target_arch = "x86_64"), not(feature = "generic_simd")))]
+#[inline(always)]
+unsafe fn format_escaped_str_to_ptr(dst: *mut u8, value: &str) -> usize {
+    unsafe {
+        crate::serialize::writer::str::format_escaped_str_scalar(
+            dst,
+            value.as_bytes().as_ptr(),
+            value.len(),
+        )
     }
 }
 
-/// Format escaped string - non-x86_64 with generic SIMD
-#[cfg(all(not(target_arch = "x86_64"), feature = "generic_simd"))]
 #[inline(always)]
 fn format_escaped_str<W>(writer: &mut W, value: &str)
 where
@@ -597,53 +757,101 @@ where
 {
     unsafe {
         reserve_str(writer, value);
-
-        let written = crate::serialize::writer::str::format_escaped_str_impl_generic_128(
-            writer.as_mut_buffer_ptr(),
-            value.as_bytes().as_ptr(),
-            value.len(),
-        );
-
+        let written = format_escaped_str_to_ptr(writer.as_mut_buffer_ptr(), value);
         writer.advance_mut(written);
     }
 }
 
-/// Format escaped string - non-x86_64 scalar fallback
-#[cfg(all(not(target_arch = "x86_64"), not(feature = "generic_simd")))]
-#[inline(always)]
-fn format_escaped_str<W>(writer: &mut W, value: &str)
+#[inline(never)]
+fn format_escaped_str_ascii<W>(writer: &mut W, value: &str)
 where
     W: ?Sized + WriteExt + bytes::BufMut,
 {
     unsafe {
         reserve_str(writer, value);
-
-        let written = crate::serialize::writer::str::format_escaped_str_scalar(
+        let written = crate::serialize::writer::str::format_escaped_str_ascii(
             writer.as_mut_buffer_ptr(),
-            value.as_bytes().as_ptr(),
-            value.len(),
+            value,
         );
-
         writer.advance_mut(written);
     }
 }
 
+/// Writes the text formatted by `Serializer::collect_str()` escaped, as the
+/// contents of a string whose quotes it writes.
+struct EscapedContents<'a, W: ?Sized> {
+    writer: &'a mut W,
+    ensure_ascii: bool,
+}
+
+impl<W> core::fmt::Write for EscapedContents<'_, W>
+where
+    W: ?Sized + WriteExt + bytes::BufMut,
+{
+    fn write_str(&mut self, value: &str) -> core::fmt::Result {
+        if value.is_empty() {
+            return Ok(());
+        }
+        unsafe {
+            reserve_str(self.writer, value);
+            let dst = self.writer.as_mut_buffer_ptr();
+            let written = if self.ensure_ascii && !value.is_ascii() {
+                crate::serialize::writer::str::format_escaped_str_ascii(dst, value)
+            } else {
+                format_escaped_str_to_ptr(dst, value)
+            };
+            // the escaped text is written quoted, so drop the quotes
+            core::ptr::copy(dst.add(1), dst, written - 2);
+            self.writer.advance_mut(written - 2);
+        }
+        Ok(())
+    }
+}
+
+/// Append `value` as a quoted, escaped JSON string to `buf`, escaping
+/// non-ASCII codepoints if `ensure_ascii` is set.
+#[inline]
+pub(crate) fn format_escaped_str_to_vec(buf: &mut Vec<u8>, value: &str, ensure_ascii: bool) {
+    buf.reserve(value.len() * 8 + 32);
+    unsafe {
+        let dst = buf.as_mut_ptr().add(buf.len());
+        let written = if ensure_ascii && !value.is_ascii() {
+            crate::serialize::writer::str::format_escaped_str_ascii(dst, value)
+        } else {
+            format_escaped_str_to_ptr(dst, value)
+        };
+        buf.set_len(buf.len() + written);
+    }
+}
+
 #[inline]
-pub(crate) fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+pub(crate) fn to_writer<W, T>(writer: W, value: &T, opts: Opt) -> Result<()>
 where
     W: WriteExt + bytes::BufMut,
     T: ?Sized + Serialize,
 {
-    let mut ser = Serializer::new(writer);
-    value.serialize(&mut ser)
+    match Profile::from_opts(opts) {
+        Profile::V1 => value.serialize(&mut Serializer::new(writer, opts)),
+    }
 }
 
 #[inline]
-pub(crate) fn to_writer_pretty<W, T>(writer: W, value: &T) -> Result<()>
+pub(crate) fn to_writer_pretty<W, T>(
+    writer: W,
+    value: &T,
+    indent: usize,
+    fill: u8,
+    width: usize,
+    depth: usize,
+    opts: Opt,
+) -> Result<()>
 where
     W: WriteExt + bytes::BufMut,
     T: ?Sized + Serialize,
 {
-    let mut ser = Serializer::pretty(writer);
-    value.serialize(&mut ser)
+    match Profile::from_opts(opts) {
+        Profile::V1 => value.serialize(&mut Serializer::pretty(
+            writer, indent, fill, width, depth, opts,
+        )),
+    }
 }
@@ -2,9 +2,16 @@
 // Copyright ijl (2024-2025)
 
 mod byteswriter;
+mod counter;
 mod formatter;
+mod hasher;
 mod json;
+mod msgpack;
+mod profile;
 mod str;
 
 pub(crate) use byteswriter::{BytesWriter, WriteExt};
-pub(crate) use json::{to_writer, to_writer_pretty};
+pub(crate) use counter::LengthCounter;
+pub(crate) use hasher::HashWriter;
+pub(crate) use json::{format_escaped_str_to_vec, to_writer, to_writer_pretty};
+pub(crate) use msgpack::MsgpackSerializer;
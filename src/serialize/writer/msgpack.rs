@@ -0,0 +1,410 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! A MessagePack `Serializer` for the serializers of `serialize/per_type`.
+//!
+//! The length of an array or map is not known when it is begun, so each is
+//! begun with a header of the widest form and the header is rewritten in the
+//! narrowest form that fits when it ends.
+//!
+//! `serialize_bytes()` writes binary data, e.g., `bytes`. Serializers that
+//! write JSON they have already formatted, such as a `Fragment` or a batch of
+//! numbers, instead write values when `is_human_readable()` is false.
+
+use serde::ser::{self, Error as _, Impossible, Serialize};
+use serde_json::error::{Error, Result};
+
+/// The widest array or map header: the marker and a `u32` length.
+const HEADER_LEN: usize = 5;
+
+pub(crate) struct MsgpackSerializer {
+    buf: Vec<u8>,
+    /// The position of the header of each array or map being written and
+    /// the number of values, keys included, written within it.
+    open: Vec<(usize, usize)>,
+}
+
+impl MsgpackSerializer {
+    pub fn new() -> Self {
+        MsgpackSerializer {
+            buf: Vec::with_capacity(1024),
+            open: Vec::new(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    #[inline(always)]
+    fn value(&mut self) {
+        if let Some((_, count)) = self.open.last_mut() {
+            *count += 1;
+        }
+    }
+
+    fn write_nil(&mut self) {
+        self.value();
+        self.buf.push(0xc0);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.value();
+        self.buf.push(if value { 0xc3 } else { 0xc2 });
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.value();
+        if value < 128 {
+            self.buf.push(value as u8);
+        } else if let Ok(value) = u8::try_from(value) {
+            self.buf.extend_from_slice(&[0xcc, value]);
+        } else if let Ok(value) = u16::try_from(value) {
+            self.buf.push(0xcd);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        } else if let Ok(value) = u32::try_from(value) {
+            self.buf.push(0xce);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        } else {
+            self.buf.push(0xcf);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        if let Ok(value) = u64::try_from(value) {
+            return self.write_u64(value);
+        }
+        self.value();
+        if value >= -32 {
+            #[allow(clippy::cast_sign_loss)]
+            self.buf.push(value as i8 as u8);
+        } else if let Ok(value) = i8::try_from(value) {
+            self.buf.extend_from_slice(&[0xd0, value.to_be_bytes()[0]]);
+        } else if let Ok(value) = i16::try_from(value) {
+            self.buf.push(0xd1);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        } else if let Ok(value) = i32::try_from(value) {
+            self.buf.push(0xd2);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        } else {
+            self.buf.push(0xd3);
+            self.buf.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.value();
+        self.buf.push(0xca);
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.value();
+        self.buf.push(0xcb);
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) -> Result<()> {
+        self.value();
+        let len = value.len();
+        if len < 32 {
+            self.buf.push(0xa0 | len as u8);
+        } else if let Ok(len) = u8::try_from(len) {
+            self.buf.extend_from_slice(&[0xd9, len]);
+        } else if let Ok(len) = u16::try_from(len) {
+            self.buf.push(0xda);
+            self.buf.extend_from_slice(&len.to_be_bytes());
+        } else if let Ok(len) = u32::try_from(len) {
+            self.buf.push(0xdb);
+            self.buf.extend_from_slice(&len.to_be_bytes());
+        } else {
+            return Err(Error::custom("str exceeds MessagePack length limit"));
+        }
+        self.buf.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+
+    fn write_bin(&mut self, value: &[u8]) -> Result<()> {
+        self.value();
+        let len = value.len();
+        if let Ok(len) = u8::try_from(len) {
+            self.buf.extend_from_slice(&[0xc4, len]);
+        } else if let Ok(len) = u16::try_from(len) {
+            self.buf.push(0xc5);
+            self.buf.extend_from_slice(&len.to_be_bytes());
+        } else if let Ok(len) = u32::try_from(len) {
+            self.buf.push(0xc6);
+            self.buf.extend_from_slice(&len.to_be_bytes());
+        } else {
+            return Err(Error::custom("bytes exceed MessagePack length limit"));
+        }
+        self.buf.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn begin(&mut self) {
+        self.value();
+        self.open.push((self.buf.len(), 0));
+        self.buf.extend_from_slice(&[0; HEADER_LEN]);
+    }
+
+    /// End the array or map begun last, writing its header.
+    fn end(&mut self, is_map: bool) -> Result<()> {
+        let (start, mut count) = self.open.pop().unwrap();
+        if is_map {
+            count /= 2;
+        }
+        let Ok(len) = u32::try_from(count) else {
+            return Err(Error::custom(
+                "array or map exceeds MessagePack length limit",
+            ));
+        };
+        let (fix, marker16, marker32) = if is_map {
+            (0x80, 0xde, 0xdf)
+        } else {
+            (0x90, 0xdc, 0xdd)
+        };
+        let mut header = [0u8; HEADER_LEN];
+        let header_len = if len < 16 {
+            header[0] = fix | len as u8;
+            1
+        } else if let Ok(len) = u16::try_from(len) {
+            header[0] = marker16;
+            header[1..3].copy_from_slice(&len.to_be_bytes());
+            3
+        } else {
+            header[0] = marker32;
+            header[1..5].copy_from_slice(&len.to_be_bytes());
+            5
+        };
+        self.buf[start..start + header_len].copy_from_slice(&header[..header_len]);
+        if header_len != HEADER_LEN {
+            self.buf.drain(start + header_len..start + HEADER_LEN);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct Compound<'a> {
+    ser: &'a mut MsgpackSerializer,
+}
+
+impl<'a> ser::Serializer for &'a mut MsgpackSerializer {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    #[inline(always)]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<()> {
+        self.write_bool(value);
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<()> {
+        self.write_i64(i64::from(value));
+        Ok(())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<()> {
+        self.write_i64(i64::from(value));
+        Ok(())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<()> {
+        self.write_i64(i64::from(value));
+        Ok(())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<()> {
+        self.write_i64(value);
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<()> {
+        self.write_u64(u64::from(value));
+        Ok(())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<()> {
+        self.write_u64(u64::from(value));
+        Ok(())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<()> {
+        self.write_u64(u64::from(value));
+        Ok(())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<()> {
+        self.write_u64(value);
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<()> {
+        self.write_f32(value);
+        Ok(())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<()> {
+        self.write_f64(value);
+        Ok(())
+    }
+
+    fn serialize_char(self, _value: char) -> Result<()> {
+        unreachable!();
+    }
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.write_str(value)
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.write_bin(value)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write_nil();
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<()> {
+        self.write_str(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        unreachable!();
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        unreachable!();
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        unreachable!();
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.begin();
+        Ok(Compound { ser: self })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        unreachable!();
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        unreachable!();
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        unreachable!();
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.begin();
+        Ok(Compound { ser: self })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        unreachable!();
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        unreachable!();
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.end(false)
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(&mut *self.ser)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.end(true)
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Output profiles: the choices `dumps()` makes that no option selects, such
+//! as how floats are written, which characters of a `str` are escaped, and
+//! the format of `datetime`.
+//!
+//! A profile, once released, does not change. `OPT_COMPAT_V1` selects
+//! `Profile::V1` so that golden files keep matching when the default output
+//! changes. A change to the default output adds a profile that `to_writer()`
+//! and `to_writer_pretty()` map to its own `Formatter`, rather than a check of
+//! an option where the output is written.
+
+use crate::opt::{COMPAT_V1, Opt};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Profile {
+    /// The output of hyperjson 0.1.
+    V1,
+}
+
+/// The profile used without an `OPT_COMPAT_` option.
+pub(crate) const LATEST: Profile = Profile::V1;
+
+impl Profile {
+    #[inline(always)]
+    pub const fn from_opts(opts: Opt) -> Self {
+        if opts & COMPAT_V1 != 0 {
+            Profile::V1
+        } else {
+            LATEST
+        }
+    }
+}
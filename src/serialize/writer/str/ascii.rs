@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+#[inline(always)]
+unsafe fn write_u_escape(dst: *mut u8, unit: u32) -> *mut u8 {
+    unsafe {
+        core::ptr::copy_nonoverlapping(b"\\u".as_ptr(), dst, 2);
+        core::ptr::write(dst.add(2), HEX[((unit >> 12) & 0xf) as usize]);
+        core::ptr::write(dst.add(3), HEX[((unit >> 8) & 0xf) as usize]);
+        core::ptr::write(dst.add(4), HEX[((unit >> 4) & 0xf) as usize]);
+        core::ptr::write(dst.add(5), HEX[(unit & 0xf) as usize]);
+        dst.add(6)
+    }
+}
+
+/// Escape `value` as a quoted JSON string containing only ASCII, writing
+/// codepoints above U+007F as `\uXXXX` and those above U+FFFF as a UTF-16
+/// surrogate pair. Each input byte expands to at most 6 output bytes.
+#[inline(never)]
+pub(crate) unsafe fn format_escaped_str_ascii(odst: *mut u8, value: &str) -> usize {
+    unsafe {
+        let mut dst = odst;
+        core::ptr::write(dst, b'"');
+        dst = dst.add(1);
+
+        let bytes = value.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let byte = *bytes.get_unchecked(idx);
+            if byte < 0x80 {
+                core::ptr::write(dst, byte);
+                dst = dst.add(1);
+                if *super::escape::NEED_ESCAPED.get_unchecked(byte as usize) != 0 {
+                    dst = dst.sub(1);
+                    write_escape!(byte, dst);
+                }
+                idx += 1;
+                continue;
+            }
+            let (codepoint, width) = if byte < 0xe0 {
+                (
+                    (u32::from(byte & 0x1f) << 6) | u32::from(*bytes.get_unchecked(idx + 1) & 0x3f),
+                    2,
+                )
+            } else if byte < 0xf0 {
+                (
+                    (u32::from(byte & 0x0f) << 12)
+                        | (u32::from(*bytes.get_unchecked(idx + 1) & 0x3f) << 6)
+                        | u32::from(*bytes.get_unchecked(idx + 2) & 0x3f),
+                    3,
+                )
+            } else {
+                (
+                    (u32::from(byte & 0x07) << 18)
+                        | (u32::from(*bytes.get_unchecked(idx + 1) & 0x3f) << 12)
+                        | (u32::from(*bytes.get_unchecked(idx + 2) & 0x3f) << 6)
+                        | u32::from(*bytes.get_unchecked(idx + 3) & 0x3f),
+                    4,
+                )
+            };
+            if codepoint > 0xffff {
+                let offset = codepoint - 0x10000;
+                dst = write_u_escape(dst, 0xd800 | (offset >> 10));
+                dst = write_u_escape(dst, 0xdc00 | (offset & 0x3ff));
+            } else {
+                dst = write_u_escape(dst, codepoint);
+            }
+            idx += width;
+        }
+
+        core::ptr::write(dst, b'"');
+        dst = dst.add(1);
+
+        dst as usize - odst as usize
+    }
+}
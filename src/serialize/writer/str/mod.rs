@@ -6,12 +6,16 @@ mod escape;
 #[macro_use]
 mod scalar;
 
+mod ascii;
+
 #[cfg(all(feature = "generic_simd", not(target_arch = "x86_64")))]
 mod generic;
 
 #[cfg(target_arch = "x86_64")]
 mod sse2;
 
+pub(crate) use ascii::format_escaped_str_ascii;
+
 #[cfg(all(not(target_arch = "x86_64"), not(feature = "generic_simd")))]
 pub(crate) use scalar::format_escaped_str_scalar;
 
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+// Copyright ijl (2025)
+
+//! Counts of calls to each entry point and of each `option=` flag passed to
+//! them, for `hyperjson.stats()`.
+//!
+//! Counting is off until `hyperjson.collect_stats(True)`. While off, a call
+//! pays for one relaxed load of the flag. The counts belong to the state of
+//! the interpreter, so `_reset_for_testing()` clears them and turns counting
+//! off again.
+
+use core::ffi::CStr;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::ffi::{
+    Py_DECREF, PyDict_New, PyDict_SetItem, PyLong_FromUnsignedLongLong, PyObject,
+    PyUnicode_InternFromString,
+};
+use crate::opt::{self, Opt};
+
+/// An entry point whose calls are counted.
+#[derive(Clone, Copy)]
+pub(crate) enum Entry {
+    Dumps,
+    DumpsBatch,
+    DumpsLength,
+    Loads,
+    LoadsTracked,
+    LoadsNumpy,
+    VerifyAndLoads,
+    JoinArray,
+    JoinObject,
+    CompareDigestJson,
+    Builder,
+    LinesWriter,
+    Edit,
+    Splice,
+    SchemaLoads,
+    SchemaDumps,
+    MergePatch,
+    Pointer,
+    Equals,
+    Merge3,
+    Flatten,
+    Unflatten,
+    Minify,
+    Prettify,
+    Validate,
+    Hash,
+    DumpsMsgpack,
+    LoadsMsgpack,
+}
+
+const ENTRIES: [&CStr; 28] = [
+    c"dumps",
+    c"dumps_batch",
+    c"dumps_length",
+    c"loads",
+    c"loads_tracked",
+    c"loads_numpy",
+    c"verify_and_loads",
+    c"join_array",
+    c"join_object",
+    c"compare_digest_json",
+    c"Builder",
+    c"LinesWriter",
+    c"edit",
+    c"splice",
+    c"Schema.loads",
+    c"Schema.dumps",
+    c"merge_patch",
+    c"pointer",
+    c"equals",
+    c"merge3",
+    c"flatten",
+    c"unflatten",
+    c"minify",
+    c"prettify",
+    c"validate",
+    c"hash",
+    c"dumps_msgpack",
+    c"loads_msgpack",
+];
+
+impl Entry {
+    pub fn name(self) -> &'static str {
+        ENTRIES[self as usize].to_str().unwrap()
+    }
+}
+
+/// The flags reported, each a single bit. The deprecated flags are 0 and
+/// `OPT_OMIT_EMPTY` is reported as the flags it combines.
+const OPTIONS: [(&CStr, Opt); 29] = [
+    (c"OPT_APPEND_CRLF", opt::APPEND_CRLF),
+    (c"OPT_APPEND_NEWLINE", opt::APPEND_NEWLINE),
+    (c"OPT_BIG_INTEGER", opt::BIG_INTEGER),
+    (c"OPT_BYTES_KEYS_BASE64", opt::BYTES_KEYS_BASE64),
+    (c"OPT_BYTES_KEYS_LATIN1", opt::BYTES_KEYS_LATIN1),
+    (c"OPT_COMPAT_V1", opt::COMPAT_V1),
+    (c"OPT_DEFAULT_PATH", opt::DEFAULT_PATH),
+    (c"OPT_ENSURE_ASCII", opt::ENSURE_ASCII),
+    (c"OPT_INDENT_2", opt::INDENT_2),
+    (c"OPT_INDENT_4", opt::INDENT_4),
+    (c"OPT_INDENT_8", opt::INDENT_8),
+    (c"OPT_INDENT_TAB", opt::INDENT_TAB),
+    (c"OPT_NAIVE_UTC", opt::NAIVE_UTC),
+    (c"OPT_NAMEDTUPLE_AS_OBJECT", opt::NAMEDTUPLE_AS_OBJECT),
+    (c"OPT_NON_STR_KEYS", opt::NON_STR_KEYS),
+    (c"OPT_NULL_UNSUPPORTED", opt::NULL_UNSUPPORTED),
+    (c"OPT_OMIT_EMPTY_DICT", opt::OMIT_EMPTY_DICT),
+    (c"OPT_OMIT_EMPTY_LIST", opt::OMIT_EMPTY_LIST),
+    (c"OPT_OMIT_EMPTY_STR", opt::OMIT_EMPTY_STR),
+    (c"OPT_OMIT_MICROSECONDS", opt::OMIT_MICROSECONDS),
+    (c"OPT_OMIT_NULL", opt::OMIT_NULL),
+    (c"OPT_OMIT_NULL_ON_LOAD", opt::OMIT_NULL_ON_LOAD),
+    (c"OPT_PASSTHROUGH_DATACLASS", opt::PASSTHROUGH_DATACLASS),
+    (c"OPT_PASSTHROUGH_DATETIME", opt::PASSTHROUGH_DATETIME),
+    (c"OPT_PASSTHROUGH_SUBCLASS", opt::PASSTHROUGH_SUBCLASS),
+    (c"OPT_SERIALIZE_NUMPY", opt::SERIALIZE_NUMPY),
+    (c"OPT_SKIP_UNSUPPORTED", opt::SKIP_UNSUPPORTED),
+    (c"OPT_SORT_KEYS", opt::SORT_KEYS),
+    (c"OPT_STRICT_INTEGER", opt::STRICT_INTEGER),
+];
+
+/// Flags that only `option=` sets, as opposed to keyword arguments setting
+/// bits of `Opt`, e.g., `key_order=` setting `SORT_KEYS`.
+const OPTION_BITS: Opt = opt::MAX_OPT as Opt | opt::MAX_LOADS_OPT as Opt;
+
+pub(crate) struct Stats {
+    enabled: AtomicBool,
+    calls: [AtomicU64; ENTRIES.len()],
+    /// Indexed by bit.
+    options: [AtomicU64; Opt::BITS as usize],
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            enabled: AtomicBool::new(false),
+            calls: [const { AtomicU64::new(0) }; ENTRIES.len()],
+            options: [const { AtomicU64::new(0) }; Opt::BITS as usize],
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Turn counting off and zero the counts.
+    #[cold]
+    pub fn clear(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+        self.zero();
+    }
+
+    /// Zero the counts, for `stats(reset=True)`.
+    #[cold]
+    pub fn zero(&self) {
+        for count in self.calls.iter().chain(self.options.iter()) {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Count a call to `entry` with the flags of `option=` in `opts`.
+    #[inline(always)]
+    pub fn record(&self, entry: Entry, opts: Opt) {
+        if self.enabled.load(Ordering::Relaxed) {
+            cold_path!();
+            self.record_slow(entry, opts);
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn record_slow(&self, entry: Entry, opts: Opt) {
+        self.calls[entry as usize].fetch_add(1, Ordering::Relaxed);
+        let mut bits = opts & OPTION_BITS;
+        while bits != 0 {
+            self.options[bits.trailing_zeros() as usize].fetch_add(1, Ordering::Relaxed);
+            bits &= bits - 1;
+        }
+    }
+
+    /// `{"enabled": bool, "calls": {name: int}, "options": {name: int}}`,
+    /// with every entry point and flag present.
+    #[cold]
+    pub fn to_dict(&self) -> *mut PyObject {
+        unsafe {
+            let calls = PyDict_New();
+            for (name, count) in ENTRIES.iter().zip(self.calls.iter()) {
+                set_count(calls, name, count.load(Ordering::Relaxed));
+            }
+            let options = PyDict_New();
+            for (name, bit) in OPTIONS {
+                let count = &self.options[bit.trailing_zeros() as usize];
+                set_count(options, name, count.load(Ordering::Relaxed));
+            }
+            let dict = PyDict_New();
+            let enabled = if self.enabled.load(Ordering::Relaxed) {
+                crate::typeref::true_ptr()
+            } else {
+                crate::typeref::false_ptr()
+            };
+            set_item(dict, c"enabled", enabled);
+            set_item(dict, c"calls", calls);
+            Py_DECREF(calls);
+            set_item(dict, c"options", options);
+            Py_DECREF(options);
+            dict
+        }
+    }
+}
+
+unsafe fn set_item(dict: *mut PyObject, name: &CStr, value: *mut PyObject) {
+    unsafe {
+        let key = PyUnicode_InternFromString(name.as_ptr());
+        PyDict_SetItem(dict, key, value);
+        Py_DECREF(key);
+    }
+}
+
+unsafe fn set_count(dict: *mut PyObject, name: &CStr, count: u64) {
+    unsafe {
+        let value = PyLong_FromUnsignedLongLong(count);
+        set_item(dict, name, value);
+        Py_DECREF(value);
+    }
+}
+
+/// Count a call to `entry` in the current interpreter.
+#[inline(always)]
+pub(crate) fn record(entry: Entry, opts: Opt) {
+    unsafe { (*crate::runtime::current_state()).stats.record(entry, opts) }
+}
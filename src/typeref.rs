@@ -2,14 +2,15 @@
 // Copyright ijl (2018-2025), Aviram Hassan (2020-2021), Nazar Kostetskyi (2022), Ben Sully (2021)
 
 use core::ffi::CStr;
-use core::ptr::{NonNull, null_mut};
-use once_cell::race::OnceBox;
+use core::ptr::null_mut;
+use std::sync::OnceLock;
 
 use crate::ffi::{
     Py_False, Py_None, Py_True, Py_XDECREF, PyBool_Type, PyByteArray_Type, PyBytes_Type,
-    PyDict_Type, PyErr_Clear, PyFloat_Type, PyImport_ImportModule, PyList_Type, PyLong_Type,
-    PyMapping_GetItemString, PyMemoryView_Type, PyObject, PyObject_GenericGetDict, PyTuple_Type,
-    PyTypeObject, PyUnicode_Type,
+    PyDict_GetItemString, PyDict_Type, PyErr_Clear, PyFloat_Type, PyImport_GetModuleDict,
+    PyImport_ImportModule, PyList_Type, PyLong_Type, PyMapping_GetItemString, PyMemoryView_Type,
+    PyObject, PyObject_GenericGetDict, PyObject_GetAttrString, PyTuple_Type, PyTypeObject,
+    PyUnicode_Type,
 };
 
 // ============================================================================
@@ -114,7 +115,7 @@ macro_rules! get_state {
     () => {
         // Inline the state access for better optimization
         unsafe {
-            let state_ptr = crate::interpreter_state::get_current_state();
+            let state_ptr = $crate::runtime::current_state();
             debug_assert!(!state_ptr.is_null());
             &*state_ptr
         }
@@ -124,6 +125,11 @@ macro_rules! get_state {
 use crate::interpreter_state::InterpreterState;
 
 // Accessor functions for per-interpreter values (use direct *_ptr() for built-in types in hot paths)
+#[inline(always)]
+pub(crate) fn get_algo() -> *mut PyObject {
+    unsafe { get_state!().algo }
+}
+
 #[inline(always)]
 pub(crate) fn get_default() -> *mut PyObject {
     unsafe { get_state!().default }
@@ -134,6 +140,221 @@ pub(crate) fn get_option() -> *mut PyObject {
     unsafe { get_state!().option }
 }
 
+#[inline(always)]
+pub(crate) fn get_exc_class() -> *mut PyObject {
+    unsafe { get_state!().exc_class }
+}
+
+#[inline(always)]
+pub(crate) fn get_float_decimals() -> *mut PyObject {
+    unsafe { get_state!().float_decimals }
+}
+
+#[inline(always)]
+pub(crate) fn get_key_order() -> *mut PyObject {
+    unsafe { get_state!().key_order }
+}
+
+#[inline(always)]
+pub(crate) fn get_float_format() -> *mut PyObject {
+    unsafe { get_state!().float_format }
+}
+
+#[inline(always)]
+pub(crate) fn get_non_finite() -> *mut PyObject {
+    unsafe { get_state!().non_finite }
+}
+
+#[inline(always)]
+pub(crate) fn get_length() -> *mut PyObject {
+    unsafe { get_state!().length }
+}
+
+#[inline(always)]
+pub(crate) fn get_allow_nan() -> *mut PyObject {
+    unsafe { get_state!().allow_nan }
+}
+
+#[inline(always)]
+pub(crate) fn get_json5() -> *mut PyObject {
+    unsafe { get_state!().json5 }
+}
+
+#[inline(always)]
+pub(crate) fn get_allow_comments() -> *mut PyObject {
+    unsafe { get_state!().allow_comments }
+}
+
+#[inline(always)]
+pub(crate) fn get_allow_trailing_commas() -> *mut PyObject {
+    unsafe { get_state!().allow_trailing_commas }
+}
+
+#[inline(always)]
+pub(crate) fn get_duplicate_keys() -> *mut PyObject {
+    unsafe { get_state!().duplicate_keys }
+}
+
+#[inline(always)]
+pub(crate) fn get_strict() -> *mut PyObject {
+    unsafe { get_state!().strict }
+}
+
+#[inline(always)]
+pub(crate) fn get_object_pairs_hook() -> *mut PyObject {
+    unsafe { get_state!().object_pairs_hook }
+}
+
+#[inline(always)]
+pub(crate) fn get_parse_float() -> *mut PyObject {
+    unsafe { get_state!().parse_float }
+}
+
+#[inline(always)]
+pub(crate) fn get_parse_int() -> *mut PyObject {
+    unsafe { get_state!().parse_int }
+}
+
+#[inline(always)]
+pub(crate) fn get_use_decimal() -> *mut PyObject {
+    unsafe { get_state!().use_decimal }
+}
+
+#[inline(always)]
+pub(crate) fn get_raw_numbers() -> *mut PyObject {
+    unsafe { get_state!().raw_numbers }
+}
+
+#[inline(always)]
+pub(crate) fn get_int_overflow() -> *mut PyObject {
+    unsafe { get_state!().int_overflow }
+}
+
+#[inline(always)]
+pub(crate) fn get_exact_floats() -> *mut PyObject {
+    unsafe { get_state!().exact_floats }
+}
+
+#[inline(always)]
+pub(crate) fn get_rename() -> *mut PyObject {
+    unsafe { get_state!().rename }
+}
+
+#[inline(always)]
+pub(crate) fn get_validate() -> *mut PyObject {
+    unsafe { get_state!().validate }
+}
+
+#[inline(always)]
+pub(crate) fn get_width() -> *mut PyObject {
+    unsafe { get_state!().width }
+}
+
+#[inline(always)]
+pub(crate) fn get_datetime64_range() -> *mut PyObject {
+    unsafe { get_state!().datetime64_range }
+}
+
+#[inline(always)]
+pub(crate) fn get_field_order() -> *mut PyObject {
+    unsafe { get_state!().field_order }
+}
+
+#[inline(always)]
+pub(crate) fn get_pandas_orient() -> *mut PyObject {
+    unsafe { get_state!().pandas_orient }
+}
+
+#[inline(always)]
+pub(crate) fn get_serialize_arrow() -> *mut PyObject {
+    unsafe { get_state!().serialize_arrow }
+}
+
+#[inline(always)]
+pub(crate) fn get_json_schema() -> *mut PyObject {
+    unsafe { get_state!().json_schema }
+}
+
+#[inline(always)]
+pub(crate) fn get_typ() -> *mut PyObject {
+    unsafe { get_state!().typ }
+}
+
+#[inline(always)]
+pub(crate) fn get_strict_types() -> *mut PyObject {
+    unsafe { get_state!().strict_types }
+}
+
+#[inline(always)]
+pub(crate) fn get_tagged() -> *mut PyObject {
+    unsafe { get_state!().tagged }
+}
+
+#[inline(always)]
+pub(crate) fn get_sep() -> *mut PyObject {
+    unsafe { get_state!().sep }
+}
+
+#[inline(always)]
+pub(crate) fn get_indent() -> *mut PyObject {
+    unsafe { get_state!().indent }
+}
+
+#[inline(always)]
+pub(crate) fn get_algorithm() -> *mut PyObject {
+    unsafe { get_state!().algorithm }
+}
+
+#[inline(always)]
+pub(crate) fn get_extended_json() -> *mut PyObject {
+    unsafe { get_state!().extended_json }
+}
+
+#[inline(always)]
+pub(crate) fn get_sort_keys() -> *mut PyObject {
+    unsafe { get_state!().sort_keys }
+}
+
+#[inline(always)]
+pub(crate) fn get_separators() -> *mut PyObject {
+    unsafe { get_state!().separators }
+}
+
+#[inline(always)]
+pub(crate) fn get_ensure_ascii() -> *mut PyObject {
+    unsafe { get_state!().ensure_ascii }
+}
+
+#[inline(always)]
+pub(crate) fn get_cls() -> *mut PyObject {
+    unsafe { get_state!().cls }
+}
+
+#[inline(always)]
+pub(crate) fn get_object_hook() -> *mut PyObject {
+    unsafe { get_state!().object_hook }
+}
+
+#[inline(always)]
+pub(crate) fn get_encoding() -> *mut PyObject {
+    unsafe { get_state!().encoding }
+}
+
+#[inline(always)]
+pub(crate) fn get_errors() -> *mut PyObject {
+    unsafe { get_state!().errors }
+}
+
+#[inline(always)]
+pub(crate) fn get_type_key_str() -> *mut PyObject {
+    unsafe { get_state!().type_key_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_reset() -> *mut PyObject {
+    unsafe { get_state!().reset }
+}
+
 /// Get None singleton - use `none_ptr()` directly in hot paths
 #[inline(always)]
 pub(crate) fn get_none() -> *mut PyObject {
@@ -161,6 +382,36 @@ pub(crate) fn get_fragment_type() -> *mut PyTypeObject {
     unsafe { get_state!().fragment_type }
 }
 
+#[inline(always)]
+pub(crate) fn get_builder_type() -> *mut PyTypeObject {
+    unsafe { get_state!().builder_type }
+}
+
+#[inline(always)]
+pub(crate) fn get_lines_writer_type() -> *mut PyTypeObject {
+    unsafe { get_state!().lines_writer_type }
+}
+
+#[inline(always)]
+pub(crate) fn get_editor_type() -> *mut PyTypeObject {
+    unsafe { get_state!().editor_type }
+}
+
+#[inline(always)]
+pub(crate) fn get_schema_type() -> *mut PyTypeObject {
+    unsafe { get_state!().schema_type }
+}
+
+#[inline(always)]
+pub(crate) fn get_raw_number_type() -> *mut PyTypeObject {
+    unsafe { get_state!().raw_number_type }
+}
+
+#[inline(always)]
+pub(crate) fn get_write_str() -> *mut PyObject {
+    unsafe { get_state!().write_str }
+}
+
 #[inline(always)]
 pub(crate) fn get_json_encode_error() -> *mut PyObject {
     unsafe { get_state!().json_encode_error }
@@ -200,6 +451,11 @@ pub(crate) fn get_fragment_type_from_state(state: *const InterpreterState) -> *m
     unsafe { (*state).fragment_type }
 }
 
+#[inline(always)]
+pub(crate) fn get_raw_number_type_from_state(state: *const InterpreterState) -> *mut PyTypeObject {
+    unsafe { (*state).raw_number_type }
+}
+
 #[inline(always)]
 pub(crate) fn get_enum_type_from_state(state: *const InterpreterState) -> *mut PyTypeObject {
     unsafe { (*state).enum_type }
@@ -220,6 +476,13 @@ pub(crate) fn get_slots_str_from_state(state: *const InterpreterState) -> *mut P
     unsafe { (*state).slots_str }
 }
 
+#[inline(always)]
+pub(crate) fn get_namedtuple_fields_str_from_state(
+    state: *const InterpreterState,
+) -> *mut PyObject {
+    unsafe { (*state).namedtuple_fields_str }
+}
+
 // Additional accessors for string constants
 #[inline(always)]
 pub(crate) fn get_value_str() -> *mut PyObject {
@@ -284,11 +547,51 @@ pub(crate) fn get_descr_str() -> *mut PyObject {
     unsafe { get_state!().descr_str }
 }
 
+#[inline(always)]
+pub(crate) fn get_mask_str() -> *mut PyObject {
+    unsafe { get_state!().mask_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_items_str() -> *mut PyObject {
+    unsafe { get_state!().items_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_to_numpy_str() -> *mut PyObject {
+    unsafe { get_state!().to_numpy_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_arrow_c_array_str() -> *mut PyObject {
+    unsafe { get_state!().arrow_c_array_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_arrow_c_stream_str() -> *mut PyObject {
+    unsafe { get_state!().arrow_c_stream_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_dlpack_str() -> *mut PyObject {
+    unsafe { get_state!().dlpack_str }
+}
+
+#[inline(always)]
+pub(crate) fn get_array_interface_str() -> *mut PyObject {
+    unsafe { get_state!().array_interface_str }
+}
+
 pub(crate) struct NumpyTypes {
     pub array: *mut PyTypeObject,
+    pub recarray: *mut PyTypeObject,
+    /// `numpy.ma.MaskedArray`, or null if `numpy.ma` cannot be imported.
+    pub masked_array: *mut PyTypeObject,
     pub float64: *mut PyTypeObject,
     pub float32: *mut PyTypeObject,
     pub float16: *mut PyTypeObject,
+    pub complex128: *mut PyTypeObject,
+    pub complex64: *mut PyTypeObject,
     pub int64: *mut PyTypeObject,
     pub int32: *mut PyTypeObject,
     pub int16: *mut PyTypeObject,
@@ -299,10 +602,11 @@ pub(crate) struct NumpyTypes {
     pub uint8: *mut PyTypeObject,
     pub bool_: *mut PyTypeObject,
     pub datetime64: *mut PyTypeObject,
+    pub timedelta64: *mut PyTypeObject,
+    /// `numpy.asarray()`.
+    pub asarray: *mut PyObject,
 }
 
-pub(crate) static mut NUMPY_TYPES: OnceBox<Option<NonNull<NumpyTypes>>> = OnceBox::new();
-
 unsafe fn look_up_numpy_type(
     numpy_module_dict: *mut PyObject,
     np_type: &CStr,
@@ -314,21 +618,37 @@ unsafe fn look_up_numpy_type(
     }
 }
 
+/// Import numpy and look up its types. The pointers are borrowed from the
+/// module, which is never unloaded.
 #[cold]
 #[cfg_attr(feature = "optimize", optimize(size))]
-pub(crate) fn load_numpy_types() -> Box<Option<NonNull<NumpyTypes>>> {
+fn look_up_numpy_types() -> Option<NumpyTypes> {
     unsafe {
         let numpy = PyImport_ImportModule(c"numpy".as_ptr());
         if numpy.is_null() {
             PyErr_Clear();
-            return Box::new(None);
+            return None;
         }
         let numpy_module_dict = PyObject_GenericGetDict(numpy, null_mut());
-        let types = Box::new(NumpyTypes {
+        let mut masked_array = null_mut();
+        let ma = PyObject_GetAttrString(numpy, c"ma".as_ptr());
+        if ma.is_null() {
+            PyErr_Clear();
+        } else {
+            let ma_module_dict = PyObject_GenericGetDict(ma, null_mut());
+            masked_array = look_up_numpy_type(ma_module_dict, c"MaskedArray");
+            Py_XDECREF(ma_module_dict);
+            Py_XDECREF(ma);
+        }
+        let types = NumpyTypes {
             array: look_up_numpy_type(numpy_module_dict, c"ndarray"),
+            recarray: look_up_numpy_type(numpy_module_dict, c"recarray"),
+            masked_array: masked_array,
             float16: look_up_numpy_type(numpy_module_dict, c"half"),
             float32: look_up_numpy_type(numpy_module_dict, c"float32"),
             float64: look_up_numpy_type(numpy_module_dict, c"float64"),
+            complex64: look_up_numpy_type(numpy_module_dict, c"complex64"),
+            complex128: look_up_numpy_type(numpy_module_dict, c"complex128"),
             int8: look_up_numpy_type(numpy_module_dict, c"int8"),
             int16: look_up_numpy_type(numpy_module_dict, c"int16"),
             int32: look_up_numpy_type(numpy_module_dict, c"int32"),
@@ -339,9 +659,192 @@ pub(crate) fn load_numpy_types() -> Box<Option<NonNull<NumpyTypes>>> {
             uint8: look_up_numpy_type(numpy_module_dict, c"uint8"),
             bool_: look_up_numpy_type(numpy_module_dict, c"bool_"),
             datetime64: look_up_numpy_type(numpy_module_dict, c"datetime64"),
-        });
+            timedelta64: look_up_numpy_type(numpy_module_dict, c"timedelta64"),
+            asarray: look_up_numpy_type(numpy_module_dict, c"asarray").cast::<PyObject>(),
+        };
         Py_XDECREF(numpy_module_dict);
         Py_XDECREF(numpy);
-        Box::new(Some(nonnull!(Box::<NumpyTypes>::into_raw(types))))
+        Some(types)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn load_numpy_types(cell: &OnceLock<Option<NumpyTypes>>) -> Option<&NumpyTypes> {
+    // The import runs before, not inside, the initialization of the cell. A
+    // thread blocked on the cell while attached to the interpreter would keep
+    // the importing thread from taking the GIL back or, without the GIL,
+    // from completing a stop-the-world pause. Threads that race here each
+    // look the types up and all but the first discard theirs, which holds
+    // no references.
+    let types = look_up_numpy_types();
+    let _ = cell.set(types);
+    cell.get().and_then(Option::as_ref)
+}
+
+/// Return the numpy types of the interpreter, or `None` if numpy is not
+/// installed.
+#[inline]
+pub(crate) fn get_numpy_types_from_state(
+    state: *const InterpreterState,
+) -> Option<&'static NumpyTypes> {
+    let cell = unsafe { &(*state).numpy_types };
+    match cell.get() {
+        Some(types) => types.as_ref(),
+        None => load_numpy_types(cell),
+    }
+}
+
+/// Whether numpy has been imported in the interpreter, so that its types are
+/// looked up without importing it for an object that cannot be numpy's.
+#[cold]
+pub(crate) fn numpy_is_imported(state: *const InterpreterState) -> bool {
+    let cell = unsafe { &(*state).numpy_types };
+    match cell.get() {
+        Some(types) => types.is_some(),
+        None => unsafe {
+            !PyDict_GetItemString(PyImport_GetModuleDict(), c"numpy".as_ptr()).is_null()
+        },
+    }
+}
+
+pub(crate) struct PandasTypes {
+    pub dataframe: *mut PyTypeObject,
+    pub series: *mut PyTypeObject,
+}
+
+/// Import pandas and look up its types, borrowed from the module as the
+/// numpy types are.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn look_up_pandas_types() -> Option<PandasTypes> {
+    unsafe {
+        let pandas = PyImport_ImportModule(c"pandas".as_ptr());
+        if pandas.is_null() {
+            PyErr_Clear();
+            return None;
+        }
+        let pandas_module_dict = PyObject_GenericGetDict(pandas, null_mut());
+        let types = PandasTypes {
+            dataframe: look_up_numpy_type(pandas_module_dict, c"DataFrame"),
+            series: look_up_numpy_type(pandas_module_dict, c"Series"),
+        };
+        Py_XDECREF(pandas_module_dict);
+        Py_XDECREF(pandas);
+        if types.dataframe.is_null() || types.series.is_null() {
+            PyErr_Clear();
+            return None;
+        }
+        Some(types)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn load_pandas_types(cell: &OnceLock<Option<PandasTypes>>) -> Option<&PandasTypes> {
+    // imported before the initialization of the cell for the reason given
+    // in load_numpy_types()
+    let types = look_up_pandas_types();
+    let _ = cell.set(types);
+    cell.get().and_then(Option::as_ref)
+}
+
+/// Return the pandas types of the interpreter, or `None` if pandas is not
+/// installed.
+#[inline]
+pub(crate) fn get_pandas_types_from_state(
+    state: *const InterpreterState,
+) -> Option<&'static PandasTypes> {
+    let cell = unsafe { &(*state).pandas_types };
+    match cell.get() {
+        Some(types) => types.as_ref(),
+        None => load_pandas_types(cell),
+    }
+}
+
+/// Import decimal and look up `Decimal`, borrowed from the module as the
+/// numpy types are. Null if decimal cannot be imported.
+#[cold]
+#[inline(never)]
+fn look_up_decimal_type() -> *mut PyObject {
+    unsafe {
+        let decimal = PyImport_ImportModule(c"decimal".as_ptr());
+        if decimal.is_null() {
+            PyErr_Clear();
+            return null_mut();
+        }
+        let decimal_module_dict = PyObject_GenericGetDict(decimal, null_mut());
+        let ptr = PyMapping_GetItemString(decimal_module_dict, c"Decimal".as_ptr());
+        if ptr.is_null() {
+            PyErr_Clear();
+        }
+        Py_XDECREF(ptr);
+        Py_XDECREF(decimal_module_dict);
+        Py_XDECREF(decimal);
+        ptr
+    }
+}
+
+/// Return `decimal.Decimal`, or null if decimal cannot be imported.
+#[inline]
+pub(crate) fn get_decimal_type() -> *mut PyObject {
+    let cell = unsafe { &get_state!().decimal_type };
+    match cell.get() {
+        Some(ptr) => *ptr,
+        None => {
+            // imported outside of the initialization of the cell as numpy is
+            let ptr = look_up_decimal_type();
+            let _ = cell.set(ptr);
+            *cell.get().unwrap()
+        }
+    }
+}
+
+/// Import json and look up `name`, borrowed from the module as `Decimal` is.
+/// Null if json cannot be imported.
+#[cold]
+#[inline(never)]
+fn look_up_json_type(name: &CStr) -> *mut PyObject {
+    unsafe {
+        let json = PyImport_ImportModule(c"json".as_ptr());
+        if json.is_null() {
+            PyErr_Clear();
+            return null_mut();
+        }
+        let ptr = PyObject_GetAttrString(json, name.as_ptr());
+        if ptr.is_null() {
+            PyErr_Clear();
+        }
+        Py_XDECREF(ptr);
+        Py_XDECREF(json);
+        ptr
+    }
+}
+
+/// Return `json.JSONEncoder`, or null if json cannot be imported.
+#[cold]
+pub(crate) fn get_json_encoder_type() -> *mut PyObject {
+    let cell = unsafe { &get_state!().json_encoder_type };
+    match cell.get() {
+        Some(ptr) => *ptr,
+        None => {
+            let ptr = look_up_json_type(c"JSONEncoder");
+            let _ = cell.set(ptr);
+            *cell.get().unwrap()
+        }
+    }
+}
+
+/// Return `json.JSONDecoder`, or null if json cannot be imported.
+#[cold]
+pub(crate) fn get_json_decoder_type() -> *mut PyObject {
+    let cell = unsafe { &get_state!().json_decoder_type };
+    match cell.get() {
+        Some(ptr) => *ptr,
+        None => {
+            let ptr = look_up_json_type(c"JSONDecoder");
+            let _ = cell.set(ptr);
+            *cell.get().unwrap()
+        }
     }
 }
@@ -247,6 +247,22 @@ macro_rules! use_immortal {
     };
 }
 
+/// Release a reference to `None`, `True`, or `False` obtained from
+/// `use_immortal!`.
+#[cfg(Py_3_12)]
+macro_rules! release_immortal {
+    ($op:expr) => {
+        let _ = $op;
+    };
+}
+
+#[cfg(not(Py_3_12))]
+macro_rules! release_immortal {
+    ($op:expr) => {
+        ffi!(Py_DECREF($op))
+    };
+}
+
 #[cfg(all(CPython, not(Py_3_13)))]
 macro_rules! pydict_next {
     ($obj1:expr, $obj2:expr, $obj3:expr, $obj4:expr) => {
@@ -268,6 +284,30 @@ macro_rules! pydict_next {
     };
 }
 
+/// `pydict_next!`, or `PyDict_Next()` when `$compat` is set by
+/// `set_compat_mode()`.
+macro_rules! pydict_next_compat {
+    ($compat:expr, $obj1:expr, $obj2:expr, $obj3:expr, $obj4:expr) => {
+        if $compat {
+            unsafe { crate::ffi::PyDict_Next($obj1, $obj2, $obj3, $obj4) }
+        } else {
+            pydict_next!($obj1, $obj2, $obj3, $obj4)
+        }
+    };
+}
+
+/// The length of a dict, with `PyDict_Size()` when `$compat` is set by
+/// `set_compat_mode()`.
+macro_rules! pydict_len {
+    ($compat:expr, $dict:expr) => {
+        if $compat {
+            ffi!(PyDict_Size($dict))
+        } else {
+            ffi!(Py_SIZE($dict))
+        }
+    };
+}
+
 #[cfg(CPython)]
 macro_rules! pydict_setitem {
     ($dict:expr, $pykey:expr, $pyval:expr) => {
@@ -332,6 +372,22 @@ macro_rules! unreachable_unchecked {
     };
 }
 
+/// FNV-1a 64-bit hash - simple, fast, good distribution for short strings
+/// This is significantly faster than xxhash for short strings (< 64 bytes)
+/// because it has no setup cost and is branch-free
+#[inline(always)]
+pub(crate) fn fnv1a_hash(data: &[u8]) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET;
+    for &byte in data {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
 #[inline(always)]
 #[allow(clippy::cast_possible_wrap)]
 pub(crate) fn usize_to_isize(val: usize) -> isize {
@@ -345,3 +401,21 @@ pub(crate) fn isize_to_usize(val: isize) -> usize {
     debug_assert!(val >= 0);
     val as usize
 }
+
+/// Compare every byte of `right` against `left`, as `hmac.compare_digest()`
+/// does, so that the time taken does not depend on where they differ.
+#[inline(never)]
+pub(crate) fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    let mut diff = u8::from(left.len() != right.len());
+    // compare right against itself if the lengths differ so that the loop
+    // is the same
+    let other = if left.len() == right.len() {
+        left
+    } else {
+        right
+    };
+    for (lhs, rhs) in other.iter().zip(right.iter()) {
+        diff |= core::hint::black_box(lhs ^ rhs);
+    }
+    core::hint::black_box(diff) == 0
+}
@@ -171,7 +171,7 @@ class TestApi:
         dumps() option out of range high
         """
         with pytest.raises(hyperjson.JSONEncodeError):
-            hyperjson.dumps(True, option=1 << 12)
+            hyperjson.dumps(True, option=1 << 24)
 
     def test_opts_multiple(self):
         """
@@ -248,10 +248,34 @@ class TestApi:
         """
         dumps() valid __text_signature__
         """
-        assert (
-            str(inspect.signature(hyperjson.dumps))
-            == "(obj, /, default=None, option=None)"
+        assert str(inspect.signature(hyperjson.dumps)).startswith(
+            "(obj, /, default=None, option=None, *, "
         )
+        assert [
+            param.name
+            for param in inspect.signature(hyperjson.dumps).parameters.values()
+            if param.kind == inspect.Parameter.KEYWORD_ONLY
+        ] == [
+            "exc_class",
+            "length",
+            "float_decimals",
+            "float_format",
+            "key_order",
+            "non_finite",
+            "width",
+            "datetime64_range",
+            "field_order",
+            "pandas_orient",
+            "serialize_arrow",
+            "json_schema",
+            "tagged",
+            "extended_json",
+            "sort_keys",
+            "ensure_ascii",
+            "indent",
+            "separators",
+            "cls",
+        ]
         inspect.signature(hyperjson.dumps).bind("str")
         inspect.signature(hyperjson.dumps).bind("str", default=default, option=1)
         inspect.signature(hyperjson.dumps).bind("str", default=None, option=None)
@@ -260,7 +284,19 @@ class TestApi:
         """
         loads() valid __text_signature__
         """
-        assert str(inspect.signature(hyperjson.loads)), "(obj == /)"
+        assert str(inspect.signature(hyperjson.loads)).startswith("(obj, /, *, ")
+        params = inspect.signature(hyperjson.loads).parameters
+        for name in (
+            "type",
+            "strict_types",
+            "tagged",
+            "extended_json",
+            "object_hook",
+            "cls",
+            "encoding",
+            "errors",
+        ):
+            assert params[name].kind == inspect.Parameter.KEYWORD_ONLY
         inspect.signature(hyperjson.loads).bind("[]")
 
     def test_dumps_module_str(self):
@@ -13,6 +13,65 @@ class TestAppendNewline:
         """
         assert hyperjson.dumps([], option=hyperjson.OPT_APPEND_NEWLINE) == b"[]\n"
 
+    def test_dumps_crlf(self):
+        """
+        dumps() OPT_APPEND_CRLF
+        """
+        assert hyperjson.dumps([], option=hyperjson.OPT_APPEND_CRLF) == b"[]\r\n"
+
+    def test_dumps_crlf_and_newline(self):
+        """
+        dumps() OPT_APPEND_CRLF takes precedence over OPT_APPEND_NEWLINE
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": 1},
+                option=hyperjson.OPT_APPEND_CRLF | hyperjson.OPT_APPEND_NEWLINE,
+            )
+            == b'{"a":1}\r\n'
+        )
+
+    def test_dumps_crlf_indent(self):
+        """
+        dumps() OPT_APPEND_CRLF and OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [1]},
+                option=hyperjson.OPT_APPEND_CRLF | hyperjson.OPT_INDENT_2,
+            )
+            == b'{\n  "a": [\n    1\n  ]\n}\r\n'
+        )
+
+    def test_dumps_crlf_buffer_boundary(self):
+        """
+        dumps() OPT_APPEND_CRLF with output filling the initial buffer
+        """
+        for length in range(1000, 1040):
+            val = "a" * length
+            assert (
+                hyperjson.dumps(val, option=hyperjson.OPT_APPEND_CRLF)
+                == b'"' + val.encode("utf-8") + b'"\r\n'
+            )
+
+    def test_dumps_crlf_records(self):
+        """
+        dumps() OPT_APPEND_CRLF records are split by bytes.splitlines()
+        """
+        records = [{"idx": idx} for idx in range(10)]
+        output = b"".join(
+            hyperjson.dumps(record, option=hyperjson.OPT_APPEND_CRLF)
+            for record in records
+        )
+        assert [hyperjson.loads(line) for line in output.splitlines()] == records
+
+    def test_loads_crlf(self):
+        """
+        loads() OPT_APPEND_CRLF output
+        """
+        val = {"a": [1, 2.5, "b", None]}
+        assert hyperjson.loads(hyperjson.dumps(val, option=hyperjson.OPT_APPEND_CRLF)) == val
+
     @needs_data
     def test_twitter_newline(self):
         """
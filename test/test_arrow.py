@@ -0,0 +1,156 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+
+import pytest
+
+import hyperjson
+
+from .util import pyarrow
+
+
+@pytest.mark.skipif(pyarrow is None, reason="pyarrow is not installed")
+class TestArrow:
+    def table(self):
+        return pyarrow.table(
+            {
+                "int": [1, None, 3],
+                "float": [1.5, 2.0, -0.25],
+                "bool": [True, False, None],
+                "str": ["a", None, "é"],
+            }
+        )
+
+    def test_arrow_table(self):
+        """
+        dumps() serialize_arrow=True writes a Table as an array of an object
+        per row
+        """
+        assert hyperjson.dumps(self.table(), serialize_arrow=True) == (
+            b'[{"int":1,"float":1.5,"bool":true,"str":"a"},'
+            b'{"int":null,"float":2.0,"bool":false,"str":null},'
+            b'{"int":3,"float":-0.25,"bool":null,"str":"\xc3\xa9"}]'
+        )
+
+    def test_arrow_record_batch(self):
+        """
+        dumps() serialize_arrow=True writes a RecordBatch as a Table
+        """
+        table = self.table()
+        for batch in table.to_batches():
+            assert hyperjson.dumps(batch, serialize_arrow=True) == hyperjson.dumps(
+                batch.to_pylist()
+            )
+
+    def test_arrow_table_batches(self):
+        """
+        dumps() serialize_arrow=True writes the rows of each batch of a Table
+        """
+        table = pyarrow.concat_tables([self.table(), self.table().slice(1)])
+        assert len(table.to_batches()) == 2
+        assert hyperjson.dumps(table, serialize_arrow=True) == hyperjson.dumps(
+            table.to_pylist()
+        )
+
+    def test_arrow_array(self):
+        """
+        dumps() serialize_arrow=True writes an Array and a ChunkedArray as an
+        array of values
+        """
+        assert (
+            hyperjson.dumps(pyarrow.array([1, None, 3]), serialize_arrow=True)
+            == b"[1,null,3]"
+        )
+        assert (
+            hyperjson.dumps(
+                pyarrow.chunked_array([[1.5], [], [None, 2.5]]), serialize_arrow=True
+            )
+            == b"[1.5,null,2.5]"
+        )
+
+    def test_arrow_slice(self):
+        """
+        dumps() serialize_arrow=True applies the offset of a slice
+        """
+        array = pyarrow.array(["a", None, "c", "d"]).slice(1, 2)
+        assert hyperjson.dumps(array, serialize_arrow=True) == b'[null,"c"]'
+        table = self.table().slice(2)
+        assert hyperjson.dumps(table, serialize_arrow=True) == hyperjson.dumps(
+            table.to_pylist()
+        )
+
+    def test_arrow_nested(self):
+        """
+        dumps() serialize_arrow=True writes list, struct, and dictionary types
+        """
+        array = pyarrow.array(
+            [{"a": [1, 2], "b": "x"}, None, {"a": [], "b": None}],
+        )
+        assert (
+            hyperjson.dumps(array, serialize_arrow=True)
+            == b'[{"a":[1,2],"b":"x"},null,{"a":[],"b":null}]'
+        )
+        array = pyarrow.array(["lo", "hi", "lo", None]).dictionary_encode()
+        assert (
+            hyperjson.dumps(array, serialize_arrow=True) == b'["lo","hi","lo",null]'
+        )
+
+    def test_arrow_temporal(self):
+        """
+        dumps() serialize_arrow=True writes dates, naive timestamps, and
+        durations as datetime.date and numpy.datetime64 and timedelta64
+        """
+        table = pyarrow.table(
+            {
+                "date": pyarrow.array([datetime.date(2025, 1, 2)]),
+                "ts": pyarrow.array(
+                    [datetime.datetime(2025, 1, 2, 3, 4, 5, 6)],
+                    pyarrow.timestamp("us"),
+                ),
+                "td": pyarrow.array(
+                    [datetime.timedelta(days=1, seconds=1)], pyarrow.duration("s")
+                ),
+            }
+        )
+        assert hyperjson.dumps(table, serialize_arrow=True) == (
+            b'[{"date":"2025-01-02","ts":"2025-01-02T03:04:05.000006",'
+            b'"td":"P1DT1S"}]'
+        )
+
+    def test_arrow_unsupported(self):
+        """
+        dumps() serialize_arrow=True passes data with an unsupported type to
+        default=, and raises without it
+        """
+        array = pyarrow.array([b"a"])
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(array, serialize_arrow=True)
+        assert str(exc.value) == "unsupported datatype in arrow data"
+        assert (
+            hyperjson.dumps(array, serialize_arrow=True, default=lambda x: x.to_pylist())
+            == b'["a"]'
+        )
+
+    def test_arrow_disabled(self):
+        """
+        dumps() passes Arrow data to default= without serialize_arrow=True
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(self.table())
+        assert hyperjson.dumps(
+            pyarrow.array([1]), default=lambda x: x.to_pylist()
+        ) == b"[1]"
+
+
+class TestSerializeArrow:
+    def test_serialize_arrow_invalid(self):
+        """
+        dumps() serialize_arrow must be a bool
+        """
+        for val in (1, None, "true"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps([], serialize_arrow=val)  # type: ignore
+            assert str(exc.value) == "dumps() serialize_arrow must be a bool"
+        assert hyperjson.dumps([1], serialize_arrow=False) == b"[1]"
+        assert hyperjson.dumps_length([1], serialize_arrow=True) == 3
@@ -0,0 +1,108 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+import sys
+
+import pytest
+
+import hyperjson
+
+
+class SubInt(int):
+    def __str__(self):
+        return "subclass"
+
+    def __repr__(self):
+        return "subclass"
+
+
+class TestBigInteger:
+    def test_big_integer(self):
+        """
+        OPT_BIG_INTEGER writes an int outside of 64 bits as its digits
+        """
+        for val in (
+            18446744073709551616,
+            -9223372036854775809,
+            2**200,
+            -(3**150),
+        ):
+            assert hyperjson.dumps(val, option=hyperjson.OPT_BIG_INTEGER) == str(
+                val
+            ).encode("utf-8")
+
+    def test_big_integer_roundtrip(self):
+        """
+        OPT_BIG_INTEGER output is read by json as the same int
+        """
+        val = {"a": [2**100, 1, -(2**64)], "b": 10**50}
+        assert json.loads(hyperjson.dumps(val, option=hyperjson.OPT_BIG_INTEGER)) == val
+
+    def test_big_integer_64(self):
+        """
+        OPT_BIG_INTEGER does not change an int within 64 bits
+        """
+        val = [0, -1, 18446744073709551615, -9223372036854775808]
+        assert hyperjson.dumps(val, option=hyperjson.OPT_BIG_INTEGER) == hyperjson.dumps(
+            val
+        )
+
+    def test_big_integer_default(self):
+        """
+        an int outside of 64 bits raises without OPT_BIG_INTEGER
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(2**64)
+        assert str(exc.value) == "Integer exceeds 64-bit range"
+
+    def test_big_integer_strict(self):
+        """
+        OPT_STRICT_INTEGER takes precedence over OPT_BIG_INTEGER
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(
+                2**64,
+                option=hyperjson.OPT_BIG_INTEGER | hyperjson.OPT_STRICT_INTEGER,
+            )
+        assert str(exc.value) == "Integer exceeds 53-bit range"
+
+    def test_big_integer_subclass(self):
+        """
+        OPT_BIG_INTEGER writes the value of an int subclass, not its str()
+        """
+        assert (
+            hyperjson.dumps(SubInt(2**70), option=hyperjson.OPT_BIG_INTEGER)
+            == b"1180591620717411303424"
+        )
+
+    def test_big_integer_indent(self):
+        """
+        OPT_BIG_INTEGER with OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [2**64]},
+                option=hyperjson.OPT_BIG_INTEGER | hyperjson.OPT_INDENT_2,
+            )
+            == b'{\n  "a": [\n    18446744073709551616\n  ]\n}'
+        )
+
+    @pytest.mark.skipif(
+        not hasattr(sys, "set_int_max_str_digits"),
+        reason="no limit on int string conversion",
+    )
+    def test_big_integer_max_str_digits(self):
+        """
+        OPT_BIG_INTEGER raises JSONEncodeError for an int with more digits
+        than sys.get_int_max_str_digits() allows
+        """
+        previous = sys.get_int_max_str_digits()
+        sys.set_int_max_str_digits(1000)
+        try:
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps(10**2000, option=hyperjson.OPT_BIG_INTEGER)
+            assert "sys.set_int_max_str_digits()" in str(exc.value)
+            assert exc.value.__cause__ is None
+        finally:
+            sys.set_int_max_str_digits(previous)
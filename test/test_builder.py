@@ -0,0 +1,351 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+import gc
+import weakref
+
+import pytest
+
+import hyperjson
+
+
+class TestBuilder:
+    def test_builder_object(self):
+        """
+        Builder object
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        builder.key("a")
+        builder.value(1)
+        builder.key("b")
+        builder.value([1, "2", None])
+        builder.end_object()
+        assert builder.finish() == b'{"a":1,"b":[1,"2",null]}'
+
+    def test_builder_array(self):
+        """
+        Builder array
+        """
+        builder = hyperjson.Builder()
+        builder.start_array()
+        for i in range(3):
+            builder.value(i)
+        builder.end_array()
+        assert builder.finish() == b"[0,1,2]"
+
+    def test_builder_empty_containers(self):
+        """
+        Builder empty object and array
+        """
+        builder = hyperjson.Builder()
+        builder.start_array()
+        builder.start_object()
+        builder.end_object()
+        builder.start_array()
+        builder.end_array()
+        builder.end_array()
+        assert builder.finish() == b"[{},[]]"
+
+    def test_builder_nested(self):
+        """
+        Builder nested containers
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        builder.key("rows")
+        builder.start_array()
+        for i in range(2):
+            builder.start_object()
+            builder.key("id")
+            builder.value(i)
+            builder.end_object()
+        builder.end_array()
+        builder.key("total")
+        builder.value(2)
+        builder.end_object()
+        assert builder.finish() == b'{"rows":[{"id":0},{"id":1}],"total":2}'
+
+    def test_builder_matches_dumps(self):
+        """
+        Builder output equals dumps() of the equivalent object
+        """
+        obj = {f"key{i}": {"value": [i, str(i), "🐈"]} for i in range(500)}
+        builder = hyperjson.Builder()
+        builder.start_object()
+        for key, value in obj.items():
+            builder.key(key)
+            builder.value(value)
+        builder.end_object()
+        assert builder.finish() == hyperjson.dumps(obj)
+
+    def test_builder_scalar(self):
+        """
+        Builder top-level scalar
+        """
+        builder = hyperjson.Builder()
+        builder.value("a")
+        assert builder.finish() == b'"a"'
+
+    def test_builder_key_escaped(self):
+        """
+        Builder key() escapes
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        builder.key('a"\n')
+        builder.value(1)
+        builder.end_object()
+        assert builder.finish() == b'{"a\\"\\n":1}'
+
+    def test_builder_fragment(self):
+        """
+        Builder value() Fragment
+        """
+        builder = hyperjson.Builder()
+        builder.start_array()
+        builder.value(hyperjson.Fragment(b'{"cached":true}'))
+        builder.end_array()
+        assert builder.finish() == b'[{"cached":true}]'
+
+    def test_builder_option(self):
+        """
+        Builder option
+        """
+        builder = hyperjson.Builder(option=hyperjson.OPT_UTC_Z | hyperjson.OPT_NAIVE_UTC)
+        builder.value(datetime.datetime(2000, 1, 1))
+        assert builder.finish() == b'"2000-01-01T00:00:00Z"'
+
+    def test_builder_default(self):
+        """
+        Builder default
+        """
+
+        class Custom:
+            pass
+
+        builder = hyperjson.Builder(default=lambda _: "custom")
+        builder.start_array()
+        builder.value(Custom())
+        builder.end_array()
+        assert builder.finish() == b'["custom"]'
+
+    def test_builder_default_positional(self):
+        """
+        Builder default and option positional
+        """
+        builder = hyperjson.Builder(str, None)
+        builder.value(object())
+        assert builder.finish().startswith(b'"<object object')
+
+    def test_builder_invalid_opts(self):
+        """
+        Builder invalid option
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(option=-1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(option="a")  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(option=hyperjson.OPT_INDENT_2)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(option=hyperjson.OPT_INDENT_4)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(option=hyperjson.OPT_INDENT_TAB)
+
+    def test_builder_invalid_args(self):
+        """
+        Builder invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(None, None, None)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(foo=1)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Builder(None, default=None)  # type: ignore
+
+    def test_builder_value_error_rolls_back(self):
+        """
+        Builder value() that fails to serialize writes nothing
+        """
+        builder = hyperjson.Builder()
+        builder.start_array()
+        builder.value(1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.value([2, object()])
+        builder.value(3)
+        builder.end_array()
+        assert builder.finish() == b"[1,3]"
+
+    def test_builder_value_in_object_requires_key(self):
+        """
+        Builder value() inside object without key()
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.value(1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.start_array()
+
+    def test_builder_key_outside_object(self):
+        """
+        Builder key() outside object
+        """
+        builder = hyperjson.Builder()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.key("a")
+        builder.start_array()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.key("a")
+
+    def test_builder_key_twice(self):
+        """
+        Builder key() twice
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        builder.key("a")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.key("b")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.end_object()
+
+    def test_builder_key_not_str(self):
+        """
+        Builder key() must be str
+        """
+        builder = hyperjson.Builder()
+        builder.start_object()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.key(1)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.key("\ud800")
+
+    def test_builder_mismatched_end(self):
+        """
+        Builder end_object() and end_array() mismatched
+        """
+        builder = hyperjson.Builder()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.end_array()
+        builder.start_array()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.end_object()
+        builder.end_array()
+        assert builder.finish() == b"[]"
+
+    def test_builder_incomplete(self):
+        """
+        Builder finish() with open containers
+        """
+        builder = hyperjson.Builder()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.finish()
+        builder.start_array()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.finish()
+
+    def test_builder_complete(self):
+        """
+        Builder writing after a complete document
+        """
+        builder = hyperjson.Builder()
+        builder.value(1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.value(2)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.start_object()
+        assert builder.finish() == b"1"
+
+    def test_builder_reuse(self):
+        """
+        Builder can be reused after finish()
+        """
+        builder = hyperjson.Builder()
+        builder.value(1)
+        assert builder.finish() == b"1"
+        builder.value(2)
+        assert builder.finish() == b"2"
+
+    def test_builder_dealloc_unfinished(self):
+        """
+        Builder dealloc with a partial document
+        """
+        builder = hyperjson.Builder(default=str)
+        builder.start_array()
+        builder.value("a" * 4096)
+        del builder
+
+    def test_builder_reentrant(self):
+        """
+        Builder methods called from default while value() serializes raise
+        """
+        builder = None
+        calls = []
+
+        def default(obj):
+            for call in (
+                lambda: builder.value("y" * 5000),
+                lambda: builder.key("k"),
+                builder.start_array,
+                builder.end_array,
+                builder.finish,
+            ):
+                with pytest.raises(hyperjson.JSONEncodeError):
+                    call()
+                calls.append(call)
+            return "x"
+
+        builder = hyperjson.Builder(default=default)
+        builder.start_array()
+        builder.value(["a" * 10])
+        builder.value(object())
+        builder.value([object(), 1])
+        builder.end_array()
+        assert builder.finish() == b'[["aaaaaaaaaa"],"x",["x",1]]'
+        assert len(calls) == 10
+
+    def test_builder_reentrant_error(self):
+        """
+        Builder is usable after default raised from a reentrant call
+        """
+        builder = None
+
+        def default(obj):
+            builder.value(1)
+
+        builder = hyperjson.Builder(default=default)
+        builder.start_array()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            builder.value(object())
+        builder.value(2)
+        builder.end_array()
+        assert builder.finish() == b"[2]"
+
+    def test_builder_cycle_collected(self):
+        """
+        Builder referred to by its default is freed by the garbage collector
+        """
+
+        class Default:
+            def __call__(self, obj):
+                return str(obj)
+
+        default = Default()
+        builder = hyperjson.Builder(default=default)
+        default.builder = builder
+        ref = weakref.ref(default)
+        del builder, default
+        gc.collect()
+        assert ref() is None
+
+    def test_builder_not_subclassable(self):
+        """
+        Builder cannot be subclassed
+        """
+        with pytest.raises(TypeError):
+
+            class Sub(hyperjson.Builder):  # type: ignore
+                pass
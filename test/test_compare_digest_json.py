@@ -0,0 +1,60 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestCompareDigestJson:
+    def test_compare_digest_json_equal(self):
+        """
+        compare_digest_json() of equal documents
+        """
+        assert hyperjson.compare_digest_json(
+            {"event": "push", "ref": "main", "commits": [{"id": 1}]},
+            {"commits": [{"id": 1}], "ref": "main", "event": "push"},
+        )
+        assert hyperjson.compare_digest_json([], [])
+        assert hyperjson.compare_digest_json(None, None)
+
+    def test_compare_digest_json_not_equal(self):
+        """
+        compare_digest_json() of documents that differ
+        """
+        assert not hyperjson.compare_digest_json({"a": "secret"}, {"a": "secreT"})
+        assert not hyperjson.compare_digest_json({"a": "secret"}, {"a": "secrets"})
+        assert not hyperjson.compare_digest_json({"a": "secrets"}, {"a": "secret"})
+        assert not hyperjson.compare_digest_json([1, 2], [2, 1])
+        assert not hyperjson.compare_digest_json("", [])
+
+    def test_compare_digest_json_loads(self):
+        """
+        compare_digest_json() of a decoded document and the object it encodes
+        """
+        body = b'{ "token" : "abc", "nested": {"b": 1, "a": [1.5, null]} }'
+        expected = {"nested": {"a": [1.5, None], "b": 1}, "token": "abc"}
+        assert hyperjson.compare_digest_json(hyperjson.loads(body), expected)
+        assert hyperjson.compare_digest_json(hyperjson.loads_tracked(body), expected)
+
+    def test_compare_digest_json_unsupported(self):
+        """
+        compare_digest_json() of a document that cannot be serialized
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.compare_digest_json({"a": object()}, {})
+        assert str(exc.value).startswith("compare_digest_json() argument 1: ")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.compare_digest_json({}, {"a": object()})
+        assert str(exc.value).startswith("compare_digest_json() argument 2: ")
+
+    def test_compare_digest_json_args(self):
+        """
+        compare_digest_json() takes exactly two positional arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.compare_digest_json({})  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.compare_digest_json({}, {}, {})  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.compare_digest_json(a={}, b={})  # type: ignore
@@ -0,0 +1,199 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+from typing import Any, Dict, List, Tuple, TypedDict
+
+import pytest
+
+import hyperjson
+
+DOCS = (
+    '{"a": [1, -2, 3.5, 1e300, true, false, null], "b": {"c": {}, "d": []}}',
+    '[[[[]]], {"\\u00e9": "\\ud83d\\ude00"}, "x", 18446744073709551615]',
+    '{"a": 1, "a": 2}',
+    "[" + ",".join(f'{{"k{i}": [{i}]}}' for i in range(200)) + "]",
+    "[]",
+    "{}",
+    '"\\n"',
+    "123",
+)
+
+INVALID = (
+    "",
+    "[1,]",
+    '{"a" 1}',
+    '["tab\\   character"]',
+    '"\\ud800"',
+    "[1",
+    "01",
+    "1 2",
+    "   ",
+    "[tx]",
+    "[fals",
+    "[-]",
+    "1.",
+    "[1e]",
+    "-01",
+    '{"a": 1,}',
+    '"\\u12',
+    '"\\ud800\\u12"',
+    '"\\',
+    "[" * 1025 + "]" * 1025,
+)
+
+
+def decode_error(data):
+    with pytest.raises(hyperjson.JSONDecodeError) as exc:
+        hyperjson.loads(data)
+    return (exc.value.msg, exc.value.pos)
+
+
+class TestCompatMode:
+    def setup_method(self, method):
+        hyperjson._reset_for_testing()
+
+    def teardown_method(self, method):
+        hyperjson._reset_for_testing()
+
+    def test_compat_mode_loads(self):
+        """
+        set_compat_mode(True) decodes as loads() does by default
+        """
+        expected = [hyperjson.loads(doc) for doc in DOCS]
+        hyperjson.set_compat_mode(True)
+        assert [hyperjson.loads(doc) for doc in DOCS] == expected
+        assert [hyperjson.loads(doc.encode()) for doc in DOCS] == expected
+
+    def test_compat_mode_errors(self):
+        """
+        set_compat_mode(True) raises the same errors as loads() does by default
+        """
+        expected = [decode_error(doc) for doc in INVALID]
+        hyperjson.set_compat_mode(True)
+        assert [decode_error(doc) for doc in INVALID] == expected
+
+    def test_compat_mode_options(self):
+        """
+        set_compat_mode(True) applies with the keyword arguments of loads()
+        """
+        hyperjson.set_compat_mode(True)
+        assert hyperjson.loads('{"a": null, "b": 1}', rename={"b": "c"}) == {
+            "a": None,
+            "c": 1,
+        }
+        assert hyperjson.loads(
+            '{"a": null, "b": 1}', option=hyperjson.OPT_OMIT_NULL_ON_LOAD
+        ) == {"b": 1}
+        assert hyperjson.loads('{"a": 1, "a": 2}', duplicate_keys="first") == {"a": 1}
+        assert hyperjson.loads("[1, NaN,]", json5=True)[0] == 1
+        assert hyperjson.loads('{"a": 1}', object_pairs_hook=list) == [("a", 1)]
+        assert hyperjson.loads_tracked('{"a": [1]}') == {"a": [1]}
+
+    def test_compat_mode_dumps(self):
+        """
+        set_compat_mode(True) does not change what dumps() returns
+        """
+
+        @dataclasses.dataclass
+        class Point:
+            x: int
+            y: List[int]
+
+        class Opaque:
+            pass
+
+        values = (
+            {"a": [1, (2,)]},
+            list(range(40)),
+            tuple(str(i) for i in range(40)),
+            [float(i) / 2 for i in range(40)],
+            [{"a": i, "b": [i]} for i in range(40)],
+            [Point(i, [i]) for i in range(20)],
+            {"b": 1, "a": {"d": [], "c": {}}},
+            {"a": [Opaque(), Opaque()]},
+            [[], (), {}, ""],
+        )
+        options = (
+            0,
+            hyperjson.OPT_SORT_KEYS | hyperjson.OPT_NON_STR_KEYS,
+            hyperjson.OPT_INDENT_2,
+            hyperjson.OPT_OMIT_EMPTY,
+        )
+
+        def default(obj):
+            if isinstance(obj, Opaque):
+                return [1, {"x": 2}]
+            raise TypeError
+
+        expected = [
+            hyperjson.dumps(value, option=option, default=default)
+            for value in values
+            for option in options
+        ]
+        hyperjson.set_compat_mode(True)
+        assert [
+            hyperjson.dumps(value, option=option, default=default)
+            for value in values
+            for option in options
+        ] == expected
+        assert (
+            hyperjson.dumps(
+                {1: "a", None: [2], "b": {2: 3}}, option=hyperjson.OPT_NON_STR_KEYS
+            )
+            == b'{"1":"a","null":[2],"b":{"2":3}}'
+        )
+
+    def test_compat_mode_loads_type(self):
+        """
+        set_compat_mode(True) does not change what loads(type=) returns
+        """
+
+        class Movie(TypedDict):
+            title: str
+            year: int
+
+        cases = (
+            ("[1, 2]", List[int]),
+            ('[1, "a"]', Tuple[int, str]),
+            ('{"a": [1], "b": []}', Dict[str, List[int]]),
+            ('{"title": "x", "year": 1, "other": null}', Movie),
+            ('[{"a": [1, {"b": null}]}, 2]', List[Any]),
+            ('{"a": {"b": [1, 2]}}', Dict[str, Any]),
+        )
+        expected = [hyperjson.loads(doc, type=kind) for doc, kind in cases]
+        hyperjson.set_compat_mode(True)
+        assert [hyperjson.loads(doc, type=kind) for doc, kind in cases] == expected
+        assert hyperjson.loads(
+            '{"a": {"b": null, "c": 1}}',
+            type=Dict[str, Any],
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+        ) == {"a": {"c": 1}}
+
+    def test_compat_mode_disable(self):
+        """
+        set_compat_mode(False) restores the default
+        """
+        hyperjson.set_compat_mode(True)
+        hyperjson.set_compat_mode(False)
+        assert hyperjson.loads('{"a": [1]}') == {"a": [1]}
+
+    def test_compat_mode_not_bool(self):
+        """
+        set_compat_mode() argument must be a bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.set_compat_mode(1)  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.set_compat_mode()  # type: ignore
+
+    def test_invalid_escape_position(self):
+        """
+        an invalid escape is reported at the escaped character whichever
+        parser reads it
+        """
+        doc = '["tab\\   character"]'
+        assert decode_error(doc)[1] == doc.index("\\") + 1
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc, duplicate_keys="first")
+        assert exc.value.pos == doc.index("\\") + 1
@@ -0,0 +1,83 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+import uuid
+
+import hyperjson
+
+GOLDEN = {
+    "float": [0.1, 1e16, 1e-7, -0.0, 1.7976931348623157e308, 5e-324, 100.0],
+    "str": "\"\\/\x00\x1f\x7f\u00e9\u2028\U0001f600",
+    "datetime": [
+        datetime.datetime(2025, 1, 2, 3, 4, 5, 6),
+        datetime.datetime(2025, 1, 2, 3, 4, 5, tzinfo=datetime.timezone.utc),
+        datetime.datetime(
+            2025,
+            1,
+            2,
+            tzinfo=datetime.timezone(datetime.timedelta(hours=-5, minutes=-30)),
+        ),
+        datetime.date(2025, 1, 2),
+        datetime.time(3, 4, 5, 600),
+    ],
+    "uuid": uuid.UUID("12345678-1234-5678-1234-567812345678"),
+    "int": [0, -1, 18446744073709551615, -9223372036854775808],
+}
+
+GOLDEN_V1 = (
+    b'{"float":[0.1,1e16,1e-7,-0.0,1.7976931348623157e308,5e-324,100.0],'
+    b'"str":"\\"\\\\/\\u0000\\u001f\x7f\xc3\xa9\xe2\x80\xa8\xf0\x9f\x98\x80",'
+    b'"datetime":["2025-01-02T03:04:05.000006","2025-01-02T03:04:05+00:00",'
+    b'"2025-01-02T00:00:00-05:30","2025-01-02","03:04:05.000600"],'
+    b'"uuid":"12345678-1234-5678-1234-567812345678",'
+    b'"int":[0,-1,18446744073709551615,-9223372036854775808]}'
+)
+
+
+class TestCompatV1:
+    def test_compat_v1_golden(self):
+        """
+        OPT_COMPAT_V1 writes the output of hyperjson 0.1
+        """
+        assert hyperjson.dumps(GOLDEN, option=hyperjson.OPT_COMPAT_V1) == GOLDEN_V1
+
+    def test_compat_v1_latest(self):
+        """
+        the default output is that of OPT_COMPAT_V1
+        """
+        assert hyperjson.dumps(GOLDEN) == GOLDEN_V1
+
+    def test_compat_v1_options(self):
+        """
+        OPT_COMPAT_V1 with other options
+        """
+        assert (
+            hyperjson.dumps(
+                {"b": [1.5], "a": "\u00e9"},
+                option=hyperjson.OPT_COMPAT_V1
+                | hyperjson.OPT_INDENT_2
+                | hyperjson.OPT_SORT_KEYS
+                | hyperjson.OPT_ENSURE_ASCII,
+            )
+            == b'{\n  "a": "\\u00e9",\n  "b": [\n    1.5\n  ]\n}'
+        )
+        assert (
+            hyperjson.dumps_length(GOLDEN, option=hyperjson.OPT_COMPAT_V1)
+            == len(GOLDEN_V1)
+        )
+
+    def test_compat_v1_loads_tracked(self):
+        """
+        OPT_COMPAT_V1 serializes a loads_tracked() result rather than writing
+        its input
+        """
+        data = b'{"a": [1.0E2, 0.10]}'
+        obj = hyperjson.loads_tracked(data)
+        assert hyperjson.dumps_length(obj, option=hyperjson.OPT_COMPAT_V1) == len(
+            b'{"a":[100.0,0.1]}'
+        )
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_COMPAT_V1) == (
+            b'{"a":[100.0,0.1]}'
+        )
+        assert hyperjson.dumps(obj) == b'{"a":[100.0,0.1]}'
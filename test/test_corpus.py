@@ -0,0 +1,120 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import lzma
+import os
+import tempfile
+from pathlib import Path
+
+import pytest
+
+import hyperjson
+
+from .util import data_dir, needs_data
+
+
+def write(tmp, name, contents):
+    path = Path(tmp, name)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_bytes(contents)
+
+
+class TestRunCorpus:
+    @needs_data
+    def test_run_corpus_fixtures(self):
+        """
+        _run_corpus() reports nothing for the fixtures
+        """
+        for subdir in ("parsing", "jsonchecker", "roundtrip", "transform"):
+            assert hyperjson._run_corpus(os.path.join(data_dir, subdir)) == []
+
+    def test_run_corpus_passing(self):
+        """
+        _run_corpus() reports nothing for inputs that behave as named
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            write(tmp, "y_object.json", b'{"a": [1, 2.5, "\\u00e9"]}')
+            write(tmp, "n_trailing_comma.json", b"[1,]")
+            write(tmp, "fuzz/crash-1", b'[{"a": 1}, "\xff"]')
+            write(tmp, "fuzz/crash-2", b"[" * 600 + b"]" * 600)
+            write(tmp, "y_compressed.json.xz", lzma.compress(b"[true]"))
+            assert hyperjson._run_corpus(tmp) == []
+
+    def test_run_corpus_mismatch(self):
+        """
+        _run_corpus() reports a y_ file rejected and an n_ file accepted, as
+        bytes and as str
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            write(tmp, "y_rejected.json", b"[1,]")
+            write(tmp, "sub/n_accepted.json", b"[1]")
+            write(tmp, "y_invalid_utf8.json", b'["\xff"]')
+            assert hyperjson._run_corpus(tmp) == [
+                {
+                    "file": os.path.join("sub", "n_accepted.json"),
+                    "input": "bytes",
+                    "kind": "mismatch",
+                    "detail": "loads() accepted an invalid document",
+                    "repro": None,
+                },
+                {
+                    "file": os.path.join("sub", "n_accepted.json"),
+                    "input": "str",
+                    "kind": "mismatch",
+                    "detail": "loads() accepted an invalid document",
+                    "repro": None,
+                },
+                {
+                    "file": "y_invalid_utf8.json",
+                    "input": "bytes",
+                    "kind": "mismatch",
+                    "detail": "loads() rejected a valid document",
+                    "repro": None,
+                },
+                {
+                    "file": "y_rejected.json",
+                    "input": "bytes",
+                    "kind": "mismatch",
+                    "detail": "loads() rejected a valid document",
+                    "repro": None,
+                },
+                {
+                    "file": "y_rejected.json",
+                    "input": "str",
+                    "kind": "mismatch",
+                    "detail": "loads() rejected a valid document",
+                    "repro": None,
+                },
+            ]
+
+    def test_run_corpus_repro(self):
+        """
+        _run_corpus() writes each failed input and a description of it to
+        repro_dir
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            corpus = os.path.join(tmp, "corpus")
+            repro_dir = os.path.join(tmp, "repro")
+            write(corpus, "y_ok.json", b"[1]")
+            write(corpus, "sub/y_rejected.json.xz", lzma.compress(b"[1,]"))
+            failures = hyperjson._run_corpus(corpus, repro_dir=repro_dir)
+            repro = os.path.join(repro_dir, "sub_y_rejected.json")
+            assert [failure["repro"] for failure in failures] == [repro, repro]
+            assert sorted(os.listdir(repro_dir)) == [
+                "sub_y_rejected.json",
+                "sub_y_rejected.json.txt",
+            ]
+            assert Path(repro).read_bytes() == b"[1,]"
+            description = Path(f"{repro}.txt").read_text().splitlines()
+            assert len(description) == 2
+            assert description[0].endswith(
+                "mismatch reading bytes: loads() rejected a valid document",
+            )
+
+    def test_run_corpus_not_directory(self):
+        """
+        _run_corpus() path must be a directory
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            with pytest.raises(NotADirectoryError):
+                hyperjson._run_corpus(os.path.join(tmp, "missing"))
@@ -86,6 +86,19 @@ class UnsortedDataclass:
     d: Optional[dict]
 
 
+@dataclass
+class ReorderedDataclass:
+    item10: int
+    item2: int
+    Item1: int
+
+    def __post_init__(self):
+        item10 = self.item10
+        del self.item10
+        self.item10 = item10
+        self.extra = 0
+
+
 @dataclass
 class InitDataclass:
     a: InitVar[str]
@@ -261,6 +274,99 @@ class TestDataclass:
         )
 
 
+    def test_dataclass_field_order_insertion(self):
+        """
+        dumps() field_order="insertion" writes the attributes of a dataclass
+        in the order they were set, which is the default
+        """
+        obj = ReorderedDataclass(1, 2, 3)
+        expected = b'{"item2":2,"Item1":3,"item10":1,"extra":0}'
+        assert hyperjson.dumps(obj) == expected
+        assert hyperjson.dumps(obj, field_order="insertion") == expected
+        assert hyperjson.dumps(obj, field_order=None) == expected
+
+    def test_dataclass_field_order_declaration(self):
+        """
+        dumps() field_order="declaration" writes the fields of a dataclass in
+        the order they are declared
+        """
+        assert (
+            hyperjson.dumps(ReorderedDataclass(1, 2, 3), field_order="declaration")
+            == b'{"item10":1,"item2":2,"Item1":3}'
+        )
+        assert (
+            hyperjson.dumps(Datasubclass("a", 1, None, True), field_order="declaration")
+            == b'{"name":"a","number":1,"sub":null,"additional":true}'
+        )
+
+    def test_dataclass_field_order_sorted(self):
+        """
+        dumps() field_order="sorted" writes the fields of a dataclass sorted
+        in the order of key_order=
+        """
+        obj = ReorderedDataclass(1, 2, 3)
+        assert (
+            hyperjson.dumps(obj, field_order="sorted")
+            == b'{"Item1":3,"item10":1,"item2":2}'
+        )
+        assert (
+            hyperjson.dumps(obj, field_order="sorted", key_order="natural")
+            == b'{"Item1":3,"item2":2,"item10":1}'
+        )
+        assert (
+            hyperjson.dumps(
+                UnsortedDataclass(1, 2, 3, {"f": 2, "e": 1}),
+                field_order="sorted",
+                default=str,
+            )
+            == b'{"a":3,"b":2,"c":1,"d":{"f":2,"e":1}}'
+        )
+
+    def test_dataclass_field_order_slots(self):
+        """
+        dumps() field_order= applies to a dataclass with __slots__
+        """
+        obj = Slotsdataclass("a", 1, "c", "d")
+        assert hyperjson.dumps(obj, field_order="sorted") == b'{"a":"a","b":1}'
+        assert hyperjson.dumps(obj, field_order="declaration") == b'{"a":"a","b":1}'
+
+    def test_dataclass_field_order_omit(self):
+        """
+        dumps() field_order= omits fields as OPT_OMIT_NULL selects
+        """
+        assert (
+            hyperjson.dumps(
+                UnsortedDataclass(1, 2, 3, None),
+                option=hyperjson.OPT_OMIT_NULL,
+                field_order="sorted",
+            )
+            == b'{"a":3,"b":2,"c":1}'
+        )
+
+    def test_dataclass_field_order_length(self):
+        """
+        dumps_length() and dumps_batch() accept field_order=
+        """
+        obj = ReorderedDataclass(1, 2, 3)
+        assert hyperjson.dumps_length(obj, field_order="sorted") == len(
+            hyperjson.dumps(obj, field_order="sorted")
+        )
+        assert hyperjson.dumps_batch([obj], field_order="declaration")[0] == (
+            b'{"item10":1,"item2":2,"Item1":3}\n'
+        )
+
+    def test_dataclass_field_order_invalid(self):
+        """
+        dumps() field_order must be "insertion", "declaration", or "sorted"
+        """
+        for val in ("declared", "", 1, b"sorted"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps(ReorderedDataclass(1, 2, 3), field_order=val)  # type: ignore
+            assert str(exc.value) == (
+                'dumps() field_order must be "insertion", "declaration", or "sorted"'
+            )
+
+
 class TestDataclassPassthrough:
     def test_dataclass_passthrough_raise(self):
         """
@@ -355,3 +355,119 @@ class TestType:
             raise TypeError
 
         assert hyperjson.dumps({1, 2}, default=default) == b"[1,2]"
+
+    def test_default_mapping(self):
+        """
+        dumps() default dict of type to callable
+        """
+        default = {
+            set: sorted,
+            Custom: lambda obj: str(obj),
+            uuid.UUID: lambda obj: obj.hex,
+        }
+        ref = Custom()
+        assert hyperjson.dumps([{3, 1, 2}, ref], default=default) == (
+            b'[[1,2,3],"%s"]' % str(ref).encode("utf-8")
+        )
+
+    def test_default_mapping_subclass(self):
+        """
+        dumps() default dict matches a base in the MRO
+        """
+
+        class Base:
+            pass
+
+        class Derived(Base):
+            pass
+
+        assert (
+            hyperjson.dumps(Derived(), default={Base: lambda obj: "base"})
+            == b'"base"'
+        )
+
+    def test_default_mapping_exact_type_first(self):
+        """
+        dumps() default dict prefers the exact type over a base
+        """
+
+        class Base:
+            pass
+
+        class Derived(Base):
+            pass
+
+        default = {Base: lambda obj: "base", Derived: lambda obj: "derived"}
+        assert hyperjson.dumps([Base(), Derived()], default=default) == (
+            b'["base","derived"]'
+        )
+
+    def test_default_mapping_missing(self):
+        """
+        dumps() default dict without an entry for the type
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Custom(), default={set: list})
+
+    def test_default_mapping_empty(self):
+        """
+        dumps() default empty dict
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Custom(), default={})
+
+    def test_default_mapping_raises(self):
+        """
+        dumps() default dict callable raising
+        """
+
+        def default(obj):
+            raise ValueError
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps(Custom(), default={Custom: default})
+        assert isinstance(exc_info.value.__cause__, ValueError)
+
+    def test_default_mapping_nested(self):
+        """
+        dumps() default dict applied to values returned by a callable
+        """
+
+        class Outer:
+            pass
+
+        class Inner:
+            pass
+
+        default = {Outer: lambda obj: [Inner()], Inner: lambda obj: 1}
+        assert hyperjson.dumps(Outer(), default=default) == b"[1]"
+
+    def test_default_mapping_dict_subclass_is_callable(self):
+        """
+        dumps() default dict subclass is called rather than looked up
+        """
+
+        class Mapping(dict):
+            def __call__(self, obj):
+                return "called"
+
+        assert hyperjson.dumps(Custom(), default=Mapping({Custom: str})) == (
+            b'"called"'
+        )
+
+    def test_default_mapping_registry_precedence(self):
+        """
+        dumps() register_serializer() takes precedence over default dict
+        """
+
+        class Registered:
+            pass
+
+        hyperjson.register_serializer(Registered, lambda obj: "registered")
+        try:
+            assert (
+                hyperjson.dumps(Registered(), default={Registered: lambda obj: 1})
+                == b'"registered"'
+            )
+        finally:
+            hyperjson.register_serializer(Registered, None)
@@ -0,0 +1,265 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import gc
+import json
+import random
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    a: object
+    b: object
+
+
+@dataclasses.dataclass
+class SlotRecord:
+    __slots__ = ("a", "b")
+    a: object
+    b: object
+
+
+def rows(count):
+    return [f"row{idx}" * 4 for idx in range(count)]
+
+
+class TestDefaultMutation:
+    def test_default_clears_dict(self):
+        """
+        dumps() does not write the entries of a dict that default removes
+        """
+        obj = {"a": Custom(), "b": rows(3), "c": 1}
+
+        def default(val):
+            obj.clear()
+            return "x"
+
+        assert hyperjson.dumps(obj, default=default) == b'{"a":"x"}'
+        assert obj == {}
+
+    def test_default_replaces_value(self):
+        """
+        dumps() writes the value a dict has when it is reached if default
+        replaces it
+        """
+        obj = {"a": Custom(), "b": rows(2)}
+
+        def default(val):
+            obj["b"] = None
+            gc.collect()
+            return None
+
+        assert hyperjson.dumps(obj, default=default) == b'{"a":null,"b":null}'
+
+    def test_default_clears_list(self):
+        """
+        dumps() writes no more items than a list had when reached if default
+        changes it
+        """
+        obj = [Custom(), *rows(2)]
+
+        def default(val):
+            obj.clear()
+            obj.extend(range(1000))
+            return 0
+
+        assert hyperjson.dumps(obj, default=default) == b"[0,1,2]"
+
+    def test_default_clears_parent(self):
+        """
+        dumps() keeps a container it is in alive if default frees its parent
+        """
+        obj = {"a": [Custom(), {"b": rows(1)}], "c": [1]}
+
+        def default(val):
+            obj.clear()
+            gc.collect()
+            return 1
+
+        assert hyperjson.dumps(obj, default=default) == (
+            b'{"a":[1,{"b":["row0row0row0row0"]}]}'
+        )
+
+    def test_default_later_container(self):
+        """
+        dumps() sees changes to a container not yet reached
+        """
+        inner = [1]
+        obj = [Custom(), inner]
+
+        def default(val):
+            inner.append(2)
+            return 0
+
+        assert hyperjson.dumps(obj, default=default) == b"[0,[1,2]]"
+
+    def test_default_mutates_dataclass(self):
+        """
+        dumps() does not write dataclass attributes default removes
+        """
+        obj = Record(Custom(), rows(1))
+
+        def default(val):
+            obj.__dict__.clear()
+            return 0
+
+        assert hyperjson.dumps(obj, default=default) == b'{"a":0}'
+
+    def test_default_mutates_slots(self):
+        """
+        dumps() owns a __slots__ attribute until it is written
+        """
+        obj = SlotRecord(rows(1), [Custom(), *rows(1)])
+
+        def default(val):
+            del obj.b
+            gc.collect()
+            return 0
+
+        assert hyperjson.dumps(obj, default=default) == (
+            b'{"a":["row0row0row0row0"],"b":[0,"row0row0row0row0"]}'
+        )
+
+    def test_default_shared_keys(self):
+        """
+        dumps() of a list of instance dicts that default changes
+        """
+        records = [Custom() for _ in range(4)]
+        for idx, record in enumerate(records):
+            record.__dict__.update(a=idx, b=rows(1))
+        obj = [record.__dict__ for record in records]
+        obj.insert(2, Custom())
+
+        def default(val):
+            for record in records:
+                record.__dict__.clear()
+            gc.collect()
+            return None
+
+        assert json.loads(hyperjson.dumps(obj, default=default)) == [
+            {"a": 0, "b": ["row0row0row0row0"]},
+            {"a": 1, "b": ["row0row0row0row0"]},
+            None,
+            {},
+            {},
+        ]
+
+    def test_key_order_mutates_dict(self):
+        """
+        dumps() key_order= comparator that changes the dict being sorted
+        """
+        obj = {"b": rows(1), "a": rows(1), "c": 1}
+
+        def cmp(a, b):
+            obj.clear()
+            gc.collect()
+            return (a > b) - (a < b)
+
+        assert hyperjson.dumps(obj, key_order=cmp) == (
+            b'{"a":["row0row0row0row0"],"b":["row0row0row0row0"],"c":1}'
+        )
+
+    def test_float_format_mutates_list(self):
+        """
+        dumps() float_format= callable that clears the list being written
+        """
+        obj = [idx + 0.5 for idx in range(32)]
+
+        def fmt(val):
+            obj.clear()
+            gc.collect()
+            return repr(val)
+
+        assert hyperjson.dumps(obj, float_format=fmt) == b"[0.5]"
+        obj = {"a": 0.5, "b": rows(1), "c": 1.5}
+        assert hyperjson.dumps(obj, float_format=fmt) == b'{"a":0.5}'
+
+    def test_register_serializer_mutates(self):
+        """
+        dumps() serializer registered with register_serializer() that clears
+        the containers being written
+        """
+        obj = [{"a": Custom(), "b": rows(1)}, rows(1)]
+        first = obj[0]
+
+        def serializer(val):
+            obj.clear()
+            first.clear()
+            gc.collect()
+            return "x"
+
+        hyperjson.register_serializer(Custom, serializer)
+        try:
+            assert hyperjson.dumps(obj) == b'[{"a":"x"}]'
+        finally:
+            hyperjson.register_serializer(Custom, None)
+
+    def test_default_mutation_batch(self):
+        """
+        dumps_batch() and dumps_length() with default that changes records
+        """
+        records = [{"a": Custom(), "b": rows(1)} for _ in range(3)]
+
+        def default(val):
+            for record in records:
+                record.clear()
+            return 0
+
+        expected = b'{"a":0}'
+        assert hyperjson.dumps_length(records[0], default=default) == len(expected)
+        records = [{"a": Custom(), "b": rows(1)} for _ in range(3)]
+        assert hyperjson.dumps_batch(records, default=default)[0] == (
+            expected + b"\n" + b"{}\n" * 2
+        )
+
+    def test_default_mutation_stress(self):
+        """
+        dumps() of documents that default changes at random
+        """
+        rng = random.Random(0)
+
+        def build(depth):
+            if depth == 0:
+                return rng.choice([Custom(), "leaf" * 8, 1, None])
+            if rng.random() < 0.5:
+                return {f"k{idx}": build(depth - 1) for idx in range(rng.randint(0, 6))}
+            return [build(depth - 1) for _ in range(rng.randint(0, 6))]
+
+        def containers(val, found):
+            if isinstance(val, dict):
+                found.append(val)
+                for each in list(val.values()):
+                    containers(each, found)
+            elif isinstance(val, list):
+                found.append(val)
+                for each in list(val):
+                    containers(each, found)
+            return found
+
+        for _ in range(200):
+            obj = [build(4) for _ in range(3)]
+            found = containers(obj, [])
+
+            def default(val):
+                for _ in range(3):
+                    target = rng.choice(found)
+                    if isinstance(target, dict):
+                        if target and rng.random() < 0.5:
+                            del target[rng.choice(list(target))]
+                        else:
+                            target[f"n{rng.random()}"] = "new" * 8
+                    elif target and rng.random() < 0.5:
+                        target.pop(rng.randrange(len(target)))
+                    else:
+                        target.extend(["new" * 8] * rng.randint(1, 50))
+                gc.collect()
+                return "default"
+
+            json.loads(hyperjson.dumps(obj, default=default))
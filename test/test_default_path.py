@@ -0,0 +1,212 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    name: str
+    value: object
+
+
+def record_paths(paths):
+    def default(obj, path):
+        paths.append(path)
+        return None
+
+    return default
+
+
+class TestDefaultPath:
+    def test_default_path_top_level(self):
+        """
+        dumps() OPT_DEFAULT_PATH of the top-level object is empty
+        """
+        paths = []
+        hyperjson.dumps(
+            Custom(),
+            default=record_paths(paths),
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [[]]
+
+    def test_default_path_nested(self):
+        """
+        dumps() OPT_DEFAULT_PATH of nested dict and list
+        """
+        paths = []
+        obj = {"a": [1, {"b": Custom()}], "c": (Custom(),)}
+        assert (
+            hyperjson.dumps(
+                obj,
+                default=record_paths(paths),
+                option=hyperjson.OPT_DEFAULT_PATH,
+            )
+            == b'{"a":[1,{"b":null}],"c":[null]}'
+        )
+        assert paths == [["a", 1, "b"], ["c", 0]]
+
+    def test_default_path_dataclass(self):
+        """
+        dumps() OPT_DEFAULT_PATH of a dataclass field
+        """
+        paths = []
+        hyperjson.dumps(
+            [Record("x", Custom())],
+            default=record_paths(paths),
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [[0, "value"]]
+
+    def test_default_path_non_str_key(self):
+        """
+        dumps() OPT_DEFAULT_PATH with OPT_NON_STR_KEYS
+        """
+        paths = []
+        hyperjson.dumps(
+            {1: Custom()},
+            default=record_paths(paths),
+            option=hyperjson.OPT_DEFAULT_PATH | hyperjson.OPT_NON_STR_KEYS,
+        )
+        assert paths == [[1]]
+
+    def test_default_path_returned_value(self):
+        """
+        dumps() OPT_DEFAULT_PATH of objects within a value returned by default
+        """
+
+        class Outer:
+            pass
+
+        paths = []
+
+        def default(obj, path):
+            paths.append(path)
+            if isinstance(obj, Outer):
+                return {"inner": [Custom()]}
+            return None
+
+        hyperjson.dumps(
+            {"key": Outer()},
+            default=default,
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [["key"], ["key", "inner", 0]]
+
+    def test_default_path_same_object(self):
+        """
+        dumps() OPT_DEFAULT_PATH of an object at more than one location
+        """
+        paths = []
+        ref = Custom()
+        hyperjson.dumps(
+            [ref, ref],
+            default=record_paths(paths),
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [[0], [0]]
+
+    def test_default_path_mapping(self):
+        """
+        dumps() OPT_DEFAULT_PATH with default dict of type to callable
+        """
+        paths = []
+        hyperjson.dumps(
+            {"a": Custom()},
+            default={Custom: record_paths(paths)},
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [["a"]]
+
+    def test_default_path_error(self):
+        """
+        dumps() OPT_DEFAULT_PATH default raising with the path
+        """
+
+        def default(obj, path):
+            raise TypeError(f"unsupported at {path}")
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps(
+                {"a": [0, Custom()]},
+                default=default,
+                option=hyperjson.OPT_DEFAULT_PATH,
+            )
+        assert str(exc_info.value.__cause__) == "unsupported at ['a', 1]"
+
+    def test_default_path_disabled(self):
+        """
+        dumps() default is called with one argument without OPT_DEFAULT_PATH
+        """
+
+        def default(obj, path=None):
+            assert path is None
+            return "custom"
+
+        assert hyperjson.dumps([Custom()], default=default) == b'["custom"]'
+
+    def test_default_path_registered(self):
+        """
+        dumps() OPT_DEFAULT_PATH does not apply to register_serializer()
+        """
+
+        class Registered:
+            pass
+
+        hyperjson.register_serializer(Registered, lambda obj: "registered")
+        try:
+            assert (
+                hyperjson.dumps(
+                    [Registered()],
+                    default=lambda obj, path: None,
+                    option=hyperjson.OPT_DEFAULT_PATH,
+                )
+                == b'["registered"]'
+            )
+        finally:
+            hyperjson.register_serializer(Registered, None)
+
+    def test_default_path_nested_dumps(self):
+        """
+        dumps() OPT_DEFAULT_PATH with default calling dumps()
+        """
+        paths = []
+
+        def default(obj, path):
+            paths.append(path)
+            return hyperjson.dumps(
+                {"inner": Custom()},
+                default=record_paths(paths),
+                option=hyperjson.OPT_DEFAULT_PATH,
+            ).decode("utf-8")
+
+        hyperjson.dumps(
+            [{"outer": Custom()}, Custom()],
+            default=default,
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [[0, "outer"], ["inner"], [1], ["inner"]]
+
+    def test_default_path_builder(self):
+        """
+        Builder OPT_DEFAULT_PATH is relative to the value
+        """
+        paths = []
+        builder = hyperjson.Builder(
+            default=record_paths(paths),
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        builder.start_array()
+        builder.value({"a": Custom()})
+        builder.end_array()
+        assert builder.finish() == b'[{"a":null}]'
+        assert paths == [["a"]]
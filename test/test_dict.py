@@ -1,6 +1,9 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 # Copyright ijl (2018-2025), J. Nick Koston (2022), Anders Kaseorg (2022)
 
+import json
+import sys
+
 import pytest
 
 import hyperjson
@@ -168,3 +171,102 @@ class TestDict:
                 self.b = 1
 
         assert hyperjson.dumps(C().__dict__) == b'{"a":0,"b":1}'
+
+
+class Record:
+    def __init__(self, idx):
+        self.id = idx
+        self.name = f"record\n{idx}"
+        self.tags = ["a", "b"]
+
+
+class TestDictSharedKeys:
+    def test_shared_keys_list(self):
+        """
+        dumps() list of dict sharing a keys object
+        """
+        obj = [vars(Record(idx)) for idx in range(100)]
+        assert hyperjson.dumps(obj) == json.dumps(
+            obj,
+            separators=(",", ":"),
+        ).encode("utf-8")
+
+    def test_shared_keys_indent(self):
+        """
+        dumps() list of dict sharing a keys object with OPT_INDENT_2
+        """
+        obj = [vars(Record(idx)) for idx in range(10)]
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2) == json.dumps(
+            obj,
+            indent=2,
+        ).encode("utf-8")
+
+    def test_shared_keys_sort_keys(self):
+        """
+        dumps() list of dict sharing a keys object with OPT_SORT_KEYS
+        """
+        obj = [vars(Record(idx)) for idx in range(10)]
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_SORT_KEYS) == json.dumps(
+            obj,
+            sort_keys=True,
+            separators=(",", ":"),
+        ).encode("utf-8")
+
+    def test_shared_keys_extra_key(self):
+        """
+        dumps() list of dict sharing a keys object where later rows have more keys
+        """
+        records = [Record(idx) for idx in range(10)]
+        for record in records[5:]:
+            record.extra = "extra"
+        obj = [vars(record) for record in records]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_shared_keys_missing_key(self):
+        """
+        dumps() list of dict sharing a keys object where some rows lack a key
+        """
+        records = [Record(idx) for idx in range(10)]
+        for record in records[::2]:
+            del record.tags
+        obj = [vars(record) for record in records]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_shared_keys_mixed(self):
+        """
+        dumps() list mixing shared-keys dict, other classes, and plain dict
+        """
+
+        class Other:
+            def __init__(self):
+                self.id = "other"
+
+        obj = [
+            vars(Record(0)),
+            vars(Record(1)),
+            vars(Other()),
+            {"id": 2, "name": "plain"},
+            vars(Record(3)),
+            vars(Other()),
+            vars(Other()),
+            vars(Record(4)),
+            {},
+        ]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_shared_keys_nested(self):
+        """
+        dumps() nested lists of dict sharing a keys object
+        """
+        obj = [[vars(Record(idx)) for idx in range(3)] for _ in range(3)]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_shared_keys_refcount(self):
+        """
+        dumps() does not leak references to shared keys
+        """
+        obj = [vars(Record(idx)) for idx in range(10)]
+        key = next(iter(obj[0]))
+        ref = sys.getrefcount(key)
+        hyperjson.dumps(obj)
+        assert sys.getrefcount(key) == ref
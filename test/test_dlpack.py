@@ -0,0 +1,81 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+from .util import numpy
+
+
+class Tensor:
+    """
+    An object that exports only __dlpack__(), as a tensor of PyTorch does
+    """
+
+    def __init__(self, array):
+        self.array = array
+
+    def __dlpack__(self, stream=None):
+        return self.array.__dlpack__()
+
+    def __dlpack_device__(self):
+        return self.array.__dlpack_device__()
+
+
+@pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+class TestDlpack:
+    def test_dlpack(self):
+        """
+        dumps() OPT_SERIALIZE_NUMPY serializes an object with __dlpack__() as
+        a numpy array
+        """
+        for dtype in ("float32", "float64", "int8", "int64", "uint16", "bool"):
+            array = numpy.array([[1, 0, 1], [0, 1, 1]], dtype=dtype)
+            assert hyperjson.dumps(
+                Tensor(array), option=hyperjson.OPT_SERIALIZE_NUMPY
+            ) == hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+
+    def test_dlpack_strided(self):
+        """
+        dumps() OPT_SERIALIZE_NUMPY serializes a tensor that is not contiguous
+        """
+        array = numpy.arange(12, dtype=numpy.int32).reshape(3, 4)
+        for view in (array.T, array[:, 1::2], array[1:]):
+            assert hyperjson.dumps(
+                Tensor(view), option=hyperjson.OPT_SERIALIZE_NUMPY
+            ) == hyperjson.dumps(view.tolist())
+
+    def test_dlpack_nested(self):
+        """
+        dumps() OPT_SERIALIZE_NUMPY serializes a tensor in a container
+        """
+        tensor = Tensor(numpy.array([1.5, 2.5]))
+        assert (
+            hyperjson.dumps({"a": [tensor]}, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'{"a":[[1.5,2.5]]}'
+        )
+
+    def test_dlpack_unsupported(self):
+        """
+        dumps() OPT_SERIALIZE_NUMPY passes a tensor of a 0-dimensional shape to
+        default=
+        """
+        tensor = Tensor(numpy.array(1.5))
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(tensor, option=hyperjson.OPT_SERIALIZE_NUMPY)
+        assert (
+            hyperjson.dumps(
+                tensor,
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+                default=lambda x: float(x.array),
+            )
+            == b"1.5"
+        )
+
+    def test_dlpack_without_option(self):
+        """
+        dumps() does not serialize a tensor without OPT_SERIALIZE_NUMPY
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Tensor(numpy.array([1])))
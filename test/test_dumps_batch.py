@@ -0,0 +1,108 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+class TestDumpsBatch:
+    def test_dumps_batch(self):
+        """
+        dumps_batch() writes one line per record and its offsets
+        """
+        output, offsets = hyperjson.dumps_batch([{"a": 1}, [1, 2], "x", None])
+        assert output == b'{"a":1}\n[1,2]\n"x"\nnull\n'
+        assert offsets == [0, 8, 14, 18, 23]
+
+    def test_dumps_batch_split(self):
+        """
+        dumps_batch() offsets split the output into records
+        """
+        records = [{"id": idx, "value": "é" * idx} for idx in range(10)]
+        output, offsets = hyperjson.dumps_batch(records)
+        assert len(offsets) == len(records) + 1
+        assert offsets[-1] == len(output)
+        for idx, record in enumerate(records):
+            line = output[offsets[idx] : offsets[idx + 1]]
+            assert line == hyperjson.dumps(record) + b"\n"
+
+    def test_dumps_batch_empty(self):
+        """
+        dumps_batch() of no records
+        """
+        assert hyperjson.dumps_batch([]) == (b"", [0])
+
+    def test_dumps_batch_iterable(self):
+        """
+        dumps_batch() accepts a tuple or generator
+        """
+        assert hyperjson.dumps_batch((1, 2)) == (b"1\n2\n", [0, 2, 4])
+        assert hyperjson.dumps_batch(idx for idx in range(2)) == (
+            b"0\n1\n",
+            [0, 2, 4],
+        )
+
+    def test_dumps_batch_default_option(self):
+        """
+        dumps_batch() default and option
+        """
+        output, offsets = hyperjson.dumps_batch(
+            [{"b": Custom(), "a": 1}],
+            default=lambda obj: "custom",
+            option=hyperjson.OPT_SORT_KEYS | hyperjson.OPT_APPEND_CRLF,
+        )
+        assert output == b'{"a":1,"b":"custom"}\r\n'
+        assert offsets == [0, len(output)]
+
+    def test_dumps_batch_positional(self):
+        """
+        dumps_batch() default and option as positional arguments
+        """
+        assert hyperjson.dumps_batch(
+            [Custom()], lambda obj: 1, hyperjson.OPT_APPEND_NEWLINE
+        ) == (b"1\n", [0, 2])
+
+    def test_dumps_batch_default_path(self):
+        """
+        dumps_batch() OPT_DEFAULT_PATH is relative to the record
+        """
+        paths = []
+
+        def default(obj, path):
+            paths.append(path)
+            return None
+
+        hyperjson.dumps_batch(
+            [{"a": Custom()}, [0, Custom()]],
+            default=default,
+            option=hyperjson.OPT_DEFAULT_PATH,
+        )
+        assert paths == [["a"], [1]]
+
+    def test_dumps_batch_error(self):
+        """
+        dumps_batch() raises with the index of the record that failed
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps_batch([1, 2, Custom()])
+        assert str(exc_info.value).startswith("dumps_batch() record 2: ")
+
+    def test_dumps_batch_invalid(self):
+        """
+        dumps_batch() invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_batch()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_batch(1)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_batch([1], option=hyperjson.OPT_INDENT_2)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_batch([1], option=-1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_batch([1], unknown=1)  # type: ignore
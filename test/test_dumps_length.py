@@ -0,0 +1,137 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import datetime
+import uuid
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    name: str
+    value: object
+
+
+DOCUMENTS = (
+    None,
+    1,
+    -(2**63),
+    1.5,
+    "",
+    "a" * 1024,
+    'é"\\\n\U0001f600' * 64,
+    [],
+    {},
+    [1, "a", None, True, 2.5, [[]], {"b": {}}],
+    {"key": ["value"] * 100, "é": {"nested": list(range(100))}},
+    [datetime.datetime(2025, 1, 2, 3, 4, 5, 6), datetime.date(2025, 1, 2)],
+    [uuid.UUID(int=1), Record("a", [1, 2])],
+)
+
+
+class TestDumpsLength:
+    def test_dumps_length(self):
+        """
+        dumps_length() is the length of dumps()
+        """
+        for obj in DOCUMENTS:
+            assert hyperjson.dumps_length(obj) == len(hyperjson.dumps(obj))
+
+    def test_dumps_length_option(self):
+        """
+        dumps_length() with options that change the output
+        """
+        for option in (
+            hyperjson.OPT_INDENT_2,
+            hyperjson.OPT_INDENT_TAB,
+            hyperjson.OPT_APPEND_NEWLINE,
+            hyperjson.OPT_APPEND_CRLF,
+            hyperjson.OPT_ENSURE_ASCII,
+            hyperjson.OPT_SORT_KEYS | hyperjson.OPT_NON_STR_KEYS,
+        ):
+            for obj in DOCUMENTS:
+                assert hyperjson.dumps_length(obj, option=option) == len(
+                    hyperjson.dumps(obj, option=option)
+                )
+
+    def test_dumps_length_repeated_str(self):
+        """
+        dumps_length() counts a str from the escape cache
+        """
+        value = "é\n" * 32
+        obj = [value] * 8
+        assert hyperjson.dumps_length(obj) == len(hyperjson.dumps(obj))
+
+    def test_dumps_length_default(self):
+        """
+        dumps_length() calls default
+        """
+        calls = []
+
+        def default(obj):
+            calls.append(obj)
+            return "custom"
+
+        ref = Custom()
+        assert hyperjson.dumps_length([ref], default) == len(b'["custom"]')
+        assert calls == [ref]
+
+    def test_dumps_length_loads_tracked(self):
+        """
+        dumps_length() of an unmodified loads_tracked() result does not
+        consume it
+        """
+        source = b'{"a":[1,2.5]}'
+        obj = hyperjson.loads_tracked(source)
+        assert hyperjson.dumps_length(obj) == len(source)
+        assert hyperjson.dumps(obj) == source
+
+    def test_dumps_length_error(self):
+        """
+        dumps_length() raises as dumps() does
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_length(Custom())
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_length()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_length(1, option=-1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_length(1, unknown=1)  # type: ignore
+
+    def test_dumps_with_length(self):
+        """
+        dumps() length= allocates the measured length
+        """
+        for obj in DOCUMENTS:
+            length = hyperjson.dumps_length(obj)
+            output = hyperjson.dumps(obj, length=length)
+            assert output == hyperjson.dumps(obj)
+            assert len(output) == length
+
+    def test_dumps_with_length_inexact(self):
+        """
+        dumps() length= that does not match the output
+        """
+        obj = {"a": ["b" * 1000] * 10}
+        expected = hyperjson.dumps(obj)
+        assert hyperjson.dumps(obj, length=0) == expected
+        assert hyperjson.dumps(obj, length=10) == expected
+        assert hyperjson.dumps(obj, length=len(expected) * 4) == expected
+        assert hyperjson.dumps(obj, length=None) == expected
+
+    def test_dumps_with_length_invalid(self):
+        """
+        dumps() length= that is not a non-negative int
+        """
+        for val in (-1, 1.5, "1", True):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps(1, length=val)  # type: ignore
@@ -0,0 +1,214 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+PRETTY = b"""{
+  "name": "example",
+  "version": 1,
+  "tags": [
+    "a",
+    "b"
+  ],
+  "nested": {"x": 1,  "y": [ ]}
+}
+"""
+
+
+class TestEdit:
+    def test_edit_unchanged(self):
+        """
+        edit().bytes() returns the document as it was given
+        """
+        for doc in (PRETTY, b' [1,  2]\n', b"{}", b"1"):
+            assert hyperjson.edit(doc).bytes() == doc
+        assert hyperjson.edit('{"a": "é"}').bytes() == '{"a": "é"}'.encode()
+        assert hyperjson.edit(bytearray(b"[ ]")).bytes() == b"[ ]"
+        assert hyperjson.edit(memoryview(b"[ ]")).bytes() == b"[ ]"
+
+    def test_edit_set_same_length(self):
+        """
+        set() of a value of the same length changes only its bytes
+        """
+        out = hyperjson.edit(PRETTY).set("/version", 2).bytes()
+        assert out == PRETTY.replace(b'"version": 1', b'"version": 2')
+
+    def test_edit_set_splice(self):
+        """
+        set() of a value of another length splices it in
+        """
+        out = hyperjson.edit(PRETTY).set("/name", {"first": [1, None]}).bytes()
+        assert out == PRETTY.replace(b'"example"', b'{"first":[1,null]}')
+
+    def test_edit_set_nested(self):
+        """
+        set() follows the pointer through objects and arrays
+        """
+        out = hyperjson.edit(PRETTY).set("/tags/1", "c").set("/nested/x", 10).bytes()
+        assert out == PRETTY.replace(b'"b"', b'"c"').replace(b'"x": 1', b'"x": 10')
+
+    def test_edit_set_add_member(self):
+        """
+        set() of a missing key adds a member after the last, indented and
+        separated as it is
+        """
+        out = hyperjson.edit(PRETTY).set("/added", True).bytes()
+        assert out == PRETTY.replace(
+            b'"y": [ ]}\n}', b'"y": [ ]},\n  "added": true\n}'
+        )
+        out = hyperjson.edit(PRETTY).set("/nested/z", 3).bytes()
+        assert out == PRETTY.replace(b'"y": [ ]}', b'"y": [ ],  "z": 3}')
+        assert hyperjson.edit(b'{"a":1}').set("/b", 2).bytes() == b'{"a":1,"b":2}'
+
+    def test_edit_set_add_member_empty(self):
+        """
+        set() of a key in an empty object adds the only member
+        """
+        assert hyperjson.edit(b"{}").set("/a", 1).bytes() == b'{"a":1}'
+        assert hyperjson.edit(b"{ }").set("/a", 1).bytes() == b'{"a":1 }'
+
+    def test_edit_set_append(self):
+        """
+        set() of "-" or the length of an array appends to it
+        """
+        out = hyperjson.edit(PRETTY).set("/tags/-", "c").bytes()
+        assert out == PRETTY.replace(b'"b"\n', b'"b",\n    "c"\n')
+        assert hyperjson.edit(b"[1]").set("/1", 2).bytes() == b"[1,2]"
+        assert hyperjson.edit(b"[ ]").set("/-", 1).bytes() == b"[1 ]"
+        out = hyperjson.edit(PRETTY).set("/nested/y/0", 1).bytes()
+        assert out == PRETTY.replace(b"[ ]", b"[1 ]")
+
+    def test_edit_set_root(self):
+        """
+        set() of the empty pointer replaces the document, keeping the
+        whitespace around it
+        """
+        assert hyperjson.edit(b" [1] \n").set("", {"a": 1}).bytes() == b' {"a":1} \n'
+
+    def test_edit_set_not_found(self):
+        """
+        set() raises if the parent of the value does not exist
+        """
+        for pointer in ("/missing/a", "/tags/5", "/tags/01", "/tags/x", "/name/a", "/version/0"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.edit(PRETTY).set(pointer, 1)
+            assert str(exc.value) == f"Editor set() path not found: {pointer}"
+
+    def test_edit_set_unsupported(self):
+        """
+        set() raises if the value cannot be serialized and leaves the document
+        as it was
+        """
+        editor = hyperjson.edit(PRETTY)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            editor.set("/name", object())
+        assert editor.bytes() == PRETTY
+
+    def test_edit_delete(self):
+        """
+        delete() removes a member and the separator after it
+        """
+        out = hyperjson.edit(PRETTY).delete("/version").bytes()
+        assert out == PRETTY.replace(b'  "version": 1,\n', b"")
+        out = hyperjson.edit(PRETTY).delete("/tags/0").bytes()
+        assert out == PRETTY.replace(b'"a",\n    ', b"")
+
+    def test_edit_delete_last(self):
+        """
+        delete() of the last member removes the separator before it
+        """
+        out = hyperjson.edit(PRETTY).delete("/nested").bytes()
+        assert out == PRETTY.replace(b',\n  "nested": {"x": 1,  "y": [ ]}', b"")
+        assert hyperjson.edit(b"[1, 2, 3]").delete("/2").bytes() == b"[1, 2]"
+
+    def test_edit_delete_only(self):
+        """
+        delete() of the only member leaves an empty container
+        """
+        assert hyperjson.edit(b'{\n  "a": 1\n}').delete("/a").bytes() == b"{}"
+        assert hyperjson.edit(b"[ 1 ]").delete("/0").bytes() == b"[]"
+
+    def test_edit_delete_not_found(self):
+        """
+        delete() raises if the value does not exist
+        """
+        for pointer in ("/missing", "/tags/2", "/tags/-", "/nested/x/y"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.edit(PRETTY).delete(pointer)
+            assert str(exc.value) == f"Editor delete() path not found: {pointer}"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.edit(PRETTY).delete("")
+
+    def test_edit_duplicate_keys(self):
+        """
+        set() changes the last member with a key and delete() removes every
+        member with it
+        """
+        doc = b'{"a": 1, "b": 2, "a": 3}'
+        assert hyperjson.edit(doc).set("/a", 4).bytes() == b'{"a": 1, "b": 2, "a": 4}'
+        assert hyperjson.edit(doc).delete("/a").bytes() == b'{"b": 2}'
+        assert hyperjson.edit(doc).set("/b", 5).bytes() == b'{"a": 1, "b": 5, "a": 3}'
+
+    def test_edit_pointer_escapes(self):
+        """
+        pointer tokens are unescaped and compared to unescaped keys
+        """
+        doc = b'{"a/b": 1, "c~d": 2, "\\u00e9": 3, "q\\"": 4, "": 5}'
+        editor = hyperjson.edit(doc)
+        editor.set("/a~1b", 10).set("/c~0d", 20).set("/é", 30).set('/q"', 40)
+        editor.set("/", 50)
+        assert hyperjson.loads(editor.bytes()) == {
+            "a/b": 10,
+            "c~d": 20,
+            "é": 30,
+            'q"': 40,
+            "": 50,
+        }
+        out = hyperjson.edit(b"{}").set("/x~1y", "\n").bytes()
+        assert out == b'{"x/y":"\\n"}'
+
+    def test_edit_pointer_invalid(self):
+        """
+        a pointer must be a str that is empty or starts with "/"
+        """
+        for pointer in ("a", "/a~2", "/a~", 1, None):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.edit(b'{"a": 1}').set(pointer, 1)
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.edit(b'{"a": 1}').delete(pointer)
+
+    def test_edit_chained(self):
+        """
+        set() and delete() return the editor
+        """
+        editor = hyperjson.edit(PRETTY)
+        assert editor.set("/version", 2) is editor
+        assert editor.delete("/version") is editor
+        assert isinstance(editor, hyperjson.Editor)
+
+    def test_edit_invalid(self):
+        """
+        edit() raises JSONDecodeError for a document that is not valid
+        """
+        for doc in (b"", b"[1,]", b'{"a" 1}', b"\xff"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.edit(doc)
+
+    def test_edit_type(self):
+        """
+        edit() takes bytes, bytearray, memoryview, or str and Editor cannot be
+        created directly
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.edit([b"[1]"])
+        with pytest.raises(TypeError):
+            hyperjson.Editor()
+
+    def test_edit_set_arguments(self):
+        """
+        set() takes a pointer and a value
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.edit(b"{}").set("/a")
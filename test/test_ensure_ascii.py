@@ -0,0 +1,195 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import io
+import json
+
+import pytest
+
+import hyperjson
+
+
+class TestEnsureAscii:
+    def test_ensure_ascii_str(self):
+        """
+        dumps() OPT_ENSURE_ASCII escapes non-ASCII codepoints
+        """
+        for obj in ("é", "aé\nb", "\u4e2d\u6587", "\u2028\u2029", "\x80\xff"):
+            assert (
+                hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII)
+                == json.dumps(obj, ensure_ascii=True, separators=(",", ":")).encode()
+            )
+
+    def test_ensure_ascii_astral(self):
+        """
+        dumps() OPT_ENSURE_ASCII escapes codepoints above U+FFFF as a
+        surrogate pair
+        """
+        assert (
+            hyperjson.dumps("a\U0001f600b", option=hyperjson.OPT_ENSURE_ASCII)
+            == b'"a\\ud83d\\ude00b"'
+        )
+        assert (
+            hyperjson.dumps("\U0010ffff", option=hyperjson.OPT_ENSURE_ASCII)
+            == b'"\\udbff\\udfff"'
+        )
+
+    def test_ensure_ascii_control(self):
+        """
+        dumps() OPT_ENSURE_ASCII still escapes control characters and quotes
+        """
+        obj = 'é"\\\x00\x1f\t'
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII)
+            == b'"\\u00e9\\"\\\\\\u0000\\u001f\\t"'
+        )
+
+    def test_ensure_ascii_ascii(self):
+        """
+        dumps() OPT_ENSURE_ASCII does not change ASCII output
+        """
+        obj = {"a": ["b", 1, None], "c": "d" * 64}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII) == (
+            hyperjson.dumps(obj)
+        )
+
+    def test_ensure_ascii_roundtrip(self):
+        """
+        dumps() OPT_ENSURE_ASCII output loads to the same object
+        """
+        obj = {"ключ": ["значение", "\U0001f600" * 20, "ü" * 100]}
+        output = hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII)
+        assert output.isascii()
+        assert hyperjson.loads(output) == obj
+
+    def test_ensure_ascii_keys(self):
+        """
+        dumps() OPT_ENSURE_ASCII escapes dict keys
+        """
+        obj = {"é": 1, "ü": {"ß": 2}}
+        expected = b'{"\\u00e9":1,"\\u00fc":{"\\u00df":2}}'
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII) == expected
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_ENSURE_ASCII | hyperjson.OPT_SORT_KEYS
+            )
+            == expected
+        )
+
+    def test_ensure_ascii_shared_keys(self):
+        """
+        dumps() OPT_ENSURE_ASCII escapes keys of dicts sharing a keys object
+        """
+
+        class Row:
+            def __init__(self):
+                self.é = "ü"
+
+        rows = [Row().__dict__ for _ in range(4)]
+        assert (
+            hyperjson.dumps(rows, option=hyperjson.OPT_ENSURE_ASCII)
+            == b"[" + b",".join([b'{"\\u00e9":"\\u00fc"}'] * 4) + b"]"
+        )
+        assert hyperjson.dumps(rows) == (
+            b"[" + b",".join(['{"é":"ü"}'.encode()] * 4) + b"]"
+        )
+
+    def test_ensure_ascii_repeated(self):
+        """
+        dumps() OPT_ENSURE_ASCII escapes a repeated long str each time
+        """
+        value = "é" * 32
+        obj = [value] * 4
+        expected = json.dumps(obj, ensure_ascii=True, separators=(",", ":"))
+        assert hyperjson.dumps(obj) == json.dumps(
+            obj, ensure_ascii=False, separators=(",", ":")
+        ).encode("utf-8")
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII)
+            == expected.encode()
+        )
+
+    def test_ensure_ascii_default(self):
+        """
+        dumps() OPT_ENSURE_ASCII called from default of dumps() without it
+        """
+        value = "é" * 32
+
+        class Custom:
+            pass
+
+        def default(obj):
+            return hyperjson.dumps(
+                [value, value], option=hyperjson.OPT_ENSURE_ASCII
+            ).decode()
+
+        inner = json.dumps([value, value], ensure_ascii=True, separators=(",", ":"))
+        assert hyperjson.dumps([value, value, Custom()], default=default) == (
+            json.dumps(
+                [value, value, inner], ensure_ascii=False, separators=(",", ":")
+            ).encode("utf-8")
+        )
+
+    def test_ensure_ascii_indent(self):
+        """
+        dumps() OPT_ENSURE_ASCII and OPT_INDENT_2
+        """
+        obj = {"é": ["ü"]}
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_ENSURE_ASCII | hyperjson.OPT_INDENT_2
+            )
+            == json.dumps(obj, ensure_ascii=True, indent=2).encode()
+        )
+
+    def test_ensure_ascii_str_subclass(self):
+        """
+        dumps() OPT_ENSURE_ASCII str subclass
+        """
+
+        class Sub(str):
+            pass
+
+        assert (
+            hyperjson.dumps(Sub("é"), option=hyperjson.OPT_ENSURE_ASCII)
+            == b'"\\u00e9"'
+        )
+
+    def test_ensure_ascii_invalid_str(self):
+        """
+        dumps() OPT_ENSURE_ASCII does not serialize a lone surrogate
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps("\ud800", option=hyperjson.OPT_ENSURE_ASCII)
+
+    def test_ensure_ascii_loads_tracked(self):
+        """
+        dumps() OPT_ENSURE_ASCII of an unmodified loads_tracked() result
+        """
+        obj = hyperjson.loads_tracked('{"a":"é"}'.encode())
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_ENSURE_ASCII)
+            == b'{"a":"\\u00e9"}'
+        )
+
+    def test_ensure_ascii_builder(self):
+        """
+        Builder OPT_ENSURE_ASCII escapes keys and values
+        """
+        builder = hyperjson.Builder(option=hyperjson.OPT_ENSURE_ASCII)
+        builder.start_object()
+        builder.key("é")
+        builder.value(["ü"])
+        builder.end_object()
+        assert builder.finish() == b'{"\\u00e9":["\\u00fc"]}'
+
+    def test_ensure_ascii_lines_writer(self):
+        """
+        LinesWriter OPT_ENSURE_ASCII
+        """
+        fileobj = io.BytesIO()
+        with hyperjson.LinesWriter(
+            fileobj, option=hyperjson.OPT_ENSURE_ASCII
+        ) as writer:
+            writer.write({"é": "ü"})
+        assert fileobj.getvalue() == b'{"\\u00e9":"\\u00fc"}\n'
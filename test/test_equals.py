@@ -0,0 +1,75 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import datetime
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: float
+
+
+class TestEquals:
+    def test_equals_key_order(self):
+        """
+        equals() compares objects regardless of the order of their keys
+        """
+        assert hyperjson.equals(
+            b'{"a":1,"b":[2,{"c":3,"d":4}]}',
+            b'{ "b": [2, {"d": 4, "c": 3}], "a": 1 }',
+        )
+        assert not hyperjson.equals(b'{"a":1}', b'{"a":1,"b":2}')
+        assert not hyperjson.equals(b'{"a":1}', b'{"b":1}')
+
+    def test_equals_numbers(self):
+        """
+        equals() compares numbers by value
+        """
+        assert hyperjson.equals(b"[1, 1.0, 1e2, -0, 0.50]", b"[1.00, 1, 100, 0, 5e-1]")
+        assert not hyperjson.equals(b"1", b"1.5")
+        assert hyperjson.equals(b"18446744073709551616", b"1.8446744073709552e19")
+
+    def test_equals_bool(self):
+        """
+        equals() does not compare true and false equal to numbers
+        """
+        assert not hyperjson.equals(b"true", b"1")
+        assert not hyperjson.equals(b"[false]", b"[0]")
+        assert not hyperjson.equals(b"null", b"0")
+        assert hyperjson.equals(b"[true, null]", [True, None])
+
+    def test_equals_arrays(self):
+        """
+        equals() compares arrays in order
+        """
+        assert not hyperjson.equals(b"[1,2]", b"[2,1]")
+        assert not hyperjson.equals(b"[1,2]", b"[1,2,3]")
+        assert not hyperjson.equals(b"[]", b"{}")
+        assert not hyperjson.equals(b'"1"', b"1")
+
+    def test_equals_objects(self):
+        """
+        equals() compares an object as dumps() writes it
+        """
+        assert hyperjson.equals({"a": (1, 2)}, b'{"a":[1,2]}')
+        assert hyperjson.equals(Point(1, 2), {"y": 2, "x": 1.0})
+        assert hyperjson.equals(datetime.date(2025, 1, 2), memoryview(b'"2025-01-02"'))
+        assert hyperjson.equals(bytearray(b"[]"), [])
+
+    def test_equals_invalid(self):
+        """
+        equals() raises JSONDecodeError for an invalid document and
+        JSONEncodeError for an object that cannot be serialized
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.equals(b"[", b"[]")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.equals(b"[]", object())
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.equals(b"[]")
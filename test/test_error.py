@@ -190,3 +190,83 @@ class TestJsonEncodeError:
         with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
             hyperjson.dumps(10**60)
         assert exc_info.type == hyperjson.JSONEncodeError
+
+
+class SerializationError(Exception):
+    pass
+
+
+class TestExcClass:
+    def test_dumps_exc_class(self):
+        """
+        dumps() exc_class is raised instead of JSONEncodeError
+        """
+        with pytest.raises(SerializationError) as exc_info:
+            hyperjson.dumps(Custom(), exc_class=SerializationError)
+        assert exc_info.type == SerializationError
+        assert str(exc_info.value) == "Type is not JSON serializable: Custom"
+        assert exc_info.value.__cause__ is None
+
+    def test_dumps_exc_class_chain_default(self):
+        """
+        dumps() exc_class chains the exception raised by default
+        """
+        with pytest.raises(SerializationError) as exc_info:
+            hyperjson.dumps(
+                Custom(),
+                default=default_customerror,
+                exc_class=SerializationError,
+            )
+        assert isinstance(exc_info.value.__cause__, CustomException)
+        assert str(exc_info.value.__cause__) == CUSTOM_ERROR_MESSAGE
+
+    def test_dumps_exc_class_integer(self):
+        """
+        dumps() exc_class for an integer out of range
+        """
+        with pytest.raises(SerializationError):
+            hyperjson.dumps(10**60, exc_class=SerializationError)
+
+    def test_dumps_exc_class_none(self):
+        """
+        dumps() exc_class=None raises JSONEncodeError
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps(Custom(), exc_class=None)
+        assert exc_info.type == hyperjson.JSONEncodeError
+
+    def test_dumps_exc_class_success(self):
+        """
+        dumps() exc_class does not change output
+        """
+        assert hyperjson.dumps([1], exc_class=SerializationError) == b"[1]"
+
+    def test_dumps_exc_class_invalid(self):
+        """
+        dumps() exc_class that is not an exception type
+        """
+        for exc_class in (str, SerializationError("a"), 1):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+                hyperjson.dumps([], exc_class=exc_class)  # type: ignore
+            assert str(exc_info.value) == "dumps() exc_class must be an exception type"
+
+    def test_dumps_exc_class_arguments(self):
+        """
+        dumps() exc_class does not apply to invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps([], option=-1, exc_class=SerializationError)
+
+    def test_dumps_exc_class_constructor(self):
+        """
+        dumps() exc_class that stores the message
+        """
+
+        class WithMessage(Exception):
+            def __init__(self, message):
+                super().__init__(message)
+                self.message = message
+
+        with pytest.raises(WithMessage) as exc_info:
+            hyperjson.dumps({"a": Custom()}, exc_class=WithMessage)
+        assert exc_info.value.message == "Type is not JSON serializable: Custom"
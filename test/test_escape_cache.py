@@ -0,0 +1,114 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+import sys
+
+import hyperjson
+
+
+class TestEscapeCache:
+    def test_repeated_str_list(self):
+        """
+        dumps() repeated identical str in list
+        """
+        value = "status: \"active\"\né\U0001f600 with tail"
+        obj = [value] * 1000
+        assert hyperjson.dumps(obj) == json.dumps(
+            obj,
+            ensure_ascii=False,
+            separators=(",", ":"),
+        ).encode("utf-8")
+
+    def test_repeated_str_dict_values(self):
+        """
+        dumps() repeated identical str as dict values in list of dict
+        """
+        kind = "telemetry.event.kind"
+        level = "level\twith\ttabs and more"
+        obj = [{"kind": kind, "level": level, "idx": idx} for idx in range(500)]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_repeated_str_indent(self):
+        """
+        dumps() repeated identical str with OPT_INDENT_2
+        """
+        value = "a long enough \"quoted\" value"
+        obj = {"a": [value, value, value], "b": value}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2) == json.dumps(
+            obj,
+            indent=2,
+        ).encode("utf-8")
+
+    def test_distinct_equal_str(self):
+        """
+        dumps() equal but distinct str objects
+        """
+        obj = ["".join(("abcdefghijklmnop", str(idx % 3))) for idx in range(300)]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_many_distinct_str(self):
+        """
+        dumps() more distinct repeated str than cache slots
+        """
+        values = [f"value number {idx:08d}" for idx in range(2000)]
+        obj = values * 3
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_large_str(self):
+        """
+        dumps() repeated str larger than the cache limit
+        """
+        value = "\n" * (1024 * 1024)
+        obj = [value, value, value]
+        assert hyperjson.loads(hyperjson.dumps(obj)) == obj
+
+    def test_short_str(self):
+        """
+        dumps() repeated str below the cache threshold
+        """
+        obj = ["a\n"] * 100
+        assert hyperjson.dumps(obj) == b"[" + b",".join([b'"a\\n"'] * 100) + b"]"
+
+    def test_refcount(self):
+        """
+        dumps() does not leak references to cached str
+        """
+        value = "refcount checked value\n"
+        ref = sys.getrefcount(value)
+        hyperjson.dumps([value] * 100)
+        assert sys.getrefcount(value) == ref
+
+    def test_nested_dumps_in_default(self):
+        """
+        dumps() with default calling dumps() on the same str
+        """
+        value = "shared across nested calls"
+
+        class Custom:
+            pass
+
+        def default(obj):
+            return hyperjson.dumps([value, value]).decode("utf-8")
+
+        obj = [value, value, Custom(), value]
+        assert hyperjson.loads(hyperjson.dumps(obj, default=default)) == [
+            value,
+            value,
+            hyperjson.dumps([value, value]).decode("utf-8"),
+            value,
+        ]
+
+    def test_error_after_cache(self):
+        """
+        dumps() error after str was cached
+        """
+        value = "cached before the error"
+        try:
+            hyperjson.dumps([value, value, value, object()])
+        except hyperjson.JSONEncodeError:
+            pass
+        assert hyperjson.dumps([value, value]) == b'["%s","%s"]' % (
+            value.encode(),
+            value.encode(),
+        )
@@ -0,0 +1,83 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestExactFloats:
+    def test_exact_floats(self):
+        """
+        loads() exact_floats=True reads a float whose digits it keeps
+        """
+        for val in (
+            "0.1",
+            "1.50",
+            "-0.0",
+            "15e-1",
+            "0.15E1",
+            "1.7976931348623157e308",
+            "5e-324",
+            "123456789012345.6",
+            "0.30000000000000004",
+            "1.00000000000000000000",
+        ):
+            assert hyperjson.loads(val, exact_floats=True) == float(val)
+
+    def test_exact_floats_lossy(self):
+        """
+        loads() exact_floats=True raises for a float whose digits are rounded
+        """
+        for val in (
+            "0.30000000000000001",
+            "3.141592653589793238",
+            "1.000000000000000001",
+            "9007199254740993.0",
+            "1e-400",
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads(f"[1, {val}]", exact_floats=True)
+            assert exc.value.msg == "number loses precision when parsed as double"
+            assert exc.value.pos == 4
+            assert hyperjson.loads(val) == float(val)
+
+    def test_exact_floats_int(self):
+        """
+        loads() exact_floats=True does not apply to integers
+        """
+        assert hyperjson.loads(
+            "[9007199254740993, 18446744073709551616]", exact_floats=True
+        ) == [9007199254740993, 18446744073709551616.0]
+
+    def test_exact_floats_parse_float(self):
+        """
+        loads() exact_floats=True does not apply to numbers passed to
+        parse_float
+        """
+        assert (
+            hyperjson.loads(
+                "0.30000000000000001", exact_floats=True, parse_float=str
+            )
+            == "0.30000000000000001"
+        )
+
+    def test_exact_floats_json5(self):
+        """
+        loads() exact_floats=True applies to JSON5 numbers
+        """
+        assert hyperjson.loads("[.5, 5., +1.25]", json5=True, exact_floats=True) == [
+            0.5,
+            5.0,
+            1.25,
+        ]
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads("+.30000000000000001", json5=True, exact_floats=True)
+
+    def test_exact_floats_invalid(self):
+        """
+        loads() exact_floats must be a bool
+        """
+        with pytest.raises(TypeError) as exc:
+            hyperjson.loads("1", exact_floats=1)  # type: ignore
+        assert str(exc.value) == "loads() exact_floats must be a bool"
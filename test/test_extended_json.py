@@ -0,0 +1,258 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import base64
+import datetime
+import decimal
+import math
+import uuid
+
+import pytest
+
+import hyperjson
+
+UTC = datetime.timezone.utc
+
+
+class TestExtendedJsonDumps:
+    def test_relaxed_numbers(self):
+        """
+        dumps() extended_json="relaxed" writes numbers as JSON except floats
+        that are not finite
+        """
+        assert hyperjson.dumps([1, 2**40, 1.5], extended_json="relaxed") == (
+            b"[1,1099511627776,1.5]"
+        )
+        assert hyperjson.dumps(
+            [float("nan"), float("inf"), float("-inf")], extended_json="relaxed"
+        ) == (
+            b'[{"$numberDouble":"NaN"},{"$numberDouble":"Infinity"},'
+            b'{"$numberDouble":"-Infinity"}]'
+        )
+
+    def test_canonical_numbers(self):
+        """
+        dumps() extended_json="canonical" wraps each number in its type
+        """
+        assert hyperjson.dumps(
+            {"a": 1, "b": -(2**40), "c": 1.5}, extended_json="canonical"
+        ) == (
+            b'{"a":{"$numberInt":"1"},"b":{"$numberLong":"-1099511627776"},'
+            b'"c":{"$numberDouble":"1.5"}}'
+        )
+
+    def test_canonical_int_64_bits(self):
+        """
+        dumps() extended_json="canonical" raises for an int of more than 64 bits
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(2**64, extended_json="canonical")
+
+    def test_date(self):
+        """
+        dumps() extended_json= writes a datetime as "$date", an ISO-8601 string
+        in relaxed mode from 1970 and milliseconds otherwise
+        """
+        dt = datetime.datetime(2020, 1, 2, 3, 4, 5, 678901, tzinfo=UTC)
+        assert hyperjson.dumps(dt, extended_json="relaxed") == (
+            b'{"$date":"2020-01-02T03:04:05.678Z"}'
+        )
+        assert hyperjson.dumps(dt, extended_json="canonical") == (
+            b'{"$date":{"$numberLong":"1577934245678"}}'
+        )
+        old = datetime.datetime(1960, 1, 1)
+        assert hyperjson.dumps(old, extended_json="relaxed") == (
+            b'{"$date":{"$numberLong":"-315619200000"}}'
+        )
+
+    def test_date_offset(self):
+        """
+        dumps() extended_json= writes a datetime with an offset in UTC
+        """
+        tz = datetime.timezone(datetime.timedelta(hours=2))
+        dt = datetime.datetime(2020, 1, 1, 1, tzinfo=tz)
+        assert hyperjson.dumps(dt, extended_json="relaxed") == (
+            b'{"$date":"2019-12-31T23:00:00.000Z"}'
+        )
+
+    def test_binary(self):
+        """
+        dumps() extended_json= writes bytes and UUID as "$binary"
+        """
+        assert hyperjson.dumps(
+            [b"\x00\x01hi", uuid.UUID(int=1)], extended_json="relaxed"
+        ) == (
+            b'[{"$binary":{"base64":"AAFoaQ==","subType":"00"}},'
+            b'{"$binary":{"base64":"AAAAAAAAAAAAAAAAAAAAAQ==","subType":"04"}}]'
+        )
+
+    def test_binary_large(self):
+        """
+        dumps() extended_json= writes bytes longer than one chunk of base64
+        """
+        for length in (3 * 1024 - 1, 3 * 1024, 3 * 1024 + 1, 100_000):
+            data = bytes(range(256)) * (length // 256) + b"\xff" * (length % 256)
+            expected = (
+                b'{"$binary":{"base64":"%s","subType":"00"}}' % base64.b64encode(data)
+            )
+            assert hyperjson.dumps(data, extended_json="relaxed") == expected
+            assert hyperjson.dumps_length(data, extended_json="relaxed") == len(
+                expected
+            )
+            indented = hyperjson.dumps(
+                {"a": data}, extended_json="relaxed", option=hyperjson.OPT_INDENT_2
+            )
+            assert hyperjson.loads(indented) == {"a": hyperjson.loads(expected)}
+
+    def test_decimal(self):
+        """
+        dumps() extended_json= writes Decimal as "$numberDecimal"
+        """
+        assert hyperjson.dumps(decimal.Decimal("1.10"), extended_json="canonical") == (
+            b'{"$numberDecimal":"1.10"}'
+        )
+
+    def test_non_str_keys(self):
+        """
+        dumps() extended_json= writes keys as OPT_NON_STR_KEYS does
+        """
+        assert (
+            hyperjson.dumps(
+                {1: 2}, extended_json="canonical", option=hyperjson.OPT_NON_STR_KEYS
+            )
+            == b'{"1":{"$numberInt":"2"}}'
+        )
+
+    def test_dumps_batch_and_length(self):
+        """
+        dumps_batch() and dumps_length() accept extended_json=
+        """
+        assert hyperjson.dumps_batch([[1]], extended_json="canonical") == (
+            b'[{"$numberInt":"1"}]\n',
+            [0, 21],
+        )
+        assert hyperjson.dumps_length([1], extended_json="canonical") == 20
+
+    def test_default(self):
+        """
+        dumps() without extended_json= or with None is unchanged
+        """
+        assert hyperjson.dumps([1, 1.5], extended_json=None) == b"[1,1.5]"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(b"a")
+
+    def test_other_keyword_arguments(self):
+        """
+        dumps() extended_json= is independent of the other keyword arguments
+        """
+        assert (
+            hyperjson.dumps([1.23456, 2], extended_json="relaxed", float_decimals=2)
+            == b"[1.23,2]"
+        )
+        for float_decimals in (0, 15):
+            assert hyperjson.dumps(
+                {"b": [1.5], "a": None},
+                extended_json="canonical",
+                float_decimals=float_decimals,
+                key_order="natural",
+                non_finite="raise",
+            ) == b'{"a":null,"b":[{"$numberDouble":"1.5"}]}'
+        assert hyperjson.dumps([1.25], float_decimals=15) == b"[1.25]"
+
+    def test_invalid(self):
+        """
+        dumps() extended_json= must be "relaxed" or "canonical"
+        """
+        for val in ("strict", True, 1):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps(1, extended_json=val)
+
+
+class TestExtendedJsonLoads:
+    def test_roundtrip(self):
+        """
+        loads(extended_json=True) reads what dumps(extended_json=) writes
+        """
+        obj = {
+            "int": 1,
+            "long": 2**40,
+            "float": 1.5,
+            "inf": float("inf"),
+            "dt": datetime.datetime(2020, 1, 2, 3, 4, 5, 678000, tzinfo=UTC),
+            "old": datetime.datetime(1960, 1, 1, tzinfo=UTC),
+            "bytes": b"\x00\x01hi",
+            "uuid": uuid.UUID(int=1),
+            "decimal": decimal.Decimal("1.10"),
+        }
+        for mode in ("relaxed", "canonical"):
+            data = hyperjson.dumps(obj, extended_json=mode)
+            assert hyperjson.loads(data, extended_json=True) == obj
+
+    def test_nan(self):
+        """
+        loads(extended_json=True) reads "$numberDouble" of "NaN"
+        """
+        assert math.isnan(
+            hyperjson.loads('{"$numberDouble": "NaN"}', extended_json=True)
+        )
+
+    def test_date_forms(self):
+        """
+        loads(extended_json=True) reads "$date" with an offset, without one
+        as UTC, and of milliseconds
+        """
+        expected = datetime.datetime(2019, 12, 31, 23, tzinfo=UTC)
+        for data in (
+            '{"$date": "2020-01-01T00:00:00+01:00"}',
+            '{"$date": "2019-12-31T23:00:00"}',
+            '{"$date": 1577833200000}',
+        ):
+            assert hyperjson.loads(data, extended_json=True) == expected
+
+    def test_legacy_binary_and_uuid(self):
+        """
+        loads(extended_json=True) reads the legacy "$binary" and "$uuid"
+        """
+        assert hyperjson.loads(
+            '{"$binary": "aGk=", "$type": "00"}', extended_json=True
+        ) == b"hi"
+        assert hyperjson.loads(
+            '{"$uuid": "00000000-0000-0000-0000-000000000001"}', extended_json=True
+        ) == uuid.UUID(int=1)
+
+    def test_other_objects(self):
+        """
+        loads(extended_json=True) leaves other objects, including other
+        wrappers, unchanged
+        """
+        data = '{"$oid": "abc", "a": {"$numberInt": "1", "b": 2}}'
+        assert hyperjson.loads(data, extended_json=True) == {
+            "$oid": "abc",
+            "a": {"$numberInt": "1", "b": 2},
+        }
+        assert hyperjson.loads('{"$numberInt": "1"}') == {"$numberInt": "1"}
+
+    def test_invalid(self):
+        """
+        loads(extended_json=True) raises for a wrapper of an invalid value
+        """
+        for data in (
+            '{"$numberInt": "3000000000"}',
+            '{"$numberLong": 1}',
+            '{"$numberDouble": "one"}',
+            '{"$numberDecimal": "x"}',
+            '{"$date": "x"}',
+            '{"$date": {"$numberLong": "999999999999999999"}}',
+            '{"$binary": {"base64": "a", "subType": "00"}}',
+            '{"$binary": {"base64": "aGk=", "subType": "04"}}',
+            '{"$uuid": "x"}',
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(data, extended_json=True)
+
+    def test_invalid_argument(self):
+        """
+        loads() extended_json must be a bool
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads("1", extended_json=1)
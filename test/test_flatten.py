@@ -0,0 +1,84 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+NESTED = {"a": {"b": 1, "c": {"d": [1, {"e": 2}], "f": {}}}, "g": None}
+FLAT = {"a.b": 1, "a.c.d": [1, {"e": 2}], "a.c.f": {}, "g": None}
+
+
+class TestFlatten:
+    def test_flatten(self):
+        """
+        flatten() joins the keys on the way to each value that is not a
+        non-empty object
+        """
+        assert hyperjson.flatten(NESTED) == FLAT
+        assert list(hyperjson.flatten(NESTED)) == list(FLAT)
+        assert hyperjson.flatten({}) == {}
+
+    def test_flatten_sep(self):
+        """
+        flatten() joins keys with sep
+        """
+        assert hyperjson.flatten({"a": {"b": 1}}, sep="/") == {"a/b": 1}
+        assert hyperjson.flatten({"a": {"b": 1}}, "__") == {"a__b": 1}
+
+    def test_flatten_document(self):
+        """
+        flatten() reads a document of an object
+        """
+        assert hyperjson.flatten(hyperjson.dumps(NESTED)) == FLAT
+
+    def test_unflatten(self):
+        """
+        unflatten() reverses flatten()
+        """
+        assert hyperjson.unflatten(FLAT) == NESTED
+        assert hyperjson.unflatten(hyperjson.dumps(FLAT)) == NESTED
+        assert hyperjson.unflatten({"a/b": 1}, sep="/") == {"a": {"b": 1}}
+
+    def test_unflatten_not_modified(self):
+        """
+        unflatten() does not add to an object that is a value of the mapping
+        """
+        value = {}
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.unflatten({"a": value, "a.b": 1})
+        assert str(exc.value) == "unflatten() key conflicts with another key: a.b"
+        assert value == {}
+
+    def test_unflatten_conflict(self):
+        """
+        unflatten() raises for a key that is both a value and an object
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.unflatten({"a.b": 1, "a": 2})
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.unflatten({"a": 1, "a.b": 2})
+
+    def test_flatten_invalid(self):
+        """
+        flatten() and unflatten() raise for an argument that is not an
+        object, a key that is not a str, or a sep that is not a non-empty
+        str
+        """
+        for func in (hyperjson.flatten, hyperjson.unflatten):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func([1])
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func(b"[1]")
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func({1: 2})
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func({}, sep="")
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func({}, sep=1)
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func({}, ".", sep=".")
+            with pytest.raises(hyperjson.JSONEncodeError):
+                func({}, other=1)
+            with pytest.raises(hyperjson.JSONDecodeError):
+                func(b"{")
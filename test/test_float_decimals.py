@@ -0,0 +1,104 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestFloatDecimals:
+    def test_float_decimals(self):
+        """
+        dumps() float_decimals= rounds floats
+        """
+        assert (
+            hyperjson.dumps(
+                [1.23456789, 123456.789, 0.1 + 0.2, 52.5200066, 13.4049540],
+                float_decimals=6,
+            )
+            == b"[1.234568,123456.789,0.3,52.520007,13.404954]"
+        )
+
+    def test_float_decimals_zero(self):
+        """
+        dumps() float_decimals=0 rounds half away from zero
+        """
+        assert (
+            hyperjson.dumps([1.5, 2.5, -1.5, 3.14159], float_decimals=0)
+            == b"[2.0,3.0,-2.0,3.0]"
+        )
+
+    def test_float_decimals_small(self):
+        """
+        dumps() float_decimals= of values smaller than the precision
+        """
+        assert (
+            hyperjson.dumps([1.5e-6, 2.5e-10, -3e-7], float_decimals=6)
+            == b"[2e-6,0.0,-0.0]"
+        )
+
+    def test_float_decimals_large(self):
+        """
+        dumps() float_decimals= of values too large to have that many
+        decimal places
+        """
+        obj = [1e300, -1.7976931348623157e308, 12345678901234.567]
+        assert hyperjson.dumps(obj, float_decimals=15) == hyperjson.dumps(obj)
+
+    def test_float_decimals_nonfinite(self):
+        """
+        dumps() float_decimals= of nan and infinity
+        """
+        assert (
+            hyperjson.dumps([float("nan"), float("inf")], float_decimals=2)
+            == b"[null,null]"
+        )
+
+    def test_float_decimals_nested(self):
+        """
+        dumps() float_decimals= applies to values only
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [1, 1.234, "1.234", {"b": 2.345}], 1.234: True},
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_INDENT_2,
+                float_decimals=1,
+            )
+            == b'{\n  "a": [\n    1,\n    1.2,\n    "1.234",\n    {\n      "b": 2.3\n    }\n  ],\n  "1.234": true\n}'
+        )
+
+    def test_float_decimals_none(self):
+        """
+        dumps() float_decimals=None writes the shortest representation
+        """
+        assert hyperjson.dumps(1.23456789, float_decimals=None) == b"1.23456789"
+
+    def test_float_decimals_loads_tracked(self):
+        """
+        dumps() float_decimals= of a loads_tracked() result is rounded
+        """
+        obj = hyperjson.loads_tracked(b"[1.23456]")
+        assert hyperjson.dumps(obj, float_decimals=2) == b"[1.23]"
+
+    def test_float_decimals_length_batch(self):
+        """
+        dumps_length() and dumps_batch() float_decimals=
+        """
+        obj = {"lat": 52.5200066, "lon": 13.4049540}
+        output = hyperjson.dumps(obj, float_decimals=3)
+        assert output == b'{"lat":52.52,"lon":13.405}'
+        assert hyperjson.dumps_length(obj, float_decimals=3) == len(output)
+        assert hyperjson.dumps_batch([obj], float_decimals=3) == (
+            output + b"\n",
+            [0, len(output) + 1],
+        )
+
+    def test_float_decimals_invalid(self):
+        """
+        dumps() float_decimals= that is not an int from 0 to 15
+        """
+        for val in (-1, 16, 2**64, 1.0, "1", True):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps(1.0, float_decimals=val)  # type: ignore
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps_length(1.0, float_decimals=val)  # type: ignore
@@ -0,0 +1,170 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestFloatFormat:
+    def test_float_format_fixed(self):
+        """
+        dumps() float_format=".Nf" writes N digits after the decimal point
+        """
+        obj = [19.9, 0.1 + 0.2, 2.675, -0.004, 1e20, 3]
+        assert hyperjson.dumps(obj, float_format=".2f") == (
+            b"[19.90,0.30,2.67,-0.00,100000000000000000000.00,3]"
+        )
+        assert hyperjson.dumps(obj[:-1], float_format=".2f") == (
+            ("[" + ",".join(format(val, ".2f") for val in obj[:-1]) + "]").encode()
+        )
+
+    def test_float_format_fixed_zero(self):
+        """
+        dumps() float_format=".0f" writes no decimal point
+        """
+        assert hyperjson.dumps([1.4, 2.5, 3.5], float_format=".0f") == b"[1,2,4]"
+
+    def test_float_format_callable(self):
+        """
+        dumps() float_format= callable is given each float and its str is
+        written
+        """
+        calls = []
+
+        def fmt(val):
+            calls.append(val)
+            return f"{val:.3e}"
+
+        assert (
+            hyperjson.dumps({"a": [1.5, 2], "b": 12345.678}, float_format=fmt)
+            == b'{"a":[1.500e+00,2],"b":1.235e+04}'
+        )
+        assert calls == [1.5, 12345.678]
+
+    def test_float_format_nested(self):
+        """
+        dumps() float_format= applies to values only
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [1.234, {"b": 2.0}], 1.5: True},
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_INDENT_2,
+                float_format=".1f",
+            )
+            == b'{\n  "a": [\n    1.2,\n    {\n      "b": 2.0\n    }\n  ],\n  "1.5": true\n}'
+        )
+
+    def test_float_format_nonfinite(self):
+        """
+        dumps() float_format= does not apply to nan and infinity
+        """
+        assert (
+            hyperjson.dumps(
+                [float("nan"), float("-inf")],
+                float_format=lambda val: "0",
+                non_finite="literal",
+            )
+            == b"[NaN,-Infinity]"
+        )
+
+    def test_float_format_loads_tracked(self):
+        """
+        dumps() float_format= of a loads_tracked() result is applied
+        """
+        obj = hyperjson.loads_tracked(b"[1.5]")
+        assert hyperjson.dumps(obj, float_format=".2f") == b"[1.50]"
+
+    def test_float_format_length_batch(self):
+        """
+        dumps_length() and dumps_batch() float_format=
+        """
+        obj = {"price": 9.5}
+        output = hyperjson.dumps(obj, float_format=".2f")
+        assert output == b'{"price":9.50}'
+        assert hyperjson.dumps_length(obj, float_format=".2f") == len(output)
+        assert hyperjson.dumps_length(obj, float_format=lambda val: "9.50") == len(
+            output
+        )
+        assert hyperjson.dumps_batch([obj], float_format=".2f") == (
+            output + b"\n",
+            [0, len(output) + 1],
+        )
+
+    def test_float_format_none(self):
+        """
+        dumps() float_format=None writes the shortest representation
+        """
+        assert hyperjson.dumps(0.1, float_format=None) == b"0.1"
+
+    def test_float_format_not_number(self):
+        """
+        dumps() float_format= callable must return a str that is a JSON number
+        """
+        for val in ("1.", ".5", "01", "1e", "NaN", "1,5", " 1", "", "-", "0x1", 1.5):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps([1.0], float_format=lambda _, val=val: val)
+            assert (
+                str(exc.value)
+                == "float_format callable must return a str that is a JSON number"
+            )
+        for val in ("0", "-0.5", "1E+5", "12.50e-3"):
+            assert hyperjson.dumps(1.0, float_format=lambda _, val=val: val) == (
+                val.encode()
+            )
+
+    def test_float_format_raises(self):
+        """
+        dumps() float_format= callable raising is chained as the cause
+        """
+
+        def fmt(val):
+            raise ValueError("rejected")
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps([1.0], float_format=fmt)
+        assert isinstance(exc.value.__cause__, ValueError)
+
+    def test_float_format_invalid(self):
+        """
+        dumps() float_format= that is not ".Nf" with N from 0 to 15, a
+        callable, or None
+        """
+        for val in (".16f", ".f", "2f", ".2e", "%.2f", ".-1f", 2, b".2f"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps(1.0, float_format=val)  # type: ignore
+            assert str(exc.value) == (
+                'dumps() float_format must be ".Nf" with N from 0 to 15, a '
+                "callable, or None"
+            )
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps_length(1.0, float_format=val)  # type: ignore
+
+    def test_float_format_float_decimals(self):
+        """
+        dumps() float_format= cannot be combined with float_decimals=
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(1.0, float_decimals=2, float_format=".2f")
+        assert (
+            str(exc.value)
+            == "dumps() float_format cannot be combined with float_decimals"
+        )
+
+    def test_float_format_default(self):
+        """
+        dumps() float_format= does not limit the calls of default=
+        """
+
+        class Custom:
+            pass
+
+        calls = []
+
+        def default(_):
+            calls.append(1)
+            return Custom() if len(calls) < 200 else 1.5
+
+        assert hyperjson.dumps(Custom(), default=default, float_format=".15f") == (
+            b"1.500000000000000"
+        )
@@ -0,0 +1,106 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import datetime
+import hashlib
+import uuid
+
+import pytest
+
+import hyperjson
+
+
+def canonical_sha256(obj):
+    return hashlib.sha256(
+        hyperjson.dumps(obj, option=hyperjson.OPT_SORT_KEYS)
+    ).digest()
+
+
+@dataclasses.dataclass
+class Dataclass:
+    b: int
+    a: str
+
+
+class TestHash:
+    def test_hash(self):
+        """
+        hash() is the SHA-256 of the output with sorted keys
+        """
+        obj = {"b": [1, 2.5, None, True], "a": {"d": "é", "c": "\n "}}
+        assert hyperjson.hash(obj) == canonical_sha256(obj)
+        assert len(hyperjson.hash(obj)) == 32
+
+    def test_hash_key_order(self):
+        """
+        hash() does not depend on the order of keys
+        """
+        assert hyperjson.hash({"a": 1, "b": {"c": 1, "d": 2}}) == hyperjson.hash(
+            {"b": {"d": 2, "c": 1}, "a": 1}
+        )
+
+    def test_hash_types(self):
+        """
+        hash() hashes the output of each supported type
+        """
+        for obj in (
+            None,
+            "",
+            "a" * 10000,
+            0,
+            -(2**63),
+            2**64 - 1,
+            1.0e-7,
+            [],
+            {},
+            uuid.UUID("7202d115-7ff3-4c81-a7c1-2a1f067b1ece"),
+            datetime.datetime(2025, 1, 2, 3, 4, 5, 678901),
+            datetime.date(2025, 1, 2),
+            Dataclass(1, "x"),
+            [{"k" * 100: list(range(1000))}] * 10,
+        ):
+            assert hyperjson.hash(obj) == canonical_sha256(obj)
+
+    def test_hash_known(self):
+        """
+        hash() of {} is the SHA-256 of b"{}"
+        """
+        assert hyperjson.hash({}).hex() == (
+            "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        )
+
+    def test_hash_algorithm(self):
+        """
+        hash() takes algorithm="sha256" positionally or as a keyword
+        """
+        assert hyperjson.hash([1], "sha256") == canonical_sha256([1])
+        assert hyperjson.hash([1], algorithm="sha256") == canonical_sha256([1])
+
+    def test_hash_invalid_algorithm(self):
+        """
+        hash() raises JSONEncodeError for another algorithm
+        """
+        for algorithm in ("md5", "SHA256", b"sha256", None):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.hash([1], algorithm=algorithm)  # type: ignore
+
+    def test_hash_unsupported(self):
+        """
+        hash() raises JSONEncodeError for an unsupported type
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.hash({"a": object()})
+
+    def test_hash_arguments(self):
+        """
+        hash() rejects invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.hash()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.hash([], "sha256", 1)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.hash([], "sha256", algorithm="sha256")  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.hash([], option=1)  # type: ignore
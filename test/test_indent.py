@@ -130,3 +130,113 @@ class TestIndentedOutput:
             indent=2,
             ensure_ascii=False,
         ).encode("utf-8")
+
+
+class TestIndentSize:
+    def test_indent_4(self):
+        """
+        OPT_INDENT_4 is equivalent to indent=4
+        """
+        obj = {"a": "b", "c": {"d": True}, "e": [1, [2, {}]], "f": []}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_4) == json.dumps(
+            obj,
+            indent=4,
+        ).encode("utf-8")
+
+    def test_indent_8(self):
+        """
+        OPT_INDENT_8 is equivalent to indent=8
+        """
+        obj = {"a": "b", "c": {"d": True}, "e": [1, [2, {}]], "f": []}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_8) == json.dumps(
+            obj,
+            indent=8,
+        ).encode("utf-8")
+
+    def test_indent_widest(self):
+        """
+        OPT_INDENT_8 takes precedence over OPT_INDENT_4 and OPT_INDENT_2
+        """
+        obj = {"a": [1]}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2 | hyperjson.OPT_INDENT_4)
+            == b'{\n    "a": [\n        1\n    ]\n}'
+        )
+        assert hyperjson.dumps(
+            obj,
+            option=hyperjson.OPT_INDENT_2
+            | hyperjson.OPT_INDENT_4
+            | hyperjson.OPT_INDENT_8,
+        ) == json.dumps(obj, indent=8).encode("utf-8")
+
+    def test_indent_4_int_list(self):
+        """
+        OPT_INDENT_4 list of int
+        """
+        obj = list(range(20))
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_4) == json.dumps(
+            obj,
+            indent=4,
+        ).encode("utf-8")
+
+    def test_indent_4_sort_keys(self):
+        """
+        OPT_INDENT_4 and OPT_SORT_KEYS
+        """
+        assert (
+            hyperjson.dumps(
+                {"b": 1, "a": 2},
+                option=hyperjson.OPT_INDENT_4 | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{\n    "a": 2,\n    "b": 1\n}'
+        )
+
+    def test_indent_8_append_newline(self):
+        """
+        OPT_INDENT_8 and OPT_APPEND_NEWLINE
+        """
+        assert (
+            hyperjson.dumps(
+                [None],
+                option=hyperjson.OPT_INDENT_8 | hyperjson.OPT_APPEND_NEWLINE,
+            )
+            == b"[\n        null\n]\n"
+        )
+
+    def test_indent_tab(self):
+        """
+        OPT_INDENT_TAB is equivalent to indent="\\t"
+        """
+        obj = {"a": "b", "c": {"d": True}, "e": [1, [2, {}]], "f": []}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_TAB) == json.dumps(
+            obj,
+            indent="\t",
+        ).encode("utf-8")
+
+    def test_indent_tab_precedence(self):
+        """
+        OPT_INDENT_TAB takes precedence over OPT_INDENT_2 and OPT_INDENT_4
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [1]},
+                option=hyperjson.OPT_INDENT_TAB
+                | hyperjson.OPT_INDENT_2
+                | hyperjson.OPT_INDENT_4,
+            )
+            == b'{\n\t"a": [\n\t\t1\n\t]\n}'
+        )
+
+    def test_indent_tab_deep(self):
+        """
+        OPT_INDENT_TAB nested beyond the initial buffer
+        """
+        obj: list = []
+        target = obj
+        for _ in range(200):
+            target.append([])
+            target = target[0]
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_TAB) == json.dumps(
+            obj,
+            indent="\t",
+        ).encode("utf-8")
@@ -0,0 +1,112 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+
+import pytest
+
+import hyperjson
+
+
+class TestIndentWidth:
+    def test_width_matrix(self):
+        """
+        dumps() width= keeps rows of a matrix on one line
+        """
+        obj = {"matrix": [[1, 2, 3], [4, 5, 6]], "name": "m"}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2, width=30)
+            == b'{\n  "matrix": [\n    [1, 2, 3],\n    [4, 5, 6]\n  ],\n  "name": "m"\n}'
+        )
+
+    def test_width_fits(self):
+        """
+        dumps() width= writes a document that fits on one line
+        """
+        obj = {"a": [1, {"b": None}], "c": "d", "e": [], "f": {}}
+        output = b'{"a": [1, {"b": null}], "c": "d", "e": [], "f": {}}'
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2, width=len(output))
+            == output
+        )
+        assert hyperjson.dumps(
+            obj, option=hyperjson.OPT_INDENT_2, width=len(output) - 1
+        ) == hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2).replace(
+            b'[\n    1,\n    {\n      "b": null\n    }\n  ]', b'[1, {"b": null}]'
+        )
+
+    def test_width_column(self):
+        """
+        dumps() width= counts from the start of the line
+        """
+        obj = {"key": [1, 2], "other": "a" * 20}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_4, width=17) == (
+            b'{\n    "key": [1, 2],\n    "other": "' + b"a" * 20 + b'"\n}'
+        )
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_4, width=16) == (
+            b'{\n    "key": [\n        1,\n        2\n    ],\n    "other": "'
+            + b"a" * 20
+            + b'"\n}'
+        )
+
+    def test_width_tab(self):
+        """
+        dumps() width= with OPT_INDENT_TAB
+        """
+        obj = [[1, 2], [3, "a" * 20]]
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_TAB, width=12)
+            == b'[\n\t[1, 2],\n\t[\n\t\t3,\n\t\t"' + b"a" * 20 + b'"\n\t]\n]'
+        )
+
+    def test_width_roundtrip(self):
+        """
+        dumps() width= output decodes to the same document
+        """
+        obj = {
+            "a": [[idx, str(idx), {"b": [idx] * idx}] for idx in range(20)],
+            "c": {"d": "e\n f,", "g": ["h" * 50]},
+            "i": [hyperjson.Fragment(b'{\n  "j": [1,\n 2]}')],
+        }
+        for width in (1, 10, 40, 80, 1000):
+            for option in (hyperjson.OPT_INDENT_2, hyperjson.OPT_INDENT_TAB):
+                output = hyperjson.dumps(
+                    obj, option=option | hyperjson.OPT_SORT_KEYS, width=width
+                )
+                assert json.loads(output) == json.loads(hyperjson.dumps(obj))
+                for line in output.split(b"\n"):
+                    line = line.rstrip(b",")
+                    # an empty array is never broken
+                    if b"[" in line and b"]" in line and not line.endswith(b"[]"):
+                        assert len(line) <= width
+
+    def test_width_length(self):
+        """
+        dumps_length() width= is the length of dumps() width=
+        """
+        obj = {"a": [[1, 2], [3, 4]], "b": list(range(100))}
+        for width in (1, 10, 80):
+            assert hyperjson.dumps_length(
+                obj, option=hyperjson.OPT_INDENT_2, width=width
+            ) == len(hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2, width=width))
+
+    def test_width_none(self):
+        """
+        dumps() width=None writes each value on its own line
+        """
+        obj = [[1, 2]]
+        assert hyperjson.dumps(
+            obj, option=hyperjson.OPT_INDENT_2, width=None
+        ) == hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2)
+
+    def test_width_invalid(self):
+        """
+        dumps() width= that is not a positive int or without indentation
+        """
+        for val in (0, -1, 1.0, "80", True):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps([1], option=hyperjson.OPT_INDENT_2, width=val)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps([1], width=80)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_length([1], width=80)
@@ -0,0 +1,84 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestIntOverflow:
+    def test_int_overflow_default(self):
+        """
+        loads() reads an integer outside of 64 bits as a float
+        """
+        assert hyperjson.loads(b"[18446744073709551616, -9223372036854775809]") == [
+            18446744073709551616.0,
+            -9223372036854775809.0,
+        ]
+        assert hyperjson.loads(
+            b"18446744073709551616", int_overflow="float"
+        ) == 18446744073709551616.0
+
+    def test_int_overflow_string(self):
+        """
+        loads() int_overflow="string" reads an integer outside of 64 bits as
+        its text
+        """
+        assert hyperjson.loads(
+            b'{"a": [18446744073709551616, 1, -9223372036854775809, 1.5]}',
+            int_overflow="string",
+        ) == {"a": ["18446744073709551616", 1, "-9223372036854775809", 1.5]}
+
+    def test_int_overflow_error(self):
+        """
+        loads() int_overflow="error" raises for an integer outside of 64 bits
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b"[1, 18446744073709551616]", int_overflow="error")
+        assert exc.value.msg == "integer exceeds 64-bit range"
+        assert exc.value.pos == 4
+
+    def test_int_overflow_64(self):
+        """
+        loads() int_overflow= does not apply to integers within 64 bits or to
+        floats
+        """
+        for val in ("string", "error"):
+            assert hyperjson.loads(
+                b"[18446744073709551615, -9223372036854775808, 1e30]",
+                int_overflow=val,
+            ) == [18446744073709551615, -9223372036854775808, 1e30]
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(b"1e400", int_overflow=val)
+
+    def test_int_overflow_json5(self):
+        """
+        loads() int_overflow= applies to JSON5 integers, including hexadecimal
+        """
+        doc = b"[+18446744073709551616, 0x10000000000000000]"
+        assert hyperjson.loads(doc, json5=True, int_overflow="string") == [
+            "+18446744073709551616",
+            "0x10000000000000000",
+        ]
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b"[0x10000000000000000]", json5=True, int_overflow="error")
+
+    def test_int_overflow_parse_int(self):
+        """
+        loads() parse_int= is called instead of applying int_overflow=
+        """
+        assert hyperjson.loads(
+            b"18446744073709551616", parse_int=int, int_overflow="error"
+        ) == 18446744073709551616
+
+    def test_int_overflow_invalid(self):
+        """
+        loads() int_overflow must be "float", "string", or "error"
+        """
+        for val in ("str", "raise", None, 1, b"float"):
+            with pytest.raises(TypeError) as exc:
+                hyperjson.loads(b"1", int_overflow=val)  # type: ignore
+            assert (
+                str(exc.value)
+                == 'loads() int_overflow must be "float", "string", or "error"'
+            )
@@ -0,0 +1,209 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import inspect
+
+import pytest
+
+import hyperjson
+
+
+class TestJoinArray:
+    def test_join_array_empty(self):
+        """
+        join_array() empty
+        """
+        assert hyperjson.join_array([]) == b"[]"
+        assert hyperjson.join_array(()) == b"[]"
+
+    def test_join_array_fragment(self):
+        """
+        join_array() Fragment
+        """
+        assert (
+            hyperjson.join_array([hyperjson.Fragment(b"{}"), hyperjson.Fragment("[1]")])
+            == b"[{},[1]]"
+        )
+
+    def test_join_array_bytes_str(self):
+        """
+        join_array() bytes and str pieces
+        """
+        assert hyperjson.join_array([b'{"a":1}', '"b"', b"null"]) == b'[{"a":1},"b",null]'
+
+    def test_join_array_iterator(self):
+        """
+        join_array() accepts any iterable
+        """
+        pieces = (hyperjson.dumps(i) for i in range(3))
+        assert hyperjson.join_array(pieces) == b"[0,1,2]"
+
+    def test_join_array_matches_dumps(self):
+        """
+        join_array() output equals dumps() of the decoded pieces
+        """
+        objs = [{"a": [1, 2]}, "🐈", None, 1.5, {"b": {}}] * 100
+        pieces = [hyperjson.dumps(each) for each in objs]
+        assert hyperjson.join_array(pieces) == hyperjson.dumps(objs)
+
+    def test_join_array_not_validated(self):
+        """
+        join_array() does not parse pieces by default
+        """
+        assert hyperjson.join_array([b"{"]) == b"[{]"
+
+    def test_join_array_validate(self):
+        """
+        join_array() validate=True
+        """
+        assert hyperjson.join_array([b"{}", b"[]"], validate=True) == b"[{},[]]"
+        with pytest.raises(hyperjson.JSONEncodeError, match="item 1 is not valid JSON"):
+            hyperjson.join_array([b"{}", b"{"], validate=True)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([b""], validate=True)
+
+    def test_join_array_validate_positional(self):
+        """
+        join_array() validate positional
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([b"{"], True)
+
+    def test_join_array_validate_bool(self):
+        """
+        join_array() validate must be bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([b"{}"], validate=1)
+
+    def test_join_array_invalid_piece(self):
+        """
+        join_array() rejects pieces that are not Fragment, bytes, or str
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([1])  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([hyperjson.Fragment(1)])  # type: ignore
+
+    def test_join_array_invalid_str(self):
+        """
+        join_array() rejects str that is not valid UTF-8
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array(["\ud800"])
+
+    def test_join_array_not_iterable(self):
+        """
+        join_array() rejects non-iterable
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array(1)  # type: ignore
+
+    def test_join_array_iterator_exception(self):
+        """
+        join_array() exception raised by iterator is the cause
+        """
+
+        def pieces():
+            yield b"1"
+            raise ValueError("upstream")
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.join_array(pieces())
+        assert isinstance(exc_info.value.__cause__, ValueError)
+
+    def test_join_array_args(self):
+        """
+        join_array() argument errors
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([], False, False)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([], foo=True)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_array([], True, validate=True)  # type: ignore
+
+    def test_join_array_signature(self):
+        """
+        join_array() valid __text_signature__
+        """
+        assert (
+            str(inspect.signature(hyperjson.join_array))
+            == "(iterable, /, validate=False)"
+        )
+
+
+class TestJoinObject:
+    def test_join_object_empty(self):
+        """
+        join_object() empty
+        """
+        assert hyperjson.join_object([]) == b"{}"
+
+    def test_join_object(self):
+        """
+        join_object() pieces
+        """
+        assert (
+            hyperjson.join_object(
+                [("a", hyperjson.Fragment(b"[1]")), ("b", b"{}"), ("c", "null")],
+            )
+            == b'{"a":[1],"b":{},"c":null}'
+        )
+
+    def test_join_object_dict_items(self):
+        """
+        join_object() dict.items()
+        """
+        upstream = {"x": b"1", "y": b"2"}
+        assert hyperjson.join_object(upstream.items()) == b'{"x":1,"y":2}'
+
+    def test_join_object_key_escaped(self):
+        """
+        join_object() escapes keys
+        """
+        key = 'a"\\\n🐈' * 64
+        assert hyperjson.join_object([(key, b"1")]) == hyperjson.dumps({key: 1})
+
+    def test_join_object_matches_dumps(self):
+        """
+        join_object() output equals dumps() of the decoded pieces
+        """
+        obj = {f"key{i}": {"value": [i, str(i)]} for i in range(500)}
+        pairs = [(k, hyperjson.dumps(v)) for k, v in obj.items()]
+        assert hyperjson.join_object(pairs) == hyperjson.dumps(obj)
+
+    def test_join_object_validate(self):
+        """
+        join_object() validate=True
+        """
+        assert hyperjson.join_object([("a", b"1")], validate=True) == b'{"a":1}'
+        with pytest.raises(hyperjson.JSONEncodeError, match='"b" is not valid JSON'):
+            hyperjson.join_object([("a", b"1"), ("b", b"tru")], validate=True)
+
+    def test_join_object_key_not_str(self):
+        """
+        join_object() key must be str
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_object([(1, b"1")])  # type: ignore
+
+    def test_join_object_not_pair(self):
+        """
+        join_object() items must be 2-tuples
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_object([("a",)])  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.join_object([["a", b"1"]])  # type: ignore
+
+    def test_join_object_signature(self):
+        """
+        join_object() valid __text_signature__
+        """
+        assert (
+            str(inspect.signature(hyperjson.join_object))
+            == "(pairs, /, validate=False)"
+        )
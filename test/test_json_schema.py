@@ -0,0 +1,245 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+USER = {
+    "type": "object",
+    "properties": {
+        "id": {"type": "integer", "minimum": 1},
+        "name": {"type": "string", "minLength": 1, "maxLength": 8},
+        "tags": {"type": "array", "items": {"type": "string"}, "uniqueItems": True},
+        "role": {"enum": ["admin", "user"]},
+        "score": {"type": ["number", "null"], "exclusiveMaximum": 10},
+    },
+    "required": ["id", "name"],
+    "additionalProperties": False,
+}
+
+
+def mismatch(obj, schema):
+    with pytest.raises(hyperjson.JSONEncodeError) as exc:
+        hyperjson.dumps(obj, json_schema=schema)
+    return str(exc.value)
+
+
+class TestJsonSchema:
+    def test_json_schema_valid(self):
+        """
+        dumps() json_schema= writes an object that matches the schema
+        """
+        obj = {"id": 1, "name": "a", "tags": ["x", "y"], "role": "user", "score": 1.5}
+        assert hyperjson.dumps(obj, json_schema=USER) == hyperjson.dumps(obj)
+        assert hyperjson.dumps({"id": 2, "name": "b", "score": None}, json_schema=USER)
+
+    def test_json_schema_none(self):
+        """
+        dumps() json_schema=None does not check the object
+        """
+        assert hyperjson.dumps([1], json_schema=None) == b"[1]"
+
+    def test_json_schema_mismatch(self):
+        """
+        dumps() json_schema= raises with the path of the value that does not
+        match
+        """
+        for obj, msg in (
+            ({"id": "1", "name": "a"}, "expected integer at $.id"),
+            ({"id": 0, "name": "a"}, "number is less than minimum 1 at $.id"),
+            ({"name": "a"}, 'missing required property "id" at $'),
+            ({"id": 1, "name": ""}, "string is shorter than minLength 1 at $.name"),
+            ({"id": 1, "name": "a", "tags": ["x", 1]}, "expected string at $.tags[1]"),
+            (
+                {"id": 1, "name": "a", "tags": ["x", "x"]},
+                "array has items that are not unique at $.tags",
+            ),
+            ({"id": 1, "name": "a", "role": "x"}, "value is not one of enum at $.role"),
+            (
+                {"id": 1, "name": "a", "score": 10},
+                "number is not less than exclusiveMaximum 10 at $.score",
+            ),
+            ({"id": 1, "name": "a", "z": 1}, 'property "z" is not allowed at $'),
+            ([], "expected object at $"),
+        ):
+            assert mismatch(obj, USER).startswith("json_schema: ")
+            assert msg in mismatch(obj, USER)
+
+    def test_json_schema_types(self):
+        """
+        dumps() json_schema= type distinguishes bool from integer and accepts
+        a float without a fraction as an integer
+        """
+        assert hyperjson.dumps(1.0, json_schema={"type": "integer"}) == b"1.0"
+        assert hyperjson.dumps((1, 2), json_schema={"type": "array"}) == b"[1,2]"
+        assert mismatch(True, {"type": "integer"}) == (
+            "json_schema: expected integer at $"
+        )
+        assert mismatch(1.5, {"type": ["integer", "string"]}) == (
+            "json_schema: expected integer or string at $"
+        )
+
+    def test_json_schema_const(self):
+        """
+        dumps() json_schema= const and enum compare as JSON values
+        """
+        assert hyperjson.dumps([1, 2], json_schema={"const": [1, 2]})
+        assert hyperjson.dumps((1, 2), json_schema={"const": [1, 2]})
+        assert mismatch(1, {"const": True}) == "json_schema: value is not const at $"
+        assert mismatch(True, {"enum": [1, 0]})
+
+    def test_json_schema_applicators(self):
+        """
+        dumps() json_schema= applies allOf, anyOf, oneOf, not, and if
+        """
+        schema = {
+            "anyOf": [{"type": "string"}, {"type": "integer", "multipleOf": 3}],
+            "not": {"const": 9},
+        }
+        assert hyperjson.dumps("a", json_schema=schema)
+        assert hyperjson.dumps(6, json_schema=schema)
+        assert "does not match any of anyOf" in mismatch(4, schema)
+        assert "value matches not" in mismatch(9, schema)
+        one_of = {"oneOf": [{"type": "number"}, {"type": "integer"}]}
+        assert hyperjson.dumps(1.5, json_schema=one_of)
+        assert "more than one of oneOf" in mismatch(1, one_of)
+        conditional = {
+            "if": {"properties": {"kind": {"const": "a"}}},
+            "then": {"required": ["a"]},
+            "else": {"required": ["b"]},
+        }
+        assert hyperjson.dumps({"kind": "a", "a": 1}, json_schema=conditional)
+        assert hyperjson.dumps({"kind": "c", "b": 1}, json_schema=conditional)
+        assert 'missing required property "b"' in mismatch({"kind": "c"}, conditional)
+        assert hyperjson.dumps(0.3, json_schema={"multipleOf": 0.1})
+
+    def test_json_schema_ref(self):
+        """
+        dumps() json_schema= resolves $ref within the schema, including to
+        itself
+        """
+        schema = {
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"type": "integer"},
+                        "children": {
+                            "type": "array",
+                            "items": {"$ref": "#/$defs/node"},
+                        },
+                    },
+                }
+            },
+            "$ref": "#/$defs/node",
+        }
+        obj = {"value": 1, "children": [{"value": 2, "children": [{"value": 3}]}]}
+        assert hyperjson.dumps(obj, json_schema=schema)
+        obj["children"][0]["children"][0]["value"] = "3"
+        assert mismatch(obj, schema) == (
+            "json_schema: expected integer at $.children[0].children[0].value"
+        )
+        recursive = {"type": ["array", "integer"], "items": {"$ref": "#"}}
+        assert hyperjson.dumps([1, [2, [3]]], json_schema=recursive)
+        assert "expected integer or array at $[1][1][0]" in mismatch(
+            [1, [2, ["a"]]], recursive
+        )
+
+    def test_json_schema_dataclass(self):
+        """
+        dumps() json_schema= checks the fields a dataclass is written as
+        """
+
+        @dataclasses.dataclass
+        class Point:
+            x: int
+            y: int = 0
+
+        schema = {"properties": {"p": {"properties": {"x": {"type": "integer"}}}}}
+        assert hyperjson.dumps({"p": Point(1)}, json_schema=schema) == (
+            b'{"p":{"x":1,"y":0}}'
+        )
+        assert mismatch({"p": Point("1")}, schema) == (
+            "json_schema: expected integer at $.p.x"
+        )
+        assert mismatch(Point(1), {"maxProperties": 1}).startswith("json_schema: ")
+
+    def test_json_schema_default(self):
+        """
+        dumps() json_schema= checks what default returns
+        """
+        schema = {"type": "array", "items": {"type": "integer"}, "maxItems": 2}
+        assert hyperjson.dumps({1, 2}, default=sorted, json_schema=schema) == (
+            b"[1,2]"
+        )
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps({1, 2, 3}, default=sorted, json_schema=schema)
+        assert str(exc.value) == (
+            "json_schema: array has more than maxItems 2 items at $"
+        )
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps({"a"}, default=sorted, json_schema=schema)
+        assert str(exc.value) == "json_schema: expected integer at $[0]"
+
+    def test_json_schema_output(self):
+        """
+        dumps() json_schema= checks values as they are written
+        """
+        assert mismatch(float("nan"), {"type": "number"}) == (
+            "json_schema: expected number at $"
+        )
+        assert hyperjson.dumps(
+            float("nan"), non_finite="literal", json_schema={"type": "number"}
+        ) == (b"NaN")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(
+                {1: "a"},
+                option=hyperjson.OPT_NON_STR_KEYS,
+                json_schema={"propertyNames": {"maxLength": 0}},
+            )
+        assert str(exc.value).startswith("json_schema: ")
+
+    def test_json_schema_bool(self):
+        """
+        dumps() json_schema= accepts a boolean schema
+        """
+        assert hyperjson.dumps(1, json_schema=True) == b"1"
+        assert mismatch(1, False) == "json_schema: value is not allowed at $"
+        assert "property name" in mismatch(
+            {"ab": 1}, {"propertyNames": {"maxLength": 1}}
+        )
+
+    def test_json_schema_exc_class(self):
+        """
+        dumps() json_schema= raises exc_class for a mismatch
+        """
+        with pytest.raises(ValueError):
+            hyperjson.dumps(1, json_schema={"type": "string"}, exc_class=ValueError)
+
+    def test_json_schema_invalid(self):
+        """
+        dumps() json_schema= raises for a schema that is not valid or uses a
+        keyword that is not supported
+        """
+        for schema, msg in (
+            ([], "dumps() json_schema must be a dict or bool"),
+            ({"type": "int"}, "dumps() json_schema has an invalid type"),
+            ({"minLength": -1}, "dumps() json_schema has an invalid minLength"),
+            ({"pattern": "a"}, "dumps() json_schema keyword is not supported: pattern"),
+            ({"$ref": "#/x"}, "dumps() json_schema $ref cannot be resolved: #/x"),
+        ):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps(1, json_schema=schema)
+            assert str(exc.value) == msg
+
+    def test_json_schema_recursion(self):
+        """
+        dumps() json_schema= raises for an object that refers to itself before
+        it is checked
+        """
+        obj: list = []
+        obj.append(obj)
+        assert not mismatch(obj, {"items": {"$ref": "#"}}).startswith("json_schema")
@@ -0,0 +1,106 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+def reverse(a, b):
+    return (a < b) - (a > b)
+
+
+class TestKeyOrder:
+    def test_key_order_natural(self):
+        """
+        dumps() key_order="natural" compares runs of digits by value
+        """
+        obj = {"item10": 1, "item2": 2, "item1": 3, "item02": 4, "b": 5, "a10b": 6}
+        assert (
+            hyperjson.dumps(obj, key_order="natural")
+            == b'{"a10b":6,"b":5,"item1":3,"item02":4,"item2":2,"item10":1}'
+        )
+
+    def test_key_order_case_insensitive(self):
+        """
+        dumps() key_order="case_insensitive" ignores case, then uses codepoint
+        """
+        obj = {"b": 1, "B": 2, "a": 3, "C": 4}
+        assert (
+            hyperjson.dumps(obj, key_order="case_insensitive")
+            == b'{"a":3,"B":2,"b":1,"C":4}'
+        )
+
+    def test_key_order_codepoint(self):
+        """
+        dumps() key_order="codepoint" is OPT_SORT_KEYS
+        """
+        obj = {"item10": 1, "item2": 2, "B": 3}
+        assert hyperjson.dumps(obj, key_order="codepoint") == hyperjson.dumps(
+            obj, option=hyperjson.OPT_SORT_KEYS
+        )
+        assert hyperjson.dumps(obj, key_order=None) == b'{"item10":1,"item2":2,"B":3}'
+
+    def test_key_order_callable(self):
+        """
+        dumps() key_order= a comparator
+        """
+        obj = {"a": 1, "c": {"y": 2, "z": 3}, "b": 4}
+        assert (
+            hyperjson.dumps(obj, key_order=reverse)
+            == b'{"c":{"z":3,"y":2},"b":4,"a":1}'
+        )
+
+    def test_key_order_callable_raises(self):
+        """
+        dumps() key_order= a comparator that raises
+        """
+
+        def cmp(a, b):
+            raise ValueError("cmp")
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps({"a": 1, "b": 2}, key_order=cmp)
+        assert isinstance(exc.value.__cause__, ValueError)
+
+    def test_key_order_callable_not_int(self):
+        """
+        dumps() key_order= a comparator that does not return an int
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps({"a": 1, "b": 2}, key_order=lambda a, b: "a")
+
+    def test_key_order_non_str_keys(self):
+        """
+        dumps() key_order= with OPT_NON_STR_KEYS
+        """
+        obj = {10: 1, 2: 2, "item1": 3}
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_NON_STR_KEYS, key_order="natural"
+            )
+            == b'{"2":2,"10":1,"item1":3}'
+        )
+
+    def test_key_order_length_batch(self):
+        """
+        dumps_length() and dumps_batch() key_order=
+        """
+        obj = {"item10": 1, "item2": 2}
+        assert hyperjson.dumps_length(obj, key_order="natural") == len(
+            b'{"item2":2,"item10":1}'
+        )
+        assert hyperjson.dumps_batch([obj, {"a": 1, "b": 2}], key_order=reverse) == (
+            b'{"item2":2,"item10":1}\n{"b":2,"a":1}\n',
+            [0, 23, 37],
+        )
+
+    def test_key_order_invalid(self):
+        """
+        dumps() key_order= not an ordering
+        """
+        for val in ("reverse", 1, b"natural"):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps({}, key_order=val)  # type: ignore
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps_length({}, key_order=val)  # type: ignore
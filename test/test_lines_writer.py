@@ -0,0 +1,270 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import io
+import os
+import pathlib
+import tempfile
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+class TestLinesWriter:
+    def test_lines_writer_path(self):
+        """
+        LinesWriter appends records to a path
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "log.ndjson")
+            with hyperjson.LinesWriter(path) as writer:
+                writer.write({"a": 1})
+                writer.write([1, 2])
+                writer.write("x")
+            with open(path, "rb") as fileobj:
+                assert fileobj.read() == b'{"a":1}\n[1,2]\n"x"\n'
+
+    def test_lines_writer_append(self):
+        """
+        LinesWriter appends to an existing file
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            path = pathlib.Path(tmp) / "log.ndjson"
+            path.write_bytes(b"1\n")
+            with hyperjson.LinesWriter(path) as writer:
+                writer.write(2)
+            assert path.read_bytes() == b"1\n2\n"
+
+    def test_lines_writer_fileobj(self):
+        """
+        LinesWriter writes to a file object
+        """
+        fileobj = io.BytesIO()
+        writer = hyperjson.LinesWriter(fileobj)
+        writer.write({"a": [True, None]})
+        assert fileobj.getvalue() == b""
+        writer.flush()
+        assert fileobj.getvalue() == b'{"a":[true,null]}\n'
+        writer.close()
+        assert not fileobj.closed
+
+    def test_lines_writer_buffer_size(self):
+        """
+        LinesWriter writes out the buffer once it holds buffer_size bytes
+        """
+        fileobj = io.BytesIO()
+        writer = hyperjson.LinesWriter(fileobj, buffer_size=8)
+        writer.write("abc")
+        assert fileobj.getvalue() == b""
+        writer.write("def")
+        assert fileobj.getvalue() == b'"abc"\n"def"\n'
+        writer.close()
+
+    def test_lines_writer_dealloc(self):
+        """
+        LinesWriter writes buffered records when deallocated
+        """
+        fileobj = io.BytesIO()
+        writer = hyperjson.LinesWriter(fileobj)
+        writer.write(1)
+        del writer
+        assert fileobj.getvalue() == b"1\n"
+
+    def test_lines_writer_rotate(self):
+        """
+        LinesWriter calls on_rotate before exceeding max_bytes
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "log.ndjson")
+            rotated = []
+
+            def on_rotate(target):
+                rotated.append(target)
+                os.rename(target, f"{target}.{len(rotated)}")
+
+            with hyperjson.LinesWriter(
+                path,
+                max_bytes=10,
+                on_rotate=on_rotate,
+            ) as writer:
+                for idx in range(5):
+                    writer.write([idx, idx])
+            assert rotated == [path] * 4
+            for idx in range(4):
+                with open(f"{path}.{idx + 1}", "rb") as fileobj:
+                    assert fileobj.read() == f"[{idx},{idx}]\n".encode("utf-8")
+            with open(path, "rb") as fileobj:
+                assert fileobj.read() == b"[4,4]\n"
+
+    def test_lines_writer_rotate_fileobj(self):
+        """
+        LinesWriter on_rotate returns the file object to continue with
+        """
+        files = [io.BytesIO()]
+
+        def on_rotate(target):
+            assert target is files[-1]
+            files.append(io.BytesIO())
+            return files[-1]
+
+        writer = hyperjson.LinesWriter(files[0], max_bytes=12, on_rotate=on_rotate)
+        for idx in range(4):
+            writer.write(idx * 11111)
+        writer.close()
+        assert [each.getvalue() for each in files] == [
+            b"0\n11111\n",
+            b"22222\n33333\n",
+        ]
+
+    def test_lines_writer_rotate_record_too_large(self):
+        """
+        LinesWriter writes a record larger than max_bytes to its own file
+        """
+        files = [io.BytesIO()]
+
+        def on_rotate(target):
+            files.append(io.BytesIO())
+            return files[-1]
+
+        writer = hyperjson.LinesWriter(files[0], max_bytes=4, on_rotate=on_rotate)
+        writer.write("a")
+        writer.write("abcdef")
+        writer.write("b")
+        writer.close()
+        assert [each.getvalue() for each in files] == [
+            b'"a"\n',
+            b'"abcdef"\n',
+            b'"b"\n',
+        ]
+
+    def test_lines_writer_rotate_raises(self):
+        """
+        LinesWriter is closed if on_rotate raises
+        """
+
+        def on_rotate(target):
+            raise ValueError("rotate")
+
+        fileobj = io.BytesIO()
+        writer = hyperjson.LinesWriter(fileobj, max_bytes=3, on_rotate=on_rotate)
+        writer.write(1)
+        with pytest.raises(ValueError):
+            writer.write(2)
+        assert fileobj.getvalue() == b"1\n"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            writer.write(3)
+
+    def test_lines_writer_reentrant(self):
+        """
+        LinesWriter on_rotate may not write to the writer
+        """
+        writer = None
+
+        def on_rotate(target):
+            writer.write("reentrant")
+
+        writer = hyperjson.LinesWriter(
+            io.BytesIO(),
+            max_bytes=2,
+            on_rotate=on_rotate,
+        )
+        writer.write(1)
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            writer.write(2)
+        assert str(exc_info.value) == "LinesWriter is already writing"
+
+    def test_lines_writer_invalid_record(self):
+        """
+        LinesWriter discards a record that fails to serialize
+        """
+        fileobj = io.BytesIO()
+        with hyperjson.LinesWriter(fileobj) as writer:
+            writer.write(1)
+            with pytest.raises(hyperjson.JSONEncodeError):
+                writer.write([2, Custom()])
+            writer.write(3)
+        assert fileobj.getvalue() == b"1\n3\n"
+
+    def test_lines_writer_default_option(self):
+        """
+        LinesWriter default and option
+        """
+        fileobj = io.BytesIO()
+        with hyperjson.LinesWriter(
+            fileobj,
+            default=lambda obj: "custom",
+            option=hyperjson.OPT_SORT_KEYS | hyperjson.OPT_APPEND_CRLF,
+        ) as writer:
+            writer.write({"b": Custom(), "a": 1})
+        assert fileobj.getvalue() == b'{"a":1,"b":"custom"}\r\n'
+
+    def test_lines_writer_closed(self):
+        """
+        LinesWriter write() and flush() after close()
+        """
+        writer = hyperjson.LinesWriter(io.BytesIO())
+        writer.close()
+        writer.close()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            writer.write(1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            writer.flush()
+
+    def test_lines_writer_fileobj_error(self):
+        """
+        LinesWriter propagates an exception raised by write() and keeps the
+        buffer
+        """
+
+        class Failing(io.BytesIO):
+            fail = True
+
+            def write(self, data):
+                if self.fail:
+                    raise OSError("disk full")
+                return super().write(data)
+
+        fileobj = Failing()
+        writer = hyperjson.LinesWriter(fileobj)
+        writer.write(1)
+        with pytest.raises(OSError):
+            writer.flush()
+        fileobj.fail = False
+        writer.flush()
+        assert fileobj.getvalue() == b"1\n"
+
+    def test_lines_writer_path_error(self):
+        """
+        LinesWriter raises FileNotFoundError for a path in a missing directory
+        """
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "missing", "log.ndjson")
+            with pytest.raises(FileNotFoundError) as exc_info:
+                hyperjson.LinesWriter(path)
+            assert exc_info.value.filename == path
+
+    def test_lines_writer_invalid_args(self):
+        """
+        LinesWriter invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(1)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), max_bytes=10)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), max_bytes=0, on_rotate=print)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), buffer_size=-1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), on_rotate=1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), option=hyperjson.OPT_INDENT_2)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.LinesWriter(io.BytesIO(), unknown=1)  # type: ignore
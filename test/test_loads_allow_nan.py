@@ -0,0 +1,100 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+import math
+
+import pytest
+
+import hyperjson
+
+
+class TestLoadsAllowNan:
+    def test_allow_nan(self):
+        """
+        loads() allow_nan=True reads NaN, Infinity, and -Infinity
+        """
+        val = hyperjson.loads(b"[NaN, Infinity, -Infinity]", allow_nan=True)
+        assert math.isnan(val[0])
+        assert val[1:] == [math.inf, -math.inf]
+
+    def test_allow_nan_root(self):
+        """
+        loads() allow_nan=True of a literal as the document
+        """
+        assert math.isnan(hyperjson.loads("NaN", allow_nan=True))
+        assert hyperjson.loads(" Infinity ", allow_nan=True) == math.inf
+        assert hyperjson.loads("-Infinity", allow_nan=True) == -math.inf
+
+    def test_allow_nan_default(self):
+        """
+        loads() rejects NaN and Infinity by default
+        """
+        for val in ("NaN", "Infinity", "-Infinity", '{"a": NaN}'):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_nan=False)
+
+    def test_allow_nan_stdlib(self):
+        """
+        loads() allow_nan=True of json.dumps() output
+        """
+        obj = {
+            "a": [1, -2, 18446744073709551615, -9223372036854775808, 0.5, -0.0],
+            "b": {"c": math.inf, "d": [None, True, False, "é\n😀"]},
+            "": "",
+        }
+        doc = json.dumps(obj, indent=2)
+        assert hyperjson.loads(doc, allow_nan=True) == json.loads(doc)
+        doc = json.dumps(obj, ensure_ascii=False)
+        assert hyperjson.loads(doc, allow_nan=True) == json.loads(doc)
+
+    def test_allow_nan_strict(self):
+        """
+        loads() allow_nan=True does not accept other non-standard literals
+        """
+        for val in ("nan", "infinity", "-inf", "+Infinity", "[NaN,]", "Infinityx"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_nan=True)
+
+    def test_allow_nan_invalid(self):
+        """
+        loads() allow_nan=True errors as loads() does
+        """
+        for val in (
+            "",
+            " ",
+            "[1",
+            '{"a" 1}',
+            "01",
+            "1.",
+            '"\\ud800"',
+            '"\\x"',
+            '"\x01"',
+            "1e400",
+            "[" * 2048 + "]" * 2048,
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_nan=True)
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads('{"a": NaN, "b": nan}', allow_nan=True)
+        assert exc.value.pos == 16
+
+    def test_allow_nan_rename(self):
+        """
+        loads() allow_nan=True with rename=
+        """
+        val = hyperjson.loads(
+            '{"a": NaN, "b": [{"a": 1}]}', allow_nan=True, rename={"a": "x"}
+        )
+        assert list(val) == ["x", "b"]
+        assert math.isnan(val["x"])
+        assert val["b"] == [{"x": 1}]
+
+    def test_allow_nan_not_bool(self):
+        """
+        loads() allow_nan= not a bool
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads("NaN", allow_nan=1)  # type: ignore
@@ -0,0 +1,83 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestLoadsChunks:
+    def test_loads_chunks(self):
+        """
+        loads() a document split across a list of chunks
+        """
+        assert hyperjson.loads([b'{"a": [1, ', b"2, 3], ", b'"b": null}']) == {
+            "a": [1, 2, 3],
+            "b": None,
+        }
+
+    def test_loads_chunks_every_split(self):
+        """
+        loads() a document split at every offset
+        """
+        doc = '{"key": ["value", 1.5, true, {"é": "\U0001f600"}]}'.encode()
+        expected = hyperjson.loads(doc)
+        for idx in range(len(doc) + 1):
+            assert hyperjson.loads([doc[:idx], doc[idx:]]) == expected
+
+    def test_loads_chunks_codepoint(self):
+        """
+        loads() a chunk ending within a codepoint
+        """
+        doc = '"\U0001f600"'.encode()
+        assert hyperjson.loads([doc[i : i + 1] for i in range(len(doc))]) == "\U0001f600"
+
+    def test_loads_chunks_types(self):
+        """
+        loads() chunks of bytes, bytearray, and memoryview in a tuple
+        """
+        assert hyperjson.loads((b"[1,", bytearray(b"2,"), memoryview(b"3]"))) == [
+            1,
+            2,
+            3,
+        ]
+
+    def test_loads_chunks_single(self):
+        """
+        loads() a single chunk
+        """
+        assert hyperjson.loads([b"[1]"]) == [1]
+
+    def test_loads_chunks_empty(self):
+        """
+        loads() no chunks or empty chunks
+        """
+        for val in ([], (), [b""], [b"", bytearray()]):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+        assert hyperjson.loads([b"", b"1", b""]) == 1
+
+    def test_loads_chunks_invalid_type(self):
+        """
+        loads() chunks that are not bytes-like
+        """
+        for val in (["[1]"], [b"[", 1, b"]"], [[b"1"]]):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+
+    def test_loads_chunks_invalid_utf8(self):
+        """
+        loads() chunks that are not valid UTF-8 when joined
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads([b'"\xf0\x9f', b'\x98"'])
+
+    def test_loads_chunks_error(self):
+        """
+        loads() error position is relative to the joined chunks
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc_info:
+            hyperjson.loads([b'{"a":', b"\n[1,", b" x]}"])
+        assert exc_info.value.doc == '{"a":\n[1, x]}'
+        assert exc_info.value.pos == 10
+        assert exc_info.value.lineno == 2
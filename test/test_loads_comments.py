@@ -0,0 +1,109 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import math
+
+import pytest
+
+import hyperjson
+
+SETTINGS = """\
+// settings.jsonc
+{
+    /* editor */
+    "editor.fontSize": 14, // px
+    "files.exclude": {
+        "**/.git": true /* also
+        node_modules */
+    },
+    "url": "http://example.com/*not a comment*/"
+}
+"""
+
+
+class TestLoadsComments:
+    def test_allow_comments(self):
+        """
+        loads() allow_comments=True skips // and /* */ comments
+        """
+        assert hyperjson.loads(SETTINGS, allow_comments=True) == {
+            "editor.fontSize": 14,
+            "files.exclude": {"**/.git": True},
+            "url": "http://example.com/*not a comment*/",
+        }
+        assert hyperjson.loads(SETTINGS.encode("utf-8"), allow_comments=True) == (
+            hyperjson.loads(SETTINGS, allow_comments=True)
+        )
+
+    def test_allow_comments_default(self):
+        """
+        loads() rejects comments by default
+        """
+        for val in ("// a\n1", "1 /* a */", "[1, /**/ 2]"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_comments=False)
+
+    def test_allow_comments_none(self):
+        """
+        loads() allow_comments=True of a document without comments
+        """
+        doc = '{"a": "//", "b": "/*", "c": "\\"/*"}'
+        assert hyperjson.loads(doc, allow_comments=True) == hyperjson.loads(doc)
+
+    def test_allow_comments_only_comments(self):
+        """
+        loads() allow_comments=True of only a comment
+        """
+        for val in ("// a", "/* a */", "/**/"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_comments=True)
+        assert hyperjson.loads("/**/1/**/", allow_comments=True) == 1
+        assert hyperjson.loads("1//", allow_comments=True) == 1
+
+    def test_allow_comments_strict(self):
+        """
+        loads() allow_comments=True does not accept other extensions
+        """
+        for val in ("[1,]", "{'a': 1}", "/ 1", "1 /", "# a\n1"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_comments=True)
+
+    def test_allow_comments_error(self):
+        """
+        loads() allow_comments=True errors refer to the input
+        """
+        doc = '/* é */ {"a": ?}'
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc, allow_comments=True)
+        assert exc.value.pos == 14
+        assert exc.value.doc == doc
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads("[1, /* 2 ]", allow_comments=True)
+        assert exc.value.pos == 4
+        assert str(exc.value).startswith("unclosed multiline comment")
+
+    def test_allow_comments_extended(self):
+        """
+        loads() allow_comments=True with allow_nan=True and json5=True
+        """
+        assert hyperjson.loads(
+            "[NaN, /* a */ Infinity] // b", allow_comments=True, allow_nan=True
+        )[1:] == [math.inf]
+        assert hyperjson.loads(
+            "['//', /* a */ 1,]", allow_comments=True, json5=True
+        ) == ["//", 1]
+
+    def test_allow_comments_chunks(self):
+        """
+        loads() allow_comments=True of chunks
+        """
+        assert hyperjson.loads([b"[1, /* a", b" */ 2]"], allow_comments=True) == [1, 2]
+
+    def test_allow_comments_not_bool(self):
+        """
+        loads() allow_comments= not a bool
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads("1", allow_comments=1)  # type: ignore
@@ -0,0 +1,89 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+DOC = '{"a": 1, "b": {"c": 2, "c": 3}, "a": [4]}'
+
+
+class TestLoadsDuplicateKeys:
+    def test_duplicate_keys_default(self):
+        """
+        loads() keeps the last member with a key by default
+        """
+        expected = {"a": [4], "b": {"c": 3}}
+        assert hyperjson.loads(DOC) == expected
+        assert hyperjson.loads(DOC, duplicate_keys="last") == expected
+
+    def test_duplicate_keys_first(self):
+        """
+        loads() duplicate_keys="first" keeps the first member with a key
+        """
+        assert hyperjson.loads(DOC, duplicate_keys="first") == {
+            "a": 1,
+            "b": {"c": 2},
+        }
+        assert list(
+            hyperjson.loads('{"b": 1, "a": 2, "b": 3}', duplicate_keys="first")
+        ) == ["b", "a"]
+
+    def test_duplicate_keys_error(self):
+        """
+        loads() duplicate_keys="error" raises at the second member with a key
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(DOC, duplicate_keys="error")
+        assert str(exc.value).startswith("duplicate key")
+        assert exc.value.pos == DOC.index('"c": 3')
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'[{"\xc3\xa9": 1, "\xc3\xa9": 2}]', duplicate_keys="error")
+        assert exc.value.pos == 10
+
+    def test_duplicate_keys_unique(self):
+        """
+        loads() duplicate_keys= of objects without duplicate keys
+        """
+        doc = '{"a": [{"a": 1}, {"a": 2}], "b": {"a": null}, "": ""}'
+        for policy in ("first", "error"):
+            assert hyperjson.loads(doc, duplicate_keys=policy) == hyperjson.loads(doc)
+
+    def test_duplicate_keys_rename(self):
+        """
+        loads() duplicate_keys= compares keys after rename=
+        """
+        doc = '{"a": 1, "b": 2}'
+        rename = {"b": "a"}
+        assert hyperjson.loads(doc, rename=rename, duplicate_keys="first") == {"a": 1}
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(doc, rename=rename, duplicate_keys="error")
+
+    def test_duplicate_keys_omit_null(self):
+        """
+        loads() duplicate_keys= with OPT_OMIT_NULL_ON_LOAD compares the keys
+        of null members
+        """
+        doc = '{"a": null, "b": null, "a": 1}'
+        option = hyperjson.OPT_OMIT_NULL_ON_LOAD
+        assert hyperjson.loads(doc, option=option) == {"a": 1}
+        assert hyperjson.loads(doc, option=option, duplicate_keys="first") == {}
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(doc, option=option, duplicate_keys="error")
+
+    def test_duplicate_keys_json5(self):
+        """
+        loads() duplicate_keys= with json5=True
+        """
+        doc = "{a: 1, 'a': 2, \"a\": 3,}"
+        assert hyperjson.loads(doc, json5=True, duplicate_keys="first") == {"a": 1}
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(doc, json5=True, duplicate_keys="error")
+
+    def test_duplicate_keys_invalid(self):
+        """
+        loads() duplicate_keys= not "last", "first", or "error"
+        """
+        for val in ("raise", "LAST", None, True):
+            with pytest.raises(TypeError):
+                hyperjson.loads("{}", duplicate_keys=val)  # type: ignore
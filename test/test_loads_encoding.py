@@ -0,0 +1,93 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Name:
+    name: str
+
+
+class TestLoadsEncoding:
+    def test_latin_1(self):
+        """
+        loads() encoding= decodes bytes from latin-1
+        """
+        data = '{"café": "naïve \\u00ff"}'.encode("latin-1")
+        assert hyperjson.loads(data, encoding="latin-1") == {"café": "naïve ÿ"}
+        assert hyperjson.loads(b'"\xe9"', encoding="iso-8859-1") == "é"
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b'"\xe9"')
+
+    def test_other_codecs(self):
+        """
+        loads() encoding= is any codec that decodes to str
+        """
+        assert hyperjson.loads(b'["\x80"]', encoding="cp1252") == ["€"]
+        data = '{"a": ["ü"]}'.encode("utf-16")
+        assert hyperjson.loads(data, encoding="utf-16") == {"a": ["ü"]}
+        assert hyperjson.loads(b"[1]", encoding="utf-8") == [1]
+
+    def test_input_types(self):
+        """
+        loads() encoding= applies to bytearray, memoryview, and chunks
+        """
+        assert hyperjson.loads(bytearray(b'"\xe9"'), encoding="latin-1") == "é"
+        assert hyperjson.loads(memoryview(b'"\xe9"'), encoding="latin-1") == "é"
+        assert hyperjson.loads([b'["\xe9', b'"]'], encoding="latin-1") == ["é"]
+
+    def test_str(self):
+        """
+        loads() encoding= does not apply to str
+        """
+        assert hyperjson.loads('"é"', encoding="latin-1") == "é"
+
+    def test_options(self):
+        """
+        loads() encoding= with other options and type=
+        """
+        data = b'{"a": NaN, "\xe9": 1.5}'
+        val = hyperjson.loads(data, encoding="latin-1", allow_nan=True, parse_float=str)
+        assert list(val) == ["a", "é"]
+        assert val["é"] == "1.5"
+        data = b'{"name": "\xe9"}'
+        assert hyperjson.loads(data, encoding="latin-1", type=Name) == Name("é")
+
+    def test_none(self):
+        """
+        loads() encoding=None reads UTF-8
+        """
+        assert hyperjson.loads(b'"\xc3\xa9"', encoding=None) == "é"
+
+    def test_invalid_input(self):
+        """
+        loads() encoding= raises JSONDecodeError for input not in the codec
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc_info:
+            hyperjson.loads(b'"\xff"', encoding="ascii")
+        assert str(exc_info.value).startswith("Input is not valid ascii")
+        for data in (b"", b"[1", [b"[", b"x]"]):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(data, encoding="latin-1")
+
+    def test_invalid_error_position(self):
+        """
+        loads() encoding= reports the position in the decoded document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc_info:
+            hyperjson.loads(b'["\xe9\xe9", x]', encoding="latin-1")
+        assert exc_info.value.pos == 7
+        assert exc_info.value.doc == '["éé", x]'
+
+    def test_invalid_encoding(self):
+        """
+        loads() encoding= must name a codec
+        """
+        for encoding in ("latin-99", b"latin-1", 1):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.loads(b"[]", encoding=encoding)
@@ -0,0 +1,96 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Name:
+    name: str
+
+
+class TestLoadsErrors:
+    def test_replace(self):
+        """
+        loads() errors="replace" reads invalid UTF-8 as U+FFFD
+        """
+        data = b'{"a\xff": ["x\xc3", "\xed\xa0\x80", 1]}'
+        assert hyperjson.loads(data, errors="replace") == {"a�": ["x�", "���", 1]}
+
+    def test_replace_matches_decode(self):
+        """
+        loads() errors="replace" replaces as bytes.decode() does
+        """
+        for invalid in (b"\x80", b"\xf0\x9f\x98", b"\xc0\xaf", b"\xf4\x90\x80\x80"):
+            data = b'"' + invalid + b'z"'
+            expected = data.decode("utf-8", "replace")[1:-1]
+            assert hyperjson.loads(data, errors="replace") == expected
+
+    def test_replace_valid(self):
+        """
+        loads() errors="replace" reads valid UTF-8 unchanged
+        """
+        data = '{"é": ["😀", 1.5, null]}'.encode()
+        assert hyperjson.loads(data, errors="replace") == hyperjson.loads(data)
+        assert hyperjson.loads("[1]", errors="replace") == [1]
+
+    def test_strict(self):
+        """
+        loads() errors="strict" raises for invalid UTF-8
+        """
+        assert hyperjson.loads(b"[1]", errors="strict") == [1]
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b'"\xff"', errors="strict")
+
+    def test_input_types(self):
+        """
+        loads() errors="replace" applies to bytearray, memoryview, and chunks
+        """
+        assert hyperjson.loads(bytearray(b'"\xff"'), errors="replace") == "�"
+        assert hyperjson.loads(memoryview(b'"\xff"'), errors="replace") == "�"
+        assert hyperjson.loads([b'["\xe2\x82', b'"]'], errors="replace") == ["�"]
+        assert hyperjson.loads((b'["\xe2\x82', b'\xac"]'), errors="replace") == ["€"]
+
+    def test_options(self):
+        """
+        loads() errors="replace" with other options and type=
+        """
+        data = b'{"a": NaN, "\xff": 1.5}'
+        val = hyperjson.loads(data, errors="replace", allow_nan=True, parse_float=str)
+        assert list(val) == ["a", "�"]
+        assert val["�"] == "1.5"
+        data = b'{"name": "\xff"}'
+        assert hyperjson.loads(data, errors="replace", type=Name) == Name("�")
+
+    def test_encoding(self):
+        """
+        loads() errors="replace" applies to encoding=
+        """
+        assert hyperjson.loads(b'"a\xff"', encoding="ascii", errors="replace") == (
+            "a�"
+        )
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b'"a\xff"', encoding="ascii", errors="strict")
+
+    def test_invalid_document(self):
+        """
+        loads() errors="replace" reports the position in the replaced document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc_info:
+            hyperjson.loads(b'["\xff\xff", x]', errors="replace")
+        assert exc_info.value.pos == 7
+        assert exc_info.value.doc == '["��", x]'
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b"", errors="replace")
+
+    def test_invalid_errors(self):
+        """
+        loads() errors= must be "strict" or "replace"
+        """
+        for errors in ("ignore", None, b"replace"):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.loads(b"[]", errors=errors)
@@ -0,0 +1,150 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import math
+
+import pytest
+
+import hyperjson
+
+CONFIG = """
+// the spec's example document
+{
+  // comments
+  unquoted: 'and you can quote me on that',
+  singleQuotes: 'I can use "double quotes" here',
+  lineBreaks: "Look, Mom! \\
+No \\\\n's!",
+  hexadecimal: 0xdecaf,
+  leadingDecimalPoint: .8675309, andTrailing: 8675309.,
+  positiveSign: +1,
+  trailingComma: 'in objects', andIn: ['arrays',],
+  "backwardsCompatible": "with JSON",
+}
+"""
+
+
+class TestLoadsJson5:
+    def test_json5(self):
+        """
+        loads() json5=True of the JSON5 spec's example
+        """
+        assert hyperjson.loads(CONFIG, json5=True) == {
+            "unquoted": "and you can quote me on that",
+            "singleQuotes": 'I can use "double quotes" here',
+            "lineBreaks": "Look, Mom! No \\n's!",
+            "hexadecimal": 0xDECAF,
+            "leadingDecimalPoint": 0.8675309,
+            "andTrailing": 8675309.0,
+            "positiveSign": 1,
+            "trailingComma": "in objects",
+            "andIn": ["arrays"],
+            "backwardsCompatible": "with JSON",
+        }
+
+    def test_json5_default(self):
+        """
+        loads() rejects JSON5 by default
+        """
+        for val in ("[1,]", "{a: 1}", "'a'", "0x1", "+1", ".5", "// c\n1"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, json5=False)
+
+    def test_json5_comments(self):
+        """
+        loads() json5=True reads single-line and multi-line comments
+        """
+        doc = "/* a */ [1, // b\n 2 /* c\n */, /**/ 3] // d"
+        assert hyperjson.loads(doc, json5=True) == [1, 2, 3]
+        assert hyperjson.loads("1 //", json5=True) == 1
+        for val in ("[1 /* c", "/ 1", "1 /"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, json5=True)
+
+    def test_json5_numbers(self):
+        """
+        loads() json5=True numbers
+        """
+        assert hyperjson.loads(
+            "[0x1F, -0XfF, +0x0, 5., -.5, +1e3, 18446744073709551615]", json5=True
+        ) == [31, -255, 0, 5.0, -0.5, 1000.0, 18446744073709551615]
+        assert hyperjson.loads("0x10000000000000000", json5=True) == float(1 << 64)
+        val = hyperjson.loads(
+            "[NaN, +NaN, -NaN, Infinity, +Infinity, -Infinity]", json5=True
+        )
+        assert all(math.isnan(each) for each in val[:3])
+        assert val[3:] == [math.inf, math.inf, -math.inf]
+        for num in ("0x", "01", ".", "+", "-.", "1e", "0x1.5", "++1", "1e400"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(num, json5=True)
+
+    def test_json5_strings(self):
+        """
+        loads() json5=True single-quoted strings and escapes
+        """
+        doc = r"""['a"b', "a'b", '\'', '\x41\u00e9\v\0', '\a\ \é', 'a\
+b', "\t"]"""
+        assert hyperjson.loads(doc, json5=True) == [
+            'a"b',
+            "a'b",
+            "'",
+            "Aé\x0b\x00",
+            "a é",
+            "ab",
+            "\t",
+        ]
+        assert hyperjson.loads("'a\tb'", json5=True) == "a\tb"
+        for val in ("'a", "'\\1'", "'\\x4'", "'a\nb'", "\"\\'\n\""):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, json5=True)
+
+    def test_json5_keys(self):
+        """
+        loads() json5=True unquoted keys are identifiers
+        """
+        doc = "{$a: 1, _b2: 2, null: 3, 'c d': 4, café: 5}"
+        assert hyperjson.loads(doc, json5=True) == {
+            "$a": 1,
+            "_b2": 2,
+            "null": 3,
+            "c d": 4,
+            "café": 5,
+        }
+        for val in ("{1a: 1}", "{a-b: 1}", "{: 1}", "{a: 1,,}", "[,]", "{,}"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, json5=True)
+
+    def test_json5_whitespace(self):
+        """
+        loads() json5=True whitespace
+        """
+        doc = "\ufeff\v\f\u00a0[1,\u2028\u3000 2]\u2029"
+        assert hyperjson.loads(doc, json5=True) == [1, 2]
+
+    def test_json5_options(self):
+        """
+        loads() json5=True with rename= and OPT_OMIT_NULL_ON_LOAD
+        """
+        assert hyperjson.loads(
+            "{a: null, b: 1, c: [null,],}",
+            json5=True,
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+            rename={"b": "x"},
+        ) == {"x": 1, "c": [None]}
+
+    def test_json5_error_pos(self):
+        """
+        loads() json5=True errors have the position
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads("{a: 1, /* b */ c: ?}", json5=True)
+        assert exc.value.pos == 18
+
+    def test_json5_not_bool(self):
+        """
+        loads() json5= not a bool
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads("1", json5=1)  # type: ignore
@@ -0,0 +1,127 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+from .util import numpy
+
+
+@pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+class TestLoadsNumpy:
+    def test_loads_numpy(self):
+        """
+        loads_numpy() reads an array of numbers to a float64 ndarray
+        """
+        array = hyperjson.loads_numpy(b"[1, 2.5, -3e2]")
+        assert isinstance(array, numpy.ndarray)
+        assert array.dtype == numpy.float64
+        assert array.tolist() == [1.0, 2.5, -300.0]
+
+    def test_loads_numpy_shape(self):
+        """
+        loads_numpy() reads nested arrays of the same shape to an array of
+        that shape
+        """
+        array = hyperjson.loads_numpy("[[[1, 2]], [[3, 4]], [[5, 6]]]", dtype="int32")
+        assert array.shape == (3, 1, 2)
+        assert array.flags["C_CONTIGUOUS"]
+        assert array.tolist() == [[[1, 2]], [[3, 4]], [[5, 6]]]
+        assert hyperjson.loads_numpy("[]").shape == (0,)
+        assert hyperjson.loads_numpy("[[], []]").shape == (2, 0)
+
+    def test_loads_numpy_dtype(self):
+        """
+        loads_numpy() writes each supported dtype
+        """
+        for dtype in (
+            numpy.int8,
+            numpy.int16,
+            numpy.int32,
+            numpy.int64,
+            numpy.uint8,
+            numpy.uint16,
+            numpy.uint32,
+            numpy.uint64,
+            numpy.float16,
+            numpy.float32,
+            numpy.float64,
+        ):
+            array = hyperjson.loads_numpy(b"[0, 1, 127]", dtype=dtype)
+            assert array.dtype == numpy.dtype(dtype)
+            assert array.tolist() == [0, 1, 127]
+        array = hyperjson.loads_numpy(b"[true, false]", numpy.bool_)
+        assert array.tolist() == [True, False]
+
+    def test_loads_numpy_range(self):
+        """
+        loads_numpy() raises for an integer that does not fit in dtype
+        """
+        assert hyperjson.loads_numpy(
+            b"[18446744073709551615]", dtype=numpy.uint64
+        ).tolist() == [18446744073709551615]
+        for data, dtype in (
+            (b"[128]", numpy.int8),
+            (b"[-1]", numpy.uint32),
+            (b"[9223372036854775808]", numpy.int64),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads_numpy(data, dtype=dtype)
+            assert exc.value.msg == "loads_numpy() integer out of range for dtype"
+
+    def test_loads_numpy_invalid_item(self):
+        """
+        loads_numpy() raises for an item dtype cannot hold
+        """
+        for data, dtype, msg in (
+            (b"[1.5]", numpy.int32, "float cannot be stored in an integer dtype"),
+            (b"[1]", numpy.bool_, "array item is not a bool"),
+            (b"[true]", numpy.float64, "array item is not a number"),
+            (b'[1, "2"]', numpy.float64, "array item is not a number or bool"),
+            (b"[null]", numpy.float64, "array item is not a number or bool"),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads_numpy(data, dtype=dtype)
+            assert exc.value.msg == f"loads_numpy() {msg}"
+
+    def test_loads_numpy_not_rectangular(self):
+        """
+        loads_numpy() raises for arrays of different shapes
+        """
+        for data in (b"[[1, 2], [3]]", b"[[1], 2]", b"[1, [2]]", b"[[[1]], [[]]]"):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads_numpy(data)
+            assert exc.value.msg == "loads_numpy() array is not rectangular"
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads_numpy(b'{"a": 1}')
+        assert exc.value.msg == "loads_numpy() document must be an array"
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_numpy(b"[1,")
+
+    def test_loads_numpy_unsupported_dtype(self):
+        """
+        loads_numpy() raises for a dtype that is not a native-endian bool,
+        integer, or float
+        """
+        for dtype, msg in (
+            ("U4", "dtype must be a bool, integer, or float type"),
+            (numpy.complex128, "dtype must be a bool, integer, or float type"),
+            (">i4" if numpy.little_endian else "<i4", "dtype must be native-endian"),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads_numpy(b"[1]", dtype=dtype)
+            assert exc.value.msg == f"loads_numpy() {msg}"
+        with pytest.raises(TypeError):
+            hyperjson.loads_numpy(b"[1]", dtype="not a dtype")
+
+    def test_loads_numpy_args(self):
+        """
+        loads_numpy() takes data and dtype
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_numpy()  # type: ignore
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_numpy(b"[1]", "int8", dtype="int8")  # type: ignore
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_numpy(b"[1]", other=1)  # type: ignore
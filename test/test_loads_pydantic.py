@@ -0,0 +1,136 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Union
+
+import pytest
+
+import hyperjson
+
+from .util import pydantic
+
+if pydantic is not None:
+
+    class Address(pydantic.BaseModel):
+        city: str
+        zip_code: str = pydantic.Field(alias="zipCode")
+
+    class User(pydantic.BaseModel):
+        id: int
+        name: str
+        tags: List[str] = []
+        address: Optional[Address] = None
+        previous: List[Address] = []
+        by_kind: Dict[str, Address] = {}
+
+    class Strict(pydantic.BaseModel):
+        model_config = pydantic.ConfigDict(extra="forbid")
+
+        a: int
+
+    class Validated(pydantic.BaseModel):
+        a: int
+
+        @pydantic.field_validator("a")
+        @classmethod
+        def double(cls, value):
+            return value * 2
+
+    class Either(pydantic.BaseModel):
+        value: Union[Address, Strict]
+
+    @dataclass
+    class Wrapper:
+        user: "User"
+
+
+@pytest.mark.skipif(pydantic is None, reason="pydantic is not installed")
+class TestLoadsPydantic:
+    def test_loads_pydantic(self):
+        """
+        loads() type= validates a pydantic model, building the models of its
+        fields from the document
+        """
+        obj = hyperjson.loads(
+            b'{"id":1,"name":"a","tags":["x"],'
+            b'"address":{"city":"c","zipCode":"1"},'
+            b'"previous":[{"city":"d","zipCode":"2"}],'
+            b'"by_kind":{"home":{"city":"e","zipCode":"3"}}}',
+            type=User,
+        )
+        assert obj == User(
+            id=1,
+            name="a",
+            tags=["x"],
+            address=Address(city="c", zipCode="1"),
+            previous=[Address(city="d", zipCode="2")],
+            by_kind={"home": Address(city="e", zipCode="3")},
+        )
+
+    def test_loads_pydantic_lax(self):
+        """
+        loads() type= leaves values to the model's validator, which may
+        convert them
+        """
+        assert hyperjson.loads(b'{"id":"1","name":"a"}', type=User) == User(
+            id=1, name="a"
+        )
+        assert hyperjson.loads(b'{"a":2}', type=Validated).a == 4
+
+    def test_loads_pydantic_invalid(self):
+        """
+        loads() type= raises the ValidationError of a model
+        """
+        with pytest.raises(pydantic.ValidationError):
+            hyperjson.loads(b'{"id":"a","name":"a"}', type=User)
+        with pytest.raises(pydantic.ValidationError):
+            hyperjson.loads(b'{"id":1,"name":"a","address":{"city":"c"}}', type=User)
+        with pytest.raises(pydantic.ValidationError):
+            hyperjson.loads(b'{"a":1,"b":2}', type=Strict)
+
+    def test_loads_pydantic_union(self):
+        """
+        loads() type= leaves a Union within a model to its validator
+        """
+        assert hyperjson.loads(b'{"value":{"a":1}}', type=Either) == Either(
+            value=Strict(a=1)
+        )
+
+    def test_loads_pydantic_container(self):
+        """
+        loads() type= reads a model in a container or dataclass
+        """
+        assert hyperjson.loads(b'[{"id":1,"name":"a"}]', type=List[User]) == [
+            User(id=1, name="a")
+        ]
+        assert hyperjson.loads(
+            b'{"user":{"id":1,"name":"a"}}', type=Wrapper
+        ) == Wrapper(User(id=1, name="a"))
+
+    def test_loads_pydantic_nested_location(self):
+        """
+        loads() type= raises the ValidationError of a nested model at its
+        location from the outermost model
+        """
+        with pytest.raises(pydantic.ValidationError) as exc:
+            hyperjson.loads(
+                b'{"id":1,"name":"a","previous":[{"city":"d"}]}', type=User
+            )
+        assert exc.value.errors()[0]["loc"] == ("previous", 0, "zipCode")
+        with pytest.raises(pydantic.ValidationError) as exc:
+            hyperjson.loads(b'{"id":1,"name":"a","address":{"city":1}}', type=User)
+        assert exc.value.errors()[0]["loc"] == ("address", "city")
+
+    def test_loads_pydantic_not_object(self):
+        """
+        loads() type= raises the ValidationError of a model that is not an
+        object
+        """
+        with pytest.raises(pydantic.ValidationError) as exc:
+            hyperjson.loads(b'{"id":1,"name":"a","previous":[1]}', type=User)
+        assert exc.value.errors()[0]["loc"] == ("previous", 0)
+        with pytest.raises(pydantic.ValidationError):
+            hyperjson.loads(b"[1]", type=User)
+        with pytest.raises(pydantic.ValidationError):
+            hyperjson.loads(b"[1]", type=List[User])
@@ -0,0 +1,97 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestLoadsRename:
+    def test_rename(self):
+        """
+        loads() rename= replaces keys at every depth
+        """
+        doc = (
+            b'{"userId": 1, "items": [{"itemId": 2, "userId": 3}],'
+            b' "other": {"userId": null}}'
+        )
+        assert hyperjson.loads(
+            doc, rename={"userId": "user_id", "itemId": "item_id"}
+        ) == {
+            "user_id": 1,
+            "items": [{"item_id": 2, "user_id": 3}],
+            "other": {"user_id": None},
+        }
+
+    def test_rename_values(self):
+        """
+        loads() rename= does not replace values
+        """
+        assert hyperjson.loads(b'{"a": "a", "b": ["a"]}', rename={"a": "c"}) == {
+            "c": "a",
+            "b": ["a"],
+        }
+
+    def test_rename_hash(self):
+        """
+        loads() rename= keys can be looked up
+        """
+        obj = hyperjson.loads(b'{"a": 1}', rename={"a": "b" * 100})
+        assert obj["b" * 100] == 1
+        assert list(obj) == ["b" * 100]
+
+    def test_rename_many(self):
+        """
+        loads() rename= with more keys than are in the document
+        """
+        rename = {f"key{idx}": f"renamed{idx}" for idx in range(1000)}
+        doc = hyperjson.dumps({f"key{idx}": idx for idx in range(0, 2000, 3)})
+        assert hyperjson.loads(doc, rename=rename) == {
+            (f"renamed{idx}" if idx < 1000 else f"key{idx}"): idx
+            for idx in range(0, 2000, 3)
+        }
+
+    def test_rename_non_ascii(self):
+        """
+        loads() rename= of non-ASCII keys
+        """
+        assert hyperjson.loads(
+            '{"ключ": 1, "🐈": 2}'.encode(), rename={"ключ": "key", "🐈": "猫"}
+        ) == {"key": 1, "猫": 2}
+
+    def test_rename_collision(self):
+        """
+        loads() rename= to a key in the document keeps the last value
+        """
+        assert hyperjson.loads(b'{"a": 1, "b": 2}', rename={"a": "b"}) == {"b": 2}
+        assert hyperjson.loads(b'{"b": 2, "a": 1}', rename={"a": "b"}) == {"b": 1}
+
+    def test_rename_empty(self):
+        """
+        loads() rename= that is empty or None
+        """
+        assert hyperjson.loads(b'{"a": 1}', rename={}) == {"a": 1}
+        assert hyperjson.loads(b'{"a": 1}', rename=None) == {"a": 1}
+        assert hyperjson.loads(b"[]", rename={"a": "b"}) == []
+        assert hyperjson.loads([b'{"a"', b": 1}"], rename={"a": "b"}) == {"b": 1}
+
+    def test_rename_invalid(self):
+        """
+        loads() rename= that is not a dict of str to str
+        """
+        for rename in ([("a", "b")], {"a": 1}, {1: "a"}, {"a": None}, "a"):
+            with pytest.raises(TypeError):
+                hyperjson.loads(b'{"a": 1}', rename=rename)  # type: ignore
+
+    def test_loads_args(self):
+        """
+        loads() positional and keyword arguments
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads()  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.loads(b"[]", b"[]")  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.loads(b"[]", renamed={})  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.loads(obj=b"[]")  # type: ignore
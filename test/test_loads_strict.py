@@ -0,0 +1,173 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+def strict_code(data):
+    with pytest.raises(hyperjson.JSONDecodeError) as exc:
+        hyperjson.loads(data, strict=True)
+    return exc.value.code
+
+
+class TestLoadsStrict:
+    def test_strict_valid(self):
+        """
+        loads(strict=True) reads RFC 8259 documents as loads() does
+        """
+        for doc in (
+            '{"a": [1, -0, 0.5, 1e3, -2E-2, true, false, null], "b": {"c": "\\ud83d\\ude00"}}',
+            "[]",
+            "{}",
+            '"\\u00e9"',
+            " 123 ",
+            "[18446744073709551615, -9223372036854775808]",
+        ):
+            assert hyperjson.loads(doc, strict=True) == hyperjson.loads(doc)
+
+    def test_strict_duplicate_key(self):
+        """
+        loads(strict=True) rejects duplicate keys at any depth
+        """
+        doc = '{"a": {"b": 1, "b": 2}}'
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc, strict=True)
+        assert exc.value.code == "duplicate_key"
+        assert exc.value.pos == doc.index('"b": 2')
+        assert strict_code('[{"a": 1}, {"a": 1, "a": 1}]') == "duplicate_key"
+
+    def test_strict_duplicate_key_renamed(self):
+        """
+        loads(strict=True) compares keys after rename=
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads('{"a": 1, "b": 2}', rename={"a": "b"}, strict=True)
+        assert exc.value.code == "duplicate_key"
+
+    def test_strict_duplicate_keys_error(self):
+        """
+        loads(strict=True) can be combined with duplicate_keys="error"
+        """
+        assert strict_code('{"a": 1, "a": 2}') == "duplicate_key"
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads('{"a": 1, "a": 2}', strict=True, duplicate_keys="error")
+        assert exc.value.code == "duplicate_key"
+
+    def test_strict_lone_surrogate(self):
+        """
+        loads(strict=True) rejects escaped lone surrogates
+        """
+        assert strict_code('"\\ud800"') == "lone_surrogate"
+        assert strict_code('"\\ud800abc"') == "lone_surrogate"
+        assert strict_code('"\\udc00"') == "lone_surrogate"
+        assert strict_code('"\\ud800\\u0041"') == "lone_surrogate"
+        assert strict_code('["\\ud800\\ud800"]') == "lone_surrogate"
+
+    def test_strict_leading_zero(self):
+        """
+        loads(strict=True) rejects numbers with leading zeros
+        """
+        assert strict_code("01") == "leading_zero"
+        assert strict_code("[-00.5]") == "leading_zero"
+        assert strict_code('{"a": 007}') == "leading_zero"
+
+    def test_strict_invalid_utf8(self):
+        """
+        loads(strict=True) rejects input that is not UTF-8
+        """
+        assert strict_code(b'"\xff"') == "invalid_utf8"
+        assert strict_code(b'["\xed\xa0\x80"]') == "invalid_utf8"
+        assert strict_code(bytearray(b'"\xc3"')) == "invalid_utf8"
+        assert strict_code('"\ud800"') == "invalid_utf8"
+
+    def test_strict_other(self):
+        """
+        loads(strict=True) reports other errors by kind
+        """
+        assert strict_code('"\x01"') == "control_character"
+        assert strict_code('"\\x41"') == "invalid_escape"
+        assert strict_code('"\\u12"') == "invalid_escape"
+        assert strict_code("1e400") == "number_out_of_range"
+        assert strict_code("[" * 2000 + "]" * 2000) == "depth_exceeded"
+        for doc in ("", "[1,]", "NaN", "// a\n1", "{'a': 1}", "﻿1", "1 2", "[1"):
+            assert strict_code(doc) == "syntax"
+
+    def test_strict_error_message(self):
+        """
+        loads(strict=True) raises with the same message and position as loads()
+        """
+        doc = '[1, 2, "\\ud800"]'
+        with pytest.raises(hyperjson.JSONDecodeError) as strict_exc:
+            hyperjson.loads(doc, strict=True)
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc)
+        assert strict_exc.value.msg == exc.value.msg
+        assert strict_exc.value.pos == exc.value.pos
+        assert isinstance(strict_exc.value, ValueError)
+
+    def test_code_default(self):
+        """
+        JSONDecodeError.code is None without strict=True
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads("01")
+        assert exc.value.code is None
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads("01", strict=False)
+        assert exc.value.code is None
+
+    def test_strict_false(self):
+        """
+        loads(strict=False) is the default
+        """
+        assert hyperjson.loads('{"a": 1, "a": 2}', strict=False) == {"a": 2}
+
+    def test_strict_omit_null(self):
+        """
+        loads(strict=True) rejects a duplicate key whose value is null with
+        OPT_OMIT_NULL_ON_LOAD
+        """
+        assert hyperjson.loads(
+            '{"a": null, "b": 1}',
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+            strict=True,
+        ) == {"b": 1}
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(
+                '{"a": null, "a": null}',
+                option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+                strict=True,
+            )
+        assert exc.value.code == "duplicate_key"
+
+    def test_strict_chunks(self):
+        """
+        loads(strict=True) applies to a document in chunks
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads((b'{"a": 1,', b' "a": 2}'), strict=True)
+        assert exc.value.code == "duplicate_key"
+
+    def test_strict_not_bool(self):
+        """
+        loads() strict must be a bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads("1", strict=1)
+
+    def test_strict_combined(self):
+        """
+        loads(strict=True) cannot be combined with an extension
+        """
+        for kwargs in (
+            {"allow_nan": True},
+            {"json5": True},
+            {"allow_comments": True},
+            {"allow_trailing_commas": True},
+            {"duplicate_keys": "first"},
+        ):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.loads("1", strict=True, **kwargs)
+        assert hyperjson.loads("1", strict=True, allow_nan=False, json5=False) == 1
@@ -0,0 +1,221 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import sys
+
+import pytest
+
+import hyperjson
+
+
+class TestLoadsTracked:
+    def test_loads_tracked_value(self):
+        """
+        loads_tracked() returns the same value as loads()
+        """
+        doc = b'{"a": [1, 2.5, "x", null, true], "b": {"c": "d"}}'
+        assert hyperjson.loads_tracked(doc) == hyperjson.loads(doc)
+
+    def test_loads_tracked_passthrough(self):
+        """
+        dumps() of an unmodified loads_tracked() result writes the input
+        """
+        doc = '{"a":[1,2.5,"é\\n\\u001f"],"b":{"c":"d"},"e":[true,null,1e16,-0.5]}'
+        obj = hyperjson.loads_tracked(doc.encode("utf-8"))
+        assert hyperjson.dumps(obj) == doc.encode("utf-8")
+
+    def test_loads_tracked_not_canonical(self):
+        """
+        dumps() of a loads_tracked() result of input not written as dumps()
+        writes it is serialized
+        """
+        for doc in (
+            b'{"a": [1, 2]}',
+            b"[1.10]",
+            b"[1e2]",
+            b"[-0]",
+            b'["\\u00e9"]',
+            b'["\\/"]',
+            b'["\\u000A"]',
+            b'["\\u000a"]',
+            b"[123456789012345678901]",
+        ):
+            obj = hyperjson.loads_tracked(doc)
+            assert hyperjson.dumps(obj) == hyperjson.dumps(hyperjson.loads(doc))
+
+    def test_loads_tracked_passthrough_str(self):
+        """
+        dumps() of an unmodified loads_tracked() result of str input
+        """
+        doc = '["é",1]'
+        assert hyperjson.dumps(hyperjson.loads_tracked(doc)) == doc.encode("utf-8")
+
+    def test_loads_tracked_passthrough_empty(self):
+        """
+        dumps() of an unmodified empty loads_tracked() result
+        """
+        assert hyperjson.dumps(hyperjson.loads_tracked(b"[]")) == b"[]"
+        assert hyperjson.dumps(hyperjson.loads_tracked(b"{}")) == b"{}"
+
+    def test_loads_tracked_append_newline(self):
+        """
+        dumps() of a loads_tracked() result with OPT_APPEND_NEWLINE
+        """
+        doc = b"[1,2]"
+        assert (
+            hyperjson.dumps(
+                hyperjson.loads_tracked(doc),
+                option=hyperjson.OPT_APPEND_NEWLINE,
+            )
+            == doc + b"\n"
+        )
+
+    def test_loads_tracked_append_crlf(self):
+        """
+        dumps() of a loads_tracked() result with OPT_APPEND_CRLF
+        """
+        doc = b"[1,2]"
+        assert (
+            hyperjson.dumps(
+                hyperjson.loads_tracked(doc),
+                option=hyperjson.OPT_APPEND_CRLF,
+            )
+            == doc + b"\r\n"
+        )
+
+    @pytest.mark.parametrize(
+        "option",
+        (
+            hyperjson.OPT_INDENT_2,
+            hyperjson.OPT_INDENT_4,
+            hyperjson.OPT_INDENT_8,
+            hyperjson.OPT_SORT_KEYS,
+            hyperjson.OPT_STRICT_INTEGER,
+        ),
+    )
+    def test_loads_tracked_incompatible_option(self, option):
+        """
+        dumps() of a loads_tracked() result with a formatting option
+        """
+        doc = b'{"b":1,"a":[2]}'
+        assert hyperjson.dumps(hyperjson.loads_tracked(doc), option=option) == (
+            hyperjson.dumps(hyperjson.loads(doc), option=option)
+        )
+
+    def test_loads_tracked_repeated(self):
+        """
+        each dumps() of a loads_tracked() result writes the same
+        """
+        for doc in (b"[1,2.5]", b"[1, 2.50]"):
+            obj = hyperjson.loads_tracked(doc)
+            assert hyperjson.dumps(obj) == b"[1,2.5]"
+            assert hyperjson.dumps(obj) == b"[1,2.5]"
+            assert hyperjson.dumps_length(obj) == 7
+
+    def test_loads_tracked_nested(self):
+        """
+        dumps() of a value nested in a loads_tracked() result is serialized
+        """
+        obj = hyperjson.loads_tracked(b'{"a": [1,  2]}')
+        assert hyperjson.dumps(obj["a"]) == b"[1,2]"
+
+    def test_loads_tracked_modified_value(self):
+        """
+        dumps() of a loads_tracked() result with a replaced value
+        """
+        obj = hyperjson.loads_tracked(b'{"a": [1,  2], "b": 3}')
+        obj["a"][1] = 4
+        assert hyperjson.dumps(obj) == b'{"a":[1,4],"b":3}'
+
+    def test_loads_tracked_modified_equal(self):
+        """
+        dumps() of a loads_tracked() result with an equal replacement
+        """
+        obj = hyperjson.loads_tracked(b'{"a": "value"}')
+        obj["a"] = "".join(("val", "ue"))
+        assert hyperjson.dumps(obj) == b'{"a":"value"}'
+
+    def test_loads_tracked_added(self):
+        """
+        dumps() of a loads_tracked() result with an added item
+        """
+        obj = hyperjson.loads_tracked(b'{"a": [1]}')
+        obj["a"].append(2)
+        assert hyperjson.dumps(obj) == b'{"a":[1,2]}'
+        obj = hyperjson.loads_tracked(b'{"a": [1]}')
+        obj["b"] = None
+        assert hyperjson.dumps(obj) == b'{"a":[1],"b":null}'
+
+    def test_loads_tracked_removed(self):
+        """
+        dumps() of a loads_tracked() result with a removed item
+        """
+        obj = hyperjson.loads_tracked(b'{"a": [1, 2], "b": 3}')
+        obj["a"].pop()
+        assert hyperjson.dumps(obj) == b'{"a":[1],"b":3}'
+        obj = hyperjson.loads_tracked(b'{"a": [1, 2], "b": 3}')
+        del obj["b"]
+        assert hyperjson.dumps(obj) == b'{"a":[1,2]}'
+
+    def test_loads_tracked_reordered(self):
+        """
+        dumps() of a loads_tracked() result with reordered keys
+        """
+        obj = hyperjson.loads_tracked(b'{"a": 1, "b": 2}')
+        obj["a"] = obj.pop("a")
+        assert hyperjson.dumps(obj) == b'{"b":2,"a":1}'
+
+    def test_loads_tracked_unsupported_added(self):
+        """
+        dumps() of a loads_tracked() result with an unsupported object added
+        """
+        obj = hyperjson.loads_tracked(b'[1,  2]')
+        obj.append(object())
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj)
+
+    def test_loads_tracked_duplicate_keys(self):
+        """
+        loads_tracked() of a document with duplicate keys is not passed through
+        """
+        obj = hyperjson.loads_tracked(b'{"a": 1,  "a": 2}')
+        assert hyperjson.dumps(obj) == b'{"a":2}'
+
+    def test_loads_tracked_scalar(self):
+        """
+        loads_tracked() of a scalar document
+        """
+        assert hyperjson.loads_tracked(b' "a" ') == "a"
+        assert hyperjson.loads_tracked(b"1") == 1
+
+    def test_loads_tracked_invalid(self):
+        """
+        loads_tracked() of invalid input raises JSONDecodeError
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_tracked(b"[1,")
+
+    def test_loads_tracked_evicted(self):
+        """
+        loads_tracked() releases the oldest results
+        """
+        first = hyperjson.loads_tracked(b"[1,2]")
+        ref = sys.getrefcount(first)
+        kept = [hyperjson.loads_tracked(b"[3,4]") for _ in range(16)]
+        assert sys.getrefcount(first) == ref - 1
+        assert hyperjson.dumps(first) == b"[1,2]"
+        assert len(kept) == 16
+
+    def test_loads_tracked_refcount(self):
+        """
+        a loads_tracked() result is tracked until it is freed by its user
+        """
+        obj = hyperjson.loads_tracked(b'{"key":["value"]}')
+        inner = obj["key"]
+        ref = sys.getrefcount(inner)
+        hyperjson.dumps(obj)
+        assert sys.getrefcount(inner) == ref
+        del obj
+        hyperjson.dumps([])
+        hyperjson.loads_tracked(b"[1]")
+        assert sys.getrefcount(inner) == ref - 2
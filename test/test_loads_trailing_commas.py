@@ -0,0 +1,92 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import math
+
+import pytest
+
+import hyperjson
+
+FIXTURE = """\
+{
+    "name": "fixture",
+    "tags": [
+        "a",
+        "b",
+    ],
+    "nested": {"empty": [], "one": {"a": 1,},},
+}
+"""
+
+
+class TestLoadsTrailingCommas:
+    def test_allow_trailing_commas(self):
+        """
+        loads() allow_trailing_commas=True accepts a comma after the last
+        element or member
+        """
+        assert hyperjson.loads(FIXTURE, allow_trailing_commas=True) == {
+            "name": "fixture",
+            "tags": ["a", "b"],
+            "nested": {"empty": [], "one": {"a": 1}},
+        }
+        assert hyperjson.loads(b"[1, 2 ,\n]", allow_trailing_commas=True) == [1, 2]
+
+    def test_allow_trailing_commas_default(self):
+        """
+        loads() rejects trailing commas by default
+        """
+        for val in ("[1,]", '{"a": 1,}', "[[],]"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val)
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_trailing_commas=False)
+
+    def test_allow_trailing_commas_standard(self):
+        """
+        loads() allow_trailing_commas=True of standard JSON
+        """
+        doc = '{"a": [1, -2.5, "c", null, true, false, {}], "": "é"}'
+        assert hyperjson.loads(doc, allow_trailing_commas=True) == hyperjson.loads(
+            doc
+        )
+
+    def test_allow_trailing_commas_strict(self):
+        """
+        loads() allow_trailing_commas=True accepts only one comma after a
+        value
+        """
+        for val in ("[,]", "{,}", "[1,,]", '{"a": 1,,}', "1,", "[1] ,", "[NaN,]"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(val, allow_trailing_commas=True)
+
+    def test_allow_trailing_commas_extended(self):
+        """
+        loads() allow_trailing_commas=True with allow_nan=True and
+        allow_comments=True
+        """
+        val = hyperjson.loads(
+            "[NaN, Infinity,]", allow_trailing_commas=True, allow_nan=True
+        )
+        assert math.isnan(val[0])
+        assert val[1:] == [math.inf]
+        assert hyperjson.loads(
+            '{"a": 1, // b\n}', allow_trailing_commas=True, allow_comments=True
+        ) == {"a": 1}
+
+    def test_allow_trailing_commas_omit_null(self):
+        """
+        loads() allow_trailing_commas=True with OPT_OMIT_NULL_ON_LOAD
+        """
+        assert hyperjson.loads(
+            '{"a": null, "b": 1, "c": null,}',
+            allow_trailing_commas=True,
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+        ) == {"b": 1}
+
+    def test_allow_trailing_commas_not_bool(self):
+        """
+        loads() allow_trailing_commas= not a bool
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads("[1,]", allow_trailing_commas=1)  # type: ignore
@@ -0,0 +1,496 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import collections
+import datetime
+import decimal
+import uuid
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, NamedTuple, Optional, Tuple, TypedDict, Union
+
+import pytest
+
+import hyperjson
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+@dataclass
+class Item:
+    id: int
+    name: str
+    price: float
+    tags: List[str] = field(default_factory=list)
+    note: Optional[str] = None
+
+
+@dataclass
+class Order:
+    items: List[Item]
+    origin: Point
+    meta: Dict[str, Any]
+    pair: Tuple[int, str]
+    values: Tuple[float, ...]
+
+
+@dataclass
+class Node:
+    value: int
+    children: List["Node"] = field(default_factory=list)
+
+
+@dataclass(frozen=True)
+class Frozen:
+    a: int
+
+
+@dataclass
+class PostInit:
+    a: int
+    b: int = field(init=False)
+
+    def __post_init__(self):
+        self.b = self.a * 2
+
+
+@dataclass
+class Event:
+    id: uuid.UUID
+    at: datetime.datetime
+    on: datetime.date
+    amount: decimal.Decimal
+    seen: List[datetime.datetime] = field(default_factory=list)
+
+
+@dataclass
+class Raises:
+    a: int
+
+    def __post_init__(self):
+        raise ValueError("a")
+
+
+@dataclass
+class Shape:
+    shape: Union[Point, List[int], None]
+
+
+@dataclass
+class Unresolved:
+    a: "DoesNotExist"  # type: ignore  # noqa: F821
+
+
+class Movie(TypedDict):
+    title: str
+    year: int
+    cast: List["Actor"]
+
+
+class Actor(TypedDict, total=False):
+    name: str
+    born: Optional[int]
+
+
+class Pair(NamedTuple):
+    a: int
+    b: List[Point]
+    c: str = "c"
+
+
+Untyped = collections.namedtuple("Untyped", ["a", "b"], defaults=[2])
+
+
+class TestLoadsType:
+    def test_loads_type_dataclass(self):
+        """
+        loads() type= builds a dataclass from an object
+        """
+        assert hyperjson.loads(b'{"x":1,"y":2}', type=Point) == Point(1, 2)
+        assert hyperjson.loads('{"y":2,"x":1}', type=Point) == Point(1, 2)
+
+    def test_loads_type_nested(self):
+        """
+        loads() type= builds the dataclasses and containers of the fields
+        """
+        data = (
+            b'{"items":[{"id":1,"name":"a","price":2,"tags":["x"]},'
+            b'{"id":2,"name":"b","price":1.5,"note":"n"}],'
+            b'"origin":{"x":0,"y":-1},"meta":{"k":[1,{"z":null}]},'
+            b'"pair":[1,"a"],"values":[1,2.5]}'
+        )
+        obj = hyperjson.loads(data, type=Order)
+        assert obj == Order(
+            items=[Item(1, "a", 2.0, ["x"]), Item(2, "b", 1.5, [], "n")],
+            origin=Point(0, -1),
+            meta={"k": [1, {"z": None}]},
+            pair=(1, "a"),
+            values=(1.0, 2.5),
+        )
+        assert type(obj.items[0].price) is float
+
+    def test_loads_type_recursive(self):
+        """
+        loads() type= reads a dataclass that refers to itself
+        """
+        assert hyperjson.loads(
+            b'{"value":1,"children":[{"value":2},{"value":3,"children":[]}]}',
+            type=Node,
+        ) == Node(1, [Node(2), Node(3)])
+
+    def test_loads_type_container(self):
+        """
+        loads() type= reads a container or scalar annotation
+        """
+        assert hyperjson.loads(b'[{"x":1,"y":2}]', type=List[Point]) == [Point(1, 2)]
+        assert hyperjson.loads(b'{"a":{"x":1,"y":2}}', type=Dict[str, Point]) == {
+            "a": Point(1, 2)
+        }
+        assert hyperjson.loads(b"[1,2]", type=list) == [1, 2]
+        assert hyperjson.loads(b"1", type=float) == 1.0
+        assert hyperjson.loads(b'"a"', type=str) == "a"
+        assert hyperjson.loads(b"null", type=Optional[int]) is None
+        assert hyperjson.loads(b'{"a":[1]}', type=Any) == {"a": [1]}
+
+    def test_loads_type_union(self):
+        """
+        loads() type= reads a value as the first member of a Union that
+        reads it
+        """
+        assert hyperjson.loads(b'{"shape":{"x":1,"y":2}}', type=Shape) == Shape(
+            Point(1, 2)
+        )
+        assert hyperjson.loads(b'{"shape":[1,2]}', type=Shape) == Shape([1, 2])
+        assert hyperjson.loads(b'{"shape":null}', type=Shape) == Shape(None)
+        assert hyperjson.loads(b'[1,"a"]', type=List[Union[int, str]]) == [1, "a"]
+
+    def test_loads_type_init(self):
+        """
+        loads() type= calls __init__, so that frozen dataclasses, fields with
+        init=False, and __post_init__ apply
+        """
+        assert hyperjson.loads(b'{"a":1}', type=Frozen) == Frozen(1)
+        obj = hyperjson.loads(b'{"a":2,"b":0}', type=PostInit)
+        assert (obj.a, obj.b) == (2, 4)
+
+    def test_loads_type_init_raises(self):
+        """
+        loads() type= raises the exception of __init__
+        """
+        with pytest.raises(ValueError):
+            hyperjson.loads(b'[{"a":1}]', type=List[Raises])
+
+    def test_loads_type_unknown_key(self):
+        """
+        loads() type= ignores keys that are not fields
+        """
+        assert hyperjson.loads(b'{"x":1,"z":[{}],"y":2}', type=Point) == Point(1, 2)
+
+    def test_loads_type_duplicate_key(self):
+        """
+        loads() type= keeps the last of duplicate keys
+        """
+        assert hyperjson.loads(b'{"x":1,"y":2,"x":3}', type=Point) == Point(3, 2)
+
+    def test_loads_type_missing(self):
+        """
+        loads() type= raises for a field without a default that is missing
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'{"origin":{"x":1}}', type=Dict[str, Point])
+        assert exc.value.msg == 'missing required field "y" of Point at $.origin'
+
+    def test_loads_type_mismatch(self):
+        """
+        loads() type= raises with the path of a value of the wrong type
+        """
+        for data, msg in (
+            (b'{"x":1,"y":"2"}', "expected int at $.y"),
+            (b"[]", "expected Point at $"),
+            (b'{"x":1.5,"y":2}', "expected int at $.x"),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads(data, type=Point)
+            assert exc.value.msg == msg
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(
+                b'{"value":1,"children":[{"value":2,"children":[{"value":null}]}]}',
+                type=Node,
+            )
+        assert exc.value.msg == "expected int at $.children[0].children[0].value"
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'{"shape":{"x":1}}', type=Shape)
+        assert exc.value.msg == 'missing required field "y" of Point at $.shape'
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'{"shape":"a"}', type=Shape)
+        assert exc.value.msg == "expected Point | list[int] | None at $.shape"
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b"[1]", type=Tuple[int, int])
+        assert exc.value.msg == "expected tuple[int, int] of 2 items at $"
+
+    def test_loads_type_rename(self):
+        """
+        loads() type= matches fields after rename=
+        """
+        assert hyperjson.loads(
+            b'{"X":1,"Y":2}', type=Point, rename={"X": "x", "Y": "y"}
+        ) == Point(1, 2)
+
+    def test_loads_type_omit_null(self):
+        """
+        loads() type= with OPT_OMIT_NULL_ON_LOAD leaves a null field to its
+        default
+        """
+        assert hyperjson.loads(
+            b'{"id":1,"name":"a","price":1,"note":null,"tags":null}',
+            type=Item,
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+        ) == Item(1, "a", 1.0)
+
+    def test_loads_type_none(self):
+        """
+        loads() type=None reads as loads() does
+        """
+        assert hyperjson.loads(b'{"x":1}', type=None) == {"x": 1}
+
+    def test_loads_type_invalid_document(self):
+        """
+        loads() type= raises for a document that is not valid
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b'{"x":1,', type=Point)
+
+    def test_loads_type_unsupported(self):
+        """
+        loads() type= raises for an annotation that cannot be read
+        """
+        for typ in (set, Dict[int, str], 1):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.loads(b"{}", type=typ)
+            assert str(exc.value).startswith("loads() type is not supported")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads(b"{}", type=Unresolved)
+        assert (
+            str(exc.value)
+            == "loads() type Unresolved has annotations that cannot be resolved"
+        )
+        assert isinstance(exc.value.__cause__, NameError)
+
+    def test_loads_type_combined(self):
+        """
+        loads() type cannot be combined with options of the extended parser
+        """
+        for kwargs in ({"json5": True}, {"strict": True}, {"parse_float": str}):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.loads(b"{}", type=Point, **kwargs)
+            assert str(exc.value).startswith("loads() type cannot be combined")
+
+    def test_loads_type_typeddict(self):
+        """
+        loads() type= reads a TypedDict as a dict of its keys, reading the
+        value of each as annotated
+        """
+        obj = hyperjson.loads(
+            b'{"title":"t","year":2000,"cast":[{"name":"a","born":1},{}],"x":1}',
+            type=Movie,
+        )
+        assert obj == {
+            "title": "t",
+            "year": 2000,
+            "cast": [{"name": "a", "born": 1}, {}],
+        }
+        assert type(obj) is dict
+
+    def test_loads_type_typeddict_required(self):
+        """
+        loads() type= raises for a missing key in __required_keys__ of a
+        TypedDict
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'{"title":"t","cast":[]}', type=Movie)
+        assert exc.value.msg == 'missing required field "year" of Movie at $'
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(
+                b'{"title":"t","year":1,"cast":[{"born":"1"}]}', type=Movie
+            )
+        assert exc.value.msg == "expected int | None at $.cast[0].born"
+
+    def test_loads_type_namedtuple(self):
+        """
+        loads() type= reads a NamedTuple from an array of its fields in order
+        or from an object
+        """
+        for data in (b'[1,[{"x":1,"y":2}]]', b'{"b":[{"x":1,"y":2}],"a":1}'):
+            obj = hyperjson.loads(data, type=Pair)
+            assert obj == Pair(1, [Point(1, 2)], "c")
+            assert type(obj) is Pair
+        assert hyperjson.loads(b'[1,[],"d"]', type=Pair) == Pair(1, [], "d")
+        assert hyperjson.loads(b'[[{"a":1}],{"a":1}]', type=List[Untyped]) == [
+            Untyped({"a": 1}, 2),
+            Untyped(1, 2),
+        ]
+
+    def test_loads_type_namedtuple_invalid(self):
+        """
+        loads() type= raises for an array of a NamedTuple that has too many
+        or too few items
+        """
+        for data, msg in (
+            (b"[1,[],\"d\",1]", "expected Pair of at most 3 items at $"),
+            (b"[1]", 'missing required field "b" of Pair at $'),
+            (b'[1,[{"x":1}]]', 'missing required field "y" of Point at $[1][0]'),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads(data, type=Pair)
+            assert exc.value.msg == msg
+
+    def test_loads_type_scalar(self):
+        """
+        loads() type= reads a string as a datetime, date, UUID, or Decimal
+        annotated so
+        """
+        obj = hyperjson.loads(
+            b'{"id":"7d6dfc3e-0c7c-4b4b-9f3e-2c58d2f3b0a1",'
+            b'"at":"2025-01-02T03:04:05.678Z","on":"2025-01-02",'
+            b'"amount":"1.10","seen":["2025-01-02 03:04:05+05:30",'
+            b'"2025-01-02T03:04:05.123456789-01:00","2025-01-02T03:04:05"]}',
+            type=Event,
+        )
+        assert obj.id == uuid.UUID("7d6dfc3e-0c7c-4b4b-9f3e-2c58d2f3b0a1")
+        assert obj.at == datetime.datetime(
+            2025, 1, 2, 3, 4, 5, 678000, tzinfo=datetime.timezone.utc
+        )
+        assert obj.on == datetime.date(2025, 1, 2)
+        assert type(obj.on) is datetime.date
+        assert obj.amount == decimal.Decimal("1.10")
+        assert str(obj.amount) == "1.10"
+        tz = datetime.timezone(datetime.timedelta(hours=5, minutes=30))
+        assert obj.seen == [
+            datetime.datetime(2025, 1, 2, 3, 4, 5, tzinfo=tz),
+            datetime.datetime(
+                2025,
+                1,
+                2,
+                3,
+                4,
+                5,
+                123456,
+                tzinfo=datetime.timezone(-datetime.timedelta(hours=1)),
+            ),
+            datetime.datetime(2025, 1, 2, 3, 4, 5),
+        ]
+        assert obj.seen[2].tzinfo is None
+
+    def test_loads_type_scalar_forms(self):
+        """
+        loads() type= reads a UUID without hyphens and a Decimal from an
+        integer
+        """
+        assert hyperjson.loads(
+            b'"7D6DFC3E0C7C4B4B9F3E2C58D2F3B0A1"', type=uuid.UUID
+        ) == uuid.UUID("7d6dfc3e-0c7c-4b4b-9f3e-2c58d2f3b0a1")
+        assert hyperjson.loads(
+            b"[12345678901234567890]", type=List[decimal.Decimal]
+        ) == [decimal.Decimal(12345678901234567890)]
+        assert hyperjson.loads(
+            b'["2024-02-29",null]', type=List[Optional[datetime.date]]
+        ) == [datetime.date(2024, 2, 29), None]
+
+    def test_loads_type_scalar_invalid(self):
+        """
+        loads() type= raises with the path of a string that is not a valid
+        datetime, date, UUID, or Decimal
+        """
+        for data, typ, msg in (
+            (b'"2025-13-01T00:00:00Z"', datetime.datetime, "invalid datetime at $"),
+            (b'"2025-01-01T24:00:00Z"', datetime.datetime, "invalid datetime at $"),
+            (b'"2025-01-01T00:00:00+0100"', datetime.datetime, "invalid datetime at $"),
+            (b'"2025-01-01"', datetime.datetime, "invalid datetime at $"),
+            (b'"2023-02-29"', datetime.date, "invalid date at $"),
+            (
+                b'["7d6dfc3e-0c7c-4b4b-9f3e-2c58d2f3b0a"]',
+                List[uuid.UUID],
+                "invalid UUID at $[0]",
+            ),
+            (b'"7d6dfc3e0-c7c-4b4b-9f3e-2c58d2f3b0a1"', uuid.UUID, "invalid UUID at $"),
+            (b'"abc"', decimal.Decimal, "invalid Decimal at $"),
+            (b"1.5", decimal.Decimal, "expected Decimal at $"),
+            (b"1", datetime.datetime, "expected datetime at $"),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads(data, type=typ)
+            assert exc.value.msg == msg
+
+    def test_loads_type_strict_types(self):
+        """
+        loads() strict_types=True returns what type= builds when it is of the
+        annotation
+        """
+        data = (
+            b'{"items": [{"id": 1, "name": "a", "price": 2}], "origin": '
+            b'{"x": 1, "y": 2}, "meta": {"k": [1]}, "pair": [1, "b"], '
+            b'"values": [1.5, 2]}'
+        )
+        expected = hyperjson.loads(data, type=Order)
+        assert hyperjson.loads(data, type=Order, strict_types=True) == expected
+        movie = b'{"title": "a", "year": 1, "cast": [{"name": "b"}]}'
+        assert hyperjson.loads(movie, type=Movie, strict_types=True) == {
+            "title": "a",
+            "year": 1,
+            "cast": [{"name": "b"}],
+        }
+        assert hyperjson.loads(
+            b'[1, [{"x": 1, "y": 2}]]', type=Pair, strict_types=True
+        ) == Pair(1, [Point(1, 2)])
+        assert hyperjson.loads(b'{"a": 1}', type=PostInit, strict_types=True).b == 2
+        assert hyperjson.loads(b"null", type=Optional[int], strict_types=True) is None
+
+    def test_loads_type_strict_types_mismatch(self):
+        """
+        loads() strict_types=True raises with the path of a value that
+        __post_init__ or a registered deserializer built as another type
+        """
+
+        @dataclass
+        class Converted:
+            a: int
+            b: List[str]
+
+            def __post_init__(self):
+                self.b = [s.upper() if s else None for s in self.b]
+
+        data = b'{"a": 1, "b": ["x", ""]}'
+        assert hyperjson.loads(data, type=Converted).b == ["X", None]
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(data, type=Converted, strict_types=True)
+        assert exc.value.msg == (
+            "strict_types: expected str, got NoneType at $.b[1]"
+        )
+
+        class Celsius:
+            def __init__(self, value):
+                self.value = value
+
+        hyperjson.register_deserializer(Celsius, float)
+        try:
+            assert hyperjson.loads(b"[1.5]", type=List[Celsius]) == [1.5]
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.loads(b"[1.5]", type=List[Celsius], strict_types=True)
+            assert exc.value.msg == (
+                "strict_types: expected Celsius, got float at $[0]"
+            )
+        finally:
+            hyperjson.register_deserializer(Celsius, None)
+
+    def test_loads_type_strict_types_invalid(self):
+        """
+        loads() strict_types= is a bool and requires type=
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads(b"1", strict_types=True)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads(b"1", type=int, strict_types=1)
+        assert hyperjson.loads(b"1", type=int, strict_types=False) == 1
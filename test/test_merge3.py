@@ -0,0 +1,91 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestMerge3:
+    def test_merge3(self):
+        """
+        merge3() takes the changes of each side
+        """
+        base = b'{"name":"app","replicas":1,"env":{"A":"1","B":"2"},"ports":[80]}'
+        ours = (
+            b'{"name":"app","replicas":3,"env":{"A":"1","B":"2","C":"3"},'
+            b'"ports":[80]}'
+        )
+        theirs = b'{"name":"app","replicas":1,"env":{"A":"9"},"ports":[80,443]}'
+        assert hyperjson.merge3(base, ours, theirs) == (
+            {
+                "name": "app",
+                "replicas": 3,
+                "env": {"A": "9", "C": "3"},
+                "ports": [80, 443],
+            },
+            [],
+        )
+
+    def test_merge3_same_change(self):
+        """
+        merge3() keeps a change both sides make
+        """
+        assert hyperjson.merge3({"a": 1}, {"a": 2}, {"a": 2.0}) == ({"a": 2}, [])
+        assert hyperjson.merge3({"a": 1}, {}, {}) == ({}, [])
+
+    def test_merge3_conflict(self):
+        """
+        merge3() keeps ours for a value both sides change and reports it
+        """
+        merged, conflicts = hyperjson.merge3(
+            {"a": {"b/c": 1, "d": [1]}},
+            {"a": {"b/c": 2, "d": [1, 2]}},
+            {"a": {"b/c": 3, "d": [0]}},
+        )
+        assert merged == {"a": {"b/c": 2, "d": [1, 2]}}
+        assert conflicts == [
+            {"path": "/a/b~1c", "base": 1, "ours": 2, "theirs": 3},
+            {"path": "/a/d", "base": [1], "ours": [1, 2], "theirs": [0]},
+        ]
+
+    def test_merge3_conflict_missing(self):
+        """
+        merge3() leaves out of a conflict the side that does not have the
+        member
+        """
+        assert hyperjson.merge3({"a": 1}, {}, {"a": 2}) == (
+            {},
+            [{"path": "/a", "base": 1, "theirs": 2}],
+        )
+        assert hyperjson.merge3({}, {"a": 1}, {"a": 2}) == (
+            {"a": 1},
+            [{"path": "/a", "ours": 1, "theirs": 2}],
+        )
+
+    def test_merge3_added_objects(self):
+        """
+        merge3() merges objects both sides add
+        """
+        assert hyperjson.merge3({}, {"a": {"b": 1}}, {"a": {"c": 2}}) == (
+            {"a": {"b": 1, "c": 2}},
+            [],
+        )
+
+    def test_merge3_root(self):
+        """
+        merge3() reports a conflict of the document with the empty path
+        """
+        assert hyperjson.merge3(1, 2, 3) == (
+            2,
+            [{"path": "", "base": 1, "ours": 2, "theirs": 3}],
+        )
+
+    def test_merge3_invalid(self):
+        """
+        merge3() raises for an invalid document or missing argument
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.merge3(b"{}", b"{", b"{}")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.merge3({}, {})
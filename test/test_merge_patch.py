@@ -0,0 +1,106 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+# RFC 7386, Appendix A
+RFC_CASES = (
+    ({"a": "b"}, {"a": "c"}, {"a": "c"}),
+    ({"a": "b"}, {"b": "c"}, {"a": "b", "b": "c"}),
+    ({"a": "b"}, {"a": None}, {}),
+    ({"a": "b", "b": "c"}, {"a": None}, {"b": "c"}),
+    ({"a": ["b"]}, {"a": "c"}, {"a": "c"}),
+    ({"a": "c"}, {"a": ["b"]}, {"a": ["b"]}),
+    (
+        {"a": {"b": "c"}},
+        {"a": {"b": "d", "c": None}},
+        {"a": {"b": "d"}},
+    ),
+    ({"a": [{"b": "c"}]}, {"a": [1]}, {"a": [1]}),
+    (["a", "b"], ["c", "d"], ["c", "d"]),
+    ({"a": "b"}, ["c"], ["c"]),
+    ({"a": "foo"}, None, None),
+    ({"a": "foo"}, "bar", "bar"),
+    ({"e": None}, {"a": 1}, {"e": None, "a": 1}),
+    ([1, 2], {"a": "b", "c": None}, {"a": "b"}),
+    ({}, {"a": {"bb": {"ccc": None}}}, {"a": {"bb": {}}}),
+)
+
+
+class TestMergePatch:
+    @pytest.mark.parametrize("target,patch,expected", RFC_CASES)
+    def test_merge_patch_rfc(self, target, patch, expected):
+        """
+        merge_patch() gives the results in RFC 7386
+        """
+        assert hyperjson.merge_patch(target, patch) == expected
+
+    def test_merge_patch_bytes(self):
+        """
+        merge_patch() reads bytes-like documents and returns bytes if target
+        is one
+        """
+        target = b'{"spec":{"replicas":1,"image":"a"},"status":{}}'
+        patch = b'{"spec":{"replicas":3},"status":null}'
+        assert hyperjson.merge_patch(target, patch) == (
+            b'{"spec":{"replicas":3,"image":"a"}}'
+        )
+        assert hyperjson.merge_patch(bytearray(target), memoryview(patch)) == (
+            b'{"spec":{"replicas":3,"image":"a"}}'
+        )
+        assert hyperjson.merge_patch({"a": 1}, b'{"b":2}') == {"a": 1, "b": 2}
+
+    def test_merge_patch_not_modified(self):
+        """
+        merge_patch() does not modify target or patch
+        """
+        target = {"a": {"b": 1, "c": 2}, "d": [1]}
+        patch = {"a": {"b": None, "e": {"f": None}}}
+        assert hyperjson.merge_patch(target, patch) == {
+            "a": {"c": 2, "e": {}},
+            "d": [1],
+        }
+        assert target == {"a": {"b": 1, "c": 2}, "d": [1]}
+        assert patch == {"a": {"b": None, "e": {"f": None}}}
+
+    def test_merge_patch_str(self):
+        """
+        merge_patch() uses str as a value rather than a document
+        """
+        assert hyperjson.merge_patch({"a": 1}, '{"b":2}') == '{"b":2}'
+
+    def test_merge_patch_invalid_document(self):
+        """
+        merge_patch() raises JSONDecodeError for an invalid document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.merge_patch(b"{", {})
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.merge_patch({}, b"{")
+
+    def test_merge_patch_unsupported(self):
+        """
+        merge_patch() raises JSONEncodeError if the result of a document
+        target cannot be serialized
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.merge_patch(b"{}", {"a": object()})
+
+    def test_merge_patch_recursion(self):
+        """
+        merge_patch() raises for a patch nested too deeply
+        """
+        patch: dict = {}
+        for _ in range(2000):
+            patch = {"a": patch}
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.merge_patch({}, patch)
+
+    def test_merge_patch_arguments(self):
+        """
+        merge_patch() takes exactly two positional arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.merge_patch({})
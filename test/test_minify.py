@@ -0,0 +1,170 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+
+import pytest
+
+import hyperjson
+
+DOCUMENT = b'{ "a" : [1, -2, 3.5, true, false, null],\n "b": {"c": "d\\u00e9\\n"} }'
+
+
+class TestMinify:
+    def test_minify(self):
+        """
+        minify() removes whitespace
+        """
+        assert hyperjson.minify(DOCUMENT) == (
+            b'{"a":[1,-2,3.5,true,false,null],"b":{"c":"d\xc3\xa9\\n"}}'
+        )
+
+    def test_minify_input_types(self):
+        """
+        minify() reads bytes, bytearray, memoryview, and str
+        """
+        expected = b'{"a":[1]}'
+        for data in (b' {"a": [1]} ', bytearray(b'{"a": [1]}'), '{"a": [1]}'):
+            assert hyperjson.minify(data) == expected
+        assert hyperjson.minify(memoryview(b'{"a": [1]}')) == expected
+
+    def test_minify_scalar(self):
+        """
+        minify() writes a document that is not a container
+        """
+        assert hyperjson.minify(b' "a" ') == b'"a"'
+        assert hyperjson.minify(b" 18446744073709551615 ") == b"18446744073709551615"
+        assert hyperjson.minify(b"null") == b"null"
+
+    def test_minify_empty_containers(self):
+        """
+        minify() writes empty arrays and objects
+        """
+        assert hyperjson.minify(b'[ [ ], { }, {"a": []} ]') == b'[[],{},{"a":[]}]'
+
+    def test_minify_matches_dumps(self):
+        """
+        minify() writes a document as dumps() of loads() of it does
+        """
+        data = json.dumps(
+            {"a": [1.25, 0.1, -(2**63)], "b": " ", "c": {"d": [{}]}},
+            indent=4,
+        ).encode()
+        assert hyperjson.minify(data) == hyperjson.dumps(hyperjson.loads(data))
+
+    def test_minify_numbers(self):
+        """
+        minify() writes numbers as they are in the document
+        """
+        assert hyperjson.minify(b"[ 123456789012345678901234567890 , -0 ]") == (
+            b"[123456789012345678901234567890,-0]"
+        )
+        assert hyperjson.minify(b'{"a": 0.10000000000000000000000001}') == (
+            b'{"a":0.10000000000000000000000001}'
+        )
+        assert hyperjson.minify(b"[1e400, 1E+2, 1.50, -0.0]") == (
+            b"[1e400,1E+2,1.50,-0.0]"
+        )
+        assert hyperjson.minify(b" 1e400 ") == b"1e400"
+
+    def test_minify_invalid_number(self):
+        """
+        minify() raises JSONDecodeError for an invalid number
+        """
+        for data in (b"[01]", b"[1.]", b"[-]", b"[1e]", b"[.5]"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.minify(data)
+
+    def test_minify_invalid(self):
+        """
+        minify() raises JSONDecodeError for an invalid document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.minify(b'{"a":')
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.minify(b"")
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.minify(b"[1] [2]")
+
+    def test_minify_type(self):
+        """
+        minify() raises JSONDecodeError for an object that is not a document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.minify({"a": 1})  # type: ignore
+
+
+class TestPrettify:
+    def test_prettify(self):
+        """
+        prettify() indents by 2 spaces by default
+        """
+        assert hyperjson.prettify(b'{"a":[1,{}],"b":{"c":null}}') == (
+            b'{\n  "a": [\n    1,\n    {}\n  ],\n  "b": {\n    "c": null\n  }\n}'
+        )
+
+    def test_prettify_matches_dumps(self):
+        """
+        prettify() writes a document as dumps() with OPT_INDENT_2 does
+        """
+        data = b'{"a":[1,2.5,"x",[]],"b":{"c":{"d":false}}}'
+        assert hyperjson.prettify(data) == hyperjson.dumps(
+            hyperjson.loads(data), option=hyperjson.OPT_INDENT_2
+        )
+        assert hyperjson.prettify(data, indent=4) == hyperjson.dumps(
+            hyperjson.loads(data), option=hyperjson.OPT_INDENT_4
+        )
+
+    def test_prettify_numbers(self):
+        """
+        prettify() writes numbers as they are in the document
+        """
+        assert hyperjson.prettify(b"[1e5,18446744073709551616]") == (
+            b"[\n  1e5,\n  18446744073709551616\n]"
+        )
+        assert hyperjson.prettify(b'{"a":3.14159265358979323846264338327950}') == (
+            b'{\n  "a": 3.14159265358979323846264338327950\n}'
+        )
+
+    def test_prettify_indent(self):
+        """
+        prettify() takes indent positionally or as a keyword
+        """
+        assert hyperjson.prettify(b"[1]", 3) == b"[\n   1\n]"
+        assert hyperjson.prettify(b"[1]", indent=1) == b"[\n 1\n]"
+
+    def test_prettify_roundtrip(self):
+        """
+        minify() of prettify() is minify()
+        """
+        data = b'{"a": [1, {"b": [true, null]}], "c": "\\ud83d\\ude00"}'
+        assert hyperjson.minify(hyperjson.prettify(data)) == hyperjson.minify(data)
+
+    def test_prettify_invalid_indent(self):
+        """
+        prettify() raises JSONEncodeError for an indent that is not a
+        non-negative int
+        """
+        for indent in (-1, 2.0, "2", None, 2**100):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.prettify(b"[]", indent=indent)  # type: ignore
+
+    def test_prettify_arguments(self):
+        """
+        prettify() rejects invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.prettify()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.prettify(b"[]", 2, 2)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.prettify(b"[]", 2, indent=2)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.prettify(b"[]", width=2)  # type: ignore
+
+    def test_prettify_invalid(self):
+        """
+        prettify() raises JSONDecodeError for an invalid document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.prettify(b"[1,")
@@ -0,0 +1,296 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import datetime
+import enum
+import uuid
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+    b: str
+
+
+class Color(enum.Enum):
+    RED = "red"
+
+
+class TestDumpsMsgpack:
+    def test_dumps_msgpack_scalars(self):
+        """
+        dumps_msgpack() writes scalars in their narrowest form
+        """
+        for obj, expected in (
+            (None, b"\xc0"),
+            (True, b"\xc3"),
+            (False, b"\xc2"),
+            (0, b"\x00"),
+            (127, b"\x7f"),
+            (128, b"\xcc\x80"),
+            (256, b"\xcd\x01\x00"),
+            (65536, b"\xce\x00\x01\x00\x00"),
+            (2**64 - 1, b"\xcf" + b"\xff" * 8),
+            (-1, b"\xff"),
+            (-32, b"\xe0"),
+            (-33, b"\xd0\xdf"),
+            (-129, b"\xd1\xff\x7f"),
+            (-(2**63), b"\xd3\x80" + b"\x00" * 7),
+            (1.5, b"\xcb\x3f\xf8" + b"\x00" * 6),
+            ("", b"\xa0"),
+            ("abc", b"\xa3abc"),
+            ("a" * 32, b"\xd9\x20" + b"a" * 32),
+            ("a" * 256, b"\xda\x01\x00" + b"a" * 256),
+            ("é", b"\xa2\xc3\xa9"),
+        ):
+            assert hyperjson.dumps_msgpack(obj) == expected
+
+    def test_dumps_msgpack_containers(self):
+        """
+        dumps_msgpack() writes arrays and maps with the narrowest header
+        """
+        assert hyperjson.dumps_msgpack([]) == b"\x90"
+        assert hyperjson.dumps_msgpack({}) == b"\x80"
+        assert hyperjson.dumps_msgpack([1, [2]]) == b"\x92\x01\x91\x02"
+        assert hyperjson.dumps_msgpack({"a": {"b": None}}) == (
+            b"\x81\xa1a\x81\xa1b\xc0"
+        )
+        assert hyperjson.dumps_msgpack([0] * 16) == b"\xdc\x00\x10" + b"\x00" * 16
+        assert hyperjson.dumps_msgpack(list(range(70000)))[:5] == (
+            b"\xdd\x00\x01\x11\x70"
+        )
+
+    def test_dumps_msgpack_types(self):
+        """
+        dumps_msgpack() supports the types dumps() does, written as dumps()
+        writes them
+        """
+        obj = {
+            "dt": datetime.datetime(2025, 1, 2, 3, 4, 5),
+            "date": datetime.date(2025, 1, 2),
+            "uuid": uuid.UUID("7202d115-7ff3-4c81-a7c1-2a1f067b1ece"),
+            "dataclass": Dataclass(1, "x"),
+            "enum": Color.RED,
+            "tuple": (1, 2),
+            "fragment": hyperjson.Fragment(b'{"x": [1, "y\\n", null, 2.5]}'),
+            "empty": [[], {}, ()],
+        }
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(obj)) == (
+            hyperjson.loads(hyperjson.dumps(obj))
+        )
+
+    def test_dumps_msgpack_number_lists(self):
+        """
+        dumps_msgpack() writes each number of a list of numbers
+        """
+        ints = list(range(-100, 100))
+        floats = [i / 3 for i in range(100)]
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(ints)) == ints
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(floats)) == floats
+
+    def test_dumps_msgpack_keys(self):
+        """
+        dumps_msgpack() writes the keys of dicts that share keys
+        """
+        obj = [{"key\n": i, "kéy": -i, "\U0001f600": None} for i in range(50)]
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(obj)) == obj
+        strs = ["a long string that is cached when escaped " * 4] * 10
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(strs)) == strs
+
+    def test_dumps_msgpack_non_finite(self):
+        """
+        dumps_msgpack() writes floats that are not finite
+        """
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(float("inf"))) == (
+            float("inf")
+        )
+
+    def test_dumps_msgpack_option(self):
+        """
+        dumps_msgpack() applies options, ignoring those that format JSON
+        """
+        assert hyperjson.dumps_msgpack(
+            {"b": 1, "a": None},
+            option=hyperjson.OPT_SORT_KEYS | hyperjson.OPT_INDENT_2,
+        ) == (b"\x82\xa1a\xc0\xa1b\x01")
+        assert hyperjson.dumps_msgpack(
+            {"b": 1, "a": None}, option=hyperjson.OPT_OMIT_NULL
+        ) == (b"\x81\xa1b\x01")
+        assert hyperjson.dumps_msgpack(
+            {1: 2}, None, hyperjson.OPT_NON_STR_KEYS
+        ) == (b"\x81\xa11\x02")
+
+    def test_dumps_msgpack_default(self):
+        """
+        dumps_msgpack() calls default
+        """
+        assert hyperjson.dumps_msgpack(1j, default=str) == b"\xa21j"
+        assert hyperjson.dumps_msgpack([{1}], default=list) == b"\x91\x91\x01"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(object())
+
+    def test_dumps_msgpack_bytes(self):
+        """
+        dumps_msgpack() writes bytes, bytearray, and memoryview as bin
+        """
+        for obj in (b"x", bytearray(b"x"), memoryview(b"x")):
+            assert hyperjson.dumps_msgpack(obj) == b"\xc4\x01x"
+        assert hyperjson.dumps_msgpack(b"") == b"\xc4\x00"
+        assert hyperjson.dumps_msgpack(b"a" * 256)[:3] == b"\xc5\x01\x00"
+        assert hyperjson.dumps_msgpack(b"a" * 65536)[:5] == b"\xc6\x00\x01\x00\x00"
+        obj = {"a": [b"\x00\xff", bytearray(b"y")]}
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(obj)) == {
+            "a": [b"\x00\xff", b"y"]
+        }
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(memoryview(b"abcd")[::2])
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(b"x")
+
+    def test_dumps_msgpack_unsupported_message(self):
+        """
+        dumps_msgpack() names MessagePack rather than JSON for an unsupported
+        type
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps_msgpack(object())
+        assert str(exc_info.value) == (
+            "Type is not MessagePack serializable: object"
+        )
+
+    def test_dumps_msgpack_raw_number(self):
+        """
+        dumps_msgpack() writes the value of a RawNumber
+        """
+        for text, expected in (
+            ("1", b"\x01"),
+            ("-1", b"\xff"),
+            ("18446744073709551615", b"\xcf" + b"\xff" * 8),
+            ("1.5", b"\xcb\x3f\xf8" + b"\x00" * 6),
+            ("15e-1", b"\xcb\x3f\xf8" + b"\x00" * 6),
+        ):
+            assert hyperjson.dumps_msgpack(hyperjson.RawNumber(text)) == expected
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(hyperjson.RawNumber("18446744073709551616"))
+
+    def test_dumps_msgpack_registered_dataclass(self):
+        """
+        dumps_msgpack() writes the field names of a registered dataclass
+        """
+        @dataclasses.dataclass
+        class Registered:
+            a: int
+            b: str
+
+        hyperjson.register_type(Registered)
+        assert hyperjson.dumps_msgpack(Registered(1, "x")) == (
+            b"\x82\xa1a\x01\xa1b\xa1x"
+        )
+
+    def test_dumps_msgpack_big_integer(self):
+        """
+        dumps_msgpack() raises for an integer exceeding 64 bits
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(2**64)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(2**64, option=hyperjson.OPT_BIG_INTEGER)
+
+    def test_dumps_msgpack_invalid_fragment(self):
+        """
+        dumps_msgpack() raises for a Fragment that is not valid JSON
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(hyperjson.Fragment(b"[1,"))
+
+    def test_dumps_msgpack_arguments(self):
+        """
+        dumps_msgpack() rejects invalid arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack()  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(1, None, None, None)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(1, None, default=None)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(1, width=2)  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps_msgpack(1, option=-1)
+
+
+class TestLoadsMsgpack:
+    def test_loads_msgpack_roundtrip(self):
+        """
+        loads_msgpack() reads what dumps_msgpack() writes
+        """
+        obj = {
+            "a": [None, True, False, 0, -1, 2**63 - 1, -(2**63), 2**64 - 1],
+            "b": {"c": [1.5, -0.0, "x" * 70000], "d": {}},
+            "e": [[]] * 20,
+        }
+        assert hyperjson.loads_msgpack(hyperjson.dumps_msgpack(obj)) == obj
+
+    def test_loads_msgpack_formats(self):
+        """
+        loads_msgpack() reads each format, including those not written
+        """
+        for data, expected in (
+            (b"\xd0\x7f", 127),
+            (b"\xd2\xff\xff\xff\xff", -1),
+            (b"\xcc\x01", 1),
+            (b"\xca\x3f\xc0\x00\x00", 1.5),
+            (b"\xdb\x00\x00\x00\x01a", "a"),
+            (b"\xdd\x00\x00\x00\x01\xc0", [None]),
+            (b"\xde\x00\x01\xa1a\x01", {"a": 1}),
+            (b"\xc4\x02ab", b"ab"),
+            (b"\xc5\x00\x01a", b"a"),
+        ):
+            assert hyperjson.loads_msgpack(data) == expected
+
+    def test_loads_msgpack_input_types(self):
+        """
+        loads_msgpack() reads bytes, bytearray, and memoryview
+        """
+        for data in (b"\x91\x01", bytearray(b"\x91\x01"), memoryview(b"\x91\x01")):
+            assert hyperjson.loads_msgpack(data) == [1]
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_msgpack("\x91\x01")  # type: ignore
+
+    def test_loads_msgpack_duplicate_keys(self):
+        """
+        loads_msgpack() keeps the last value of a duplicate key
+        """
+        assert hyperjson.loads_msgpack(b"\x82\xa1a\x01\xa1a\x02") == {"a": 2}
+
+    def test_loads_msgpack_invalid(self):
+        """
+        loads_msgpack() raises JSONDecodeError for invalid data
+        """
+        for data in (
+            b"",
+            b"\x92\x01",
+            b"\x01\x02",
+            b"\xa2a",
+            b"\xa1\xff",
+            b"\xc1",
+            b"\xd4\x01\x00",
+            b"\x81\x01\x02",
+            b"\xdd\xff\xff\xff\xff",
+            b"\xcd\x01",
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads_msgpack(data)
+
+    def test_loads_msgpack_recursion(self):
+        """
+        loads_msgpack() raises for data nested too deeply
+        """
+        assert hyperjson.loads_msgpack(b"\x91" * 1000 + b"\x90")
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads_msgpack(b"\x91" * 2000 + b"\x90")
@@ -0,0 +1,195 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import collections
+import dataclasses
+import typing
+
+import pytest
+
+import hyperjson
+
+Point = collections.namedtuple("Point", ["x", "y"])
+
+
+class TypedPoint(typing.NamedTuple):
+    x: int
+    y: int = 0
+
+
+class SubPoint(Point):
+    pass
+
+
+Empty = collections.namedtuple("Empty", [])
+
+
+class NotNamedTuple(tuple):
+    pass
+
+
+class PropertyFields(tuple):
+    @property
+    def _fields(self):
+        return tuple(f"k{idx}" for idx in range(len(self)))
+
+
+class TestNamedTuple:
+    def test_namedtuple_default(self):
+        """
+        dumps() namedtuple without OPT_NAMEDTUPLE_AS_OBJECT raises
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Point(1, 2))
+
+    def test_namedtuple_as_object(self):
+        """
+        dumps() collections.namedtuple OPT_NAMEDTUPLE_AS_OBJECT
+        """
+        assert (
+            hyperjson.dumps(Point(1, 2), option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b'{"x":1,"y":2}'
+        )
+
+    def test_namedtuple_typing(self):
+        """
+        dumps() typing.NamedTuple OPT_NAMEDTUPLE_AS_OBJECT
+        """
+        assert (
+            hyperjson.dumps(TypedPoint(1), option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b'{"x":1,"y":0}'
+        )
+
+    def test_namedtuple_subclass(self):
+        """
+        dumps() subclass of namedtuple OPT_NAMEDTUPLE_AS_OBJECT
+        """
+        assert (
+            hyperjson.dumps(SubPoint(1, 2), option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b'{"x":1,"y":2}'
+        )
+
+    def test_namedtuple_empty(self):
+        """
+        dumps() empty namedtuple OPT_NAMEDTUPLE_AS_OBJECT
+        """
+        assert (
+            hyperjson.dumps(Empty(), option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT) == b"{}"
+        )
+
+    def test_namedtuple_nested(self):
+        """
+        dumps() namedtuple in list, dict, and namedtuple
+        """
+        obj = {"a": [Point(Point(1, 2), {"b": TypedPoint(3, 4)})]}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b'{"a":[{"x":{"x":1,"y":2},"y":{"b":{"x":3,"y":4}}}]}'
+        )
+
+    def test_namedtuple_sort_keys(self):
+        """
+        dumps() namedtuple preserves field order; OPT_SORT_KEYS does not apply
+        """
+        Rev = collections.namedtuple("Rev", ["b", "a"])
+        assert (
+            hyperjson.dumps(
+                Rev(1, 2),
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"b":1,"a":2}'
+        )
+
+    def test_namedtuple_indent(self):
+        """
+        dumps() namedtuple OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps(
+                Point(1, 2),
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT | hyperjson.OPT_INDENT_2,
+            )
+            == b'{\n  "x": 1,\n  "y": 2\n}'
+        )
+
+    def test_namedtuple_default_value(self):
+        """
+        dumps() namedtuple field value uses default
+        """
+
+        class Custom:
+            pass
+
+        assert (
+            hyperjson.dumps(
+                Point(Custom(), 1),
+                default=lambda _: "custom",
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT,
+            )
+            == b'{"x":"custom","y":1}'
+        )
+
+    def test_namedtuple_tuple_unchanged(self):
+        """
+        dumps() plain tuple unaffected by OPT_NAMEDTUPLE_AS_OBJECT
+        """
+        assert (
+            hyperjson.dumps((1, 2), option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b"[1,2]"
+        )
+
+    def test_namedtuple_tuple_subclass_without_fields(self):
+        """
+        dumps() tuple subclass without _fields still raises
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(
+                NotNamedTuple((1, 2)),
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT,
+            )
+
+    def test_namedtuple_property_fields(self):
+        """
+        dumps() tuple subclass whose _fields is a property returning a new tuple
+        """
+        obj = PropertyFields((1, 2, 3))
+        assert (
+            hyperjson.dumps([obj, obj], option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
+            == b'[{"k0":1,"k1":2,"k2":3},{"k0":1,"k1":2,"k2":3}]'
+        )
+
+    def test_namedtuple_dataclass_field(self):
+        """
+        dumps() namedtuple as dataclass field
+        """
+
+        @dataclasses.dataclass
+        class Container:
+            point: Point
+
+        assert (
+            hyperjson.dumps(
+                Container(Point(1, 2)),
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT,
+            )
+            == b'{"point":{"x":1,"y":2}}'
+        )
+
+    def test_namedtuple_non_str_keys(self):
+        """
+        dumps() namedtuple as dict key is invalid with OPT_NON_STR_KEYS
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(
+                {Point(1, 2): 1},
+                option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT | hyperjson.OPT_NON_STR_KEYS,
+            )
+
+    def test_namedtuple_circular(self):
+        """
+        dumps() circular namedtuple
+        """
+        obj = Point([], 1)
+        obj.x.append(obj)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj, option=hyperjson.OPT_NAMEDTUPLE_AS_OBJECT)
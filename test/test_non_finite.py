@@ -0,0 +1,122 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import json
+import math
+
+import pytest
+
+import hyperjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+VALUES = [math.nan, math.inf, -math.inf, 1.5]
+
+
+class TestNonFinite:
+    def test_non_finite_null(self):
+        """
+        dumps() writes non-finite floats as null by default
+        """
+        assert hyperjson.dumps(VALUES) == b"[null,null,null,1.5]"
+        assert hyperjson.dumps(VALUES, non_finite="null") == b"[null,null,null,1.5]"
+        assert hyperjson.dumps(VALUES, non_finite=None) == b"[null,null,null,1.5]"
+
+    def test_non_finite_literal(self):
+        """
+        dumps() non_finite="literal" writes what json.dumps() does
+        """
+        assert (
+            hyperjson.dumps(VALUES, non_finite="literal")
+            == b"[NaN,Infinity,-Infinity,1.5]"
+        )
+        assert hyperjson.dumps(VALUES, non_finite="literal") == json.dumps(
+            VALUES, separators=(",", ":")
+        ).encode("utf-8")
+
+    def test_non_finite_literal_round_trip(self):
+        """
+        dumps() non_finite="literal" is read by loads(allow_nan=True)
+        """
+        obj = {"a": math.inf, "b": [-math.inf]}
+        doc = hyperjson.dumps(obj, non_finite="literal", option=hyperjson.OPT_INDENT_2)
+        assert hyperjson.loads(doc, allow_nan=True) == obj
+
+    def test_non_finite_string(self):
+        """
+        dumps() non_finite="string"
+        """
+        assert (
+            hyperjson.dumps(VALUES, non_finite="string")
+            == b'["NaN","Infinity","-Infinity",1.5]'
+        )
+
+    def test_non_finite_raise(self):
+        """
+        dumps() non_finite="raise"
+        """
+        for val in VALUES[:3]:
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps({"a": [val]}, non_finite="raise")
+            assert str(exc.value).startswith("float is not finite")
+        assert hyperjson.dumps(1.5, non_finite="raise") == b"1.5"
+
+    def test_non_finite_non_str_keys(self):
+        """
+        dumps() non_finite= of dict keys with OPT_NON_STR_KEYS
+        """
+        obj = {math.inf: 1}
+        opt = hyperjson.OPT_NON_STR_KEYS
+        assert hyperjson.dumps(obj, option=opt) == b'{"null":1}'
+        assert hyperjson.dumps(obj, option=opt, non_finite="literal") == (
+            b'{"Infinity":1}'
+        )
+        assert hyperjson.dumps(obj, option=opt, non_finite="string") == (
+            b'{"Infinity":1}'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj, option=opt, non_finite="raise")
+
+    def test_non_finite_loads_tracked(self):
+        """
+        dumps() non_finite= of a loads_tracked() result is serialized again
+        """
+        obj = hyperjson.loads_tracked(b"[1.5, 2.5]")
+        obj.append(math.nan)
+        assert hyperjson.dumps(obj, non_finite="string") == b'[1.5,2.5,"NaN"]'
+
+    def test_non_finite_length_batch(self):
+        """
+        dumps_length() and dumps_batch() non_finite=
+        """
+        assert hyperjson.dumps_length([math.nan], non_finite="literal") == 5
+        assert hyperjson.dumps_batch([[math.nan]], non_finite="string") == (
+            b'["NaN"]\n',
+            [0, 8],
+        )
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_non_finite_numpy(self):
+        """
+        dumps() non_finite= of numpy arrays
+        """
+        arr = numpy.array([numpy.nan, numpy.inf, 1.0], numpy.float32)
+        assert (
+            hyperjson.dumps(
+                arr, option=hyperjson.OPT_SERIALIZE_NUMPY, non_finite="literal"
+            )
+            == b"[NaN,Infinity,1.0]"
+        )
+
+    def test_non_finite_invalid(self):
+        """
+        dumps() non_finite= not a policy
+        """
+        for val in ("NaN", "", 1, True):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps(1.5, non_finite=val)  # type: ignore
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps_length(1.5, non_finite=val)  # type: ignore
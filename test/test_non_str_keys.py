@@ -1,6 +1,7 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 # Copyright ijl (2020-2025)
 
+import base64
 import dataclasses
 import datetime
 import uuid
@@ -244,6 +245,89 @@ class TestNonStrKeyTests:
             == b'{"1970-01-03":3,"1970-01-05":2,"other":1}'
         )
 
+    def test_dict_non_str_keys_sort_numeric(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS sort int and float keys numerically
+        """
+        assert (
+            hyperjson.dumps(
+                {10: 1, 9: 2, -1: 3, 2.5: 4, 18446744073709551615: 5, -0.5: 6},
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"-1":3,"-0.5":6,"2.5":4,"9":2,"10":1,"18446744073709551615":5}'
+        )
+
+    def test_dict_non_str_keys_sort_numeric_exact(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS sort int keys by their exact value
+        """
+        assert (
+            hyperjson.dumps(
+                {
+                    9007199254740994.0: "a",
+                    9007199254740993: "b",
+                    9007199254740992: "c",
+                },
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"9007199254740992":"c","9007199254740993":"b",'
+            b'"9007199254740994.0":"a"}'
+        )
+
+    def test_dict_non_str_keys_sort_datetime(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS sort datetime keys chronologically
+        across timezones
+        """
+        tz = datetime.timezone(datetime.timedelta(hours=-5))
+        assert (
+            hyperjson.dumps(
+                {
+                    datetime.datetime(2020, 1, 1, 10, tzinfo=tz): 1,
+                    datetime.datetime(2020, 1, 1, 12, tzinfo=datetime.timezone.utc): 2,
+                    datetime.date(2020, 1, 1): 3,
+                },
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"2020-01-01":3,"2020-01-01T12:00:00+00:00":2,'
+            b'"2020-01-01T10:00:00-05:00":1}'
+        )
+
+    def test_dict_non_str_keys_sort_mixed(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS sort numbers, then dates, then the
+        other keys by string
+        """
+        assert (
+            hyperjson.dumps(
+                {
+                    "b": 1,
+                    datetime.date(1970, 1, 1): 2,
+                    100: 3,
+                    None: 4,
+                    "10": 5,
+                    2: 6,
+                    True: 7,
+                },
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"2":6,"100":3,"1970-01-01":2,"10":5,"b":1,"null":4,"true":7}'
+        )
+
+    def test_dict_non_str_keys_sort_key_order(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS sort the strings written with a
+        key_order other than the default
+        """
+        assert (
+            hyperjson.dumps(
+                {10: 1, 9: 2, -1: 3},
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+                key_order="case_insensitive",
+            )
+            == b'{"-1":3,"10":1,"9":2}'
+        )
+
     @pytest.mark.skipif(pytz is None, reason="pytz optional")
     def test_dict_keys_time_err(self):
         """
@@ -319,3 +403,102 @@ class TestNonStrKeyTests:
         val = Obj()
         with pytest.raises(hyperjson.JSONEncodeError):
             hyperjson.dumps({val: True}, option=hyperjson.OPT_NON_STR_KEYS)
+
+    def test_dict_keys_bytes(self):
+        """
+        OPT_NON_STR_KEYS decodes bytes keys as UTF-8
+        """
+        assert (
+            hyperjson.dumps(
+                {b"key": 1, "é".encode("utf-8"): 2, b"": 3},
+                option=hyperjson.OPT_NON_STR_KEYS,
+            )
+            == '{"key":1,"é":2,"":3}'.encode("utf-8")
+        )
+
+    def test_dict_keys_bytes_escape(self):
+        """
+        OPT_NON_STR_KEYS escapes decoded bytes keys
+        """
+        assert (
+            hyperjson.dumps({b'a"\n': 1}, option=hyperjson.OPT_NON_STR_KEYS)
+            == b'{"a\\"\\n":1}'
+        )
+
+    def test_dict_keys_bytes_sort(self):
+        """
+        OPT_NON_STR_KEYS and OPT_SORT_KEYS with bytes keys
+        """
+        assert (
+            hyperjson.dumps(
+                {b"b": 1, "c": 2, b"a": 3},
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":3,"b":1,"c":2}'
+        )
+
+    def test_dict_keys_bytes_invalid_utf8(self):
+        """
+        OPT_NON_STR_KEYS bytes key that is not UTF-8 names the key
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps({b"ok": 1, b"\xffbad": 2}, option=hyperjson.OPT_NON_STR_KEYS)
+        assert (
+            str(exc_info.value)
+            == "Dict bytes key is not valid UTF-8: b'\\xffbad'; "
+            "use OPT_BYTES_KEYS_LATIN1 or OPT_BYTES_KEYS_BASE64"
+        )
+
+    def test_dict_keys_bytes_latin1(self):
+        """
+        OPT_BYTES_KEYS_LATIN1 decodes bytes keys as latin-1
+        """
+        assert hyperjson.dumps(
+            {b"\xffbad": 1, b"plain": 2},
+            option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_BYTES_KEYS_LATIN1,
+        ) == '{"\xffbad":1,"plain":2}'.encode("utf-8")
+
+    def test_dict_keys_bytes_base64(self):
+        """
+        OPT_BYTES_KEYS_BASE64 encodes bytes keys as base64
+        """
+        keys = [b"", b"a", b"ab", b"abc", b"abcd", b"\x00\xff\xfe\xfd\xfc"]
+        obj = {key: idx for idx, key in enumerate(keys)}
+        assert hyperjson.loads(
+            hyperjson.dumps(
+                obj,
+                option=hyperjson.OPT_NON_STR_KEYS | hyperjson.OPT_BYTES_KEYS_BASE64,
+            )
+        ) == {base64.b64encode(key).decode("ascii"): idx for idx, key in enumerate(keys)}
+
+    def test_dict_keys_bytes_base64_precedence(self):
+        """
+        OPT_BYTES_KEYS_BASE64 takes precedence over OPT_BYTES_KEYS_LATIN1
+        """
+        assert (
+            hyperjson.dumps(
+                {b"\xff": 1},
+                option=hyperjson.OPT_NON_STR_KEYS
+                | hyperjson.OPT_BYTES_KEYS_LATIN1
+                | hyperjson.OPT_BYTES_KEYS_BASE64,
+            )
+            == b'{"/w==":1}'
+        )
+
+    def test_dict_keys_bytes_policy_without_non_str_keys(self):
+        """
+        OPT_BYTES_KEYS_LATIN1 without OPT_NON_STR_KEYS does not allow bytes keys
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps({b"key": 1}, option=hyperjson.OPT_BYTES_KEYS_LATIN1)
+
+    def test_dict_keys_bytes_subclass(self):
+        """
+        OPT_NON_STR_KEYS bytes subclass key is not supported
+        """
+
+        class SubBytes(bytes):
+            pass
+
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps({SubBytes(b"a"): 1}, option=hyperjson.OPT_NON_STR_KEYS)
@@ -2,6 +2,8 @@
 # Copyright ijl (2020-2025), Ben Sully (2021), Nazar Kostetskyi (2022), Aviram Hassan (2020-2021), Marco Ribeiro (2020), Eric Jolibois (2021)
 # mypy: ignore-errors
 
+import os
+import subprocess
 import sys
 
 import pytest
@@ -11,12 +13,6 @@ import hyperjson
 from .util import numpy
 
 
-def numpy_default(obj):
-    if isinstance(obj, numpy.ndarray):
-        return obj.tolist()
-    raise TypeError
-
-
 @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
 class TestNumpy:
     def test_numpy_array_d1_uintp(self):
@@ -35,6 +31,94 @@ class TestNumpy:
             option=hyperjson.OPT_SERIALIZE_NUMPY,
         ) == f"[{low},{high}]".encode("ascii")
 
+    def test_numpy_without_option(self):
+        assert (
+            hyperjson.dumps([numpy.int64(1), numpy.array([2, 3], numpy.int64)])
+            == b"[1,[2,3]]"
+        )
+        assert hyperjson.dumps(
+            {"a": numpy.float32(1.5)}, option=hyperjson.OPT_INDENT_2
+        ) == hyperjson.dumps({"a": 1.5}, option=hyperjson.OPT_INDENT_2)
+
+    def test_numpy_without_option_default(self):
+        """
+        default is called for numpy types unless OPT_SERIALIZE_NUMPY is given
+        """
+
+        def default(obj):
+            if isinstance(obj, (numpy.generic, numpy.ndarray)):
+                return "numpy"
+            raise TypeError
+
+        obj = [numpy.int64(1), numpy.array([2, 3], numpy.int64)]
+        assert hyperjson.dumps(obj, default=default) == b'["numpy","numpy"]'
+        assert (
+            hyperjson.dumps(obj, default=default, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b"[1,[2,3]]"
+        )
+        assert hyperjson.dumps(obj, default={numpy.int64: str}) == b'["1",[2,3]]'
+        with pytest.raises(hyperjson.JSONEncodeError) as cm:
+            hyperjson.dumps(numpy.array(["a", "b"]), default={int: str})
+        assert "unsupported datatype in numpy array" in str(cm)
+
+    def test_numpy_array_like(self):
+        class ArrayInterface:
+            def __init__(self, array):
+                self.array = array
+
+            @property
+            def __array_interface__(self):
+                return self.array.__array_interface__
+
+        class ArrayStruct:
+            def __init__(self, array):
+                self.array = array
+
+            @property
+            def __array_struct__(self):
+                return self.array.__array_struct__
+
+        class Subclass(numpy.ndarray):
+            pass
+
+        array = numpy.array([[1, 2], [3, 4]], numpy.int32)
+        for obj in (
+            ArrayInterface(array),
+            ArrayInterface(array.T),
+            ArrayStruct(array),
+            array.view(Subclass),
+        ):
+            assert (
+                hyperjson.dumps(obj, option=hyperjson.OPT_SERIALIZE_NUMPY)
+                == hyperjson.dumps(numpy.asarray(obj).tolist())
+            )
+        assert (
+            hyperjson.dumps(
+                [ArrayInterface(numpy.array(["2021-01-01"], "datetime64[D]"))],
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+            )
+            == b'[["2021-01-01T00:00:00"]]'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(ArrayInterface(array))
+
+    def test_numpy_array_like_default(self):
+        class Invalid:
+            @property
+            def __array_interface__(self):
+                raise ValueError
+
+        assert (
+            hyperjson.dumps(
+                Invalid(),
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+                default=lambda _: "default",
+            )
+            == b'"default"'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Invalid(), option=hyperjson.OPT_SERIALIZE_NUMPY)
+
     def test_numpy_array_d1_i64(self):
         assert (
             hyperjson.dumps(
@@ -62,6 +146,76 @@ class TestNumpy:
             == b"[-128,127]"
         )
 
+    def test_numpy_array_d1_int_batch(self):
+        for dtype in (
+            numpy.int8,
+            numpy.int16,
+            numpy.int32,
+            numpy.int64,
+            numpy.uint8,
+            numpy.uint16,
+            numpy.uint32,
+            numpy.uint64,
+        ):
+            info = numpy.iinfo(dtype)
+            for length in (1, 15, 16, 17, 100):
+                obj = [
+                    info.min if idx % 3 == 0 else info.max if idx % 3 == 1 else 0
+                    for idx in range(length)
+                ]
+                assert hyperjson.dumps(
+                    numpy.array(obj, dtype),
+                    option=hyperjson.OPT_SERIALIZE_NUMPY,
+                ) == ("[" + ",".join(str(val) for val in obj) + "]").encode("ascii")
+
+    def test_numpy_array_d1_float_batch(self):
+        for dtype in (numpy.float32, numpy.float64):
+            info = numpy.finfo(dtype)
+            for length in (1, 15, 16, 17, 100):
+                obj = numpy.array(
+                    [
+                        info.min if idx % 4 == 0 else info.tiny if idx % 4 == 1 else 0.1
+                        for idx in range(length)
+                    ],
+                    dtype,
+                )
+                assert hyperjson.dumps(
+                    obj,
+                    option=hyperjson.OPT_SERIALIZE_NUMPY,
+                ) == hyperjson.dumps(
+                    list(obj),
+                    option=hyperjson.OPT_SERIALIZE_NUMPY,
+                )
+
+    def test_numpy_array_d1_float_batch_options(self):
+        obj = numpy.arange(40, dtype=numpy.float64) / 3
+        obj[20] = numpy.nan
+        obj[33] = numpy.inf
+        for kwargs in (
+            {},
+            {"float_format": ".2f"},
+            {"float_decimals": 3},
+            {"non_finite": "string"},
+        ):
+            assert hyperjson.dumps(
+                obj, option=hyperjson.OPT_SERIALIZE_NUMPY, **kwargs
+            ) == hyperjson.dumps(obj.tolist(), **kwargs)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_SERIALIZE_NUMPY, non_finite="raise"
+            )
+
+    def test_numpy_array_d2_int_batch_indent(self):
+        obj = numpy.arange(40, dtype=numpy.int64).reshape(2, 20)
+        assert hyperjson.dumps(
+            obj,
+            option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_INDENT_2,
+        ) == hyperjson.dumps(obj.tolist(), option=hyperjson.OPT_INDENT_2)
+        assert hyperjson.dumps(
+            obj,
+            option=hyperjson.OPT_SERIALIZE_NUMPY,
+        ) == hyperjson.dumps(obj.tolist())
+
     def test_numpy_array_d1_u8(self):
         assert (
             hyperjson.dumps(
@@ -447,28 +601,53 @@ class TestNumpy:
     def test_numpy_array_fortran(self):
         array = numpy.array([[1, 2], [3, 4]], order="F")
         assert array.flags["F_CONTIGUOUS"] is True
-        with pytest.raises(hyperjson.JSONEncodeError):
-            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
         assert hyperjson.dumps(
-            array,
-            default=numpy_default,
-            option=hyperjson.OPT_SERIALIZE_NUMPY,
+            array, option=hyperjson.OPT_SERIALIZE_NUMPY
         ) == hyperjson.dumps(array.tolist())
 
-    def test_numpy_array_non_contiguous_message(self):
-        array = numpy.array([[1, 2], [3, 4]], order="F")
-        assert array.flags["F_CONTIGUOUS"] is True
-        try:
-            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
-            raise AssertionError()
-        except TypeError as exc:
-            assert (
-                str(exc)
-                == "numpy array is not C contiguous; use ndarray.tolist() in default"
-            )
+    def test_numpy_array_non_contiguous(self):
+        base = numpy.arange(24, dtype=numpy.float64).reshape(2, 3, 4)
+        for array in (
+            base.T,
+            base[:, ::2, ::3],
+            base[::-1, :, ::-1],
+            base[..., 1],
+            numpy.broadcast_to(numpy.arange(3), (2, 3)),
+        ):
+            assert array.flags["C_CONTIGUOUS"] is False
+            assert hyperjson.dumps(
+                array, option=hyperjson.OPT_SERIALIZE_NUMPY
+            ) == hyperjson.dumps(array.tolist())
+            assert hyperjson.dumps(
+                array, option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_INDENT_2
+            ) == hyperjson.dumps(array.tolist(), option=hyperjson.OPT_INDENT_2)
+
+    def test_numpy_array_non_contiguous_types(self):
+        for dtype in (
+            numpy.int8,
+            numpy.uint16,
+            numpy.int32,
+            numpy.uint64,
+            numpy.float16,
+            numpy.float32,
+            numpy.complex64,
+            numpy.bool_,
+        ):
+            array = numpy.arange(6).astype(dtype)[::2]
+            assert hyperjson.dumps(
+                array, option=hyperjson.OPT_SERIALIZE_NUMPY
+            ) == hyperjson.dumps(array.tolist())
+
+    def test_numpy_array_unaligned(self):
+        data = b"\x00" + numpy.arange(3, dtype=numpy.int64).tobytes()
+        array = numpy.frombuffer(data, numpy.int64, offset=1)
+        assert array.flags["ALIGNED"] is False
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY) == b"[0,1,2]"
+        )
 
     def test_numpy_array_unsupported_dtype(self):
-        array = numpy.array([[1, 2], [3, 4]], numpy.csingle)  # type: ignore
+        array = numpy.array([["a", "b"], ["c", "d"]])
         with pytest.raises(hyperjson.JSONEncodeError) as cm:
             hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
         assert "unsupported datatype in numpy array" in str(cm)
@@ -748,6 +927,135 @@ class TestNumpy:
             == b"123.123"
         )
 
+    def test_numpy_scalar_complex(self):
+        assert (
+            hyperjson.dumps(
+                [numpy.complex64(1.5 - 2j), numpy.complex128(0.1 + 1e300j)],
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+            )
+            == b"[[1.5,-2.0],[0.1,1e300]]"
+        )
+
+    def test_numpy_array_complex(self):
+        for dtype in (numpy.complex64, numpy.complex128):
+            array = numpy.array([[1 + 2j, -0.5j], [3, 0]], dtype)
+            assert (
+                hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+                == b"[[[1.0,2.0],[0.0,-0.5]],[[3.0,0.0],[0.0,0.0]]]"
+            )
+
+    def test_numpy_array_complex_indent(self):
+        array = numpy.array([1 + 2j], numpy.complex128)
+        assert (
+            hyperjson.dumps(
+                array,
+                option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_INDENT_2,
+            )
+            == b"[\n  [\n    1.0,\n    2.0\n  ]\n]"
+        )
+
+    def test_numpy_array_structured(self):
+        array = numpy.array(
+            [(1, 1.5, True, "2021-01-01"), (-2, 0.0, False, "NaT")],
+            dtype=numpy.dtype(
+                [("a", "i4"), ("b", "f8"), ("c", "?"), ("d", "datetime64[s]")],
+                align=True,
+            ),
+        )
+        assert array.dtype.itemsize > 21
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'[{"a":1,"b":1.5,"c":true,"d":"2021-01-01T00:00:00"},'
+            b'{"a":-2,"b":0.0,"c":false,"d":null}]'
+        )
+
+    def test_numpy_array_structured_recarray(self):
+        array = numpy.rec.array(
+            [(1, 2.5), (3, -1.0)], dtype=[("x", "u2"), (("title", "y"), "f4")]
+        )
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'[{"x":1,"y":2.5},{"x":3,"y":-1.0}]'
+        )
+
+    def test_numpy_array_structured_2d(self):
+        array = numpy.zeros((2, 1), dtype=[("a", "i8"), ("b", "c8")])
+        array["a"] = [[1], [2]]
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'[[{"a":1,"b":[0.0,0.0]}],[{"a":2,"b":[0.0,0.0]}]]'
+        )
+
+    def test_numpy_array_structured_strided(self):
+        array = numpy.array(
+            [(i, i * 2) for i in range(5)], dtype=[("a", "i2"), ("b", "i8")]
+        )[::2]
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'[{"a":0,"b":0},{"a":2,"b":4},{"a":4,"b":8}]'
+        )
+
+    def test_numpy_array_structured_unsupported(self):
+        for dtype in (
+            [("a", "i4"), ("b", "U4")],
+            [("a", "i4"), ("b", [("c", "i4")])],
+            [("a", "i4"), ("b", "i4", (2,))],
+        ):
+            with pytest.raises(hyperjson.JSONEncodeError) as cm:
+                hyperjson.dumps(
+                    numpy.zeros(2, dtype=dtype), option=hyperjson.OPT_SERIALIZE_NUMPY
+                )
+            assert "unsupported datatype in numpy array" in str(cm)
+
+    def test_numpy_array_masked(self):
+        array = numpy.ma.array([1.5, 2.0, 3.0], mask=[False, True, False])
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b"[1.5,null,3.0]"
+        )
+
+    def test_numpy_array_masked_2d(self):
+        array = numpy.ma.masked_equal(numpy.array([[1, -1], [-1, 4]], numpy.int32), -1)
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b"[[1,null],[null,4]]"
+        )
+        assert (
+            hyperjson.dumps(
+                {"a": array},
+                option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_INDENT_2,
+            )
+            == hyperjson.dumps(
+                {"a": [[1, None], [None, 4]]}, option=hyperjson.OPT_INDENT_2
+            )
+        )
+
+    def test_numpy_array_masked_nomask(self):
+        array = numpy.ma.array([True, False])
+        assert array.mask is numpy.ma.nomask
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b"[true,false]"
+        )
+
+    def test_numpy_array_masked_datetime(self):
+        array = numpy.ma.array(
+            numpy.array(["2021-01-01", "NaT"], dtype="datetime64[D]"),
+            mask=[False, True],
+        )
+        assert (
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            == b'["2021-01-01T00:00:00",null]'
+        )
+
+    def test_numpy_array_masked_structured(self):
+        array = numpy.ma.array(
+            numpy.zeros(2, dtype=[("a", "i4")]), mask=[(True,), (False,)]
+        )
+        with pytest.raises(hyperjson.JSONEncodeError) as cm:
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+        assert "unsupported datatype in numpy array" in str(cm)
+
     def test_numpy_bool(self):
         assert (
             hyperjson.dumps(
@@ -1111,6 +1419,68 @@ class TestNumpy:
                 option=hyperjson.OPT_SERIALIZE_NUMPY,
             )
 
+    def test_numpy_datetime_week(self):
+        assert (
+            hyperjson.dumps(
+                numpy.array(["2021-01-07", "1969-12-25"], dtype="datetime64[W]"),
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+            )
+            == b'["2021-01-07T00:00:00","1969-12-25T00:00:00"]'
+        )
+
+    def test_numpy_timedelta(self):
+        for unit, values, expected in (
+            ("Y", [2, -1], b'["P2Y","-P1Y"]'),
+            ("M", [14, 0], b'["P14M","P0M"]'),
+            ("W", [2, -1], b'["P14D","-P7D"]'),
+            ("D", [1, 0], b'["P1D","PT0S"]'),
+            ("h", [25, 1], b'["P1DT1H","PT1H"]'),
+            ("m", [90, -61], b'["PT1H30M","-PT1H1M"]'),
+            ("s", [90, 86401], b'["PT1M30S","P1DT1S"]'),
+            ("ms", [1500, -1], b'["PT1.5S","-PT0.001S"]'),
+            ("us", [1, 3_600_000_001], b'["PT0.000001S","PT1H0.000001S"]'),
+            ("ns", [1, -1_000_000_010], b'["PT0.000000001S","-PT1.00000001S"]'),
+        ):
+            assert (
+                hyperjson.dumps(
+                    numpy.array(values, dtype=f"timedelta64[{unit}]"),
+                    option=hyperjson.OPT_SERIALIZE_NUMPY,
+                )
+                == expected
+            )
+
+    def test_numpy_timedelta_scalar(self):
+        assert (
+            hyperjson.dumps(
+                [numpy.timedelta64(90, "s"), numpy.timedelta64(3, "M")],
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+            )
+            == b'["PT1M30S","P3M"]'
+        )
+
+    def test_numpy_timedelta_nat(self):
+        array = numpy.array([1, "NaT"], dtype="timedelta64[s]")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+        assert str(exc.value) == "numpy.timedelta64 NaT at index [1]"
+        assert (
+            hyperjson.dumps(
+                array,
+                option=hyperjson.OPT_SERIALIZE_NUMPY,
+                datetime64_range="null",
+            )
+            == b'["PT1S",null]'
+        )
+
+    def test_numpy_timedelta_unsupported_unit(self):
+        for value, unit in (
+            (numpy.timedelta64(1, "ps"), "picoseconds"),
+            (numpy.timedelta64(1), "generic"),
+        ):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps(value, option=hyperjson.OPT_SERIALIZE_NUMPY)
+            assert str(exc.value) == f"unsupported numpy.timedelta64 unit: {unit}"
+
     def test_numpy_repeated(self):
         data = numpy.array([[[1, 2], [3, 4], [5, 6], [7, 8]]], numpy.int64)  # type: ignore
         for _ in range(3):
@@ -1188,3 +1558,103 @@ class NumpyEndianness:
         array = numpy.array([0, 1, 0.4, 5.7], dtype=f"{wrong_endianness}f8")
         with pytest.raises(hyperjson.JSONEncodeError):
             hyperjson.dumps(array, option=hyperjson.OPT_SERIALIZE_NUMPY)
+
+
+FIRST_USE_SCRIPT = """
+import threading
+
+import hyperjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None
+
+
+class Custom:
+    pass
+
+
+barrier = threading.Barrier(16)
+errors = []
+
+
+def run():
+    barrier.wait()
+    try:
+        if numpy is not None:
+            assert (
+                hyperjson.dumps(
+                    [numpy.int64(1), numpy.array([2, 3])],
+                    option=hyperjson.OPT_SERIALIZE_NUMPY,
+                )
+                == b"[1,[2,3]]"
+            )
+        try:
+            hyperjson.dumps(Custom(), option=hyperjson.OPT_SERIALIZE_NUMPY)
+        except hyperjson.JSONEncodeError:
+            pass
+        else:
+            raise AssertionError
+    except BaseException as exc:
+        errors.append(exc)
+
+
+threads = [threading.Thread(target=run) for _ in range(16)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+assert not errors, errors
+"""
+
+
+class TestNumpyFirstUse:
+    def test_numpy_types_first_use_concurrent(self):
+        """
+        dumps() looking up numpy types from many threads at once
+        """
+        env = dict(os.environ, PYTHONPATH=os.pathsep.join(sys.path))
+        proc = subprocess.run(
+            [sys.executable, "-c", FIRST_USE_SCRIPT],
+            env=env,
+            capture_output=True,
+            timeout=60,
+        )
+        assert proc.returncode == 0, proc.stderr
+
+
+NOT_IMPORTED_SCRIPT = """
+import sys
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+try:
+    hyperjson.dumps(Custom())
+except hyperjson.JSONEncodeError:
+    pass
+else:
+    raise AssertionError
+assert "numpy" not in sys.modules
+"""
+
+
+class TestNumpyNotImported:
+    def test_numpy_not_imported(self):
+        """
+        dumps() without OPT_SERIALIZE_NUMPY does not import numpy to look up
+        the type of an object
+        """
+        env = dict(os.environ, PYTHONPATH=os.pathsep.join(sys.path))
+        proc = subprocess.run(
+            [sys.executable, "-c", NOT_IMPORTED_SCRIPT],
+            env=env,
+            capture_output=True,
+            timeout=60,
+        )
+        assert proc.returncode == 0, proc.stderr
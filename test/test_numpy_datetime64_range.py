@@ -0,0 +1,242 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+# mypy: ignore-errors
+
+import pytest
+
+import hyperjson
+
+from .util import numpy
+
+NAT = -(2**63)
+
+# days since 1970-01-01 of 0001-01-01 and 9999-12-31
+MIN_DAY = -719162
+MAX_DAY = 2932896
+
+UNITS = {
+    "W": None,
+    "D": 1,
+    "h": 24,
+    "m": 24 * 60,
+    "s": 24 * 60 * 60,
+    "ms": 24 * 60 * 60 * 1_000,
+    "us": 24 * 60 * 60 * 1_000_000,
+}
+
+
+def dumps(obj, **kwargs):
+    return hyperjson.dumps(obj, option=hyperjson.OPT_SERIALIZE_NUMPY, **kwargs)
+
+
+class TestDatetime64RangeOption:
+    def test_invalid(self):
+        """
+        datetime64_range= must be one of the policies
+        """
+        for val in ("", "NULL", 1, b"null"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+                hyperjson.dumps([], datetime64_range=val)
+            assert (
+                str(exc_info.value)
+                == 'dumps() datetime64_range must be "raise", "null", "clamp", or "expanded"'
+            )
+
+    def test_without_numpy_values(self):
+        """
+        datetime64_range= has no effect on other types
+        """
+        assert hyperjson.dumps([1, None], datetime64_range="null") == b"[1,null]"
+
+
+@pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+class TestNumpyDatetime64Range:
+    def test_default_raise_before(self):
+        """
+        a value before year 1 raises by default
+        """
+        val = numpy.datetime64(MIN_DAY - 1, "D")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            dumps(val)
+        assert (
+            str(exc_info.value)
+            == f"numpy.datetime64 out of range: {MIN_DAY - 1} days"
+        )
+
+    def test_default_raise_after(self):
+        """
+        a value after year 9999 raises by default
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            dumps(numpy.datetime64(MAX_DAY + 1, "D"), datetime64_range="raise")
+        assert (
+            str(exc_info.value)
+            == f"numpy.datetime64 out of range: {MAX_DAY + 1} days"
+        )
+
+    def test_raise_index(self):
+        """
+        an error in an array names the index of the value
+        """
+        arr = numpy.array(
+            [[0, 1], [2, MAX_DAY + 1]],
+            dtype="datetime64[D]",
+        )
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            dumps(arr)
+        assert (
+            str(exc_info.value)
+            == f"numpy.datetime64 out of range at index [1, 1]: {MAX_DAY + 1} days"
+        )
+
+    def test_raise_nat(self):
+        """
+        NaT raises by default
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            dumps(numpy.datetime64("NaT"))
+        assert str(exc_info.value) == "numpy.datetime64 NaT"
+        arr = numpy.array(["2021-01-01", "NaT"], dtype="datetime64[s]")
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            dumps(arr)
+        assert str(exc_info.value) == "numpy.datetime64 NaT at index [1]"
+
+    def test_bounds(self):
+        """
+        years 1 and 9999 are in range in every unit
+        """
+        for unit, per_day in UNITS.items():
+            if per_day is None:
+                continue
+            arr = numpy.array(
+                [MIN_DAY * per_day, (MAX_DAY + 1) * per_day - 1],
+                dtype=f"datetime64[{unit}]",
+            )
+            out = hyperjson.dumps(
+                arr,
+                option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_OMIT_MICROSECONDS,
+            )
+            assert out.startswith(b'["0001-01-01T00:00:00","9999-12-31T'), unit
+
+    def test_null(self):
+        """
+        datetime64_range="null" writes null for NaT and out of range values
+        """
+        for unit, per_day in UNITS.items():
+            per_day = per_day or 1
+            arr = numpy.array(
+                [0, (MIN_DAY - 7) * per_day, (MAX_DAY + 7) * per_day],
+                dtype=f"datetime64[{unit}]",
+            )
+            assert (
+                dumps(arr, datetime64_range="null")
+                == b'["1970-01-01T00:00:00",null,null]'
+            ), unit
+        assert dumps(numpy.datetime64("NaT"), datetime64_range="null") == b"null"
+        assert (
+            dumps([numpy.datetime64(-20000, "Y")], datetime64_range="null")
+            == b"[null]"
+        )
+
+    def test_clamp(self):
+        """
+        datetime64_range="clamp" writes the first or last representable instant
+        """
+        for unit, per_day in UNITS.items():
+            per_day = per_day or 1
+            arr = numpy.array(
+                [(MIN_DAY - 7) * per_day, (MAX_DAY + 7) * per_day, NAT],
+                dtype=f"datetime64[{unit}]",
+            )
+            assert (
+                dumps(arr, datetime64_range="clamp")
+                == b'["0001-01-01T00:00:00","9999-12-31T23:59:59.999999",null]'
+            ), unit
+
+    def test_clamp_omit_microseconds(self):
+        """
+        datetime64_range="clamp" respects OPT_OMIT_MICROSECONDS
+        """
+        assert (
+            hyperjson.dumps(
+                numpy.datetime64(20000, "Y"),
+                option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_OMIT_MICROSECONDS,
+                datetime64_range="clamp",
+            )
+            == b'"9999-12-31T23:59:59"'
+        )
+
+    def test_expanded(self):
+        """
+        datetime64_range="expanded" writes a sign and at least six digits of
+        year
+        """
+        assert (
+            dumps(numpy.datetime64(MIN_DAY - 1, "D"), datetime64_range="expanded")
+            == b'"+000000-12-31T00:00:00"'
+        )
+        assert (
+            dumps(numpy.datetime64(MIN_DAY - 366, "D"), datetime64_range="expanded")
+            == b'"-000001-12-31T00:00:00"'
+        )
+        assert (
+            dumps(numpy.datetime64(MAX_DAY + 1, "D"), datetime64_range="expanded")
+            == b'"+010000-01-01T00:00:00"'
+        )
+        assert (
+            hyperjson.dumps(
+                numpy.datetime64((MAX_DAY + 1) * 86400 + 3661, "s"),
+                option=hyperjson.OPT_SERIALIZE_NUMPY | hyperjson.OPT_NAIVE_UTC,
+                datetime64_range="expanded",
+            )
+            == b'"+010000-01-01T01:01:01+00:00"'
+        )
+        assert (
+            dumps(numpy.datetime64(1_000_000 - 1970, "Y"), datetime64_range="expanded")
+            == b'"+1000000-01-01T00:00:00"'
+        )
+        assert dumps(numpy.datetime64("NaT"), datetime64_range="expanded") == b"null"
+
+    def test_in_range_unchanged(self):
+        """
+        values in range are written the same under every policy
+        """
+        arr = numpy.array(
+            ["2021-01-01T00:00:00.172", "1969-12-31T23:59:59.5"],
+            dtype="datetime64[ms]",
+        )
+        for policy in (None, "raise", "null", "clamp", "expanded"):
+            assert (
+                dumps(arr, datetime64_range=policy)
+                == b'["2021-01-01T00:00:00.172000","1969-12-31T23:59:59.500000"]'
+            )
+
+    def test_months_before_epoch(self):
+        """
+        months before 1970 are written
+        """
+        arr = numpy.array(["1969-12", "1900-01"], dtype="datetime64[M]")
+        assert dumps(arr) == b'["1969-12-01T00:00:00","1900-01-01T00:00:00"]'
+
+    def test_years(self):
+        """
+        years before 1 and after 9999 follow the policy
+        """
+        arr = numpy.array([-1970, 10000 - 1970], dtype="datetime64[Y]")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            dumps(arr)
+        assert (
+            dumps(arr, datetime64_range="expanded")
+            == b'["+000000-01-01T00:00:00","+010000-01-01T00:00:00"]'
+        )
+
+    def test_dumps_length(self):
+        """
+        dumps_length() takes datetime64_range=
+        """
+        val = numpy.datetime64(MAX_DAY + 1, "D")
+        assert hyperjson.dumps_length(
+            val,
+            option=hyperjson.OPT_SERIALIZE_NUMPY,
+            datetime64_range="null",
+        ) == len(b"null")
@@ -0,0 +1,147 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import collections
+import json
+
+import pytest
+
+import hyperjson
+
+
+class TestObjectPairsHook:
+    def test_pairs(self):
+        """
+        object_pairs_hook receives a list of (key, value) tuples in order
+        """
+        calls = []
+
+        def hook(pairs):
+            calls.append(pairs)
+            return pairs
+
+        doc = '{"b": 1, "a": [{"c": null}], "d": {}}'
+        outer = [("b", 1), ("a", [[("c", None)]]), ("d", [])]
+        assert hyperjson.loads(doc, object_pairs_hook=hook) == outer
+        assert calls == [[("c", None)], [], outer]
+        assert all(type(pairs) is list for pairs in calls)
+        assert all(type(pair) is tuple for pairs in calls for pair in pairs)
+
+    def test_ordered_dict(self):
+        """
+        object_pairs_hook can construct another mapping
+        """
+        doc = '{"z": 1, "a": {"y": 2, "b": 3}}'
+        val = hyperjson.loads(doc, object_pairs_hook=collections.OrderedDict)
+        assert val == json.loads(doc, object_pairs_hook=collections.OrderedDict)
+        assert type(val) is collections.OrderedDict
+        assert type(val["a"]) is collections.OrderedDict
+
+    def test_empty_document(self):
+        """
+        object_pairs_hook is called for a document that is an empty object
+        """
+        assert hyperjson.loads("{}", object_pairs_hook=tuple) == ()
+        assert hyperjson.loads(b"{ }", object_pairs_hook=tuple) == ()
+        assert hyperjson.loads("[]", object_pairs_hook=tuple) == []
+
+    def test_duplicate_keys(self):
+        """
+        object_pairs_hook receives every member with a key by default
+        """
+        doc = '{"a": 1, "a": 2, "b": 3}'
+        assert hyperjson.loads(doc, object_pairs_hook=list) == json.loads(
+            doc, object_pairs_hook=list
+        )
+        assert hyperjson.loads(doc, object_pairs_hook=list) == [
+            ("a", 1),
+            ("a", 2),
+            ("b", 3),
+        ]
+
+    def test_duplicate_keys_option(self):
+        """
+        duplicate_keys= and strict= apply before object_pairs_hook
+        """
+        doc = '{"a": 1, "a": 2, "b": 3}'
+        assert hyperjson.loads(
+            doc, object_pairs_hook=list, duplicate_keys="first"
+        ) == [("a", 1), ("b", 3)]
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc, object_pairs_hook=list, duplicate_keys="error")
+        assert exc.value.pos == doc.index('"a": 2')
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(doc, object_pairs_hook=list, strict=True)
+        assert exc.value.code == "duplicate_key"
+
+    def test_rename(self):
+        """
+        object_pairs_hook receives keys after rename=
+        """
+        assert hyperjson.loads(
+            '{"a": 1, "b": 2}', object_pairs_hook=list, rename={"a": "c"}
+        ) == [("c", 1), ("b", 2)]
+
+    def test_omit_null(self):
+        """
+        object_pairs_hook does not receive null members with
+        OPT_OMIT_NULL_ON_LOAD
+        """
+        assert hyperjson.loads(
+            '{"a": null, "b": 1}',
+            object_pairs_hook=list,
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+        ) == [("b", 1)]
+
+    def test_extensions(self):
+        """
+        object_pairs_hook can be combined with the other options
+        """
+        assert hyperjson.loads(
+            "{a: NaN, // c\n 'b': 1,}", object_pairs_hook=list, json5=True
+        )[1] == ("b", 1)
+        assert hyperjson.loads(
+            '/* c */ {"a": 1}', object_pairs_hook=list, allow_comments=True
+        ) == [("a", 1)]
+        assert hyperjson.loads(
+            (b'{"a":', b" 1}"), object_pairs_hook=list
+        ) == [("a", 1)]
+
+    def test_none(self):
+        """
+        object_pairs_hook=None returns dict
+        """
+        assert hyperjson.loads('{"a": 1}', object_pairs_hook=None) == {"a": 1}
+
+    def test_not_callable(self):
+        """
+        object_pairs_hook must be callable or None
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("{}", object_pairs_hook=1)
+        assert str(exc.value) == "loads() object_pairs_hook must be callable or None"
+
+    def test_hook_raises(self):
+        """
+        an exception raised by object_pairs_hook propagates
+        """
+
+        def hook(pairs):
+            if pairs:
+                raise ValueError("rejected")
+            return {}
+
+        with pytest.raises(ValueError, match="rejected") as exc:
+            hyperjson.loads('[{}, {"a": {}}]', object_pairs_hook=hook)
+        assert not isinstance(exc.value, hyperjson.JSONDecodeError)
+        with pytest.raises(ValueError, match="rejected"):
+            hyperjson.loads('[{}, {"a": {}}]', object_pairs_hook=hook, strict=True)
+
+    def test_invalid_after_hook(self):
+        """
+        a document that is invalid after an object raises JSONDecodeError
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads('[{"a": 1}, ]', object_pairs_hook=list)
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads('{"a": {"b": 1}', object_pairs_hook=list)
@@ -0,0 +1,130 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Exported:
+    name: str
+    tags: list
+    meta: dict
+
+
+class Text(str):
+    pass
+
+
+class TestOmitEmpty:
+    def test_omit_empty(self):
+        """
+        dumps() OPT_OMIT_EMPTY leaves out empty dicts, lists, tuples, and str
+        """
+        obj = {"a": {}, "b": [], "c": (), "d": "", "e": 0, "f": None, "g": [{}]}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY)
+            == b'{"e":0,"f":null,"g":[{}]}'
+        )
+
+    def test_omit_empty_subset(self):
+        """
+        dumps() OPT_OMIT_EMPTY_DICT, OPT_OMIT_EMPTY_LIST, OPT_OMIT_EMPTY_STR
+        """
+        obj = {"a": {}, "b": [], "c": (), "d": ""}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY_DICT)
+            == b'{"b":[],"c":[],"d":""}'
+        )
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY_LIST)
+            == b'{"a":{},"d":""}'
+        )
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY_STR)
+            == b'{"a":{},"b":[],"c":[]}'
+        )
+        assert hyperjson.OPT_OMIT_EMPTY == (
+            hyperjson.OPT_OMIT_EMPTY_DICT
+            | hyperjson.OPT_OMIT_EMPTY_LIST
+            | hyperjson.OPT_OMIT_EMPTY_STR
+        )
+
+    def test_omit_empty_nested(self):
+        """
+        dumps() OPT_OMIT_EMPTY decides by the value, not its output
+        """
+        obj = {"a": {"b": {}}, "c": [[]]}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY)
+            == b'{"a":{},"c":[[]]}'
+        )
+
+    def test_omit_empty_subclass(self):
+        """
+        dumps() OPT_OMIT_EMPTY does not leave out subclasses
+        """
+        assert (
+            hyperjson.dumps({"a": Text("")}, option=hyperjson.OPT_OMIT_EMPTY)
+            == b'{"a":""}'
+        )
+
+    def test_omit_empty_sort_keys(self):
+        """
+        dumps() OPT_OMIT_EMPTY with OPT_SORT_KEYS and OPT_NON_STR_KEYS
+        """
+        obj = {"c": "", "b": [1], "a": {}}
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_OMIT_EMPTY | hyperjson.OPT_SORT_KEYS
+            )
+            == b'{"b":[1]}'
+        )
+        assert (
+            hyperjson.dumps(
+                {1: [], 2: "x"},
+                option=hyperjson.OPT_OMIT_EMPTY | hyperjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"2":"x"}'
+        )
+
+    def test_omit_empty_dataclass(self):
+        """
+        dumps() OPT_OMIT_EMPTY leaves out dataclass fields
+        """
+        assert (
+            hyperjson.dumps(
+                Exported("", ["x"], {}),
+                option=hyperjson.OPT_OMIT_EMPTY | hyperjson.OPT_OMIT_NULL,
+            )
+            == b'{"tags":["x"]}'
+        )
+
+    def test_omit_empty_indent(self):
+        """
+        dumps() OPT_OMIT_EMPTY with OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [], "b": 1},
+                option=hyperjson.OPT_OMIT_EMPTY | hyperjson.OPT_INDENT_2,
+            )
+            == b'{\n  "b": 1\n}'
+        )
+
+    def test_omit_empty_loads_tracked(self):
+        """
+        dumps() OPT_OMIT_EMPTY of a loads_tracked() result
+        """
+        obj = hyperjson.loads_tracked(b'{"a": [], "b": ""}')
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_EMPTY) == b"{}"
+
+    def test_omit_empty_loads_option(self):
+        """
+        loads() does not accept OPT_OMIT_EMPTY
+        """
+        with pytest.raises(TypeError):
+            hyperjson.loads(b"{}", option=hyperjson.OPT_OMIT_EMPTY)
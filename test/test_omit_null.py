@@ -0,0 +1,122 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+from typing import Optional
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Sparse:
+    a: Optional[int]
+    b: Optional[str] = None
+
+
+@dataclasses.dataclass
+class SparseSlots:
+    __slots__ = ("a", "b")
+    a: Optional[int]
+    b: Optional[str]
+
+
+class TestOmitNull:
+    def test_omit_null_dict(self):
+        """
+        dumps() OPT_OMIT_NULL leaves out dict entries that are None
+        """
+        obj = {"a": None, "b": 1, "c": None, "d": [None], "e": {"f": None}}
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_NULL)
+            == b'{"b":1,"d":[null],"e":{}}'
+        )
+
+    def test_omit_null_all(self):
+        """
+        dumps() OPT_OMIT_NULL of a dict whose values are all None
+        """
+        obj = {"a": None, "b": None}
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_NULL) == b"{}"
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_OMIT_NULL | hyperjson.OPT_INDENT_2
+            )
+            == b"{}"
+        )
+
+    def test_omit_null_sort_keys(self):
+        """
+        dumps() OPT_OMIT_NULL with OPT_SORT_KEYS
+        """
+        obj = {"c": 1, "b": None, "a": 2}
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_OMIT_NULL | hyperjson.OPT_SORT_KEYS
+            )
+            == b'{"a":2,"c":1}'
+        )
+
+    def test_omit_null_non_str_keys(self):
+        """
+        dumps() OPT_OMIT_NULL with OPT_NON_STR_KEYS
+        """
+        obj = {1: None, None: 2, "a": None}
+        assert (
+            hyperjson.dumps(
+                obj, option=hyperjson.OPT_OMIT_NULL | hyperjson.OPT_NON_STR_KEYS
+            )
+            == b'{"null":2}'
+        )
+
+    def test_omit_null_shared_keys(self):
+        """
+        dumps() OPT_OMIT_NULL of a list of dicts with the same keys
+        """
+        obj = [{"a": idx, "b": None if idx % 2 else idx} for idx in range(4)]
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_NULL)
+            == b'[{"a":0,"b":0},{"a":1},{"a":2,"b":2},{"a":3}]'
+        )
+
+    def test_omit_null_dataclass(self):
+        """
+        dumps() OPT_OMIT_NULL leaves out dataclass fields that are None
+        """
+        assert hyperjson.dumps(Sparse(1), option=hyperjson.OPT_OMIT_NULL) == b'{"a":1}'
+        assert (
+            hyperjson.dumps(SparseSlots(None, "b"), option=hyperjson.OPT_OMIT_NULL)
+            == b'{"b":"b"}'
+        )
+        assert hyperjson.dumps(Sparse(None)) == b'{"a":null,"b":null}'
+
+    def test_omit_null_default(self):
+        """
+        dumps() OPT_OMIT_NULL does not leave out None returned by default
+        """
+
+        class Custom:
+            pass
+
+        assert (
+            hyperjson.dumps(
+                {"a": Custom()}, default=lambda obj: None, option=hyperjson.OPT_OMIT_NULL
+            )
+            == b'{"a":null}'
+        )
+
+    def test_omit_null_loads_tracked(self):
+        """
+        dumps() OPT_OMIT_NULL of a loads_tracked() result
+        """
+        obj = hyperjson.loads_tracked(b'{"a": null, "b": [null, 1]}')
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_NULL) == b'{"b":[null,1]}'
+        )
+
+    def test_omit_null_builder(self):
+        """
+        Builder OPT_OMIT_NULL
+        """
+        builder = hyperjson.Builder(option=hyperjson.OPT_OMIT_NULL)
+        builder.value({"a": None, "b": 1})
+        assert builder.finish() == b'{"b":1}'
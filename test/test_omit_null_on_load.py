@@ -0,0 +1,81 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestOmitNullOnLoad:
+    def test_omit_null_on_load(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD leaves out members that are null
+        """
+        doc = b'{"a": null, "b": 1, "c": {"d": null, "e": [null]}, "f": null}'
+        assert hyperjson.loads(doc, option=hyperjson.OPT_OMIT_NULL_ON_LOAD) == {
+            "b": 1,
+            "c": {"e": [None]},
+        }
+        assert hyperjson.loads(doc) == {
+            "a": None,
+            "b": 1,
+            "c": {"d": None, "e": [None]},
+            "f": None,
+        }
+
+    def test_omit_null_on_load_all(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD of an object whose members are all null
+        """
+        assert (
+            hyperjson.loads(
+                b'[{"a": null}, {"a": null, "b": null}]',
+                option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+            )
+            == [{}, {}]
+        )
+
+    def test_omit_null_on_load_root(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD does not change a null document
+        """
+        assert hyperjson.loads(b"null", option=hyperjson.OPT_OMIT_NULL_ON_LOAD) is None
+        assert hyperjson.loads(b"[null]", option=hyperjson.OPT_OMIT_NULL_ON_LOAD) == [
+            None
+        ]
+
+    def test_omit_null_on_load_extended(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD with allow_nan=True
+        """
+        assert hyperjson.loads(
+            b'{"a": null, "b": Infinity, "c": {"d": null}}',
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+            allow_nan=True,
+        ) == {"b": float("inf"), "c": {}}
+
+    def test_omit_null_on_load_rename(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD with rename=
+        """
+        assert hyperjson.loads(
+            b'{"a": null, "b": 1}',
+            option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+            rename={"a": "x", "b": "y"},
+        ) == {"y": 1}
+
+    def test_omit_null_on_load_invalid(self):
+        """
+        loads() option= other than a loads() option
+        """
+        for val in (hyperjson.OPT_SORT_KEYS, -1, 1 << 25, "1"):
+            with pytest.raises(TypeError):
+                hyperjson.loads(b"{}", option=val)  # type: ignore
+        assert hyperjson.loads(b'{"a": null}', option=None) == {"a": None}
+
+    def test_omit_null_on_load_dumps(self):
+        """
+        dumps() does not accept OPT_OMIT_NULL_ON_LOAD
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps({}, option=hyperjson.OPT_OMIT_NULL_ON_LOAD)
@@ -0,0 +1,157 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+
+import pytest
+
+import hyperjson
+
+from .util import numpy, pandas
+
+
+@pytest.mark.skipif(pandas is None, reason="pandas is not installed")
+class TestPandas:
+    def frame(self):
+        return pandas.DataFrame(
+            {
+                "int": [1, 2, 3],
+                "float": [1.5, float("nan"), -0.25],
+                "bool": [True, False, True],
+                "str": ["a", None, "c"],
+            }
+        )
+
+    def test_pandas_records(self):
+        """
+        dumps() pandas_orient="records" writes a DataFrame as an array of an
+        object per row, as DataFrame.to_dict("records")
+        """
+        assert hyperjson.dumps(self.frame(), pandas_orient="records") == (
+            b'[{"int":1,"float":1.5,"bool":true,"str":"a"},'
+            b'{"int":2,"float":null,"bool":false,"str":null},'
+            b'{"int":3,"float":-0.25,"bool":true,"str":"c"}]'
+        )
+
+    def test_pandas_columns(self):
+        """
+        dumps() pandas_orient="columns" writes a DataFrame as an object of an
+        array per column
+        """
+        assert hyperjson.dumps(self.frame(), pandas_orient="columns") == (
+            b'{"int":[1,2,3],"float":[1.5,null,-0.25],'
+            b'"bool":[true,false,true],"str":["a",null,"c"]}'
+        )
+
+    def test_pandas_series(self):
+        """
+        dumps() writes a Series as an array of its values with either
+        pandas_orient=
+        """
+        series = pandas.Series([1.0, 2.5], index=["a", "b"])
+        for orient in ("records", "columns"):
+            assert hyperjson.dumps(series, pandas_orient=orient) == b"[1.0,2.5]"
+
+    def test_pandas_nested(self):
+        """
+        dumps() pandas_orient= applies to a DataFrame in a container
+        """
+        frame = pandas.DataFrame({"a": [1, 2]})
+        assert (
+            hyperjson.dumps({"frame": [frame]}, pandas_orient="columns")
+            == b'{"frame":[{"a":[1,2]}]}'
+        )
+
+    def test_pandas_slice(self):
+        """
+        dumps() pandas_orient= reads a column that is a view of its block
+        """
+        frame = pandas.DataFrame(numpy.arange(12, dtype=numpy.int32).reshape(4, 3))
+        frame.columns = ["a", "b", "c"]
+        assert hyperjson.dumps(frame.iloc[::2], pandas_orient="records") == (
+            b'[{"a":0,"b":1,"c":2},{"a":6,"b":7,"c":8}]'
+        )
+
+    def test_pandas_datetime64(self):
+        """
+        dumps() pandas_orient= writes a datetime64 column as numpy.datetime64
+        """
+        frame = pandas.DataFrame(
+            {"ts": pandas.to_datetime(["2025-01-02T03:04:05", "2025-01-03"])}
+        )
+        assert hyperjson.dumps(frame, pandas_orient="records") == (
+            b'[{"ts":"2025-01-02T03:04:05"},{"ts":"2025-01-03T00:00:00"}]'
+        )
+
+    def test_pandas_empty(self):
+        """
+        dumps() pandas_orient= of a DataFrame without rows or columns
+        """
+        assert hyperjson.dumps(pandas.DataFrame(), pandas_orient="records") == b"[]"
+        assert hyperjson.dumps(pandas.DataFrame(), pandas_orient="columns") == b"{}"
+        assert (
+            hyperjson.dumps(pandas.DataFrame({"a": []}), pandas_orient="columns")
+            == b'{"a":[]}'
+        )
+
+    def test_pandas_object_default(self):
+        """
+        dumps() pandas_orient= passes an unsupported value in a column of
+        dtype object to default=
+        """
+        frame = pandas.DataFrame({"a": [datetime.timedelta(seconds=1)]}, dtype=object)
+        assert (
+            hyperjson.dumps(
+                frame, pandas_orient="columns", default=lambda x: x.total_seconds()
+            )
+            == b'{"a":[1.0]}'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(frame, pandas_orient="columns")
+
+    def test_pandas_column_name(self):
+        """
+        dumps() pandas_orient= raises for a column name that is not a str
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps(pandas.DataFrame({0: [1]}), pandas_orient="records")
+        assert str(exc.value) == "Dict key must be str"
+
+    def test_pandas_orient_none(self):
+        """
+        dumps() passes a DataFrame to default= without pandas_orient=
+        """
+        frame = pandas.DataFrame({"a": [1]})
+        assert (
+            hyperjson.dumps(frame, default=lambda x: x.to_dict("list"))
+            == b'{"a":[1]}'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(frame)
+
+    def test_pandas_dumps_length(self):
+        """
+        dumps_length() and dumps_batch() take pandas_orient=
+        """
+        frame = self.frame()
+        data = hyperjson.dumps(frame, pandas_orient="records")
+        assert hyperjson.dumps_length(frame, pandas_orient="records") == len(data)
+        assert hyperjson.dumps_batch([frame], pandas_orient="records")[0] == (
+            data + b"\n"
+        )
+
+
+class TestPandasOrient:
+    def test_pandas_orient_invalid(self):
+        """
+        dumps() pandas_orient must be "records", "columns", or None
+        """
+        for val in ("index", "split", 1, b"records"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps([], pandas_orient=val)  # type: ignore
+            assert (
+                str(exc.value)
+                == 'dumps() pandas_orient must be "records", "columns", or None'
+            )
+        assert hyperjson.dumps([1], pandas_orient=None) == b"[1]"
+        assert hyperjson.dumps([1], pandas_orient="records") == b"[1]"
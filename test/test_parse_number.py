@@ -0,0 +1,193 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import decimal
+import json
+
+import pytest
+
+import hyperjson
+
+
+class TestParseNumber:
+    def test_parse_float_decimal(self):
+        """
+        parse_float=decimal.Decimal reads numbers with a fraction or exponent
+        exactly
+        """
+        doc = '{"price": 0.1, "rate": 1.10, "big": 1e400, "count": 3, "neg": -2.5E-3}'
+        val = hyperjson.loads(doc, parse_float=decimal.Decimal)
+        assert val == json.loads(doc, parse_float=decimal.Decimal)
+        assert val["price"] == decimal.Decimal("0.1")
+        assert str(val["rate"]) == "1.10"
+        assert val["big"] == decimal.Decimal("1e400")
+        assert type(val["count"]) is int
+
+    def test_parse_int(self):
+        """
+        parse_int receives the text of each integer
+        """
+        calls = []
+
+        def hook(text):
+            calls.append(text)
+            return int(text) * 10
+
+        doc = "[1, -0, 18446744073709551616, 1.5, -123]"
+        assert hyperjson.loads(doc, parse_int=hook) == [
+            10,
+            0,
+            184467440737095516160,
+            1.5,
+            -1230,
+        ]
+        assert calls == ["1", "-0", "18446744073709551616", "-123"]
+        assert hyperjson.loads(doc, parse_int=str) == json.loads(doc, parse_int=str)
+
+    def test_parse_float_text(self):
+        """
+        parse_float receives the text of each number as written
+        """
+        doc = "[1.0, 1E5, -0.0, 2e-1, 3]"
+        assert hyperjson.loads(doc, parse_float=str) == [
+            "1.0",
+            "1E5",
+            "-0.0",
+            "2e-1",
+            3,
+        ]
+
+    def test_both(self):
+        """
+        parse_float and parse_int can be given together
+        """
+        assert hyperjson.loads(
+            '{"a": [1, 2.5]}', parse_float=decimal.Decimal, parse_int=str
+        ) == {"a": ["1", decimal.Decimal("2.5")]}
+
+    def test_keys_not_passed(self):
+        """
+        object keys that look like numbers are not passed to the hooks
+        """
+        assert hyperjson.loads('{"1": 1.5}', parse_float=str) == {"1": "1.5"}
+
+    def test_non_finite(self):
+        """
+        NaN and Infinity are passed to neither hook
+        """
+        val = hyperjson.loads(
+            "[NaN, -Infinity, 1.5]", allow_nan=True, parse_float=str, parse_int=str
+        )
+        assert val[0] != val[0]
+        assert val[1:] == [float("-inf"), "1.5"]
+
+    def test_json5(self):
+        """
+        JSON5 numbers other than hexadecimal are passed as written
+        """
+        assert hyperjson.loads(
+            "[+1, .5, 5., 0x1F]", json5=True, parse_float=str, parse_int=str
+        ) == ["+1", ".5", "5.", 31]
+
+    def test_none(self):
+        """
+        parse_float=None and parse_int=None are the default
+        """
+        assert hyperjson.loads("[1, 1.5]", parse_float=None, parse_int=None) == [
+            1,
+            1.5,
+        ]
+
+    def test_not_callable(self):
+        """
+        parse_float and parse_int must be callable or None
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("1.5", parse_float="x")
+        assert str(exc.value) == "loads() parse_float must be callable or None"
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("1", parse_int=1)
+        assert str(exc.value) == "loads() parse_int must be callable or None"
+
+    def test_hook_raises(self):
+        """
+        an exception raised by a hook propagates
+        """
+        with pytest.raises(decimal.InvalidOperation):
+            hyperjson.loads('[1, {"a": 1.5}]', parse_float=lambda _: decimal.Decimal("x"))
+        with pytest.raises(ValueError):
+            hyperjson.loads("[1, 2]", parse_int=lambda text: int(text, 2))
+
+    def test_invalid(self):
+        """
+        invalid numbers raise JSONDecodeError before a hook is called
+        """
+        for doc in ("[1.]", "01", "[-]", "1e"):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.loads(doc, parse_float=str, parse_int=str)
+
+
+class TestUseDecimal:
+    def test_use_decimal(self):
+        """
+        use_decimal=True reads numbers with a fraction or exponent as
+        decimal.Decimal
+        """
+        doc = '{"price": 19.99, "rate": 1.10, "big": 1e400, "count": 3, "neg": -2.5E-3}'
+        val = hyperjson.loads(doc, use_decimal=True)
+        assert val == json.loads(doc, parse_float=decimal.Decimal)
+        assert type(val["price"]) is decimal.Decimal
+        assert val["price"] == decimal.Decimal("19.99")
+        assert str(val["rate"]) == "1.10"
+        assert str(val["neg"]) == "-0.0025"
+        assert type(val["count"]) is int
+
+    def test_use_decimal_bytes(self):
+        """
+        use_decimal=True with bytes input and nested containers
+        """
+        assert hyperjson.loads(b'[[0.1], {"a": [0.2]}]', use_decimal=True) == [
+            [decimal.Decimal("0.1")],
+            {"a": [decimal.Decimal("0.2")]},
+        ]
+
+    def test_use_decimal_false(self):
+        """
+        use_decimal=False reads float
+        """
+        assert type(hyperjson.loads("[0.1]", use_decimal=False)[0]) is float
+
+    def test_use_decimal_parse_int(self):
+        """
+        use_decimal=True can be combined with parse_int
+        """
+        assert hyperjson.loads("[1, 1.5]", use_decimal=True, parse_int=str) == [
+            "1",
+            decimal.Decimal("1.5"),
+        ]
+
+    def test_use_decimal_nan(self):
+        """
+        use_decimal=True reads NaN and Infinity as float
+        """
+        val = hyperjson.loads("[NaN, Infinity]", use_decimal=True, allow_nan=True)
+        assert [type(each) for each in val] == [float, float]
+
+    def test_use_decimal_parse_float(self):
+        """
+        use_decimal=True cannot be combined with parse_float
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("[]", use_decimal=True, parse_float=float)
+        assert (
+            str(exc.value) == "loads() use_decimal cannot be combined with parse_float"
+        )
+        assert hyperjson.loads("[1.5]", use_decimal=False, parse_float=str) == ["1.5"]
+
+    def test_use_decimal_not_bool(self):
+        """
+        use_decimal must be a bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("[]", use_decimal=1)
+        assert str(exc.value) == "loads() use_decimal must be a bool"
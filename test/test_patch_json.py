@@ -0,0 +1,130 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+import datetime
+import io
+import json
+import math
+
+import pytest
+
+import hyperjson
+
+ORIGINALS = (json.dumps, json.loads, json.dump, json.load)
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+
+
+class TestPatchJson:
+    def test_patch_unpatch(self):
+        """
+        patch_json() replaces the functions of json and unpatch() restores them
+        """
+        hyperjson.patch_json()
+        try:
+            assert json.dumps is not ORIGINALS[0]
+            assert json.loads is not ORIGINALS[1]
+            hyperjson.patch_json()
+        finally:
+            hyperjson.unpatch()
+        assert (json.dumps, json.loads, json.dump, json.load) == ORIGINALS
+        hyperjson.unpatch()
+        assert json.dumps is ORIGINALS[0]
+
+    def test_unpatch_replaced(self):
+        """
+        unpatch() leaves a function replaced after patch_json()
+        """
+        hyperjson.patch_json()
+        replaced = lambda *args, **kwargs: "replaced"  # noqa: E731
+        json.dumps = replaced
+        try:
+            hyperjson.unpatch()
+            assert json.dumps is replaced
+            assert json.loads is ORIGINALS[1]
+        finally:
+            json.dumps = ORIGINALS[0]
+
+
+class TestPatchedJson:
+    def setup_method(self, method):
+        hyperjson.patch_json()
+
+    def teardown_method(self, method):
+        hyperjson.unpatch()
+
+    def test_dumps(self):
+        """
+        patched json.dumps() returns a compact str with json's escaping
+        """
+        assert json.dumps({"a": ["é", float("nan")], 1: None}) == (
+            '{"a":["\\u00e9",NaN],"1":null}'
+        )
+        assert json.dumps("é", ensure_ascii=False) == '"é"'
+
+    def test_dumps_indent(self):
+        """
+        patched json.dumps() indent= and sort_keys= write what json does
+        """
+        obj = {"b": [1, {"c": None}], "a": 2}
+        assert json.dumps(obj, indent=2, sort_keys=True) == ORIGINALS[0](
+            obj, indent=2, sort_keys=True
+        )
+        assert json.dumps(obj, indent=3) == ORIGINALS[0](obj, indent=3)
+
+    def test_dumps_default(self):
+        """
+        patched json.dumps() passes datetime and dataclasses to default=
+        """
+        obj = [datetime.date(2020, 1, 2), Point(1)]
+        assert json.dumps(obj, default=repr) == (
+            '["datetime.date(2020, 1, 2)","Point(x=1)"]'
+        )
+        with pytest.raises(TypeError):
+            json.dumps(Point(1))
+
+    def test_dumps_fallback(self):
+        """
+        patched json.dumps() calls json for arguments hyperjson cannot apply
+        """
+        obj = {"a": 1, (1,): 2}
+        assert json.dumps(obj, skipkeys=True) == '{"a": 1}'
+        assert json.dumps({"a": 1}, separators=(", ", ": ")) == '{"a": 1}'
+
+    def test_dumps_allow_nan(self):
+        """
+        patched json.dumps() allow_nan=False raises
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            json.dumps(float("nan"), allow_nan=False)
+
+    def test_loads(self):
+        """
+        patched json.loads() reads what json does, including NaN
+        """
+        val = json.loads('[NaN, 1, {"a": 1.5}]')
+        assert math.isnan(val[0])
+        assert val[1:] == [1, {"a": 1.5}]
+        assert json.loads(b'{"a": 1.5}', parse_float=str) == {"a": "1.5"}
+        assert json.loads("[Infinity]", object_pairs_hook=dict) == [float("inf")]
+        with pytest.raises(json.JSONDecodeError):
+            json.loads("[")
+
+    def test_loads_fallback(self):
+        """
+        patched json.loads() calls json for parse_constant=
+        """
+        assert json.loads("[NaN]", parse_constant=str) == ["NaN"]
+
+    def test_dump_load(self):
+        """
+        patched json.dump() and json.load() use files
+        """
+        fp = io.StringIO()
+        json.dump({"a": [1]}, fp)
+        assert fp.getvalue() == '{"a":[1]}'
+        assert json.load(io.StringIO('{"a": [1]}')) == {"a": [1]}
@@ -0,0 +1,74 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+DOC = b'{"a": {"b": [10, {"c": "d"}, null]}, "e/f": 1, "g~h": [], "": 2}'
+
+
+class TestPointer:
+    def test_pointer(self):
+        """
+        pointer() returns the value a JSON Pointer refers to
+        """
+        assert hyperjson.pointer(DOC, "/a/b/0") == 10
+        assert hyperjson.pointer(DOC, "/a/b/1") == {"c": "d"}
+        assert hyperjson.pointer(DOC, "/a/b/1/c") == "d"
+        assert hyperjson.pointer(DOC, "/a/b/2") is None
+        assert hyperjson.pointer(DOC, "/e~1f") == 1
+        assert hyperjson.pointer(DOC, "/g~0h") == []
+        assert hyperjson.pointer(DOC, "/") == 2
+        assert hyperjson.pointer(DOC, "") == hyperjson.loads(DOC)
+
+    def test_pointer_input(self):
+        """
+        pointer() reads bytes, bytearray, memoryview, and str
+        """
+        for data in (DOC, bytearray(DOC), memoryview(DOC), DOC.decode()):
+            assert hyperjson.pointer(data, "/a/b/1/c") == "d"
+
+    def test_pointer_escaped_key(self):
+        """
+        pointer() compares keys with their escapes read
+        """
+        assert hyperjson.pointer(b'{"\\u00e9\\n":1}', "/é\n") == 1
+
+    def test_pointer_duplicate_key(self):
+        """
+        pointer() refers to the last member with a key, as loads() reads it
+        """
+        assert hyperjson.pointer(b'{"a":1,"a":2}', "/a") == 2
+
+    def test_pointer_not_found(self):
+        """
+        pointer() raises JSONDecodeError for a path that is not in the
+        document
+        """
+        for path in ("/x", "/a/b/3", "/a/b/01", "/a/b/-", "/a/b/0/x"):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.pointer(DOC, path)
+            assert exc.value.msg == f"pointer() path not found: {path}"
+
+    def test_pointer_invalid_value(self):
+        """
+        pointer() raises JSONDecodeError for a value that is not valid but
+        does not validate the rest of the document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.pointer(b'{"a":[1,tru]}', "/a")
+        assert hyperjson.pointer(b'{"a":[1,tru],"b":2}', "/b") == 2
+
+    def test_pointer_invalid_pointer(self):
+        """
+        pointer() raises JSONEncodeError for a pointer that is not valid
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.pointer(DOC, "a")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.pointer(DOC, "/a~2")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.pointer(DOC, 1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.pointer(DOC)
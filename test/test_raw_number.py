@@ -0,0 +1,128 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestRawNumber:
+    def test_raw_numbers(self):
+        """
+        loads() raw_numbers=True reads each number as a RawNumber of its text
+        """
+        obj = hyperjson.loads(
+            b"[1.10, -0, 1E+400, 12345678901234567890123]", raw_numbers=True
+        )
+        assert obj == ["1.10", "-0", "1E+400", "12345678901234567890123"]
+        assert all(type(each) is hyperjson.RawNumber for each in obj)
+
+    def test_raw_numbers_roundtrip(self):
+        """
+        dumps() writes a RawNumber as it was read
+        """
+        data = (
+            b'{"a":1.10,"b":[2.5e-3,0,-1E5],"c":"1.10",'
+            b'"d":100000000000000000000001}'
+        )
+        assert hyperjson.dumps(hyperjson.loads(data, raw_numbers=True)) == data
+
+    def test_raw_numbers_indent(self):
+        """
+        dumps() writes a RawNumber with OPT_INDENT_2
+        """
+        obj = hyperjson.loads(b'{"a":[1.50]}', raw_numbers=True)
+        assert (
+            hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2)
+            == b'{\n  "a": [\n    1.50\n  ]\n}'
+        )
+
+    def test_raw_numbers_false(self):
+        """
+        loads() raw_numbers=False reads numbers as int and float
+        """
+        assert hyperjson.loads(b"[1, 1.10]", raw_numbers=False) == [1, 1.1]
+
+    def test_raw_number_str(self):
+        """
+        RawNumber is a str
+        """
+        val = hyperjson.RawNumber("1.10")
+        assert isinstance(val, str)
+        assert val == "1.10"
+        assert float(val) == 1.1
+        assert not hasattr(val, "__dict__")
+
+    def test_raw_number_dumps(self):
+        """
+        dumps() writes a constructed RawNumber unquoted
+        """
+        assert hyperjson.dumps([hyperjson.RawNumber("3.14159265358979323846")]) == (
+            b"[3.14159265358979323846]"
+        )
+
+    def test_raw_number_invalid(self):
+        """
+        dumps() RawNumber must be a JSON number
+        """
+        for val in ("", "abc", "1.", "+1", "NaN", "0x10", "1 "):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps([hyperjson.RawNumber(val)])
+            assert str(exc.value) == "hyperjson.RawNumber's content is not a JSON number"
+
+    def test_raw_number_key(self):
+        """
+        dumps() RawNumber dict key is written as a str
+        """
+        assert (
+            hyperjson.dumps(
+                {hyperjson.RawNumber("1.10"): 1},
+                option=hyperjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"1.10":1}'
+        )
+
+    def test_raw_number_passthrough_subclass(self):
+        """
+        OPT_PASSTHROUGH_SUBCLASS does not apply to RawNumber
+        """
+        assert (
+            hyperjson.dumps(
+                hyperjson.RawNumber("1.10"),
+                option=hyperjson.OPT_PASSTHROUGH_SUBCLASS,
+            )
+            == b"1.10"
+        )
+
+    def test_raw_numbers_nan(self):
+        """
+        loads() raw_numbers=True does not apply to NaN and Infinity
+        """
+        obj = hyperjson.loads(b"[NaN, 1.0]", allow_nan=True, raw_numbers=True)
+        assert obj[0] != obj[0]
+        assert type(obj[1]) is hyperjson.RawNumber
+
+    def test_raw_numbers_invalid(self):
+        """
+        loads() raw_numbers must be a bool
+        """
+        with pytest.raises(TypeError) as exc:
+            hyperjson.loads(b"1", raw_numbers=1)  # type: ignore
+        assert str(exc.value) == "loads() raw_numbers must be a bool"
+
+    def test_raw_numbers_combined(self):
+        """
+        loads() raw_numbers cannot be combined with parse_float, parse_int, or
+        use_decimal
+        """
+        for kwargs in (
+            {"parse_float": float},
+            {"parse_int": int},
+            {"use_decimal": True},
+        ):
+            with pytest.raises(TypeError) as exc:
+                hyperjson.loads(b"1", raw_numbers=True, **kwargs)
+            assert str(exc.value) == (
+                "loads() raw_numbers cannot be combined with parse_float, "
+                "parse_int, or use_decimal"
+            )
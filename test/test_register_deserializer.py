@@ -0,0 +1,134 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import contextlib
+import datetime
+from dataclasses import dataclass
+from typing import Dict, List, Optional
+
+import pytest
+
+import hyperjson
+
+
+class Money:
+    def __init__(self, amount, currency):
+        self.amount = amount
+        self.currency = currency
+
+    def __eq__(self, other):
+        return (self.amount, self.currency) == (other.amount, other.currency)
+
+    @classmethod
+    def parse(cls, value):
+        amount, currency = value.split(" ")
+        return cls(int(amount), currency)
+
+
+class GeoPoint:
+    def __init__(self, lat, lon):
+        self.lat = lat
+        self.lon = lon
+
+    def __eq__(self, other):
+        return (self.lat, self.lon) == (other.lat, other.lon)
+
+
+@dataclass
+class Listing:
+    price: Money
+    location: Optional[GeoPoint]
+    history: List[Money]
+
+
+@contextlib.contextmanager
+def registered():
+    hyperjson.register_deserializer(Money, Money.parse)
+    hyperjson.register_deserializer(GeoPoint, lambda obj: GeoPoint(*obj))
+    try:
+        yield
+    finally:
+        hyperjson.register_deserializer(Money, None)
+        hyperjson.register_deserializer(GeoPoint, None)
+
+
+class TestRegisterDeserializer:
+    def test_register_deserializer(self):
+        """
+        register_deserializer() builds the registered type from the value
+        loads() reads
+        """
+        with registered():
+            obj = hyperjson.loads(
+                b'{"price":"5 EUR","location":[1.5,2.5],"history":["4 EUR"]}',
+                type=Listing,
+            )
+        assert obj == Listing(Money(5, "EUR"), GeoPoint(1.5, 2.5), [Money(4, "EUR")])
+
+    def test_register_deserializer_container(self):
+        """
+        register_deserializer() applies within containers and unions
+        """
+        with registered():
+            assert hyperjson.loads(
+                b'{"a":"1 USD","b":null}', type=Dict[str, Optional[Money]]
+            ) == {"a": Money(1, "USD"), "b": None}
+            obj = hyperjson.loads(
+                b'{"price":"1 USD","location":null,"history":[]}', type=Listing
+            )
+            assert obj.location is None
+
+    def test_register_deserializer_unregistered(self):
+        """
+        loads() type= raises for a type that is not registered, including
+        after it is unregistered
+        """
+        with registered():
+            pass
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads(b'"1 USD"', type=Money)
+        assert str(exc.value).startswith("loads() type is not supported")
+
+    def test_register_deserializer_override(self):
+        """
+        register_deserializer() takes precedence over how a type is otherwise
+        read
+        """
+        hyperjson.register_deserializer(
+            datetime.date, lambda obj: datetime.date.fromordinal(obj)
+        )
+        try:
+            assert hyperjson.loads(b"1", type=datetime.date) == datetime.date(1, 1, 1)
+        finally:
+            hyperjson.register_deserializer(datetime.date, None)
+        assert hyperjson.loads(b'"0001-01-01"', type=datetime.date) == (
+            datetime.date(1, 1, 1)
+        )
+
+    def test_register_deserializer_raises(self):
+        """
+        loads() type= raises the exception of the registered callable
+        """
+        with registered():
+            with pytest.raises(ValueError):
+                hyperjson.loads(b'"1"', type=Money)
+
+    def test_register_deserializer_schema(self):
+        """
+        Schema uses the callables registered when it is created
+        """
+        with registered():
+            schema = hyperjson.Schema(List[Money])
+        assert schema.loads(b'["2 GBP"]') == [Money(2, "GBP")]
+
+    def test_register_deserializer_invalid(self):
+        """
+        register_deserializer() raises for arguments that are not a type and a
+        callable
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_deserializer(1, Money.parse)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_deserializer(Money, 1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_deserializer(Money)
@@ -0,0 +1,183 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import contextlib
+import datetime
+import decimal
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    def __init__(self, value):
+        self.value = value
+
+
+class CustomSubclass(Custom):
+    pass
+
+
+@contextlib.contextmanager
+def registered():
+    hyperjson.register_serializer(Custom, lambda obj: {"custom": obj.value})
+    try:
+        yield
+    finally:
+        hyperjson.register_serializer(Custom, None)
+
+
+class TestRegisterSerializer:
+    def test_register_serializer(self):
+        """
+        register_serializer() is used for the registered type
+        """
+        with registered():
+            assert hyperjson.dumps(Custom(1)) == b'{"custom":1}'
+
+    def test_register_serializer_nested(self):
+        """
+        register_serializer() is used in containers
+        """
+        with registered():
+            assert (
+                hyperjson.dumps({"a": [Custom(1), Custom("b")]})
+                == b'{"a":[{"custom":1},{"custom":"b"}]}'
+            )
+
+    def test_register_serializer_subclass(self):
+        """
+        register_serializer() is used for subclasses of the registered type
+        """
+        with registered():
+            assert hyperjson.dumps(CustomSubclass(2)) == b'{"custom":2}'
+
+    def test_register_serializer_subclass_override(self):
+        """
+        register_serializer() of a subclass takes precedence over its base
+        """
+        with registered():
+            hyperjson.register_serializer(CustomSubclass, lambda obj: "subclass")
+            try:
+                assert hyperjson.dumps([Custom(1), CustomSubclass(2)]) == (
+                    b'[{"custom":1},"subclass"]'
+                )
+            finally:
+                hyperjson.register_serializer(CustomSubclass, None)
+
+    def test_register_serializer_before_default(self):
+        """
+        register_serializer() is consulted before default
+        """
+
+        def default(obj):
+            if isinstance(obj, decimal.Decimal):
+                return str(obj)
+            raise TypeError
+
+        with registered():
+            assert (
+                hyperjson.dumps([Custom(1), decimal.Decimal("1.5")], default=default)
+                == b'[{"custom":1},"1.5"]'
+            )
+
+    def test_register_serializer_unregister(self):
+        """
+        register_serializer() with None removes the registration
+        """
+        hyperjson.register_serializer(Custom, lambda obj: obj.value)
+        assert hyperjson.dumps(Custom(1)) == b"1"
+        hyperjson.register_serializer(Custom, None)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Custom(1))
+
+    def test_register_serializer_unregister_missing(self):
+        """
+        register_serializer() with None for an unregistered type
+        """
+        hyperjson.register_serializer(Custom, None)
+
+    def test_register_serializer_replace(self):
+        """
+        register_serializer() replaces an existing registration
+        """
+        with registered():
+            hyperjson.register_serializer(Custom, lambda obj: "replaced")
+            assert hyperjson.dumps(Custom(1)) == b'"replaced"'
+
+    def test_register_serializer_native_type(self):
+        """
+        register_serializer() does not change natively serialized types
+        """
+        hyperjson.register_serializer(datetime.date, lambda obj: "registered")
+        try:
+            assert hyperjson.dumps(datetime.date(2025, 1, 1)) == b'"2025-01-01"'
+        finally:
+            hyperjson.register_serializer(datetime.date, None)
+
+    def test_register_serializer_passthrough(self):
+        """
+        register_serializer() applies to types passed through by option
+        """
+        hyperjson.register_serializer(datetime.date, lambda obj: "registered")
+        try:
+            assert (
+                hyperjson.dumps(
+                    datetime.date(2025, 1, 1),
+                    option=hyperjson.OPT_PASSTHROUGH_DATETIME,
+                )
+                == b'"registered"'
+            )
+        finally:
+            hyperjson.register_serializer(datetime.date, None)
+
+    def test_register_serializer_raises(self):
+        """
+        register_serializer() callable raising is chained
+        """
+
+        def serializer(obj):
+            raise ValueError("invalid")
+
+        hyperjson.register_serializer(Custom, serializer)
+        try:
+            with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+                hyperjson.dumps(Custom(1))
+            assert isinstance(exc_info.value.__cause__, ValueError)
+        finally:
+            hyperjson.register_serializer(Custom, None)
+
+    def test_register_serializer_recursion(self):
+        """
+        register_serializer() callable returning the same type
+        """
+        hyperjson.register_serializer(Custom, lambda obj: obj)
+        try:
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps(Custom(1))
+        finally:
+            hyperjson.register_serializer(Custom, None)
+
+    def test_register_serializer_not_type(self):
+        """
+        register_serializer() first argument must be a type
+        """
+        with pytest.raises(TypeError):
+            hyperjson.register_serializer(Custom(1), str)
+
+    def test_register_serializer_not_callable(self):
+        """
+        register_serializer() second argument must be callable
+        """
+        with pytest.raises(TypeError):
+            hyperjson.register_serializer(Custom, 1)
+
+    def test_register_serializer_args(self):
+        """
+        register_serializer() takes exactly two arguments
+        """
+        with pytest.raises(TypeError):
+            hyperjson.register_serializer(Custom)  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.register_serializer(Custom, str, str)  # type: ignore
@@ -0,0 +1,191 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+from typing import List, Optional
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Inner:
+    name: str
+    score: float
+
+
+@dataclasses.dataclass
+class Record:
+    id: int
+    name: str
+    ratio: float
+    active: bool
+    inner: Optional[Inner]
+    tags: List[str]
+    _private: int = 0
+
+
+@dataclasses.dataclass
+class Slotted:
+    __slots__ = ("a", "b")
+    a: int
+    b: str
+
+
+@dataclasses.dataclass
+class Unicode:
+    clé: str
+
+
+def record():
+    return Record(1, "a", 0.5, True, Inner("b", 1.5), ["x"], _private=2)
+
+
+class TestRegisterType:
+    def test_register_type(self):
+        """
+        register_type() does not change what dumps() writes
+        """
+        expected = hyperjson.dumps(record())
+        hyperjson.register_type(Record)
+        hyperjson.register_type(Inner)
+        assert hyperjson.dumps(record()) == expected
+        assert hyperjson.dumps([record(), record()]) == b"[%s,%s]" % (
+            expected,
+            expected,
+        )
+
+    def test_register_type_mismatch(self):
+        """
+        register_type() writes a value that is not its annotated type as
+        dumps() otherwise would
+        """
+        hyperjson.register_type(Record)
+        obj = Record("1", 2, None, 1, None, [])
+        assert hyperjson.dumps(obj) == (
+            b'{"id":"1","name":2,"ratio":null,"active":1,'
+            b'"inner":null,"tags":[]}'
+        )
+
+    def test_register_type_extra_attributes(self):
+        """
+        register_type() writes attributes set on an instance that are not
+        fields as dumps() otherwise would
+        """
+        obj = Inner("a", 1.0)
+        obj.extra = 5
+        obj._hidden = 6
+        expected = hyperjson.dumps(obj)
+        assert expected == b'{"name":"a","score":1.0,"extra":5}'
+        hyperjson.register_type(Inner)
+        assert hyperjson.dumps(obj) == expected
+        assert hyperjson.dumps(obj, field_order="declaration") == (
+            b'{"name":"a","score":1.0}'
+        )
+
+    def test_register_type_attribute_order(self):
+        """
+        register_type() writes fields missing from or reordered in the
+        instance __dict__ as dumps() otherwise would
+        """
+        missing = Inner("a", 1.0)
+        del missing.name
+        reordered = Inner("b", 2.0)
+        del reordered.name
+        reordered.name = "c"
+        private = record()
+        expected = [hyperjson.dumps(obj) for obj in (missing, reordered, private)]
+        assert expected[:2] == [b'{"score":1.0}', b'{"score":2.0,"name":"c"}']
+        hyperjson.register_type(Inner)
+        hyperjson.register_type(Record)
+        assert [
+            hyperjson.dumps(obj) for obj in (missing, reordered, private)
+        ] == expected
+
+    def test_register_type_slots(self):
+        """
+        register_type() supports dataclasses with __slots__
+        """
+        hyperjson.register_type(Slotted)
+        assert hyperjson.dumps(Slotted(1, "b")) == b'{"a":1,"b":"b"}'
+
+    def test_register_type_ensure_ascii(self):
+        """
+        register_type() writes keys escaped as OPT_ENSURE_ASCII requires
+        """
+        hyperjson.register_type(Unicode)
+        assert hyperjson.dumps(Unicode("é")) == '{"clé":"é"}'.encode()
+        assert hyperjson.dumps(Unicode("é"), option=hyperjson.OPT_ENSURE_ASCII) == (
+            b'{"cl\\u00e9":"\\u00e9"}'
+        )
+
+    def test_register_type_options(self):
+        """
+        register_type() applies omission, indentation, and sorted field order
+        """
+        hyperjson.register_type(Record)
+        obj = Record(1, "", 0.5, False, None, [])
+        assert hyperjson.dumps(obj, option=hyperjson.OPT_OMIT_NULL) == (
+            b'{"id":1,"name":"","ratio":0.5,"active":false,"tags":[]}'
+        )
+        assert hyperjson.dumps(Inner("a", 1.0), option=hyperjson.OPT_INDENT_2) == (
+            b'{\n  "name": "a",\n  "score": 1.0\n}'
+        )
+        assert hyperjson.dumps(Inner("b", 1.0), field_order="sorted") == (
+            b'{"name":"b","score":1.0}'
+        )
+
+    def test_register_type_default(self):
+        """
+        register_type() calls default for a field that is not supported
+        """
+
+        @dataclasses.dataclass
+        class WithObject:
+            value: object
+
+        hyperjson.register_type(WithObject)
+        assert hyperjson.dumps(WithObject({1, 2}), default=sorted) == (
+            b'{"value":[1,2]}'
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(WithObject({1}))
+
+    def test_register_type_subclass(self):
+        """
+        register_type() applies to exactly the registered class
+        """
+
+        @dataclasses.dataclass
+        class Child(Inner):
+            extra: int = 0
+
+        hyperjson.register_type(Inner)
+        assert hyperjson.dumps(Child("a", 1.0, 2)) == (
+            b'{"name":"a","score":1.0,"extra":2}'
+        )
+
+    def test_register_type_again(self):
+        """
+        register_type() rebuilds the plan of a class registered again
+        """
+
+        @dataclasses.dataclass
+        class Changing:
+            a: int
+
+        hyperjson.register_type(Changing)
+        hyperjson.register_type(Changing)
+        assert hyperjson.dumps(Changing(1)) == b'{"a":1}'
+
+    def test_register_type_invalid(self):
+        """
+        register_type() raises for an argument that is not a dataclass
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_type(dict)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_type(record())
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_type()
@@ -0,0 +1,176 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import os
+import subprocess
+import sys
+
+import pytest
+
+import hyperjson
+
+
+class Custom:
+    pass
+
+
+REIMPORT_SCRIPT = """
+import gc
+import sys
+
+import hyperjson
+
+old = hyperjson
+tracked = old.loads_tracked(b'{"a": [1, 2]}')
+del sys.modules["hyperjson"]
+del sys.modules["hyperjson.hyperjson"]
+
+import hyperjson
+
+assert hyperjson is not old
+assert hyperjson.dumps(tracked) == b'{"a":[1,2]}'
+assert old.dumps({"b": 1.5}) == b'{"b":1.5}'
+del old
+gc.collect()
+assert hyperjson.loads(b'{"key": ["value"]}') == {"key": ["value"]}
+assert hyperjson.dumps({"key": ["value"]}) == b'{"key":["value"]}'
+"""
+
+
+class TestResetForTesting:
+    def test_reset_register_serializer(self):
+        """
+        _reset_for_testing() clears registered serializers
+        """
+        hyperjson.register_serializer(Custom, lambda obj: "custom")
+        assert hyperjson.dumps(Custom()) == b'"custom"'
+        hyperjson._reset_for_testing()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Custom())
+
+    def test_reset_loads_tracked(self):
+        """
+        _reset_for_testing() forgets loads_tracked() results
+        """
+        obj = hyperjson.loads_tracked(b'{ "a" : [1, 2] }')
+        hyperjson._reset_for_testing()
+        assert hyperjson.dumps(obj) == b'{"a":[1,2]}'
+
+    def test_reset_caches(self):
+        """
+        loads() and dumps() after _reset_for_testing()
+        """
+        doc = b'{"key": ["value", {"key": 1.5}]}'
+        assert hyperjson.loads(doc) == {"key": ["value", {"key": 1.5}]}
+        hyperjson._reset_for_testing()
+        assert hyperjson.loads(doc) == {"key": ["value", {"key": 1.5}]}
+        assert (
+            hyperjson.dumps(hyperjson.loads(doc)) == b'{"key":["value",{"key":1.5}]}'
+        )
+
+    def test_reset_while_serializing(self):
+        """
+        _reset_for_testing() raises when called from default
+        """
+        calls = []
+
+        def default(obj):
+            try:
+                hyperjson._reset_for_testing()
+            except hyperjson.JSONEncodeError as exc:
+                calls.append(str(exc))
+            return "custom"
+
+        hyperjson.register_serializer(Custom, None)
+        assert hyperjson.dumps(Custom(), default=default) == b'"custom"'
+        assert calls == ["_reset_for_testing() cannot be called while serializing"]
+        builder = hyperjson.Builder(default=default)
+        builder.value(Custom())
+        assert builder.finish() == b'"custom"'
+        assert len(calls) == 2
+
+    def test_reset_args(self):
+        """
+        _reset_for_testing() takes no arguments
+        """
+        with pytest.raises(TypeError):
+            hyperjson._reset_for_testing(1)  # type: ignore
+
+    def test_reimport(self):
+        """
+        importing hyperjson again after removing it from sys.modules
+        """
+        env = dict(os.environ, PYTHONPATH=os.pathsep.join(sys.path))
+        proc = subprocess.run(
+            [sys.executable, "-c", REIMPORT_SCRIPT],
+            env=env,
+            capture_output=True,
+            timeout=60,
+        )
+        assert proc.returncode == 0, proc.stderr
+
+
+FORK_SCRIPT = """
+import os
+import threading
+
+import hyperjson
+
+tracked = hyperjson.loads_tracked(b'{ "a" : [1, 2] }')
+ready = threading.Event()
+done = threading.Event()
+
+
+def work():
+    while not done.is_set():
+        hyperjson.loads(hyperjson.dumps({"a": [1, 2.5, "b"]}))
+        ready.set()
+
+
+thread = threading.Thread(target=work)
+thread.start()
+ready.wait()
+for _ in range(20):
+    pid = os.fork()
+    if pid == 0:
+        try:
+            assert hyperjson.loads(b'{"key": ["value"]}') == {"key": ["value"]}
+            assert hyperjson.dumps({"key": ["value"]}) == b'{"key":["value"]}'
+            assert hyperjson.dumps(tracked) in (b'{ "a" : [1, 2] }', b'{"a":[1,2]}')
+            hyperjson._reset_for_testing()
+        except BaseException:
+            os._exit(1)
+        os._exit(0)
+    _, status = os.waitpid(pid, 0)
+    assert os.waitstatus_to_exitcode(status) == 0
+done.set()
+thread.join()
+assert hyperjson.dumps({"key": ["value"]}) == b'{"key":["value"]}'
+"""
+
+
+@pytest.mark.skipif(not hasattr(os, "fork"), reason="no fork")
+class TestFork:
+    def test_fork(self):
+        """
+        loads() and dumps() in the child of os.fork() while another thread
+        is using hyperjson
+        """
+        env = dict(os.environ, PYTHONPATH=os.pathsep.join(sys.path))
+        proc = subprocess.run(
+            [sys.executable, "-W", "ignore", "-c", FORK_SCRIPT],
+            env=env,
+            capture_output=True,
+            timeout=60,
+        )
+        assert proc.returncode == 0, proc.stderr
+
+    def test_fork_in_process(self):
+        """
+        os.fork() in the interpreter running the tests
+        """
+        pid = os.fork()
+        if pid == 0:
+            os._exit(0 if hyperjson.loads(b"[1]") == [1] else 1)
+        _, status = os.waitpid(pid, 0)
+        assert os.waitstatus_to_exitcode(status) == 0
@@ -0,0 +1,191 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+import decimal
+import uuid
+from dataclasses import dataclass, field
+from typing import Dict, List, NamedTuple, Optional, Tuple, TypedDict, Union
+
+import pytest
+
+import hyperjson
+
+from .util import pydantic
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+@dataclass
+class Line:
+    points: List[Point]
+    label: Optional[str] = None
+    tags: Dict[str, Tuple[int, int]] = field(default_factory=dict)
+
+
+@dataclass
+class Counter:
+    a: int
+    b: int = field(init=False, default=0)
+
+
+@dataclass
+class Node:
+    value: Union[Point, List[int], None]
+    children: List["Node"] = field(default_factory=list)
+
+
+@dataclass
+class Payment:
+    id: uuid.UUID
+    at: datetime.datetime
+    amount: decimal.Decimal
+
+
+class Movie(TypedDict, total=False):
+    title: str
+    year: int
+
+
+class Pair(NamedTuple):
+    a: int
+    b: Point
+
+
+if pydantic is not None:
+
+    class Address(pydantic.BaseModel):
+        city: str
+        zip_code: str = pydantic.Field(alias="zipCode")
+
+    class User(pydantic.BaseModel):
+        id: int
+        address: Address
+
+
+class TestSchema:
+    def test_schema_loads(self):
+        """
+        Schema.loads() reads as loads() type= does
+        """
+        schema = hyperjson.Schema(Line)
+        data = b'{"points":[{"x":1,"y":2}],"tags":{"a":[1,2]}}'
+        assert schema.loads(data) == hyperjson.loads(data, type=Line)
+        assert schema.loads(data.decode()) == Line([Point(1, 2)], None, {"a": (1, 2)})
+        assert schema.loads(b'{"points":[]}') == Line([])
+
+    def test_schema_loads_error(self):
+        """
+        Schema.loads() raises JSONDecodeError with the path of the value
+        """
+        schema = hyperjson.Schema(Line)
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            schema.loads(b'{"points":[{"x":1,"y":"2"}]}')
+        assert exc.value.msg == "expected int at $.points[0].y"
+        with pytest.raises(hyperjson.JSONDecodeError):
+            schema.loads(b"{")
+
+    def test_schema_dumps(self):
+        """
+        Schema.dumps() writes the fields of a dataclass
+        """
+        schema = hyperjson.Schema(Line)
+        obj = Line([Point(1, 2)], "a", {"k": (3, 4)})
+        assert (
+            schema.dumps(obj)
+            == b'{"points":[{"x":1,"y":2}],"label":"a","tags":{"k":[3,4]}}'
+        )
+        assert schema.loads(schema.dumps(obj)) == obj
+
+    def test_schema_dumps_fields(self):
+        """
+        Schema.dumps() writes only the fields Schema.loads() reads
+        """
+        schema = hyperjson.Schema(Counter)
+        obj = Counter(1)
+        obj.b = 2
+        assert schema.dumps(obj) == b'{"a":1}'
+        assert hyperjson.dumps(obj) == b'{"a":1,"b":2}'
+
+    def test_schema_dumps_union(self):
+        """
+        Schema.dumps() writes a union as the member the value is an instance
+        of
+        """
+        schema = hyperjson.Schema(Node)
+        obj = Node(Point(1, 2), [Node([1]), Node(None)])
+        assert schema.dumps(obj) == (
+            b'{"value":{"x":1,"y":2},"children":'
+            b'[{"value":[1],"children":[]},{"value":null,"children":[]}]}'
+        )
+        assert schema.loads(schema.dumps(obj)) == obj
+
+    def test_schema_dumps_other(self):
+        """
+        Schema.dumps() writes a value that is not of the type as dumps() does
+        """
+        schema = hyperjson.Schema(List[Point])
+        assert schema.dumps([Point(1, 2), {"a": 1}]) == b'[{"x":1,"y":2},{"a":1}]'
+        assert schema.dumps({"a": 1}) == b'{"a":1}'
+        with pytest.raises(hyperjson.JSONEncodeError):
+            schema.dumps([object()])
+
+    def test_schema_scalar(self):
+        """
+        Schema.dumps() writes a Decimal as a string so that a datetime, UUID,
+        and Decimal read back as they were
+        """
+        schema = hyperjson.Schema(Payment)
+        obj = Payment(
+            uuid.UUID(int=1),
+            datetime.datetime(2025, 1, 2, 3, 4, 5, tzinfo=datetime.timezone.utc),
+            decimal.Decimal("0.10"),
+        )
+        assert schema.dumps(obj) == (
+            b'{"id":"00000000-0000-0000-0000-000000000001",'
+            b'"at":"2025-01-02T03:04:05+00:00","amount":"0.10"}'
+        )
+        assert schema.loads(schema.dumps(obj)) == obj
+
+    def test_schema_typeddict(self):
+        """
+        Schema.dumps() writes the keys of a TypedDict that are present
+        """
+        schema = hyperjson.Schema(Movie)
+        assert schema.dumps({"year": 2000, "other": 1}) == b'{"year":2000}'
+        assert schema.loads(b'{"title":"t"}') == {"title": "t"}
+
+    def test_schema_namedtuple(self):
+        """
+        Schema.dumps() writes a NamedTuple as an array of its fields
+        """
+        schema = hyperjson.Schema(Pair)
+        obj = Pair(1, Point(2, 3))
+        assert schema.dumps(obj) == b'[1,{"x":2,"y":3}]'
+        assert schema.loads(schema.dumps(obj)) == obj
+
+    @pytest.mark.skipif(pydantic is None, reason="pydantic is not installed")
+    def test_schema_pydantic(self):
+        """
+        Schema.dumps() writes a pydantic model by the aliases of its fields
+        """
+        schema = hyperjson.Schema(User)
+        obj = User(id=1, address=Address(city="c", zipCode="1"))
+        assert schema.dumps(obj) == b'{"id":1,"address":{"city":"c","zipCode":"1"}}'
+        assert schema.loads(schema.dumps(obj)) == obj
+
+    def test_schema_unsupported(self):
+        """
+        Schema() raises for an annotation that cannot be read
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.Schema(set)
+        assert str(exc.value).startswith("loads() type is not supported")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Schema()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.Schema(Point, option=hyperjson.OPT_INDENT_2)
@@ -0,0 +1,71 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import sys
+
+import hyperjson
+
+DOC = b'{"a": null, "b": [true, false, null], "c": {"d": true}, "e": false}'
+
+
+def refcounts():
+    return (sys.getrefcount(None), sys.getrefcount(True), sys.getrefcount(False))
+
+
+class TestSingletons:
+    def test_loads_refcount(self):
+        """
+        loads() of null, true, and false leaves the singletons' reference
+        counts as they were once the result is released
+        """
+        for kwargs in (
+            {},
+            {"allow_nan": True},
+            {"option": hyperjson.OPT_OMIT_NULL_ON_LOAD},
+            {"option": hyperjson.OPT_OMIT_NULL_ON_LOAD, "json5": True},
+            {"duplicate_keys": "first"},
+        ):
+            hyperjson.loads(DOC, **kwargs)
+            before = refcounts()
+            for _ in range(100):
+                val = hyperjson.loads(DOC, **kwargs)
+                del val
+            assert refcounts() == before
+
+    def test_loads_omit_null(self):
+        """
+        loads() OPT_OMIT_NULL_ON_LOAD of the extended parser
+        """
+        before = refcounts()
+        for _ in range(100):
+            assert hyperjson.loads(
+                '{"a": null, "b": null,}',
+                option=hyperjson.OPT_OMIT_NULL_ON_LOAD,
+                allow_trailing_commas=True,
+            ) == {}
+        assert refcounts() == before
+
+    def test_dumps_refcount(self):
+        """
+        dumps() of None, True, and False leaves their reference counts as
+        they were
+        """
+        obj = {"a": None, "b": [True, False, None], "c": (True,)}
+        hyperjson.dumps(obj)
+        before = refcounts()
+        for _ in range(100):
+            hyperjson.dumps(obj)
+            hyperjson.dumps(obj, default=str)
+        assert refcounts() == before
+
+    def test_return_refcount(self):
+        """
+        functions returning None, True, or False
+        """
+        hyperjson.compare_digest_json(1, 1)
+        before = refcounts()
+        for _ in range(100):
+            assert hyperjson.compare_digest_json(1, 1) is True
+            assert hyperjson.compare_digest_json(1, 2) is False
+            assert hyperjson.collect_stats(False) is None
+        assert refcounts() == before
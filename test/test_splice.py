@@ -0,0 +1,115 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import datetime
+
+import pytest
+
+import hyperjson
+
+OBJ = {
+    "name": "example",
+    "tags": ["a", "b"],
+    "nested": {"x": 1, "y": [{"z": None}]},
+}
+
+
+class TestSplice:
+    def test_splice(self):
+        """
+        splice() of the output of dumps() matches dumps() of the changed object
+        """
+        data = hyperjson.dumps(OBJ)
+        for pointer, value, expected in (
+            ("/name", "other", {**OBJ, "name": "other"}),
+            ("/tags/1", {"c": [1, 2]}, {**OBJ, "tags": ["a", {"c": [1, 2]}]}),
+            (
+                "/nested/y/0/z",
+                [True],
+                {**OBJ, "nested": {"x": 1, "y": [{"z": [True]}]}},
+            ),
+            ("", [1], [1]),
+        ):
+            assert hyperjson.splice(data, pointer, value) == hyperjson.dumps(expected)
+
+    def test_splice_indent(self):
+        """
+        splice() indents the value for its depth in the document
+        """
+        for opt in (
+            hyperjson.OPT_INDENT_2,
+            hyperjson.OPT_INDENT_4 | hyperjson.OPT_SORT_KEYS,
+            hyperjson.OPT_INDENT_TAB,
+        ):
+            data = hyperjson.dumps(OBJ, option=opt)
+            value = {"b": [1, {"c": []}], "a": {}}
+            expected = {**OBJ, "nested": {"x": 1, "y": [{"z": value}]}}
+            assert hyperjson.splice(
+                data, "/nested/y/0/z", value, option=opt
+            ) == hyperjson.dumps(expected, option=opt)
+
+    def test_splice_add(self):
+        """
+        splice() adds a key to an object and appends to an array with "-"
+        """
+        data = hyperjson.dumps(OBJ, option=hyperjson.OPT_INDENT_2)
+        assert hyperjson.loads(
+            hyperjson.splice(data, "/nested/w", [1], option=hyperjson.OPT_INDENT_2)
+        ) == {**OBJ, "nested": {"x": 1, "y": [{"z": None}], "w": [1]}}
+        assert hyperjson.splice(b'{"a":[1]}', "/a/-", 2) == b'{"a":[1,2]}'
+        assert hyperjson.splice(b"{}", "/a", 1) == b'{"a":1}'
+
+    def test_splice_ensure_ascii(self):
+        """
+        splice() escapes a value and an added key with OPT_ENSURE_ASCII
+        """
+        assert (
+            hyperjson.splice(b"{}", "/é", "é", option=hyperjson.OPT_ENSURE_ASCII)
+            == b'{"\\u00e9":"\\u00e9"}'
+        )
+
+    def test_splice_default(self):
+        """
+        splice() calls default= for a value that is not supported
+        """
+        value = datetime.timedelta(seconds=1)
+        assert (
+            hyperjson.splice(b"[0]", "/0", value, default=lambda x: x.total_seconds())
+            == b"[1.0]"
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.splice(b"[0]", "/0", value)
+
+    def test_splice_not_found(self):
+        """
+        splice() raises for a path not in the document
+        """
+        for pointer in ("/missing/a", "/tags/5", "/name/a"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.splice(hyperjson.dumps(OBJ), pointer, 1)
+            assert str(exc.value) == f"splice() path not found: {pointer}"
+
+    def test_splice_malformed(self):
+        """
+        splice() of data that is not a document raises rather than crashing
+        """
+        for data in (b"", b"[", b'{"a"', b'{"a":[1,', b'{"a":"\\', b"[1]]"):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.splice(data, "/a/0", 1)
+
+    def test_splice_pointer(self):
+        """
+        splice() pointer must be a str
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.splice(b"[]", 0, 1)  # type: ignore
+        assert str(exc.value) == "splice() pointer must be a str"
+
+    def test_splice_args(self):
+        """
+        splice() takes exactly 3 positional arguments
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.splice(b"[]", "/0")  # type: ignore
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.splice(b"[]", "/0", 1, other=1)  # type: ignore
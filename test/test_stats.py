@@ -0,0 +1,177 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import io
+from typing import List
+
+import pytest
+
+import hyperjson
+
+
+class TestStats:
+    def setup_method(self, method):
+        hyperjson._reset_for_testing()
+
+    def teardown_method(self, method):
+        hyperjson._reset_for_testing()
+
+    def test_stats_disabled(self):
+        """
+        stats() counts nothing until collect_stats(True)
+        """
+        hyperjson.dumps([], option=hyperjson.OPT_SORT_KEYS)
+        hyperjson.loads("[]")
+        stats = hyperjson.stats()
+        assert stats["enabled"] is False
+        assert set(stats["calls"].values()) == {0}
+        assert set(stats["options"].values()) == {0}
+
+    def test_stats_calls(self):
+        """
+        stats() counts calls of each function
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps([])
+        hyperjson.dumps({})
+        hyperjson.dumps_batch([1])
+        hyperjson.dumps_length([])
+        hyperjson.loads("[]")
+        hyperjson.loads("[]", option=None)
+        hyperjson.loads_tracked("[]")
+        hyperjson.join_array([b"1"])
+        hyperjson.join_object([("a", b"1")])
+        hyperjson.compare_digest_json(1, 1)
+        hyperjson.Builder()
+        hyperjson.LinesWriter(io.BytesIO())
+        hyperjson.edit(b"[]")
+        hyperjson.splice(b"[1]", "/0", 2)
+        schema = hyperjson.Schema(List[int])
+        schema.loads("[1]")
+        schema.dumps([1])
+        hyperjson.merge_patch({}, {})
+        hyperjson.pointer(b"[1]", "/0")
+        hyperjson.equals(1, 1)
+        hyperjson.merge3({}, {}, {})
+        hyperjson.flatten({"a": {"b": 1}})
+        hyperjson.unflatten({"a.b": 1})
+        hyperjson.minify(b"[ ]")
+        hyperjson.prettify(b"[]")
+        hyperjson.validate(b"[]")
+        hyperjson.hash([])
+        hyperjson.dumps_msgpack([])
+        hyperjson.loads_msgpack(b"\x90")
+        stats = hyperjson.stats()
+        assert stats["enabled"] is True
+        assert stats["calls"] == {
+            "dumps": 2,
+            "dumps_batch": 1,
+            "dumps_length": 1,
+            "loads": 2,
+            "loads_tracked": 1,
+            "loads_numpy": 0,
+            "verify_and_loads": 0,
+            "join_array": 1,
+            "join_object": 1,
+            "compare_digest_json": 1,
+            "Builder": 1,
+            "LinesWriter": 1,
+            "edit": 1,
+            "splice": 1,
+            "Schema.loads": 1,
+            "Schema.dumps": 1,
+            "merge_patch": 1,
+            "pointer": 1,
+            "equals": 1,
+            "merge3": 1,
+            "flatten": 1,
+            "unflatten": 1,
+            "minify": 1,
+            "prettify": 1,
+            "validate": 1,
+            "hash": 1,
+            "dumps_msgpack": 1,
+            "loads_msgpack": 1,
+        }
+
+    def test_stats_options(self):
+        """
+        stats() counts each flag of option=
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps(
+            {}, option=hyperjson.OPT_SORT_KEYS | hyperjson.OPT_NON_STR_KEYS
+        )
+        hyperjson.dumps({}, None, hyperjson.OPT_SORT_KEYS)
+        hyperjson.dumps_length({}, option=hyperjson.OPT_OMIT_EMPTY)
+        hyperjson.loads("{}", option=hyperjson.OPT_OMIT_NULL_ON_LOAD)
+        hyperjson.Builder(option=hyperjson.OPT_NAIVE_UTC)
+        options = {
+            key: val for key, val in hyperjson.stats()["options"].items() if val
+        }
+        assert options == {
+            "OPT_SORT_KEYS": 2,
+            "OPT_NON_STR_KEYS": 1,
+            "OPT_OMIT_EMPTY_DICT": 1,
+            "OPT_OMIT_EMPTY_LIST": 1,
+            "OPT_OMIT_EMPTY_STR": 1,
+            "OPT_OMIT_NULL_ON_LOAD": 1,
+            "OPT_NAIVE_UTC": 1,
+        }
+
+    def test_stats_keyword_not_option(self):
+        """
+        stats() does not count a flag set by a keyword argument
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps({}, key_order="natural")
+        hyperjson.dumps_batch([{}], key_order="natural")
+        assert hyperjson.stats()["options"]["OPT_SORT_KEYS"] == 0
+
+    def test_collect_stats_stop(self):
+        """
+        collect_stats(False) stops counting and keeps the counts
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps([])
+        hyperjson.collect_stats(False)
+        hyperjson.dumps([])
+        stats = hyperjson.stats()
+        assert stats["enabled"] is False
+        assert stats["calls"]["dumps"] == 1
+
+    def test_stats_reset(self):
+        """
+        _reset_for_testing() clears the counts and stops counting
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps([])
+        hyperjson._reset_for_testing()
+        hyperjson.dumps([])
+        stats = hyperjson.stats()
+        assert stats["enabled"] is False
+        assert stats["calls"]["dumps"] == 0
+
+    def test_stats_reset_kwarg(self):
+        """
+        stats(reset=True) returns the counts and then zeroes them without
+        stopping counting
+        """
+        hyperjson.collect_stats(True)
+        hyperjson.dumps([])
+        stats = hyperjson.stats(reset=True)
+        assert stats["calls"]["dumps"] == 1
+        stats = hyperjson.stats()
+        assert stats["enabled"] is True
+        assert stats["calls"]["dumps"] == 0
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.stats(reset=1)  # type: ignore
+
+    def test_collect_stats_not_bool(self):
+        """
+        collect_stats() argument not a bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.collect_stats(1)  # type: ignore
+        with pytest.raises(TypeError):
+            hyperjson.collect_stats()  # type: ignore
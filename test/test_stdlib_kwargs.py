@@ -0,0 +1,218 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import decimal
+import json
+
+import pytest
+
+import hyperjson
+
+
+class SetEncoder(json.JSONEncoder):
+    def default(self, o):
+        if isinstance(o, set):
+            return sorted(o)
+        return super().default(o)
+
+
+class HookDecoder(json.JSONDecoder):
+    def __init__(self):
+        super().__init__(object_hook=self.hook, parse_float=decimal.Decimal)
+
+    def hook(self, obj):
+        return ("hook", obj)
+
+
+class TestDumpsStdlibKwargs:
+    def test_sort_keys(self):
+        """
+        dumps() sort_keys= is OPT_SORT_KEYS
+        """
+        obj = {"b": 1, "a": 2}
+        assert hyperjson.dumps(obj, sort_keys=True) == b'{"a":2,"b":1}'
+        assert hyperjson.dumps(obj, sort_keys=False) == b'{"b":1,"a":2}'
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj, sort_keys=1)
+
+    def test_ensure_ascii(self):
+        """
+        dumps() ensure_ascii= is OPT_ENSURE_ASCII
+        """
+        assert hyperjson.dumps("é", ensure_ascii=True) == b'"\\u00e9"'
+        assert hyperjson.dumps("é", ensure_ascii=False) == '"é"'.encode()
+
+    def test_indent(self):
+        """
+        dumps() indent= of 2, 4, or 8 spaces or a tab
+        """
+        obj = {"a": [1]}
+        assert hyperjson.dumps(obj, indent=2) == hyperjson.dumps(
+            obj, option=hyperjson.OPT_INDENT_2
+        )
+        assert hyperjson.dumps(obj, indent="    ") == json.dumps(obj, indent=4).encode()
+        assert hyperjson.dumps(obj, indent=8) == json.dumps(obj, indent=8).encode()
+        assert hyperjson.dumps(obj, indent="\t") == (
+            json.dumps(obj, indent="\t").encode()
+        )
+        assert hyperjson.dumps(obj, indent=None) == b'{"a":[1]}'
+
+    def test_indent_invalid(self):
+        """
+        dumps() indent= of another width raises
+        """
+        for indent in (0, 3, -1, " x", 2.0):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps([], indent=indent)
+
+    def test_separators(self):
+        """
+        dumps() separators= may be those dumps() writes
+        """
+        assert hyperjson.dumps([1, 2], separators=(",", ":")) == b"[1,2]"
+        assert hyperjson.dumps({"a": 1}, indent=2, separators=[",", ": "]) == (
+            json.dumps({"a": 1}, indent=2).encode()
+        )
+        assert hyperjson.dumps([1, 2], separators=None) == b"[1,2]"
+
+    def test_separators_invalid(self):
+        """
+        dumps() separators= that dumps() does not write raises
+        """
+        for kwargs in (
+            {"separators": (", ", ": ")},
+            {"separators": (",", ": ")},
+            {"separators": (",", ":"), "indent": 2},
+            {"separators": (",",)},
+            {"separators": ","},
+        ):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps([1], **kwargs)
+
+    def test_cls(self):
+        """
+        dumps() cls= uses the default() of a json.JSONEncoder subclass
+        """
+        assert hyperjson.dumps({2, 1}, cls=SetEncoder) == b"[1,2]"
+        assert hyperjson.dumps({2, 1}, cls=SetEncoder, default=list) in (
+            b"[1,2]",
+            b"[2,1]",
+        )
+        assert hyperjson.dumps([1], cls=json.JSONEncoder) == b"[1]"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(object(), cls=SetEncoder)
+
+    def test_cls_invalid(self):
+        """
+        dumps() cls= must be a json.JSONEncoder subclass that does not override
+        encode() or iterencode()
+        """
+
+        class Encoder(json.JSONEncoder):
+            def encode(self, o):
+                return ""
+
+        for cls in (int, Encoder, SetEncoder()):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.dumps([], cls=cls)
+
+
+    def test_unsupported(self):
+        """
+        dumps() names the json.dumps() arguments it does not support
+        """
+        for kwarg in ("skipkeys", "allow_nan", "check_circular"):
+            with pytest.raises(hyperjson.JSONEncodeError) as exc:
+                hyperjson.dumps({}, **{kwarg: True})
+            assert f"json.dumps() argument: '{kwarg}'" in str(exc.value)
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.dumps({}, other=True)  # type: ignore
+        assert str(exc.value) == "dumps() got an unexpected keyword argument"
+
+
+class TestLoadsStdlibKwargs:
+    def test_object_hook(self):
+        """
+        loads() object_hook= is called with each dict, innermost first
+        """
+        seen = []
+
+        def hook(obj):
+            seen.append(obj)
+            return len(obj)
+
+        assert hyperjson.loads('[{"a": {"b": 1}, "c": 2}]', object_hook=hook) == [2]
+        assert seen == [{"b": 1}, {"a": 1, "c": 2}]
+
+    def test_object_hook_matches_json(self):
+        """
+        loads() object_hook= returns what json.loads() does
+        """
+        data = '{"a": [{"b": {}}, 1], "c": {"d": null}}'
+        assert hyperjson.loads(data, object_hook=lambda d: ("h", d)) == json.loads(
+            data, object_hook=lambda d: ("h", d)
+        )
+
+    def test_object_hook_pairs_priority(self):
+        """
+        loads() object_pairs_hook= takes priority over object_hook=
+        """
+        assert hyperjson.loads('{"a": 1}', object_hook=len, object_pairs_hook=list) == [
+            ("a", 1)
+        ]
+
+    def test_object_hook_raises(self):
+        """
+        loads() object_hook= that raises
+        """
+        with pytest.raises(ZeroDivisionError):
+            hyperjson.loads("{}", object_hook=lambda d: 1 / 0)
+
+    def test_object_hook_invalid(self):
+        """
+        loads() object_hook= must be callable and not combined with type=
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads("{}", object_hook=1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads("{}", object_hook=len, type=dict)
+
+    def test_cls(self):
+        """
+        loads() cls= uses the hooks of a json.JSONDecoder subclass
+        """
+        data = '{"a": {"b": 1.5}}'
+        expected = json.loads(data, cls=HookDecoder)
+        assert hyperjson.loads(data, cls=HookDecoder) == expected
+        assert hyperjson.loads(data, cls=json.JSONDecoder) == {"a": {"b": 1.5}}
+
+    def test_cls_explicit_hooks(self):
+        """
+        loads() hooks given as arguments replace those of cls=
+        """
+        assert hyperjson.loads("[1.5]", cls=HookDecoder, parse_float=str) == ["1.5"]
+
+    def test_cls_invalid(self):
+        """
+        loads() cls= must be a json.JSONDecoder subclass that does not override
+        decode() or raw_decode()
+        """
+
+        class Decoder(json.JSONDecoder):
+            def decode(self, s):
+                return None
+
+        for cls in (dict, Decoder, HookDecoder()):
+            with pytest.raises(hyperjson.JSONEncodeError):
+                hyperjson.loads("{}", cls=cls)
+
+    def test_unsupported(self):
+        """
+        loads() names the json.loads() arguments it does not support
+        """
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("[]", parse_constant=float)  # type: ignore
+        assert "json.loads() argument: 'parse_constant'" in str(exc.value)
+        with pytest.raises(hyperjson.JSONEncodeError) as exc:
+            hyperjson.loads("[]", other=True)  # type: ignore
+        assert str(exc.value) == "loads() got an unexpected keyword argument"
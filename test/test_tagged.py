@@ -0,0 +1,170 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import dataclasses
+
+import pytest
+
+import hyperjson
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: int
+    _cache: int = 0
+
+
+class Circle:
+    def __init__(self, center, radius):
+        self.center = center
+        self.radius = radius
+        self._area = None
+
+    def __eq__(self, other):
+        return (self.center, self.radius) == (other.center, other.radius)
+
+
+class Unregistered:
+    pass
+
+
+def register():
+    hyperjson.register_tagged(Point)
+    hyperjson.register_tagged(Circle, "shape.Circle")
+
+
+class TestTagged:
+    def test_tagged_dumps(self):
+        """
+        dumps() tagged=True writes a registered type as an object of its tag
+        and public fields
+        """
+        register()
+        assert hyperjson.dumps(Point(1, 2), tagged=True) == (
+            b'{"__type__":"Point","x":1,"y":2}'
+        )
+        assert hyperjson.dumps(Circle(Point(0, 1), 2), tagged=True) == (
+            b'{"__type__":"shape.Circle","center":'
+            b'{"__type__":"Point","x":0,"y":1},"radius":2}'
+        )
+
+    def test_tagged_dumps_default(self):
+        """
+        dumps() without tagged=True writes a registered type as it otherwise
+        would
+        """
+        register()
+        assert hyperjson.dumps(Point(1, 2)) == b'{"x":1,"y":2}'
+        assert hyperjson.dumps(Point(1, 2), tagged=False) == b'{"x":1,"y":2}'
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Circle(1, 2))
+
+    def test_tagged_roundtrip(self):
+        """
+        loads() tagged=True builds the registered type of each tagged object
+        """
+        register()
+        obj = {"shapes": [Circle(Point(0, 1), 2), Point(3, 4)], "n": 2}
+        data = hyperjson.dumps(obj, tagged=True)
+        assert hyperjson.loads(data, tagged=True) == obj
+        assert hyperjson.loads(data)["shapes"][1] == {
+            "__type__": "Point",
+            "x": 3,
+            "y": 4,
+        }
+
+    def test_tagged_loads_unregistered(self):
+        """
+        loads() tagged=True raises JSONDecodeError for a tag that is not
+        registered
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'[{"__type__":"Missing"}]', tagged=True)
+        assert exc.value.msg == "tagged type is not registered: Missing"
+
+    def test_tagged_loads_missing_member(self):
+        """
+        loads() tagged=True raises JSONDecodeError for an object whose members
+        the type cannot be built from
+        """
+        register()
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.loads(b'{"__type__":"Point","x":1}', tagged=True)
+        assert exc.value.msg == "tagged type cannot be built from its members: Point"
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.loads(b'[{"__type__":"Point","x":1,"y":2,"z":3}]', tagged=True)
+
+    def test_tagged_loads_error(self):
+        """
+        loads() tagged=True raises the exception of the type it calls other
+        than TypeError
+        """
+
+        class Failing:
+            def __init__(self, value):
+                raise ZeroDivisionError
+
+        hyperjson.register_tagged(Failing)
+        with pytest.raises(ZeroDivisionError):
+            hyperjson.loads(b'{"__type__":"Failing","value":1}', tagged=True)
+
+    def test_tagged_subclass(self):
+        """
+        dumps() tagged=True writes only the registered type, not subclasses
+        """
+        register()
+
+        @dataclasses.dataclass
+        class Point3(Point):
+            z: int = 0
+
+        assert hyperjson.dumps(Point3(1, 2, z=3), tagged=True) == (
+            b'{"x":1,"y":2,"z":3}'
+        )
+
+    def test_tagged_unregistered(self):
+        """
+        dumps() tagged=True raises for a type that is neither registered nor
+        otherwise supported
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Unregistered(), tagged=True)
+
+    def test_register_tagged_invalid(self):
+        """
+        register_tagged() raises for an argument that is not a type or tag,
+        or a tag registered to another type
+        """
+        register()
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_tagged(1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_tagged(Unregistered, 1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_tagged(Unregistered, "Point")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.register_tagged()
+
+    def test_register_tagged_replace(self):
+        """
+        register_tagged() replaces the tag of a type registered again
+        """
+
+        class Other:
+            pass
+
+        hyperjson.register_tagged(Other, "a")
+        hyperjson.register_tagged(Other, "b")
+        assert hyperjson.dumps(Other(), tagged=True) == b'{"__type__":"b"}'
+        hyperjson.register_tagged(Circle, "a")
+        register()
+
+    def test_tagged_invalid(self):
+        """
+        dumps() and loads() raise for tagged= that is not a bool
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(1, tagged=1)
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.loads(b"1", tagged=1)
@@ -459,6 +459,106 @@ class TestType:
         for val in (18446744073709551616, -9223372036854775809):
             pytest.raises(hyperjson.JSONEncodeError, hyperjson.dumps, val)
 
+    def test_int_list_batch(self):
+        """
+        list of int written in batches
+        """
+        for length in (1, 2, 15, 16, 17, 33, 1000):
+            obj = [(idx * 7919) % 2000003 - 1000001 for idx in range(length)]
+            assert hyperjson.dumps(obj) == (
+                "[" + ",".join(str(val) for val in obj) + "]"
+            ).encode("utf-8")
+
+    def test_int_list_batch_limits(self):
+        """
+        list of int at and beyond the single-digit boundary
+        """
+        obj = [
+            0,
+            1073741823,
+            -1073741823,
+            1073741824,
+            -1073741824,
+            2,
+            9223372036854775807,
+            3,
+            4,
+        ]
+        assert hyperjson.dumps(obj) == (
+            "[" + ",".join(str(val) for val in obj) + "]"
+        ).encode("utf-8")
+
+    def test_int_list_batch_mixed(self):
+        """
+        list of int interrupted by other types
+        """
+        obj = [1, 2, True, 3, 4, None, 5, "6", 7, 8.0, 9, 10, [11, 12]]
+        assert hyperjson.dumps(obj) == b'[1,2,true,3,4,null,5,"6",7,8.0,9,10,[11,12]]'
+
+    def test_int_list_batch_indent(self):
+        """
+        list of int with OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps([1, 2, 3], option=hyperjson.OPT_INDENT_2)
+            == b"[\n  1,\n  2,\n  3\n]"
+        )
+
+    def test_int_list_batch_strict(self):
+        """
+        list of int with OPT_STRICT_INTEGER
+        """
+        assert (
+            hyperjson.dumps([1, 2, 3], option=hyperjson.OPT_STRICT_INTEGER)
+            == b"[1,2,3]"
+        )
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps([1, 2, 9007199254740992], option=hyperjson.OPT_STRICT_INTEGER)
+
+    def test_list_homogeneous(self):
+        """
+        list or tuple of only str, int, or float is written as a list that
+        also has None
+        """
+        for obj in (
+            [f"item{idx}\n" for idx in range(40)],
+            [idx * 7919 - 2**40 for idx in range(40)] + [2**63 - 1],
+            [idx / 7 for idx in range(40)],
+            tuple(idx * 1e300 for idx in range(40)),
+        ):
+            mixed = hyperjson.dumps([*obj, None])
+            assert hyperjson.dumps(obj) == mixed[: -len(b",null]")] + b"]"
+            assert hyperjson.loads(
+                hyperjson.dumps(obj, option=hyperjson.OPT_INDENT_2)
+            ) == hyperjson.loads(mixed)[:-1]
+
+    def test_list_homogeneous_float(self):
+        """
+        list of only float with options that change how floats are written
+        """
+        obj = [idx / 3 for idx in range(40)]
+        obj[20] = float("nan")
+        assert hyperjson.dumps(obj, float_decimals=2) == hyperjson.dumps(
+            [round(val, 2) for val in obj]
+        )
+        assert hyperjson.dumps(obj, float_format=".1f").startswith(b"[0.0,0.3,0.7,")
+        assert b",NaN," in hyperjson.dumps(obj, non_finite="literal")
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj, non_finite="raise")
+
+    def test_list_homogeneous_subclass(self):
+        """
+        list of str with a subclass of str among them
+        """
+
+        class Str(str):
+            pass
+
+        obj = ["a"] * 20 + [Str("b")]
+        assert hyperjson.dumps(obj) == b"[" + b'"a",' * 20 + b'"b"]'
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(obj, option=hyperjson.OPT_PASSTHROUGH_SUBCLASS)
+
     def test_float(self):
         """
         float
@@ -0,0 +1,129 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import io
+import math
+
+import pytest
+
+import hyperjson
+import hyperjson.ujson as ujson
+
+
+class TestUjsonDumps:
+    def test_dumps_default(self):
+        """
+        ujson.dumps() returns str escaping non-ASCII and "/" by default
+        """
+        assert ujson.dumps({"a": ["é/x", 1, 1.5, None]}) == (
+            '{"a":["\\u00e9\\/x",1,1.5,null]}'
+        )
+
+    def test_ensure_ascii_and_slashes(self):
+        """
+        ujson.dumps() ensure_ascii=False and escape_forward_slashes=False
+        """
+        assert ujson.dumps("é/x", ensure_ascii=False) == '"é\\/x"'
+        assert ujson.dumps("é/x", escape_forward_slashes=False) == '"\\u00e9/x"'
+
+    def test_encode_html_chars(self):
+        """
+        ujson.dumps() encode_html_chars=True escapes "<", ">", and "&"
+        """
+        assert ujson.dumps("<a&b>", encode_html_chars=True) == (
+            '"\\u003ca\\u0026b\\u003e"'
+        )
+
+    def test_double_precision(self):
+        """
+        ujson.dumps() double_precision= rounds floats to that many decimals
+        """
+        assert ujson.dumps([1.23456, 0.1 + 0.2], double_precision=2) == "[1.23,0.3]"
+        assert ujson.dumps(0.1 + 0.2, double_precision=17) == "0.30000000000000004"
+
+    def test_sort_keys_and_indent(self):
+        """
+        ujson.dumps() sort_keys= and indent= of any width
+        """
+        assert ujson.dumps({"b": 1, "a": [1]}, sort_keys=True) == '{"a":[1],"b":1}'
+        assert ujson.dumps({"a": [1]}, indent=3) == '{\n   "a": [\n      1\n   ]\n}'
+        assert ujson.dumps({"a": "x\n  y"}, indent=1) == '{\n "a": "x\\n  y"\n}'
+
+    def test_allow_nan(self):
+        """
+        ujson.dumps() writes NaN and Infinity unless allow_nan=False
+        """
+        assert ujson.dumps([float("nan"), float("-inf")]) == "[NaN,-Infinity]"
+        with pytest.raises(hyperjson.JSONEncodeError):
+            ujson.dumps(float("nan"), allow_nan=False)
+
+    def test_reject_bytes(self):
+        """
+        ujson.dumps() reject_bytes=False writes bytes as UTF-8 str
+        """
+        with pytest.raises(TypeError):
+            ujson.dumps(b"x")
+        assert ujson.dumps([b"x"], reject_bytes=False) == '["x"]'
+        assert ujson.dumps([b"x", {1}], reject_bytes=False, default=list) == (
+            '["x",[1]]'
+        )
+
+    def test_default(self):
+        """
+        ujson.dumps() default=
+        """
+        assert ujson.dumps({1, 2}, default=sorted) == "[1,2]"
+
+    def test_separators(self):
+        """
+        ujson.dumps() separators= may only be those written by default
+        """
+        assert ujson.dumps([1, 2], separators=(",", ":")) == "[1,2]"
+        with pytest.raises(TypeError):
+            ujson.dumps([1, 2], separators=(", ", ": "))
+
+    def test_encode(self):
+        """
+        ujson.encode() is ujson.dumps()
+        """
+        assert ujson.encode is ujson.dumps
+
+    def test_dump(self):
+        """
+        ujson.dump() writes to a file
+        """
+        fp = io.StringIO()
+        ujson.dump({"a": "/"}, fp, escape_forward_slashes=False)
+        assert fp.getvalue() == '{"a":"/"}'
+
+
+class TestUjsonLoads:
+    def test_loads(self):
+        """
+        ujson.loads() and ujson.decode() read str and bytes
+        """
+        assert ujson.loads('{"a":[1,"\\/"]}') == {"a": [1, "/"]}
+        assert ujson.decode(b"[1.5]", precise_float=True) == [1.5]
+
+    def test_loads_nan(self):
+        """
+        ujson.loads() reads NaN and Infinity
+        """
+        val = ujson.loads("[NaN, Infinity]")
+        assert math.isnan(val[0])
+        assert val[1] == float("inf")
+
+    def test_loads_invalid(self):
+        """
+        ujson.loads() raises JSONDecodeError, a ValueError
+        """
+        with pytest.raises(ujson.JSONDecodeError):
+            ujson.loads("[")
+        with pytest.raises(ValueError):
+            ujson.loads("[")
+
+    def test_load(self):
+        """
+        ujson.load() reads a file
+        """
+        assert ujson.load(io.StringIO('{"a": 1}')) == {"a": 1}
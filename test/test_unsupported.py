@@ -0,0 +1,382 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import collections
+import dataclasses
+import sys
+
+import pytest
+
+import hyperjson
+
+from .util import SUPPORTS_GETREFCOUNT
+
+
+class Custom:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    name: str
+    value: object
+
+
+@dataclasses.dataclass
+class SlotsRecord:
+    __slots__ = ("name", "value")
+    name: str
+    value: object
+
+
+Point = collections.namedtuple("Point", ("x", "y"))
+
+
+class TestSkipUnsupported:
+    def test_skip_list(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops array elements
+        """
+        assert (
+            hyperjson.dumps(
+                [Custom(), 1, Custom(), 2, Custom()],
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+            == b"[1,2]"
+        )
+
+    def test_skip_list_all(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops every array element
+        """
+        assert (
+            hyperjson.dumps([Custom(), Custom()], option=hyperjson.OPT_SKIP_UNSUPPORTED)
+            == b"[]"
+        )
+
+    def test_skip_dict(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops keys
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": Custom(), "b": 1, "c": Custom()},
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+            == b'{"b":1}'
+        )
+
+    def test_skip_dict_all(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops every key
+        """
+        assert (
+            hyperjson.dumps({"a": Custom()}, option=hyperjson.OPT_SKIP_UNSUPPORTED)
+            == b"{}"
+        )
+
+    def test_skip_dict_sort_keys(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED and OPT_SORT_KEYS
+        """
+        assert (
+            hyperjson.dumps(
+                {"c": 3, "b": Custom(), "a": 1},
+                option=hyperjson.OPT_SKIP_UNSUPPORTED | hyperjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":1,"c":3}'
+        )
+
+    def test_skip_dict_non_str_keys(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED and OPT_NON_STR_KEYS
+        """
+        assert (
+            hyperjson.dumps(
+                {1: Custom(), 2: "b"},
+                option=hyperjson.OPT_SKIP_UNSUPPORTED | hyperjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"2":"b"}'
+        )
+
+    def test_skip_dataclass(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops dataclass fields
+        """
+        assert (
+            hyperjson.dumps(
+                [Record("a", Custom()), SlotsRecord("b", Custom())],
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+            == b'[{"name":"a"},{"name":"b"}]'
+        )
+
+    def test_skip_namedtuple(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops namedtuple fields
+        """
+        assert (
+            hyperjson.dumps(
+                Point(1, Custom()),
+                option=hyperjson.OPT_SKIP_UNSUPPORTED
+                | hyperjson.OPT_NAMEDTUPLE_AS_OBJECT,
+            )
+            == b'{"x":1}'
+        )
+
+    def test_skip_indent(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED and OPT_INDENT_2
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [Custom(), 1], "b": Custom(), "c": [Custom()]},
+                option=hyperjson.OPT_SKIP_UNSUPPORTED | hyperjson.OPT_INDENT_2,
+            )
+            == b'{\n  "a": [\n    1\n  ],\n  "c": []\n}'
+        )
+
+    def test_skip_shared_keys(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED in list of dict sharing keys
+        """
+
+        class Row:
+            def __init__(self, value):
+                self.a = 1
+                self.b = value
+
+        rows = [Row(Custom()).__dict__ for _ in range(4)]
+        assert (
+            hyperjson.dumps(rows, option=hyperjson.OPT_SKIP_UNSUPPORTED)
+            == b'[{"a":1},{"a":1},{"a":1},{"a":1}]'
+        )
+
+    def test_skip_default(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED uses default before dropping
+        """
+
+        def default(obj):
+            if isinstance(obj, set):
+                return sorted(obj)
+            raise TypeError
+
+        assert (
+            hyperjson.dumps(
+                {"a": {2, 1}, "b": Custom()},
+                default=default,
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+            == b'{"a":[1,2]}'
+        )
+
+    def test_skip_default_error(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED drops only what default raises TypeError
+        for and raises other exceptions
+        """
+
+        def default(obj):
+            return 1 / 0
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps(
+                {"a": Custom()},
+                default=default,
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+        assert isinstance(exc_info.value.__cause__, ZeroDivisionError)
+
+    def test_skip_default_called_once(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED calls default once per object
+        """
+        calls = []
+
+        def default(obj):
+            calls.append(obj)
+            return "custom"
+
+        ref = Custom()
+        assert (
+            hyperjson.dumps(
+                {"a": ref},
+                default=default,
+                option=hyperjson.OPT_SKIP_UNSUPPORTED,
+            )
+            == b'{"a":"custom"}'
+        )
+        assert calls == [ref]
+
+    def test_skip_top_level(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED top-level object raises
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(Custom(), option=hyperjson.OPT_SKIP_UNSUPPORTED)
+
+    def test_skip_invalid_str(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED does not drop invalid str
+        """
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(["\ud800"], option=hyperjson.OPT_SKIP_UNSUPPORTED)
+
+    def test_skip_refcount(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED does not leak default results
+        """
+        ref = Custom()
+        result = [1, 2]
+
+        def default(obj):
+            return result
+
+        if SUPPORTS_GETREFCOUNT:
+            refcount = sys.getrefcount(result)
+        hyperjson.dumps(
+            [ref, {"a": ref}],
+            default=default,
+            option=hyperjson.OPT_SKIP_UNSUPPORTED,
+        )
+        if SUPPORTS_GETREFCOUNT:
+            assert sys.getrefcount(result) == refcount
+
+
+class TestNullUnsupported:
+    def test_null_list(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED replaces array elements
+        """
+        assert (
+            hyperjson.dumps([Custom(), 1], option=hyperjson.OPT_NULL_UNSUPPORTED)
+            == b"[null,1]"
+        )
+
+    def test_null_dict(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED replaces values
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": Custom(), "b": Record("x", Custom())},
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b'{"a":null,"b":{"name":"x","value":null}}'
+        )
+
+    def test_null_top_level(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED top-level object
+        """
+        assert (
+            hyperjson.dumps(Custom(), option=hyperjson.OPT_NULL_UNSUPPORTED)
+            == b"null"
+        )
+
+    def test_null_default_raises(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED when default raises
+        """
+
+        def default(obj):
+            raise TypeError
+
+        assert (
+            hyperjson.dumps(
+                [Custom()],
+                default=default,
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b"[null]"
+        )
+
+    def test_null_default_returns_unsupported(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED when default returns an unsupported type
+        """
+        def default(obj):
+            if isinstance(obj, Custom):
+                return object()
+            raise TypeError
+
+        assert (
+            hyperjson.dumps(
+                [Custom()],
+                default=default,
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b"[null]"
+        )
+
+    def test_null_default_recursion(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED does not hide the default recursion limit
+        """
+
+        def default(obj):
+            return Custom()
+
+        with pytest.raises(hyperjson.JSONEncodeError):
+            hyperjson.dumps(
+                Custom(),
+                default=default,
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+
+    def test_skip_and_null(self):
+        """
+        dumps() OPT_SKIP_UNSUPPORTED and OPT_NULL_UNSUPPORTED
+        """
+        assert (
+            hyperjson.dumps(
+                {"a": [Custom(), 1], "b": Custom()},
+                option=hyperjson.OPT_SKIP_UNSUPPORTED
+                | hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b'{"a":[1]}'
+        )
+        assert (
+            hyperjson.dumps(
+                Custom(),
+                option=hyperjson.OPT_SKIP_UNSUPPORTED
+                | hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b"null"
+        )
+
+    def test_null_default_error(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED writes null only for what default raises
+        TypeError for and raises other exceptions
+        """
+
+        def default(obj):
+            return 1 / 0
+
+        with pytest.raises(hyperjson.JSONEncodeError) as exc_info:
+            hyperjson.dumps(
+                [Custom()],
+                default=default,
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+        assert isinstance(exc_info.value.__cause__, ZeroDivisionError)
+
+    def test_null_exception_cleared(self):
+        """
+        dumps() OPT_NULL_UNSUPPORTED leaves no exception set
+        """
+
+        def default(obj):
+            raise TypeError
+
+        assert (
+            hyperjson.dumps(
+                [Custom()],
+                default=default,
+                option=hyperjson.OPT_NULL_UNSUPPORTED,
+            )
+            == b"[null]"
+        )
+        assert hyperjson.dumps([1]) == b"[1]"
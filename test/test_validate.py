@@ -0,0 +1,51 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import pytest
+
+import hyperjson
+
+
+class TestValidate:
+    def test_validate(self):
+        """
+        validate() returns None for a valid document
+        """
+        assert hyperjson.validate(b'{"a": [1, 2.5, "b", true, null, {}]}') is None
+        assert hyperjson.validate(b"[]") is None
+        assert hyperjson.validate(b'""') is None
+        assert hyperjson.validate(b" 1 ") is None
+
+    def test_validate_input_types(self):
+        """
+        validate() reads bytes, bytearray, memoryview, and str
+        """
+        for data in (b"[1]", bytearray(b"[1]"), memoryview(b"[1]"), "[1]"):
+            assert hyperjson.validate(data) is None
+
+    def test_validate_invalid(self):
+        """
+        validate() raises JSONDecodeError as loads() does
+        """
+        for data in (b'{"a":', b"[1,]", b"[1] [2]", b"", b"NaN", b"\xff"):
+            with pytest.raises(hyperjson.JSONDecodeError) as validate_exc:
+                hyperjson.validate(data)
+            with pytest.raises(hyperjson.JSONDecodeError) as loads_exc:
+                hyperjson.loads(data)
+            assert str(validate_exc.value) == str(loads_exc.value)
+
+    def test_validate_position(self):
+        """
+        validate() reports the position of the error
+        """
+        with pytest.raises(hyperjson.JSONDecodeError) as exc:
+            hyperjson.validate(b'{\n  "a": tru\n}')
+        assert exc.value.lineno == 2
+        assert exc.value.pos == 9
+
+    def test_validate_type(self):
+        """
+        validate() raises JSONDecodeError for an object that is not a document
+        """
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.validate([1])  # type: ignore
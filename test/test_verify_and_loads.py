@@ -0,0 +1,98 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+# Copyright ijl (2025)
+
+import hashlib
+import hmac
+
+import pytest
+
+import hyperjson
+
+KEY = b"webhook secret"
+BODY = b'{"action": "opened", "number": 1, "sender": {"login": "octocat"}}'
+
+
+def sign(body, key=KEY):
+    return hmac.new(key, body, hashlib.sha256).hexdigest()
+
+
+class TestVerifyAndLoads:
+    def test_verify_and_loads(self):
+        """
+        verify_and_loads() of a body with a valid signature
+        """
+        expected = {"action": "opened", "number": 1, "sender": {"login": "octocat"}}
+        signature = sign(BODY)
+        assert hyperjson.verify_and_loads(BODY, signature, KEY) == expected
+        assert hyperjson.verify_and_loads(BODY, "sha256=" + signature, KEY) == expected
+        assert hyperjson.verify_and_loads(BODY, signature.upper(), KEY) == expected
+        assert (
+            hyperjson.verify_and_loads(BODY, bytes.fromhex(signature), KEY) == expected
+        )
+        assert (
+            hyperjson.verify_and_loads(
+                bytearray(BODY), signature.encode(), KEY.decode(), algo="hmac-sha256"
+            )
+            == expected
+        )
+        assert (
+            hyperjson.verify_and_loads(memoryview(BODY), signature, KEY, "hmac-sha256")
+            == expected
+        )
+
+    def test_verify_and_loads_lengths(self):
+        """
+        verify_and_loads() of bodies and keys around the SHA-256 block size
+        """
+        for body_len in (1, 54, 55, 56, 63, 64, 65, 119, 120, 1000):
+            body = b'"' + b"a" * body_len + b'"'
+            for key in (b"", b"k", b"k" * 64, b"k" * 65, b"k" * 200):
+                assert hyperjson.verify_and_loads(body, sign(body, key), key) == (
+                    "a" * body_len
+                )
+
+    def test_verify_and_loads_mismatch(self):
+        """
+        verify_and_loads() of a body that does not match its signature
+        """
+        signature = sign(BODY)
+        for body, sig, key in (
+            (BODY.replace(b"1", b"2"), signature, KEY),
+            (BODY + b" ", signature, KEY),
+            (BODY, signature, KEY + b"x"),
+            (BODY, signature[:-1] + ("0" if signature[-1] != "0" else "1"), KEY),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError) as exc:
+                hyperjson.verify_and_loads(body, sig, key)
+            assert str(exc.value).startswith(
+                "verify_and_loads() signature does not match body"
+            )
+
+    def test_verify_and_loads_invalid_json(self):
+        """
+        verify_and_loads() of a signed body that is not valid JSON
+        """
+        body = b'{"a": '
+        with pytest.raises(hyperjson.JSONDecodeError):
+            hyperjson.verify_and_loads(body, sign(body), KEY)
+
+    def test_verify_and_loads_invalid_args(self):
+        """
+        verify_and_loads() arguments of the wrong type or value
+        """
+        signature = sign(BODY)
+        for args, kwargs in (
+            ((BODY, signature), {}),
+            ((BODY, signature, KEY, "hmac-sha256", None), {}),
+            ((BODY, signature, KEY), {"algo": "hmac-sha1"}),
+            ((BODY, signature, KEY), {"algo": None}),
+            ((BODY, signature, KEY, "hmac-sha256"), {"algo": "hmac-sha256"}),
+            ((BODY, signature, KEY), {"key": KEY}),
+            ((BODY, signature[:-2], KEY), {}),
+            ((BODY, "z" * 64, KEY), {}),
+            ((BODY, 1, KEY), {}),
+            ((BODY, signature, None), {}),
+            ((1, signature, KEY), {}),
+        ):
+            with pytest.raises(hyperjson.JSONDecodeError):
+                hyperjson.verify_and_loads(*args, **kwargs)  # type: ignore
@@ -28,6 +28,19 @@ if not IS_FREETHREADING:
     except ImportError:
         pass
 
+pyarrow = None  # type: ignore
+if not IS_FREETHREADING:
+    try:
+        import pyarrow  # type: ignore # noqa: F401
+    except ImportError:
+        pass
+
+pydantic = None  # type: ignore
+try:
+    import pydantic  # type: ignore # noqa: F401
+except ImportError:
+    pass
+
 import pytest
 
 import hyperjson